@@ -43,6 +43,8 @@ pub struct CleanStats {
     pub contains_count: u32,
     /// Regex匹配类型数量
     pub regex_count: u32,
+    /// 自定义匹配类型数量
+    pub custom_count: u32,
     /// 无效正则总数（已剔除）
     pub invalid_regex_total: u32,
 
@@ -151,9 +153,10 @@ impl CleanStats {
         
         // 匹配类型统计
         log::debug!(
-            "Match type stats: Contains {} | Regex {} | Invalid regex removed {}",
+            "Match type stats: Contains {} | Regex {} | Custom {} | Invalid regex removed {}",
             self.contains_count,
             self.regex_count,
+            self.custom_count,
             self.invalid_regex_total
         );
         
@@ -6,7 +6,9 @@
 pub mod rule_cleaner;
 pub mod pattern_processor;
 pub mod regex_fixer;
+pub mod pattern_tags;
 pub mod clean_stats;
 
 pub use rule_cleaner::RuleCleaner;
-pub use clean_stats::CleanStats;
\ No newline at end of file
+pub use clean_stats::CleanStats;
+pub use pattern_tags::{parse_pattern_tags, PatternTags};
\ No newline at end of file
@@ -1,6 +1,7 @@
 use std::cell::RefCell;
 
 use super::clean_stats::CleanStats;
+use super::pattern_tags::parse_pattern_tags;
 use super::regex_fixer::RegexFixer;
 use crate::core::{MatchScope, MatchType, ParsedTechRule, Pattern};
 use crate::indexer::{PatternList, PatternMap};
@@ -35,6 +36,7 @@ impl PatternProcessor {
         Option<PatternMap>,
         Option<PatternMap>,
         Option<PatternMap>,
+        Option<PatternMap>,
     )> {
         // 1. 处理列表型规则（Url/Html）：标准JSON解析+清理
         let url = self.build_list_pattern(original_tech, MatchScope::Url, stats, "url")?;
@@ -43,14 +45,15 @@ impl PatternProcessor {
         // 2. 处理列表型规则（Script/ScriptSrc）：专用清理方法，补全正则修复
         let (scripts, script_src) = self.clean_and_mark_script_patterns(original_tech, stats)?;
 
-        // 3. 处理KV型规则（Meta/Header/Cookie）：复用统一的键值对清理逻辑
+        // 3. 处理KV型规则（Meta/Header/Cookie/Js）：复用统一的键值对清理逻辑
         let meta = self.build_keyed_pattern(original_tech, MatchScope::Meta, stats, "meta")?;
         let headers =
             self.build_keyed_pattern(original_tech, MatchScope::Header, stats, "header")?;
         let cookies =
             self.build_keyed_pattern(original_tech, MatchScope::Cookie, stats, "cookie")?;
+        let js = self.build_keyed_pattern(original_tech, MatchScope::Js, stats, "js")?;
 
-        Ok((url, html, scripts, script_src, meta, headers, cookies))
+        Ok((url, html, scripts, script_src, meta, headers, cookies, js))
     }
 
     /// 统一构建列表型规则
@@ -64,12 +67,8 @@ impl PatternProcessor {
         let Some(rule_set) = tech.match_rules.get(&scope) else {
             return Ok(None);
         };
-        let pattern_strs: Vec<&str> = rule_set
-            .list_patterns
-            .iter()
-            .map(|p| p.pattern.as_str())
-            .collect();
-        let patterns = self.clean_pattern_str_list(&pattern_strs, stats, pat_type)?;
+        let originals: Vec<&Pattern> = rule_set.list_patterns.iter().collect();
+        let patterns = self.clean_parsed_patterns(&originals, stats, pat_type)?;
         Ok(patterns.to_opt_pattern())
     }
 
@@ -84,18 +83,17 @@ impl PatternProcessor {
         let Some(rule_set) = tech.match_rules.get(&scope) else {
             return Ok(None);
         };
-        // 直接构建字符串Map，不转Value::String
+        // 直接构建模式Map，不转Value::String
         let mut keyed_map = FxHashMap::default();
         for kp in &rule_set.keyed_patterns {
-            keyed_map.insert(kp.key.clone(), kp.pattern.pattern.clone());
+            keyed_map.insert(kp.key.clone(), &kp.pattern);
         }
-        // 直接处理字符串Map，不走JSON解析
+        // 直接处理模式Map，不走JSON解析
         let mut valid_keyed_patterns = FxHashMap::default();
         stats.update_original_pattern_stats(pat_type, keyed_map.len());
         for (key, val) in keyed_map {
             let key_lower = key.to_lowercase();
-            let pat_strs = vec![val.as_str()];
-            let pats = self.clean_pattern_str_list(&pat_strs, stats, pat_type)?;
+            let pats = self.clean_parsed_patterns(&[val], stats, pat_type)?;
             if !pats.is_empty() {
                 valid_keyed_patterns.insert(key_lower, pats);
             }
@@ -103,6 +101,129 @@ impl PatternProcessor {
         Ok(valid_keyed_patterns.to_opt_pattern_map())
     }
 
+    /// 构建TLS证书颁发者规则（列表型，复用列表型清理逻辑）
+    /// 参数：
+    /// - tech: 原始技术规则
+    /// - stats: 清理统计
+    ///
+    /// 返回：清理后的模式列表（无有效规则时为None）
+    pub fn build_cert_issuer_pattern(
+        &self,
+        tech: &ParsedTechRule,
+        stats: &mut CleanStats,
+    ) -> CoreResult<Option<PatternList>> {
+        self.build_list_pattern(tech, MatchScope::CertIssuer, stats, "cert_issuer")
+    }
+
+    /// 构建Favicon mmh3哈希规则（列表型，复用列表型清理逻辑）
+    /// 参数：
+    /// - tech: 原始技术规则
+    /// - stats: 清理统计
+    ///
+    /// 返回：清理后的模式列表（无有效规则时为None）
+    pub fn build_favicon_hash_pattern(
+        &self,
+        tech: &ParsedTechRule,
+        stats: &mut CleanStats,
+    ) -> CoreResult<Option<PatternList>> {
+        self.build_list_pattern(tech, MatchScope::FaviconHash, stats, "favicon_hash")
+    }
+
+    /// 构建页面标题规则（列表型，复用列表型清理逻辑）
+    /// 参数：
+    /// - tech: 原始技术规则
+    /// - stats: 清理统计
+    ///
+    /// 返回：清理后的模式列表（无有效规则时为None）
+    pub fn build_title_pattern(
+        &self,
+        tech: &ParsedTechRule,
+        stats: &mut CleanStats,
+    ) -> CoreResult<Option<PatternList>> {
+        self.build_list_pattern(tech, MatchScope::Title, stats, "title")
+    }
+
+    /// 构建HTML注释规则（列表型，复用列表型清理逻辑）
+    /// 参数：
+    /// - tech: 原始技术规则
+    /// - stats: 清理统计
+    ///
+    /// 返回：清理后的模式列表（无有效规则时为None）
+    pub fn build_comment_pattern(
+        &self,
+        tech: &ParsedTechRule,
+        stats: &mut CleanStats,
+    ) -> CoreResult<Option<PatternList>> {
+        self.build_list_pattern(tech, MatchScope::Comment, stats, "comment")
+    }
+
+    /// 构建DOM型规则（键为选择器 + 检查目标，原样保留大小写；同一键的多条模式全部保留）
+    /// 参数：
+    /// - tech: 原始技术规则
+    /// - stats: 清理统计
+    ///
+    /// 返回：键 → 清理后的模式列表（无有效规则时为None）
+    pub fn build_dom_pattern(
+        &self,
+        tech: &ParsedTechRule,
+        stats: &mut CleanStats,
+    ) -> CoreResult<Option<PatternMap>> {
+        self.build_multi_keyed_pattern(tech, MatchScope::Dom, stats, "dom", false)
+    }
+
+    /// 构建DNS型规则（键为记录类型，统一小写；同一记录类型的多条模式全部保留）
+    /// 参数：
+    /// - tech: 原始技术规则
+    /// - stats: 清理统计
+    ///
+    /// 返回：记录类型 → 清理后的模式列表（无有效规则时为None）
+    pub fn build_dns_pattern(
+        &self,
+        tech: &ParsedTechRule,
+        stats: &mut CleanStats,
+    ) -> CoreResult<Option<PatternMap>> {
+        self.build_multi_keyed_pattern(tech, MatchScope::Dns, stats, "dns", true)
+    }
+
+    /// 构建一键多模式的键值对型规则（空模式可仅带标签，视为存在性检测）
+    fn build_multi_keyed_pattern(
+        &self,
+        tech: &ParsedTechRule,
+        scope: MatchScope,
+        stats: &mut CleanStats,
+        pat_type: &str,
+        lowercase_key: bool,
+    ) -> CoreResult<Option<PatternMap>> {
+        let Some(rule_set) = tech.match_rules.get(&scope) else {
+            return Ok(None);
+        };
+        stats.update_original_pattern_stats(pat_type, rule_set.keyed_patterns.len());
+        let mut valid_keyed_patterns: FxHashMap<String, Vec<Pattern>> = FxHashMap::default();
+        for kp in &rule_set.keyed_patterns {
+            // 空模式（可带 confidence 等标签）→ 存在性检测
+            let tags = parse_pattern_tags(&kp.pattern.pattern);
+            let cleaned = if tags.regex.trim().is_empty() {
+                Some(Pattern::new(String::new(), MatchType::Exists, None).with_confidence(tags.confidence))
+            } else {
+                self.process_single_pattern(&kp.pattern.pattern, stats)?
+            };
+            match cleaned {
+                Some(pattern) => {
+                    #[cfg(feature = "provenance")]
+                    let pattern = Pattern {
+                        source: kp.pattern.source.clone(),
+                        ..pattern
+                    };
+                    let key = if lowercase_key { kp.key.to_lowercase() } else { kp.key.clone() };
+                    valid_keyed_patterns.entry(key).or_default().push(pattern);
+                    stats.update_valid_pattern_stats(pat_type, 1);
+                }
+                None => stats.update_invalid_regex_stats(pat_type, 1),
+            }
+        }
+        Ok(valid_keyed_patterns.to_opt_pattern_map())
+    }
+
     // 判断是否有有效模式
     // pub fn has_valid_pattern(
     //     &self,
@@ -134,11 +255,27 @@ impl PatternProcessor {
             return Ok(None);
         };
 
-        let pattern_strs: Vec<&str> = parsed_patterns.iter().map(|p| p.pattern.as_str()).collect();
-        let patterns = self.clean_pattern_str_list(&pattern_strs, stats, pattern_type)?;
+        let originals: Vec<&Pattern> = parsed_patterns.iter().collect();
+        let patterns = self.clean_parsed_patterns(&originals, stats, pattern_type)?;
         Ok(patterns.to_opt_pattern())
     }
 
+    /// 清理已解析的Pattern列表（保留原始模式的来源信息）
+    fn clean_parsed_patterns(
+        &self,
+        originals: &[&Pattern],
+        stats: &mut CleanStats,
+        pattern_type: &str,
+    ) -> CoreResult<Vec<Pattern>> {
+        let pattern_strs: Vec<&str> = originals.iter().map(|p| p.pattern.as_str()).collect();
+        let mut cleaned = self.clean_pattern_str_list_indexed(&pattern_strs, stats, pattern_type)?;
+        #[cfg(feature = "provenance")]
+        for (idx, pattern) in cleaned.iter_mut() {
+            pattern.source = originals[*idx].source.clone();
+        }
+        Ok(cleaned.drain(..).map(|(_, pattern)| pattern).collect())
+    }
+
     /// 清理并标记列表型模式（url/html/scripts/script_src）从JSON值清理列表型模式（仅解析JSON）
     pub fn clean_and_mark_list_pattern(
         &self,
@@ -181,25 +318,35 @@ impl PatternProcessor {
         stats: &mut CleanStats,
         pattern_type: &str,
     ) -> CoreResult<Vec<Pattern>> {
+        let patterns = self.clean_pattern_str_list_indexed(pattern_strs, stats, pattern_type)?;
+        Ok(patterns.into_iter().map(|(_, pattern)| pattern).collect())
+    }
+
+    /// 清理字符串模式列表（附带每条有效模式在输入中的序号）
+    fn clean_pattern_str_list_indexed(
+        &self,
+        pattern_strs: &[&str],
+        stats: &mut CleanStats,
+        pattern_type: &str,
+    ) -> CoreResult<Vec<(usize, Pattern)>> {
         let mut patterns = Vec::new();
         let original_count = pattern_strs.len();
         stats.update_original_pattern_stats(pattern_type, original_count);
 
-        for s in pattern_strs {
+        for (idx, s) in pattern_strs.iter().enumerate() {
             let s_trimmed = s.trim();
-            // 规则：header/meta/cookie 类型 + 空字符串 → 标记为 Exists 存在性检测，不判定为无效！
-            let is_exists_detection =
-                (pattern_type == "header" || pattern_type == "meta" || pattern_type == "cookie")
-                    && s_trimmed.is_empty();
-
-            if is_exists_detection {
-                patterns.push(Pattern {
-                    pattern: "".to_string(),
-                    match_type: MatchType::Exists,
-                    version_template: None,
-                });
-                stats.update_valid_pattern_stats(pattern_type, 1);
-                continue;
+            // 规则：header/meta/cookie/js 类型 + 空字符串（可仅带标签，如 `\\;confidence:50`）
+            // → 标记为 Exists 存在性检测，保留置信度/版本来源 Header 标签，不判定为无效！
+            if matches!(pattern_type, "header" | "meta" | "cookie" | "js") {
+                let tags = parse_pattern_tags(s_trimmed);
+                if tags.regex.trim().is_empty() && tags.matcher.is_none() {
+                    let pattern = Pattern::new(String::new(), MatchType::Exists, None)
+                        .with_confidence(tags.confidence)
+                        .with_version_header(tags.version_header);
+                    patterns.push((idx, pattern));
+                    stats.update_valid_pattern_stats(pattern_type, 1);
+                    continue;
+                }
             }
 
             // 其他场景：空字符串判定为无效
@@ -210,7 +357,7 @@ impl PatternProcessor {
 
             // 正常处理非空的匹配规则
             if let Some(marked_pat) = self.process_single_pattern(s_trimmed, stats)? {
-                patterns.push(marked_pat);
+                patterns.push((idx, marked_pat));
                 stats.update_valid_pattern_stats(pattern_type, 1);
             } else {
                 stats.update_invalid_regex_stats(pattern_type, 1);
@@ -276,33 +423,56 @@ impl PatternProcessor {
         raw_pattern: &str,
         stats: &mut CleanStats,
     ) -> CoreResult<Option<Pattern>> {
+        // 解析模式标签（version/confidence/matcher/versionHeader 可组合出现、顺序任意）
+        let tags = parse_pattern_tags(raw_pattern);
+        let body = tags.regex.trim();
+        let confidence = tags.confidence;
+        let with_tags = |pattern: Pattern| {
+            pattern
+                .with_confidence(confidence)
+                .with_version_header(tags.version_header)
+        };
+
+        // 仅声明版本来源 Header 的空模式（`\\;versionHeader:<name>`）→ 存在性检测
+        if body.is_empty() {
+            return Ok(tags
+                .version_header
+                .map(|_| with_tags(Pattern::new(String::new(), MatchType::Exists, None))));
+        }
+
+        // 自定义匹配类型（`<pattern>\\;matcher:<kind>`）：模式原样保留，不走正则修复逻辑
+        if let Some(kind) = tags.matcher {
+            if kind.is_empty() {
+                return Ok(None);
+            }
+            stats.custom_count += 1;
+            return Ok(Some(with_tags(Pattern::new(
+                body.to_string(),
+                MatchType::Custom(kind.to_string()),
+                None,
+            ))));
+        }
+
         // 第一步：先判断简单模式，直接返回，不走后续修复逻辑
-        if self.regex_fixer.is_simple_contains(raw_pattern) {
+        if self.regex_fixer.is_simple_contains(body) {
             stats.contains_count += 1;
-            return Ok(Some(Pattern {
-                pattern: raw_pattern.to_string(),
-                match_type: MatchType::Contains,
-                version_template: None, // 简单模式无版本模板
-            }));
+            // 简单模式无版本模板
+            return Ok(Some(with_tags(Pattern::new(body.to_string(), MatchType::Contains, None))));
         }
 
         // 提取版本模板
-        let version_template = if raw_pattern.contains(";version:") {
-            // 带 ;version: 标记的规则 → 解析自定义版本模板
-            let parts: Vec<&str> = raw_pattern.splitn(2, ";version:").collect();
-            parts.get(1).map(|v| v.to_string())
-        } else {
+        let version_template = match tags.version {
+            // 带 version 标签的规则 → 使用自定义版本模板（空模板表示不提取版本）
+            Some(template) => (!template.is_empty()).then(|| template.to_string()),
             // 纯正则无标记规则 → 有捕获组则自动赋值默认模板 ${1}
             // 优先查缓存，避免重复编译
-            let (has_capture, _) = self.get_regex_cache(raw_pattern)?;
-            if has_capture {
-                Some("${1}".to_string())
-            } else {
-                None
+            None => {
+                let (has_capture, _) = self.get_regex_cache(body)?;
+                has_capture.then(|| "${1}".to_string())
             }
         };
 
-        let pattern_without_delimiter = self.regex_fixer.remove_pcre_delimiter(raw_pattern);
+        let pattern_without_delimiter = self.regex_fixer.remove_pcre_delimiter(body);
         let pattern_trimmed = pattern_without_delimiter.trim();
         if pattern_trimmed.is_empty() {
             return Ok(None);
@@ -311,7 +481,6 @@ impl PatternProcessor {
         let mut cleaned_pattern = pattern_trimmed.to_string();
         let mut is_fixed = false;
 
-        cleaned_pattern = self.regex_fixer.remove_version_marker(&cleaned_pattern);
         cleaned_pattern = self.regex_fixer.remove_look_around(&cleaned_pattern);
 
         let (fixed_escapes_pattern, fixed_escapes) =
@@ -376,11 +545,7 @@ impl PatternProcessor {
         // }
         stats.regex_count += 1; // 原 StartsWith 规则归为正则统计
 
-        Ok(Some(Pattern {
-            pattern: normalized_pattern,
-            match_type: MatchType::Regex,
-            version_template,
-        }))
+        Ok(Some(with_tags(Pattern::new(normalized_pattern, MatchType::Regex, version_template))))
     }
 
     // 缓存辅助方法
@@ -442,3 +607,56 @@ impl ToOptionPatternMap for FxHashMap<String, Vec<Pattern>> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rustc_hash::FxHashMap;
+
+    use crate::test_support::compile;
+    use crate::CompiledPattern;
+
+    const RULES: &str = r#"{"technologies": {
+        "Shop": { "cookies": { "shop_session": "\\;confidence:50" } },
+        "Edge": { "headers": { "X-Edge": " \\;confidence:30", "X-Via": "" } },
+        "Widget": { "js": { "Widget.init": "\\;confidence:20" }, "html": "\\;confidence:40" },
+        "Custom": { "headers": { "X-Custom": "\\;matcher:base64" } }
+    }}"#;
+
+    fn first<'a>(patterns: Option<&'a FxHashMap<String, Vec<CompiledPattern>>>, key: &str) -> &'a CompiledPattern {
+        &patterns.unwrap()[key][0]
+    }
+
+    #[test]
+    fn test_tag_only_patterns_become_exists() {
+        let library = compile(RULES);
+        let shop = first(library.tech_patterns["Shop"].cookie_patterns.as_ref(), "shop_session");
+        assert!(shop.exec.get_matcher().is_exists());
+        assert_eq!(shop.exec.confidence, 50);
+
+        let edge = &library.tech_patterns["Edge"];
+        let tagged = first(edge.header_patterns.as_ref(), "x-edge");
+        assert!(tagged.exec.get_matcher().is_exists());
+        assert_eq!(tagged.exec.confidence, 30);
+        // 无标签的空模式保持默认置信度
+        assert_eq!(first(edge.header_patterns.as_ref(), "x-via").exec.confidence, 100);
+
+        let widget = first(library.tech_patterns["Widget"].js_patterns.as_ref(), "widget.init");
+        assert!(widget.exec.get_matcher().is_exists());
+        assert_eq!(widget.exec.confidence, 20);
+    }
+
+    #[test]
+    fn test_tag_only_content_pattern_dropped() {
+        // html 等内容型规则不支持存在性检测，仅含标签的空模式视为无效
+        let library = compile(RULES);
+        assert!(library.tech_patterns["Widget"].html_patterns.is_none());
+    }
+
+    #[test]
+    fn test_custom_matcher_not_exists() {
+        // 自定义匹配类型标记不被当作存在性检测（未注册时编译阶段丢弃）
+        let library = compile(RULES);
+        let custom = library.tech_patterns.get("Custom");
+        assert!(custom.and_then(|t| t.header_patterns.as_ref()).is_none_or(|h| h.is_empty()));
+    }
+}
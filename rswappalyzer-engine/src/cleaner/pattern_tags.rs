@@ -0,0 +1,169 @@
+//! 模式标签解析
+//! Wappalyzer 规则模式格式：`<regex>\;<key>:<value>\;<key>:<value>...`
+//! 标签（version/confidence/matcher 等）可组合出现且顺序任意，值中允许包含 `:`（如版本三元表达式 `\1?a:b`）；
+//! 兼容省略反斜杠的已知标签写法（`<regex>;version:\1`）；
+//! `versionHeader` 为扩展标签：版本号取自另一个 Header（如 X-Powered-By 识别、X-AspNet-Version 取版本）
+
+/// 可识别的标签键（用于兼容省略反斜杠的写法）
+const KNOWN_TAGS: &[&str] = &["version", "confidence", "matcher", "versionHeader"];
+
+/// 模式解析结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PatternTags<'a> {
+    /// 去除标签后的正则/模式主体
+    pub regex: &'a str,
+    /// 版本模板（`version` 标签）
+    pub version: Option<&'a str>,
+    /// 置信度（`confidence` 标签，非法值忽略，超过 100 截断）
+    pub confidence: Option<u8>,
+    /// 自定义匹配类型（`matcher` 标签）
+    pub matcher: Option<&'a str>,
+    /// 版本来源 Header（`versionHeader` 标签，仅 Header 规则生效）
+    pub version_header: Option<&'a str>,
+}
+
+/// 解析模式中的全部标签
+/// 重复的标签以最后一次出现为准（与上游一致），未知标签忽略
+/// 参数：raw - 原始模式字符串
+/// 返回：模式主体与标签
+pub fn parse_pattern_tags(raw: &str) -> PatternTags<'_> {
+    let mut segments = split_segments(raw).into_iter();
+    let mut tags = PatternTags {
+        regex: segments.next().unwrap_or_default(),
+        ..Default::default()
+    };
+
+    for segment in segments {
+        let Some((key, value)) = segment.split_once(':') else {
+            continue;
+        };
+        match key.trim() {
+            "version" => tags.version = Some(value),
+            "confidence" => {
+                tags.confidence = value.trim().parse::<u32>().ok().map(|c| c.min(100) as u8)
+            }
+            "matcher" => tags.matcher = Some(value.trim()),
+            "versionHeader" => {
+                tags.version_header = Some(value.trim()).filter(|h| !h.is_empty())
+            }
+            _ => {}
+        }
+    }
+    tags
+}
+
+/// 按标签分隔符切分：`\;` 一律视为分隔符，裸 `;` 仅在其后紧跟已知标签键时视为分隔符
+fn split_segments(raw: &str) -> Vec<&str> {
+    let bytes = raw.as_bytes();
+    let mut segments = Vec::new();
+    let mut start = 0;
+    for (i, _) in raw.match_indices(';') {
+        let escaped = i > 0 && bytes[i - 1] == b'\\';
+        if !escaped && !starts_with_known_tag(&raw[i + 1..]) {
+            continue;
+        }
+        let end = if escaped { i - 1 } else { i };
+        segments.push(&raw[start..end]);
+        start = i + 1;
+    }
+    segments.push(&raw[start..]);
+    segments
+}
+
+fn starts_with_known_tag(rest: &str) -> bool {
+    KNOWN_TAGS.iter().any(|tag| {
+        rest.strip_prefix(tag)
+            .is_some_and(|after| after.starts_with(':'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 用例参照上游 Wappalyzer 的模式解析规范（parsePattern）
+
+    #[test]
+    fn test_plain_pattern() {
+        let tags = parse_pattern_tags("jquery");
+        assert_eq!(tags.regex, "jquery");
+        assert_eq!(tags.version, None);
+        assert_eq!(tags.confidence, None);
+    }
+
+    #[test]
+    fn test_version_tag() {
+        let tags = parse_pattern_tags(r"jquery-([0-9.]+)\.js\;version:\1");
+        assert_eq!(tags.regex, r"jquery-([0-9.]+)\.js");
+        assert_eq!(tags.version, Some(r"\1"));
+        assert_eq!(tags.confidence, None);
+    }
+
+    #[test]
+    fn test_confidence_tag() {
+        let tags = parse_pattern_tags(r"X-Powered-By\;confidence:50");
+        assert_eq!(tags.regex, "X-Powered-By");
+        assert_eq!(tags.confidence, Some(50));
+        assert_eq!(tags.version, None);
+    }
+
+    #[test]
+    fn test_combined_tags_any_order() {
+        let forward = parse_pattern_tags(r"foo/([\d.]+)\;version:\1\;confidence:50");
+        let reversed = parse_pattern_tags(r"foo/([\d.]+)\;confidence:50\;version:\1");
+        for tags in [forward, reversed] {
+            assert_eq!(tags.regex, r"foo/([\d.]+)");
+            assert_eq!(tags.version, Some(r"\1"));
+            assert_eq!(tags.confidence, Some(50));
+        }
+    }
+
+    #[test]
+    fn test_version_ternary_keeps_colons() {
+        let tags = parse_pattern_tags(r"(modern)?legacy\;version:\1?2.0:1.0\;confidence:75");
+        assert_eq!(tags.regex, "(modern)?legacy");
+        assert_eq!(tags.version, Some(r"\1?2.0:1.0"));
+        assert_eq!(tags.confidence, Some(75));
+    }
+
+    #[test]
+    fn test_unescaped_known_tag() {
+        let tags = parse_pattern_tags(r"nginx/([\d.]+);version:\1");
+        assert_eq!(tags.regex, r"nginx/([\d.]+)");
+        assert_eq!(tags.version, Some(r"\1"));
+    }
+
+    #[test]
+    fn test_semicolon_in_regex_preserved() {
+        let tags = parse_pattern_tags(r"a;b=c\;confidence:25");
+        assert_eq!(tags.regex, "a;b=c");
+        assert_eq!(tags.confidence, Some(25));
+    }
+
+    #[test]
+    fn test_invalid_and_unknown_tags() {
+        let tags = parse_pattern_tags(r"foo\;confidence:high\;unknown:1\;flag");
+        assert_eq!(tags.regex, "foo");
+        assert_eq!(tags.confidence, None);
+        assert_eq!(parse_pattern_tags(r"foo\;confidence:150").confidence, Some(100));
+    }
+
+    #[test]
+    fn test_version_header_tag() {
+        let tags = parse_pattern_tags(r"ASP\.NET\;versionHeader:X-AspNet-Version\;confidence:90");
+        assert_eq!(tags.regex, r"ASP\.NET");
+        assert_eq!(tags.version_header, Some("X-AspNet-Version"));
+        assert_eq!(tags.confidence, Some(90));
+
+        let wildcard = parse_pattern_tags(r"\;versionHeader:X-AspNet-Version");
+        assert_eq!(wildcard.regex, "");
+        assert_eq!(wildcard.version_header, Some("X-AspNet-Version"));
+    }
+
+    #[test]
+    fn test_empty_version_tag() {
+        let tags = parse_pattern_tags(r"foo\;version:");
+        assert_eq!(tags.regex, "foo");
+        assert_eq!(tags.version, Some(""));
+    }
+}
@@ -22,7 +22,7 @@ impl RuleCleaner {
     ) -> CoreResult<FxHashMap<MatchScope, MatchRuleSet>> {
         let mut match_rules: FxHashMap<MatchScope, MatchRuleSet> = FxHashMap::default();
 
-        // 1. 处理 列表型规则（Url/Html/Script/ScriptSrc）→ 赋值给 list_patterns
+        // 1. 处理 列表型规则（Url/Html/Script/ScriptSrc/CertIssuer/FaviconHash/Title/Comment）→ 赋值给 list_patterns
         if let Some(url_patterns) = &raw_rules.url_patterns {
             if !url_patterns.0.is_empty() {
                 match_rules.insert(
@@ -75,7 +75,59 @@ impl RuleCleaner {
             }
         }
 
-        // 2. 处理 KV 型规则（Meta/Header/Cookie）→ 转换为 KeyedPattern 后赋值给 keyed_patterns
+        if let Some(cert_issuer_patterns) = &raw_rules.cert_issuer_patterns {
+            if !cert_issuer_patterns.0.is_empty() {
+                match_rules.insert(
+                    MatchScope::CertIssuer,
+                    MatchRuleSet {
+                        condition: Default::default(),
+                        list_patterns: cert_issuer_patterns.0.clone(),
+                        keyed_patterns: Vec::new(),
+                    },
+                );
+            }
+        }
+
+        if let Some(favicon_hash_patterns) = &raw_rules.favicon_hash_patterns {
+            if !favicon_hash_patterns.0.is_empty() {
+                match_rules.insert(
+                    MatchScope::FaviconHash,
+                    MatchRuleSet {
+                        condition: Default::default(),
+                        list_patterns: favicon_hash_patterns.0.clone(),
+                        keyed_patterns: Vec::new(),
+                    },
+                );
+            }
+        }
+
+        if let Some(title_patterns) = &raw_rules.title_patterns {
+            if !title_patterns.0.is_empty() {
+                match_rules.insert(
+                    MatchScope::Title,
+                    MatchRuleSet {
+                        condition: Default::default(),
+                        list_patterns: title_patterns.0.clone(),
+                        keyed_patterns: Vec::new(),
+                    },
+                );
+            }
+        }
+
+        if let Some(comment_patterns) = &raw_rules.comment_patterns {
+            if !comment_patterns.0.is_empty() {
+                match_rules.insert(
+                    MatchScope::Comment,
+                    MatchRuleSet {
+                        condition: Default::default(),
+                        list_patterns: comment_patterns.0.clone(),
+                        keyed_patterns: Vec::new(),
+                    },
+                );
+            }
+        }
+
+        // 2. 处理 KV 型规则（Meta/Header/Cookie/Js）→ 转换为 KeyedPattern 后赋值给 keyed_patterns
         if let Some(meta_pattern_map) = &raw_rules.meta_pattern_map {
             if !meta_pattern_map.0.is_empty() {
                 // 将 PatternMap 转换为 KeyedPattern 列表
@@ -158,10 +210,96 @@ impl RuleCleaner {
             }
         }
 
+        if let Some(js_pattern_map) = &raw_rules.js_pattern_map {
+            let keyed_patterns: Vec<KeyedPattern> = js_pattern_map
+                .0
+                .iter()
+                .flat_map(|(key, patterns)| {
+                    patterns.iter().map(move |pattern| KeyedPattern {
+                        key: key.to_lowercase(),
+                        pattern: pattern.clone(),
+                    })
+                })
+                .collect();
+            if !keyed_patterns.is_empty() {
+                match_rules.insert(
+                    MatchScope::Js,
+                    MatchRuleSet {
+                        condition: Default::default(),
+                        list_patterns: Vec::new(),
+                        keyed_patterns,
+                    },
+                );
+            }
+        }
+
+        // 3. 处理 DOM 型规则（键为选择器 + 检查目标，选择器大小写敏感，不做小写化）
+        if let Some(dom_pattern_map) = &raw_rules.dom_pattern_map {
+            let keyed_patterns: Vec<KeyedPattern> = dom_pattern_map
+                .0
+                .iter()
+                .flat_map(|(key, patterns)| {
+                    patterns.iter().map(move |pattern| KeyedPattern {
+                        key: key.clone(),
+                        pattern: pattern.clone(),
+                    })
+                })
+                .collect();
+            if !keyed_patterns.is_empty() {
+                match_rules.insert(
+                    MatchScope::Dom,
+                    MatchRuleSet {
+                        condition: Default::default(),
+                        list_patterns: Vec::new(),
+                        keyed_patterns,
+                    },
+                );
+            }
+        }
+
+        // 4. 处理 DNS 型规则（键为记录类型，清理时已统一小写）
+        if let Some(dns_pattern_map) = &raw_rules.dns_pattern_map {
+            let keyed_patterns: Vec<KeyedPattern> = dns_pattern_map
+                .0
+                .iter()
+                .flat_map(|(key, patterns)| {
+                    patterns.iter().map(move |pattern| KeyedPattern {
+                        key: key.clone(),
+                        pattern: pattern.clone(),
+                    })
+                })
+                .collect();
+            if !keyed_patterns.is_empty() {
+                match_rules.insert(
+                    MatchScope::Dns,
+                    MatchRuleSet {
+                        condition: Default::default(),
+                        list_patterns: Vec::new(),
+                        keyed_patterns,
+                    },
+                );
+            }
+        }
+
         //debug!("技术 {} 清理完成，生成 {} 个匹配作用域规则", tech_name, match_rules.len());
         Ok(match_rules)
     }
 
+    /// 沿用原始规则集的匹配条件（clean_from_raw 仅基于模式产出规则集，条件默认为 Or）
+    /// 参数：
+    /// - original_tech: 原始技术规则
+    /// - match_rules: 清理后的规则集
+    fn inherit_conditions(
+        original_tech: &ParsedTechRule,
+        match_rules: &mut FxHashMap<MatchScope, MatchRuleSet>,
+    ) {
+        for (scope, rule_set) in match_rules.iter_mut() {
+            if let Some(original) = original_tech.match_rules.get(scope) {
+                rule_set.condition = original.condition.clone();
+            }
+        }
+    }
+
     pub fn clean_log(&self, original_rule_lib: &RuleLibrary) -> CoreResult<RuleLibrary> {
         // 总耗时计时开始
         let total_start = std::time::Instant::now();
@@ -197,9 +335,28 @@ impl RuleCleaner {
                 meta_pattern_map,
                 header_pattern_map,
                 cookie_pattern_map,
+                js_pattern_map,
             ) = self
                 .pattern_processor
                 .process_tech_rule_patterns(original_tech, &mut clean_stats)?;
+            let dom_pattern_map = self
+                .pattern_processor
+                .build_dom_pattern(original_tech, &mut clean_stats)?;
+            let cert_issuer_patterns = self
+                .pattern_processor
+                .build_cert_issuer_pattern(original_tech, &mut clean_stats)?;
+            let favicon_hash_patterns = self
+                .pattern_processor
+                .build_favicon_hash_pattern(original_tech, &mut clean_stats)?;
+            let title_patterns = self
+                .pattern_processor
+                .build_title_pattern(original_tech, &mut clean_stats)?;
+            let comment_patterns = self
+                .pattern_processor
+                .build_comment_pattern(original_tech, &mut clean_stats)?;
+            let dns_pattern_map = self
+                .pattern_processor
+                .build_dns_pattern(original_tech, &mut clean_stats)?;
 
             let raw_match_set = RawMatchSet {
                 url_patterns,
@@ -209,6 +366,13 @@ impl RuleCleaner {
                 meta_pattern_map,
                 header_pattern_map,
                 cookie_pattern_map,
+                js_pattern_map,
+                dom_pattern_map,
+                cert_issuer_patterns,
+                favicon_hash_patterns,
+                title_patterns,
+                comment_patterns,
+                dns_pattern_map,
             };
 
             // 记录原始维度是否存在规则
@@ -239,13 +403,46 @@ impl RuleCleaner {
                 || raw_match_set
                     .cookie_pattern_map
                     .as_ref()
-                    .map_or(false, |m| !m.0.is_empty());
+                    .map_or(false, |m| !m.0.is_empty())
+                || raw_match_set
+                    .js_pattern_map
+                    .as_ref()
+                    .is_some_and(|m| !m.0.is_empty())
+                || raw_match_set
+                    .dom_pattern_map
+                    .as_ref()
+                    .is_some_and(|m| !m.0.is_empty())
+                || raw_match_set
+                    .cert_issuer_patterns
+                    .as_ref()
+                    .is_some_and(|p| !p.0.is_empty())
+                || raw_match_set
+                    .favicon_hash_patterns
+                    .as_ref()
+                    .is_some_and(|p| !p.0.is_empty())
+                || raw_match_set
+                    .title_patterns
+                    .as_ref()
+                    .is_some_and(|p| !p.0.is_empty())
+                || raw_match_set
+                    .comment_patterns
+                    .as_ref()
+                    .is_some_and(|p| !p.0.is_empty())
+                || raw_match_set
+                    .dns_pattern_map
+                    .as_ref()
+                    .is_some_and(|m| !m.0.is_empty());
 
             // 2. 核心清理（移除子步骤计时和日志）
-            let match_rules = self.clean_from_raw(&tech_name.to_string(), &raw_match_set)?;
+            let mut match_rules = self.clean_from_raw(&tech_name.to_string(), &raw_match_set)?;
+            Self::inherit_conditions(original_tech, &mut match_rules);
 
             // 判断是否有有效模式
-            if match_rules.is_empty() && has_any_supported_dimension {
+            // 仅声明主机名后缀的技术不含模式，同样保留
+            if match_rules.is_empty()
+                && has_any_supported_dimension
+                && original_tech.basic.hostnames.is_empty()
+            {
                 clean_stats.discarded_tech_rules += 1;
                 // 仅保留丢弃规则的关键打印（移除冗余字段，简化输出）
                 if clean_stats.discarded_tech_rules as usize % PROGRESS_INTERVAL == 0 {
@@ -266,19 +463,12 @@ impl RuleCleaner {
                 tech_name: Some(tech_name.to_string()),
                 category_ids: original_tech.basic.category_ids.clone(),
                 implies: original_tech.basic.implies.clone(),
-
-                #[cfg(feature = "full-meta")]
-                description: original_tech.basic.description.clone(),
-                #[cfg(feature = "full-meta")]
-                website: original_tech.basic.website.clone(),
-                #[cfg(feature = "full-meta")]
-                icon: original_tech.basic.icon.clone(),
-                #[cfg(feature = "full-meta")]
-                cpe: original_tech.basic.cpe.clone(),
-                #[cfg(feature = "full-meta")]
-                saas: original_tech.basic.saas,
-                #[cfg(feature = "full-meta")]
-                pricing: original_tech.basic.pricing.clone(),
+                excludes: original_tech.basic.excludes.clone(),
+                requires: original_tech.basic.requires.clone(),
+                requires_category: original_tech.basic.requires_category.clone(),
+                lifecycle: original_tech.basic.lifecycle.clone(),
+                hostnames: original_tech.basic.hostnames.clone(),
+                source: original_tech.basic.source.clone(),
             };
 
             // 4. 构建最终规则（移除子步骤计时和日志）
@@ -360,9 +550,18 @@ impl RuleCleaner {
         category_process_duration.as_secs_f64() * 1000.0
     );
 
+        // 元信息旁路表仅保留清洗后仍存在的技术
+        let meta_table = original_rule_lib
+            .meta_table
+            .iter()
+            .filter(|(name, _)| cleaned_tech_rules.contains_key(*name))
+            .map(|(name, meta)| (name.clone(), meta.clone()))
+            .collect();
+
         Ok(RuleLibrary {
             core_tech_map: cleaned_tech_rules,
             category_rules: cleaned_category_rules,
+            meta_table,
         })
     }
 
@@ -387,9 +586,28 @@ impl RuleCleaner {
                 meta_pattern_map,
                 header_pattern_map,
                 cookie_pattern_map,
+                js_pattern_map,
             ) = self
                 .pattern_processor
                 .process_tech_rule_patterns(original_tech, &mut clean_stats)?;
+            let dom_pattern_map = self
+                .pattern_processor
+                .build_dom_pattern(original_tech, &mut clean_stats)?;
+            let cert_issuer_patterns = self
+                .pattern_processor
+                .build_cert_issuer_pattern(original_tech, &mut clean_stats)?;
+            let favicon_hash_patterns = self
+                .pattern_processor
+                .build_favicon_hash_pattern(original_tech, &mut clean_stats)?;
+            let title_patterns = self
+                .pattern_processor
+                .build_title_pattern(original_tech, &mut clean_stats)?;
+            let comment_patterns = self
+                .pattern_processor
+                .build_comment_pattern(original_tech, &mut clean_stats)?;
+            let dns_pattern_map = self
+                .pattern_processor
+                .build_dns_pattern(original_tech, &mut clean_stats)?;
 
             //eprintln!("pattern_processor 后的 header_pattern_map {:?}", header_pattern_map.clone());
 
@@ -401,6 +619,13 @@ impl RuleCleaner {
                 meta_pattern_map,
                 header_pattern_map,
                 cookie_pattern_map,
+                js_pattern_map,
+                dom_pattern_map,
+                cert_issuer_patterns,
+                favicon_hash_patterns,
+                title_patterns,
+                comment_patterns,
+                dns_pattern_map,
             };
 
             // 记录原始维度是否存在规则
@@ -431,15 +656,48 @@ impl RuleCleaner {
                 || raw_match_set
                     .cookie_pattern_map
                     .as_ref()
-                    .map_or(false, |m| !m.0.is_empty());
+                    .map_or(false, |m| !m.0.is_empty())
+                || raw_match_set
+                    .js_pattern_map
+                    .as_ref()
+                    .is_some_and(|m| !m.0.is_empty())
+                || raw_match_set
+                    .dom_pattern_map
+                    .as_ref()
+                    .is_some_and(|m| !m.0.is_empty())
+                || raw_match_set
+                    .cert_issuer_patterns
+                    .as_ref()
+                    .is_some_and(|p| !p.0.is_empty())
+                || raw_match_set
+                    .favicon_hash_patterns
+                    .as_ref()
+                    .is_some_and(|p| !p.0.is_empty())
+                || raw_match_set
+                    .title_patterns
+                    .as_ref()
+                    .is_some_and(|p| !p.0.is_empty())
+                || raw_match_set
+                    .comment_patterns
+                    .as_ref()
+                    .is_some_and(|p| !p.0.is_empty())
+                || raw_match_set
+                    .dns_pattern_map
+                    .as_ref()
+                    .is_some_and(|m| !m.0.is_empty());
 
             // 2. 调用核心清理方法，生成统一的 match_rules
-            let match_rules = self.clean_from_raw(&tech_name.to_string(), &raw_match_set)?;
+            let mut match_rules = self.clean_from_raw(&tech_name.to_string(), &raw_match_set)?;
+            Self::inherit_conditions(original_tech, &mut match_rules);
 
             // 判断是否有有效模式（match_rules 非空即有有效规则）
-            if match_rules.is_empty() && has_any_supported_dimension {
+            // 仅声明主机名后缀的技术不含模式，同样保留
+            if match_rules.is_empty()
+                && has_any_supported_dimension
+                && original_tech.basic.hostnames.is_empty()
+            {
                 clean_stats.discarded_tech_rules += 1;
-                println!(
+                log::debug!(
                     "[CLEAN DROP] {} | 原始维度: url={} html={} script={} script_src={} meta={} header={} cookie={}",
                     tech_name,
                     raw_match_set
@@ -480,19 +738,12 @@ impl RuleCleaner {
                 tech_name: Some(tech_name.to_string()),
                 category_ids: original_tech.basic.category_ids.clone(),
                 implies: original_tech.basic.implies.clone(),
-
-                #[cfg(feature = "full-meta")]
-                description: original_tech.basic.description.clone(),
-                #[cfg(feature = "full-meta")]
-                website: original_tech.basic.website.clone(),
-                #[cfg(feature = "full-meta")]
-                icon: original_tech.basic.icon.clone(),
-                #[cfg(feature = "full-meta")]
-                cpe: original_tech.basic.cpe.clone(),
-                #[cfg(feature = "full-meta")]
-                saas: original_tech.basic.saas,
-                #[cfg(feature = "full-meta")]
-                pricing: original_tech.basic.pricing.clone(),
+                excludes: original_tech.basic.excludes.clone(),
+                requires: original_tech.basic.requires.clone(),
+                requires_category: original_tech.basic.requires_category.clone(),
+                lifecycle: original_tech.basic.lifecycle.clone(),
+                hostnames: original_tech.basic.hostnames.clone(),
+                source: original_tech.basic.source.clone(),
             };
 
             // 4. 构建新的 ParsedTechRule（仅包含 basic 和 match_rules）
@@ -524,9 +775,18 @@ impl RuleCleaner {
         clean_stats.update_fixed_stats();
         clean_stats.print_stats(start.elapsed());
 
+        // 元信息旁路表仅保留清洗后仍存在的技术
+        let meta_table = original_rule_lib
+            .meta_table
+            .iter()
+            .filter(|(name, _)| cleaned_tech_rules.contains_key(*name))
+            .map(|(name, meta)| (name.clone(), meta.clone()))
+            .collect();
+
         Ok(RuleLibrary {
             core_tech_map: cleaned_tech_rules,
             category_rules: cleaned_category_rules,
+            meta_table,
         })
     }
 }
@@ -18,22 +18,114 @@ pub struct TechBasicInfo {
     pub category_ids: Vec<u32>,
     #[serde(default)]
     pub implies: Option<Vec<String>>,
+    /// 互斥技术（检出该技术时从结果中移除的技术）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excludes: Vec<String>,
+    /// 依赖技术（任一依赖技术检出时该技术才保留）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+    /// 依赖分类（任一该分类技术检出时该技术才保留）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires_category: Vec<u32>,
+    /// 生命周期信息（废弃/过期/替代技术），无则为None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<TechLifecycle>,
+    /// 主机名后缀（规则扩展字段，`github.io` 匹配自身及子域，`*.github.io` 仅匹配子域）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hostnames: Vec<String>,
+    /// 所属规则源名称（多规则源合并时记录，如 official / internal），未记录则为None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
 
-    // 非规则必须字段 - 特性开关控制
-    #[cfg(feature = "full-meta")]
-    pub cpe: Option<String>,
-    #[cfg(feature = "full-meta")]
+/// 技术规则生命周期信息（自定义规则包的退役管理）
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct TechLifecycle {
+    /// 是否已废弃
+    #[serde(default)]
+    pub deprecated: bool,
+    /// 有效期截止日期（含当日，格式 YYYY-MM-DD）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<String>,
+    /// 替代技术名称
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replacement: Option<String>,
+}
+
+impl TechLifecycle {
+    /// 由原始字段构建，全部为空时返回None
+    pub fn from_parts(
+        deprecated: bool,
+        valid_until: Option<String>,
+        replacement: Option<String>,
+    ) -> Option<Self> {
+        let lifecycle = Self {
+            deprecated,
+            valid_until: valid_until.map(|d| d.trim().to_string()).filter(|d| !d.is_empty()),
+            replacement: replacement.map(|r| r.trim().to_string()).filter(|r| !r.is_empty()),
+        };
+        (lifecycle != Self::default()).then_some(lifecycle)
+    }
+
+    /// 有效期是否为合法的 YYYY-MM-DD 日期（未设置有效期视为合法）
+    pub fn has_valid_date(&self) -> bool {
+        self.valid_until.as_deref().is_none_or(is_iso_date)
+    }
+
+    /// 是否已过期
+    /// 参数：today - 当前日期（YYYY-MM-DD）
+    ///
+    /// 返回：有效期合法且早于today时为true
+    pub fn is_expired(&self, today: &str) -> bool {
+        match self.valid_until.as_deref() {
+            // ISO日期字符串的字典序即时间顺序
+            Some(date) if is_iso_date(date) => date < today,
+            _ => false,
+        }
+    }
+}
+
+/// 校验 YYYY-MM-DD 格式日期（仅校验格式与月/日范围）
+pub(crate) fn is_iso_date(date: &str) -> bool {
+    let bytes = date.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return false;
+    }
+    let digits = |range: std::ops::Range<usize>| -> Option<u32> {
+        let part = &date[range];
+        part.bytes().all(|b| b.is_ascii_digit()).then(|| part.parse().ok())?
+    };
+    match (digits(0..4), digits(5..7), digits(8..10)) {
+        (Some(_), Some(month), Some(day)) => (1..=12).contains(&month) && (1..=31).contains(&day),
+        _ => false,
+    }
+}
+
+/// 技术展示元信息（非规则必须字段）
+/// 始终随规则解析进入旁路表（meta_table），是否嵌入二进制规则包由 `full-meta` 特性决定
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct TechMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    #[cfg(feature = "full-meta")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub website: Option<String>,
-    #[cfg(feature = "full-meta")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
-    #[cfg(feature = "full-meta")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpe: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub saas: Option<bool>,
-    #[cfg(feature = "full-meta")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pricing: Option<Vec<String>>,
 }
 
+impl TechMeta {
+    /// 是否无任何元信息
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CategoryEntry {
     #[serde(default)] // 缺groups → 空数组 []
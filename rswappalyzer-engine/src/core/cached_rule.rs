@@ -1,7 +1,7 @@
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 
-use crate::{MatchCondition, MatchScope, Pattern, TechBasicInfo};
+use crate::{MatchCondition, MatchScope, Pattern, TechBasicInfo, TechMeta};
 
 
 
@@ -19,6 +19,9 @@ pub struct CachedScopeRule {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CachedTechRule {
     pub basic: TechBasicInfo, // 技术基础信息（含 tech_name）
+    // 技术展示元信息（旁路表条目，旧缓存无此字段）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<TechMeta>,
     // 按作用域聚合规则，1个作用域 = 1个条目，避免重复存储 condition
     pub rules: FxHashMap<MatchScope, CachedScopeRule>,
 }
@@ -0,0 +1,74 @@
+//! DOM 选择器规则键
+//! Wappalyzer `dom` 字段按 CSS 选择器声明检查项（元素存在/属性值/文本内容），
+//! 以 KV 型规则存储：键为"选择器 + 检查目标"，值为检查目标的匹配模式（存在性检查为 Exists 模式）
+//! 键的文本形式：
+//! 1. 元素存在：`<selector>`
+//! 2. 属性值：`<selector> @<attribute>`（属性名小写）
+//! 3. 文本内容：`<selector> ::text`
+
+use std::fmt::{Display, Formatter};
+
+/// 文本内容检查的键后缀
+const TEXT_SUFFIX: &str = " ::text";
+/// 属性检查的键分隔符
+const ATTRIBUTE_SEPARATOR: &str = " @";
+
+/// DOM 检查目标
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DomTarget {
+    /// 匹配选择器的元素存在
+    Exists,
+    /// 元素的文本内容
+    Text,
+    /// 元素的属性值（属性名小写）
+    Attribute(String),
+}
+
+/// DOM 规则键（选择器 + 检查目标）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DomKey {
+    /// CSS 选择器（原样保留，类名/属性值大小写敏感）
+    pub selector: String,
+    /// 检查目标
+    pub target: DomTarget,
+}
+
+impl DomKey {
+    /// 创建 DOM 规则键
+    pub fn new(selector: impl Into<String>, target: DomTarget) -> Self {
+        Self {
+            selector: selector.into(),
+            target,
+        }
+    }
+
+    /// 从键的文本形式解析（无法识别检查目标时视为元素存在检查）
+    /// 参数：key - 键的文本形式
+    ///
+    /// 返回：DOM 规则键
+    pub fn parse(key: &str) -> Self {
+        if let Some(selector) = key.strip_suffix(TEXT_SUFFIX) {
+            return Self::new(selector, DomTarget::Text);
+        }
+        if let Some((selector, name)) = key.rsplit_once(ATTRIBUTE_SEPARATOR) {
+            let is_attribute_name = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'));
+            if is_attribute_name {
+                return Self::new(selector, DomTarget::Attribute(name.to_string()));
+            }
+        }
+        Self::new(key, DomTarget::Exists)
+    }
+}
+
+impl Display for DomKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.target {
+            DomTarget::Exists => write!(f, "{}", self.selector),
+            DomTarget::Text => write!(f, "{}{}", self.selector, TEXT_SUFFIX),
+            DomTarget::Attribute(name) => write!(f, "{}{}{}", self.selector, ATTRIBUTE_SEPARATOR, name),
+        }
+    }
+}
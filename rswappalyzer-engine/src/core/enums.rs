@@ -12,6 +12,18 @@ pub enum MatchScope {
     Header,
     Cookie,
     Meta,
+    /// DOM 选择器（KV型：键为选择器 + 检查目标）
+    Dom,
+    /// TLS 证书颁发者（列表型）
+    CertIssuer,
+    /// DNS 记录（KV型：键为记录类型，如 mx/txt/cname）
+    Dns,
+    /// Favicon mmh3 哈希（列表型，整串匹配）
+    FaviconHash,
+    /// 页面标题（列表型，匹配首个 `<title>` 文本）
+    Title,
+    /// HTML 注释（列表型，匹配 `<!-- -->` 内的文本）
+    Comment,
 }
 
 impl Display for MatchScope {
@@ -19,12 +31,18 @@ impl Display for MatchScope {
         match self {
             MatchScope::Url => write!(f, "url"),
             MatchScope::Html => write!(f, "html"),
-            MatchScope::Js => write!(f, "html"),
+            MatchScope::Js => write!(f, "js"),
             MatchScope::Script => write!(f, "script"),
             MatchScope::ScriptSrc => write!(f, "script"),
             MatchScope::Meta => write!(f, "meta"),
             MatchScope::Header => write!(f, "header"),
             MatchScope::Cookie => write!(f, "cookie"),
+            MatchScope::Dom => write!(f, "dom"),
+            MatchScope::CertIssuer => write!(f, "cert_issuer"),
+            MatchScope::Dns => write!(f, "dns"),
+            MatchScope::FaviconHash => write!(f, "favicon_hash"),
+            MatchScope::Title => write!(f, "title"),
+            MatchScope::Comment => write!(f, "comment"),
         }
     }
 }
@@ -35,6 +53,8 @@ pub enum MatchType {
     Contains,
     Regex,
     Exists, // 存在性检测（仅用于 headers/meta 的空值场景）
+    /// 自定义匹配类型（规则中以 `\;matcher:<kind>` 标记，编译时由 MatcherRegistry 构建）
+    Custom(String),
 }
 
 impl Default for MatchType {
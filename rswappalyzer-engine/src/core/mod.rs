@@ -3,10 +3,12 @@ mod basic_info;
 mod pattern;
 mod rule;
 mod cached_rule;
+mod dom;
 
 // 导出常用项
 pub use enums::{MatchCondition, MatchScope, MatchType};
-pub use basic_info::{CategoryEntry, CategoryRule, TechBasicInfo};
-pub use pattern::{KeyedPattern, MatchRuleSet, Pattern};
+pub use basic_info::{CategoryEntry, CategoryRule, TechBasicInfo, TechLifecycle, TechMeta};
+pub use pattern::{KeyedPattern, MatchRuleSet, Pattern, PatternSource};
 pub use rule::{CategoryJsonRoot, ParsedTechRule, RuleLibrary};
-pub use cached_rule::{CachedRuleEntry, CachedTechRule, CachedScopeRule};
\ No newline at end of file
+pub use cached_rule::{CachedRuleEntry, CachedTechRule, CachedScopeRule};
+pub use dom::{DomKey, DomTarget};
\ No newline at end of file
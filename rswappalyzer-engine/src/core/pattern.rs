@@ -10,6 +10,69 @@ pub struct Pattern {
     pub pattern: String,
     pub match_type: MatchType,
     pub version_template: Option<String>,
+    /// 置信度（`\;confidence:` 标签，None 表示默认 100）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<u8>,
+    /// 版本来源 Header（`\;versionHeader:` 标签，小写；匹配成功且自身未提取到版本时从该 Header 取版本）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_header: Option<String>,
+    /// 模式来源（provenance 特性开启时记录）
+    #[cfg(feature = "provenance")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<PatternSource>,
+}
+
+impl Pattern {
+    /// 创建模式（来源信息为空）
+    pub fn new(pattern: String, match_type: MatchType, version_template: Option<String>) -> Self {
+        Self {
+            pattern,
+            match_type,
+            version_template,
+            confidence: None,
+            version_header: None,
+            #[cfg(feature = "provenance")]
+            source: None,
+        }
+    }
+
+    /// 设置置信度（链式）
+    pub fn with_confidence(mut self, confidence: Option<u8>) -> Self {
+        self.confidence = confidence;
+        self
+    }
+
+    /// 设置版本来源 Header（链式，统一转小写）
+    pub fn with_version_header(mut self, header: Option<&str>) -> Self {
+        self.version_header = header.map(str::to_lowercase);
+        self
+    }
+}
+
+/// 模式来源：定位编译后模式对应的原始规则位置（诊断用）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct PatternSource {
+    /// 规则源名称（如 wappalyzer）
+    pub source: String,
+    /// 技术名称
+    pub tech: String,
+    /// 匹配作用域
+    pub scope: MatchScope,
+    /// KV型规则的键名（Header/Cookie/Meta）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// 在原始规则同一作用域（KV型为同一键名）下的序号
+    pub index: u32,
+}
+
+impl std::fmt::Display for PatternSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}/{:?}", self.source, self.tech, self.scope)?;
+        if let Some(key) = &self.key {
+            write!(f, "[{}]", key)?;
+        }
+        write!(f, "#{}", self.index)
+    }
 }
 
 /// KV规则结构体（Header/Meta/Cookie专用）
@@ -61,12 +124,12 @@ impl MatchRuleSet {
     pub fn from_cached(scope: &MatchScope, cached: CachedScopeRule) -> Self {
         let mut rule_set = Self::with_condition(cached.condition);
         match scope {
-            MatchScope::Url | MatchScope::Html | MatchScope::Script | MatchScope::ScriptSrc => {
+            MatchScope::Url | MatchScope::Html | MatchScope::Script | MatchScope::ScriptSrc | MatchScope::CertIssuer | MatchScope::FaviconHash | MatchScope::Title | MatchScope::Comment => {
                 if let Some(patterns) = cached.list_patterns {
                     rule_set.list_patterns = patterns;
                 }
             }
-            MatchScope::Header | MatchScope::Cookie | MatchScope::Meta | MatchScope::Js=> {
+            MatchScope::Header | MatchScope::Cookie | MatchScope::Meta | MatchScope::Js | MatchScope::Dom | MatchScope::Dns => {
                 if let Some(keyed) = cached.keyed_patterns {
                     // 用 flat_map 替代 map + flatten，减少一层 collect
                     rule_set.keyed_patterns = keyed.into_iter()
@@ -86,12 +149,12 @@ impl MatchRuleSet {
             keyed_patterns: None,
         };
         match scope {
-            MatchScope::Url | MatchScope::Html | MatchScope::Script | MatchScope::ScriptSrc => {
+            MatchScope::Url | MatchScope::Html | MatchScope::Script | MatchScope::ScriptSrc | MatchScope::CertIssuer | MatchScope::FaviconHash | MatchScope::Title | MatchScope::Comment => {
                 if !self.list_patterns.is_empty() {
                     cached.list_patterns = Some(self.list_patterns.clone());
                 }
             }
-            MatchScope::Header | MatchScope::Cookie | MatchScope::Meta | MatchScope::Js => {
+            MatchScope::Header | MatchScope::Cookie | MatchScope::Meta | MatchScope::Js | MatchScope::Dom | MatchScope::Dns => {
                 if !self.keyed_patterns.is_empty() {
                     // 显式指定 HashMap 类型
                     let mut keyed: FxHashMap<String, Vec<Pattern>> = FxHashMap::default();
@@ -1,7 +1,7 @@
 use rustc_hash::FxHashMap;
 use crate::CategoryRule;
 
-use super::basic_info::{CategoryEntry, TechBasicInfo};
+use super::basic_info::{CategoryEntry, TechBasicInfo, TechMeta};
 use super::enums::MatchScope;
 use super::pattern::MatchRuleSet;
 
@@ -25,6 +25,117 @@ pub struct RuleLibrary {
     pub core_tech_map: FxHashMap<String, ParsedTechRule>,
     /// 分类规则（ID → 分类信息）
     pub category_rules: FxHashMap<u32, CategoryRule>,
+    /// 技术展示元信息旁路表（技术名称 → TechMeta，仅收录非空元信息）
+    pub meta_table: FxHashMap<String, TechMeta>,
 }
 
-pub type CategoryJsonRoot = FxHashMap<String, CategoryEntry>;
\ No newline at end of file
+impl RuleLibrary {
+    /// 为所有技术记录所属规则源（已记录规则源的技术保持不变）
+    /// 参数：source - 规则源名称
+    pub fn stamp_source(&mut self, source: &str) {
+        for rule in self.core_tech_map.values_mut() {
+            rule.basic.source.get_or_insert_with(|| source.to_string());
+        }
+    }
+
+    /// 为所有模式记录来源信息（已有来源的模式保持不变）
+    /// 参数：source - 规则源名称
+    #[cfg(feature = "provenance")]
+    pub fn stamp_provenance(&mut self, source: &str) {
+        use super::pattern::PatternSource;
+
+        for (tech_name, rule) in self.core_tech_map.iter_mut() {
+            for (scope, rule_set) in rule.match_rules.iter_mut() {
+                for (index, pattern) in rule_set.list_patterns.iter_mut().enumerate() {
+                    pattern.source.get_or_insert_with(|| PatternSource {
+                        source: source.to_string(),
+                        tech: tech_name.clone(),
+                        scope: scope.clone(),
+                        key: None,
+                        index: index as u32,
+                    });
+                }
+                // KV型规则按键名分别计数
+                let mut key_counters: FxHashMap<String, u32> = FxHashMap::default();
+                for keyed in rule_set.keyed_patterns.iter_mut() {
+                    let counter = key_counters.entry(keyed.key.clone()).or_default();
+                    let index = *counter;
+                    *counter += 1;
+                    keyed.pattern.source.get_or_insert_with(|| PatternSource {
+                        source: source.to_string(),
+                        tech: tech_name.clone(),
+                        scope: scope.clone(),
+                        key: Some(keyed.key.clone()),
+                        index,
+                    });
+                }
+            }
+        }
+    }
+}
+
+pub type CategoryJsonRoot = FxHashMap<String, CategoryEntry>;
+
+#[cfg(all(test, feature = "provenance"))]
+mod tests {
+    use crate::core::{MatchScope, PatternSource};
+    use crate::test_support::compile;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Acme": {
+                "cats": [1],
+                "html": ["acme-alpha", "\\;confidence:50", "acme-gamma"],
+                "headers": { "X-Acme": "acme", "Server": "acme-server" }
+            }
+        }
+    }"#;
+
+    fn html_sources() -> Vec<PatternSource> {
+        let lib = compile(RULES);
+        let mut sources: Vec<PatternSource> = lib.tech_patterns["Acme"]
+            .html_patterns
+            .iter()
+            .flatten()
+            .filter_map(|p| p.source.clone())
+            .collect();
+        sources.sort_by_key(|s| s.index);
+        sources
+    }
+
+    #[test]
+    fn test_list_pattern_provenance() {
+        let sources = html_sources();
+        assert!(sources.iter().all(|s| s.source == "wappalyzer" && s.tech == "Acme"));
+        assert!(sources.iter().all(|s| s.scope == MatchScope::Html && s.key.is_none()));
+        // 序号指向原始规则位置（清理后被丢弃的模式不影响后续序号）
+        assert_eq!(sources.iter().map(|s| s.index).collect::<Vec<_>>(), vec![0, 2]);
+    }
+
+    #[test]
+    fn test_keyed_pattern_provenance() {
+        let lib = compile(RULES);
+        let headers = lib.tech_patterns["Acme"].header_patterns.as_ref().unwrap();
+        for (key, patterns) in headers {
+            let source = patterns[0].source.as_ref().unwrap();
+            assert_eq!(source.key.as_deref().map(str::to_lowercase).as_deref(), Some(key.as_str()));
+            // KV型规则按键名分别计数
+            assert_eq!(source.index, 0);
+            assert!(patterns[0].describe_rule().ends_with(&format!("@ {}", source)));
+        }
+    }
+
+    #[test]
+    fn test_source_display() {
+        let source = PatternSource {
+            source: "wappalyzer".to_string(),
+            tech: "Acme".to_string(),
+            scope: MatchScope::Header,
+            key: Some("X-Acme".to_string()),
+            index: 1,
+        };
+        assert_eq!(source.to_string(), "wappalyzer:Acme/Header[X-Acme]#1");
+        let source = PatternSource { key: None, scope: MatchScope::Html, ..source };
+        assert_eq!(source.to_string(), "wappalyzer:Acme/Html#1");
+    }
+}
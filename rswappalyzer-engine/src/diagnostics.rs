@@ -0,0 +1,159 @@
+//! 规则编写诊断报告（diagnostics 特性）
+//! 将正则编译失败转换为带源码标注的 miette 诊断：以模式文本为源码，按 regex-syntax 报告的错误跨度高亮出错位置，
+//! 用于规则编写流程中快速定位问题（CoreError 仅携带错误文本，无法定位到模式内的具体位置）
+//! 1. AST 解析错误（括号不配对/非法转义等）：精确到出错片段，重复命名组等附带关联跨度
+//! 2. HIR 转换错误（非法 Unicode 类等）：精确到出错片段
+//! 3. 其它编译错误（编译体积超限等）：标注整个模式
+
+use miette::{Diagnostic, GraphicalReportHandler, GraphicalTheme, NamedSource, SourceSpan};
+use regex::RegexBuilder;
+use regex_syntax::ast::parse::Parser as AstParser;
+use regex_syntax::ast::ErrorKind as AstErrorKind;
+use regex_syntax::hir::translate::TranslatorBuilder;
+use thiserror::Error;
+
+use crate::indexer::{BrokenPattern, CompiledRuleLibrary};
+
+/// 正则模式诊断
+#[derive(Debug, Error, Diagnostic)]
+#[error("{message}")]
+#[diagnostic(code(rswappalyzer::pattern))]
+pub struct PatternDiagnostic {
+    /// 诊断摘要（含模式来源）
+    message: String,
+    /// 模式源码（名称为模式来源，如 `WordPress/Html`）
+    #[source_code]
+    source_code: NamedSource<String>,
+    /// 出错跨度
+    #[label("{label}")]
+    span: SourceSpan,
+    /// 出错跨度标注文本
+    label: String,
+    /// 关联跨度（如重复命名组的首次定义位置）
+    #[label("first defined here")]
+    auxiliary: Option<SourceSpan>,
+    /// 修复提示
+    #[help]
+    help: Option<String>,
+}
+
+impl PatternDiagnostic {
+    /// 标注整个模式的诊断（无法定位具体跨度时使用）
+    fn whole(name: &str, pattern: &str, error: String) -> Self {
+        Self {
+            message: format!("invalid pattern in {}", name),
+            source_code: NamedSource::new(name, pattern.to_string()),
+            span: (0, pattern.len()).into(),
+            label: error,
+            auxiliary: None,
+            help: None,
+        }
+    }
+
+    /// 出错跨度（字节偏移，长度）
+    pub fn span(&self) -> (usize, usize) {
+        (self.span.offset(), self.span.len())
+    }
+
+    /// 出错跨度标注文本（regex-syntax 错误描述）
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// 渲染为纯文本报告（无颜色，适合日志/CI 输出）
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let _ = GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor())
+            .render_report(&mut out, self);
+        out
+    }
+}
+
+/// 诊断单个正则模式
+/// 参数：
+/// - name: 模式来源名称（报告中作为源码名）
+/// - pattern: 正则源码
+/// - case_insensitive: 是否大小写不敏感
+///
+/// 返回：None 表示模式可正常编译 | 诊断
+pub fn diagnose_pattern(name: &str, pattern: &str, case_insensitive: bool) -> Option<PatternDiagnostic> {
+    let ast = match AstParser::new().parse(pattern) {
+        Ok(ast) => ast,
+        Err(err) => {
+            let mut diag = PatternDiagnostic::whole(name, pattern, err.kind().to_string());
+            diag.span = span_of(err.span());
+            diag.auxiliary = err.auxiliary_span().map(span_of);
+            diag.help = ast_help(err.kind()).map(str::to_string);
+            return Some(diag);
+        }
+    };
+    if let Err(err) = TranslatorBuilder::new()
+        .case_insensitive(case_insensitive)
+        .build()
+        .translate(pattern, &ast)
+    {
+        let mut diag = PatternDiagnostic::whole(name, pattern, err.kind().to_string());
+        diag.span = span_of(err.span());
+        return Some(diag);
+    }
+    RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .build()
+        .err()
+        .map(|err| PatternDiagnostic::whole(name, pattern, err.to_string()))
+}
+
+/// 常见 AST 错误的修复提示（多为未转义的元字符）
+fn ast_help(kind: &AstErrorKind) -> Option<&'static str> {
+    match kind {
+        AstErrorKind::GroupUnclosed | AstErrorKind::GroupUnopened => {
+            Some("escape literal parentheses as `\\(` / `\\)`")
+        }
+        AstErrorKind::ClassUnclosed => Some("escape a literal `[` as `\\[`"),
+        AstErrorKind::RepetitionMissing => Some("escape literal `*`, `+`, `?` or `{` with `\\`"),
+        AstErrorKind::EscapeUnrecognized => Some("remove the backslash or use a supported escape"),
+        _ => None,
+    }
+}
+
+fn span_of(span: &regex_syntax::ast::Span) -> SourceSpan {
+    (span.start.offset, span.end.offset - span.start.offset).into()
+}
+
+impl BrokenPattern {
+    /// 生成带源码标注的诊断
+    /// 重新解析模式定位出错跨度，无法定位时标注整个模式并附带记录的编译错误
+    pub fn diagnostic(&self) -> PatternDiagnostic {
+        let mut name = format!("{}/{:?}", self.tech, self.scope);
+        if let Some(key) = &self.key {
+            name.push_str(&format!("[{}]", key));
+        }
+        diagnose_pattern(&name, &self.pattern, false)
+            .or_else(|| diagnose_pattern(&name, &self.pattern, true))
+            .unwrap_or_else(|| PatternDiagnostic::whole(&name, &self.pattern, self.error.clone()))
+    }
+}
+
+impl CompiledRuleLibrary {
+    /// 为全部编译失败模式生成诊断（需先以 Lenient 模式校验）
+    pub fn pattern_diagnostics(&self) -> Vec<PatternDiagnostic> {
+        self.broken_patterns.iter().map(BrokenPattern::diagnostic).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnose_pattern_span() {
+        assert!(diagnose_pattern("ok", r"jquery-([\d.]+)\.js", false).is_none());
+
+        let diag = diagnose_pattern("Demo/Html", r"foo(bar", false).unwrap();
+        assert_eq!(diag.span(), (3, 1));
+        assert!(diag.render().contains("Demo/Html"));
+
+        let diag = diagnose_pattern("Demo/Url", r"a\qb", false).unwrap();
+        assert_eq!(diag.span(), (1, 2));
+    }
+}
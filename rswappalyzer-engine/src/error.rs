@@ -3,6 +3,7 @@
 use thiserror::Error;
 
 use regex::Error as RegexError;
+use serde_json::Error as SerdeJsonError;
 
 /// 内核核心错误枚举
 /// 封装rswappalyzer-core层所有错误类型，专注内核级逻辑错误
@@ -25,6 +26,10 @@ pub enum CoreError {
     #[error("Rule parse failed: {0}")]
     RuleParseError(String),
 
+    /// JSON序列化/反序列化失败（规则JSON/证据导出等）
+    #[error("JSON parse/serialize failed: {0}")]
+    JsonError(#[from] SerdeJsonError),
+
     // ===================== 编译相关错误 =====================
     /// 正则表达式编译失败（正则语法错误/不支持的特性）
     #[error("Regex compilation failed: {0}")]
@@ -67,6 +72,14 @@ pub enum CoreError {
     /// 非法的规则状态转换（规则生命周期状态非法变更）
     #[error("Invalid rule state transition: {0}")]
     InvalidStateTransition(String),
+
+    /// 存在编译失败的模式（严格模式校验）
+    #[error("Broken patterns: {0}")]
+    BrokenPatterns(String),
+
+    /// 规则库由不兼容（更新）的引擎版本编译
+    #[error("Incompatible engine: {0}")]
+    IncompatibleEngine(String),
 }
 
 /// 内核层全局Result类型别名
@@ -1,11 +1,13 @@
 use crate::{
-    core::{CategoryJsonRoot, TechBasicInfo},
+    core::{CategoryJsonRoot, MatchCondition, MatchType, TechBasicInfo},
     indexer::{
         compiled::CompiledTechRule,
         index_rules::CommonIndexedRule,
         library::CompiledRuleLibrary,
         matcher::{fold_to_match_gate, Matcher},
-        CompiledPattern, ExecutablePattern, MatchGate, RuleLibraryIndex, ScopedIndexedRule,
+        parse_hostname_spec, url_regex_host_suffix, CompileOptions, CompiledPattern,
+        ExecutablePattern, HostnameIndex, HostnameRule, MatchGate, RuleLibraryIndex,
+        ScopedIndexedRule,
         StructuralPrereq,
     },
     min_evidence::MinEvidenceMeta,
@@ -16,6 +18,7 @@ use crate::{
 };
 use once_cell::sync::OnceCell;
 use rustc_hash::{FxHashMap, FxHashSet};
+use std::sync::Arc;
 
 // HTML关键字黑名单（全局懒加载）
 static HTML_TOKEN_BLACKLIST: OnceCell<FxHashSet<&'static str>> = OnceCell::new();
@@ -37,6 +40,20 @@ struct BuiltTechRule {
     header_rules: FxHashMap<String, Vec<CommonIndexedRule>>,
     /// Cookie匹配规则映射（Key=Cookie名称）
     cookie_rules: FxHashMap<String, Vec<CommonIndexedRule>>,
+    /// JS匹配规则映射（Key=全局变量属性链）
+    js_rules: FxHashMap<String, Vec<CommonIndexedRule>>,
+    /// DOM匹配规则映射（Key=选择器 + 检查目标）
+    dom_rules: FxHashMap<String, Vec<CommonIndexedRule>>,
+    /// TLS证书颁发者匹配规则列表
+    cert_issuer_rules: Vec<CommonIndexedRule>,
+    /// Favicon mmh3 哈希匹配规则列表
+    favicon_hash_rules: Vec<CommonIndexedRule>,
+    /// 页面标题匹配规则列表
+    title_rules: Vec<CommonIndexedRule>,
+    /// HTML 注释匹配规则列表
+    comment_rules: Vec<CommonIndexedRule>,
+    /// DNS匹配规则映射（Key=记录类型）
+    dns_rules: FxHashMap<String, Vec<CommonIndexedRule>>,
 }
 
 /// 技术规则构建器（生命周期内）
@@ -90,6 +107,10 @@ impl<'a> TechRuleBuilder<'a> {
             (crate::core::MatchScope::Script | crate::core::MatchScope::ScriptSrc, _) => {
                 rule.script_rules.push(common.clone())
             }
+            (crate::core::MatchScope::CertIssuer, _) => rule.cert_issuer_rules.push(common.clone()),
+            (crate::core::MatchScope::FaviconHash, _) => rule.favicon_hash_rules.push(common.clone()),
+            (crate::core::MatchScope::Title, _) => rule.title_rules.push(common.clone()),
+            (crate::core::MatchScope::Comment, _) => rule.comment_rules.push(common.clone()),
             (crate::core::MatchScope::Meta, ScopedIndexedRule::KV { key, .. }) => rule
                 .meta_rules
                 .entry(key.clone())
@@ -105,13 +126,44 @@ impl<'a> TechRuleBuilder<'a> {
                 .entry(key.clone())
                 .or_default()
                 .push(common.clone()),
-            _ => eprintln!(
+            (crate::core::MatchScope::Js, ScopedIndexedRule::KV { key, .. }) => rule
+                .js_rules
+                .entry(key.clone())
+                .or_default()
+                .push(common.clone()),
+            (crate::core::MatchScope::Dom, ScopedIndexedRule::KV { key, .. }) => rule
+                .dom_rules
+                .entry(key.clone())
+                .or_default()
+                .push(common.clone()),
+            (crate::core::MatchScope::Dns, ScopedIndexedRule::KV { key, .. }) => rule
+                .dns_rules
+                .entry(key.clone())
+                .or_default()
+                .push(common.clone()),
+            _ => log::warn!(
                 "Tech [{}] has invalid rule type for scope {}",
                 tech_name, scope
             ),
         }
     }
 
+    /// 为声明了主机名后缀的技术补充规则条目（仅声明主机名后缀、不含模式的技术同样需要编译）
+    fn add_hostname_techs(&mut self) {
+        for (tech_name, info) in self.tech_info_map {
+            if info.hostnames.is_empty() || self.tech_rules.contains_key(tech_name) {
+                continue;
+            }
+            self.tech_rules.insert(
+                tech_name.clone(),
+                BuiltTechRule {
+                    tech_info: info.clone(),
+                    ..BuiltTechRule::default()
+                },
+            );
+        }
+    }
+
     /// 转换为迭代器（消费构建器）
     fn into_iter(self) -> impl Iterator<Item = (String, BuiltTechRule)> {
         self.tech_rules.into_iter()
@@ -129,6 +181,9 @@ impl RuleIndexer {
     /// 使用默认分类文件构建编译规则库
     /// 参数：index - 规则库索引
     /// 返回：编译后的规则库 | 错误
+    #[deprecated(
+        note = "reads data/categories_data.json relative to the working directory; use build_compiled_library_with_category_map or pass an explicit path"
+    )]
     pub fn build_compiled_library_with_default_category(
         index: &RuleLibraryIndex,
     ) -> CoreResult<CompiledRuleLibrary> {
@@ -143,65 +198,138 @@ impl RuleIndexer {
     pub fn build_compiled_library(
         index: &RuleLibraryIndex,
         category_json_path: Option<&str>,
+    ) -> CoreResult<CompiledRuleLibrary> {
+        Self::build_compiled_library_with_options(
+            index,
+            category_json_path,
+            &CompileOptions::default(),
+        )
+    }
+
+    /// 构建编译规则库（指定编译选项）
+    /// 参数：
+    /// - index: 规则库索引
+    /// - category_json_path: 分类JSON文件路径（可选）
+    /// - options: 编译选项（正则大小写策略等）
+    ///
+    /// 返回：编译后的规则库 | 错误
+    pub fn build_compiled_library_with_options(
+        index: &RuleLibraryIndex,
+        category_json_path: Option<&str>,
+        options: &CompileOptions,
+    ) -> CoreResult<CompiledRuleLibrary> {
+        let category_map = match category_json_path {
+            Some(path) => Self::load_category_map(path),
+            None => FxHashMap::default(),
+        };
+        Self::build_compiled_library_with_category_map(index, category_map, options)
+    }
+
+    /// 构建编译规则库（使用已加载的分类映射，不访问文件系统）
+    /// 参数：
+    /// - index: 规则库索引
+    /// - category_map: 分类ID到名称的映射（可由 parse_category_map 解析内置/自定义分类数据）
+    /// - options: 编译选项
+    ///
+    /// 返回：编译后的规则库 | 错误
+    pub fn build_compiled_library_with_category_map(
+        index: &RuleLibraryIndex,
+        category_map: FxHashMap<u32, String>,
+        options: &CompileOptions,
     ) -> CoreResult<CompiledRuleLibrary> {
         // 1. 构建临时技术规则
         let mut builder = TechRuleBuilder::new(&index.tech_info_map);
         for (scope, rules) in &index.rules {
             rules.iter().for_each(|r| builder.add_scoped_rule(scope, r));
         }
+        if options.compiles_scope(PruneScope::Hostname) {
+            builder.add_hostname_techs();
+        }
 
-        // 2. 编译为CompiledTechRule
+        // 2. 编译为CompiledTechRule（主机名后缀型 URL 正则转入主机名索引）
         let mut compiled_tech = FxHashMap::default();
         let mut compiled_meta = FxHashMap::default();
+        let mut hostname_index = HostnameIndex::default();
+        // 规则源名称去重（同一规则源的技术共享同一 Arc）
+        let mut sources: FxHashMap<String, Arc<str>> = FxHashMap::default();
 
-        for (name, rule) in builder.into_iter() {
+        for (name, mut rule) in builder.into_iter() {
+            // 未编译 Hostname 作用域时主机名后缀型 URL 正则保留在 URL 作用域
+            if options.compiles_scope(PruneScope::Hostname) {
+                Self::extract_hostname_rules(&name, &mut rule, &mut hostname_index);
+            }
             let implies = rule.tech_info.implies.clone().unwrap_or_default();
+            let source = rule.tech_info.source.as_ref().map(|source| {
+                sources
+                    .entry(source.clone())
+                    .or_insert_with(|| Arc::from(source.as_str()))
+                    .clone()
+            });
             compiled_tech.insert(
                 name.clone(),
                 CompiledTechRule {
                     name: name.clone(),
-                    url_patterns: Self::compile_content_patterns(&rule.url_rules, PruneScope::Url),
+                    url_patterns: Self::compile_content_patterns(&rule.url_rules, PruneScope::Url, options),
                     html_patterns: Self::compile_content_patterns(
                         &rule.html_rules,
                         PruneScope::Html,
+                        options,
                     ),
                     script_patterns: Self::compile_content_patterns(
                         &rule.script_rules,
                         PruneScope::Script,
+                        options,
                     ),
-                    meta_patterns: Self::compile_keyed_patterns(&rule.meta_rules, PruneScope::Meta),
+                    meta_patterns: Self::compile_keyed_patterns(&rule.meta_rules, PruneScope::Meta, options),
                     header_patterns: Self::compile_keyed_patterns(
                         &rule.header_rules,
                         PruneScope::Header,
+                        options,
                     ),
                     cookie_patterns: Self::compile_keyed_patterns(
                         &rule.cookie_rules,
                         PruneScope::Cookie,
+                        options,
+                    ),
+                    js_patterns: Self::compile_keyed_patterns(&rule.js_rules, PruneScope::Js, options),
+                    dom_patterns: Self::compile_keyed_patterns(&rule.dom_rules, PruneScope::Dom, options),
+                    cert_issuer_patterns: Self::compile_content_patterns(
+                        &rule.cert_issuer_rules,
+                        PruneScope::CertIssuer,
+                        options,
                     ),
+                    dns_patterns: Self::compile_keyed_patterns(&rule.dns_rules, PruneScope::Dns, options),
+                    favicon_hash_patterns: Self::compile_content_patterns(
+                        &rule.favicon_hash_rules,
+                        PruneScope::FaviconHash,
+                        options,
+                    ),
+                    title_patterns: Self::compile_content_patterns(&rule.title_rules, PruneScope::Title, options),
+                    comment_patterns: Self::compile_content_patterns(&rule.comment_rules, PruneScope::Comment, options),
+                    and_scopes: Self::and_scopes(&rule),
                     category_ids: rule.tech_info.category_ids.clone(),
                     implies,
+                    excludes: rule.tech_info.excludes.clone(),
+                    requires: rule.tech_info.requires.clone(),
+                    requires_category: rule.tech_info.requires_category.clone(),
+                    source,
+                    regex_sets: Default::default(),
                 },
             );
             compiled_meta.insert(name, rule.tech_info);
         }
 
-        // 3. 加载分类映射
-        let category_map = match category_json_path {
-            Some(path) => Self::load_category_map(path),
-            None => FxHashMap::default(),
-        };
-
-        // 4. 构建证据索引
+        // 3. 构建证据索引
         let (evidence_index, no_evidence_index) = Self::build_evidence_indexes(&compiled_tech);
 
-        // 5. 构建 known_tokens 和 known_tokens_by_scope
+        // 4. 构建 known_tokens 和 known_tokens_by_scope
         let mut known_tokens = FxHashSet::default();
         let mut known_tokens_by_scope = FxHashMap::default();
         for (token, scope_to_techs) in &evidence_index {
-            // 5.1 填充全局known_tokens（所有证据token的全集）
+            // 4.1 填充全局known_tokens（所有证据token的全集）
             known_tokens.insert(token.clone());
 
-            // 5.2 填充按scope的known_tokens_by_scope（按scope分组）
+            // 4.2 填充按scope的known_tokens_by_scope（按scope分组）
             for (scope, _techs) in scope_to_techs {
                 known_tokens_by_scope
                     .entry(*scope)
@@ -210,7 +338,7 @@ impl RuleIndexer {
             }
         }
 
-        Ok(CompiledRuleLibrary {
+        let mut library = CompiledRuleLibrary {
             tech_patterns: compiled_tech,
             category_map,
             tech_meta: compiled_meta,
@@ -218,7 +346,117 @@ impl RuleIndexer {
             known_tokens,
             known_tokens_by_scope,
             no_evidence_index,
-        })
+            meta_table: index.meta_table.clone(),
+            broken_patterns: Vec::new(),
+            rejected_patterns: Vec::new(),
+            hostname_index,
+            dedup_stats: Default::default(),
+            engine: super::EngineRequirements::current(),
+            literal_prefilters: None,
+        };
+
+        // 5. 等价模式去重（默认开启）
+        if options.dedup_patterns {
+            let stats = library.dedup_patterns();
+            log::debug!("Duplicate patterns removed | Count: {} | By scope: {:?}", stats.removed, stats.by_scope);
+        }
+
+        // 6. 低特异性模式降权（可选，依赖证据索引）
+        if let Some(policy) = &options.low_specificity {
+            let changed = library.apply_low_specificity_policy(policy);
+            log::debug!("Low-specificity patterns down-weighted | Count: {}", changed);
+        }
+
+        // 7. 规则生命周期校验（废弃/过期告警，按策略剔除过期规则）
+        library.apply_lifecycle_policy(options.expired_rules, &super::today_utc());
+
+        // 8. 正则上限（剔除超长正则；运行期懒编译沿用同一上限）与模式校验（可选，严格模式下存在编译失败的模式时返回错误）
+        super::set_regex_limits(options.regex_limits);
+        library.enforce_regex_limits(&options.regex_limits);
+        library.validate_patterns(options.pattern_validation)?;
+
+        // 9. 按作用域构建准入字面量自动机（运行期每个输入仅扫描一次）
+        library.build_literal_prefilters();
+
+        Ok(library)
+    }
+
+    /// 填充技术的主机名后缀规则
+    /// 1. 规则扩展字段 `hostname` 声明的后缀（置信度 100）
+    /// 2. 形如 `^https?://[^/]+\.github\.io` 的 URL 正则（Or 条件、不含版本模板），转换后从 URL 规则中移除
+    ///
+    /// 参数：
+    /// - name: 技术名称
+    /// - rule: 临时技术规则
+    /// - hostname_index: 主机名后缀索引
+    fn extract_hostname_rules(
+        name: &str,
+        rule: &mut BuiltTechRule,
+        hostname_index: &mut HostnameIndex,
+    ) {
+        let mut insert = |(suffix, include_apex): (String, bool), confidence: u8| {
+            hostname_index.insert(
+                &suffix,
+                HostnameRule {
+                    tech: name.to_string(),
+                    confidence,
+                    include_apex,
+                },
+            );
+        };
+
+        for spec in &rule.tech_info.hostnames {
+            match parse_hostname_spec(spec) {
+                Some(parsed) => insert(parsed, 100),
+                None => log::warn!("Invalid hostname suffix ignored | Tech: {} | Hostname: {}", name, spec),
+            }
+        }
+
+        rule.url_rules.retain(|url_rule| {
+            let pattern = &url_rule.pattern;
+            if url_rule.match_type != MatchType::Regex
+                || url_rule.condition != MatchCondition::Or
+                || pattern.version_template.is_some()
+            {
+                return true;
+            }
+            match url_regex_host_suffix(&pattern.pattern) {
+                Some(parsed) => {
+                    insert(parsed, pattern.confidence.unwrap_or(100));
+                    false
+                }
+                None => true,
+            }
+        });
+    }
+
+    /// 收集 And 条件的作用域（Script/ScriptSrc 共用 Script 作用域，任一规则为 And 即按 And 执行）
+    /// 参数：rule - 临时技术规则
+    ///
+    /// 返回：And 条件作用域列表
+    fn and_scopes(rule: &BuiltTechRule) -> Vec<PruneScope> {
+        let is_and = |rules: &[CommonIndexedRule]| rules.iter().any(|r| r.condition == MatchCondition::And);
+        let is_keyed_and =
+            |rules: &FxHashMap<String, Vec<CommonIndexedRule>>| rules.values().any(|rules| is_and(rules));
+
+        [
+            (PruneScope::Url, is_and(&rule.url_rules)),
+            (PruneScope::Html, is_and(&rule.html_rules)),
+            (PruneScope::Script, is_and(&rule.script_rules)),
+            (PruneScope::Meta, is_keyed_and(&rule.meta_rules)),
+            (PruneScope::Header, is_keyed_and(&rule.header_rules)),
+            (PruneScope::Cookie, is_keyed_and(&rule.cookie_rules)),
+            (PruneScope::Js, is_keyed_and(&rule.js_rules)),
+            (PruneScope::Dom, is_keyed_and(&rule.dom_rules)),
+            (PruneScope::CertIssuer, is_and(&rule.cert_issuer_rules)),
+            (PruneScope::FaviconHash, is_and(&rule.favicon_hash_rules)),
+            (PruneScope::Title, is_and(&rule.title_rules)),
+            (PruneScope::Comment, is_and(&rule.comment_rules)),
+            (PruneScope::Dns, is_keyed_and(&rule.dns_rules)),
+        ]
+        .into_iter()
+        .filter_map(|(scope, and)| and.then_some(scope))
+        .collect()
     }
 
     /// 从指定路径加载分类映射
@@ -238,8 +476,15 @@ impl RuleIndexer {
             }
         };
 
+        Self::parse_category_map(&json_content)
+    }
+
+    /// 解析分类JSON内容为分类映射（Wappalyzer categories 格式）
+    /// 参数：json_content - 分类JSON文本
+    /// 返回：分类ID到名称的映射（空映射表示解析失败）
+    pub fn parse_category_map(json_content: &str) -> FxHashMap<u32, String> {
         // 解析JSON
-        let category_entries: CategoryJsonRoot = match serde_json::from_str(&json_content) {
+        let category_entries: CategoryJsonRoot = match serde_json::from_str(json_content) {
             Ok(v) => v,
             Err(e) => {
                 log::debug!(
@@ -308,6 +553,30 @@ impl RuleIndexer {
                 PruneScope::Script,
                 &mut evidence_index,
             );
+            Self::fill_evidence_index_with_scope(
+                tech_name,
+                tech_rule.cert_issuer_patterns.as_ref(),
+                PruneScope::CertIssuer,
+                &mut evidence_index,
+            );
+            Self::fill_evidence_index_with_scope(
+                tech_name,
+                tech_rule.favicon_hash_patterns.as_ref(),
+                PruneScope::FaviconHash,
+                &mut evidence_index,
+            );
+            Self::fill_evidence_index_with_scope(
+                tech_name,
+                tech_rule.title_patterns.as_ref(),
+                PruneScope::Title,
+                &mut evidence_index,
+            );
+            Self::fill_evidence_index_with_scope(
+                tech_name,
+                tech_rule.comment_patterns.as_ref(),
+                PruneScope::Comment,
+                &mut evidence_index,
+            );
 
             // 填充KV型规则的证据索引
             Self::fill_evidence_index_for_keyed_with_scope(
@@ -328,6 +597,18 @@ impl RuleIndexer {
                 PruneScope::Cookie,
                 &mut evidence_index,
             );
+            Self::fill_evidence_index_for_keyed_with_scope(
+                tech_name,
+                tech_rule.js_patterns.as_ref(),
+                PruneScope::Js,
+                &mut evidence_index,
+            );
+            Self::fill_evidence_index_for_keyed_with_scope(
+                tech_name,
+                tech_rule.dns_patterns.as_ref(),
+                PruneScope::Dns,
+                &mut evidence_index,
+            );
 
             // 填充无证据索引
             Self::fill_no_evidence_index_with_scope(tech_name, tech_rule, &mut no_evidence_index);
@@ -497,22 +778,96 @@ impl RuleIndexer {
                 .or_default()
                 .insert(tech_name.clone());
         }
+        if rule
+            .js_patterns
+            .as_ref()
+            .is_some_and(|k| k.values().any(|p| p.iter().any(is_no_evidence)))
+        {
+            no_evidence_map
+                .entry(PruneScope::Js)
+                .or_default()
+                .insert(tech_name.clone());
+        }
+        if rule
+            .cert_issuer_patterns
+            .as_ref()
+            .is_some_and(|p| p.iter().any(is_no_evidence))
+        {
+            no_evidence_map
+                .entry(PruneScope::CertIssuer)
+                .or_default()
+                .insert(tech_name.clone());
+        }
+        if rule
+            .favicon_hash_patterns
+            .as_ref()
+            .is_some_and(|p| p.iter().any(is_no_evidence))
+        {
+            no_evidence_map
+                .entry(PruneScope::FaviconHash)
+                .or_default()
+                .insert(tech_name.clone());
+        }
+        if rule
+            .title_patterns
+            .as_ref()
+            .is_some_and(|p| p.iter().any(is_no_evidence))
+        {
+            no_evidence_map
+                .entry(PruneScope::Title)
+                .or_default()
+                .insert(tech_name.clone());
+        }
+        if rule
+            .comment_patterns
+            .as_ref()
+            .is_some_and(|p| p.iter().any(is_no_evidence))
+        {
+            no_evidence_map
+                .entry(PruneScope::Comment)
+                .or_default()
+                .insert(tech_name.clone());
+        }
+        if rule
+            .dns_patterns
+            .as_ref()
+            .is_some_and(|k| k.values().any(|p| p.iter().any(is_no_evidence)))
+        {
+            no_evidence_map
+                .entry(PruneScope::Dns)
+                .or_default()
+                .insert(tech_name.clone());
+        }
+        // DOM 规则按选择器匹配，不参与令牌证据索引，含 DOM 规则的技术均为该作用域候选
+        if rule.dom_patterns.is_some() {
+            no_evidence_map
+                .entry(PruneScope::Dom)
+                .or_default()
+                .insert(tech_name.clone());
+        }
     }
 
     /// 编译内容型匹配规则（URL/HTML/Script）
     /// 参数：
     /// - rules: 通用索引规则列表
     /// - scope: 剪枝作用域
+    /// - options: 编译选项
     /// 返回：编译后的匹配模式列表（None表示空）
     fn compile_content_patterns(
         rules: &[CommonIndexedRule],
         scope: PruneScope,
+        options: &CompileOptions,
     ) -> Option<Vec<CompiledPattern>> {
+        if !options.compiles_scope(scope) {
+            return None;
+        }
         let mut pats = Vec::new();
 
         for r in rules {
-            // 构建匹配器
-            let matcher = Matcher::from_match_type_lazy(&r.match_type, &r.pattern);
+            // 构建匹配器（自定义匹配类型构建失败时丢弃该模式）
+            let Some(matcher) = Self::build_matcher(r, scope, options) else {
+                continue;
+            };
             let matcher_spec = matcher.to_spec();
 
             // 提取剪枝策略和证据
@@ -533,11 +888,14 @@ impl RuleIndexer {
                 index_key: String::new(),
                 exec: ExecutablePattern {
                     matcher: matcher_spec,
-                    matcher_cache: OnceCell::new(),
+                    matcher_cache: OnceCell::with_value(matcher),
                     match_gate,
-                    confidence: 100,
+                    confidence: r.pattern.confidence.unwrap_or(100),
                     version_template: r.pattern.version_template.clone(),
+                    version_header: None,
                 },
+                #[cfg(feature = "provenance")]
+                source: r.pattern.source.clone(),
             });
         }
 
@@ -549,19 +907,26 @@ impl RuleIndexer {
     /// 参数：
     /// - rules: KV型通用索引规则映射
     /// - scope: 剪枝作用域
+    /// - options: 编译选项
     /// 返回：编译后的KV型匹配模式（None表示空）
     fn compile_keyed_patterns(
         rules: &FxHashMap<String, Vec<CommonIndexedRule>>,
         scope: PruneScope,
+        options: &CompileOptions,
     ) -> Option<FxHashMap<String, Vec<CompiledPattern>>> {
+        if !options.compiles_scope(scope) {
+            return None;
+        }
         let mut pats = FxHashMap::default();
 
         for (k, rs) in rules {
             let mut rule_pats = Vec::new();
 
             for r in rs {
-                // 构建匹配器
-                let matcher = Matcher::from_match_type_lazy(&r.match_type, &r.pattern);
+                // 构建匹配器（自定义匹配类型构建失败时丢弃该模式）
+                let Some(matcher) = Self::build_matcher(r, scope, options) else {
+                    continue;
+                };
                 let matcher_spec = matcher.to_spec();
 
                 // 提取剪枝策略和证据
@@ -577,17 +942,21 @@ impl RuleIndexer {
                     index_key: k.clone(),
                     exec: ExecutablePattern {
                         matcher: matcher_spec,
-                        matcher_cache: OnceCell::new(),
+                        matcher_cache: OnceCell::with_value(matcher),
                         match_gate,
-                        confidence: 100,
+                        confidence: r.pattern.confidence.unwrap_or(100),
                         version_template: r.pattern.version_template.clone(),
+                        version_header: r.pattern.version_header.clone(),
                     },
+                    #[cfg(feature = "provenance")]
+                    source: r.pattern.source.clone(),
                 });
             }
 
-            // 非空列表才插入（Key转为小写）
+            // 非空列表才插入（Key转为小写；DOM 选择器大小写敏感，保留原样）
             if !rule_pats.is_empty() {
-                pats.insert(k.to_lowercase(), rule_pats);
+                let key = if scope == PruneScope::Dom { k.clone() } else { k.to_lowercase() };
+                pats.insert(key, rule_pats);
             }
         }
 
@@ -595,6 +964,38 @@ impl RuleIndexer {
         (!pats.is_empty()).then_some(pats)
     }
 
+    /// 构建匹配器：内置匹配类型按作用域大小写策略构建，自定义匹配类型查询注册表
+    /// 参数：
+    /// - r: 通用索引规则
+    /// - scope: 剪枝作用域
+    /// - options: 编译选项
+    ///
+    /// 返回：运行时匹配器（自定义匹配类型未注册/构建失败时为None）
+    fn build_matcher(
+        r: &CommonIndexedRule,
+        scope: PruneScope,
+        options: &CompileOptions,
+    ) -> Option<Matcher> {
+        if let MatchType::Custom(kind) = &r.match_type {
+            return Matcher::custom(&options.matchers, kind, &r.pattern.pattern)
+                .map_err(|e| {
+                    log::warn!(
+                        "Custom matcher build failed: kind={} pattern={} error={}",
+                        kind,
+                        r.pattern.pattern,
+                        e
+                    )
+                })
+                .ok();
+        }
+
+        Some(Matcher::from_match_type_with_case(
+            &r.match_type,
+            &r.pattern,
+            options.regex_case_insensitive(scope, &r.pattern.pattern),
+        ))
+    }
+
     /// 提取最小证据元信息
     #[inline(always)]
     fn extract_min_evidence_with_meta(matcher: &Matcher) -> MinEvidenceMeta {
@@ -621,7 +1022,8 @@ impl RuleIndexer {
                     source_literal: min_evidence.source_literal,
                 }
             }
-            Matcher::Exists => MinEvidenceMeta {
+            // 自定义匹配器语义未知，不参与证据剪枝（匹配门控为Open）
+            Matcher::Exists | Matcher::Custom { .. } => MinEvidenceMeta {
                 tokens: FxHashSet::default(),
                 source_len: 0,
                 source_literal: String::new(), // Exists无字面量，赋值空字符串
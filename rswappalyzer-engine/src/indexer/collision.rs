@@ -0,0 +1,302 @@
+//! 跨技术模式冲突分析
+//! 识别被多个技术共享的相同模式/泛化证据（如通用的 `admin` token），
+//! 这类低特异性模式容易导致多个技术同时误报
+//! 1. 编译期报告：列出冲突模式及涉及的技术
+//! 2. 可选降权：将低特异性模式的置信度下调（见 LowSpecificityPolicy）
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+
+use crate::scope_pruner::PruneScope;
+
+use super::{CompiledPattern, CompiledRuleLibrary, MatchGate, MatcherSpec};
+
+/// 低特异性模式降权策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LowSpecificityPolicy {
+    /// 判定阈值：同一模式/证据被至少多少个技术共享时视为低特异性
+    pub min_techs: usize,
+    /// 低特异性模式的置信度（0-100）
+    pub confidence: u8,
+}
+
+impl Default for LowSpecificityPolicy {
+    fn default() -> Self {
+        Self {
+            min_techs: 5,
+            confidence: 50,
+        }
+    }
+}
+
+/// 冲突类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CollisionKind {
+    /// 完全相同的模式（作用域+Key+匹配器一致）出现在多个技术中
+    IdenticalPattern,
+    /// 准入证据 token 被多个技术共享（证据最稀有的 token 也不够特异）
+    SharedEvidence,
+}
+
+/// 单条冲突记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternCollision {
+    /// 冲突类型
+    pub kind: CollisionKind,
+    /// 作用域
+    pub scope: PruneScope,
+    /// 索引Key（Header/Cookie/Meta名称，内容型作用域为空）
+    pub key: String,
+    /// 冲突描述：相同模式为匹配器描述，共享证据为 token
+    pub signature: String,
+    /// 涉及的技术（已排序）
+    pub techs: Vec<String>,
+    /// 冲突模式的原始来源（provenance 特性，仅相同模式类冲突记录）
+    #[cfg(feature = "provenance")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<crate::core::PatternSource>,
+}
+
+/// 冲突报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PatternCollisionReport {
+    /// 判定阈值
+    pub min_techs: usize,
+    /// 冲突记录（按涉及技术数降序）
+    pub collisions: Vec<PatternCollision>,
+}
+
+impl PatternCollisionReport {
+    /// 是否无冲突
+    pub fn is_empty(&self) -> bool {
+        self.collisions.is_empty()
+    }
+}
+
+/// 模式签名：作用域 + 索引Key + 匹配器
+type PatternSignature = (PruneScope, String, MatcherSpec);
+
+#[inline]
+fn signature_of(pat: &CompiledPattern) -> PatternSignature {
+    (pat.scope, pat.index_key.clone(), pat.exec.matcher.clone())
+}
+
+/// 统计相同模式签名 -> 技术集合
+fn collect_identical(lib: &CompiledRuleLibrary) -> FxHashMap<PatternSignature, FxHashSet<String>> {
+    let mut groups: FxHashMap<PatternSignature, FxHashSet<String>> = FxHashMap::default();
+    for (tech_name, tech) in &lib.tech_patterns {
+        tech.for_each_pattern(|pat| {
+            // Exists 类型本身无内容，仅按Key区分，同样参与统计
+            groups
+                .entry(signature_of(pat))
+                .or_default()
+                .insert(tech_name.clone());
+        });
+    }
+    groups
+}
+
+/// 收集指定模式签名的全部来源（按来源位置排序）
+#[cfg(feature = "provenance")]
+fn sources_of(lib: &CompiledRuleLibrary, sig: &PatternSignature) -> Vec<crate::core::PatternSource> {
+    let mut sources = Vec::new();
+    for tech in lib.tech_patterns.values() {
+        tech.for_each_pattern(|pat| {
+            if let Some(source) = &pat.source {
+                if &signature_of(pat) == sig {
+                    sources.push(source.clone());
+                }
+            }
+        });
+    }
+    sources.sort_by_key(|s| s.to_string());
+    sources
+}
+
+/// 计算准入证据在作用域内的共享技术数（取最稀有 token 的技术数）
+/// 返回：None 表示无证据（Open 网关）
+fn evidence_sharing(lib: &CompiledRuleLibrary, pat: &CompiledPattern) -> Option<usize> {
+    let tech_count = |token: &String| {
+        lib.evidence_index
+            .get(token)
+            .and_then(|by_scope| by_scope.get(&pat.scope))
+            .map_or(0, FxHashSet::len)
+    };
+    match &pat.exec.match_gate {
+        MatchGate::Open => None,
+        MatchGate::RequireAll(set) => set.iter().map(tech_count).min(),
+        MatchGate::RequireAnyLiteral(list) => list.iter().map(tech_count).max(),
+    }
+}
+
+impl CompiledRuleLibrary {
+    /// 生成跨技术模式冲突报告
+    /// 参数：min_techs - 同一模式/证据至少被多少个技术共享才计入报告
+    ///
+    /// 返回：冲突报告（按涉及技术数降序，稳定排序）
+    pub fn collision_report(&self, min_techs: usize) -> PatternCollisionReport {
+        let min_techs = min_techs.max(2);
+        let mut collisions = Vec::new();
+
+        // 1. 完全相同的模式
+        for ((scope, key, spec), techs) in collect_identical(self) {
+            if techs.len() < min_techs {
+                continue;
+            }
+            let mut techs: Vec<String> = techs.into_iter().collect();
+            techs.sort_unstable();
+            #[cfg(feature = "provenance")]
+            let sources = sources_of(self, &(scope, key.clone(), spec.clone()));
+            collisions.push(PatternCollision {
+                kind: CollisionKind::IdenticalPattern,
+                scope,
+                key,
+                #[cfg(feature = "provenance")]
+                sources,
+                signature: spec.to_matcher().describe(),
+                techs,
+            });
+        }
+
+        // 2. 共享证据 token
+        for (token, by_scope) in &self.evidence_index {
+            for (scope, techs) in by_scope {
+                if techs.len() < min_techs {
+                    continue;
+                }
+                let mut techs: Vec<String> = techs.iter().cloned().collect();
+                techs.sort_unstable();
+                collisions.push(PatternCollision {
+                    kind: CollisionKind::SharedEvidence,
+                    scope: *scope,
+                    key: String::new(),
+                    signature: token.clone(),
+                    techs,
+                    #[cfg(feature = "provenance")]
+                    sources: Vec::new(),
+                });
+            }
+        }
+
+        collisions.sort_by(|a, b| {
+            b.techs
+                .len()
+                .cmp(&a.techs.len())
+                .then_with(|| a.signature.cmp(&b.signature))
+                .then_with(|| a.scope.as_str().cmp(b.scope.as_str()))
+        });
+
+        PatternCollisionReport {
+            min_techs,
+            collisions,
+        }
+    }
+
+    /// 按策略对低特异性模式降权
+    /// 判定：相同模式被 ≥min_techs 个技术共享，或其准入证据在作用域内被 ≥min_techs 个技术共享
+    /// 返回：被降权的模式数量
+    pub fn apply_low_specificity_policy(&mut self, policy: &LowSpecificityPolicy) -> usize {
+        let min_techs = policy.min_techs.max(2);
+        let identical = collect_identical(self);
+
+        // 先只读计算降权目标，再统一修改，避免借用冲突
+        let mut targets: FxHashMap<String, FxHashSet<PatternSignature>> = FxHashMap::default();
+        for (tech_name, tech) in &self.tech_patterns {
+            tech.for_each_pattern(|pat| {
+                let sig = signature_of(pat);
+                let shared_identical = identical.get(&sig).map_or(0, FxHashSet::len);
+                let shared_evidence = evidence_sharing(self, pat).unwrap_or(0);
+                if shared_identical >= min_techs || shared_evidence >= min_techs {
+                    targets.entry(tech_name.clone()).or_default().insert(sig);
+                }
+            });
+        }
+
+        let mut changed = 0;
+        for (tech_name, sigs) in targets {
+            let Some(tech) = self.tech_patterns.get_mut(&tech_name) else {
+                continue;
+            };
+            tech.for_each_pattern_mut(|pat| {
+                if pat.exec.confidence > policy.confidence && sigs.contains(&signature_of(pat)) {
+                    pat.exec.confidence = policy.confidence;
+                    changed += 1;
+                }
+            });
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{compile, compile_with};
+    use crate::CompileOptions;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Alpha": { "cats": [1], "headers": { "X-Shared": "shared-[0-9]+" }, "html": "alpha-unique-[a-z]+" },
+            "Beta": { "cats": [1], "headers": { "X-Shared": "shared-[0-9]+" }, "html": "common-widget-[0-9]+" },
+            "Gamma": { "cats": [1], "headers": { "X-Shared": "shared-[0-9]+\\;confidence:30" }, "html": "common-widget-[a-z]+" },
+            "Delta": { "cats": [1], "html": "common-widget" }
+        }
+    }"#;
+
+    /// 指定技术全部模式的置信度（按作用域排序）
+    fn confidences(lib: &CompiledRuleLibrary, tech: &str) -> Vec<(PruneScope, u8)> {
+        let mut out = Vec::new();
+        lib.tech_patterns[tech].for_each_pattern(|pat| out.push((pat.scope, pat.exec.confidence)));
+        out.sort_by_key(|(scope, _)| scope.as_str());
+        out
+    }
+
+    #[test]
+    fn test_collision_report() {
+        let lib = compile(RULES);
+        let report = lib.collision_report(3);
+        assert_eq!(report.min_techs, 3);
+
+        let identical: Vec<_> = report
+            .collisions
+            .iter()
+            .filter(|c| c.kind == CollisionKind::IdenticalPattern)
+            .collect();
+        assert_eq!(identical.len(), 1);
+        assert_eq!(identical[0].scope, PruneScope::Header);
+        assert_eq!(identical[0].key, "x-shared");
+        assert_eq!(identical[0].techs, ["Alpha", "Beta", "Gamma"]);
+
+        let shared = report
+            .collisions
+            .iter()
+            .find(|c| c.kind == CollisionKind::SharedEvidence && c.scope == PruneScope::Html)
+            .expect("shared html evidence");
+        assert_eq!(shared.techs, ["Beta", "Delta", "Gamma"]);
+        let alpha = "Alpha".to_string();
+        assert!(!report.collisions.iter().any(|c| c.scope == PruneScope::Html && c.techs.contains(&alpha)));
+
+        // 按涉及技术数降序
+        assert!(report.collisions.windows(2).all(|w| w[0].techs.len() >= w[1].techs.len()));
+        // 阈值高于共享技术数时无冲突；阈值下限为 2
+        assert!(lib.collision_report(4).is_empty());
+        assert_eq!(lib.collision_report(0).min_techs, 2);
+    }
+
+    #[test]
+    fn test_low_specificity_down_weight() {
+        let policy = LowSpecificityPolicy { min_techs: 3, confidence: 40 };
+        let lib = compile_with(RULES, &CompileOptions::new().low_specificity(policy));
+        // 共享模式降至策略置信度，已低于策略值的模式不上调
+        assert_eq!(confidences(&lib, "Alpha"), [(PruneScope::Header, 40), (PruneScope::Html, 100)]);
+        assert_eq!(confidences(&lib, "Beta"), [(PruneScope::Header, 40), (PruneScope::Html, 40)]);
+        assert_eq!(confidences(&lib, "Gamma"), [(PruneScope::Header, 30), (PruneScope::Html, 40)]);
+
+        // 默认不降权；已编译规则库可事后应用
+        let mut lib = compile(RULES);
+        assert_eq!(confidences(&lib, "Beta"), [(PruneScope::Header, 100), (PruneScope::Html, 100)]);
+        assert!(lib.apply_low_specificity_policy(&policy) > 0);
+        assert_eq!(confidences(&lib, "Beta"), [(PruneScope::Header, 40), (PruneScope::Html, 40)]);
+        assert_eq!(lib.apply_low_specificity_policy(&policy), 0);
+    }
+}
@@ -0,0 +1,129 @@
+//! 引擎与已编译规则库的兼容性
+//! 预编译规则包/内置规则库由某个引擎版本编译后序列化分发，旧版本引擎加载新版本引擎编译的规则库时，
+//! 新增字段会被静默忽略、字段语义变化会表现为反序列化失败或检测结果漂移；编译期记录引擎要求，加载期显式校验：
+//! 1. built_with：编译该规则库的引擎版本
+//! 2. min_engine_version：加载所需的最低引擎版本（同一 minor 版本内的引擎保证可加载）
+//! 3. format_version：已编译规则库格式版本（字段语义不兼容变更时递增）
+//!
+//! 未记录引擎要求的规则库（早于该功能的规则包）视为兼容
+
+use serde::{Deserialize, Serialize};
+
+use super::CompiledRuleLibrary;
+use crate::error::{CoreError, CoreResult};
+
+/// 当前引擎版本
+pub const ENGINE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// 当前已编译规则库格式版本
+pub const COMPILED_FORMAT_VERSION: u32 = 1;
+
+/// 已编译规则库的引擎要求
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineRequirements {
+    /// 编译该规则库的引擎版本
+    pub built_with: String,
+    /// 加载所需的最低引擎版本
+    pub min_engine_version: String,
+    /// 已编译规则库格式版本
+    pub format_version: u32,
+}
+
+impl EngineRequirements {
+    /// 当前引擎编译产物的引擎要求
+    pub fn current() -> Self {
+        let (major, minor, _) = parse_version(ENGINE_VERSION).unwrap_or_default();
+        Self {
+            built_with: ENGINE_VERSION.to_string(),
+            min_engine_version: format!("{}.{}.0", major, minor),
+            format_version: COMPILED_FORMAT_VERSION,
+        }
+    }
+
+    /// 是否未记录引擎要求（早于该功能的规则库）
+    pub fn is_unknown(&self) -> bool {
+        self.min_engine_version.is_empty() && self.format_version == 0
+    }
+
+    /// 校验当前引擎能否加载
+    /// 返回：兼容 | IncompatibleEngine 错误
+    pub fn check(&self) -> CoreResult<()> {
+        self.check_against(ENGINE_VERSION, COMPILED_FORMAT_VERSION)
+    }
+
+    /// 校验指定引擎能否加载
+    /// 参数：
+    /// - engine_version: 引擎版本
+    /// - format_version: 引擎支持的规则库格式版本
+    ///
+    /// 返回：兼容 | IncompatibleEngine 错误
+    pub fn check_against(&self, engine_version: &str, format_version: u32) -> CoreResult<()> {
+        if self.is_unknown() {
+            return Ok(());
+        }
+        if self.format_version > format_version {
+            return Err(CoreError::IncompatibleEngine(format!(
+                "rule library format v{} (built with engine {}) is newer than supported format v{} of engine {}",
+                self.format_version, self.built_with, format_version, engine_version
+            )));
+        }
+        let required = parse_version(&self.min_engine_version).ok_or_else(|| {
+            CoreError::IncompatibleEngine(format!(
+                "invalid minimum engine version '{}' recorded in rule library",
+                self.min_engine_version
+            ))
+        })?;
+        let current = parse_version(engine_version).unwrap_or_default();
+        if current < required {
+            return Err(CoreError::IncompatibleEngine(format!(
+                "rule library built with engine {} requires engine >= {}, current engine is {}",
+                self.built_with, self.min_engine_version, engine_version
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// 解析 `major.minor.patch` 版本号（忽略预发布/构建后缀）
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.trim().split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().unwrap_or(Some(0))?;
+    let patch = parts.next().unwrap_or(Some(0))?;
+    Some((major, minor, patch))
+}
+
+impl CompiledRuleLibrary {
+    /// 规则库记录的引擎要求（编译期写入）
+    pub fn engine_requirements(&self) -> &EngineRequirements {
+        &self.engine
+    }
+
+    /// 校验当前引擎能否加载该规则库
+    /// 返回：兼容 | IncompatibleEngine 错误
+    pub fn verify_compatibility(&self) -> CoreResult<()> {
+        self.engine.check()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_requirements() {
+        let current = EngineRequirements::current();
+        assert!(current.check().is_ok());
+        assert!(EngineRequirements::default().check_against("0.0.1", 0).is_ok());
+
+        let newer = EngineRequirements {
+            built_with: "9.1.0".to_string(),
+            min_engine_version: "9.1.0".to_string(),
+            format_version: 1,
+        };
+        assert!(newer.check_against("9.1.3-beta", 1).is_ok());
+        assert!(matches!(newer.check_against("9.0.7", 1), Err(CoreError::IncompatibleEngine(_))));
+        assert!(matches!(newer.check_against("9.1.0", 0), Err(CoreError::IncompatibleEngine(_))));
+    }
+}
@@ -1,9 +1,10 @@
 use crate::{
-    Matcher, indexer::{MatcherSpec, enums::MatchGate}, pruner::{min_evidence_checker, scope_pruner}, scope_pruner::PruneScope
+    Matcher, indexer::{LiteralHits, MatcherSpec, enums::MatchGate}, pruner::{min_evidence_checker, scope_pruner}, scope_pruner::PruneScope
 };
 use once_cell::sync::OnceCell;
 use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// 可执行匹配模式（核心执行单元）
 /// 职责：封装「如何匹配」的完整逻辑，包含匹配执行体、准入网关、权重和版本模板
@@ -23,6 +24,9 @@ pub struct ExecutablePattern {
     pub confidence: u8,
     /// 版本提取模板（可选）
     pub version_template: Option<String>,
+    /// 版本来源 Header（可选，仅 Header 规则生效）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_header: Option<String>,
 }
 
 impl ExecutablePattern {
@@ -44,9 +48,23 @@ pub struct CompiledPattern {
     pub index_key: String,
     /// 可执行匹配核心
     pub exec: ExecutablePattern,
+    /// 模式来源（provenance 特性开启时记录）
+    #[cfg(feature = "provenance")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<crate::core::PatternSource>,
 }
 
 impl CompiledPattern {
+    /// 规则描述（匹配器描述，provenance 特性开启时附带来源位置）
+    pub fn describe_rule(&self) -> String {
+        let desc = self.exec.get_matcher().describe();
+        #[cfg(feature = "provenance")]
+        if let Some(source) = &self.source {
+            return format!("{} @ {}", desc, source);
+        }
+        desc
+    }
+
     /// 执行匹配（自动激活懒加载Matcher）
     /// 参数：input - 待匹配的字符串
     /// 返回：匹配结果（bool）
@@ -55,6 +73,14 @@ impl CompiledPattern {
         self.exec.get_matcher().matches(input)
     }
 
+    /// 按字节执行匹配（输入非合法 UTF-8 时使用，见 Matcher::matches_bytes）
+    /// 参数：input - 待匹配的字节
+    /// 返回：匹配结果（bool）
+    #[inline(always)]
+    pub fn matches_bytes(&self, input: &[u8]) -> bool {
+        self.exec.get_matcher().matches_bytes(input)
+    }
+
     /// 剪枝过滤（纯业务逻辑，无日志，极致性能）
     /// 优先级：全局黑名单剪枝 > MatchGate统一剪枝
     /// 参数：
@@ -63,10 +89,45 @@ impl CompiledPattern {
     /// 返回：是否通过剪枝（true=继续匹配，false=直接过滤）
     #[inline(always)]
     pub fn prune_check(&self, input: &str, input_tokens: &FxHashSet<String>) -> bool {
+        self.prune_check_with_hits(input, input_tokens, None)
+    }
+
+    /// 剪枝过滤（使用输入的字面量扫描结果，见 CompiledRuleLibrary::literal_hits）
+    /// 参数：
+    /// - input: 待匹配字符串
+    /// - input_tokens: 输入令牌集合
+    /// - hits: 同一输入的字面量扫描结果（None 时逐模式查找）
+    ///
+    /// 返回：是否通过剪枝
+    #[inline(always)]
+    pub fn prune_check_with_hits(
+        &self,
+        input: &str,
+        input_tokens: &FxHashSet<String>,
+        hits: Option<&LiteralHits<'_>>,
+    ) -> bool {
         // 优先级1: 全局黑名单剪枝
         scope_pruner::struct_prune(self.scope, input, Some(&self.index_key))
         // 优先级2: MatchGate统一剪枝（收敛min_evidence + prune_strategy）
-        && self.exec.match_gate.check(input, input_tokens)
+        && self.exec.match_gate.check_with_hits(input, input_tokens, hits)
+    }
+
+    /// 剪枝过滤（非合法 UTF-8 的字节输入）
+    /// 结构化黑名单仅识别文本（构建产物路径等），字节输入跳过黑名单，仅执行 MatchGate 剪枝
+    /// 参数：
+    /// - input: 待匹配字节
+    /// - input_tokens: 输入令牌集合
+    /// - hits: 同一输入的字面量扫描结果（None 时逐模式查找）
+    ///
+    /// 返回：是否通过剪枝
+    #[inline(always)]
+    pub fn prune_check_bytes(
+        &self,
+        input: &[u8],
+        input_tokens: &FxHashSet<String>,
+        hits: Option<&LiteralHits<'_>>,
+    ) -> bool {
+        self.exec.match_gate.check_bytes_with_hits(input, input_tokens, hits)
     }
 
     /// 剪枝 + 匹配 核心方法（高性能）
@@ -192,8 +253,154 @@ pub struct CompiledTechRule {
     pub header_patterns: Option<FxHashMap<String, Vec<CompiledPattern>>>,
     /// Cookie匹配模式映射（Key=Cookie名称，Value=匹配模式列表）
     pub cookie_patterns: Option<FxHashMap<String, Vec<CompiledPattern>>>,
+    /// JS匹配模式映射（Key=全局变量属性链（小写），Value=匹配模式列表）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub js_patterns: Option<FxHashMap<String, Vec<CompiledPattern>>>,
+    /// DOM匹配模式映射（Key=选择器 + 检查目标，见 DomKey；Value=匹配模式列表）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dom_patterns: Option<FxHashMap<String, Vec<CompiledPattern>>>,
+    /// TLS证书颁发者匹配模式列表（可选）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cert_issuer_patterns: Option<Vec<CompiledPattern>>,
+    /// DNS匹配模式映射（Key=记录类型（小写，如 mx/txt），Value=匹配模式列表）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dns_patterns: Option<FxHashMap<String, Vec<CompiledPattern>>>,
+    /// Favicon mmh3哈希匹配模式列表（可选，整串匹配）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub favicon_hash_patterns: Option<Vec<CompiledPattern>>,
+    /// 页面标题匹配模式列表（可选）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_patterns: Option<Vec<CompiledPattern>>,
+    /// HTML 注释匹配模式列表（可选）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment_patterns: Option<Vec<CompiledPattern>>,
+    /// And 条件作用域（作用域内全部模式命中才记录该技术，其余作用域按 Or 执行）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub and_scopes: Vec<PruneScope>,
     /// 所属分类ID列表
     pub category_ids: Vec<u32>,
     /// 推导技术列表（匹配该技术后可推导的其他技术）
     pub implies: Vec<String>,
-}
\ No newline at end of file
+    /// 互斥技术列表（检出该技术时从结果中移除的技术）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub excludes: Vec<String>,
+    /// 依赖技术列表（任一依赖技术检出时该技术才保留）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+    /// 依赖分类ID列表（任一该分类技术检出时该技术才保留）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires_category: Vec<u32>,
+    /// 所属规则源名称（同一规则源的技术共享同一实例），未记录则为None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<Arc<str>>,
+    /// 按（作用域, Key）分组的正则集合缓存（懒加载，不序列化）
+    #[serde(skip)]
+    pub regex_sets: super::RegexSetCache,
+}
+
+impl CompiledTechRule {
+    /// 遍历该技术的全部编译模式（只读）
+    pub fn for_each_pattern(&self, mut f: impl FnMut(&CompiledPattern)) {
+        for list in [
+            &self.url_patterns,
+            &self.html_patterns,
+            &self.script_patterns,
+            &self.cert_issuer_patterns,
+            &self.favicon_hash_patterns,
+            &self.title_patterns,
+            &self.comment_patterns,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            list.iter().for_each(&mut f);
+        }
+        for map in [
+            &self.meta_patterns,
+            &self.header_patterns,
+            &self.cookie_patterns,
+            &self.js_patterns,
+            &self.dom_patterns,
+            &self.dns_patterns,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            map.values().flatten().for_each(&mut f);
+        }
+    }
+
+    /// 遍历该技术的全部编译模式（可变，用于编译后调整；同时重置正则集合缓存）
+    pub fn for_each_pattern_mut(&mut self, mut f: impl FnMut(&mut CompiledPattern)) {
+        self.reset_regex_sets();
+        for list in [
+            &mut self.url_patterns,
+            &mut self.html_patterns,
+            &mut self.script_patterns,
+            &mut self.cert_issuer_patterns,
+            &mut self.favicon_hash_patterns,
+            &mut self.title_patterns,
+            &mut self.comment_patterns,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            list.iter_mut().for_each(&mut f);
+        }
+        for map in [
+            &mut self.meta_patterns,
+            &mut self.header_patterns,
+            &mut self.cookie_patterns,
+            &mut self.js_patterns,
+            &mut self.dom_patterns,
+            &mut self.dns_patterns,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            map.values_mut().flatten().for_each(&mut f);
+        }
+    }
+
+    /// 按判定函数保留该技术的编译模式（同时重置正则集合缓存）
+    /// 参数：keep - 返回 false 的模式被移除
+    ///
+    /// 返回：移除的模式数量
+    pub fn retain_patterns(&mut self, mut keep: impl FnMut(&CompiledPattern) -> bool) -> usize {
+        self.reset_regex_sets();
+        let mut removed = 0;
+        let mut retain = |list: &mut Vec<CompiledPattern>| {
+            let before = list.len();
+            list.retain(&mut keep);
+            removed += before - list.len();
+        };
+        for list in [
+            &mut self.url_patterns,
+            &mut self.html_patterns,
+            &mut self.script_patterns,
+            &mut self.cert_issuer_patterns,
+            &mut self.favicon_hash_patterns,
+            &mut self.title_patterns,
+            &mut self.comment_patterns,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            retain(list);
+        }
+        for map in [
+            &mut self.meta_patterns,
+            &mut self.header_patterns,
+            &mut self.cookie_patterns,
+            &mut self.js_patterns,
+            &mut self.dom_patterns,
+            &mut self.dns_patterns,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            map.values_mut().for_each(&mut retain);
+        }
+        removed
+    }
+}
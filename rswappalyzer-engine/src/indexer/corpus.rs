@@ -0,0 +1,215 @@
+//! 模式测试语料生成
+//! 为规则库中每个编译后的模式合成一条最小匹配输入（正则按语法树取最短路径，字面量/证据 token 原样保留），
+//! 对语料跑一遍完整检测即可端到端验证：经清洗/修复/编译/剪枝网关后，每个模式仍然可达且可匹配
+//! 1. 正则：交替取首个分支、重复取最小次数、字符类优先取小写字母/数字
+//! 2. 合成后自检：输入无法被模式自身匹配时视为无法合成（input 为 None），与"被网关剪死"区分
+
+use regex_syntax::hir::{Class, Hir, HirKind};
+use serde::{Deserialize, Serialize};
+
+use crate::scope_pruner::PruneScope;
+
+use super::{CompiledPattern, CompiledRuleLibrary, MatcherSpec};
+
+/// Exists 类模式的占位输入（任意非空值即可命中）
+const EXISTS_PLACEHOLDER: &str = "1";
+
+/// 单条语料
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorpusEntry {
+    /// 技术名
+    pub tech: String,
+    /// 作用域
+    pub scope: PruneScope,
+    /// 索引Key（Header/Cookie/Meta名称，内容型作用域为空）
+    pub key: String,
+    /// 模式描述
+    pub pattern: String,
+    /// 合成的最小匹配输入（None 表示无法合成：自定义匹配器/合成结果未通过自检）
+    pub input: Option<String>,
+}
+
+/// 模式测试语料
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatternCorpus {
+    /// 语料条目（按技术名/作用域/Key/模式排序，保证输出稳定）
+    pub entries: Vec<CorpusEntry>,
+}
+
+impl PatternCorpus {
+    /// 为规则库中的全部模式生成语料
+    pub fn from_library(lib: &CompiledRuleLibrary) -> Self {
+        let mut entries = Vec::new();
+        for (tech_name, tech) in &lib.tech_patterns {
+            tech.for_each_pattern(|pat| {
+                entries.push(CorpusEntry {
+                    tech: tech_name.clone(),
+                    scope: pat.scope,
+                    key: pat.index_key.clone(),
+                    pattern: pat.exec.get_matcher().describe(),
+                    input: synthesize_input(pat),
+                });
+            });
+        }
+        // 主机名后缀规则：仅匹配子域的后缀以 `www.` 子域作为输入
+        lib.hostname_index.for_each_rule(|suffix, rule| {
+            let (pattern, input) = if rule.include_apex {
+                (suffix.to_string(), suffix.to_string())
+            } else {
+                (format!("*.{}", suffix), format!("www.{}", suffix))
+            };
+            entries.push(CorpusEntry {
+                tech: rule.tech.clone(),
+                scope: PruneScope::Hostname,
+                key: String::new(),
+                pattern,
+                input: Some(input),
+            });
+        });
+        entries.sort_by(|a, b| {
+            (&a.tech, a.scope.as_str(), &a.key, &a.pattern)
+                .cmp(&(&b.tech, b.scope.as_str(), &b.key, &b.pattern))
+        });
+        entries.dedup();
+        Self { entries }
+    }
+
+    /// 可合成输入的条目
+    pub fn synthesized(&self) -> impl Iterator<Item = (&CorpusEntry, &str)> {
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.input.as_deref().map(|input| (entry, input)))
+    }
+
+    /// 无法合成输入的条目
+    pub fn unsynthesizable(&self) -> impl Iterator<Item = &CorpusEntry> {
+        self.entries.iter().filter(|entry| entry.input.is_none())
+    }
+
+    /// 序列化为 JSON 语料文件内容
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+/// 为单个模式合成最小匹配输入（合成后以模式自身校验）
+pub fn synthesize_input(pat: &CompiledPattern) -> Option<String> {
+    let input = match &pat.exec.matcher {
+        MatcherSpec::Contains(literal) => literal.clone(),
+        MatcherSpec::Exists => EXISTS_PLACEHOLDER.to_string(),
+        MatcherSpec::Regex {
+            pattern,
+            case_insensitive,
+        } => {
+            let hir = regex_syntax::ParserBuilder::new()
+                .case_insensitive(*case_insensitive)
+                .build()
+                .parse(pattern)
+                .ok()?;
+            let mut out = String::new();
+            write_minimal(&hir, &mut out)?;
+            out
+        }
+        MatcherSpec::Custom { .. } => return None,
+    };
+    pat.matches(&input).then_some(input)
+}
+
+/// 按语法树写出最短匹配串
+/// 返回：None 表示语法树含无法合成的节点（如空字符类）
+fn write_minimal(hir: &Hir, out: &mut String) -> Option<()> {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => {}
+        HirKind::Literal(literal) => out.push_str(&String::from_utf8_lossy(&literal.0)),
+        HirKind::Class(class) => out.push(pick_class_char(class)?),
+        HirKind::Repetition(rep) => {
+            for _ in 0..rep.min {
+                write_minimal(&rep.sub, out)?;
+            }
+        }
+        HirKind::Capture(capture) => write_minimal(&capture.sub, out)?,
+        HirKind::Concat(subs) => {
+            for sub in subs {
+                write_minimal(sub, out)?;
+            }
+        }
+        // 交替优先取首个可合成的分支
+        HirKind::Alternation(subs) => {
+            let start = out.len();
+            let ok = subs.iter().any(|sub| {
+                out.truncate(start);
+                write_minimal(sub, out).is_some()
+            });
+            if !ok {
+                return None;
+            }
+        }
+    }
+    Some(())
+}
+
+/// 从字符类中挑选一个字符：宽泛类（`.`/`[^>]` 等）取空格，避免与相邻字面量粘连成新 token；
+/// 其余按 小写字母 > 数字 > 其它可见ASCII > 类中首个字符
+fn pick_class_char(class: &Class) -> Option<char> {
+    let contains = |c: char| match class {
+        Class::Unicode(cls) => cls.ranges().iter().any(|r| r.start() <= c && c <= r.end()),
+        Class::Bytes(cls) => u8::try_from(c).is_ok_and(|b| cls.ranges().iter().any(|r| r.start() <= b && b <= r.end())),
+    };
+    if [' ', 'a', '0', '/'].into_iter().all(contains) {
+        return Some(' ');
+    }
+    let preferred = ('a'..='z').chain('0'..='9').chain('!'..='~');
+    if let Some(c) = preferred.into_iter().find(|c| contains(*c)) {
+        return Some(c);
+    }
+    match class {
+        Class::Unicode(cls) => cls.ranges().first().map(|r| r.start()),
+        Class::Bytes(cls) => cls.ranges().first().map(|r| char::from(r.start())),
+    }
+}
+
+impl CompiledRuleLibrary {
+    /// 生成模式测试语料（每个模式一条最小匹配输入）
+    pub fn pattern_corpus(&self) -> PatternCorpus {
+        PatternCorpus::from_library(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexer::{ExecutablePattern, MatchGate};
+
+    fn regex_pattern(pattern: &str) -> CompiledPattern {
+        CompiledPattern {
+            scope: PruneScope::Html,
+            index_key: String::new(),
+            exec: ExecutablePattern {
+                matcher: MatcherSpec::Regex {
+                    pattern: pattern.to_string(),
+                    case_insensitive: true,
+                },
+                matcher_cache: Default::default(),
+                match_gate: MatchGate::Open,
+                confidence: 100,
+                version_template: None,
+                version_header: None,
+            },
+            #[cfg(feature = "provenance")]
+            source: None,
+        }
+    }
+
+    #[test]
+    fn test_synthesize_minimal_inputs() {
+        let cases = [
+            (r"^wordpress/([\d.]+)$", "wordpress/0"),
+            (r"<script[^>]+src=.(?:foo|bar)\.js", "<script src= foo.js"),
+            (r"jquery(?:-|\.)?min\.js\?ver=\d+", "jquerymin.js?ver=0"),
+            (r"Powered by (?i)Drupal", "powered by drupal"),
+        ];
+        for (pattern, expected) in cases {
+            assert_eq!(synthesize_input(&regex_pattern(pattern)).as_deref(), Some(expected), "{}", pattern);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! 自定义匹配器扩展点
+//! 自定义规则包可声明内置 Contains/Regex/Exists 之外的匹配类型（如 `base64-prefix`、`md5-equals`、`jsonpath`），
+//! 规则中以 `\\;matcher:<kind>` 标记，编译时按匹配类型名查询注册表，由宿主程序提供的工厂构建匹配器，
+//! 无需修改引擎源码
+
+use std::fmt;
+use std::sync::Arc;
+
+use rustc_hash::FxHashMap;
+
+/// 自定义匹配器（运行期执行体）
+pub trait CustomMatcher: Send + Sync {
+    /// 执行匹配
+    /// 参数：input - 待匹配的字符串
+    /// 返回：匹配结果（bool）
+    fn matches(&self, input: &str) -> bool;
+}
+
+/// 闭包可直接作为自定义匹配器
+impl<F> CustomMatcher for F
+where
+    F: Fn(&str) -> bool + Send + Sync,
+{
+    #[inline(always)]
+    fn matches(&self, input: &str) -> bool {
+        self(input)
+    }
+}
+
+impl fmt::Debug for dyn CustomMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CustomMatcher")
+    }
+}
+
+/// 自定义匹配器工厂：模式字符串 -> 匹配器 | 错误描述（编译期调用）
+pub type MatcherFactory =
+    Arc<dyn Fn(&str) -> Result<Arc<dyn CustomMatcher>, String> + Send + Sync>;
+
+/// 自定义匹配类型注册表（匹配类型名 -> 匹配器工厂）
+#[derive(Clone, Default)]
+pub struct MatcherRegistry {
+    factories: FxHashMap<String, MatcherFactory>,
+}
+
+impl MatcherRegistry {
+    /// 创建空注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册匹配类型（同名覆盖）
+    /// 参数：
+    /// - kind: 匹配类型名（规则中 `\\;matcher:<kind>` 的取值）
+    /// - factory: 匹配器工厂，模式非法时返回错误描述（该模式在编译时被丢弃）
+    pub fn register<F, M>(&mut self, kind: impl Into<String>, factory: F)
+    where
+        F: Fn(&str) -> Result<M, String> + Send + Sync + 'static,
+        M: CustomMatcher + 'static,
+    {
+        let factory: MatcherFactory = Arc::new(move |pattern: &str| {
+            factory(pattern).map(|matcher| Arc::new(matcher) as Arc<dyn CustomMatcher>)
+        });
+        self.factories.insert(kind.into(), factory);
+    }
+
+    /// 注册匹配类型（链式）
+    pub fn with<F, M>(mut self, kind: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn(&str) -> Result<M, String> + Send + Sync + 'static,
+        M: CustomMatcher + 'static,
+    {
+        self.register(kind, factory);
+        self
+    }
+
+    /// 是否已注册指定匹配类型
+    #[inline]
+    pub fn contains(&self, kind: &str) -> bool {
+        self.factories.contains_key(kind)
+    }
+
+    /// 是否为空注册表
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.factories.is_empty()
+    }
+
+    /// 已注册的匹配类型名（按名称排序）
+    pub fn kinds(&self) -> Vec<&str> {
+        let mut kinds: Vec<&str> = self.factories.keys().map(String::as_str).collect();
+        kinds.sort_unstable();
+        kinds
+    }
+
+    /// 构建匹配器
+    /// 参数：
+    /// - kind: 匹配类型名
+    /// - pattern: 模式字符串
+    ///
+    /// 返回：匹配器 | 错误描述（未注册的匹配类型/工厂构建失败）
+    pub fn build(&self, kind: &str, pattern: &str) -> Result<Arc<dyn CustomMatcher>, String> {
+        let factory = self
+            .factories
+            .get(kind)
+            .ok_or_else(|| format!("unregistered matcher kind: {}", kind))?;
+        factory(pattern)
+    }
+}
+
+impl fmt::Debug for MatcherRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MatcherRegistry")
+            .field("kinds", &self.kinds())
+            .finish()
+    }
+}
+
+/// 注册表按已注册的匹配类型名比较（工厂闭包不可比较）
+impl PartialEq for MatcherRegistry {
+    fn eq(&self, other: &Self) -> bool {
+        self.kinds() == other.kinds()
+    }
+}
+
+impl Eq for MatcherRegistry {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::compile_with;
+    use crate::{CompileOptions, Matcher};
+
+    const RULES: &str = r#"{"technologies": {
+        "Acme": { "html": "acme-\\;matcher:suffix" },
+        "Widget": { "html": "widget" }
+    }}"#;
+
+    fn suffix_registry() -> MatcherRegistry {
+        MatcherRegistry::new().with("suffix", |pattern: &str| {
+            let suffix = pattern.to_string();
+            Ok(move |input: &str| input.trim_end().ends_with(suffix.as_str()))
+        })
+    }
+
+    fn acme_html_patterns(options: &CompileOptions) -> usize {
+        let library = compile_with(RULES, options);
+        library
+            .tech_patterns
+            .get("Acme")
+            .and_then(|rule| rule.html_patterns.as_ref())
+            .map_or(0, Vec::len)
+    }
+
+    #[test]
+    fn test_registry_kinds() {
+        let registry = suffix_registry()
+            .with("never", |_: &str| Ok(|_: &str| false))
+            .with("suffix", |_: &str| Err::<fn(&str) -> bool, _>("replaced".to_string()));
+        assert!(!registry.is_empty());
+        assert!(registry.contains("suffix"));
+        assert_eq!(registry.kinds(), vec!["never", "suffix"]);
+        // 同名注册覆盖先前的工厂
+        assert_eq!(registry.build("suffix", "x").unwrap_err(), "replaced");
+        assert_eq!(
+            registry.build("md5", "x").unwrap_err(),
+            "unregistered matcher kind: md5"
+        );
+        // 注册表按匹配类型名比较
+        let same_kinds = MatcherRegistry::new()
+            .with("suffix", |_: &str| Ok(|_: &str| true))
+            .with("never", |_: &str| Ok(|_: &str| true));
+        assert_eq!(registry, same_kinds);
+    }
+
+    #[test]
+    fn test_custom_matcher_matches() {
+        let matcher = Matcher::custom(&suffix_registry(), "suffix", "acme-").unwrap();
+        assert!(matcher.matches("built by acme-\n"));
+        assert!(!matcher.matches("acme-widget"));
+        assert!(Matcher::custom(&MatcherRegistry::new(), "suffix", "acme-").is_err());
+    }
+
+    #[test]
+    fn test_compile_with_registry() {
+        let options = CompileOptions::default().matchers(suffix_registry());
+        let library = compile_with(RULES, &options);
+        let pattern = &library.tech_patterns["Acme"].html_patterns.as_ref().unwrap()[0];
+        assert!(pattern.exec.get_matcher().matches("powered by acme-"));
+        assert!(!pattern.exec.get_matcher().matches("powered by acme-widget"));
+    }
+
+    #[test]
+    fn test_unregistered_kind_dropped() {
+        assert_eq!(acme_html_patterns(&CompileOptions::default()), 0);
+        assert_eq!(acme_html_patterns(&CompileOptions::default().matchers(suffix_registry())), 1);
+    }
+}
@@ -0,0 +1,149 @@
+//! 等价模式去重
+//! 合并多个规则源后，同一技术同一作用域（同一 Key）下常出现完全相同的模式，每条都会被重复求值。
+//! 编译期按（匹配器 + 准入网关 + 版本模板）判定等价，仅保留首条：
+//! 1. 置信度取等价模式中的最大值（任一等价模式命中时其余必然同时命中）
+//! 2. 首条缺失版本来源 Header 时继承后续等价模式的取值
+//! 3. 不同技术之间的相同模式不受影响（见 PatternCollisionReport）
+
+use std::collections::BTreeMap;
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use super::{CompiledPattern, CompiledRuleLibrary, MatcherSpec};
+
+/// 去重统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PatternDedupStats {
+    /// 移除的重复模式总数
+    pub removed: usize,
+    /// 按作用域统计的移除数量（作用域名 -> 数量）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub by_scope: BTreeMap<String, usize>,
+}
+
+impl PatternDedupStats {
+    /// 是否未移除任何模式
+    pub fn is_empty(&self) -> bool {
+        self.removed == 0
+    }
+
+    fn merge(&mut self, other: &PatternDedupStats) {
+        self.removed += other.removed;
+        for (scope, count) in &other.by_scope {
+            *self.by_scope.entry(scope.clone()).or_default() += count;
+        }
+    }
+}
+
+impl CompiledRuleLibrary {
+    /// 移除各技术内的等价重复模式（结果累加到 dedup_stats）
+    /// 重复模式与首条的准入证据一致，证据索引无需重建
+    ///
+    /// 返回：本次去重统计
+    pub fn dedup_patterns(&mut self) -> PatternDedupStats {
+        let mut stats = PatternDedupStats::default();
+        for tech in self.tech_patterns.values_mut() {
+            tech.reset_regex_sets();
+            for list in [
+                &mut tech.url_patterns,
+                &mut tech.html_patterns,
+                &mut tech.script_patterns,
+                &mut tech.cert_issuer_patterns,
+                &mut tech.favicon_hash_patterns,
+                &mut tech.title_patterns,
+                &mut tech.comment_patterns,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                dedup_list(list, &mut stats);
+            }
+            for map in [
+                &mut tech.meta_patterns,
+                &mut tech.header_patterns,
+                &mut tech.cookie_patterns,
+                &mut tech.js_patterns,
+                &mut tech.dom_patterns,
+                &mut tech.dns_patterns,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                map.values_mut().for_each(|list| dedup_list(list, &mut stats));
+            }
+        }
+        self.dedup_stats.merge(&stats);
+        stats
+    }
+}
+
+/// 单个模式列表去重（保持原有顺序）
+fn dedup_list(list: &mut Vec<CompiledPattern>, stats: &mut PatternDedupStats) {
+    if list.len() < 2 {
+        return;
+    }
+    // 匹配器 + 版本模板 -> 已保留模式下标（准入网关不可哈希，同键下逐一比较）
+    let mut seen: FxHashMap<(MatcherSpec, Option<String>), Vec<usize>> = FxHashMap::default();
+    let mut kept: Vec<CompiledPattern> = Vec::with_capacity(list.len());
+
+    for pat in list.drain(..) {
+        let key = (pat.exec.matcher.clone(), pat.exec.version_template.clone());
+        let candidates = seen.entry(key).or_default();
+        match candidates
+            .iter()
+            .find(|&&i| kept[i].exec.match_gate == pat.exec.match_gate)
+        {
+            Some(&i) => {
+                let first = &mut kept[i].exec;
+                first.confidence = first.confidence.max(pat.exec.confidence);
+                if first.version_header.is_none() {
+                    first.version_header = pat.exec.version_header;
+                }
+                stats.removed += 1;
+                *stats.by_scope.entry(pat.scope.as_str().to_string()).or_default() += 1;
+            }
+            None => {
+                candidates.push(kept.len());
+                kept.push(pat);
+            }
+        }
+    }
+    *list = kept;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indexer::{ExecutablePattern, MatchGate};
+    use crate::scope_pruner::PruneScope;
+
+    fn pattern(literal: &str, confidence: u8) -> CompiledPattern {
+        CompiledPattern {
+            scope: PruneScope::Html,
+            index_key: String::new(),
+            exec: ExecutablePattern {
+                matcher: MatcherSpec::Contains(literal.to_string()),
+                matcher_cache: Default::default(),
+                match_gate: MatchGate::Open,
+                confidence,
+                version_template: None,
+                version_header: None,
+            },
+            #[cfg(feature = "provenance")]
+            source: None,
+        }
+    }
+
+    #[test]
+    fn test_dedup_list() {
+        let mut list = vec![pattern("wp-content", 50), pattern("wp-json", 100), pattern("wp-content", 80)];
+        let mut stats = PatternDedupStats::default();
+        dedup_list(&mut list, &mut stats);
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].exec.confidence, 80);
+        assert_eq!(stats.removed, 1);
+        assert_eq!(stats.by_scope.get("html"), Some(&1));
+    }
+}
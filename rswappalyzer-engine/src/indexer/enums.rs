@@ -3,10 +3,11 @@ use std::sync::Arc;
 use rustc_hash::FxHashSet;
 use serde::{Deserialize, Serialize};
 
+use super::LiteralHits;
 use crate::Matcher;
 
 // 纯静态的匹配规则描述体
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum MatcherSpec {
     Contains(String),
     Exists,
@@ -14,6 +15,11 @@ pub enum MatcherSpec {
         pattern: String,
         case_insensitive: bool,
     },
+    /// 自定义匹配（反序列化后未绑定工厂，需重新编译规则才可执行）
+    Custom {
+        kind: String,
+        pattern: String,
+    },
 }
 
 // 运行时匹配器 转换方法
@@ -30,6 +36,11 @@ impl MatcherSpec {
                 pattern: Arc::new(pattern.clone()),
                 case_insensitive: *case_insensitive,
             },
+            MatcherSpec::Custom { kind, pattern } => Matcher::Custom {
+                kind: Arc::new(kind.clone()),
+                pattern: Arc::new(pattern.clone()),
+                inner: None,
+            },
         }
     }
 }
@@ -50,15 +61,50 @@ impl MatchGate {
     /// 运行期剪枝校验核心方法 - 内联优化，零开销，短路执行
     #[inline(always)]
     pub fn check(&self, input: &str, input_tokens: &FxHashSet<String>) -> bool {
+        self.check_with_hits(input, input_tokens, None)
+    }
+
+    /// 使用输入的字面量扫描结果执行剪枝校验（hits 须由同一输入扫描得到，None 时逐模式查找）
+    #[inline(always)]
+    pub fn check_with_hits(
+        &self,
+        input: &str,
+        input_tokens: &FxHashSet<String>,
+        hits: Option<&LiteralHits<'_>>,
+    ) -> bool {
         match self {
             MatchGate::Open => true,
-            MatchGate::RequireAll(set) => set.iter().all(|t| input_tokens.contains(t.as_str())),
-            MatchGate::RequireAnyLiteral(list) => {
+            MatchGate::RequireAll(set) => {
+                hits.is_none_or(|hits| hits.may_contain_all(set))
+                    && set.iter().all(|t| input_tokens.contains(t.as_str()))
+            }
+            MatchGate::RequireAnyLiteral(list) => match hits {
                 // Structural literals (non-atomic, non-tokenizable).
-                // Checked via raw substring search by design.
-                // Count is intentionally small (<=3).
-                list.iter().any(|substr| input.contains(substr))
+                // Served from the per-scope Aho-Corasick scan when available, raw substring search otherwise.
+                Some(hits) => hits.contains_any(input, list),
+                None => list.iter().any(|substr| input.contains(substr)),
+            },
+        }
+    }
+
+    /// 按字节执行剪枝校验（非合法 UTF-8 的输入，规则与 check_with_hits 一致）
+    #[inline(always)]
+    pub fn check_bytes_with_hits(
+        &self,
+        input: &[u8],
+        input_tokens: &FxHashSet<String>,
+        hits: Option<&LiteralHits<'_>>,
+    ) -> bool {
+        match self {
+            MatchGate::Open => true,
+            MatchGate::RequireAll(set) => {
+                hits.is_none_or(|hits| hits.may_contain_all(set))
+                    && set.iter().all(|t| input_tokens.contains(t.as_str()))
             }
+            MatchGate::RequireAnyLiteral(list) => match hits {
+                Some(hits) => hits.contains_any(input, list),
+                None => list.iter().any(|substr| memchr::memmem::find(input, substr.as_bytes()).is_some()),
+            },
         }
     }
 }
@@ -0,0 +1,177 @@
+//! 证据 token 导出/导入
+//! 供外部预过滤组件（eBPF/XDP、WAF 等）按作用域获取字面量证据 token，
+//! 在流量进入 rswappalyzer 之前做粗筛
+//!
+//! 紧凑文本格式（行式，便于非 Rust 组件解析）：
+//! ```text
+//! #rswappalyzer-evidence v1
+//! [url]
+//! wp-content
+//! [html]
+//! generator
+//! ```
+//! - 以 `#` 开头的行为注释/头部
+//! - `[scope]` 切换当前作用域
+//! - 其余非空行为当前作用域下的一个 token（已排序、去重）
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::{CoreError, CoreResult}, scope_pruner::PruneScope};
+
+use super::CompiledRuleLibrary;
+
+/// 紧凑格式头部标识
+pub const EVIDENCE_EXPORT_HEADER: &str = "#rswappalyzer-evidence v1";
+
+/// 按作用域导出的证据 token 集合
+/// BTree 结构保证导出结果有序、可复现（便于 diff / 版本对比）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvidenceTokenExport {
+    /// 作用域名 -> token 集合
+    pub scopes: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl EvidenceTokenExport {
+    /// 从编译后规则库导出全部作用域的证据 token
+    pub fn from_library(lib: &CompiledRuleLibrary) -> Self {
+        let mut scopes = BTreeMap::new();
+        for scope in PruneScope::ALL {
+            let tokens: BTreeSet<String> = lib.evidence_tokens(scope).map(str::to_string).collect();
+            if !tokens.is_empty() {
+                scopes.insert(scope.as_str().to_string(), tokens);
+            }
+        }
+        Self { scopes }
+    }
+
+    /// 获取指定作用域的 token 迭代器
+    pub fn tokens(&self, scope: PruneScope) -> impl Iterator<Item = &str> {
+        self.scopes
+            .get(scope.as_str())
+            .into_iter()
+            .flat_map(|set| set.iter().map(String::as_str))
+    }
+
+    /// token 总数
+    pub fn total_tokens(&self) -> usize {
+        self.scopes.values().map(BTreeSet::len).sum()
+    }
+
+    /// 序列化为紧凑文本格式
+    pub fn to_compact(&self) -> String {
+        let mut out = String::with_capacity(self.total_tokens() * 12 + 64);
+        out.push_str(EVIDENCE_EXPORT_HEADER);
+        out.push('\n');
+        for (scope, tokens) in &self.scopes {
+            out.push('[');
+            out.push_str(scope);
+            out.push_str("]\n");
+            for token in tokens {
+                out.push_str(token);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// 从紧凑文本格式解析
+    /// 错误：未知作用域名，或 token 出现在任何作用域声明之前
+    pub fn from_compact(input: &str) -> CoreResult<Self> {
+        let mut scopes: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut current: Option<&'static str> = None;
+
+        for (line_no, raw_line) in input.lines().enumerate() {
+            let line = raw_line.trim_end_matches('\r');
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let scope = PruneScope::from_name(name).ok_or_else(|| {
+                    CoreError::RuleParseError(format!(
+                        "evidence export line {}: unknown scope '{}'",
+                        line_no + 1,
+                        name
+                    ))
+                })?;
+                current = Some(scope.as_str());
+                scopes.entry(scope.as_str().to_string()).or_default();
+                continue;
+            }
+            let scope = current.ok_or_else(|| {
+                CoreError::RuleParseError(format!(
+                    "evidence export line {}: token before any [scope] section",
+                    line_no + 1
+                ))
+            })?;
+            scopes
+                .entry(scope.to_string())
+                .or_default()
+                .insert(line.to_string());
+        }
+
+        Ok(Self { scopes })
+    }
+
+    /// 序列化为 JSON
+    pub fn to_json(&self) -> CoreResult<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// 从 JSON 反序列化
+    pub fn from_json(input: &str) -> CoreResult<Self> {
+        Ok(serde_json::from_str(input)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::compile;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "WordPress": { "cats": [1], "html": "<link[^>]+wp-content", "url": "/wp-admin/" },
+            "Nginx": { "cats": [22], "headers": { "Server": "nginx" } }
+        }
+    }"#;
+
+    #[test]
+    fn test_export_from_library() {
+        let lib = compile(RULES);
+        let export = lib.export_evidence_tokens();
+        assert!(export.tokens(PruneScope::Html).any(|token| token == "wp-content"));
+        // 导出结果与规则库的作用域 token 一致，且有序
+        for scope in PruneScope::ALL {
+            let mut expected: Vec<_> = lib.evidence_tokens(scope).collect();
+            expected.sort_unstable();
+            assert_eq!(export.tokens(scope).collect::<Vec<_>>(), expected);
+        }
+        assert_eq!(export.total_tokens(), export.scopes.values().map(BTreeSet::len).sum::<usize>());
+    }
+
+    #[test]
+    fn test_compact_round_trip() {
+        let export = compile(RULES).export_evidence_tokens();
+        let compact = export.to_compact();
+        assert!(compact.starts_with(EVIDENCE_EXPORT_HEADER));
+        assert!(compact.contains("[html]\n"));
+        assert_eq!(EvidenceTokenExport::from_compact(&compact).unwrap(), export);
+        assert_eq!(EvidenceTokenExport::from_json(&export.to_json().unwrap()).unwrap(), export);
+
+        // 作用域名大小写不敏感，CRLF/空行/注释忽略，重复 token 去重
+        let parsed = EvidenceTokenExport::from_compact("# comment\r\n[HTML]\r\nb\r\n\r\na\r\nb\r\n[url]\r\n").unwrap();
+        assert_eq!(parsed.tokens(PruneScope::Html).collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(parsed.tokens(PruneScope::Url).count(), 0);
+        assert!(parsed.scopes.contains_key("url"));
+    }
+
+    #[test]
+    fn test_compact_errors() {
+        let err = EvidenceTokenExport::from_compact("[html]\na\n[body]\nb").unwrap_err();
+        assert!(err.to_string().contains("line 3: unknown scope 'body'"), "{}", err);
+        let err = EvidenceTokenExport::from_compact("#rswappalyzer-evidence v1\nwp-content").unwrap_err();
+        assert!(err.to_string().contains("line 2: token before any [scope] section"), "{}", err);
+    }
+}
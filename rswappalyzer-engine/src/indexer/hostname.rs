@@ -0,0 +1,276 @@
+//! 主机名后缀规则（Hostname 作用域）
+//! GitHub Pages / Netlify / Vercel / Heroku 等托管平台按主机名后缀（`*.github.io`）识别，
+//! 对每个 URL 跑全 URL 正则代价高且边界不精确；按标签反向构建后缀字典树，每个主机名仅需一次 O(标签数) 查找：
+//! 1. 规则扩展字段 `hostname`：`github.io` 匹配自身及全部子域，`*.github.io` 仅匹配子域
+//! 2. 编译期识别形如 `^https?://[^/]+\.github\.io` 的 URL 正则，转换为后缀规则并从 URL 正则中移除
+//!    （转换后按主机名标签边界匹配，不会再误命中 `x.github.io.evil.com` 这类主机）
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+/// 单条主机名后缀规则
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostnameRule {
+    /// 技术名称
+    pub tech: String,
+    /// 命中置信度
+    pub confidence: u8,
+    /// 是否匹配后缀自身（false 时仅匹配子域）
+    pub include_apex: bool,
+}
+
+/// 后缀字典树节点（子节点按主机名标签索引）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct HostnameNode {
+    #[serde(default, skip_serializing_if = "FxHashMap::is_empty")]
+    children: FxHashMap<String, usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rules: Vec<HostnameRule>,
+}
+
+/// 主机名后缀索引（按标签从右向左的字典树，nodes[0] 为根节点）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostnameIndex {
+    nodes: Vec<HostnameNode>,
+}
+
+impl HostnameIndex {
+    /// 是否无任何规则
+    pub fn is_empty(&self) -> bool {
+        self.nodes.iter().all(|node| node.rules.is_empty())
+    }
+
+    /// 规则总数
+    pub fn len(&self) -> usize {
+        self.nodes.iter().map(|node| node.rules.len()).sum()
+    }
+
+    /// 插入后缀规则（同一后缀下同技术同匹配方式的规则仅保留最高置信度）
+    /// 参数：
+    /// - suffix: 已归一化的主机名后缀（小写，不含通配符）
+    /// - rule: 后缀规则
+    pub fn insert(&mut self, suffix: &str, rule: HostnameRule) {
+        if self.nodes.is_empty() {
+            self.nodes.push(HostnameNode::default());
+        }
+        let mut node = 0;
+        for label in suffix.rsplit('.') {
+            node = match self.nodes[node].children.get(label) {
+                Some(&child) => child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes.push(HostnameNode::default());
+                    self.nodes[node].children.insert(label.to_string(), child);
+                    child
+                }
+            };
+        }
+        let rules = &mut self.nodes[node].rules;
+        match rules
+            .iter_mut()
+            .find(|r| r.tech == rule.tech && r.include_apex == rule.include_apex)
+        {
+            Some(existing) => existing.confidence = existing.confidence.max(rule.confidence),
+            None => rules.push(rule),
+        }
+    }
+
+    /// 查找主机名命中的全部后缀规则
+    /// 参数：host - 主机名（大小写不敏感，可带结尾的点）
+    ///
+    /// 返回：命中的规则（按后缀由短到长）
+    pub fn lookup(&self, host: &str) -> Vec<&HostnameRule> {
+        let mut hits = Vec::new();
+        if self.nodes.is_empty() {
+            return hits;
+        }
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        let labels: Vec<&str> = host.rsplit('.').collect();
+        let mut node = 0;
+        for (consumed, label) in labels.iter().enumerate() {
+            let Some(&child) = self.nodes[node].children.get(*label) else {
+                break;
+            };
+            node = child;
+            let is_apex = consumed + 1 == labels.len();
+            hits.extend(self.nodes[node].rules.iter().filter(|r| r.include_apex || !is_apex));
+        }
+        hits
+    }
+
+    /// 按技术名称保留规则（节点结构保持不变）
+    pub fn retain_techs(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        for node in &mut self.nodes {
+            node.rules.retain(|rule| keep(&rule.tech));
+        }
+    }
+
+    /// 遍历全部规则（后缀, 规则）
+    pub fn for_each_rule(&self, mut f: impl FnMut(&str, &HostnameRule)) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let mut stack = vec![(0usize, Vec::<&str>::new())];
+        while let Some((node, labels)) = stack.pop() {
+            if !self.nodes[node].rules.is_empty() {
+                let suffix: Vec<&str> = labels.iter().rev().copied().collect();
+                let suffix = suffix.join(".");
+                self.nodes[node].rules.iter().for_each(|rule| f(&suffix, rule));
+            }
+            for (label, &child) in &self.nodes[node].children {
+                let mut child_labels = labels.clone();
+                child_labels.push(label);
+                stack.push((child, child_labels));
+            }
+        }
+    }
+}
+
+/// 解析规则扩展字段中的主机名后缀
+/// `github.io` 匹配自身及子域；`*.github.io` 仅匹配子域；至少两级标签，避免 `com` 这类过宽后缀
+/// 参数：spec - 后缀声明
+///
+/// 返回：(归一化后缀, 是否匹配后缀自身) | 非法声明
+pub fn parse_hostname_spec(spec: &str) -> Option<(String, bool)> {
+    let spec = spec.trim().trim_end_matches('.').to_ascii_lowercase();
+    let (suffix, include_apex) = match spec.strip_prefix("*.") {
+        Some(rest) => (rest, false),
+        None => (spec.trim_start_matches('.'), true),
+    };
+    is_valid_suffix(suffix).then(|| (suffix.to_string(), include_apex))
+}
+
+/// 后缀是否合法：至少两级非空标签，标签仅含字母/数字/`-`/`_`
+fn is_valid_suffix(suffix: &str) -> bool {
+    let mut labels = 0;
+    for label in suffix.split('.') {
+        if label.is_empty()
+            || !label
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+        {
+            return false;
+        }
+        labels += 1;
+    }
+    labels >= 2
+}
+
+/// URL 正则的协议前缀
+const URL_SCHEME_PREFIXES: &[&str] = &["https?://", "https?:\\/\\/", "https://", "http://"];
+
+/// 仅匹配子域的主机前缀
+const SUBDOMAIN_PREFIXES: &[&str] = &["[^/]+\\.", "[^/.]+\\.", "[\\w-]+\\.", "[\\w.-]+\\.", "[a-z0-9-]+\\."];
+
+/// 可选子域（同时匹配后缀自身）的主机前缀
+const OPTIONAL_SUBDOMAIN_PREFIXES: &[&str] = &[
+    "(?:[^/]+\\.)?",
+    "(?:[^/]*\\.)?",
+    "(?:[\\w-]+\\.)?",
+    "(?:[\\w-]+\\.)*",
+    "(?:[^/]+\\.)*",
+    "([^/]+\\.)?",
+];
+
+/// 主机名之后允许出现的结尾（均不限制主机名之后的内容）
+const HOST_TRAILERS: &[&str] = &["", "/", "(?:/|$)", "(?:$|/)", "/.*", ".*", "(?:[/:?#]|$)", "(?::\\d+)?(?:/|$)"];
+
+/// 识别形如 `^https?://[^/]+\.github\.io` 的 URL 正则，提取主机名后缀
+/// 参数：pattern - URL 正则源码
+///
+/// 返回：(归一化后缀, 是否匹配后缀自身) | 非主机名后缀型正则
+pub fn url_regex_host_suffix(pattern: &str) -> Option<(String, bool)> {
+    let rest = pattern.strip_prefix('^')?;
+    let rest = URL_SCHEME_PREFIXES.iter().find_map(|p| rest.strip_prefix(p))?;
+    let (rest, include_apex) = match SUBDOMAIN_PREFIXES.iter().find_map(|p| rest.strip_prefix(p)) {
+        Some(rest) => (rest, false),
+        None => (OPTIONAL_SUBDOMAIN_PREFIXES.iter().find_map(|p| rest.strip_prefix(p))?, true),
+    };
+
+    // 后缀字面量：仅允许标签字符与转义的点
+    let mut suffix = String::new();
+    let mut chars = rest.char_indices().peekable();
+    let mut end = rest.len();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if chars.peek().map(|(_, next)| *next) == Some('.') => {
+                chars.next();
+                suffix.push('.');
+            }
+            c if c.is_ascii_alphanumeric() || c == '-' || c == '_' => suffix.push(c.to_ascii_lowercase()),
+            _ => {
+                end = i;
+                break;
+            }
+        }
+    }
+    if !HOST_TRAILERS.contains(&&rest[end..]) || !is_valid_suffix(&suffix) {
+        return None;
+    }
+    Some((suffix, include_apex))
+}
+
+/// 提取 URL 中的主机名（去除协议/用户信息/端口，小写）
+/// 参数：url - URL（缺少协议时按主机名开头处理）
+///
+/// 返回：主机名 | 无法提取
+pub fn url_host(url: &str) -> Option<String> {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host_port = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    // IPv6 字面量不参与后缀匹配
+    if host_port.starts_with('[') {
+        return None;
+    }
+    let host = host_port.split(':').next()?.trim_end_matches('.');
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(tech: &str, include_apex: bool) -> HostnameRule {
+        HostnameRule {
+            tech: tech.to_string(),
+            confidence: 100,
+            include_apex,
+        }
+    }
+
+    #[test]
+    fn test_suffix_lookup() {
+        let mut index = HostnameIndex::default();
+        index.insert("github.io", rule("GitHub Pages", false));
+        index.insert("netlify.app", rule("Netlify", true));
+
+        let techs = |host: &str| -> Vec<String> { index.lookup(host).iter().map(|r| r.tech.clone()).collect() };
+        assert_eq!(techs("octocat.GitHub.io."), ["GitHub Pages"]);
+        assert!(techs("github.io").is_empty());
+        assert!(techs("github.io.evil.com").is_empty());
+        assert_eq!(techs("netlify.app"), ["Netlify"]);
+        assert_eq!(techs("a.b.netlify.app"), ["Netlify"]);
+        assert_eq!(index.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_specs() {
+        assert_eq!(parse_hostname_spec("*.GitHub.io"), Some(("github.io".to_string(), false)));
+        assert_eq!(parse_hostname_spec("vercel.app"), Some(("vercel.app".to_string(), true)));
+        assert_eq!(parse_hostname_spec("com"), None);
+
+        assert_eq!(
+            url_regex_host_suffix(r"^https?://[^/]+\.herokuapp\.com"),
+            Some(("herokuapp.com".to_string(), false))
+        );
+        assert_eq!(
+            url_regex_host_suffix(r"^https?://(?:[^/]+\.)?myshopify\.com(?:/|$)"),
+            Some(("myshopify.com".to_string(), true))
+        );
+        assert_eq!(url_regex_host_suffix(r"^https?://[^/]+\.example\.com/wp-admin"), None);
+        assert_eq!(url_regex_host_suffix(r"\.github\.io"), None);
+
+        assert_eq!(url_host("https://user@Docs.Example.com:8443/a?b").as_deref(), Some("docs.example.com"));
+        assert_eq!(url_host("example.org/path").as_deref(), Some("example.org"));
+    }
+}
@@ -46,4 +46,18 @@ pub struct RawMatchSet {
     pub meta_pattern_map: Option<PatternMap>,
     pub header_pattern_map: Option<PatternMap>,
     pub cookie_pattern_map: Option<PatternMap>,
+    /// JS型规则（键为全局变量属性链）
+    pub js_pattern_map: Option<PatternMap>,
+    /// DOM型规则（键为选择器 + 检查目标）
+    pub dom_pattern_map: Option<PatternMap>,
+    /// TLS证书颁发者规则
+    pub cert_issuer_patterns: Option<PatternList>,
+    /// Favicon mmh3 哈希规则
+    pub favicon_hash_patterns: Option<PatternList>,
+    /// 页面标题规则
+    pub title_patterns: Option<PatternList>,
+    /// HTML 注释规则
+    pub comment_patterns: Option<PatternList>,
+    /// DNS型规则（键为记录类型）
+    pub dns_pattern_map: Option<PatternMap>,
 }
\ No newline at end of file
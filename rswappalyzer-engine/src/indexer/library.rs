@@ -1,14 +1,18 @@
 use crate::{
-    CommonIndexedRule, CoreResult, core::{MatchRuleSet, MatchScope, RuleLibrary, TechBasicInfo}, indexer::index_rules::ScopedIndexedRule, scope_pruner::PruneScope
+    CommonIndexedRule, CoreResult, core::{MatchRuleSet, MatchScope, RuleLibrary, TechBasicInfo, TechMeta}, indexer::index_rules::ScopedIndexedRule, scope_pruner::PruneScope
 };
 use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 // 规则库索引 - 纯静态结构
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RuleLibraryIndex {
     pub rules: FxHashMap<MatchScope, Vec<ScopedIndexedRule>>,
     pub tech_info_map: FxHashMap<String, TechBasicInfo>,
+    /// 技术展示元信息旁路表
+    #[serde(default)]
+    pub meta_table: FxHashMap<String, TechMeta>,
 }
 
 // 编译后规则库
@@ -25,12 +29,197 @@ pub struct CompiledRuleLibrary {
     pub known_tokens_by_scope: FxHashMap<PruneScope, FxHashSet<String>>,
     /// 无最小证据规则（按 scope 维度） scope -> techs
     pub no_evidence_index: FxHashMap<PruneScope, FxHashSet<String>>,
+    /// 技术展示元信息旁路表（内置规则包仅在 full-meta 特性开启时保留）
+    #[serde(default, skip_serializing_if = "FxHashMap::is_empty")]
+    pub meta_table: FxHashMap<String, TechMeta>,
+    /// 编译失败的模式（仅启用模式校验时记录）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub broken_patterns: Vec<super::BrokenPattern>,
+    /// 因超出正则上限被剔除的模式（源码过长/编译体积超限）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rejected_patterns: Vec<super::RejectedPattern>,
+    /// 主机名后缀索引（Hostname 作用域：规则扩展字段 + 编译期转换的 URL 正则）
+    #[serde(default, skip_serializing_if = "super::HostnameIndex::is_empty")]
+    pub hostname_index: super::HostnameIndex,
+    /// 编译期等价模式去重统计
+    #[serde(default, skip_serializing_if = "super::PatternDedupStats::is_empty")]
+    pub dedup_stats: super::PatternDedupStats,
+    /// 引擎要求（编译期写入，加载期通过 verify_compatibility 校验）
+    #[serde(default, skip_serializing_if = "super::EngineRequirements::is_unknown")]
+    pub engine: super::EngineRequirements,
+    /// 按作用域的准入字面量自动机（编译时构建，不序列化；反序列化后为 None，由加载方调用 build_literal_prefilters）
+    #[serde(skip)]
+    pub(crate) literal_prefilters: Option<FxHashMap<PruneScope, Arc<super::LiteralPrefilter>>>,
+}
+
+impl CompiledRuleLibrary {
+    /// 获取指定作用域的字面量证据token（供外部预过滤组件使用）
+    /// 返回：token迭代器（无序；需要稳定顺序请使用 export_evidence_tokens）
+    pub fn evidence_tokens(&self, scope: PruneScope) -> impl Iterator<Item = &str> {
+        self.known_tokens_by_scope
+            .get(&scope)
+            .into_iter()
+            .flat_map(|tokens| tokens.iter().map(String::as_str))
+    }
+
+    /// 按编译选项调整已编译规则库（适用于内置/预编译规则库）
+    /// 移除未选择作用域的模式，重写正则的大小写策略（并重置对应的懒加载Matcher缓存），按需去重等价模式、对低特异性模式降权
+    /// 返回：被调整的模式数量
+    pub fn apply_compile_options(&mut self, options: &super::CompileOptions) -> usize {
+        let mut changed = 0;
+        if let Some(scopes) = &options.scopes {
+            changed += self.retain_scopes(scopes);
+        }
+        super::set_regex_limits(options.regex_limits);
+        changed += self.enforce_regex_limits(&options.regex_limits);
+        for tech in self.tech_patterns.values_mut() {
+            tech.for_each_pattern_mut(|pat| {
+                if let super::MatcherSpec::Regex {
+                    pattern,
+                    case_insensitive,
+                } = &mut pat.exec.matcher
+                {
+                    let wanted = options.regex_case_insensitive(pat.scope, pattern);
+                    if *case_insensitive != wanted {
+                        *case_insensitive = wanted;
+                        pat.exec.matcher_cache = Default::default();
+                        changed += 1;
+                    }
+                }
+            });
+        }
+        if options.dedup_patterns {
+            changed += self.dedup_patterns().removed;
+        }
+        if let Some(policy) = &options.low_specificity {
+            changed += self.apply_low_specificity_policy(policy);
+        }
+        changed
+    }
+
+    /// 仅保留指定作用域的模式及其证据索引（技术本身保留，关联推导不受影响）
+    /// 注：编译期已转入主机名索引的 URL 正则不会回填到 URL 作用域
+    /// 参数：scopes - 保留的作用域集合
+    ///
+    /// 返回：移除的模式数量
+    pub fn retain_scopes(&mut self, scopes: &FxHashSet<PruneScope>) -> usize {
+        let mut removed = 0;
+        for tech in self.tech_patterns.values_mut() {
+            tech.reset_regex_sets();
+            for (scope, list) in [
+                (PruneScope::Url, &mut tech.url_patterns),
+                (PruneScope::Html, &mut tech.html_patterns),
+                (PruneScope::Script, &mut tech.script_patterns),
+                (PruneScope::CertIssuer, &mut tech.cert_issuer_patterns),
+                (PruneScope::FaviconHash, &mut tech.favicon_hash_patterns),
+                (PruneScope::Title, &mut tech.title_patterns),
+                (PruneScope::Comment, &mut tech.comment_patterns),
+            ] {
+                if !scopes.contains(&scope) {
+                    removed += list.take().map_or(0, |list| list.len());
+                }
+            }
+            for (scope, map) in [
+                (PruneScope::Meta, &mut tech.meta_patterns),
+                (PruneScope::Header, &mut tech.header_patterns),
+                (PruneScope::Cookie, &mut tech.cookie_patterns),
+                (PruneScope::Js, &mut tech.js_patterns),
+                (PruneScope::Dom, &mut tech.dom_patterns),
+                (PruneScope::Dns, &mut tech.dns_patterns),
+            ] {
+                if !scopes.contains(&scope) {
+                    removed += map.take().map_or(0, |map| map.values().map(Vec::len).sum());
+                }
+            }
+        }
+        if !scopes.contains(&PruneScope::Hostname) {
+            removed += self.hostname_index.len();
+            self.hostname_index = Default::default();
+        }
+
+        for scope_to_techs in self.evidence_index.values_mut() {
+            scope_to_techs.retain(|scope, _| scopes.contains(scope));
+        }
+        self.evidence_index.retain(|_, scope_to_techs| !scope_to_techs.is_empty());
+        self.known_tokens = self.evidence_index.keys().cloned().collect();
+        self.known_tokens_by_scope.retain(|scope, _| scopes.contains(scope));
+        self.no_evidence_index.retain(|scope, _| scopes.contains(scope));
+        removed
+    }
+
+    /// 仅包含指定技术的规则库（目标查询按收缩后的候选集执行完整分析流程）
+    /// 证据索引/无证据索引/主机名索引同步收缩，准入字面量自动机按剩余模式重建
+    /// 参数：tech_names - 保留的技术名
+    ///
+    /// 返回：收缩后的规则库
+    pub fn subset(&self, tech_names: &FxHashSet<&str>) -> Self {
+        let keep = |name: &String| tech_names.contains(name.as_str());
+        let tech_patterns = self
+            .tech_patterns
+            .iter()
+            .filter(|(name, _)| keep(name))
+            .map(|(name, tech)| (name.clone(), tech.clone()))
+            .collect();
+
+        let mut evidence_index: FxHashMap<String, FxHashMap<PruneScope, FxHashSet<String>>> = FxHashMap::default();
+        let mut known_tokens_by_scope: FxHashMap<PruneScope, FxHashSet<String>> = FxHashMap::default();
+        for (token, scope_map) in &self.evidence_index {
+            for (scope, techs) in scope_map {
+                let kept: FxHashSet<String> = techs.iter().filter(|tech| keep(tech)).cloned().collect();
+                if kept.is_empty() {
+                    continue;
+                }
+                evidence_index.entry(token.clone()).or_default().insert(*scope, kept);
+                known_tokens_by_scope.entry(*scope).or_default().insert(token.clone());
+            }
+        }
+        let no_evidence_index = self
+            .no_evidence_index
+            .iter()
+            .map(|(scope, techs)| (*scope, techs.iter().filter(|tech| keep(tech)).cloned().collect::<FxHashSet<_>>()))
+            .filter(|(_, techs)| !techs.is_empty())
+            .collect();
+        let mut hostname_index = self.hostname_index.clone();
+        hostname_index.retain_techs(|tech| tech_names.contains(tech));
+
+        let mut subset = Self {
+            tech_patterns,
+            category_map: self.category_map.clone(),
+            tech_meta: self.tech_meta.iter().filter(|(name, _)| keep(name)).map(|(k, v)| (k.clone(), v.clone())).collect(),
+            known_tokens: evidence_index.keys().cloned().collect(),
+            evidence_index,
+            known_tokens_by_scope,
+            no_evidence_index,
+            meta_table: self.meta_table.iter().filter(|(name, _)| keep(name)).map(|(k, v)| (k.clone(), v.clone())).collect(),
+            broken_patterns: Vec::new(),
+            rejected_patterns: Vec::new(),
+            hostname_index,
+            dedup_stats: Default::default(),
+            engine: self.engine.clone(),
+            literal_prefilters: None,
+        };
+        subset.build_literal_prefilters();
+        subset
+    }
+
+    /// 获取技术展示元信息（规则源缺失或未保留元信息时返回None）
+    pub fn meta_of(&self, tech_name: &str) -> Option<&TechMeta> {
+        self.meta_table.get(tech_name)
+    }
+
+    /// 导出全部作用域的证据token（有序，可序列化为紧凑文本/JSON）
+    pub fn export_evidence_tokens(&self) -> super::EvidenceTokenExport {
+        super::EvidenceTokenExport::from_library(self)
+    }
 }
 
 // RuleLibraryIndex
 impl RuleLibraryIndex {
     pub fn from_rule_library(rule_library: &RuleLibrary) -> CoreResult<Self> {
-        let mut index = Self::default();
+        let mut index = Self {
+            meta_table: rule_library.meta_table.clone(),
+            ..Self::default()
+        };
 
         for (tech_id, parsed_tech_rule) in &rule_library.core_tech_map {
             index
@@ -59,7 +248,7 @@ impl RuleLibraryIndex {
         let mut scoped_rules = Vec::new();
 
         match scope {
-            MatchScope::Header | MatchScope::Cookie | MatchScope::Meta | MatchScope::Js => {
+            MatchScope::Header | MatchScope::Cookie | MatchScope::Meta | MatchScope::Js | MatchScope::Dom | MatchScope::Dns => {
                 for keyed_pattern in &match_rule_set.keyed_patterns {
                     let common = CommonIndexedRule {
                         tech: tech_id.clone(),
@@ -73,7 +262,7 @@ impl RuleLibraryIndex {
                     });
                 }
             }
-            MatchScope::Url | MatchScope::Html | MatchScope::Script | MatchScope::ScriptSrc => {
+            MatchScope::Url | MatchScope::Html | MatchScope::Script | MatchScope::ScriptSrc | MatchScope::CertIssuer | MatchScope::FaviconHash | MatchScope::Title | MatchScope::Comment => {
                 for pattern in &match_rule_set.list_patterns {
                     let common = CommonIndexedRule {
                         tech: tech_id.clone(),
@@ -88,4 +277,61 @@ impl RuleLibraryIndex {
 
         Ok(scoped_rules)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use crate::test_support::compile;
+    use crate::TechMeta;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Acme": {
+                "cats": [1],
+                "description": "Acme CMS",
+                "website": "https://acme.example",
+                "icon": "Acme.svg",
+                "cpe": "cpe:2.3:a:acme:cms:*:*:*:*:*:*:*:*",
+                "saas": true,
+                "pricing": ["low"],
+                "headers": { "X-Acme": "" }
+            },
+            "Bare": { "cats": [1], "headers": { "X-Bare": "" } }
+        }
+    }"#;
+
+    #[test]
+    fn test_meta_side_table() {
+        let lib = compile(RULES);
+        let meta = lib.meta_of("Acme").unwrap();
+        assert_eq!(meta.description.as_deref(), Some("Acme CMS"));
+        assert_eq!(meta.website.as_deref(), Some("https://acme.example"));
+        assert_eq!(meta.icon.as_deref(), Some("Acme.svg"));
+        assert_eq!(meta.saas, Some(true));
+        assert_eq!(meta.pricing.as_deref(), Some(&["low".to_string()][..]));
+        // 无元信息的技术不进入旁路表
+        assert!(lib.meta_of("Bare").is_none());
+        assert_eq!(lib.meta_table.len(), 1);
+    }
+
+    #[test]
+    fn test_meta_table_serde() {
+        let lib = compile(RULES);
+        let json = serde_json::to_value(&lib).unwrap();
+        let restored: super::CompiledRuleLibrary = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.meta_of("Acme"), lib.meta_of("Acme"));
+
+        // 空旁路表不序列化，旧数据反序列化为空表
+        let mut empty = compile(RULES);
+        empty.meta_table.clear();
+        let json = serde_json::to_value(&empty).unwrap();
+        assert!(json.get("meta_table").is_none());
+        let restored: super::CompiledRuleLibrary = serde_json::from_value(json).unwrap();
+        assert!(restored.meta_table.is_empty());
+    }
+
+    #[test]
+    fn test_tech_meta_is_empty() {
+        assert!(TechMeta::default().is_empty());
+        assert!(!TechMeta { saas: Some(false), ..TechMeta::default() }.is_empty());
+    }
+}
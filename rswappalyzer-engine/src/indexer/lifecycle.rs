@@ -0,0 +1,304 @@
+//! 规则生命周期校验
+//! 自定义规则包可为技术声明 `deprecated` / `valid_until` / `replacement`，
+//! 加载时按 ExpiredRulePolicy 告警或剔除过期规则，并生成生命周期校验报告
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
+
+use super::CompiledRuleLibrary;
+
+/// 过期规则处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ExpiredRulePolicy {
+    /// 保留过期规则，不输出告警
+    Keep,
+    /// 保留过期规则，加载时输出告警（默认）
+    #[default]
+    Warn,
+    /// 加载时剔除过期规则（并输出告警）
+    Skip,
+}
+
+/// 生命周期状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LifecycleStatus {
+    /// 已废弃（未过期）
+    Deprecated,
+    /// 已超过有效期
+    Expired,
+    /// 有效期格式非法（非 YYYY-MM-DD，视为未过期）
+    InvalidDate,
+}
+
+/// 单条生命周期记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifecycleEntry {
+    /// 技术名称
+    pub tech: String,
+    /// 生命周期状态
+    pub status: LifecycleStatus,
+    /// 有效期截止日期
+    pub valid_until: Option<String>,
+    /// 替代技术名称
+    pub replacement: Option<String>,
+    /// 是否已从规则库剔除
+    pub skipped: bool,
+}
+
+/// 生命周期校验报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LifecycleReport {
+    /// 校验基准日期（YYYY-MM-DD）
+    pub today: String,
+    /// 需关注的技术（按技术名排序）
+    pub entries: Vec<LifecycleEntry>,
+}
+
+impl LifecycleReport {
+    /// 是否无任何生命周期问题
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 指定状态的记录数
+    pub fn count(&self, status: LifecycleStatus) -> usize {
+        self.entries.iter().filter(|e| e.status == status).count()
+    }
+
+    /// 已剔除的技术数
+    pub fn skipped(&self) -> usize {
+        self.entries.iter().filter(|e| e.skipped).count()
+    }
+}
+
+impl CompiledRuleLibrary {
+    /// 生成生命周期校验报告（不修改规则库）
+    /// 参数：today - 校验基准日期（YYYY-MM-DD，通常为 today_utc()）
+    pub fn lifecycle_report(&self, today: &str) -> LifecycleReport {
+        let mut entries: Vec<LifecycleEntry> = self
+            .tech_meta
+            .iter()
+            .filter_map(|(name, info)| {
+                let lifecycle = info.lifecycle.as_ref()?;
+                let status = if !lifecycle.has_valid_date() {
+                    LifecycleStatus::InvalidDate
+                } else if lifecycle.is_expired(today) {
+                    LifecycleStatus::Expired
+                } else if lifecycle.deprecated {
+                    LifecycleStatus::Deprecated
+                } else {
+                    return None;
+                };
+                Some(LifecycleEntry {
+                    tech: name.clone(),
+                    status,
+                    valid_until: lifecycle.valid_until.clone(),
+                    replacement: lifecycle.replacement.clone(),
+                    skipped: !self.tech_patterns.contains_key(name),
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| a.tech.cmp(&b.tech));
+
+        LifecycleReport {
+            today: today.to_string(),
+            entries,
+        }
+    }
+
+    /// 按策略处理过期/废弃规则
+    /// 参数：
+    /// - policy: 过期规则处理策略
+    /// - today: 校验基准日期（YYYY-MM-DD）
+    ///
+    /// 返回：处理后的生命周期校验报告
+    pub fn apply_lifecycle_policy(
+        &mut self,
+        policy: ExpiredRulePolicy,
+        today: &str,
+    ) -> LifecycleReport {
+        if policy == ExpiredRulePolicy::Skip {
+            let expired: FxHashSet<String> = self
+                .tech_meta
+                .iter()
+                .filter(|(_, info)| info.lifecycle.as_ref().is_some_and(|l| l.is_expired(today)))
+                .map(|(name, _)| name.clone())
+                .collect();
+            self.remove_techs(&expired);
+        }
+
+        let report = self.lifecycle_report(today);
+        if policy != ExpiredRulePolicy::Keep {
+            for entry in &report.entries {
+                let replacement = entry.replacement.as_deref().unwrap_or("-");
+                match entry.status {
+                    LifecycleStatus::Expired => log::warn!(
+                        "Rule expired | Tech: {} | Valid until: {} | Replacement: {} | Skipped: {}",
+                        entry.tech,
+                        entry.valid_until.as_deref().unwrap_or("-"),
+                        replacement,
+                        entry.skipped
+                    ),
+                    LifecycleStatus::Deprecated => log::warn!(
+                        "Rule deprecated | Tech: {} | Replacement: {}",
+                        entry.tech,
+                        replacement
+                    ),
+                    LifecycleStatus::InvalidDate => log::warn!(
+                        "Rule has invalid valid_until (expected YYYY-MM-DD) | Tech: {} | Value: {}",
+                        entry.tech,
+                        entry.valid_until.as_deref().unwrap_or("-")
+                    ),
+                }
+            }
+        }
+        report
+    }
+
+    /// 从规则库剔除指定技术（同步清理证据索引；保留 tech_meta 以便报告）
+    fn remove_techs(&mut self, names: &FxHashSet<String>) {
+        if names.is_empty() {
+            return;
+        }
+        self.tech_patterns.retain(|name, _| !names.contains(name));
+
+        self.evidence_index.retain(|_, scope_map| {
+            scope_map.retain(|_, techs| {
+                techs.retain(|tech| !names.contains(tech));
+                !techs.is_empty()
+            });
+            !scope_map.is_empty()
+        });
+        self.no_evidence_index.retain(|_, techs| {
+            techs.retain(|tech| !names.contains(tech));
+            !techs.is_empty()
+        });
+        self.hostname_index.retain_techs(|tech| !names.contains(tech));
+
+        // 重建 known_tokens（剔除后已无技术引用的 token 不再参与剪枝）
+        self.known_tokens = self.evidence_index.keys().cloned().collect();
+        self.known_tokens_by_scope.clear();
+        for (token, scope_map) in &self.evidence_index {
+            for scope in scope_map.keys() {
+                self.known_tokens_by_scope
+                    .entry(*scope)
+                    .or_default()
+                    .insert(token.clone());
+            }
+        }
+    }
+}
+
+/// 当前UTC日期（YYYY-MM-DD）
+pub fn today_utc() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// 自1970-01-01起的天数转换为公历日期（Howard Hinnant 算法）
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::compile_with;
+    use crate::{CompileOptions, TechLifecycle};
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Old": {
+                "cats": [1],
+                "valid_until": "2000-01-01",
+                "replacement": "New",
+                "headers": { "X-Old": "old-widget" }
+            },
+            "Legacy": { "cats": [1], "deprecated": true, "headers": { "X-Legacy": "" } },
+            "Typo": { "cats": [1], "validUntil": "2030/01/01", "headers": { "X-Typo": "" } },
+            "Future": { "cats": [1], "valid_until": "2999-12-31", "headers": { "X-Future": "" } },
+            "New": { "cats": [1], "headers": { "X-New": "" } }
+        }
+    }"#;
+
+    fn compile_policy(policy: ExpiredRulePolicy) -> CompiledRuleLibrary {
+        compile_with(RULES, &CompileOptions::default().expired_rules(policy))
+    }
+
+    #[test]
+    fn test_lifecycle_report() {
+        let lib = compile_policy(ExpiredRulePolicy::Keep);
+        let report = lib.lifecycle_report("2024-06-01");
+        let techs: Vec<_> = report.entries.iter().map(|e| (e.tech.as_str(), e.status)).collect();
+        assert_eq!(
+            techs,
+            vec![
+                ("Legacy", LifecycleStatus::Deprecated),
+                ("Old", LifecycleStatus::Expired),
+                ("Typo", LifecycleStatus::InvalidDate),
+            ]
+        );
+        assert_eq!(report.entries[1].replacement.as_deref(), Some("New"));
+        assert_eq!(report.skipped(), 0);
+        // Keep 策略不剔除
+        assert!(lib.tech_patterns.contains_key("Old"));
+    }
+
+    #[test]
+    fn test_skip_expired() {
+        let lib = compile_policy(ExpiredRulePolicy::Skip);
+        assert!(!lib.tech_patterns.contains_key("Old"));
+        assert!(lib.tech_patterns.contains_key("Future"));
+        assert!(lib.tech_patterns.contains_key("Legacy"));
+        // 证据索引同步清理
+        assert!(lib
+            .evidence_index
+            .values()
+            .all(|scope_map| scope_map.values().all(|techs| !techs.contains("Old"))));
+
+        let report = lib.lifecycle_report(&today_utc());
+        let old = report.entries.iter().find(|e| e.tech == "Old").unwrap();
+        assert!(old.skipped);
+        assert_eq!(report.skipped(), 1);
+        assert_eq!(report.count(LifecycleStatus::Expired), 1);
+    }
+
+    #[test]
+    fn test_apply_policy_with_date() {
+        let mut lib = compile_policy(ExpiredRulePolicy::Keep);
+        let report = lib.apply_lifecycle_policy(ExpiredRulePolicy::Skip, "3000-01-01");
+        assert!(!lib.tech_patterns.contains_key("Future"));
+        assert_eq!(report.skipped(), 2);
+    }
+
+    #[test]
+    fn test_lifecycle_dates() {
+        assert!(TechLifecycle::from_parts(false, Some(" ".to_string()), None).is_none());
+        let lifecycle = TechLifecycle::from_parts(false, Some("2024-06-01".to_string()), None).unwrap();
+        // 截止日当天仍有效
+        assert!(!lifecycle.is_expired("2024-06-01"));
+        assert!(lifecycle.is_expired("2024-06-02"));
+        let invalid = TechLifecycle::from_parts(false, Some("2024-13-01".to_string()), None).unwrap();
+        assert!(!invalid.has_valid_date());
+        assert!(!invalid.is_expired("2999-01-01"));
+
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_723), (2024, 1, 1));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+    }
+}
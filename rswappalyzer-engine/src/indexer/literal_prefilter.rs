@@ -0,0 +1,283 @@
+//! 字面量预过滤（Aho-Corasick）
+//! 准入网关逐模式执行 str::contains / 令牌查找，大输入（HTML/Script）下开销随模式数线性增长。
+//! 规则库编译时按作用域汇总全部准入字面量构建自动机，运行期每个输入仅扫描一次，
+//! 扫描结果（LiteralHits）由调用方随输入显式传入剪枝校验：
+//! 1. RequireAnyLiteral：大小写敏感自动机，命中表即 str::contains 的结果
+//! 2. RequireAll：ASCII 大小写不敏感自动机。输入令牌均为输入的小写子串，自动机未命中的令牌必然不在令牌集合中，
+//!    直接剪枝；全部命中后仍按令牌集合校验（词边界/令牌上限），结果与逐令牌查找一致。
+//!    非 ASCII 令牌不收录（ASCII 大小写折叠无法覆盖 Unicode 大小写，收录会误剪枝）
+//! 3. 自动机未收录的字面量（如编译后调整过的模式）回退子串查找 / 令牌查找
+//! 4. 反序列化得到的规则库（内置规则/编译产物缓存/规则包）由加载方调用 build_literal_prefilters 构建
+//! 5. 自动机按字节扫描，非合法 UTF-8 的输入（按 regex::bytes 匹配时）同样适用
+
+use std::sync::Arc;
+
+use aho_corasick::AhoCorasick;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::{CompiledRuleLibrary, MatchGate};
+use crate::scope_pruner::PruneScope;
+
+/// 构建字面量自动机的作用域（输入为整段文本，逐模式扫描代价最高）
+pub const PREFILTER_SCOPES: [PruneScope; 2] = [PruneScope::Html, PruneScope::Script];
+
+/// 单组字面量的自动机
+#[derive(Debug, Clone)]
+struct LiteralAutomaton {
+    automaton: AhoCorasick,
+    /// 字面量 -> 自动机模式 id
+    ids: FxHashMap<String, usize>,
+}
+
+impl LiteralAutomaton {
+    /// 由字面量集合构建自动机（重复字面量合并）
+    /// 参数：
+    /// - literals: 字面量
+    /// - ascii_case_insensitive: 是否忽略 ASCII 大小写
+    ///
+    /// 返回：自动机（字面量为空或构建失败时返回 None）
+    fn build<'a>(literals: impl IntoIterator<Item = &'a str>, ascii_case_insensitive: bool) -> Option<Self> {
+        let mut ids = FxHashMap::default();
+        let mut patterns = Vec::new();
+        for literal in literals {
+            if !ids.contains_key(literal) {
+                ids.insert(literal.to_string(), patterns.len());
+                patterns.push(literal);
+            }
+        }
+        if patterns.is_empty() {
+            return None;
+        }
+        match AhoCorasick::builder()
+            .ascii_case_insensitive(ascii_case_insensitive)
+            .build(&patterns)
+        {
+            Ok(automaton) => Some(Self { automaton, ids }),
+            Err(e) => {
+                log::warn!("Literal prefilter build failed | Literals: {} | Error: {}", patterns.len(), e);
+                None
+            }
+        }
+    }
+
+    /// 扫描输入一次，返回按模式 id 索引的命中表
+    fn scan(&self, input: &[u8]) -> Vec<bool> {
+        let mut hits = vec![false; self.ids.len()];
+        for mat in self.automaton.find_overlapping_iter(input) {
+            hits[mat.pattern().as_usize()] = true;
+        }
+        hits
+    }
+}
+
+/// 单个作用域的准入字面量自动机
+#[derive(Debug, Clone)]
+pub struct LiteralPrefilter {
+    /// 结构字面量自动机（RequireAnyLiteral，大小写敏感）
+    literals: Option<LiteralAutomaton>,
+    /// 最小证据令牌自动机（RequireAll，ASCII 大小写不敏感）
+    tokens: Option<LiteralAutomaton>,
+}
+
+impl LiteralPrefilter {
+    /// 由准入字面量构建自动机
+    /// 参数：
+    /// - literals: RequireAnyLiteral 字面量
+    /// - tokens: RequireAll 令牌（非 ASCII 令牌跳过，校验时视为可能出现）
+    ///
+    /// 返回：自动机（两类字面量均为空时返回 None）
+    pub fn build<'a, 'b>(
+        literals: impl IntoIterator<Item = &'a str>,
+        tokens: impl IntoIterator<Item = &'b str>,
+    ) -> Option<Self> {
+        let prefilter = Self {
+            literals: LiteralAutomaton::build(literals, false),
+            tokens: LiteralAutomaton::build(tokens.into_iter().filter(|token| token.is_ascii()), true),
+        };
+        (!prefilter.is_empty()).then_some(prefilter)
+    }
+
+    /// 收录的字面量数量（结构字面量 + 令牌）
+    pub fn len(&self) -> usize {
+        [&self.literals, &self.tokens]
+            .into_iter()
+            .flatten()
+            .map(|automaton| automaton.ids.len())
+            .sum()
+    }
+
+    /// 是否未收录任何字面量
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 扫描输入一次
+    /// 参数：input - 待匹配字符串或字节（非合法 UTF-8 的输入按字节扫描）
+    ///
+    /// 返回：该输入的命中结果
+    pub fn scan(&self, input: impl AsRef<[u8]>) -> LiteralHits<'_> {
+        let input = input.as_ref();
+        LiteralHits {
+            prefilter: self,
+            literal_hits: self.literals.as_ref().map(|a| a.scan(input)).unwrap_or_default(),
+            token_hits: self.tokens.as_ref().map(|a| a.scan(input)).unwrap_or_default(),
+        }
+    }
+}
+
+/// 单个输入的字面量扫描结果（仅对扫描的同一输入有效）
+#[derive(Debug, Clone)]
+pub struct LiteralHits<'a> {
+    prefilter: &'a LiteralPrefilter,
+    literal_hits: Vec<bool>,
+    token_hits: Vec<bool>,
+}
+
+impl LiteralHits<'_> {
+    /// 输入是否包含任一结构字面量（未收录的字面量回退子串查找）
+    /// 参数：
+    /// - input: 扫描时的输入（字符串或字节）
+    /// - literals: 准入字面量
+    ///
+    /// 返回：是否命中任一字面量
+    #[inline]
+    pub fn contains_any(&self, input: impl AsRef<[u8]>, literals: &[String]) -> bool {
+        let input = input.as_ref();
+        let ids = self.prefilter.literals.as_ref().map(|a| &a.ids);
+        literals.iter().any(|literal| match ids.and_then(|ids| ids.get(literal)) {
+            Some(&id) => self.literal_hits[id],
+            None => memchr::memmem::find(input, literal.as_bytes()).is_some(),
+        })
+    }
+
+    /// 最小证据令牌是否可能全部出现（返回 false 时令牌集合校验必然失败；未收录的令牌视为可能出现）
+    /// 参数：tokens - 准入令牌
+    ///
+    /// 返回：是否可能全部出现
+    #[inline]
+    pub fn may_contain_all(&self, tokens: &FxHashSet<String>) -> bool {
+        let Some(automaton) = &self.prefilter.tokens else {
+            return true;
+        };
+        tokens
+            .iter()
+            .all(|token| automaton.ids.get(token).is_none_or(|&id| self.token_hits[id]))
+    }
+}
+
+impl CompiledRuleLibrary {
+    /// 构建各作用域的准入字面量自动机（编译时调用；反序列化得到的规则库由加载方调用）
+    pub fn build_literal_prefilters(&mut self) {
+        let mut literals: FxHashMap<PruneScope, Vec<String>> = FxHashMap::default();
+        let mut tokens: FxHashMap<PruneScope, Vec<String>> = FxHashMap::default();
+        for tech in self.tech_patterns.values() {
+            tech.for_each_pattern(|pat| {
+                if !PREFILTER_SCOPES.contains(&pat.scope) {
+                    return;
+                }
+                match &pat.exec.match_gate {
+                    MatchGate::RequireAnyLiteral(list) => {
+                        literals.entry(pat.scope).or_default().extend(list.iter().cloned())
+                    }
+                    MatchGate::RequireAll(set) => {
+                        tokens.entry(pat.scope).or_default().extend(set.iter().cloned())
+                    }
+                    MatchGate::Open => {}
+                }
+            });
+        }
+        let prefilters = PREFILTER_SCOPES
+            .into_iter()
+            .filter_map(|scope| {
+                let literals = literals.remove(&scope).unwrap_or_default();
+                let tokens = tokens.remove(&scope).unwrap_or_default();
+                let prefilter = LiteralPrefilter::build(
+                    literals.iter().map(String::as_str),
+                    tokens.iter().map(String::as_str),
+                )?;
+                Some((scope, Arc::new(prefilter)))
+            })
+            .collect();
+        self.literal_prefilters = Some(prefilters);
+    }
+
+    /// 准入字面量自动机是否已构建（反序列化后未构建时为 false）
+    pub fn has_literal_prefilters(&self) -> bool {
+        self.literal_prefilters.is_some()
+    }
+
+    /// 指定作用域的准入字面量自动机
+    /// 参数：scope - 作用域
+    ///
+    /// 返回：自动机（未构建或该作用域无准入字面量时返回 None）
+    pub fn literal_prefilter(&self, scope: PruneScope) -> Option<&Arc<LiteralPrefilter>> {
+        self.literal_prefilters.as_ref()?.get(&scope)
+    }
+
+    /// 扫描输入一次，返回随输入传入剪枝校验的命中结果
+    /// 参数：
+    /// - scope: 输入所属作用域
+    /// - input: 待匹配字符串或字节
+    ///
+    /// 返回：命中结果（无自动机时返回 None，剪枝校验回退逐模式查找）
+    pub fn literal_hits(&self, scope: PruneScope, input: impl AsRef<[u8]>) -> Option<LiteralHits<'_>> {
+        Some(self.literal_prefilter(scope)?.scan(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_set(tokens: &[&str]) -> FxHashSet<String> {
+        tokens.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_ascii_tokens_case_insensitive() {
+        let prefilter = LiteralPrefilter::build([], ["acme", "widget"]).unwrap();
+        let hits = prefilter.scan("Powered by ACME");
+        assert!(hits.may_contain_all(&token_set(&["acme"])));
+        assert!(!hits.may_contain_all(&token_set(&["acme", "widget"])));
+    }
+
+    #[test]
+    fn test_non_ascii_tokens_not_pruned() {
+        let prefilter = LiteralPrefilter::build([], ["café", "acme", "widget"]).unwrap();
+        // 非 ASCII 令牌不收录
+        assert_eq!(prefilter.len(), 2);
+
+        let hits = prefilter.scan("CAFÉ ACME");
+        assert!(hits.may_contain_all(&token_set(&["café"])));
+        assert!(hits.may_contain_all(&token_set(&["café", "acme"])));
+        assert!(!hits.may_contain_all(&token_set(&["café", "widget"])));
+    }
+
+    #[test]
+    fn test_non_ascii_only_tokens_build_nothing() {
+        assert!(LiteralPrefilter::build([], ["naïve"]).is_none());
+    }
+
+    #[test]
+    fn test_literals_case_sensitive() {
+        let prefilter = LiteralPrefilter::build(["wp-content"], []).unwrap();
+        let literals = vec!["wp-content".to_string()];
+        let input = "/WP-CONTENT/";
+        assert!(!prefilter.scan(input).contains_any(input, &literals));
+        let input = "/wp-content/";
+        assert!(prefilter.scan(input).contains_any(input, &literals));
+    }
+
+    #[test]
+    fn test_scan_invalid_utf8_bytes() {
+        let prefilter = LiteralPrefilter::build(["wp-content"], ["acme"]).unwrap();
+        let literals = vec!["wp-content".to_string(), "wp-includes".to_string()];
+        let input: &[u8] = b"\xff/wp-content/ ACME \xe9";
+        let hits = prefilter.scan(input);
+        assert!(hits.contains_any(input, &literals));
+        assert!(hits.may_contain_all(&token_set(&["acme"])));
+        // 未收录的字面量回退字节子串查找
+        assert!(!hits.contains_any(input, &literals[1..]));
+        assert!(hits.contains_any(b"\xff/wp-includes/", &literals[1..]));
+    }
+}
@@ -1,21 +1,101 @@
 use crate::{
-    StructuralPrereq, core::{MatchType, Pattern}, min_evidence::MinEvidenceMeta, regex_literal::{extract_longest_static_substr_from_regex, extract_or_branch_literals}
+    CustomMatcher, MatcherRegistry, StructuralPrereq, core::{MatchType, Pattern}, min_evidence::MinEvidenceMeta, regex_literal::{extract_longest_static_substr_from_regex, extract_or_branch_literals}
 };
 use once_cell::sync::Lazy;
-use regex::{Captures, Regex, RegexBuilder};
-use rustc_hash::{FxHashMap};
-use std::sync::{Arc, RwLock};
+use regex::bytes::{Captures as BytesCaptures, Regex as BytesRegex};
+use regex::{Captures, Regex};
+use rustc_hash::FxHashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::utils::lru::{LruStats, ShardedLru, DEFAULT_LRU_SHARDS};
 
 /// 全局空正则常量（预编译，用于错误回退）
 /// 零拷贝、零分配，全局复用
 pub static EMPTY_REGEX_ARC: Lazy<Arc<Regex>> = Lazy::new(|| Arc::new(Regex::new(r"^$").unwrap()));
 
+/// 全局空字节正则常量（字节匹配的错误回退）
+pub static EMPTY_BYTES_REGEX_ARC: Lazy<Arc<BytesRegex>> = Lazy::new(|| Arc::new(BytesRegex::new(r"^$").unwrap()));
+
+/// 全局正则缓存默认容量（内置规则库的正则总数约数千条，留足余量避免反复淘汰重编译）
+pub const DEFAULT_REGEX_CACHE_CAPACITY: usize = 16_384;
+
 /// 全局正则缓存类型定义
 /// Key: (正则模式字符串, 是否忽略大小写)
 /// Value: 编译后的正则Arc（避免重复编译）
+/// 按 Key 分片加锁，超出容量时淘汰最久未使用的正则（再次使用时重新编译）
 type RegexCacheKey = (Arc<String>, bool);
-pub static REGEX_CACHE: Lazy<RwLock<FxHashMap<RegexCacheKey, Arc<Regex>>>> =
-    Lazy::new(|| RwLock::new(FxHashMap::default()));
+pub static REGEX_CACHE: Lazy<ShardedLru<RegexCacheKey, Arc<Regex>>> =
+    Lazy::new(|| ShardedLru::new(DEFAULT_LRU_SHARDS, DEFAULT_REGEX_CACHE_CAPACITY));
+
+/// 全局字节正则缓存（regex::bytes，仅非 UTF-8 输入按字节匹配时编译，Key 与容量同 REGEX_CACHE）
+pub static BYTES_REGEX_CACHE: Lazy<ShardedLru<RegexCacheKey, Arc<BytesRegex>>> =
+    Lazy::new(|| ShardedLru::new(DEFAULT_LRU_SHARDS, DEFAULT_REGEX_CACHE_CAPACITY));
+
+/// 已记录过编译错误的正则（同一正则仅告警一次，避免重复错误刷屏）
+static REGEX_ERROR_LOGGED: Lazy<Mutex<FxHashSet<RegexCacheKey>>> =
+    Lazy::new(|| Mutex::new(FxHashSet::default()));
+/// 编译失败总次数
+static REGEX_ERROR_TOTAL: AtomicU64 = AtomicU64::new(0);
+/// 被抑制（未输出日志）的重复编译失败次数
+static REGEX_ERROR_SUPPRESSED: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// 当前线程因超出编译体积上限而失败的正则编译次数（检测按线程执行，前后差值即单次检测的计数）
+    static THREAD_REGEX_BUDGET_EXCEEDED: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+/// 当前线程累计的正则编译超出体积上限次数
+pub fn thread_regex_budget_exceeded() -> u64 {
+    THREAD_REGEX_BUDGET_EXCEEDED.with(std::cell::Cell::get)
+}
+
+/// 将其它线程（如并行分析的工作线程）的正则编译超出体积上限次数计入当前线程
+/// 参数：count - 计入的次数
+pub fn charge_thread_regex_budget_exceeded(count: u64) {
+    THREAD_REGEX_BUDGET_EXCEEDED.with(|total| total.set(total.get() + count));
+}
+
+/// 运行期正则编译错误统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RegexErrorStats {
+    /// 编译失败的不同正则数量（每个已告警一次）
+    pub failed_patterns: u64,
+    /// 编译失败总次数
+    pub total_failures: u64,
+    /// 被抑制的重复告警次数
+    pub suppressed: u64,
+}
+
+/// 全局正则缓存条目数（容忍锁中毒）
+pub fn regex_cache_len() -> usize {
+    REGEX_CACHE.len()
+}
+
+/// 全局正则缓存统计（条目数/容量/命中/未命中/淘汰，进程级）
+pub fn regex_cache_stats() -> LruStats {
+    REGEX_CACHE.stats()
+}
+
+/// 设置全局正则缓存容量（进程级，缩小时立即淘汰超出部分；字节正则缓存使用同一容量）
+/// 参数：capacity - 容量上限（按分片均分，每个分片至少保留 1 条）
+pub fn set_regex_cache_capacity(capacity: usize) {
+    REGEX_CACHE.set_capacity(capacity);
+    BYTES_REGEX_CACHE.set_capacity(capacity);
+}
+
+/// 获取运行期正则编译错误统计（进程级）
+pub fn regex_error_stats() -> RegexErrorStats {
+    let failed_patterns = REGEX_ERROR_LOGGED
+        .lock()
+        .map(|logged| logged.len() as u64)
+        .unwrap_or_default();
+    RegexErrorStats {
+        failed_patterns,
+        total_failures: REGEX_ERROR_TOTAL.load(Ordering::Relaxed),
+        suppressed: REGEX_ERROR_SUPPRESSED.load(Ordering::Relaxed),
+    }
+}
 
 /// 运行时匹配器（非序列化）
 /// 核心特性：
@@ -36,6 +116,15 @@ pub enum Matcher {
         /// 是否忽略大小写
         case_insensitive: bool,
     },
+    /// 自定义匹配（由 MatcherRegistry 中的工厂构建）
+    Custom {
+        /// 匹配类型名
+        kind: Arc<String>,
+        /// 模式字符串
+        pattern: Arc<String>,
+        /// 匹配执行体（None 表示未绑定工厂，不匹配任何输入）
+        inner: Option<Arc<dyn CustomMatcher>>,
+    },
 }
 
 impl Matcher {
@@ -55,8 +144,18 @@ impl Matcher {
         }
     }
 
+    /// 获取字节正则捕获组（仅LazyRegex类型有效）
+    /// 参数：input - 待匹配的字节（非合法 UTF-8 的输入）
+    /// 返回：捕获组结果（None表示非正则类型/无匹配）
+    pub fn captures_bytes<'a>(&self, input: &'a [u8]) -> Option<BytesCaptures<'a>> {
+        match self {
+            Matcher::LazyRegex { .. } => self.get_compiled_bytes_regex().captures(input),
+            _ => None,
+        }
+    }
+
     /// 获取编译后的正则（懒加载+全局缓存）
-    /// 核心逻辑：读锁查缓存 → 未命中则写锁编译并缓存
+    /// 核心逻辑：分片读锁查缓存 → 未命中则分片写锁编译并缓存
     #[inline(always)]
     fn get_compiled_regex(&self) -> Arc<Regex> {
         match self {
@@ -67,42 +166,98 @@ impl Matcher {
                 // 构建缓存Key（Arc clone仅增加引用计数，零拷贝）
                 let cache_key = (pattern.clone(), *case_insensitive);
 
-                // 1. 读锁查询缓存（无锁竞争）
-                let cache_read = REGEX_CACHE.read().unwrap();
-                if let Some(re) = cache_read.get(&cache_key) {
-                    return re.clone();
-                }
-                drop(cache_read); // 显式释放读锁
-
-                // 2. 写锁编译并插入缓存（仅缓存未命中时执行）
-                let mut cache_write = REGEX_CACHE.write().unwrap();
-                cache_write
-                    .entry(cache_key)
-                    .or_insert_with(|| Self::compile_regex(pattern.as_str(), *case_insensitive))
-                    .clone()
+                // 读锁查询缓存 → 未命中时写锁编译并插入（锁中毒时继续使用缓存）
+                REGEX_CACHE.get_or_insert_with(cache_key, || {
+                    Self::compile_regex(pattern, *case_insensitive)
+                })
             }
             // 非正则类型返回全局空正则（零拷贝）
             _ => EMPTY_REGEX_ARC.clone(),
         }
     }
 
+    /// 获取编译后的字节正则（懒加载+全局字节正则缓存）
+    fn get_compiled_bytes_regex(&self) -> Arc<BytesRegex> {
+        match self {
+            Matcher::LazyRegex {
+                pattern,
+                case_insensitive,
+            } => BYTES_REGEX_CACHE.get_or_insert_with((pattern.clone(), *case_insensitive), || {
+                Self::compile_limited(pattern, *case_insensitive, |limits| {
+                    limits.bytes_builder(pattern, *case_insensitive).build()
+                })
+                .unwrap_or_else(|| EMPTY_BYTES_REGEX_ARC.clone())
+            }),
+            _ => EMPTY_BYTES_REGEX_ARC.clone(),
+        }
+    }
+
     /// 正则编译公共逻辑（带错误处理）
     /// 参数：
     /// - pattern: 正则模式字符串
     /// - case_insensitive: 是否忽略大小写
+    ///
     /// 返回：编译后的正则Arc（失败则返回空正则）
     #[inline]
-    fn compile_regex(pattern: &str, case_insensitive: bool) -> Arc<Regex> {
-        RegexBuilder::new(pattern)
-            .case_insensitive(case_insensitive)
-            .build()
-            .map_or_else(
-                |e| {
-                    log::warn!("Regex compilation failed: pattern={} error={}", pattern, e);
-                    EMPTY_REGEX_ARC.clone() // 回退到空正则
-                },
-                |re| Arc::new(re),
-            )
+    fn compile_regex(pattern: &Arc<String>, case_insensitive: bool) -> Arc<Regex> {
+        Self::compile_limited(pattern, case_insensitive, |limits| limits.builder(pattern, case_insensitive).build())
+            .unwrap_or_else(|| EMPTY_REGEX_ARC.clone()) // 回退到空正则
+    }
+
+    /// 按当前正则上限（regex_limits）编译，源码超长的正则不编译
+    /// 同一正则的编译错误仅告警一次，重复失败计入 regex_error_stats().suppressed
+    /// 参数：
+    /// - pattern: 正则模式字符串
+    /// - case_insensitive: 是否忽略大小写
+    /// - build: 按上限构建正则（str / bytes）
+    ///
+    /// 返回：编译后的正则Arc | 编译失败
+    fn compile_limited<R>(
+        pattern: &Arc<String>,
+        case_insensitive: bool,
+        build: impl FnOnce(&super::RegexLimits) -> Result<R, regex::Error>,
+    ) -> Option<Arc<R>> {
+        let limits = super::regex_limits();
+        if let Some(violation) = limits.check_len(pattern) {
+            Self::report_regex_error(pattern, case_insensitive, &violation, false);
+            return None;
+        }
+        match build(&limits) {
+            Ok(regex) => Some(Arc::new(regex)),
+            Err(e) => {
+                let too_big = matches!(e, regex::Error::CompiledTooBig(_));
+                Self::report_regex_error(pattern, case_insensitive, &e, too_big);
+                None
+            }
+        }
+    }
+
+    /// 记录正则编译错误（按正则限流：首次告警，后续仅计数）
+    /// 参数：too_big - 是否为编译体积超限（计入当前线程的超限次数）
+    #[cold]
+    fn report_regex_error(
+        pattern: &Arc<String>,
+        case_insensitive: bool,
+        error: &dyn std::fmt::Display,
+        too_big: bool,
+    ) {
+        REGEX_ERROR_TOTAL.fetch_add(1, Ordering::Relaxed);
+        if too_big {
+            THREAD_REGEX_BUDGET_EXCEEDED.with(|count| count.set(count.get() + 1));
+        }
+        let first_seen = REGEX_ERROR_LOGGED
+            .lock()
+            .map(|mut logged| logged.insert((pattern.clone(), case_insensitive)))
+            .unwrap_or(true);
+        if first_seen {
+            log::warn!(
+                "Regex compilation failed: pattern={} error={} (further failures of this pattern are suppressed)",
+                pattern,
+                error
+            );
+        } else {
+            REGEX_ERROR_SUPPRESSED.fetch_add(1, Ordering::Relaxed);
+        }
     }
 
     /// 描述匹配器规则（用于日志/调试输出）
@@ -112,6 +267,7 @@ impl Matcher {
             Matcher::Contains(s) => format!("contains: {}", s),
             Matcher::Exists => "exists".to_string(),
             Matcher::LazyRegex { pattern, .. } => format!("lazy_regex: {}", pattern),
+            Matcher::Custom { kind, pattern, .. } => format!("{}: {}", kind, pattern),
         }
     }
 
@@ -124,6 +280,23 @@ impl Matcher {
             Matcher::Contains(s) => input.contains(s.as_str()),
             Matcher::Exists => true,
             Matcher::LazyRegex { .. } => self.get_compiled_regex().is_match(input),
+            Matcher::Custom { inner, .. } => inner.as_ref().is_some_and(|m| m.matches(input)),
+        }
+    }
+
+    /// 按字节执行匹配（输入非合法 UTF-8 时使用，正则以 regex::bytes 编译，免去容错解码的整段复制）
+    /// 参数：input - 待匹配的字节
+    /// 返回：匹配结果（bool）
+    #[inline]
+    pub fn matches_bytes(&self, input: &[u8]) -> bool {
+        match self {
+            Matcher::Contains(s) => memchr::memmem::find(input, s.as_bytes()).is_some(),
+            Matcher::Exists => true,
+            Matcher::LazyRegex { .. } => self.get_compiled_bytes_regex().is_match(input),
+            // 自定义匹配器仅接受文本，按容错解码后的文本匹配
+            Matcher::Custom { inner, .. } => inner
+                .as_ref()
+                .is_some_and(|m| m.matches(&String::from_utf8_lossy(input))),
         }
     }
 
@@ -139,14 +312,35 @@ impl Matcher {
                 pattern: pattern.to_string(),
                 case_insensitive: *case_insensitive,
             },
+            Matcher::Custom { kind, pattern, .. } => super::MatcherSpec::Custom {
+                kind: kind.to_string(),
+                pattern: pattern.to_string(),
+            },
         }
     }
 
+    /// 通过注册表构建自定义匹配器
+    /// 参数：
+    /// - registry: 自定义匹配类型注册表
+    /// - kind: 匹配类型名
+    /// - pattern: 模式字符串
+    ///
+    /// 返回：运行时匹配器实例 | 错误描述
+    pub fn custom(registry: &MatcherRegistry, kind: &str, pattern: &str) -> Result<Self, String> {
+        let inner = registry.build(kind, pattern)?;
+        Ok(Self::Custom {
+            kind: Arc::new(kind.to_string()),
+            pattern: Arc::new(pattern.to_string()),
+            inner: Some(inner),
+        })
+    }
+
     /// 从匹配类型构建懒加载匹配器
     /// 参数：
-    /// - match_type: 匹配类型（Contains/Exists/Regex）
+    /// - match_type: 匹配类型（Contains/Exists/Regex/Custom）
     /// - pattern: 匹配模式
-    /// 返回：运行时匹配器实例
+    ///
+    /// 返回：运行时匹配器实例（Custom 类型未绑定工厂，需通过 Matcher::custom 构建）
     pub fn from_match_type_lazy(match_type: &MatchType, pattern: &Pattern) -> Self {
         match match_type {
             MatchType::Contains => Self::Contains(Arc::new(pattern.pattern.clone())),
@@ -155,25 +349,41 @@ impl Matcher {
                 pattern: Arc::new(pattern.pattern.clone()),
                 case_insensitive: true,
             },
+            MatchType::Custom(kind) => Self::Custom {
+                kind: Arc::new(kind.clone()),
+                pattern: Arc::new(pattern.pattern.clone()),
+                inner: None,
+            },
         }
     }
 
-    /// 从静态MatcherSpec还原运行态Matcher
-    /// 参数：spec - 静态匹配器描述体
+    /// 从匹配类型构建懒加载匹配器（指定正则大小写策略）
+    /// 参数：
+    /// - match_type: 匹配类型（Contains/Exists/Regex）
+    /// - pattern: 匹配模式
+    /// - case_insensitive: 正则是否忽略大小写（仅Regex类型生效）
+    ///
     /// 返回：运行时匹配器实例
-    pub fn from_spec(spec: &super::MatcherSpec) -> Self {
-        match spec {
-            super::MatcherSpec::Contains(s) => Self::Contains(Arc::new(s.clone())),
-            super::MatcherSpec::Exists => Self::Exists,
-            super::MatcherSpec::Regex {
+    pub fn from_match_type_with_case(
+        match_type: &MatchType,
+        pattern: &Pattern,
+        case_insensitive: bool,
+    ) -> Self {
+        match Self::from_match_type_lazy(match_type, pattern) {
+            Self::LazyRegex { pattern, .. } => Self::LazyRegex {
                 pattern,
                 case_insensitive,
-            } => Self::LazyRegex {
-                pattern: Arc::new(pattern.clone()),
-                case_insensitive: *case_insensitive,
             },
+            other => other,
         }
     }
+
+    /// 从静态MatcherSpec还原运行态Matcher
+    /// 参数：spec - 静态匹配器描述体
+    /// 返回：运行时匹配器实例
+    pub fn from_spec(spec: &super::MatcherSpec) -> Self {
+        spec.to_matcher()
+    }
 }
 
 /// 结构前置条件扩展方法
@@ -206,7 +416,7 @@ impl StructuralPrereq {
                     _ => super::StructuralPrereq::None,
                 }
             }
-            Matcher::Exists => super::StructuralPrereq::None,
+            Matcher::Exists | Matcher::Custom { .. } => super::StructuralPrereq::None,
         }
     }
 
@@ -241,7 +451,7 @@ impl StructuralPrereq {
                     _ => super::StructuralPrereq::None,
                 }
             }
-            Matcher::Exists => super::StructuralPrereq::None,
+            Matcher::Exists | Matcher::Custom { .. } => super::StructuralPrereq::None,
         }
     }
 }
@@ -292,3 +502,67 @@ pub fn fold_to_match_gate(
         _ => super::MatchGate::Open,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 统计为进程级计数，其他测试可能并发计入，只断言本测试造成的最小增量
+    #[test]
+    fn test_regex_error_rate_limited() {
+        let before = regex_error_stats();
+        let pattern = Arc::new("rate-limit-(unclosed".to_string());
+        for _ in 0..3 {
+            // 编译失败回退到全局空正则
+            assert!(Arc::ptr_eq(&Matcher::compile_regex(&pattern, true), &EMPTY_REGEX_ARC));
+        }
+        // 大小写选项不同视为不同正则，单独告警一次
+        Matcher::compile_regex(&pattern, false);
+        let after = regex_error_stats();
+        assert!(after.total_failures >= before.total_failures + 4);
+        assert!(after.suppressed >= before.suppressed + 2);
+        assert!(after.failed_patterns >= before.failed_patterns + 2);
+
+        let logged = REGEX_ERROR_LOGGED.lock().unwrap();
+        assert!(logged.contains(&(pattern.clone(), true)));
+        assert!(logged.contains(&(pattern, false)));
+    }
+
+    #[test]
+    fn test_valid_regex_not_counted() {
+        let pattern = Arc::new("rate-limit-valid-(\\d+)".to_string());
+        let regex = Matcher::compile_regex(&pattern, true);
+        assert!(regex.is_match("RATE-LIMIT-VALID-42"));
+        assert!(!REGEX_ERROR_LOGGED.lock().unwrap().contains(&(pattern, true)));
+    }
+
+    #[test]
+    fn test_matches_bytes_on_invalid_utf8() {
+        // Latin-1 编码的 "café" 后接 ASCII 版本号：非合法 UTF-8
+        let input = b"caf\xe9 AcmeJS/2.4.1 \xff";
+
+        let regex = Matcher::LazyRegex {
+            pattern: Arc::new(r"acmejs/([\d.]+)".to_string()),
+            case_insensitive: true,
+        };
+        assert!(regex.matches_bytes(input));
+        let captures = regex.captures_bytes(input).unwrap();
+        assert_eq!(&captures[1], b"2.4.1");
+        assert!(BYTES_REGEX_CACHE.keys().contains(&(Arc::new(r"acmejs/([\d.]+)".to_string()), true)));
+
+        assert!(Matcher::Contains(Arc::new("AcmeJS".to_string())).matches_bytes(input));
+        assert!(!Matcher::Contains(Arc::new("acmejs".to_string())).matches_bytes(input));
+        assert!(Matcher::Exists.matches_bytes(input));
+        assert!(Matcher::Exists.captures_bytes(input).is_none());
+    }
+
+    #[test]
+    fn test_bytes_regex_error_falls_back_to_empty() {
+        let regex = Matcher::LazyRegex {
+            pattern: Arc::new("bytes-(unclosed".to_string()),
+            case_insensitive: false,
+        };
+        assert!(!regex.matches_bytes(b"bytes-(unclosed"));
+        assert!(Arc::ptr_eq(&regex.get_compiled_bytes_regex(), &EMPTY_BYTES_REGEX_ARC));
+    }
+}
@@ -4,11 +4,42 @@ mod index_rules;
 mod compiled;
 mod library;
 mod builder;
+mod evidence_export;
+mod options;
+mod collision;
+mod custom_matcher;
+mod lifecycle;
+mod validation;
+mod corpus;
+mod hostname;
+mod dedup;
+mod compat;
+mod source_stats;
+mod literal_prefilter;
+mod regex_set;
+mod regex_limits;
 
 // 对外只导出具体内容，不导出模块名
 pub use enums::{MatchGate, StructuralPrereq, MatcherSpec};
-pub use matcher::Matcher;
+pub use matcher::{charge_thread_regex_budget_exceeded, regex_cache_len, regex_cache_stats, regex_error_stats, set_regex_cache_capacity, thread_regex_budget_exceeded, Matcher, RegexErrorStats};
 pub use index_rules::{CommonIndexedRule, ScopedIndexedRule, RawMatchSet, PatternList, PatternMap};
 pub use compiled::{CompiledPattern, CompiledTechRule, ExecutablePattern};
 pub use library::{CompiledRuleLibrary, RuleLibraryIndex};
-pub use builder::RuleIndexer;
\ No newline at end of file
+pub use builder::RuleIndexer;
+pub use custom_matcher::{CustomMatcher, MatcherFactory, MatcherRegistry};
+pub use collision::{CollisionKind, LowSpecificityPolicy, PatternCollision, PatternCollisionReport};
+pub use options::{has_explicit_case_sensitive_flag, CompileOptions};
+pub use lifecycle::{today_utc, ExpiredRulePolicy, LifecycleEntry, LifecycleReport, LifecycleStatus};
+pub use validation::{BrokenPattern, PatternValidation};
+pub use corpus::{synthesize_input, CorpusEntry, PatternCorpus};
+pub use hostname::{parse_hostname_spec, url_host, url_regex_host_suffix, HostnameIndex, HostnameRule};
+pub use dedup::PatternDedupStats;
+pub use compat::{EngineRequirements, COMPILED_FORMAT_VERSION, ENGINE_VERSION};
+pub use source_stats::{SourceRuleStats, UNATTRIBUTED_SOURCE};
+pub use literal_prefilter::{LiteralHits, LiteralPrefilter, PREFILTER_SCOPES};
+pub use regex_set::{PatternRegexSet, RegexSetCache};
+pub use regex_limits::{
+    regex_limits, set_regex_limits, RegexLimitViolation, RegexLimits, RejectedPattern, DEFAULT_MAX_PATTERN_LEN,
+    DEFAULT_REGEX_DFA_SIZE_LIMIT, DEFAULT_REGEX_SIZE_LIMIT,
+};
+pub use evidence_export::{EvidenceTokenExport, EVIDENCE_EXPORT_HEADER};
\ No newline at end of file
@@ -0,0 +1,244 @@
+//! 规则编译选项
+//! 控制 RuleIndexer 编译 CompiledRuleLibrary 时的可调行为，默认值与历史行为完全一致
+
+use rustc_hash::FxHashSet;
+
+use crate::scope_pruner::PruneScope;
+
+use super::{CustomMatcher, ExpiredRulePolicy, LowSpecificityPolicy, MatcherRegistry, PatternValidation, RegexLimits};
+
+/// 规则编译选项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileOptions {
+    /// 正则按大小写敏感编译的作用域集合
+    /// 默认为空：所有作用域的正则均忽略大小写（历史行为）
+    pub case_sensitive_scopes: FxHashSet<PruneScope>,
+    /// 低特异性模式降权策略（默认关闭）
+    pub low_specificity: Option<LowSpecificityPolicy>,
+    /// 过期规则处理策略（默认告警并保留）
+    pub expired_rules: ExpiredRulePolicy,
+    /// 模式校验策略（默认不校验）
+    pub pattern_validation: PatternValidation,
+    /// 自定义匹配类型注册表（默认为空，含自定义匹配类型的模式在编译时被丢弃）
+    pub matchers: MatcherRegistry,
+    /// 移除同一技术同一作用域内的等价重复模式（默认开启）
+    pub dedup_patterns: bool,
+    /// 仅编译的作用域集合（默认 None 编译全部作用域）
+    /// 仅做 Header 检测的被动监控部署可跳过 HTML/Script 等模式集，缩短启动时间并降低内存
+    pub scopes: Option<FxHashSet<PruneScope>>,
+    /// 正则体积/复杂度上限（超长正则编译期剔除，运行期懒编译按体积上限构建）
+    pub regex_limits: RegexLimits,
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        Self {
+            case_sensitive_scopes: FxHashSet::default(),
+            low_specificity: None,
+            expired_rules: ExpiredRulePolicy::default(),
+            pattern_validation: PatternValidation::default(),
+            matchers: MatcherRegistry::default(),
+            dedup_patterns: true,
+            scopes: None,
+            regex_limits: RegexLimits::default(),
+        }
+    }
+}
+
+impl CompileOptions {
+    /// 创建默认编译选项
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 将指定作用域的正则设为大小写敏感（链式）
+    pub fn case_sensitive_scope(mut self, scope: PruneScope) -> Self {
+        self.case_sensitive_scopes.insert(scope);
+        self
+    }
+
+    /// 启用低特异性模式降权（链式）
+    pub fn low_specificity(mut self, policy: LowSpecificityPolicy) -> Self {
+        self.low_specificity = Some(policy);
+        self
+    }
+
+    /// 设置过期规则处理策略（链式）
+    pub fn expired_rules(mut self, policy: ExpiredRulePolicy) -> Self {
+        self.expired_rules = policy;
+        self
+    }
+
+    /// 设置模式校验策略（链式）
+    pub fn pattern_validation(mut self, mode: PatternValidation) -> Self {
+        self.pattern_validation = mode;
+        self
+    }
+
+    /// 设置是否去重等价模式（链式）
+    pub fn dedup_patterns(mut self, enabled: bool) -> Self {
+        self.dedup_patterns = enabled;
+        self
+    }
+
+    /// 设置自定义匹配类型注册表（链式）
+    pub fn matchers(mut self, registry: MatcherRegistry) -> Self {
+        self.matchers = registry;
+        self
+    }
+
+    /// 设置正则体积/复杂度上限（链式）
+    pub fn regex_limits(mut self, limits: RegexLimits) -> Self {
+        self.regex_limits = limits;
+        self
+    }
+
+    /// 仅编译指定作用域的模式（链式）
+    pub fn scopes(mut self, scopes: impl IntoIterator<Item = PruneScope>) -> Self {
+        self.scopes = Some(scopes.into_iter().collect());
+        self
+    }
+
+    /// 作用域是否需要编译
+    #[inline]
+    pub fn compiles_scope(&self, scope: PruneScope) -> bool {
+        self.scopes.as_ref().is_none_or(|scopes| scopes.contains(&scope))
+    }
+
+    /// 注册单个自定义匹配类型（链式）
+    pub fn register_matcher<F, M>(mut self, kind: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn(&str) -> Result<M, String> + Send + Sync + 'static,
+        M: CustomMatcher + 'static,
+    {
+        self.matchers.register(kind, factory);
+        self
+    }
+
+    /// 计算指定作用域下某条正则的最终大小写策略
+    /// 优先级：源码显式 `(?-i)` > 作用域配置 > 默认忽略大小写
+    /// 参数：
+    /// - scope: 剪枝作用域
+    /// - pattern: 正则源码
+    ///
+    /// 返回：是否忽略大小写
+    #[inline]
+    pub fn regex_case_insensitive(&self, scope: PruneScope, pattern: &str) -> bool {
+        if has_explicit_case_sensitive_flag(pattern) {
+            return false;
+        }
+        !self.case_sensitive_scopes.contains(&scope)
+    }
+}
+
+/// 判断正则源码是否以显式关闭忽略大小写的内联标志开头
+/// 支持 `(?-i)`、`(?s-i)`、`(?-si)` 等前导标志组（不含 `:` 的纯标志组）
+pub fn has_explicit_case_sensitive_flag(pattern: &str) -> bool {
+    let Some(rest) = pattern.strip_prefix("(?") else {
+        return false;
+    };
+    let Some(end) = rest.find(')') else {
+        return false;
+    };
+    let flags = &rest[..end];
+    if !flags.chars().all(|c| matches!(c, 'i' | 'm' | 's' | 'u' | 'x' | 'U' | 'R' | '-')) {
+        return false;
+    }
+    flags
+        .split_once('-')
+        .is_some_and(|(_, disabled)| disabled.contains('i'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{compile, compile_with};
+    use crate::CompiledRuleLibrary;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Acme": {
+                "cats": [1],
+                "url": "acme-[0-9]+",
+                "html": "acme-[0-9]+",
+                "scripts": "acme-[0-9]+",
+                "meta": { "generator": "acme-[0-9]+" },
+                "headers": { "X-Powered-By": "acme-[0-9]+" },
+                "cookies": { "acme_session": "acme-[0-9]+" }
+            },
+            "Strict": { "cats": [1], "html": "(?-i)strict-[0-9]+" }
+        }
+    }"#;
+
+    const SCOPES: [PruneScope; 6] = [
+        PruneScope::Url,
+        PruneScope::Html,
+        PruneScope::Script,
+        PruneScope::Meta,
+        PruneScope::Header,
+        PruneScope::Cookie,
+    ];
+
+    /// 指定技术在某作用域下的模式对输入的匹配结果
+    fn matches(lib: &CompiledRuleLibrary, tech: &str, scope: PruneScope, input: &str) -> bool {
+        let mut hit = None;
+        lib.tech_patterns[tech].for_each_pattern(|pat| {
+            if pat.scope == scope {
+                hit = Some(hit.unwrap_or(false) | pat.matches(input));
+            }
+        });
+        hit.unwrap_or_else(|| panic!("{} has no {:?} pattern", tech, scope))
+    }
+
+    #[test]
+    fn test_default_scopes_ignore_case() {
+        let lib = compile(RULES);
+        for scope in SCOPES {
+            assert!(matches(&lib, "Acme", scope, "ACME-12"), "{:?}", scope);
+            assert!(matches(&lib, "Acme", scope, "acme-12"), "{:?}", scope);
+        }
+    }
+
+    #[test]
+    fn test_case_sensitive_scope() {
+        for scope in SCOPES {
+            let lib = compile_with(RULES, &CompileOptions::new().case_sensitive_scope(scope));
+            assert!(!matches(&lib, "Acme", scope, "ACME-12"), "{:?}", scope);
+            assert!(matches(&lib, "Acme", scope, "acme-12"), "{:?}", scope);
+            // 其余作用域保持忽略大小写
+            for other in SCOPES.into_iter().filter(|other| *other != scope) {
+                assert!(matches(&lib, "Acme", other, "ACME-12"), "{:?} with {:?} sensitive", other, scope);
+            }
+        }
+    }
+
+    #[test]
+    fn test_explicit_flag_overrides_scope() {
+        // 源码 (?-i) 优先于作用域配置
+        let lib = compile(RULES);
+        assert!(!matches(&lib, "Strict", PruneScope::Html, "STRICT-1"));
+        assert!(matches(&lib, "Strict", PruneScope::Html, "strict-1"));
+
+        let options = CompileOptions::new().case_sensitive_scope(PruneScope::Html);
+        assert!(!options.regex_case_insensitive(PruneScope::Html, "acme"));
+        assert!(options.regex_case_insensitive(PruneScope::Url, "acme"));
+        assert!(!options.regex_case_insensitive(PruneScope::Url, "(?-i)acme"));
+
+        // 已编译规则库按选项重写大小写策略
+        let mut lib = compile(RULES);
+        assert_eq!(lib.apply_compile_options(&options), 1);
+        assert!(!matches(&lib, "Acme", PruneScope::Html, "ACME-12"));
+        assert!(matches(&lib, "Acme", PruneScope::Url, "ACME-12"));
+    }
+
+    #[test]
+    fn test_has_explicit_case_sensitive_flag() {
+        assert!(has_explicit_case_sensitive_flag("(?-i)acme"));
+        assert!(has_explicit_case_sensitive_flag("(?s-i)acme"));
+        assert!(has_explicit_case_sensitive_flag("(?-si)acme"));
+        assert!(!has_explicit_case_sensitive_flag("(?i)acme"));
+        assert!(!has_explicit_case_sensitive_flag("(?-s)acme"));
+        assert!(!has_explicit_case_sensitive_flag("(?-i:acme)"));
+        assert!(!has_explicit_case_sensitive_flag("acme(?-i)"));
+    }
+}
@@ -0,0 +1,237 @@
+//! 正则体积/复杂度上限
+//! 自定义规则中的病态正则（超长源码、编译后状态机膨胀）会拖慢编译并占用大量内存。
+//! 按 RegexLimits 约束正则：
+//! 1. 源码长度超出 max_pattern_len 的正则在编译期剔除（仅比较长度，始终执行）
+//! 2. 编译体积超出 size_limit 的正则：启用模式校验时在预编译阶段剔除，否则运行期懒编译失败后回退为空正则并告警
+//! 3. 剔除的模式连同结构化原因记录到 CompiledRuleLibrary.rejected_patterns
+//!
+//! 运行期懒编译共用全局正则缓存，沿用最近一次编译/加载规则库时设置的上限（进程级）
+
+use std::sync::{PoisonError, RwLock};
+
+use once_cell::sync::Lazy;
+use regex::RegexBuilder;
+use serde::{Deserialize, Serialize};
+
+use super::{CompiledPattern, CompiledRuleLibrary, MatcherSpec};
+use crate::scope_pruner::PruneScope;
+
+/// 默认编译体积上限（与 regex 默认值一致）
+pub const DEFAULT_REGEX_SIZE_LIMIT: usize = 10 * (1 << 20);
+/// 默认懒 DFA 缓存上限（与 regex 默认值一致）
+pub const DEFAULT_REGEX_DFA_SIZE_LIMIT: usize = 2 * (1 << 20);
+/// 默认正则源码长度上限（内置规则库最长正则约 1.1K 字符）
+pub const DEFAULT_MAX_PATTERN_LEN: usize = 4096;
+
+/// 运行期懒编译使用的正则上限（进程级）
+static ACTIVE_REGEX_LIMITS: Lazy<RwLock<RegexLimits>> = Lazy::new(|| RwLock::new(RegexLimits::default()));
+
+/// 正则体积/复杂度上限
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RegexLimits {
+    /// 编译后程序体积上限（字节，对应 RegexBuilder::size_limit）
+    pub size_limit: usize,
+    /// 懒 DFA 缓存上限（字节，对应 RegexBuilder::dfa_size_limit）
+    pub dfa_size_limit: usize,
+    /// 正则源码长度上限（字节）
+    pub max_pattern_len: usize,
+}
+
+impl Default for RegexLimits {
+    fn default() -> Self {
+        Self {
+            size_limit: DEFAULT_REGEX_SIZE_LIMIT,
+            dfa_size_limit: DEFAULT_REGEX_DFA_SIZE_LIMIT,
+            max_pattern_len: DEFAULT_MAX_PATTERN_LEN,
+        }
+    }
+}
+
+impl RegexLimits {
+    /// 创建默认上限
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置编译体积上限（链式）
+    pub fn size_limit(mut self, bytes: usize) -> Self {
+        self.size_limit = bytes;
+        self
+    }
+
+    /// 设置懒 DFA 缓存上限（链式）
+    pub fn dfa_size_limit(mut self, bytes: usize) -> Self {
+        self.dfa_size_limit = bytes;
+        self
+    }
+
+    /// 设置正则源码长度上限（链式）
+    pub fn max_pattern_len(mut self, len: usize) -> Self {
+        self.max_pattern_len = len;
+        self
+    }
+
+    /// 按上限创建正则构建器
+    /// 参数：
+    /// - pattern: 正则源码
+    /// - case_insensitive: 是否忽略大小写
+    ///
+    /// 返回：正则构建器
+    pub fn builder(&self, pattern: &str, case_insensitive: bool) -> RegexBuilder {
+        let mut builder = RegexBuilder::new(pattern);
+        builder
+            .case_insensitive(case_insensitive)
+            .size_limit(self.size_limit)
+            .dfa_size_limit(self.dfa_size_limit);
+        builder
+    }
+
+    /// 按上限创建字节正则构建器（regex::bytes，匹配非 UTF-8 输入）
+    /// 参数：
+    /// - pattern: 正则源码
+    /// - case_insensitive: 是否忽略大小写
+    ///
+    /// 返回：字节正则构建器
+    pub fn bytes_builder(&self, pattern: &str, case_insensitive: bool) -> regex::bytes::RegexBuilder {
+        let mut builder = regex::bytes::RegexBuilder::new(pattern);
+        builder
+            .case_insensitive(case_insensitive)
+            .size_limit(self.size_limit)
+            .dfa_size_limit(self.dfa_size_limit);
+        builder
+    }
+
+    /// 检查正则源码长度
+    /// 参数：pattern - 正则源码
+    ///
+    /// 返回：超出上限时的违规原因
+    pub fn check_len(&self, pattern: &str) -> Option<RegexLimitViolation> {
+        (pattern.len() > self.max_pattern_len).then_some(RegexLimitViolation::PatternTooLong {
+            len: pattern.len(),
+            max: self.max_pattern_len,
+        })
+    }
+}
+
+/// 设置运行期懒编译使用的正则上限（进程级，已缓存的正则不受影响）
+/// 参数：limits - 正则上限
+pub fn set_regex_limits(limits: RegexLimits) {
+    *ACTIVE_REGEX_LIMITS.write().unwrap_or_else(PoisonError::into_inner) = limits;
+}
+
+/// 当前运行期懒编译使用的正则上限
+pub fn regex_limits() -> RegexLimits {
+    *ACTIVE_REGEX_LIMITS.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// 正则违规原因
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RegexLimitViolation {
+    /// 源码长度超出上限
+    PatternTooLong {
+        /// 源码长度
+        len: usize,
+        /// 长度上限
+        max: usize,
+    },
+    /// 编译体积超出上限
+    CompiledTooBig {
+        /// 体积上限
+        limit: usize,
+    },
+}
+
+impl std::fmt::Display for RegexLimitViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PatternTooLong { len, max } => write!(f, "pattern length {} exceeds limit {}", len, max),
+            Self::CompiledTooBig { limit } => write!(f, "compiled regex exceeds size limit {}", limit),
+        }
+    }
+}
+
+/// 因超出正则上限被剔除的模式
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RejectedPattern {
+    /// 技术名称
+    pub tech: String,
+    /// 剪枝作用域
+    pub scope: PruneScope,
+    /// KV型规则的键名（Header/Cookie/Meta）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// 正则源码
+    pub pattern: String,
+    /// 违规原因
+    pub reason: RegexLimitViolation,
+}
+
+impl RejectedPattern {
+    fn new(tech: &str, pat: &CompiledPattern, pattern: &str, reason: RegexLimitViolation) -> Self {
+        Self {
+            tech: tech.to_string(),
+            scope: pat.scope,
+            key: (!pat.index_key.is_empty()).then(|| pat.index_key.clone()),
+            pattern: pattern.to_string(),
+            reason,
+        }
+    }
+}
+
+impl std::fmt::Display for RejectedPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{:?}", self.tech, self.scope)?;
+        if let Some(key) = &self.key {
+            write!(f, "[{}]", key)?;
+        }
+        write!(f, ": {}", self.reason)
+    }
+}
+
+impl CompiledRuleLibrary {
+    /// 剔除源码长度超出上限的正则模式（记录到 rejected_patterns）
+    /// 参数：limits - 正则上限
+    ///
+    /// 返回：本次剔除的模式数量
+    pub fn enforce_regex_limits(&mut self, limits: &RegexLimits) -> usize {
+        self.reject_patterns(|pattern, _| limits.check_len(pattern))
+    }
+
+    /// 按判定函数剔除正则模式，违规模式连同原因追加到 rejected_patterns 并告警
+    /// 参数：check - (正则源码, 是否忽略大小写) -> 违规原因
+    ///
+    /// 返回：本次剔除的模式数量
+    pub(crate) fn reject_patterns(
+        &mut self,
+        mut check: impl FnMut(&str, bool) -> Option<RegexLimitViolation>,
+    ) -> usize {
+        let mut rejected = Vec::new();
+        for (tech_name, tech) in self.tech_patterns.iter_mut() {
+            tech.retain_patterns(|pat| {
+                let MatcherSpec::Regex {
+                    pattern,
+                    case_insensitive,
+                } = &pat.exec.matcher
+                else {
+                    return true;
+                };
+                match check(pattern, *case_insensitive) {
+                    Some(reason) => {
+                        rejected.push(RejectedPattern::new(tech_name, pat, pattern, reason));
+                        false
+                    }
+                    None => true,
+                }
+            });
+        }
+        rejected.sort_by(|a, b| (&a.tech, a.scope as u8, &a.key).cmp(&(&b.tech, b.scope as u8, &b.key)));
+
+        for entry in &rejected {
+            log::warn!("Rejected pattern | {}", entry);
+        }
+        let count = rejected.len();
+        self.rejected_patterns.extend(rejected);
+        count
+    }
+}
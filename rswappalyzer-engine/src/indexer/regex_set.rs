@@ -0,0 +1,239 @@
+//! 分组正则批量匹配（RegexSet）
+//! 同一技术同一作用域（同一 Key）下常有数十条正则，逐条 is_match 会反复扫描同一输入。
+//! 按（作用域, Key）分组将正则模式编译为单个 RegexSet，一次扫描即可得知哪些正则命中：
+//! 1. 懒加载（首次使用时构建整个技术的分组），不序列化；克隆或调整模式后重置
+//! 2. 仅对含 ≥2 条正则模式的分组构建；集合编译失败（含非法正则、超出体积上限）时不构建，回退逐条匹配
+//! 3. 集合仅回答“是否命中”，版本提取仍由单条正则的 captures 完成
+
+use std::sync::Arc;
+
+use once_cell::sync::OnceCell;
+use regex::{RegexSet, RegexSetBuilder};
+use rustc_hash::FxHashMap;
+
+use super::{CompiledPattern, CompiledTechRule, MatcherSpec};
+use crate::scope_pruner::PruneScope;
+
+/// 单个分组的正则集合
+#[derive(Debug)]
+pub struct PatternRegexSet {
+    set: RegexSet,
+    /// 集合成员序号 -> 分组内模式下标
+    members: Vec<usize>,
+    /// 分组内模式总数
+    pattern_count: usize,
+}
+
+impl PatternRegexSet {
+    /// 由分组内的模式构建正则集合（大小写策略按成员内联）
+    /// 参数：patterns - 分组内的模式
+    ///
+    /// 返回：正则集合（正则模式少于 2 条或编译失败时返回 None）
+    pub fn build(patterns: &[CompiledPattern]) -> Option<Self> {
+        let mut members = Vec::new();
+        let mut regexes = Vec::new();
+        for (index, pattern) in patterns.iter().enumerate() {
+            if let MatcherSpec::Regex {
+                pattern,
+                case_insensitive,
+            } = &pattern.exec.matcher
+            {
+                members.push(index);
+                regexes.push(if *case_insensitive {
+                    format!("(?i:{})", pattern)
+                } else {
+                    format!("(?:{})", pattern)
+                });
+            }
+        }
+        if members.len() < 2 {
+            return None;
+        }
+        match RegexSetBuilder::new(&regexes).build() {
+            Ok(set) => Some(Self {
+                set,
+                members,
+                pattern_count: patterns.len(),
+            }),
+            Err(e) => {
+                log::debug!("Regex set skipped | Regexes: {} | Error: {}", regexes.len(), e);
+                None
+            }
+        }
+    }
+
+    /// 集合内的正则数量
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// 集合是否为空（构建成功的集合至少包含 2 条正则）
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// 一次扫描输入，返回分组内各模式的正则命中结果
+    /// 参数：input - 待匹配字符串
+    ///
+    /// 返回：按模式下标索引的命中结果（非正则模式为 None）
+    pub fn matches(&self, input: &str) -> Vec<Option<bool>> {
+        let mut hits = vec![None; self.pattern_count];
+        for &index in &self.members {
+            hits[index] = Some(false);
+        }
+        for member in self.set.matches(input).iter() {
+            hits[self.members[member]] = Some(true);
+        }
+        hits
+    }
+}
+
+/// 技术内各分组的正则集合缓存（克隆时重置，随模式调整失效）
+#[derive(Default)]
+pub struct RegexSetCache(OnceCell<FxHashMap<(PruneScope, String), Arc<PatternRegexSet>>>);
+
+impl Clone for RegexSetCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl std::fmt::Debug for RegexSetCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RegexSetCache")
+            .field("groups", &self.0.get().map(FxHashMap::len))
+            .finish()
+    }
+}
+
+impl CompiledTechRule {
+    /// 指定分组的正则集合（列表型作用域的 Key 为空串）
+    /// 参数：
+    /// - scope: 作用域
+    /// - key: 分组 Key（Header/Meta 名称等）
+    ///
+    /// 返回：正则集合（分组不足 2 条正则或编译失败时返回 None）
+    pub fn regex_set(&self, scope: PruneScope, key: &str) -> Option<&PatternRegexSet> {
+        let sets = self.regex_sets.0.get_or_init(|| {
+            let mut sets = FxHashMap::default();
+            self.for_each_group(|scope, key, patterns| {
+                if let Some(set) = PatternRegexSet::build(patterns) {
+                    sets.insert((scope, key.to_string()), Arc::new(set));
+                }
+            });
+            sets
+        });
+        sets.get(&(scope, key.to_string())).map(Arc::as_ref)
+    }
+
+    /// 重置正则集合缓存（模式列表被直接修改后调用）
+    pub fn reset_regex_sets(&mut self) {
+        self.regex_sets = RegexSetCache::default();
+    }
+
+    /// 遍历该技术的全部（作用域, Key）分组
+    fn for_each_group(&self, mut f: impl FnMut(PruneScope, &str, &[CompiledPattern])) {
+        for (scope, list) in [
+            (PruneScope::Url, &self.url_patterns),
+            (PruneScope::Html, &self.html_patterns),
+            (PruneScope::Script, &self.script_patterns),
+            (PruneScope::CertIssuer, &self.cert_issuer_patterns),
+            (PruneScope::FaviconHash, &self.favicon_hash_patterns),
+            (PruneScope::Title, &self.title_patterns),
+            (PruneScope::Comment, &self.comment_patterns),
+        ] {
+            if let Some(list) = list {
+                f(scope, "", list);
+            }
+        }
+        for (scope, map) in [
+            (PruneScope::Meta, &self.meta_patterns),
+            (PruneScope::Header, &self.header_patterns),
+            (PruneScope::Cookie, &self.cookie_patterns),
+            (PruneScope::Js, &self.js_patterns),
+            (PruneScope::Dom, &self.dom_patterns),
+            (PruneScope::Dns, &self.dns_patterns),
+        ] {
+            for (key, list) in map.iter().flatten() {
+                f(scope, key, list);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::compile;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Acme CMS": {
+                "cats": [1],
+                "html": [
+                    "<meta name=\"acme-cms-build\" content=\"([\\d.]+)\"\\;version:\\1",
+                    "acme-cms-widget-[a-z]+",
+                    "data-acme-(?:theme|skin)=\"dark\""
+                ],
+                "dns": { "TXT": ["^acme-[0-9]+$", "^edge-[a-z]+$"], "MX": "^mx[0-9]\\.acme\\.net$" }
+            },
+            "Solo": { "cats": [1], "html": "solo-engine-v[0-9]+" }
+        }
+    }"#;
+
+    #[test]
+    fn test_matches_agree_with_single_patterns() {
+        let lib = compile(RULES);
+        let tech = &lib.tech_patterns["Acme CMS"];
+        let patterns = tech.html_patterns.as_ref().unwrap();
+        let set = tech.regex_set(PruneScope::Html, "").expect("group with several regexes");
+        let regexes = patterns
+            .iter()
+            .filter(|pattern| matches!(pattern.exec.matcher, MatcherSpec::Regex { .. }))
+            .count();
+        assert_eq!(set.len(), regexes);
+
+        for input in [
+            r#"<div class="acme-cms-widget-nav" data-acme-skin="dark"></div>"#,
+            r#"<meta name="acme-cms-build" content="4.2.1">"#,
+            "<p>nothing here</p>",
+        ] {
+            let hits = set.matches(input);
+            assert_eq!(hits.len(), patterns.len());
+            for (pattern, hit) in patterns.iter().zip(hits) {
+                if let Some(hit) = hit {
+                    assert_eq!(hit, pattern.matches(input), "{}", pattern.describe_rule());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_keyed_groups() {
+        let lib = compile(RULES);
+        let tech = &lib.tech_patterns["Acme CMS"];
+        // KV 作用域按 Key 分组，单条正则的分组不构建集合
+        let set = tech.regex_set(PruneScope::Dns, "txt").unwrap();
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.matches("edge-eu"), [Some(false), Some(true)]);
+        assert!(tech.regex_set(PruneScope::Dns, "mx").is_none());
+        assert!(tech.regex_set(PruneScope::Header, "txt").is_none());
+        assert!(lib.tech_patterns["Solo"].regex_set(PruneScope::Html, "").is_none());
+    }
+
+    #[test]
+    fn test_cache_reset() {
+        let lib = compile(RULES);
+        let tech = &lib.tech_patterns["Acme CMS"];
+        assert!(tech.regex_set(PruneScope::Html, "").is_some());
+        // 克隆后缓存重置并按需重建
+        let mut cloned = tech.clone();
+        assert_eq!(format!("{:?}", cloned.regex_sets), "RegexSetCache { groups: None }");
+        assert!(cloned.regex_set(PruneScope::Html, "").is_some());
+
+        // 调整模式后重置，按新模式重建
+        cloned.html_patterns.as_mut().unwrap().truncate(1);
+        cloned.reset_regex_sets();
+        assert!(cloned.regex_set(PruneScope::Html, "").is_none());
+    }
+}
@@ -0,0 +1,36 @@
+//! 按规则源统计
+//! 多个规则源（官方规则 + 内部补充规则等）合并编译后，各技术记录其所属规则源，
+//! 按规则源汇总技术数与模式数，便于对比各规则源的规模（检测命中数由检测器侧统计）
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::CompiledRuleLibrary;
+
+/// 未记录规则源的技术归入的统计键
+pub const UNATTRIBUTED_SOURCE: &str = "unattributed";
+
+/// 单个规则源的规则统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceRuleStats {
+    /// 技术数量
+    pub tech_count: usize,
+    /// 模式数量（主机名索引中的模式不计入）
+    pub pattern_count: usize,
+}
+
+impl CompiledRuleLibrary {
+    /// 按规则源统计技术数与模式数
+    /// 返回：规则源名称 -> 规则统计（有序；未记录规则源的技术归入 UNATTRIBUTED_SOURCE）
+    pub fn source_stats(&self) -> BTreeMap<String, SourceRuleStats> {
+        let mut stats: BTreeMap<String, SourceRuleStats> = BTreeMap::new();
+        for tech in self.tech_patterns.values() {
+            let source = tech.source.as_deref().unwrap_or(UNATTRIBUTED_SOURCE);
+            let entry = stats.entry(source.to_string()).or_default();
+            entry.tech_count += 1;
+            tech.for_each_pattern(|_| entry.pattern_count += 1);
+        }
+        stats
+    }
+}
@@ -0,0 +1,197 @@
+//! 编译期模式校验
+//! 正则编译失败时运行期会回退为永不匹配的空正则，规则缺陷被静默隐藏；
+//! 按 PatternValidation 在加载时预编译全部正则：
+//! 1. Lenient：失败模式记录到 CompiledRuleLibrary.broken_patterns 并告警
+//! 2. Strict：存在失败模式时作为加载错误返回
+//!
+//! 预编译按当前正则上限（regex_limits）构建，编译体积超限的模式不计入失败，而是剔除并记录到 rejected_patterns
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use super::{CompiledRuleLibrary, MatcherSpec, RegexLimitViolation};
+use crate::{scope_pruner::PruneScope, CoreError, CoreResult};
+
+/// 严格模式错误信息中最多列出的失败模式数量
+const STRICT_ERROR_PREVIEW: usize = 5;
+
+/// 模式校验策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PatternValidation {
+    /// 不预编译校验（默认，正则首次匹配时懒编译，失败回退为空正则）
+    #[default]
+    Off,
+    /// 预编译校验，失败模式记录到 broken_patterns 并告警
+    Lenient,
+    /// 预编译校验，存在失败模式时加载失败
+    Strict,
+}
+
+/// 编译失败的模式
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BrokenPattern {
+    /// 技术名称
+    pub tech: String,
+    /// 剪枝作用域
+    pub scope: PruneScope,
+    /// KV型规则的键名（Header/Cookie/Meta）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// 正则源码
+    pub pattern: String,
+    /// 编译错误信息
+    pub error: String,
+}
+
+impl std::fmt::Display for BrokenPattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{:?}", self.tech, self.scope)?;
+        if let Some(key) = &self.key {
+            write!(f, "[{}]", key)?;
+        }
+        write!(f, ": {}", self.pattern)
+    }
+}
+
+impl CompiledRuleLibrary {
+    /// 按策略预编译校验全部正则模式
+    /// 失败模式记录到 broken_patterns（按技术名/作用域排序）
+    /// 参数：mode - 模式校验策略
+    /// 返回：失败模式数量 | 严格模式下存在失败模式时返回错误
+    pub fn validate_patterns(&mut self, mode: PatternValidation) -> CoreResult<usize> {
+        if mode == PatternValidation::Off {
+            return Ok(0);
+        }
+
+        // 同一正则（含大小写策略）仅编译一次
+        let limits = super::regex_limits();
+        let mut compiled: FxHashMap<(String, bool), Option<regex::Error>> = FxHashMap::default();
+        let mut broken = Vec::new();
+        for (tech_name, tech) in &self.tech_patterns {
+            tech.for_each_pattern(|pat| {
+                let MatcherSpec::Regex {
+                    pattern,
+                    case_insensitive,
+                } = &pat.exec.matcher
+                else {
+                    return;
+                };
+                let error = compiled
+                    .entry((pattern.clone(), *case_insensitive))
+                    .or_insert_with(|| limits.builder(pattern, *case_insensitive).build().err());
+                if let Some(error) = error.as_ref().filter(|e| !matches!(e, regex::Error::CompiledTooBig(_))) {
+                    broken.push(BrokenPattern {
+                        tech: tech_name.clone(),
+                        scope: pat.scope,
+                        key: (!pat.index_key.is_empty()).then(|| pat.index_key.clone()),
+                        pattern: pattern.clone(),
+                        error: error.to_string(),
+                    });
+                }
+            });
+        }
+        self.reject_patterns(|pattern, case_insensitive| {
+            matches!(
+                compiled.get(&(pattern.to_string(), case_insensitive)),
+                Some(Some(regex::Error::CompiledTooBig(_)))
+            )
+            .then_some(RegexLimitViolation::CompiledTooBig {
+                limit: limits.size_limit,
+            })
+        });
+        broken.sort_by(|a, b| {
+            (&a.tech, a.scope as u8, &a.key).cmp(&(&b.tech, b.scope as u8, &b.key))
+        });
+
+        for entry in &broken {
+            log::warn!("Broken pattern | {} | Error: {}", entry, entry.error);
+        }
+        let count = broken.len();
+        self.broken_patterns = broken;
+
+        if mode == PatternValidation::Strict {
+            if let Some(err) = self.broken_patterns_error() {
+                return Err(err);
+            }
+        }
+        Ok(count)
+    }
+
+    /// 将已记录的失败模式汇总为错误（最多列出前几条）
+    /// 返回：无失败模式时为 None
+    pub fn broken_patterns_error(&self) -> Option<CoreError> {
+        if self.broken_patterns.is_empty() {
+            return None;
+        }
+        let preview = self
+            .broken_patterns
+            .iter()
+            .take(STRICT_ERROR_PREVIEW)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ");
+        Some(CoreError::BrokenPatterns(format!(
+            "{} pattern(s) failed to compile: {}",
+            self.broken_patterns.len(),
+            preview
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::source::WappalyzerParser;
+    use crate::test_support::{compile, compile_with};
+    use crate::{CompileOptions, RuleIndexer, RuleLibraryIndex, RuleProcessor};
+
+    const RULES: &str = r#"{"technologies": {
+        "Acme": { "headers": { "X-Acme": "[z-a]acme" }, "html": "acme-(\\d+)" },
+        "Widget": { "html": "widget-[z-a]" }
+    }}"#;
+
+    fn options(mode: PatternValidation) -> CompileOptions {
+        CompileOptions::default().pattern_validation(mode)
+    }
+
+    #[test]
+    fn test_off_records_nothing() {
+        let library = compile(RULES);
+        assert!(library.broken_patterns.is_empty());
+        assert!(library.broken_patterns_error().is_none());
+    }
+
+    #[test]
+    fn test_lenient_report() {
+        let library = compile_with(RULES, &options(PatternValidation::Lenient));
+        let report: Vec<String> = library.broken_patterns.iter().map(ToString::to_string).collect();
+        assert_eq!(report, vec!["Acme/Header[x-acme]: [z-a]acme", "Widget/Html: widget-[z-a]"]);
+        assert!(library.broken_patterns.iter().all(|p| !p.error.is_empty()));
+
+        let error = library.broken_patterns_error().unwrap().to_string();
+        assert!(error.starts_with("Broken patterns: 2 pattern(s) failed to compile: Acme/Header"));
+    }
+
+    #[test]
+    fn test_strict_fails() {
+        let options = options(PatternValidation::Strict);
+        let raw = WappalyzerParser.parse_to_rule_lib(RULES).unwrap();
+        let rule_lib = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+        let index = RuleLibraryIndex::from_rule_library(&rule_lib).unwrap();
+        let result = RuleIndexer::build_compiled_library_with_category_map(
+            &index,
+            FxHashMap::default(),
+            &options,
+        );
+        assert!(matches!(result, Err(CoreError::BrokenPatterns(_))));
+    }
+
+    #[test]
+    fn test_valid_rules_pass_strict() {
+        let library = compile_with(
+            r#"{"technologies": { "Acme": { "html": "acme-(\\d+)" } }}"#,
+            &options(PatternValidation::Strict),
+        );
+        assert!(library.broken_patterns.is_empty());
+    }
+}
@@ -28,8 +28,13 @@ pub mod regex_filter;
 pub mod cleaner;
 /// 自定义错误
 pub mod error;
+/// 规则编写诊断报告（正则错误源码标注）
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
 // 通用工具函数
 pub mod utils;
+#[cfg(test)]
+pub(crate) mod test_support;
 
 // 导出业务层顶层结构体/枚举/单例
 pub use core::*;
@@ -38,3 +43,5 @@ pub use processor::*;
 pub use pruner::*;
 pub use utils::*;
 pub use error::*;
+#[cfg(feature = "diagnostics")]
+pub use diagnostics::{diagnose_pattern, PatternDiagnostic};
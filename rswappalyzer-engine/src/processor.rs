@@ -14,11 +14,14 @@ impl RuleProcessor {
 
     /// 构建索引库
     pub fn build_index(&self, rule_lib: &RuleLibrary) -> RuleLibraryIndex {
-        let mut index = RuleLibraryIndex::default();
+        let mut index = RuleLibraryIndex {
+            meta_table: rule_lib.meta_table.clone(),
+            ..RuleLibraryIndex::default()
+        };
 
-        // 辅助函数：判断是否为 KV 型作用域（Header/Meta/Cookie）
+        // 辅助函数：判断是否为 KV 型作用域（Header/Meta/Cookie/Dom/Dns）
         fn is_keyed_scope(scope: &MatchScope) -> bool {
-            matches!(scope, MatchScope::Header | MatchScope::Meta | MatchScope::Cookie)
+            matches!(scope, MatchScope::Header | MatchScope::Meta | MatchScope::Cookie | MatchScope::Dom | MatchScope::Dns)
         }
 
         for (tech_name, tech_rule) in &rule_lib.core_tech_map {
@@ -69,7 +72,7 @@ impl RuleProcessor {
         };
 
         log::debug!(
-            "索引构建完成：URL={}, HTML={}, Script={}, ScriptSrc={}, Meta={}, Header={}, Cookie={}, Js={}",
+            "索引构建完成：URL={}, HTML={}, Script={}, ScriptSrc={}, Meta={}, Header={}, Cookie={}, Js={}, Dom={}, Dns={}",
             get_rule_count(&MatchScope::Url),
             get_rule_count(&MatchScope::Html),
             get_rule_count(&MatchScope::Script),
@@ -78,6 +81,8 @@ impl RuleProcessor {
             get_rule_count(&MatchScope::Header),
             get_rule_count(&MatchScope::Cookie),
             get_rule_count(&MatchScope::Js),
+            get_rule_count(&MatchScope::Dom),
+            get_rule_count(&MatchScope::Dns),
         );
 
         index
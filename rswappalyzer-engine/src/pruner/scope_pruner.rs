@@ -17,6 +17,69 @@ pub enum PruneScope {
     Header,
     Meta,
     Cookie,
+    /// 主机名后缀（后缀字典树匹配，不参与令牌剪枝）
+    Hostname,
+    /// DOM 选择器（解析文档按选择器匹配，不参与令牌剪枝）
+    Dom,
+    /// JS 全局变量（调用方提供的 window 属性取值）
+    Js,
+    /// TLS 证书颁发者（调用方提供的颁发者字符串）
+    CertIssuer,
+    /// DNS 记录（调用方解析的记录类型 → 记录值）
+    Dns,
+    /// Favicon mmh3 哈希（由调用方拉取的 favicon 字节计算）
+    FaviconHash,
+    /// 页面标题（HTML 提取的首个 `<title>` 文本）
+    Title,
+    /// HTML 注释（HTML 提取的注释文本，不含 `<!--`/`-->`）
+    Comment,
+}
+
+impl PruneScope {
+    /// 全部剪枝作用域（固定顺序，用于导出/遍历）
+    pub const ALL: [PruneScope; 14] = [
+        PruneScope::Url,
+        PruneScope::Html,
+        PruneScope::Script,
+        PruneScope::Header,
+        PruneScope::Meta,
+        PruneScope::Cookie,
+        PruneScope::Hostname,
+        PruneScope::Dom,
+        PruneScope::Js,
+        PruneScope::CertIssuer,
+        PruneScope::Dns,
+        PruneScope::FaviconHash,
+        PruneScope::Title,
+        PruneScope::Comment,
+    ];
+
+    /// 作用域的稳定字符串名（小写）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PruneScope::Url => "url",
+            PruneScope::Html => "html",
+            PruneScope::Script => "script",
+            PruneScope::Header => "header",
+            PruneScope::Meta => "meta",
+            PruneScope::Cookie => "cookie",
+            PruneScope::Hostname => "hostname",
+            PruneScope::Dom => "dom",
+            PruneScope::Js => "js",
+            PruneScope::CertIssuer => "cert_issuer",
+            PruneScope::Dns => "dns",
+            PruneScope::FaviconHash => "favicon_hash",
+            PruneScope::Title => "title",
+            PruneScope::Comment => "comment",
+        }
+    }
+
+    /// 从字符串名解析作用域（大小写不敏感），未知名称返回None
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scope| scope.as_str().eq_ignore_ascii_case(name))
+    }
 }
 
 /// 多作用域剪枝统一入口函数
@@ -30,6 +93,14 @@ pub fn struct_prune(scope: PruneScope, input: &str, key: Option<&str>) -> bool {
         //PruneScope::Meta => meta_struct_prune(key.unwrap_or(""), input),
         PruneScope::Meta => true,
         PruneScope::Cookie => cookie_struct_prune(key.unwrap_or(""), input),
+        PruneScope::Hostname => true,
+        PruneScope::Dom => true,
+        PruneScope::Js => true,
+        PruneScope::CertIssuer => true,
+        PruneScope::Dns => true,
+        PruneScope::FaviconHash => true,
+        PruneScope::Title => true,
+        PruneScope::Comment => true,
     }
 }
 
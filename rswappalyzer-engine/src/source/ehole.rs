@@ -0,0 +1,304 @@
+//! EHole 指纹转换
+//! 将 EHole `finger.json`（`{"fingerprint": [{cms, method, location, keyword}]}`）转换为内核规则：
+//! 1. method=keyword：全部关键字均需出现（多个关键字为 and 条件）；
+//!    location=body → Html 作用域，location=title → Title 作用域（页面 `<title>` 文本），
+//!    location=header 且关键字形如 `Name: value` → Header 作用域，`Set-Cookie: name=value` / `name=value` → Cookie 作用域
+//! 2. method=regula：全部正则均需命中，location 语义同上
+//! 3. method=faviconhash：任一 mmh3 哈希整串匹配 → FaviconHash 作用域
+//! 4. 无法无损表达的指纹（未知 method/location、不含 Header 名称的 header 关键字等）整体跳过并记录原因
+
+use serde::Deserialize;
+
+use super::fingerprinthub::{condition_for, merge_rule, SkippedFingerprint};
+use crate::core::{ParsedTechRule, RuleLibrary, TechBasicInfo};
+use crate::error::CoreResult;
+use crate::{KeyedPattern, MatchRuleSet, MatchScope, MatchType, Pattern};
+
+/// EHole 指纹文件
+#[derive(Debug, Deserialize)]
+struct EholeFile {
+    #[serde(default)]
+    fingerprint: Vec<EholeFingerprint>,
+}
+
+/// EHole 指纹
+#[derive(Debug, Deserialize)]
+struct EholeFingerprint {
+    cms: String,
+    method: String,
+    #[serde(default)]
+    location: String,
+    #[serde(default)]
+    keyword: Vec<String>,
+}
+
+/// 批量转换结果
+#[derive(Debug, Clone, Default)]
+pub struct EholeImport {
+    /// 转换后的规则库（同名指纹合并为 or 关系）
+    pub rule_lib: RuleLibrary,
+    /// 跳过的指纹
+    pub skipped: Vec<SkippedFingerprint>,
+}
+
+/// EHole 指纹转换器
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EholeConverter;
+
+impl EholeConverter {
+    /// 转换 EHole `finger.json`（不支持的指纹跳过并记录原因）
+    /// 参数：content - 指纹 JSON 字符串
+    ///
+    /// 返回：转换结果 | JSON 解析错误
+    pub fn convert(&self, content: &str) -> CoreResult<EholeImport> {
+        Ok(self.convert_file(serde_json::from_str(content)?))
+    }
+
+    /// 转换 EHole `finger.json` 字节（远程拉取场景）
+    /// 参数：bytes - 指纹 JSON 字节
+    ///
+    /// 返回：转换结果 | JSON 解析错误
+    pub fn convert_bytes(&self, bytes: &[u8]) -> CoreResult<EholeImport> {
+        Ok(self.convert_file(serde_json::from_slice(bytes)?))
+    }
+
+    /// 转换已解析的指纹文件
+    fn convert_file(&self, file: EholeFile) -> EholeImport {
+        let mut import = EholeImport::default();
+        for fingerprint in file.fingerprint {
+            let name = fingerprint.cms.trim().to_string();
+            let result = convert_fingerprint(&fingerprint).and_then(|rule| merge_rule(&mut import.rule_lib, &name, rule));
+            if let Err(reason) = result {
+                import.skipped.push(SkippedFingerprint { name, reason });
+            }
+        }
+        import
+    }
+}
+
+/// 转换单个指纹
+/// 返回：技术规则 | 不支持原因
+fn convert_fingerprint(fingerprint: &EholeFingerprint) -> Result<ParsedTechRule, String> {
+    let name = fingerprint.cms.trim();
+    if name.is_empty() {
+        return Err("fingerprint has no cms name".into());
+    }
+    let keywords: Vec<&str> = fingerprint
+        .keyword
+        .iter()
+        .map(|k| k.trim())
+        .filter(|k| !k.is_empty())
+        .collect();
+    if keywords.is_empty() {
+        return Err("fingerprint has no keyword".into());
+    }
+
+    let method = fingerprint.method.trim().to_ascii_lowercase();
+    let location = fingerprint.location.trim().to_ascii_lowercase();
+    let (scope, rule_set) = match method.as_str() {
+        "faviconhash" => {
+            // 多个哈希为 or 关系（同一系统的不同图标版本）
+            let mut rule_set = MatchRuleSet::new();
+            for hash in keywords {
+                let pattern = format!("^{}$", regex_syntax::escape(hash));
+                rule_set.add_list_pattern(Pattern::new(pattern, MatchType::Contains, None));
+            }
+            (MatchScope::FaviconHash, rule_set)
+        }
+        "keyword" | "regula" => {
+            let is_regex = method == "regula";
+            let mut rule_set = MatchRuleSet::with_condition(condition_for(keywords.len()));
+            let scope = match location.as_str() {
+                "body" | "" | "title" => {
+                    for keyword in keywords {
+                        let pattern = if is_regex { keyword.to_string() } else { regex_syntax::escape(keyword) };
+                        rule_set.add_list_pattern(Pattern::new(pattern, MatchType::Contains, None));
+                    }
+                    if location == "title" {
+                        MatchScope::Title
+                    } else {
+                        MatchScope::Html
+                    }
+                }
+                "header" => {
+                    let mut scopes = Vec::new();
+                    for keyword in keywords {
+                        let (scope, key, value) = split_header_keyword(keyword, is_regex)
+                            .ok_or_else(|| format!("header keyword '{}' has no 'Name:' prefix", keyword))?;
+                        let pattern = match (value.is_empty(), is_regex) {
+                            (true, _) => Pattern::new(String::new(), MatchType::Exists, None),
+                            (false, true) => Pattern::new(value.to_string(), MatchType::Contains, None),
+                            (false, false) => Pattern::new(regex_syntax::escape(value), MatchType::Contains, None),
+                        };
+                        rule_set.add_keyed_pattern(KeyedPattern { key, pattern });
+                        scopes.push(scope);
+                    }
+                    scopes.dedup();
+                    if scopes.len() > 1 {
+                        return Err("cookie and header keywords together require a cross-scope 'and'".into());
+                    }
+                    scopes.remove(0)
+                }
+                location => return Err(format!("location '{}' is not supported for method '{}'", location, method)),
+            };
+            (scope, rule_set)
+        }
+        method => return Err(format!("method '{}' is not supported", method)),
+    };
+
+    let mut rule = ParsedTechRule {
+        basic: TechBasicInfo {
+            tech_name: Some(name.to_string()),
+            ..TechBasicInfo::default()
+        },
+        match_rules: Default::default(),
+    };
+    rule.match_rules.insert(scope, rule_set);
+    Ok(rule)
+}
+
+/// 拆分 Header 关键字
+/// 1. `Name: value` → Header 作用域；Name 为 Set-Cookie/Cookie 时按 `cookie=value` 拆分到 Cookie 作用域
+/// 2. 非正则的 `cookie=value`（EHole 常见写法，如 Shiro 的 `rememberMe=`）→ Cookie 作用域
+///
+/// 返回：(作用域, 小写 Header/Cookie 名称, 值) | None
+fn split_header_keyword(keyword: &str, is_regex: bool) -> Option<(MatchScope, String, &str)> {
+    fn is_token(name: &str) -> bool {
+        !name.is_empty()
+            && name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_' || b == b'.')
+    }
+    fn split_cookie(kv: &str) -> Option<(MatchScope, String, &str)> {
+        let (name, value) = kv.split_once('=')?;
+        let name = name.trim();
+        is_token(name).then(|| (MatchScope::Cookie, name.to_ascii_lowercase(), value.trim()))
+    }
+
+    match keyword.split_once(':') {
+        Some((name, value)) if is_token(name.trim()) => {
+            let name = name.trim().to_ascii_lowercase();
+            if name == "set-cookie" || name == "cookie" {
+                // 正则无法按 Cookie 名拆分
+                return if is_regex { None } else { split_cookie(value) };
+            }
+            Some((MatchScope::Header, name, value.trim()))
+        }
+        _ if !is_regex => split_cookie(keyword),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scope_pruner::PruneScope;
+    use crate::test_support::compile_raw;
+    use crate::{CompileOptions, MatchCondition};
+
+    fn convert(fingerprints: &[&str]) -> EholeImport {
+        EholeConverter
+            .convert(&format!(r#"{{"fingerprint": [{}]}}"#, fingerprints.join(",")))
+            .unwrap()
+    }
+
+    fn rule_set<'a>(import: &'a EholeImport, name: &str, scope: MatchScope) -> &'a MatchRuleSet {
+        &import.rule_lib.core_tech_map[name].match_rules[&scope]
+    }
+
+    #[test]
+    fn test_split_header_keyword() {
+        let header = split_header_keyword("X-Edge: on", false).unwrap();
+        assert_eq!(header, (MatchScope::Header, "x-edge".to_string(), "on"));
+        let cookie = split_header_keyword("Set-Cookie: rememberMe=", false).unwrap();
+        assert_eq!(cookie, (MatchScope::Cookie, "rememberme".to_string(), ""));
+        let bare = split_header_keyword("JSESSIONID=abc", false).unwrap();
+        assert_eq!(bare, (MatchScope::Cookie, "jsessionid".to_string(), "abc"));
+        // 正则无法按 Cookie 名拆分；无 Header 名称的关键字无法定位
+        assert!(split_header_keyword("Set-Cookie: rememberMe=.*", true).is_none());
+        assert!(split_header_keyword("rememberMe=", true).is_none());
+        assert!(split_header_keyword("powered by acme", false).is_none());
+    }
+
+    #[test]
+    fn test_keyword_locations() {
+        let import = convert(&[
+            r#"{"cms": "Spring env", "method": "keyword", "location": "body",
+                "keyword": ["servletContextInitParams", "activeProfiles"]}"#,
+            r#"{"cms": "Jenkins", "method": "keyword", "location": "title",
+                "keyword": ["Dashboard [Jenkins]"]}"#,
+            r#"{"cms": "Weblogic", "method": "regula", "location": "body",
+                "keyword": ["WebLogic Server Version: \\d+"]}"#,
+        ]);
+        // 多关键字为 and 条件
+        let spring = rule_set(&import, "Spring env", MatchScope::Html);
+        assert_eq!(spring.condition, MatchCondition::And);
+        assert_eq!(spring.list_patterns.len(), 2);
+        // keyword 按字面量转义，regula 保留正则
+        let jenkins = rule_set(&import, "Jenkins", MatchScope::Title);
+        assert_eq!(jenkins.list_patterns[0].pattern, r"Dashboard \[Jenkins\]");
+        let weblogic = rule_set(&import, "Weblogic", MatchScope::Html);
+        assert_eq!(weblogic.list_patterns[0].pattern, r"WebLogic Server Version: \d+");
+
+        let library = compile_raw(&import.rule_lib, &CompileOptions::default());
+        assert_eq!(library.tech_patterns["Spring env"].and_scopes, [PruneScope::Html]);
+    }
+
+    #[test]
+    fn test_header_and_cookie_keywords() {
+        let import = convert(&[
+            r#"{"cms": "Shiro", "method": "keyword", "location": "header",
+                "keyword": ["Set-Cookie: rememberMe="]}"#,
+            r#"{"cms": "Edge", "method": "keyword", "location": "header", "keyword": ["X-Edge: on"]}"#,
+        ]);
+        let shiro = rule_set(&import, "Shiro", MatchScope::Cookie);
+        assert_eq!(shiro.keyed_patterns[0].key, "rememberme");
+        assert_eq!(shiro.keyed_patterns[0].pattern.match_type, MatchType::Exists);
+        let edge = rule_set(&import, "Edge", MatchScope::Header);
+        assert_eq!(edge.keyed_patterns[0].key, "x-edge");
+        assert_eq!(edge.keyed_patterns[0].pattern.match_type, MatchType::Contains);
+    }
+
+    #[test]
+    fn test_favicon_hash_merged() {
+        // 同名指纹合并为 or：关键字或 favicon 哈希任一命中
+        let import = convert(&[
+            r#"{"cms": "seeyon", "method": "keyword", "location": "body",
+                "keyword": ["/seeyon/USER-DATA/IMAGES/LOGIN/login.gif"]}"#,
+            r#"{"cms": "seeyon", "method": "faviconhash", "location": "body",
+                "keyword": ["-123456", "7890"]}"#,
+        ]);
+        let rules = &import.rule_lib.core_tech_map["seeyon"].match_rules;
+        assert!(rules.contains_key(&MatchScope::Html));
+        let favicon = &rules[&MatchScope::FaviconHash];
+        // 多个哈希为 or 关系，整串锚定
+        assert_eq!(favicon.condition, MatchCondition::Or);
+        let patterns: Vec<&str> = favicon.list_patterns.iter().map(|p| p.pattern.as_str()).collect();
+        assert_eq!(patterns, [r"^\-123456$", "^7890$"]);
+    }
+
+    #[test]
+    fn test_skipped_fingerprints() {
+        let import = convert(&[
+            r#"{"cms": "Unknown", "method": "md5", "location": "body", "keyword": ["abc"]}"#,
+            r#"{"cms": "Nowhere", "method": "keyword", "location": "url", "keyword": ["abc"]}"#,
+            r#"{"cms": "Bare", "method": "keyword", "location": "header", "keyword": ["powered"]}"#,
+            r#"{"cms": "Mixed", "method": "keyword", "location": "header",
+                "keyword": ["X-A: 1", "session=1"]}"#,
+            r#"{"cms": "Empty", "method": "keyword", "location": "body", "keyword": [" "]}"#,
+        ]);
+        assert!(import.rule_lib.core_tech_map.is_empty());
+        let reasons: Vec<(&str, &str)> = import
+            .skipped
+            .iter()
+            .map(|s| (s.name.as_str(), s.reason.as_str()))
+            .collect();
+        assert_eq!(reasons[0], ("Unknown", "method 'md5' is not supported"));
+        assert_eq!(reasons[1], ("Nowhere", "location 'url' is not supported for method 'keyword'"));
+        assert!(reasons[2].1.contains("no 'Name:' prefix"));
+        assert!(reasons[3].1.contains("cross-scope"));
+        assert_eq!(reasons[4], ("Empty", "fingerprint has no keyword"));
+
+        assert!(EholeConverter.convert("not json").is_err());
+    }
+}
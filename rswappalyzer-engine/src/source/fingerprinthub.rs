@@ -0,0 +1,307 @@
+//! FingerprintHub 指纹转换
+//! 将 FingerprintHub（observer_ward `web_fingerprint_v3.json`）指纹数组转换为内核规则，
+//! 仅支持可无损表达为 Wappalyzer 语义的子集：
+//! 1. keyword：全部关键字均需出现在响应体中 → Html 作用域（多个关键字为 and 条件）
+//! 2. headers：全部 Header 均需匹配（值为 `*` 表示仅要求存在）→ Header 作用域（多个 Header 为 and 条件）
+//! 3. favicon_hash / status_code：检测器不拉取 favicon、不感知响应状态码，接受但不参与匹配
+//! 4. 仅导入根路径 GET 请求且无自定义请求头/请求体的指纹；keyword 与 headers 同时存在（跨作用域 and）、
+//!    仅有 favicon_hash 等无法表达的指纹整体跳过并记录原因（不做放宽，避免误报）
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::core::{ParsedTechRule, RuleLibrary, TechBasicInfo};
+use crate::error::CoreResult;
+use crate::{KeyedPattern, MatchCondition, MatchRuleSet, MatchScope, MatchType, Pattern};
+
+/// FingerprintHub 指纹（仅解析转换所需字段）
+#[derive(Debug, Deserialize)]
+struct HubFingerprint {
+    name: String,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    request_method: Option<String>,
+    #[serde(default)]
+    request_headers: Option<Value>,
+    #[serde(default)]
+    request_data: Option<String>,
+    #[serde(default)]
+    headers: Option<serde_json::Map<String, Value>>,
+    #[serde(default)]
+    keyword: Vec<String>,
+    #[serde(default)]
+    favicon_hash: Vec<String>,
+}
+
+/// 跳过的指纹
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedFingerprint {
+    /// 指纹名称
+    pub name: String,
+    /// 跳过原因
+    pub reason: String,
+}
+
+/// 批量转换结果
+#[derive(Debug, Clone, Default)]
+pub struct FingerprintHubImport {
+    /// 转换后的规则库（同名指纹合并为 or 关系）
+    pub rule_lib: RuleLibrary,
+    /// 跳过的指纹
+    pub skipped: Vec<SkippedFingerprint>,
+}
+
+/// FingerprintHub 指纹转换器
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FingerprintHubConverter;
+
+impl FingerprintHubConverter {
+    /// 转换 FingerprintHub 指纹 JSON（不支持的指纹跳过并记录原因）
+    /// 参数：content - 指纹 JSON 字符串（顶层为数组）
+    ///
+    /// 返回：转换结果 | JSON 解析错误
+    pub fn convert(&self, content: &str) -> CoreResult<FingerprintHubImport> {
+        self.convert_fingerprints(serde_json::from_str(content)?)
+    }
+
+    /// 转换 FingerprintHub 指纹 JSON 字节（远程拉取场景）
+    /// 参数：bytes - 指纹 JSON 字节
+    ///
+    /// 返回：转换结果 | JSON 解析错误
+    pub fn convert_bytes(&self, bytes: &[u8]) -> CoreResult<FingerprintHubImport> {
+        self.convert_fingerprints(serde_json::from_slice(bytes)?)
+    }
+
+    /// 转换已解析的指纹列表
+    fn convert_fingerprints(&self, fingerprints: Vec<HubFingerprint>) -> CoreResult<FingerprintHubImport> {
+        let mut import = FingerprintHubImport::default();
+        for fingerprint in fingerprints {
+            let name = fingerprint.name.trim().to_string();
+            let result = convert_fingerprint(&fingerprint).and_then(|rule| merge_rule(&mut import.rule_lib, &name, rule));
+            if let Err(reason) = result {
+                import.skipped.push(SkippedFingerprint { name, reason });
+            }
+        }
+        Ok(import)
+    }
+}
+
+/// 转换单个指纹
+/// 返回：技术规则 | 不支持原因
+fn convert_fingerprint(fingerprint: &HubFingerprint) -> Result<ParsedTechRule, String> {
+    if fingerprint.name.trim().is_empty() {
+        return Err("fingerprint has no name".into());
+    }
+    let path = fingerprint.path.as_deref().map(str::trim).unwrap_or("/");
+    if !path.is_empty() && path != "/" {
+        return Err(format!("request path '{}' is not the root path", path));
+    }
+    if fingerprint
+        .request_method
+        .as_deref()
+        .is_some_and(|method| !method.trim().is_empty() && !method.trim().eq_ignore_ascii_case("get"))
+    {
+        return Err("only GET requests are supported".into());
+    }
+    if fingerprint.request_data.as_deref().is_some_and(|data| !data.is_empty())
+        || fingerprint.request_headers.as_ref().is_some_and(|headers| !is_empty_value(headers))
+    {
+        return Err("custom request headers/data are not supported".into());
+    }
+
+    let keywords: Vec<&String> = fingerprint.keyword.iter().filter(|k| !k.is_empty()).collect();
+    let headers: Vec<(&String, &Value)> = fingerprint.headers.iter().flatten().collect();
+    if !keywords.is_empty() && !headers.is_empty() {
+        return Err("keyword and headers together require a cross-scope 'and'".into());
+    }
+    if keywords.is_empty() && headers.is_empty() {
+        let reason = if fingerprint.favicon_hash.is_empty() {
+            "no keyword or headers"
+        } else {
+            "favicon_hash only fingerprints are not supported"
+        };
+        return Err(reason.into());
+    }
+
+    let mut rule = ParsedTechRule {
+        basic: TechBasicInfo {
+            tech_name: Some(fingerprint.name.trim().to_string()),
+            ..TechBasicInfo::default()
+        },
+        match_rules: Default::default(),
+    };
+    if !keywords.is_empty() {
+        let mut rule_set = MatchRuleSet::with_condition(condition_for(keywords.len()));
+        for keyword in keywords {
+            rule_set.add_list_pattern(Pattern::new(regex_syntax::escape(keyword), MatchType::Contains, None));
+        }
+        rule.match_rules.insert(MatchScope::Html, rule_set);
+    }
+    if !headers.is_empty() {
+        let mut rule_set = MatchRuleSet::with_condition(condition_for(headers.len()));
+        for (name, value) in headers {
+            let value = value.as_str().ok_or_else(|| format!("header '{}' value is not a string", name))?;
+            let pattern = if value == "*" || value.is_empty() {
+                Pattern::new(String::new(), MatchType::Exists, None)
+            } else {
+                Pattern::new(regex_syntax::escape(value), MatchType::Contains, None)
+            };
+            rule_set.add_keyed_pattern(KeyedPattern {
+                key: name.trim().to_ascii_lowercase(),
+                pattern,
+            });
+        }
+        rule.match_rules.insert(MatchScope::Header, rule_set);
+    }
+    Ok(rule)
+}
+
+/// 多个模式时为 and 条件
+pub(super) fn condition_for(len: usize) -> MatchCondition {
+    if len > 1 {
+        MatchCondition::And
+    } else {
+        MatchCondition::Or
+    }
+}
+
+/// 请求头字段是否为空（null / 空对象 / 空字符串）
+fn is_empty_value(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Object(map) => map.is_empty(),
+        Value::String(s) => s.is_empty(),
+        _ => false,
+    }
+}
+
+/// 合并同名技术规则（按作用域追加模式，仅 or 条件的规则集可合并）
+/// 返回：成功 | 不支持原因
+pub(super) fn merge_rule(rule_lib: &mut RuleLibrary, name: &str, rule: ParsedTechRule) -> Result<(), String> {
+    let Some(existing) = rule_lib.core_tech_map.get_mut(name) else {
+        rule_lib.core_tech_map.insert(name.to_string(), rule);
+        return Ok(());
+    };
+    let conflicts = rule.match_rules.iter().any(|(scope, rule_set)| {
+        existing
+            .match_rules
+            .get(scope)
+            .is_some_and(|target| target.condition == MatchCondition::And || rule_set.condition == MatchCondition::And)
+    });
+    if conflicts {
+        return Err("duplicate fingerprint name with an 'and' condition cannot be merged".into());
+    }
+    for (scope, rule_set) in rule.match_rules {
+        match existing.match_rules.get_mut(&scope) {
+            Some(target) => {
+                target.list_patterns.extend(rule_set.list_patterns);
+                target.keyed_patterns.extend(rule_set.keyed_patterns);
+            }
+            None => {
+                existing.match_rules.insert(scope, rule_set);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scope_pruner::PruneScope;
+    use crate::test_support::compile_raw;
+    use crate::CompileOptions;
+
+    /// 构造根路径 GET 指纹（extra 中的字段覆盖 FingerprintHub 默认值）
+    fn fingerprint(name: &str, extra: &str) -> String {
+        let mut fingerprint: Value = serde_json::from_str(
+            r#"{"path": "/", "request_method": "get", "request_headers": {}, "request_data": "",
+                "status_code": 0, "headers": {}, "keyword": [], "favicon_hash": [], "priority": 3}"#,
+        )
+        .unwrap();
+        let overrides: serde_json::Map<String, Value> =
+            serde_json::from_str(&format!(r#"{{"name": "{}"{}}}"#, name, extra)).unwrap();
+        fingerprint.as_object_mut().unwrap().extend(overrides);
+        fingerprint.to_string()
+    }
+
+    fn convert(fingerprints: &[String]) -> FingerprintHubImport {
+        FingerprintHubConverter
+            .convert(&format!("[{}]", fingerprints.join(",")))
+            .unwrap()
+    }
+
+    fn skip_reason(extra: &str) -> String {
+        let import = convert(&[fingerprint("acme", extra)]);
+        assert!(import.rule_lib.core_tech_map.is_empty());
+        import.skipped[0].reason.clone()
+    }
+
+    #[test]
+    fn test_skip_reasons() {
+        let cases = [
+            (r#", "path": "/admin/login", "keyword": ["admin"]"#, "not the root path"),
+            (r#", "request_method": "post", "keyword": ["x"]"#, "only GET"),
+            (r#", "request_data": "a=1", "keyword": ["x"]"#, "request headers/data"),
+            (r#", "request_headers": {"X-A": "1"}, "keyword": ["x"]"#, "request headers/data"),
+            (r#", "keyword": ["x"], "headers": {"Server": "x"}"#, "cross-scope"),
+            (r#", "favicon_hash": ["-1"]"#, "favicon_hash only"),
+            (r#", "keyword": [""]"#, "no keyword or headers"),
+            (r#", "headers": {"Server": 1}"#, "not a string"),
+        ];
+        for (extra, expected) in cases {
+            let reason = skip_reason(extra);
+            assert!(reason.contains(expected), "{}: {}", extra, reason);
+        }
+        assert!(FingerprintHubConverter.convert("{}").is_err());
+    }
+
+    #[test]
+    fn test_keywords_and_condition() {
+        let import =
+            convert(&[fingerprint("acme", r#", "keyword": ["acme-portal", "acme.min.js"]"#)]);
+        let rule_set = &import.rule_lib.core_tech_map["acme"].match_rules[&MatchScope::Html];
+        assert_eq!(rule_set.condition, MatchCondition::And);
+        // 关键字按字面量转义
+        let patterns: Vec<&str> =
+            rule_set.list_patterns.iter().map(|p| p.pattern.as_str()).collect();
+        assert_eq!(patterns, ["acme\\-portal", "acme\\.min\\.js"]);
+
+        let library = compile_raw(&import.rule_lib, &CompileOptions::default());
+        assert_eq!(library.tech_patterns["acme"].and_scopes, [PruneScope::Html]);
+    }
+
+    #[test]
+    fn test_headers_exists_wildcard() {
+        let extra = r#", "headers": {"X-Edge": "*", "Server": "EdgeServer"}"#;
+        let import = convert(&[fingerprint("edge", extra)]);
+        let rule_set = &import.rule_lib.core_tech_map["edge"].match_rules[&MatchScope::Header];
+        assert_eq!(rule_set.condition, MatchCondition::And);
+        let mut keyed: Vec<(&str, &MatchType)> = rule_set
+            .keyed_patterns
+            .iter()
+            .map(|p| (p.key.as_str(), &p.pattern.match_type))
+            .collect();
+        keyed.sort_by_key(|(key, _)| *key);
+        assert_eq!(
+            keyed,
+            [("server", &MatchType::Contains), ("x-edge", &MatchType::Exists)]
+        );
+    }
+
+    #[test]
+    fn test_same_name_merge() {
+        // 同名 or 指纹合并；与 and 指纹同名时跳过
+        let import = convert(&[
+            fingerprint("acme", r#", "keyword": ["acme-one"]"#),
+            fingerprint("acme", r#", "keyword": ["acme-two"]"#),
+            fingerprint("acme", r#", "keyword": ["acme-x", "acme-y"]"#),
+        ]);
+        let rule_set = &import.rule_lib.core_tech_map["acme"].match_rules[&MatchScope::Html];
+        assert_eq!(rule_set.condition, MatchCondition::Or);
+        assert_eq!(rule_set.list_patterns.len(), 2);
+        assert_eq!(import.skipped.len(), 1);
+        assert!(import.skipped[0].reason.contains("cannot be merged"));
+    }
+}
@@ -1,7 +1,25 @@
 //! 规则源解析模块
 
 pub mod wappalyzer;
+pub mod fingerprinthub;
+pub mod ehole;
+#[cfg(feature = "yaml-rules")]
+mod yaml;
+#[cfg(feature = "yaml-rules")]
+pub mod nuclei;
+#[cfg(feature = "yaml-rules")]
+pub mod rsw_yaml;
 
 // 通用解析器导出
 // Wappalyzer 解析器导出
-pub use wappalyzer::WappalyzerParser;
\ No newline at end of file
+pub use wappalyzer::WappalyzerParser;
+// FingerprintHub 转换器导出
+pub use fingerprinthub::{FingerprintHubConverter, FingerprintHubImport, SkippedFingerprint};
+// EHole 转换器导出
+pub use ehole::{EholeConverter, EholeImport};
+// Nuclei 模板导入器导出
+#[cfg(feature = "yaml-rules")]
+pub use nuclei::{NucleiImport, NucleiImporter, SkippedTemplate};
+// RSW YAML 规则解析器导出
+#[cfg(feature = "yaml-rules")]
+pub use rsw_yaml::RswYamlParser;
@@ -0,0 +1,322 @@
+//! Nuclei 模板导入（yaml-rules 特性）
+//! 将 nuclei `http`（旧版 `requests`）模板中的技术识别匹配器转换为内核规则，
+//! 使同一份指纹语料同时驱动 nuclei 与本检测器。仅支持可无损表达为 Wappalyzer 语义的子集：
+//! 1. word/regex 匹配器：part 为 body（默认）→ Html 作用域；part 为 header 且形如 `Name: value` → Header 作用域
+//! 2. status 匹配器：检测器不感知响应状态码，接受但不参与匹配
+//! 3. 仅导入请求路径为 `{{BaseURL}}`（根路径）的请求；多个内容匹配器需为 or 关系，多词 word 匹配器需为 or 条件
+//! 4. negative 匹配器、其他 part/类型、and 组合等无法表达的模板整体跳过并记录原因（不做放宽，避免误报）
+
+use serde::Deserialize;
+
+use crate::core::{ParsedTechRule, RuleLibrary, TechBasicInfo};
+use crate::error::{CoreError, CoreResult};
+use crate::{KeyedPattern, MatchScope, MatchType, Pattern};
+
+/// nuclei 模板（仅解析导入所需字段）
+#[derive(Debug, Deserialize)]
+struct NucleiTemplate {
+    id: String,
+    info: NucleiInfo,
+    #[serde(default, alias = "requests")]
+    http: Vec<NucleiRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NucleiInfo {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NucleiRequest {
+    #[serde(default)]
+    path: Vec<String>,
+    #[serde(default, rename = "matchers-condition")]
+    matchers_condition: Option<String>,
+    #[serde(default)]
+    matchers: Vec<NucleiMatcher>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NucleiMatcher {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    part: Option<String>,
+    #[serde(default)]
+    words: Vec<String>,
+    #[serde(default)]
+    regex: Vec<String>,
+    #[serde(default)]
+    condition: Option<String>,
+    #[serde(default)]
+    negative: bool,
+}
+
+/// 跳过的模板
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedTemplate {
+    /// 模板 ID（YAML 无法解析时为空）
+    pub id: String,
+    /// 跳过原因
+    pub reason: String,
+}
+
+/// 批量导入结果
+#[derive(Debug, Clone, Default)]
+pub struct NucleiImport {
+    /// 导入的规则库（同名技术的多个模板合并为 or 关系）
+    pub rule_lib: RuleLibrary,
+    /// 跳过的模板
+    pub skipped: Vec<SkippedTemplate>,
+}
+
+/// Nuclei 模板导入器
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NucleiImporter;
+
+impl NucleiImporter {
+    /// 转换单个 nuclei 模板
+    /// 参数：content - 模板 YAML 字符串
+    ///
+    /// 返回：(技术名称, 技术规则) | 解析错误/不支持的匹配器
+    pub fn parse_template(&self, content: &str) -> CoreResult<(String, ParsedTechRule)> {
+        let template: NucleiTemplate = serde_yaml::from_str(content)
+            .map_err(|e| CoreError::RuleParseError(format!("invalid nuclei template: {}", e)))?;
+        let unsupported =
+            |reason: String| CoreError::RuleParseError(format!("{}: {}", template.id, reason));
+
+        let mut rule = ParsedTechRule {
+            basic: TechBasicInfo::default(),
+            match_rules: Default::default(),
+        };
+        for request in template
+            .http
+            .iter()
+            .filter(|request| is_base_request(request))
+        {
+            let content_matchers: Vec<&NucleiMatcher> = request
+                .matchers
+                .iter()
+                .filter(|m| m.kind != "status")
+                .collect();
+            if content_matchers.len() > 1
+                && request
+                    .matchers_condition
+                    .as_deref()
+                    .is_some_and(|c| c.eq_ignore_ascii_case("and"))
+            {
+                return Err(unsupported(
+                    "matchers-condition 'and' across content matchers".into(),
+                ));
+            }
+            for matcher in content_matchers {
+                convert_matcher(matcher, &mut rule).map_err(unsupported)?;
+            }
+        }
+        if rule.match_rules.is_empty() {
+            return Err(unsupported(
+                "no importable word/regex matchers on {{BaseURL}}".into(),
+            ));
+        }
+        Ok((tech_name(&template.info.name), rule))
+    }
+
+    /// 批量导入 nuclei 模板（不支持的模板跳过并记录原因）
+    /// 参数：templates - 模板 YAML 字符串列表
+    ///
+    /// 返回：导入结果
+    pub fn import_templates<'a>(
+        &self,
+        templates: impl IntoIterator<Item = &'a str>,
+    ) -> NucleiImport {
+        let mut import = NucleiImport::default();
+        for content in templates {
+            match self.parse_template(content) {
+                Ok((name, rule)) => merge_rule(&mut import.rule_lib, name, rule),
+                Err(e) => import.skipped.push(SkippedTemplate {
+                    id: template_id(content),
+                    reason: e.to_string(),
+                }),
+            }
+        }
+        import
+    }
+}
+
+/// 是否为根路径请求（未声明路径视为根路径）
+fn is_base_request(request: &NucleiRequest) -> bool {
+    request.path.is_empty()
+        || request.path.iter().all(|path| {
+            matches!(
+                path.trim(),
+                "{{BaseURL}}" | "{{BaseURL}}/" | "{{RootURL}}" | "{{RootURL}}/"
+            )
+        })
+}
+
+/// 转换单个 word/regex 匹配器并写入技术规则
+/// 参数：
+/// - matcher: nuclei 匹配器
+/// - rule: 目标技术规则
+///
+/// 返回：成功 | 不支持原因
+fn convert_matcher(matcher: &NucleiMatcher, rule: &mut ParsedTechRule) -> Result<(), String> {
+    if matcher.negative {
+        return Err("negative matchers are not supported".into());
+    }
+    let values: Vec<String> = match matcher.kind.as_str() {
+        "word" => matcher
+            .words
+            .iter()
+            .map(|word| regex_syntax::escape(word))
+            .collect(),
+        "regex" => matcher.regex.clone(),
+        kind => return Err(format!("matcher type '{}' is not supported", kind)),
+    };
+    if values.len() > 1
+        && matcher
+            .condition
+            .as_deref()
+            .is_some_and(|c| c.eq_ignore_ascii_case("and"))
+    {
+        return Err("matcher condition 'and' with multiple values".into());
+    }
+
+    let part = matcher.part.as_deref().unwrap_or("body");
+    for value in values {
+        match part {
+            "body" => rule
+                .match_rules
+                .entry(MatchScope::Html)
+                .or_default()
+                .add_list_pattern(Pattern::new(value, MatchType::Contains, None)),
+            "header" => {
+                let (key, value) = split_header_pattern(&value)
+                    .ok_or_else(|| format!("header pattern '{}' has no 'Name:' prefix", value))?;
+                let pattern = if value.is_empty() {
+                    Pattern::new(value, MatchType::Exists, None)
+                } else {
+                    Pattern::new(value, MatchType::Contains, None)
+                };
+                rule.match_rules
+                    .entry(MatchScope::Header)
+                    .or_default()
+                    .keyed_patterns
+                    .push(KeyedPattern { key, pattern });
+            }
+            part => return Err(format!("matcher part '{}' is not supported", part)),
+        }
+    }
+    Ok(())
+}
+
+/// 拆分 `Name: value` 形式的 Header 模式（已转义的 word 与 regex 均适用）
+/// 返回：(小写 Header 名称, 值模式) | None
+fn split_header_pattern(pattern: &str) -> Option<(String, String)> {
+    let pattern = pattern.trim_start_matches("(?i)").trim_start_matches('^');
+    let (name, value) = pattern.split_once(':')?;
+    let name = name.replace("\\-", "-");
+    if name.is_empty()
+        || !name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+    {
+        return None;
+    }
+    let value = ["\\s*", "\\s+", "\\s?", " ?", " *", " "]
+        .iter()
+        .find_map(|prefix| value.strip_prefix(prefix))
+        .unwrap_or(value);
+    Some((name.to_ascii_lowercase(), value.to_string()))
+}
+
+/// 由模板名称推导技术名称（去除 "Detect"/"Detection" 后缀）
+fn tech_name(name: &str) -> String {
+    let name = name.trim();
+    for suffix in [" - Detection", " - Detect", " Detection", " Detect"] {
+        if name.len() > suffix.len()
+            && name[name.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+        {
+            return name[..name.len() - suffix.len()].trim().to_string();
+        }
+    }
+    name.to_string()
+}
+
+/// 合并同名技术规则（按作用域追加模式）
+fn merge_rule(rule_lib: &mut RuleLibrary, name: String, rule: ParsedTechRule) {
+    let Some(existing) = rule_lib.core_tech_map.get_mut(&name) else {
+        rule_lib.core_tech_map.insert(name, rule);
+        return;
+    };
+    for (scope, rule_set) in rule.match_rules {
+        let target = existing.match_rules.entry(scope).or_default();
+        target.list_patterns.extend(rule_set.list_patterns);
+        target.keyed_patterns.extend(rule_set.keyed_patterns);
+    }
+}
+
+/// 尽力读取模板 ID（用于跳过记录）
+fn template_id(content: &str) -> String {
+    serde_yaml::from_str::<serde_yaml::Value>(content)
+        .ok()
+        .and_then(|value| value.get("id")?.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NGINX: &str = r#"
+id: nginx-detect
+info:
+  name: Nginx Detect
+  severity: info
+http:
+  - method: GET
+    path:
+      - "{{BaseURL}}"
+    matchers-condition: and
+    matchers:
+      - type: word
+        part: header
+        words:
+          - "Server: nginx"
+      - type: status
+        status:
+          - 200
+"#;
+
+    const NEGATIVE: &str = r#"
+id: not-apache
+info:
+  name: Not Apache
+http:
+  - path: ["{{BaseURL}}"]
+    matchers:
+      - type: word
+        words: ["Apache"]
+        negative: true
+"#;
+
+    #[test]
+    fn test_import_templates() {
+        let (name, rule) = NucleiImporter.parse_template(NGINX).unwrap();
+        assert_eq!(name, "Nginx");
+        let header = &rule.match_rules[&MatchScope::Header].keyed_patterns[0];
+        assert_eq!(header.key, "server");
+        assert_eq!(header.pattern.pattern, "nginx");
+
+        let import = NucleiImporter.import_templates([NGINX, NEGATIVE]);
+        assert!(import.rule_lib.core_tech_map.contains_key("Nginx"));
+        assert_eq!(import.skipped.len(), 1);
+        assert_eq!(import.skipped[0].id, "not-apache");
+        assert_eq!(
+            split_header_pattern("(?i)X\\-Powered\\-By:\\s*PHP/([0-9.]+)")
+                .unwrap()
+                .1,
+            "PHP/([0-9.]+)"
+        );
+    }
+}
@@ -0,0 +1,331 @@
+//! RSW YAML 规则解析（yaml-rules 特性）
+//! 面向运维人员手写内部指纹的简化格式，每条指纹平铺为「技术 + 作用域 + 模式」，
+//! 无需理解 Wappalyzer 的嵌套结构与 `\;version:` 标签：
+//!
+//! ```yaml
+//! fingerprints:
+//!   - tech: Acme Gateway
+//!     cats: [22]
+//!     implies: [Nginx]
+//!     rules:
+//!       - scope: header
+//!         key: X-Acme-Gateway
+//!         pattern: '^acme/([\d.]+)'
+//!         version: '\1'
+//!       - scope: html
+//!         pattern: '<div class="acme-[a-z]+">'
+//! ```
+//!
+//! 1. 同一 tech 的多条规则（及重复出现的同名指纹）均为 or 关系
+//! 2. KV 型作用域（header/cookie/meta/js/dns）需填写 key，pattern 为空表示仅要求存在
+//! 3. 解析后与 Wappalyzer YAML 规则共用正则校验，非法模式逐条报告行号
+
+use serde::Deserialize;
+
+use super::yaml::validate_patterns;
+use crate::core::{ParsedTechRule, RuleLibrary, TechBasicInfo};
+use crate::error::{CoreError, CoreResult};
+use crate::{KeyedPattern, MatchScope, MatchType, Pattern};
+
+/// RSW YAML 规则文件
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RswYamlFile {
+    #[serde(default)]
+    fingerprints: Vec<RswFingerprint>,
+}
+
+/// 单条指纹
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RswFingerprint {
+    tech: String,
+    #[serde(default)]
+    cats: Vec<u32>,
+    #[serde(default)]
+    implies: Vec<String>,
+    #[serde(default)]
+    rules: Vec<RswRule>,
+}
+
+/// 单条匹配规则
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RswRule {
+    scope: String,
+    #[serde(default)]
+    key: Option<String>,
+    #[serde(default)]
+    pattern: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    confidence: Option<u8>,
+}
+
+/// RSW YAML 规则解析器
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RswYamlParser;
+
+impl RswYamlParser {
+    /// 解析 RSW YAML 规则为内核规则库（校验全部正则模式）
+    /// 参数：content - YAML 字符串
+    ///
+    /// 返回：内核规则库 | 解析错误/未知作用域/非法正则（含行号）
+    pub fn parse(&self, content: &str) -> CoreResult<RuleLibrary> {
+        let file: RswYamlFile = serde_yaml::from_str(content).map_err(|e| match e.location() {
+            Some(location) => CoreError::RuleParseError(format!(
+                "invalid RSW YAML rules at line {}, column {}: {}",
+                location.line(),
+                location.column(),
+                e
+            )),
+            None => CoreError::RuleParseError(format!("invalid RSW YAML rules: {}", e)),
+        })?;
+
+        let mut rule_lib = RuleLibrary::default();
+        for fingerprint in file.fingerprints {
+            let name = fingerprint.tech.trim().to_string();
+            if name.is_empty() {
+                return Err(CoreError::RuleParseError("fingerprint has no tech name".into()));
+            }
+            let rule = convert_fingerprint(&name, &fingerprint)?;
+            merge_rule(&mut rule_lib, name, rule);
+        }
+        validate_patterns(&rule_lib, content)?;
+        Ok(rule_lib)
+    }
+
+    /// 解析 RSW YAML 规则字节（远程拉取场景）
+    /// 参数：bytes - YAML 字节
+    ///
+    /// 返回：内核规则库 | 编码错误/解析错误
+    pub fn parse_bytes(&self, bytes: &[u8]) -> CoreResult<RuleLibrary> {
+        let content = std::str::from_utf8(bytes)
+            .map_err(|e| CoreError::RuleParseError(format!("RSW YAML rules are not valid UTF-8: {}", e)))?;
+        self.parse(content)
+    }
+}
+
+/// 转换单条指纹
+/// 返回：技术规则 | 未知作用域/缺少键名
+fn convert_fingerprint(name: &str, fingerprint: &RswFingerprint) -> CoreResult<ParsedTechRule> {
+    let mut rule = ParsedTechRule {
+        basic: TechBasicInfo {
+            category_ids: fingerprint.cats.clone(),
+            implies: Some(fingerprint.implies.clone()).filter(|implies| !implies.is_empty()),
+            ..TechBasicInfo::default()
+        },
+        match_rules: Default::default(),
+    };
+    let invalid = |reason: String| CoreError::RuleParseError(format!("{}: {}", name, reason));
+
+    for item in &fingerprint.rules {
+        let (scope, keyed) = parse_scope(&item.scope).ok_or_else(|| invalid(format!("unknown scope '{}'", item.scope)))?;
+        let pattern = if item.pattern.trim().is_empty() {
+            Pattern::new(String::new(), MatchType::Exists, None)
+        } else {
+            Pattern::new(tagged_pattern(item), MatchType::Contains, None)
+        };
+        let rule_set = rule.match_rules.entry(scope).or_default();
+        if keyed {
+            let key = item
+                .key
+                .as_deref()
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .ok_or_else(|| invalid(format!("scope '{}' requires a key", item.scope)))?;
+            rule_set.add_keyed_pattern(KeyedPattern {
+                key: key.to_lowercase(),
+                pattern,
+            });
+        } else {
+            if item.key.is_some() {
+                return Err(invalid(format!("scope '{}' does not take a key", item.scope)));
+            }
+            if item.pattern.trim().is_empty() {
+                return Err(invalid(format!("scope '{}' requires a pattern", item.scope)));
+            }
+            rule_set.add_list_pattern(pattern);
+        }
+    }
+    Ok(rule)
+}
+
+/// 解析作用域名称
+/// 返回：(匹配作用域, 是否为KV型) | None 表示未知作用域
+fn parse_scope(scope: &str) -> Option<(MatchScope, bool)> {
+    let scope = match scope.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+        "url" => (MatchScope::Url, false),
+        "html" => (MatchScope::Html, false),
+        "script" | "script_src" => (MatchScope::ScriptSrc, false),
+        "title" => (MatchScope::Title, false),
+        "comment" => (MatchScope::Comment, false),
+        "cert_issuer" => (MatchScope::CertIssuer, false),
+        "favicon_hash" | "favicon" => (MatchScope::FaviconHash, false),
+        "header" | "headers" => (MatchScope::Header, true),
+        "cookie" | "cookies" => (MatchScope::Cookie, true),
+        "meta" => (MatchScope::Meta, true),
+        "js" => (MatchScope::Js, true),
+        "dns" => (MatchScope::Dns, true),
+        _ => return None,
+    };
+    Some(scope)
+}
+
+/// 将 version / confidence 字段拼接为 Wappalyzer 模式标签（交由规则清洗统一解析）
+fn tagged_pattern(item: &RswRule) -> String {
+    let mut pattern = item.pattern.trim().to_string();
+    if let Some(version) = item.version.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+        pattern.push_str("\\;version:");
+        pattern.push_str(version);
+    }
+    if let Some(confidence) = item.confidence {
+        pattern.push_str(&format!("\\;confidence:{}", confidence.min(100)));
+    }
+    pattern
+}
+
+/// 合并同名技术规则（按作用域追加模式，分类与关联技术去重合并）
+fn merge_rule(rule_lib: &mut RuleLibrary, name: String, rule: ParsedTechRule) {
+    let Some(existing) = rule_lib.core_tech_map.get_mut(&name) else {
+        let mut rule = rule;
+        rule.basic.tech_name = Some(name.clone());
+        rule_lib.core_tech_map.insert(name, rule);
+        return;
+    };
+    for category in rule.basic.category_ids {
+        if !existing.basic.category_ids.contains(&category) {
+            existing.basic.category_ids.push(category);
+        }
+    }
+    if let Some(implies) = rule.basic.implies {
+        let target = existing.basic.implies.get_or_insert_with(Vec::new);
+        for implied in implies {
+            if !target.contains(&implied) {
+                target.push(implied);
+            }
+        }
+    }
+    for (scope, rule_set) in rule.match_rules {
+        let target = existing.match_rules.entry(scope).or_default();
+        target.list_patterns.extend(rule_set.list_patterns);
+        target.keyed_patterns.extend(rule_set.keyed_patterns);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = r#"
+fingerprints:
+  - tech: Acme Gateway
+    cats: [22]
+    implies: [Nginx]
+    rules:
+      - scope: header
+        key: X-Acme-Gateway
+        pattern: '^acme/([\d.]+)'
+        version: '\1'
+      - scope: html
+        pattern: '<div class="acme-[a-z]+">'
+"#;
+
+    #[test]
+    fn test_parse_rsw_yaml() {
+        let rule_lib = RswYamlParser.parse(RULES).unwrap();
+        let rule = &rule_lib.core_tech_map["Acme Gateway"];
+        assert_eq!(rule.basic.category_ids, [22]);
+        assert_eq!(rule.basic.implies.as_deref(), Some(&["Nginx".to_string()][..]));
+        let header = &rule.match_rules[&MatchScope::Header].keyed_patterns[0];
+        assert_eq!(header.key, "x-acme-gateway");
+        assert_eq!(header.pattern.pattern, r"^acme/([\d.]+)\;version:\1");
+
+        let err = RswYamlParser
+            .parse(&RULES.replace("acme-[a-z]+", "acme-[a-z+"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("line 12: Acme Gateway"), "{}", err);
+
+        let err = RswYamlParser
+            .parse(&RULES.replace("scope: html", "scope: body"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown scope 'body'"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_scope_aliases() {
+        assert_eq!(parse_scope("Script-Src"), Some((MatchScope::ScriptSrc, false)));
+        assert_eq!(parse_scope(" favicon "), Some((MatchScope::FaviconHash, false)));
+        assert_eq!(parse_scope("headers"), Some((MatchScope::Header, true)));
+        assert_eq!(parse_scope("body"), None);
+    }
+
+    #[test]
+    fn test_exists_and_confidence() {
+        let rule_lib = RswYamlParser
+            .parse(
+                r#"
+fingerprints:
+  - tech: Acme Session
+    rules:
+      - scope: cookie
+        key: Acme_SID
+      - scope: title
+        pattern: 'Acme Console'
+        confidence: 150
+"#,
+            )
+            .unwrap();
+        let rules = &rule_lib.core_tech_map["Acme Session"].match_rules;
+        // pattern 为空：仅要求 Cookie 存在
+        let cookie = &rules[&MatchScope::Cookie].keyed_patterns[0];
+        assert_eq!(cookie.key, "acme_sid");
+        assert_eq!(cookie.pattern.match_type, MatchType::Exists);
+        // 置信度上限 100
+        let title = &rules[&MatchScope::Title].list_patterns[0];
+        assert_eq!(title.pattern, r"Acme Console\;confidence:100");
+    }
+
+    #[test]
+    fn test_same_tech_merged() {
+        let rule_lib = RswYamlParser
+            .parse(&format!(
+                "{}{}",
+                RULES,
+                r#"
+  - tech: Acme Gateway
+    cats: [22, 31]
+    implies: [Nginx, Acme Runtime]
+    rules:
+      - scope: html
+        pattern: 'acme-gateway'
+"#
+            ))
+            .unwrap();
+        let rule = &rule_lib.core_tech_map["Acme Gateway"];
+        assert_eq!(rule.basic.category_ids, [22, 31]);
+        let implies = rule.basic.implies.as_deref().unwrap();
+        assert_eq!(implies, ["Nginx".to_string(), "Acme Runtime".to_string()]);
+        assert_eq!(rule.match_rules[&MatchScope::Html].list_patterns.len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_fingerprints() {
+        let parse_err = |yaml: String| RswYamlParser.parse(&yaml).unwrap_err().to_string();
+
+        let err = parse_err(RULES.replace("key: X-Acme-Gateway", "key: ' '"));
+        assert!(err.contains("Acme Gateway: scope 'header' requires a key"), "{}", err);
+        let err = parse_err(RULES.replace("scope: html", "scope: html\n        key: div"));
+        assert!(err.contains("scope 'html' does not take a key"), "{}", err);
+        let err = parse_err(RULES.replace("pattern: '<div class=\"acme-[a-z]+\">'", "pattern: ''"));
+        assert!(err.contains("scope 'html' requires a pattern"), "{}", err);
+        // 未知字段报告行号
+        let err = parse_err(RULES.replace("implies:", "imply:"));
+        assert!(err.contains("line 5"), "{}", err);
+        let err = parse_err(RULES.replace("Acme Gateway", "''"));
+        assert!(err.contains("fingerprint has no tech name"), "{}", err);
+    }
+}
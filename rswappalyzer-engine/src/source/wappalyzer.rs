@@ -1,10 +1,11 @@
 use crate::cleaner::clean_stats::CleanStats;
-use crate::core::{CategoryRule, ParsedTechRule, RuleLibrary, TechBasicInfo};
-use crate::{KeyedPattern, MatchCondition, MatchRuleSet, MatchScope, MatchType, Pattern};
+use crate::core::{
+    CategoryRule, DomKey, DomTarget, ParsedTechRule, RuleLibrary, TechBasicInfo, TechLifecycle, TechMeta,
+};
+use crate::{CoreResult, KeyedPattern, MatchCondition, MatchRuleSet, MatchScope, MatchType, Pattern};
 use rustc_hash::FxHashMap as HashMap;
 use serde::{Deserialize, Serialize};
 use serde_json::{self, Value};
-use std::error::Error;
 
 /// Wappalyzer 原始分类规则
 /// 对应JSON结构中的categories字段，描述技术分类信息
@@ -66,10 +67,48 @@ pub struct WappalyzerOriginalTechRule {
     /// JS变量匹配规则（KV结构，可选）
     #[serde(default)]
     pub js: Option<HashMap<String, Value>>,
+    /// DOM选择器规则（选择器字符串/数组，或 选择器 → {exists/attributes/text} 对象，可选）
+    #[serde(default)]
+    pub dom: Option<Value>,
+    /// DNS记录匹配规则（记录类型 → 字符串/数组，可选）
+    #[serde(default)]
+    pub dns: Option<HashMap<String, Value>>,
+    /// TLS证书颁发者匹配规则（支持字符串/数组格式，可选）
+    #[serde(rename = "certIssuer", default)]
+    pub cert_issuer: Option<Value>,
+    /// Favicon mmh3哈希规则（自定义扩展，支持整数/字符串/数组格式，可选）
+    #[serde(default, alias = "faviconHash")]
+    pub favicon: Option<Value>,
+    /// 页面标题匹配规则（自定义扩展，支持字符串/数组格式，可选）
+    #[serde(default)]
+    pub title: Option<Value>,
+
+    /// 主机名后缀（自定义扩展，支持字符串/数组格式，可选；`*.github.io` 仅匹配子域）
+    #[serde(default, alias = "hostnames")]
+    pub hostname: Option<Value>,
 
     /// 隐含技术关联（支持字符串/数组格式，可选）
     #[serde(default)]
     pub implies: Option<Value>,
+    /// 互斥技术（支持字符串/数组格式，可选）
+    #[serde(default)]
+    pub excludes: Option<Value>,
+    /// 依赖技术（支持字符串/数组格式，可选）
+    #[serde(default)]
+    pub requires: Option<Value>,
+    /// 依赖分类ID（支持单个ID/数组格式，可选）
+    #[serde(rename = "requiresCategory", default)]
+    pub requires_category: Option<Value>,
+
+    /// 是否已废弃（自定义规则包生命周期管理，可选）
+    #[serde(default)]
+    pub deprecated: bool,
+    /// 有效期截止日期（YYYY-MM-DD，可选）
+    #[serde(alias = "validUntil", default)]
+    pub valid_until: Option<String>,
+    /// 替代技术名称（可选）
+    #[serde(default)]
+    pub replacement: Option<String>,
 
     /// 扩展字段（兼容未定义的JSON字段）
     #[serde(flatten)]
@@ -102,41 +141,29 @@ impl WappalyzerParser {
     /// 解析字符串格式的Wappalyzer规则
     /// 参数：content - JSON字符串
     /// 返回：原始规则库 | 解析错误
-    pub fn parse(&self, content: &str) -> Result<WappalyzerOriginalRuleLibrary, Box<dyn Error>> {
+    pub fn parse(&self, content: &str) -> CoreResult<WappalyzerOriginalRuleLibrary> {
         self.parse_from_str(content)
     }
 
     /// 从字符串解析原始规则库
-    pub fn parse_from_str(
-        &self,
-        content: &str,
-    ) -> Result<WappalyzerOriginalRuleLibrary, Box<dyn Error>> {
-        serde_json::from_str(content)
-            .map_err(|e| format!("Failed to parse Wappalyzer JSON string: {}", e).into())
+    pub fn parse_from_str(&self, content: &str) -> CoreResult<WappalyzerOriginalRuleLibrary> {
+        Ok(serde_json::from_str(content)?)
     }
 
     /// 从字节流解析原始规则库
-    pub fn parse_from_bytes(
-        &self,
-        bytes: &[u8],
-    ) -> Result<WappalyzerOriginalRuleLibrary, Box<dyn Error>> {
-        serde_json::from_slice(bytes)
-            .map_err(|e| format!("Failed to parse Wappalyzer byte stream: {}", e).into())
+    pub fn parse_from_bytes(&self, bytes: &[u8]) -> CoreResult<WappalyzerOriginalRuleLibrary> {
+        Ok(serde_json::from_slice(bytes)?)
     }
 
     /// 从serde_json::Value解析原始规则库
-    pub fn parse_from_value(
-        &self,
-        value: &Value,
-    ) -> Result<WappalyzerOriginalRuleLibrary, Box<dyn Error>> {
-        serde_json::from_value(value.clone())
-            .map_err(|e| format!("Failed to parse Wappalyzer JSON Value: {}", e).into())
+    pub fn parse_from_value(&self, value: &Value) -> CoreResult<WappalyzerOriginalRuleLibrary> {
+        Ok(serde_json::from_value(value.clone())?)
     }
 
     /// 解析并转换为内核规则库
     /// 参数：content - JSON字符串
     /// 返回：内核规则库 | 解析/转换错误
-    pub fn parse_to_rule_lib(&self, content: &str) -> Result<RuleLibrary, Box<dyn Error>> {
+    pub fn parse_to_rule_lib(&self, content: &str) -> CoreResult<RuleLibrary> {
         let original = self.parse_from_str(content)?;
         Ok(self.convert_original_to_rule_lib(original))
     }
@@ -180,7 +207,21 @@ impl WappalyzerParser {
             (!res.is_empty()).then_some(res)
         }
 
-        // 将JSON Value转换为Pattern列表（兼容单字符串/数组格式）
+        // 将分类ID字段转换为ID列表（兼容单个ID/数组格式，数字字符串同样接受）
+        fn category_ids_of(val: &Option<Value>) -> Vec<u32> {
+            let id_of = |v: &Value| match v {
+                Value::Number(n) => n.as_u64().and_then(|n| u32::try_from(n).ok()),
+                Value::String(s) => s.trim().parse().ok(),
+                _ => None,
+            };
+            match val {
+                Some(Value::Array(arr)) => arr.iter().filter_map(id_of).collect(),
+                Some(v) => id_of(v).into_iter().collect(),
+                None => Vec::new(),
+            }
+        }
+
+        // 将JSON Value转换为Pattern列表（兼容单字符串/数组格式，以及带condition的 `{"condition": .., "patterns": ..}` 对象格式）
         fn json_val_to_pattern_list(val: &Option<Value>) -> Vec<Pattern> {
             let mut patterns = Vec::new();
             let Some(val) = val else {
@@ -193,11 +234,7 @@ impl WappalyzerParser {
                         if let Value::String(s) = item {
                             let s_trimmed = s.trim().to_string();
                             if !s_trimmed.is_empty() {
-                                patterns.push(Pattern {
-                                    pattern: s_trimmed,
-                                    match_type: MatchType::Contains,
-                                    version_template: None,
-                                });
+                                patterns.push(Pattern::new(s_trimmed, MatchType::Contains, None));
                             }
                         }
                     }
@@ -205,13 +242,10 @@ impl WappalyzerParser {
                 Value::String(s) => {
                     let s_trimmed = s.trim().to_string();
                     if !s_trimmed.is_empty() {
-                        patterns.push(Pattern {
-                            pattern: s_trimmed,
-                            match_type: MatchType::Contains,
-                            version_template: None,
-                        });
+                        patterns.push(Pattern::new(s_trimmed, MatchType::Contains, None));
                     }
                 }
+                Value::Object(obj) => return json_val_to_pattern_list(&obj.get("patterns").cloned()),
                 _ => {}
             }
 
@@ -258,7 +292,93 @@ impl WappalyzerParser {
             ))
         }
 
-        // 构建KV型匹配规则集（用于meta/header/cookie/js）
+        // 拆分注释锚定的HTML规则（以 `<!--` 开头的模式改为匹配注释文本，仅 Or 条件拆分，And 条件整体保留在HTML作用域）
+        fn split_comment_rule_set(
+            html: Option<(MatchScope, MatchRuleSet)>,
+        ) -> [Option<(MatchScope, MatchRuleSet)>; 2] {
+            let Some((scope, mut rule_set)) = html else {
+                return [None, None];
+            };
+            if rule_set.condition != MatchCondition::Or {
+                return [Some((scope, rule_set)), None];
+            }
+
+            let mut comment_set = MatchRuleSet::new();
+            rule_set.list_patterns.retain(|pattern| match comment_pattern_of(&pattern.pattern) {
+                Some(converted) => {
+                    let mut pattern = pattern.clone();
+                    pattern.pattern = converted;
+                    comment_set.add_list_pattern(pattern);
+                    false
+                }
+                None => true,
+            });
+            [
+                (!rule_set.list_patterns.is_empty()).then_some((scope, rule_set)),
+                (!comment_set.list_patterns.is_empty()).then_some((MatchScope::Comment, comment_set)),
+            ]
+        }
+
+        // 注释锚定模式转为注释文本模式：去掉开头的 `<!--` 与结尾的 `-->`（注释文本不含分隔符），`\;` 标签原样保留
+        // 顶层含 `|` 的模式（去掉前缀后其余分支语义改变）与仅剩空白的模式不转换
+        fn comment_pattern_of(pattern: &str) -> Option<String> {
+            let (regex, tags) = pattern.split_at(pattern.find("\\;").unwrap_or(pattern.len()));
+            let body = regex.strip_prefix("<!--")?;
+            let body = body.strip_suffix("-->").unwrap_or(body);
+            if body.trim().is_empty() || has_top_level_alternation(body) {
+                return None;
+            }
+            Some(format!("{}{}", body, tags))
+        }
+
+        // 正则是否在顶层（分组与字符类之外）包含 `|`
+        fn has_top_level_alternation(regex: &str) -> bool {
+            let (mut depth, mut in_class, mut escaped) = (0usize, false, false);
+            for c in regex.chars() {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    ']' if in_class => in_class = false,
+                    _ if in_class => {}
+                    '[' => in_class = true,
+                    '(' => depth += 1,
+                    ')' => depth = depth.saturating_sub(1),
+                    '|' if depth == 0 => return true,
+                    _ => {}
+                }
+            }
+            false
+        }
+
+        // 构建Favicon哈希匹配规则集（哈希值转义并整串锚定，避免 `-123` 命中 `-1234`）
+        fn build_favicon_match_rule_set(rule_obj: &Option<Value>) -> Option<(MatchScope, MatchRuleSet)> {
+            let hashes: Vec<String> = match rule_obj {
+                Some(Value::Array(arr)) => arr.iter().filter_map(favicon_hash_str).collect(),
+                Some(value) => favicon_hash_str(value).into_iter().collect(),
+                None => Vec::new(),
+            };
+            if hashes.is_empty() {
+                return None;
+            }
+
+            let mut rule_set = MatchRuleSet::new();
+            for hash in hashes {
+                let pattern = format!("^{}$", regex_syntax::escape(&hash));
+                rule_set.add_list_pattern(Pattern::new(pattern, MatchType::Contains, None));
+            }
+            Some((MatchScope::FaviconHash, rule_set))
+        }
+
+        // 单个Favicon哈希值（整数或字符串）
+        fn favicon_hash_str(value: &Value) -> Option<String> {
+            match value {
+                Value::Number(n) => Some(n.to_string()),
+                Value::String(s) if !s.trim().is_empty() => Some(s.trim().to_string()),
+                _ => None,
+            }
+        }
+
+        // 构建KV型匹配规则集（用于meta/header/cookie/js/dns）
         fn build_keyed_match_rule_set(
             pattern_map: &HashMap<String, Value>,
             _scope_name: &str,
@@ -266,6 +386,10 @@ impl WappalyzerParser {
             let mut keyed_patterns = Vec::new();
 
             for (k, v) in pattern_map.iter() {
+                // condition 为规则集条件字段，不是匹配键
+                if k == "condition" {
+                    continue;
+                }
                 let key = k.to_lowercase();
 
                 match v {
@@ -276,11 +400,7 @@ impl WappalyzerParser {
                                 if !s_trimmed.is_empty() {
                                     keyed_patterns.push(KeyedPattern {
                                         key: key.clone(),
-                                        pattern: Pattern {
-                                            pattern: s_trimmed,
-                                            match_type: MatchType::Contains,
-                                            version_template: None,
-                                        },
+                                        pattern: Pattern::new(s_trimmed, MatchType::Contains, None),
                                     });
                                 }
                             }
@@ -290,11 +410,7 @@ impl WappalyzerParser {
                         let s_trimmed = s.trim().to_string();
                         keyed_patterns.push(KeyedPattern {
                             key: key.clone(),
-                            pattern: Pattern {
-                                pattern: s_trimmed,
-                                match_type: MatchType::Exists,
-                                version_template: None,
-                            },
+                            pattern: Pattern::new(s_trimmed, MatchType::Exists, None),
                         });
                     }
                     _ => {}
@@ -304,7 +420,61 @@ impl WappalyzerParser {
             keyed_patterns
         }
 
-        // 转换技术规则
+        // 构建DOM型匹配规则（键为选择器 + 检查目标，properties 检查依赖 JS 运行时，忽略）
+        fn build_dom_keyed_patterns(dom_val: &Value) -> Vec<KeyedPattern> {
+            let mut keyed_patterns = Vec::new();
+            let mut push = |selector: &str, target: DomTarget, pattern: Option<&str>| {
+                let selector = selector.trim();
+                if selector.is_empty() {
+                    return;
+                }
+                let pattern = match pattern.map(str::trim) {
+                    Some(s) if !s.is_empty() => Pattern::new(s.to_string(), MatchType::Contains, None),
+                    _ => Pattern::new(String::new(), MatchType::Exists, None),
+                };
+                keyed_patterns.push(KeyedPattern {
+                    key: DomKey::new(selector, target).to_string(),
+                    pattern,
+                });
+            };
+
+            match dom_val {
+                Value::String(selector) => push(selector, DomTarget::Exists, None),
+                Value::Array(arr) => {
+                    for item in arr {
+                        if let Value::String(selector) = item {
+                            push(selector, DomTarget::Exists, None);
+                        }
+                    }
+                }
+                Value::Object(obj) => {
+                    for (selector, spec) in obj {
+                        let Value::Object(spec) = spec else {
+                            push(selector, DomTarget::Exists, None);
+                            continue;
+                        };
+                        if let Some(exists) = spec.get("exists") {
+                            push(selector, DomTarget::Exists, exists.as_str());
+                        }
+                        if let Some(Value::Object(attributes)) = spec.get("attributes") {
+                            for (name, val) in attributes {
+                                let target = DomTarget::Attribute(name.trim().to_lowercase());
+                                push(selector, target, val.as_str());
+                            }
+                        }
+                        if let Some(text) = spec.get("text") {
+                            push(selector, DomTarget::Text, text.as_str());
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            keyed_patterns
+        }
+
+        // 转换技术规则（同时收集展示元信息旁路表）
+        let mut meta_table = HashMap::default();
         let core_tech_map = original
             .technologies
             .into_iter()
@@ -315,36 +485,52 @@ impl WappalyzerParser {
                 let basic = TechBasicInfo {
                     category_ids: original_tech.category_ids,
                     implies: implies_value_to_vec(&original_tech.implies),
+                    excludes: implies_value_to_vec(&original_tech.excludes).unwrap_or_default(),
+                    requires: implies_value_to_vec(&original_tech.requires).unwrap_or_default(),
+                    requires_category: category_ids_of(&original_tech.requires_category),
+                    lifecycle: TechLifecycle::from_parts(
+                        original_tech.deprecated,
+                        original_tech.valid_until,
+                        original_tech.replacement,
+                    ),
+                    hostnames: implies_value_to_vec(&original_tech.hostname).unwrap_or_default(),
 
                     #[cfg(feature = "full-meta")]
                     tech_name: Some(tech_name.clone()),
-                    #[cfg(feature = "full-meta")]
+                    ..TechBasicInfo::default()
+                };
+
+                // 展示元信息始终解析进旁路表（与 full-meta 特性无关）
+                let meta = TechMeta {
                     description: original_tech.description,
-                    #[cfg(feature = "full-meta")]
                     website: original_tech.website,
-                    #[cfg(feature = "full-meta")]
                     icon: original_tech.icon,
-                    #[cfg(feature = "full-meta")]
                     cpe: original_tech.cpe,
-                    #[cfg(feature = "full-meta")]
                     saas: original_tech.saas,
-                    #[cfg(feature = "full-meta")]
                     pricing: original_tech.pricing,
-                    ..TechBasicInfo::default()
                 };
+                if !meta.is_empty() {
+                    meta_table.insert(tech_name.clone(), meta);
+                }
 
                 let mut match_rules = HashMap::default();
 
-                // 处理列表型规则（URL/HTML/Script/ScriptSrc）
+                // 处理列表型规则（URL/HTML/Comment/Script/ScriptSrc）
+                let [html_rules, comment_rules] =
+                    split_comment_rule_set(build_list_match_rule_set(&original_tech.html, "html", MatchScope::Html));
                 let list_rules = vec![
                     build_list_match_rule_set(&original_tech.url, "url", MatchScope::Url),
-                    build_list_match_rule_set(&original_tech.html, "html", MatchScope::Html),
+                    html_rules,
+                    comment_rules,
                     build_list_match_rule_set(&original_tech.scripts, "script", MatchScope::Script),
                     build_list_match_rule_set(
                         &original_tech.script_src,
                         "script_src",
                         MatchScope::ScriptSrc,
                     ),
+                    build_list_match_rule_set(&original_tech.cert_issuer, "cert_issuer", MatchScope::CertIssuer),
+                    build_favicon_match_rule_set(&original_tech.favicon),
+                    build_list_match_rule_set(&original_tech.title, "title", MatchScope::Title),
                 ];
                 batch_insert_list_rules(&mut match_rules, list_rules);
 
@@ -432,6 +618,40 @@ impl WappalyzerParser {
                     }
                 }
 
+                // 处理DNS匹配规则（支持condition字段）
+                if let Some(dns_map) = &original_tech.dns {
+                    let dns_keyed_patterns = build_keyed_match_rule_set(dns_map, "dns");
+                    if !dns_keyed_patterns.is_empty() {
+                        let dns_condition = dns_map
+                            .get("condition")
+                            .and_then(|v| serde_json::from_value(v.clone()).ok())
+                            .unwrap_or_default();
+                        match_rules.insert(
+                            MatchScope::Dns,
+                            MatchRuleSet {
+                                condition: dns_condition,
+                                list_patterns: Vec::new(),
+                                keyed_patterns: dns_keyed_patterns,
+                            },
+                        );
+                    }
+                }
+
+                // 处理DOM匹配规则
+                if let Some(dom_val) = &original_tech.dom {
+                    let dom_keyed_patterns = build_dom_keyed_patterns(dom_val);
+                    if !dom_keyed_patterns.is_empty() {
+                        match_rules.insert(
+                            MatchScope::Dom,
+                            MatchRuleSet {
+                                condition: MatchCondition::Or,
+                                list_patterns: Vec::new(),
+                                keyed_patterns: dom_keyed_patterns,
+                            },
+                        );
+                    }
+                }
+
                 // 构建解析后的技术规则（过滤无匹配规则的项）
                 let parsed_tech_rule = ParsedTechRule {
                     basic,
@@ -463,9 +683,17 @@ impl WappalyzerParser {
             .collect();
 
         // 构建内核规则库
-        RuleLibrary {
+        #[allow(unused_mut)]
+        let mut rule_lib = RuleLibrary {
             core_tech_map,
             category_rules,
-        }
+            meta_table,
+        };
+
+        // 记录模式来源（provenance 特性）
+        #[cfg(feature = "provenance")]
+        rule_lib.stamp_provenance("wappalyzer");
+
+        rule_lib
     }
 }
@@ -0,0 +1,148 @@
+//! YAML 格式规则解析（yaml-rules 特性）
+//! 手写的内部指纹规则含大量正则，JSON 需要对反斜杠二次转义，可读性差；
+//! YAML 规则与 Wappalyzer JSON 使用完全相同的结构（technologies/apps、categories、字段名与模式标签），
+//! 解析后复用同一转换逻辑，并额外做一次正则语法校验：
+//! 1. YAML 语法错误：报告行号与列号
+//! 2. 非法正则：逐条报告技术名、作用域与所在行号（一次性列出全部非法模式）
+
+use regex_syntax::ParserBuilder;
+
+use super::wappalyzer::{WappalyzerOriginalRuleLibrary, WappalyzerParser};
+use crate::cleaner::pattern_tags::parse_pattern_tags;
+use crate::core::RuleLibrary;
+use crate::error::{CoreError, CoreResult};
+use crate::{MatchType, Pattern};
+
+impl WappalyzerParser {
+    /// 从 YAML 字符串解析原始规则库
+    /// 参数：content - YAML 字符串（结构与 Wappalyzer JSON 一致）
+    ///
+    /// 返回：原始规则库 | 解析错误（含行号）
+    pub fn parse_from_yaml_str(&self, content: &str) -> CoreResult<WappalyzerOriginalRuleLibrary> {
+        serde_yaml::from_str(content).map_err(|e| match e.location() {
+            Some(location) => CoreError::RuleParseError(format!(
+                "invalid YAML rules at line {}, column {}: {}",
+                location.line(),
+                location.column(),
+                e
+            )),
+            None => CoreError::RuleParseError(format!("invalid YAML rules: {}", e)),
+        })
+    }
+
+    /// 解析 YAML 规则并转换为内核规则库（校验全部正则模式）
+    /// 参数：content - YAML 字符串
+    ///
+    /// 返回：内核规则库 | 解析错误/非法正则（含行号）
+    pub fn parse_yaml_to_rule_lib(&self, content: &str) -> CoreResult<RuleLibrary> {
+        let original = self.parse_from_yaml_str(content)?;
+        let rule_lib = self.convert_original_to_rule_lib(original);
+        validate_patterns(&rule_lib, content)?;
+        Ok(rule_lib)
+    }
+}
+
+/// 校验规则库中的全部正则模式
+/// 参数：
+/// - rule_lib: 转换后的规则库
+/// - content: YAML 原文（用于定位行号）
+///
+/// 返回：全部合法 | RuleParseError（按行号排序列出全部非法模式）
+pub(super) fn validate_patterns(rule_lib: &RuleLibrary, content: &str) -> CoreResult<()> {
+    let mut errors: Vec<(usize, String)> = Vec::new();
+    for (tech, rule) in &rule_lib.core_tech_map {
+        for (scope, rule_set) in &rule.match_rules {
+            let patterns = rule_set
+                .list_patterns
+                .iter()
+                .chain(rule_set.keyed_patterns.iter().map(|keyed| &keyed.pattern));
+            for pattern in patterns {
+                if let Some(error) = check_pattern(pattern) {
+                    let line = line_of(content, &pattern.pattern)
+                        .or_else(|| line_of(content, &format!("{}:", tech)))
+                        .unwrap_or(0);
+                    errors.push((
+                        line,
+                        format!(
+                            "line {}: {} ({:?}): invalid pattern '{}': {}",
+                            line, tech, scope, pattern.pattern, error
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+    if errors.is_empty() {
+        return Ok(());
+    }
+    errors.sort();
+    let messages: Vec<String> = errors.into_iter().map(|(_, message)| message).collect();
+    Err(CoreError::RuleParseError(format!(
+        "{} invalid pattern(s) in YAML rules:\n{}",
+        messages.len(),
+        messages.join("\n")
+    )))
+}
+
+/// 校验单条模式的正则语法（自定义匹配类型与空模式跳过）
+/// 返回：None 表示合法 | 错误描述
+fn check_pattern(pattern: &Pattern) -> Option<String> {
+    if matches!(pattern.match_type, MatchType::Custom(_)) {
+        return None;
+    }
+    let tags = parse_pattern_tags(&pattern.pattern);
+    if tags.matcher.is_some() {
+        return None;
+    }
+    let body = tags.regex.trim();
+    if body.is_empty() {
+        return None;
+    }
+    ParserBuilder::new().build().parse(body).err().map(|e| {
+        e.to_string()
+            .lines()
+            .last()
+            .unwrap_or_default()
+            .trim_start_matches("error:")
+            .trim()
+            .to_string()
+    })
+}
+
+/// 查找文本首次出现的行号（从 1 开始）
+fn line_of(content: &str, needle: &str) -> Option<usize> {
+    let offset = content.find(needle)?;
+    Some(content[..offset].matches('\n').count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_yaml_rules() {
+        let yaml = r#"
+technologies:
+  Acme Gateway:
+    cats: [22]
+    headers:
+      X-Acme-Gateway: '^acme/([\d.]+)\;version:\1'
+    html: '<div class="acme-[a-z]+">'
+"#;
+        let rule_lib = WappalyzerParser.parse_yaml_to_rule_lib(yaml).unwrap();
+        assert!(rule_lib.core_tech_map.contains_key("Acme Gateway"));
+
+        let invalid = yaml.replace("acme-[a-z]+", "acme-[a-z+");
+        let err = WappalyzerParser
+            .parse_yaml_to_rule_lib(&invalid)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("line 7: Acme Gateway"), "{}", err);
+
+        let err = WappalyzerParser
+            .parse_yaml_to_rule_lib("technologies: [")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("line 1"), "{}", err);
+    }
+}
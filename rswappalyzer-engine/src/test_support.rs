@@ -0,0 +1,24 @@
+//! 单元测试辅助：由 Wappalyzer JSON 规则构建编译后规则库
+
+use rustc_hash::FxHashMap;
+
+use crate::source::WappalyzerParser;
+use crate::{CompileOptions, CompiledRuleLibrary, RuleIndexer, RuleLibrary, RuleLibraryIndex, RuleProcessor};
+
+/// 编译 Wappalyzer JSON 规则（默认编译选项）
+pub(crate) fn compile(rules: &str) -> CompiledRuleLibrary {
+    compile_with(rules, &CompileOptions::default())
+}
+
+/// 编译 Wappalyzer JSON 规则（指定编译选项）
+pub(crate) fn compile_with(rules: &str, options: &CompileOptions) -> CompiledRuleLibrary {
+    let raw = WappalyzerParser.parse_to_rule_lib(rules).unwrap();
+    compile_raw(&raw, options)
+}
+
+/// 编译解析后的原始规则库（其他规则格式转换结果）
+pub(crate) fn compile_raw(raw: &RuleLibrary, options: &CompileOptions) -> CompiledRuleLibrary {
+    let rule_lib = RuleProcessor.clean_and_split_rules(raw).unwrap();
+    let index = RuleLibraryIndex::from_rule_library(&rule_lib).unwrap();
+    RuleIndexer::build_compiled_library_with_category_map(&index, FxHashMap::default(), options).unwrap()
+}
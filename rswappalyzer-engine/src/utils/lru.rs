@@ -0,0 +1,185 @@
+//! 分片 LRU 缓存
+//! 全局缓存在高并发下集中于单把读写锁，且条目只增不减。按 Key 哈希分片各自加锁，并限制总容量：
+//! 1. 每个分片为 `lru::LruCache`（哈希表 + 双向链表），命中时 O(1) 提升为最近使用，超出分片容量时 O(1) 淘汰最久未使用的条目
+//! 2. 命中需更新使用顺序，故在分片写锁内完成；临界区仅为一次哈希查找与链表调整
+//! 3. 未命中时在锁外初始化（如正则编译），避免阻塞同分片的其它 Key；插入前在写锁内再次检查，
+//!    若其它线程已先插入则沿用已有值（并发未命中同一Key时可能重复初始化，但缓存中仅保留一份）
+//! 4. 锁中毒（持锁线程 panic）时继续使用内部数据：值仅在初始化完成后插入，不存在半初始化条目
+
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::PoisonError;
+
+use lru::LruCache;
+use rustc_hash::FxBuildHasher;
+
+use super::sync::RwLock;
+
+/// 默认分片数
+pub const DEFAULT_LRU_SHARDS: usize = 16;
+
+/// 单个分片
+pub type LruShard<K, V> = LruCache<K, V, FxBuildHasher>;
+
+/// 缓存统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LruStats {
+    /// 当前条目数
+    pub entries: usize,
+    /// 容量上限
+    pub capacity: usize,
+    /// 命中次数
+    pub hits: u64,
+    /// 未命中（初始化）次数
+    pub misses: u64,
+    /// 淘汰次数
+    pub evictions: u64,
+}
+
+/// 分片 LRU 缓存
+pub struct ShardedLru<K, V> {
+    shards: Box<[RwLock<LruShard<K, V>>]>,
+    capacity: AtomicUsize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> ShardedLru<K, V> {
+    /// 创建缓存
+    /// 参数：
+    /// - shards: 分片数（至少 1）
+    /// - capacity: 容量上限（按分片均分，每个分片至少 1 条）
+    ///
+    /// 返回：空缓存
+    pub fn new(shards: usize, capacity: usize) -> Self {
+        let shards = shards.max(1);
+        let limit = shard_limit(capacity, shards);
+        Self {
+            shards: (0..shards)
+                .map(|_| RwLock::new(LruCache::with_hasher(limit, FxBuildHasher)))
+                .collect(),
+            capacity: AtomicUsize::new(capacity),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    /// 读取缓存，未命中时在锁外初始化并插入（超出容量时淘汰最久未使用的条目）
+    /// 参数：
+    /// - key: 缓存Key
+    /// - init: 未命中时的初始化函数
+    ///
+    /// 返回：缓存值（克隆）
+    pub fn get_or_insert_with(&self, key: K, init: impl FnOnce() -> V) -> V {
+        let shard = self.shard(&key);
+        {
+            let mut write = shard.write().unwrap_or_else(PoisonError::into_inner);
+            if let Some(value) = write.get(&key) {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return value.clone();
+            }
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let value = init();
+
+        let mut write = shard.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(existing) = write.get(&key) {
+            // 初始化期间已由其它线程插入，沿用已有值
+            return existing.clone();
+        }
+        if write.push(key, value.clone()).is_some() {
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+        value
+    }
+
+    /// 设置容量上限（缩小时立即淘汰超出部分）
+    /// 参数：capacity - 容量上限
+    pub fn set_capacity(&self, capacity: usize) {
+        self.capacity.store(capacity, Ordering::Relaxed);
+        let limit = shard_limit(capacity, self.shards.len());
+        for shard in self.shards.iter() {
+            let mut write = shard.write().unwrap_or_else(PoisonError::into_inner);
+            while write.len() > limit.get() && write.pop_lru().is_some() {
+                self.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+            write.resize(limit);
+        }
+    }
+
+    /// 容量上限
+    pub fn capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    /// 当前条目数（容忍锁中毒）
+    pub fn len(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.read().unwrap_or_else(PoisonError::into_inner).len())
+            .sum()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 当前全部 Key（克隆）
+    pub fn keys(&self) -> Vec<K> {
+        self.shards
+            .iter()
+            .flat_map(|shard| {
+                let read = shard.read().unwrap_or_else(PoisonError::into_inner);
+                read.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// 全部分片（用于诊断与测试）
+    pub fn shards(&self) -> &[RwLock<LruShard<K, V>>] {
+        &self.shards
+    }
+
+    /// 是否有分片锁中毒
+    #[cfg(not(loom))]
+    pub fn is_poisoned(&self) -> bool {
+        self.shards.iter().any(|shard| shard.is_poisoned())
+    }
+
+    /// 缓存统计
+    pub fn stats(&self) -> LruStats {
+        LruStats {
+            entries: self.len(),
+            capacity: self.capacity(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+        }
+    }
+
+    fn shard(&self, key: &K) -> &RwLock<LruShard<K, V>> {
+        // 取哈希高位选分片，避免与分片内哈希表的低位桶索引相关
+        let hash = FxBuildHasher.hash_one(key);
+        &self.shards[(hash >> 40) as usize % self.shards.len()]
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> fmt::Debug for ShardedLru<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShardedLru")
+            .field("shards", &self.shards.len())
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+/// 单个分片的容量上限（总容量按分片均分，至少 1 条）
+fn shard_limit(capacity: usize, shards: usize) -> NonZeroUsize {
+    NonZeroUsize::new(capacity.div_ceil(shards)).unwrap_or(NonZeroUsize::MIN)
+}
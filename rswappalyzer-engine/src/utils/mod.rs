@@ -1,3 +1,5 @@
 pub mod safe_lower;
 //pub mod preview;
-pub mod log_format;
\ No newline at end of file
+pub mod log_format;
+pub mod sync;
+pub mod lru;
\ No newline at end of file
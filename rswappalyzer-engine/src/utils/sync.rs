@@ -0,0 +1,101 @@
+//! 同步原语门面
+//! 默认使用 std::sync；以 `RUSTFLAGS="--cfg loom"` 编译时切换为 loom 实现，对缓存读写逻辑做并发模型检查：
+//! `RUSTFLAGS="--cfg loom" cargo test -p rswappalyzer-engine --lib --release loom`
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::sync::PoisonError;
+
+#[cfg(loom)]
+pub use loom::sync::RwLock;
+#[cfg(not(loom))]
+pub use std::sync::RwLock;
+
+/// 读写锁缓存的双重检查读取
+/// 读锁命中直接返回 → 未命中获取写锁，写锁内再次检查后初始化（同一Key仅初始化一次）
+/// 锁中毒（持锁线程 panic）时继续使用内部数据：值仅在初始化完成后插入，不存在半初始化条目
+/// 参数：
+/// - cache: 缓存
+/// - key: 缓存Key
+/// - init: 未命中时的初始化函数
+///
+/// 返回：缓存值（克隆）
+pub fn get_or_insert_with<K, V, S, F>(cache: &RwLock<HashMap<K, V, S>>, key: K, init: F) -> V
+where
+    K: Eq + Hash,
+    V: Clone,
+    S: BuildHasher,
+    F: FnOnce() -> V,
+{
+    {
+        let read = cache.read().unwrap_or_else(PoisonError::into_inner);
+        if let Some(value) = read.get(&key) {
+            return value.clone();
+        }
+    }
+
+    let mut write = cache.write().unwrap_or_else(PoisonError::into_inner);
+    write.entry(key).or_insert_with(init).clone()
+}
+
+/// 读取缓存条目数（容忍锁中毒）
+pub fn cache_len<K, V, S>(cache: &RwLock<HashMap<K, V, S>>) -> usize {
+    cache.read().unwrap_or_else(PoisonError::into_inner).len()
+}
+
+#[cfg(all(test, loom))]
+mod loom_tests {
+    use super::*;
+    use loom::sync::atomic::{AtomicUsize, Ordering};
+    use loom::sync::Arc;
+    use loom::thread;
+    use rustc_hash::FxHashMap;
+
+    type Cache = RwLock<FxHashMap<u8, usize>>;
+
+    #[test]
+    fn loom_same_key_initialized_once() {
+        loom::model(|| {
+            let cache: Arc<Cache> = Arc::new(RwLock::new(FxHashMap::default()));
+            let inits = Arc::new(AtomicUsize::new(0));
+
+            let handles: Vec<_> = (0..2)
+                .map(|i| {
+                    let cache = cache.clone();
+                    let inits = inits.clone();
+                    thread::spawn(move || {
+                        get_or_insert_with(&cache, 1, || {
+                            inits.fetch_add(1, Ordering::SeqCst);
+                            i
+                        })
+                    })
+                })
+                .collect();
+            let values: Vec<usize> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+            assert_eq!(inits.load(Ordering::SeqCst), 1);
+            assert_eq!(values[0], values[1]);
+            assert_eq!(cache_len(&cache), 1);
+        });
+    }
+
+    #[test]
+    fn loom_distinct_keys_not_lost() {
+        loom::model(|| {
+            let cache: Arc<Cache> = Arc::new(RwLock::new(FxHashMap::default()));
+
+            let handles: Vec<_> = (0..2u8)
+                .map(|key| {
+                    let cache = cache.clone();
+                    thread::spawn(move || get_or_insert_with(&cache, key, || key as usize))
+                })
+                .collect();
+            for (key, handle) in handles.into_iter().enumerate() {
+                assert_eq!(handle.join().unwrap(), key);
+            }
+
+            assert_eq!(cache_len(&cache), 2);
+            assert_eq!(get_or_insert_with(&cache, 0, || usize::MAX), 0);
+        });
+    }
+}
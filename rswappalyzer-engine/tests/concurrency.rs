@@ -0,0 +1,148 @@
+//! 全局正则缓存并发测试
+//! 多线程并发懒编译同一批正则、持锁线程 panic 后的缓存恢复、容量上限淘汰；
+//! 缓存读写逻辑的 loom 模型见 `utils::sync`（`RUSTFLAGS="--cfg loom"`）
+
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use rswappalyzer_engine::indexer::matcher::REGEX_CACHE;
+use rswappalyzer_engine::lru::ShardedLru;
+use rswappalyzer_engine::{MatchType, Matcher, Pattern};
+
+const THREADS: usize = 16;
+
+/// 统计缓存中以指定前缀开头的正则数量（各测试使用独立前缀，互不干扰）
+fn cached_with_prefix(prefix: &str) -> usize {
+    REGEX_CACHE
+        .keys()
+        .iter()
+        .filter(|(pattern, _)| pattern.starts_with(prefix))
+        .count()
+}
+
+fn regex_matcher(source: &str) -> Matcher {
+    let pattern = Pattern::new(source.to_string(), MatchType::Regex, None);
+    Matcher::from_match_type_lazy(&MatchType::Regex, &pattern)
+}
+
+#[test]
+fn test_regex_cache_storm() {
+    let sources: Vec<String> = (0..8).map(|i| format!(r"storm{}/([\d.]+)", i)).collect();
+    let barrier = Arc::new(Barrier::new(THREADS));
+
+    let handles: Vec<_> = (0..THREADS)
+        .map(|t| {
+            let sources = sources.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                barrier.wait();
+                // 各线程以不同顺序访问，制造读写交错
+                for i in (0..sources.len()).map(|i| (i + t) % sources.len()) {
+                    let matcher = regex_matcher(&sources[i]);
+                    let input = format!("STORM{}/{}.0", i, t);
+                    let version = matcher.captures(&input).and_then(|c| c.get(1)).map(|m| m.as_str().to_string());
+                    assert_eq!(version, Some(format!("{}.0", t)));
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // 每个正则仅编译并缓存一次
+    assert_eq!(cached_with_prefix("storm"), sources.len());
+}
+
+#[test]
+fn test_regex_cache_poison_recovery() {
+    let warm = regex_matcher(r"poisonwarm/(\d+)");
+    assert!(warm.matches("poisonwarm/1"));
+
+    // 持有全部分片写锁的线程 panic → 锁中毒
+    let result = thread::spawn(|| {
+        let _guards: Vec<_> = REGEX_CACHE.shards().iter().map(|shard| shard.write().unwrap()).collect();
+        panic!("poison regex cache");
+    })
+    .join();
+    assert!(result.is_err());
+    assert!(REGEX_CACHE.is_poisoned());
+
+    // 中毒后已缓存与新编译的正则均可正常匹配
+    assert!(warm.matches("poisonwarm/2"));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|t| {
+            thread::spawn(move || {
+                let matcher = regex_matcher(r"poisonfresh/(\d+)");
+                assert!(matcher.matches(&format!("poisonfresh/{}", t)));
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(cached_with_prefix("poisonfresh"), 1);
+}
+
+#[test]
+fn test_lru_capacity_eviction() {
+    let cache: ShardedLru<u32, u32> = ShardedLru::new(1, 4);
+    for key in 0..4 {
+        assert_eq!(cache.get_or_insert_with(key, || key * 10), key * 10);
+    }
+    // 访问 0 使其成为最近使用，插入新 Key 时淘汰最久未使用的 1
+    assert_eq!(cache.get_or_insert_with(0, || unreachable!()), 0);
+    cache.get_or_insert_with(4, || 40);
+    let mut keys = cache.keys();
+    keys.sort_unstable();
+    assert_eq!(keys, [0, 2, 3, 4]);
+
+    let stats = cache.stats();
+    assert_eq!((stats.entries, stats.capacity), (4, 4));
+    assert_eq!((stats.hits, stats.misses, stats.evictions), (1, 5, 1));
+
+    // 缩小容量立即淘汰，被淘汰的 Key 再次访问时重新初始化
+    cache.set_capacity(2);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get_or_insert_with(2, || 200), 200);
+    assert_eq!(cache.stats().evictions, 4);
+}
+
+#[test]
+fn test_lru_evicts_in_recency_order() {
+    let cache: ShardedLru<u32, u32> = ShardedLru::new(1, 3);
+    for key in 0..3 {
+        cache.get_or_insert_with(key, || key);
+    }
+    // 使用顺序（旧 → 新）：1, 0, 2 → 再访问 1：0, 2, 1
+    cache.get_or_insert_with(0, || unreachable!());
+    cache.get_or_insert_with(2, || unreachable!());
+    cache.get_or_insert_with(1, || unreachable!());
+
+    // 依次插入 3、4，应先后淘汰 0、2
+    cache.get_or_insert_with(3, || 3);
+    let mut keys = cache.keys();
+    keys.sort_unstable();
+    assert_eq!(keys, [1, 2, 3]);
+    cache.get_or_insert_with(4, || 4);
+    let mut keys = cache.keys();
+    keys.sort_unstable();
+    assert_eq!(keys, [1, 3, 4]);
+}
+
+#[test]
+fn test_lru_init_runs_outside_shard_lock() {
+    // 单分片：初始化函数内访问同分片的其它 Key，若在锁内初始化将死锁
+    let cache: ShardedLru<u32, u32> = ShardedLru::new(1, 8);
+    let value = cache.get_or_insert_with(1, || cache.get_or_insert_with(2, || 20) + 1);
+    assert_eq!(value, 21);
+
+    // 初始化期间同一Key已被插入时沿用已有值
+    let value = cache.get_or_insert_with(3, || {
+        cache.get_or_insert_with(3, || 30);
+        300
+    });
+    assert_eq!(value, 30);
+    assert_eq!(cache.get_or_insert_with(3, || unreachable!()), 30);
+    assert_eq!(cache.len(), 3);
+}
@@ -0,0 +1,110 @@
+//! 内存安全测试子集（可在 Miri 下运行）
+//! 覆盖令牌提取/小写转换/匹配器/匹配门控等核心路径，不依赖文件系统与网络：
+//! `cargo +nightly miri test -p rswappalyzer-engine --test miri_core`
+//! 同时校验工作区各 crate 均声明 `#![forbid(unsafe_code)]`
+
+use std::sync::Arc;
+
+use rswappalyzer_engine::pruner::tokenizer::{extract_atomic_tokens, MAX_INPUT_LITERAL_LENGTH};
+use rswappalyzer_engine::utils::safe_lower::safe_lowercase;
+use rswappalyzer_engine::{MatchGate, MatchType, Matcher, MatcherRegistry, MatcherSpec, Pattern};
+use rustc_hash::FxHashSet;
+
+/// 工作区 crate 根文件（编译期嵌入，Miri 隔离模式下无需文件访问）
+const CRATE_ROOTS: &[(&str, &str)] = &[
+    ("rswappalyzer-engine", include_str!("../src/lib.rs")),
+    ("rswappalyzer", include_str!("../../rswappalyzer/src/lib.rs")),
+    ("rswappalyzer build script", include_str!("../../rswappalyzer/build.rs")),
+];
+
+#[test]
+fn test_workspace_forbids_unsafe_code() {
+    for (name, source) in CRATE_ROOTS {
+        let declared = source
+            .lines()
+            .any(|line| line.trim_start().starts_with("#![forbid(unsafe_code)]"));
+        assert!(declared, "{} does not declare #![forbid(unsafe_code)]", name);
+    }
+}
+
+#[test]
+fn test_extract_atomic_tokens() {
+    let tokens = extract_atomic_tokens("WordPress/6.4 wp-content ab é_unicode");
+    let expected: FxHashSet<String> = ["wordpress", "content", "_unicode"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    assert_eq!(tokens, expected);
+
+    // 多字节字符与超长输入
+    assert!(extract_atomic_tokens("日本語テキスト").is_empty());
+    assert!(extract_atomic_tokens(&"a".repeat(MAX_INPUT_LITERAL_LENGTH + 1)).is_empty());
+}
+
+#[test]
+fn test_safe_lowercase() {
+    assert_eq!(safe_lowercase("NGINX/1.2 Ünï"), "nginx/1.2 Ünï");
+    assert_eq!(safe_lowercase(""), "");
+}
+
+#[test]
+fn test_builtin_matchers() {
+    let contains = Matcher::from_match_type_lazy(&MatchType::Contains, &pattern("wp-content"));
+    assert!(contains.matches("/wp-content/themes"));
+    assert!(!contains.matches("/static"));
+
+    let exists = Matcher::from_match_type_lazy(&MatchType::Exists, &pattern(""));
+    assert!(exists.is_exists());
+    assert!(exists.matches(""));
+
+    let regex = Matcher::from_match_type_lazy(&MatchType::Regex, &pattern(r"nginx/([\d.]+)"));
+    assert!(regex.matches("NGINX/1.25.3"));
+    let version = regex.captures("nginx/1.25.3").and_then(|c| c.get(1)).map(|m| m.as_str());
+    assert_eq!(version, Some("1.25.3"));
+
+    // 序列化描述体往返
+    for matcher in [contains, exists, regex] {
+        let spec = matcher.to_spec();
+        assert_eq!(Matcher::from_spec(&spec).to_spec(), spec);
+    }
+}
+
+#[test]
+fn test_custom_matcher() {
+    let registry = MatcherRegistry::new().with("prefix", |p: &str| {
+        let prefix = Arc::new(p.to_string());
+        Ok(move |input: &str| input.starts_with(prefix.as_str()))
+    });
+
+    let matcher = Matcher::custom(&registry, "prefix", "AAAB").unwrap();
+    assert!(matcher.matches("AAABAAEAEBAA"));
+    assert!(!matcher.matches("iVBORw0KGgo"));
+    assert!(Matcher::custom(&registry, "unknown", "x").is_err());
+
+    // 反序列化后未绑定工厂的自定义匹配器不匹配任何输入
+    let unbound = MatcherSpec::Custom {
+        kind: "prefix".to_string(),
+        pattern: "AAAB".to_string(),
+    }
+    .to_matcher();
+    assert!(!unbound.matches("AAABAAEAEBAA"));
+}
+
+#[test]
+fn test_match_gate() {
+    let input_tokens = extract_atomic_tokens("powered by wordpress");
+
+    let require_all = MatchGate::RequireAll(["wordpress"].into_iter().map(String::from).collect());
+    assert!(require_all.check("", &input_tokens));
+    assert!(!require_all.check("", &FxHashSet::default()));
+
+    let require_any = MatchGate::RequireAnyLiteral(vec!["wp-".to_string(), "drupal".to_string()]);
+    assert!(require_any.check("/wp-json/", &input_tokens));
+    assert!(!require_any.check("/static/", &input_tokens));
+
+    assert!(MatchGate::Open.check("", &FxHashSet::default()));
+}
+
+fn pattern(source: &str) -> Pattern {
+    Pattern::new(source.to_string(), MatchType::Contains, None)
+}
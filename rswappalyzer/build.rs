@@ -2,8 +2,9 @@
 // 1. 读取JSON格式构建配置
 // 2. 读取原始Wappalyzer JSON规则文件并解析
 // 3. 清洗规则并构建索引，编译为运行时高效格式
-// 4. 序列化+可选压缩后写入二进制文件
+// 4. 序列化（默认JSON，binary-rules特性下为MessagePack）+可选压缩后写入二进制文件
 // 5. 产物供主程序通过include_bytes!固化进最终二进制
+#![forbid(unsafe_code)]
 use rswappalyzer_engine::source::WappalyzerParser;
 use rswappalyzer_engine::{
     indexer::{RuleIndexer, RuleLibraryIndex},
@@ -71,10 +72,15 @@ fn main() -> Result<(), Box<dyn Error>> {
     let rule_index = RuleLibraryIndex::from_rule_library(&rule_library)
         .map_err(|e| format!("构建规则索引失败: {}", e))?;
 
-    let compiled_lib =
+    let mut compiled_lib =
         RuleIndexer::build_compiled_library(&rule_index, Some(&cfg.category_json_path))
             .map_err(|e| format!("编译规则库失败: {}", e))?;
 
+    // 展示元信息仅在 full-meta 特性开启时嵌入，避免默认构建的二进制体积膨胀
+    if std::env::var_os("CARGO_FEATURE_FULL_META").is_none() {
+        compiled_lib.meta_table.clear();
+    }
+
     // println!("cargo:warning=🔍 编译后库数据:");
     // println!(
     //     "cargo:warning=🔍 tech_patterns.len() = {}",
@@ -86,15 +92,18 @@ fn main() -> Result<(), Box<dyn Error>> {
     // println!("cargo:warning=🔍 no_evidence_index.len() = {}", compiled_lib.no_evidence_index.len());
 
     // 序列化json
-    let compiled_lib_bin = serde_json::to_vec(&compiled_lib)
+    let compiled_lib_json = serde_json::to_vec(&compiled_lib)
         .map_err(|e| format!("JSON序列化编译规则库失败: {}", e))?;
 
     // 调试代码
     let debug_json_path = Path::new("compiled_rules_debug.json");
-    fs::write(&debug_json_path, &compiled_lib_bin)
+    fs::write(&debug_json_path, &compiled_lib_json)
         .map_err(|e| format!("写入调试 JSON 失败: {} - {}", debug_json_path.display(), e))?;
     println!("✅ 调试 JSON 已写入当前目录: {}", debug_json_path.display());
 
+    // binary-rules特性：以MessagePack（字段名为键）序列化，省去运行期JSON文本解析
+    let compiled_lib_bin = serialize_binary(&compiled_lib)?.unwrap_or(compiled_lib_json);
+
     // 根据配置选择是否进行LZ4压缩
     let compressed_lib = if cfg.enable_compress {
         use lz4_flex::compress_prepend_size;
@@ -122,3 +131,24 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+/// 二进制序列化编译规则库（binary-rules特性）
+/// 规则库大量字段使用 skip_serializing_if，bincode 等按位置编码的格式无法还原，
+/// 因此采用以字段名为键的 MessagePack
+/// 返回：Some(二进制产物) | None（未开启特性，沿用JSON）
+#[cfg(feature = "binary-rules")]
+fn serialize_binary(
+    compiled_lib: &rswappalyzer_engine::CompiledRuleLibrary,
+) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+    let bin = rmp_serde::to_vec_named(compiled_lib)
+        .map_err(|e| format!("MessagePack序列化编译规则库失败: {}", e))?;
+    Ok(Some(bin))
+}
+
+/// 未开启binary-rules特性时不做二进制序列化
+#[cfg(not(feature = "binary-rules"))]
+fn serialize_binary(
+    _compiled_lib: &rswappalyzer_engine::CompiledRuleLibrary,
+) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+    Ok(None)
+}
@@ -146,9 +146,7 @@ async fn detect_with_error_handling(
         Ok(techs) => techs,
         Err(e) => {
             warn!("❌ rswappalyzer识别失败: {}", e);
-            DetectResult {
-                technologies: Vec::new(),
-            }
+            DetectResult::default()
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,76 @@
+//! Accuracy comparison between rswappalyzer and recorded wappalyzergo output
+//! rswappalyzer 与 wappalyzergo 检测结果对比工具
+//! 功能说明：
+//! 1. 读取 JSON Lines 语料（target/urls/headers/body）
+//! 2. 读取 wappalyzergo 录制的结果文件（target -> 技术列表）
+//! 3. 逐目标输出 一致/缺失/多报，汇总召回率与精确率
+//! 4. 可选输出完整 JSON 报告，便于按版本归档对比
+//!
+//! 运行命令：
+//! cargo run --example compare_wappalyzergo --features="compare" -- corpus.jsonl wappalyzergo.json [report.json]
+
+use std::{env, error::Error, fs};
+
+use rswappalyzer::{
+    compare::{compare_corpus, parse_corpus, ReferenceResults},
+    RuleConfig, TechDetector,
+};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (corpus_path, reference_path) = match (args.first(), args.get(1)) {
+        (Some(c), Some(r)) => (c, r),
+        _ => {
+            eprintln!("Usage: compare_wappalyzergo <corpus.jsonl> <wappalyzergo.json> [report.json]");
+            std::process::exit(2);
+        }
+    };
+
+    let corpus = parse_corpus(&fs::read_to_string(corpus_path)?)?;
+    let reference = ReferenceResults::from_json(&fs::read_to_string(reference_path)?)?;
+    let detector = TechDetector::with_embedded_rules(RuleConfig::default())?;
+
+    let report = compare_corpus(&detector, &corpus, &reference)?;
+
+    for target in &report.targets {
+        if target.no_reference {
+            println!("[NO-REF ] {}", target.target);
+            continue;
+        }
+        let status = if target.is_exact() { "MATCH  " } else { "DIFF   " };
+        println!(
+            "[{}] {} | agreed: {} | missing: {:?} | extra: {:?}",
+            status,
+            target.target,
+            target.agreed.len(),
+            target.missing,
+            target.extra
+        );
+        for (tech, (ours, theirs)) in &target.version_mismatch {
+            println!("          version {}: rswappalyzer={:?} wappalyzergo={:?}", tech, ours, theirs);
+        }
+    }
+
+    println!("========================================================================================");
+    println!(
+        "Targets: {} (exact: {}, no reference: {}) | Agreed: {} | Missing: {} | Extra: {} | Version mismatch: {}",
+        report.targets.len(),
+        report.exact_targets(),
+        report.no_reference,
+        report.agreed,
+        report.missing,
+        report.extra,
+        report.version_mismatch
+    );
+    println!(
+        "Recall vs wappalyzergo: {:.2}% | Precision vs wappalyzergo: {:.2}%",
+        report.recall() * 100.0,
+        report.precision() * 100.0
+    );
+
+    if let Some(out) = args.get(2) {
+        fs::write(out, serde_json::to_string_pretty(&report)?)?;
+        println!("Report written to {}", out);
+    }
+    Ok(())
+}
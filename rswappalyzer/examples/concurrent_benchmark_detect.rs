@@ -179,9 +179,7 @@ async fn detect_with_error_handling(
         Ok(techs) => techs,
         Err(e) => {
             warn!("❌ rswappalyzer识别失败: {}", e);
-            DetectResult {
-                technologies: Vec::new(),
-            }
+            DetectResult::default()
         }
     }
 }
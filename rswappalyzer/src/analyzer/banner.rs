@@ -0,0 +1,282 @@
+//! Header 横幅解析分析器（规则匹配之后的兜底流程）
+//! 按 RFC 7231 Server 语法拆分横幅：`product[/version] *( RWS ( product[/version] / comment ) )`，
+//! 产品名归一化后对照内置产品表（可选编辑距离模糊匹配），不依赖 Wappalyzer 正则：
+//! 1. 规则未检出的技术：按策略置信度新增
+//! 2. 规则已检出但缺失版本号的技术：补充版本号（置信度不变）
+
+use log::debug;
+use rswappalyzer_engine::CompiledRuleLibrary;
+use rustc_hash::FxHashMap;
+use std::collections::hash_map::Entry;
+
+use crate::config::banner::BannerPolicy;
+use crate::utils::matched_scopes;
+use crate::VersionExtractor;
+
+/// 参与横幅解析的 Header（Key小写）
+pub const BANNER_HEADERS: &[&str] = &["server", "x-powered-by"];
+
+/// 模糊匹配的最短产品名长度（归一化后），过短的名称仅精确匹配
+const FUZZY_MIN_LEN: usize = 5;
+
+/// 内置产品表：归一化产品名（小写字母数字） -> 技术名
+const BANNER_PRODUCTS: &[(&str, &str)] = &[
+    ("apache", "Apache HTTP Server"),
+    ("nginx", "Nginx"),
+    ("microsoftiis", "IIS"),
+    ("litespeed", "LiteSpeed"),
+    ("openresty", "OpenResty"),
+    ("tengine", "Tengine"),
+    ("caddy", "Caddy"),
+    ("lighttpd", "lighttpd"),
+    ("jetty", "Jetty"),
+    ("kestrel", "Kestrel"),
+    ("envoy", "Envoy"),
+    ("varnish", "Varnish"),
+    ("cloudflare", "Cloudflare"),
+    ("ats", "Apache Traffic Server"),
+    ("resin", "Resin"),
+    ("cowboy", "Cowboy"),
+    ("hiawatha", "Hiawatha"),
+    ("cherokee", "Cherokee"),
+    ("thttpd", "thttpd"),
+    ("mongrel", "Mongrel"),
+    ("webrick", "WEBrick"),
+    ("twistedweb", "TwistedWeb"),
+    ("cherrypy", "CherryPy"),
+    ("uvicorn", "Uvicorn"),
+    ("zope", "Zope"),
+    ("mojolicious", "Mojolicious"),
+    ("phusionpassenger", "Phusion Passenger"),
+    ("openssl", "OpenSSL"),
+    ("modssl", "mod_ssl"),
+    ("modperl", "mod_perl"),
+    ("modpython", "mod_python"),
+    ("modwsgi", "mod_wsgi"),
+    ("php", "PHP"),
+    ("perl", "Perl"),
+    ("python", "Python"),
+    ("express", "Express"),
+    ("aspnet", "Microsoft ASP.NET"),
+    ("ubuntu", "Ubuntu"),
+    ("debian", "Debian"),
+    ("centos", "CentOS"),
+    ("redhat", "Red Hat"),
+    ("fedora", "Fedora"),
+];
+
+/// 横幅令牌
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BannerToken<'a> {
+    /// 产品令牌（product/version）
+    Product { name: &'a str, version: Option<&'a str> },
+    /// 注释（括号内原文，不含外层括号）
+    Comment(&'a str),
+}
+
+/// Header 横幅解析分析器
+pub struct BannerAnalyzer;
+
+impl BannerAnalyzer {
+    /// 横幅兜底分析（需在 HeaderAnalyzer 之后调用）
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库（仅补充规则库中存在的技术）
+    /// - headers: 单值普通Header（Key小写）
+    /// - policy: 横幅解析策略
+    /// - detected: 检测结果（输入输出参数）
+    pub fn analyze(
+        compiled_lib: &CompiledRuleLibrary,
+        headers: &FxHashMap<String, String>,
+        policy: &BannerPolicy,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        for header in BANNER_HEADERS {
+            let Some(banner) = headers.get(*header) else {
+                continue;
+            };
+            for token in parse_banner(banner) {
+                match token {
+                    BannerToken::Product { name, version } => {
+                        if let Some(tech) = lookup_product(name, policy.max_distance) {
+                            let version = version.and_then(VersionExtractor::normalize);
+                            Self::apply(compiled_lib, header, tech, version, policy, detected);
+                        }
+                    }
+                    // 注释中常见发行版/平台信息，如 (Ubuntu) / (Red Hat)
+                    BannerToken::Comment(comment) => {
+                        for part in comment.split(';') {
+                            if let Some(tech) = lookup_product(part, policy.max_distance) {
+                                Self::apply(compiled_lib, header, tech, None, policy, detected);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// 写入横幅结果：仅新增未检出的技术，或为已检出技术补充缺失的版本号
+    fn apply(
+        compiled_lib: &CompiledRuleLibrary,
+        header: &str,
+        tech: &str,
+        version: Option<String>,
+        policy: &BannerPolicy,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        if !compiled_lib.tech_patterns.contains_key(tech) {
+            return;
+        }
+        matched_scopes::mark(tech);
+        match detected.entry(tech.to_string()) {
+            Entry::Vacant(entry) => {
+                debug!("[Banner] Match success | Tech: {} | Key: {} | Version: {:?}", tech, header, version);
+                entry.insert((policy.confidence, version));
+            }
+            Entry::Occupied(mut entry) => {
+                let (_, old_version) = entry.get_mut();
+                if old_version.is_none() && version.is_some() {
+                    debug!("[Banner] Version filled | Tech: {} | Key: {} | Version: {:?}", tech, header, version);
+                    *old_version = version;
+                }
+            }
+        }
+    }
+}
+
+/// 按 RFC 7231 Server 语法拆分横幅
+/// 产品令牌以空白分隔，`/` 后为版本；注释以括号包裹，支持嵌套与反斜杠转义
+/// 参数：banner - Server/X-Powered-By 原始值
+/// 返回：按出现顺序排列的令牌列表
+pub fn parse_banner(banner: &str) -> Vec<BannerToken<'_>> {
+    let bytes = banner.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b if b.is_ascii_whitespace() => pos += 1,
+            b'(' => {
+                let start = pos + 1;
+                let mut depth = 1;
+                pos = start;
+                while pos < bytes.len() && depth > 0 {
+                    match bytes[pos] {
+                        b'\\' => pos += 1,
+                        b'(' => depth += 1,
+                        b')' => depth -= 1,
+                        _ => {}
+                    }
+                    pos += 1;
+                }
+                // 未闭合的注释取到结尾
+                let end = if depth == 0 { pos - 1 } else { bytes.len() };
+                let comment = banner[start..end.min(bytes.len())].trim();
+                if !comment.is_empty() {
+                    tokens.push(BannerToken::Comment(comment));
+                }
+            }
+            _ => {
+                let start = pos;
+                while pos < bytes.len() && !bytes[pos].is_ascii_whitespace() && bytes[pos] != b'(' {
+                    pos += 1;
+                }
+                let product = &banner[start..pos];
+                let (name, version) = match product.split_once('/') {
+                    Some((name, version)) => (name, Some(version).filter(|v| !v.is_empty())),
+                    None => (product, None),
+                };
+                if !name.is_empty() {
+                    tokens.push(BannerToken::Product { name, version });
+                }
+            }
+        }
+    }
+
+    tokens
+}
+
+/// 查找产品表（精确匹配优先，其次按编辑距离取最近项）
+/// 参数：
+/// - name: 横幅中的产品名
+/// - max_distance: 模糊匹配最大编辑距离（0 表示仅精确匹配）
+///
+/// 返回：技术名
+pub fn lookup_product(name: &str, max_distance: usize) -> Option<&'static str> {
+    let normalized = normalize_product(name);
+    if normalized.is_empty() {
+        return None;
+    }
+    if let Some((_, tech)) = BANNER_PRODUCTS.iter().find(|(key, _)| *key == normalized) {
+        return Some(tech);
+    }
+    if max_distance == 0 || normalized.len() < FUZZY_MIN_LEN {
+        return None;
+    }
+
+    BANNER_PRODUCTS
+        .iter()
+        .filter(|(key, _)| key.len() >= FUZZY_MIN_LEN)
+        .map(|(key, tech)| (levenshtein(key, &normalized), *tech))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, tech)| tech)
+}
+
+/// 产品名归一化：仅保留字母数字并转小写（Microsoft-IIS -> microsoftiis）
+fn normalize_product(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// 编辑距离（Levenshtein）
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.as_bytes();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.bytes().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_banner() {
+        let tokens = parse_banner("Apache/2.4.41 (Ubuntu) OpenSSL/1.0.2k-fips (nested (x)) PHP");
+        assert_eq!(
+            tokens,
+            vec![
+                BannerToken::Product { name: "Apache", version: Some("2.4.41") },
+                BannerToken::Comment("Ubuntu"),
+                BannerToken::Product { name: "OpenSSL", version: Some("1.0.2k-fips") },
+                BannerToken::Comment("nested (x)"),
+                BannerToken::Product { name: "PHP", version: None },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lookup_and_version() {
+        assert_eq!(lookup_product("Microsoft-IIS", 0), Some("IIS"));
+        assert_eq!(lookup_product("ngnix", 1), None);
+        assert_eq!(lookup_product("openrsty", 1), Some("OpenResty"));
+        assert_eq!(lookup_product("openrsty", 0), None);
+        assert_eq!(VersionExtractor::normalize("1.0.2k-fips").as_deref(), Some("1.0.2k"));
+        assert_eq!(VersionExtractor::normalize("2.4.41-1ubuntu1").as_deref(), Some("2.4.41"));
+        assert_eq!(VersionExtractor::normalize("beta"), None);
+    }
+}
@@ -131,10 +131,10 @@ pub fn debug_compiled_rule_library(
     // ========== 2. 当前维度全局统计 ==========
     let mut current_scope_techs = FxHashSet::default();
     let mut current_scope_token_count = 0;
-    for (_token, scope_map) in &compiled_lib.evidence_index {
-        if scope_map.contains_key(&current_scope) {
+    for scope_map in compiled_lib.evidence_index.values() {
+        if let Some(techs) = scope_map.get(&current_scope) {
             current_scope_token_count += 1;
-            current_scope_techs.extend(scope_map.get(&current_scope).unwrap());
+            current_scope_techs.extend(techs);
         }
     }
     log::debug!(
@@ -208,7 +208,7 @@ pub fn debug_compiled_rule_library(
     let in_no_evidence = compiled_lib
         .no_evidence_index
         .get(&current_scope)
-        .map_or(false, |techs| techs.contains(target_tech_name));
+        .is_some_and(|techs| techs.contains(target_tech_name));
     log::debug!(
         "[{}] 当前维度({:?})无证据索引状态：{}",
         target_tech_name, current_scope, in_no_evidence
@@ -217,7 +217,7 @@ pub fn debug_compiled_rule_library(
     // 4.4 该技术关联的所有关键词（跨维度）
     let mut related_tokens = Vec::new();
     for (token, scope_map) in &compiled_lib.evidence_index {
-        for (_scope, tech_names) in scope_map {
+        for tech_names in scope_map.values() {
             if tech_names.contains(target_tech_name) {
                 related_tokens.push(token.clone());
             }
@@ -0,0 +1,130 @@
+//! TLS 证书颁发者分析器
+//! 匹配调用方提供的证书颁发者字符串（如 TLS 握手取得的 Issuer `O` / `CN` 字段），
+//! 对应 Wappalyzer `certIssuer` 规则
+
+use rswappalyzer_engine::{scope_pruner::PruneScope, CompiledPattern, CompiledRuleLibrary, CompiledTechRule};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    analyzer::{common::handle_match_success, Analyzer},
+    VersionExtractor,
+};
+
+// 证书颁发者分析器
+pub struct CertAnalyzer;
+impl Analyzer<[CompiledPattern], str> for CertAnalyzer {
+    const TYPE_NAME: &'static str = "CertIssuer";
+
+    fn get_patterns(tech: &CompiledTechRule) -> Option<&[CompiledPattern]> {
+        tech.cert_issuer_patterns.as_deref()
+    }
+
+    fn match_logic(
+        tech_name: &str,
+        patterns: &[CompiledPattern],
+        issuer: &str,
+        issuer_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        for pattern in patterns {
+            if pattern.matches_with_prune(issuer, issuer_tokens) {
+                let version = pattern
+                    .exec
+                    .get_matcher()
+                    .captures(issuer)
+                    .and_then(|cap| VersionExtractor::extract(&pattern.exec.version_template, &cap));
+                handle_match_success(
+                    Self::TYPE_NAME,
+                    tech_name,
+                    "CERT_ISSUER",
+                    issuer,
+                    &version,
+                    Some(pattern.exec.confidence),
+                    &pattern.describe_rule(),
+                    detected,
+                );
+                break;
+            }
+        }
+    }
+}
+
+impl CertAnalyzer {
+    /// 按证书颁发者规则匹配
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - issuer: 证书颁发者字符串（空字符串跳过）
+    /// - detected: 检测结果
+    pub fn analyze(
+        compiled_lib: &CompiledRuleLibrary,
+        issuer: &str,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        let issuer = issuer.trim();
+        if issuer.is_empty() {
+            return;
+        }
+        <Self as Analyzer<_, _>>::analyze(
+            compiled_lib,
+            issuer,
+            std::iter::once(issuer),
+            PruneScope::CertIssuer,
+            detected,
+        );
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::test_support::{detector, names};
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Cloudflare": { "cats": [31], "certIssuer": "Cloudflare" },
+            "Let's Encrypt": { "cats": [70], "certIssuer": ["Let's Encrypt"] },
+            "Nginx": { "cats": [22], "headers": { "Server": "nginx" } }
+        }
+    }"#;
+
+    fn analyze(issuer: &str) -> Vec<String> {
+        let detector = detector(RULES);
+        let mut detected = FxHashMap::default();
+        CertAnalyzer::analyze(detector.compiled_library(), issuer, &mut detected);
+        let mut names: Vec<String> = detected.into_keys().collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_issuer_substring() {
+        assert_eq!(analyze("Cloudflare Inc ECC CA-3"), ["Cloudflare"]);
+        assert_eq!(analyze("R3, Let's Encrypt, US"), ["Let's Encrypt"]);
+        assert!(analyze("DigiCert Inc").is_empty());
+    }
+
+    #[test]
+    fn test_blank_issuer_skipped() {
+        assert!(analyze("").is_empty());
+        assert!(analyze("   \t").is_empty());
+    }
+
+    #[test]
+    fn test_detect_with_cert_issuer() {
+        let detector = detector(RULES);
+        let mut headers = HeaderMap::new();
+        headers.insert("server", "nginx".parse().unwrap());
+        let result = detector
+            .detect_with_cert_issuer(&headers, &[], b"", " Cloudflare, Inc. ")
+            .unwrap();
+        assert_eq!(names(&result), ["Cloudflare", "Nginx"]);
+        let cloudflare = result.find_by_slug("cloudflare").unwrap();
+        assert_eq!(cloudflare.matched_scopes, [PruneScope::CertIssuer]);
+
+        // 未提供颁发者的检测不匹配 certIssuer 规则
+        assert_eq!(names(&detector.detect(&headers, &[], b"").unwrap()), ["Nginx"]);
+    }
+}
@@ -0,0 +1,152 @@
+//! HTML 注释分析器
+//! 匹配 HTML 提取的注释文本（不含 `<!--`/`-->`，多条注释按换行拼接），
+//! 对应仅在注释中自我标识的 CMS（如 `<!-- This site is powered by ... -->`）
+
+use rswappalyzer_engine::{scope_pruner::PruneScope, CompiledPattern, CompiledRuleLibrary, CompiledTechRule};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    analyzer::{common::handle_match_success, extract_tokens, Analyzer},
+    VersionExtractor,
+};
+
+// HTML 注释分析器
+pub struct CommentAnalyzer;
+impl Analyzer<[CompiledPattern], str> for CommentAnalyzer {
+    const TYPE_NAME: &'static str = "Comment";
+
+    fn get_patterns(tech: &CompiledTechRule) -> Option<&[CompiledPattern]> {
+        tech.comment_patterns.as_deref()
+    }
+
+    fn match_logic(
+        tech_name: &str,
+        patterns: &[CompiledPattern],
+        comments: &str,
+        comment_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        for pattern in patterns {
+            if pattern.matches_with_prune(comments, comment_tokens) {
+                let version = pattern
+                    .exec
+                    .get_matcher()
+                    .captures(comments)
+                    .and_then(|cap| VersionExtractor::extract(&pattern.exec.version_template, &cap));
+                handle_match_success(
+                    Self::TYPE_NAME,
+                    tech_name,
+                    "COMMENT",
+                    comments,
+                    &version,
+                    Some(pattern.exec.confidence),
+                    &pattern.describe_rule(),
+                    detected,
+                );
+                break;
+            }
+        }
+    }
+}
+
+impl CommentAnalyzer {
+    /// 按HTML注释规则匹配
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - comments: 注释文本（空字符串跳过）
+    /// - detected: 检测结果
+    pub fn analyze(
+        compiled_lib: &CompiledRuleLibrary,
+        comments: &str,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        Self::analyze_tokenized(compiled_lib, comments, &extract_tokens(std::iter::once(comments)), detected);
+    }
+
+    /// 使用预提取令牌分析（令牌由 PreparedInput 统一提取）
+    pub(crate) fn analyze_tokenized(
+        compiled_lib: &CompiledRuleLibrary,
+        comments: &str,
+        comment_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        if comments.is_empty() {
+            return;
+        }
+        <Self as Analyzer<_, _>>::analyze_with_tokens(compiled_lib, comments, comment_tokens, PruneScope::Comment, detected);
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::test_support::{detector, names};
+    use crate::ScopeMask;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Foo CMS": { "cats": [1], "html": ["<!-- This site is powered by Foo CMS v([\\d.]+)\\;version:\\1", "<div id=\"foo-root\">"] },
+            "Bar Shop": { "cats": [6], "html": "<!--\\s*Bar Shop -->" },
+            "Baz": { "cats": [1], "html": "<!-- baz|data-baz" }
+        }
+    }"#;
+
+    fn analyze(comments: &str) -> Vec<(String, Option<String>)> {
+        let detector = detector(RULES);
+        let mut detected = FxHashMap::default();
+        CommentAnalyzer::analyze(detector.compiled_library(), comments, &mut detected);
+        let mut techs: Vec<_> = detected
+            .into_iter()
+            .map(|(name, (_, version))| (name, version))
+            .collect();
+        techs.sort();
+        techs
+    }
+
+    #[test]
+    fn test_comment_compiled() {
+        let detector = detector(RULES);
+        let lib = detector.compiled_library();
+        // 注释锚定模式拆分到 comment 作用域，其余模式保留在 html 作用域
+        let foo = &lib.tech_patterns["Foo CMS"];
+        assert_eq!(foo.comment_patterns.as_ref().unwrap().len(), 1);
+        assert_eq!(foo.html_patterns.as_ref().unwrap().len(), 1);
+        assert!(lib.tech_patterns["Bar Shop"].html_patterns.is_none());
+        // 顶层含 `|` 的模式保留在 html 作用域
+        assert!(lib.tech_patterns["Baz"].comment_patterns.is_none());
+    }
+
+    #[test]
+    fn test_comment_text_matched() {
+        // 注释文本不含分隔符，多条注释按换行拼接
+        assert_eq!(
+            analyze(" build 42 \n This site is powered by Foo CMS v3.2.1 "),
+            [("Foo CMS".to_string(), Some("3.2.1".to_string()))]
+        );
+        assert_eq!(analyze("Bar Shop "), [("Bar Shop".to_string(), None)]);
+        assert!(analyze("").is_empty());
+    }
+
+    #[test]
+    fn test_comment_detected() {
+        let detector = detector(RULES);
+        let headers = HeaderMap::new();
+        let body = b"<html><body><!--  Bar Shop --></body></html>";
+        let result = detector.detect(&headers, &[], body).unwrap();
+        assert_eq!(names(&result), ["Bar Shop"]);
+        let bar = result.find_by_slug("bar-shop").unwrap();
+        assert_eq!(bar.matched_scopes, [PruneScope::Comment]);
+
+        // 跳过 comment 作用域时注释规则不再命中
+        let scopes = ScopeMask::BODY & !ScopeMask::COMMENT;
+        let result = detector.detect_with_scopes(&headers, &[], body, scopes).unwrap();
+        assert!(result.technologies.is_empty());
+
+        // 注释外的文本不匹配
+        let body = b"<html><body><p>This site is powered by Foo CMS v3.2.1</p></body></html>";
+        assert!(detector.detect(&headers, &[], body).unwrap().technologies.is_empty());
+    }
+}
@@ -78,6 +78,49 @@ pub fn handle_exists_success(
         tech_name,
         target_key
     );
-    dbg!(&detected);
     DetectionUpdater::update(detected, tech_name, confidence, None);
 }
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use crate::test_support::detector;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Shop": { "cats": [6], "cookies": { "shop_session": "\\;confidence:50" } },
+            "Edge": { "cats": [31], "headers": { "X-Edge": "\\;confidence:30", "X-Edge-Version": "^([\\d.]+)$\\;version:\\1\\;confidence:60" } }
+        }
+    }"#;
+
+    fn detect(headers: &[(&'static str, &'static str)]) -> Vec<(String, u8, Option<String>)> {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.append(*name, value.parse().unwrap());
+        }
+        let mut techs: Vec<_> = detector(RULES)
+            .detect(&map, &[], b"")
+            .unwrap()
+            .technologies
+            .into_iter()
+            .map(|t| (t.name, t.confidence, t.version))
+            .collect();
+        techs.sort();
+        techs
+    }
+
+    #[test]
+    fn test_exists_confidence_tag() {
+        assert_eq!(
+            detect(&[("cookie", "shop_session=1"), ("x-edge", "on")]),
+            [("Edge".to_string(), 30, None), ("Shop".to_string(), 50, None)]
+        );
+        // 同一技术多条模式命中时取最高置信度
+        assert_eq!(
+            detect(&[("x-edge", "on"), ("x-edge-version", "3.0")]),
+            [("Edge".to_string(), 60, Some("3.0".to_string()))]
+        );
+    }
+}
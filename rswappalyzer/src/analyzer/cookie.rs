@@ -1,7 +1,7 @@
 use rswappalyzer_engine::{CompiledPattern, CompiledRuleLibrary, CompiledTechRule, scope_pruner::PruneScope};
 use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::{VersionExtractor, analyzer::{Analyzer, common::handle_match_success}};
+use crate::{VersionExtractor, analyzer::{Analyzer, common::handle_match_success}, utils::PreparedHeaders};
 
 // Cookie 分析器
 pub struct CookieAnalyzer;
@@ -95,4 +95,19 @@ impl CookieAnalyzer {
             detected,
         );
     }
+
+    /// 使用预处理Header分析（复用预提取令牌）
+    pub fn analyze_prepared(
+        compiled_lib: &CompiledRuleLibrary,
+        prepared: &PreparedHeaders,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        <Self as Analyzer<_, _>>::analyze_with_tokens(
+            compiled_lib,
+            &prepared.standard_cookies,
+            &prepared.cookie_tokens,
+            PruneScope::Cookie,
+            detected,
+        );
+    }
 }
@@ -0,0 +1,181 @@
+//! DNS 记录分析器
+//! 匹配调用方自行解析的 DNS 记录（记录类型 → 记录值列表），对应 Wappalyzer `dns` 规则
+
+use rswappalyzer_engine::{scope_pruner::PruneScope, CompiledPattern, CompiledRuleLibrary, CompiledTechRule};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    analyzer::{common::handle_match_success, Analyzer},
+    VersionExtractor,
+};
+
+// DNS 记录分析器
+pub struct DnsAnalyzer;
+
+impl Analyzer<FxHashMap<String, Vec<CompiledPattern>>, FxHashMap<String, Vec<String>>> for DnsAnalyzer {
+    const TYPE_NAME: &'static str = "Dns";
+
+    fn get_patterns(tech: &CompiledTechRule) -> Option<&FxHashMap<String, Vec<CompiledPattern>>> {
+        tech.dns_patterns.as_ref()
+    }
+
+    fn match_logic(
+        tech_name: &str,
+        dns_patterns: &FxHashMap<String, Vec<CompiledPattern>>,
+        records: &FxHashMap<String, Vec<String>>,
+        record_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        for (record_type, patterns) in dns_patterns {
+            let Some(values) = records.get(record_type) else {
+                continue;
+            };
+
+            // 同一记录类型内任一记录值命中任一模式即记录
+            let hit = values.iter().find_map(|value| {
+                patterns
+                    .iter()
+                    .find(|pattern| {
+                        pattern.exec.get_matcher().is_exists() || pattern.matches_with_prune(value, record_tokens)
+                    })
+                    .map(|pattern| (pattern, value))
+            });
+            let Some((pattern, value)) = hit else {
+                continue;
+            };
+
+            let version = pattern
+                .exec
+                .get_matcher()
+                .captures(value)
+                .and_then(|cap| VersionExtractor::extract(&pattern.exec.version_template, &cap));
+            handle_match_success(
+                Self::TYPE_NAME,
+                tech_name,
+                record_type,
+                value,
+                &version,
+                Some(pattern.exec.confidence),
+                &pattern.describe_rule(),
+                detected,
+            );
+        }
+    }
+}
+
+impl DnsAnalyzer {
+    /// 按 DNS 记录规则匹配
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - records: 记录类型（大小写不敏感）→ 记录值列表
+    /// - detected: 检测结果
+    pub fn analyze(
+        compiled_lib: &CompiledRuleLibrary,
+        records: &FxHashMap<String, Vec<String>>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        // 规则键为小写记录类型，同类型不同写法的记录值合并
+        let mut normalized: FxHashMap<String, Vec<String>> = FxHashMap::default();
+        for (record_type, values) in records {
+            normalized
+                .entry(record_type.to_lowercase())
+                .or_default()
+                .extend(values.iter().map(|value| value.trim().to_string()));
+        }
+        if normalized.values().all(Vec::is_empty) {
+            return;
+        }
+
+        let token_iter = normalized.values().flatten();
+        <Self as Analyzer<_, _>>::analyze(compiled_lib, &normalized, token_iter, PruneScope::Dns, detected);
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::test_support::detector;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Google Workspace": { "cats": [30], "dns": { "MX": ["aspmx\\.l\\.google\\.com"], "TXT": ["google-site-verification"] } },
+            "Amazon SES": { "cats": [75], "dns": { "TXT": ["amazonses:"] } },
+            "Mail Server": { "cats": [30], "dns": { "MX": "mail\\.example\\.com", "SOA": ["ns([\\d]+)\\.example\\.com\\;version:\\1"] } },
+            "Route 53": { "cats": [31], "dns": { "NS": "" } }
+        }
+    }"#;
+
+    fn records(pairs: &[(&str, &str)]) -> FxHashMap<String, Vec<String>> {
+        let mut map: FxHashMap<String, Vec<String>> = FxHashMap::default();
+        for (record_type, value) in pairs {
+            map.entry(record_type.to_string()).or_default().push(value.to_string());
+        }
+        map
+    }
+
+    fn analyze(pairs: &[(&str, &str)]) -> Vec<(String, Option<String>)> {
+        let detector = detector(RULES);
+        let mut detected = FxHashMap::default();
+        DnsAnalyzer::analyze(detector.compiled_library(), &records(pairs), &mut detected);
+        let mut techs: Vec<_> = detected
+            .into_iter()
+            .map(|(name, (_, version))| (name, version))
+            .collect();
+        techs.sort();
+        techs
+    }
+
+    #[test]
+    fn test_dns_compiled() {
+        let detector = detector(RULES);
+        let tech = &detector.compiled_library().tech_patterns["Google Workspace"];
+        let patterns = tech.dns_patterns.as_ref().unwrap();
+        // 规则键统一为小写记录类型
+        let mut keys: Vec<_> = patterns.keys().collect();
+        keys.sort();
+        assert_eq!(keys, ["mx", "txt"]);
+    }
+
+    #[test]
+    fn test_record_types_merged_case_insensitive() {
+        // 同类型不同写法的记录值合并后任一命中即可
+        assert_eq!(
+            analyze(&[("TXT", "v=spf1 -all"), ("txt", "amazonses:abc123")]),
+            [("Amazon SES".to_string(), None)]
+        );
+        // 记录类型不匹配时不命中
+        assert!(analyze(&[("CNAME", "aspmx.l.google.com")]).is_empty());
+    }
+
+    #[test]
+    fn test_version_and_exists() {
+        assert_eq!(
+            analyze(&[("SOA", "  ns2.example.com. hostmaster.example.com.  ")]),
+            [("Mail Server".to_string(), Some("2".to_string()))]
+        );
+        // 存在性模式：该类型有任一记录即命中
+        assert_eq!(analyze(&[("ns", "ns-1.awsdns-01.org.")]), [("Route 53".to_string(), None)]);
+    }
+
+    #[test]
+    fn test_empty_records_skipped() {
+        assert!(analyze(&[]).is_empty());
+        let mut empty = FxHashMap::default();
+        empty.insert("NS".to_string(), Vec::new());
+        let detector = detector(RULES);
+        let result = detector.detect_with_dns(&HeaderMap::new(), &[], b"", &empty).unwrap();
+        assert!(result.technologies.is_empty());
+    }
+
+    #[test]
+    fn test_dns_scope_recorded() {
+        let detector = detector(RULES);
+        let records = records(&[("MX", "10 mail.example.com.")]);
+        let result = detector.detect_with_dns(&HeaderMap::new(), &[], b"", &records).unwrap();
+        let mail = result.find_by_slug("mail-server").unwrap();
+        assert_eq!(mail.matched_scopes, [PruneScope::Dns]);
+    }
+}
@@ -0,0 +1,285 @@
+//! DOM 分析器
+//! 按 Wappalyzer `dom` 规则的 CSS 选择器解析 HTML 文档，检查元素存在/属性值/文本内容：
+//! 1. 全部 DOM 规则按选择器分组编入 DomIndex（检测器生命周期内懒构建一次，lol_html 不支持的选择器跳过）
+//! 2. 单次流式解析文档，收集每个规则键命中的取值（单键取值条数与单条文本长度有上限）
+//! 3. 逐技术匹配 DOM 规则：存在性模式仅要求键命中，属性/文本模式匹配收集到的取值
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use lol_html::html_content::{Element, TextChunk};
+use lol_html::{ElementContentHandlers, HtmlRewriter, Selector, Settings};
+use once_cell::sync::OnceCell;
+use rswappalyzer_engine::{
+    scope_pruner::PruneScope, CompiledPattern, CompiledRuleLibrary, CompiledTechRule, DomKey, DomTarget,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    analyzer::{
+        common::{handle_exists_success, handle_match_success},
+        Analyzer,
+    },
+    VersionExtractor,
+};
+
+/// 单个规则键最多收集的取值条数
+const MAX_VALUES_PER_KEY: usize = 32;
+/// 单条元素文本的最大长度（字节，超出部分截断）
+const MAX_TEXT_LEN: usize = 4096;
+
+/// 文档中命中的 DOM 规则键 → 取值（属性值/元素文本；存在性检查为空串）
+pub(crate) type DomHits = FxHashMap<String, Vec<String>>;
+
+/// DOM 选择器索引（检测器及其克隆之间共享）
+#[derive(Debug, Default)]
+pub(crate) struct DomIndex {
+    selectors: OnceCell<Vec<DomSelector>>,
+}
+
+impl DomIndex {
+    /// 按选择器分组的 DOM 规则（首次调用时构建）
+    pub(crate) fn selectors(&self, lib: &CompiledRuleLibrary) -> &[DomSelector] {
+        self.selectors.get_or_init(|| DomSelector::build_all(lib))
+    }
+}
+
+/// 单个选择器及其检查项
+#[derive(Debug)]
+pub(crate) struct DomSelector {
+    selector: Selector,
+    /// 元素存在检查的规则键
+    exists_keys: Vec<String>,
+    /// 属性检查：(规则键, 属性名)
+    attribute_keys: Vec<(String, String)>,
+    /// 文本内容检查的规则键
+    text_keys: Vec<String>,
+}
+
+impl DomSelector {
+    /// 收集规则库全部 DOM 规则键并按选择器分组（按选择器排序，保证解析顺序稳定）
+    fn build_all(lib: &CompiledRuleLibrary) -> Vec<Self> {
+        let mut keys: FxHashSet<&String> = FxHashSet::default();
+        for tech in lib.tech_patterns.values() {
+            keys.extend(tech.dom_patterns.iter().flat_map(|patterns| patterns.keys()));
+        }
+        let mut grouped: FxHashMap<String, Vec<(String, DomTarget)>> = FxHashMap::default();
+        for key in keys {
+            let parsed = DomKey::parse(key);
+            grouped.entry(parsed.selector).or_default().push((key.clone(), parsed.target));
+        }
+
+        let mut grouped: Vec<_> = grouped.into_iter().collect();
+        grouped.sort_by(|a, b| a.0.cmp(&b.0));
+
+        grouped
+            .into_iter()
+            .filter_map(|(selector, targets)| {
+                let parsed = selector
+                    .parse::<Selector>()
+                    .map_err(|e| log::debug!("Unsupported DOM selector skipped | Selector: {} | Error: {}", selector, e))
+                    .ok()?;
+                let mut dom_selector = Self {
+                    selector: parsed,
+                    exists_keys: Vec::new(),
+                    attribute_keys: Vec::new(),
+                    text_keys: Vec::new(),
+                };
+                for (key, target) in targets {
+                    match target {
+                        DomTarget::Exists => dom_selector.exists_keys.push(key),
+                        DomTarget::Attribute(name) => dom_selector.attribute_keys.push((key, name)),
+                        DomTarget::Text => dom_selector.text_keys.push(key),
+                    }
+                }
+                Some(dom_selector)
+            })
+            .collect()
+    }
+}
+
+/// 记录规则键命中的取值（超出单键上限的取值丢弃）
+fn push_hit(hits: &mut DomHits, key: &str, value: String) {
+    let values = hits.entry(key.to_string()).or_default();
+    if values.len() < MAX_VALUES_PER_KEY {
+        values.push(value);
+    }
+}
+
+/// 按字符边界截断文本
+fn truncate_text(mut text: String) -> String {
+    if text.len() > MAX_TEXT_LEN {
+        let mut end = MAX_TEXT_LEN;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+    }
+    text
+}
+
+// DOM 分析器
+pub struct DomAnalyzer;
+impl Analyzer<FxHashMap<String, Vec<CompiledPattern>>, DomHits> for DomAnalyzer {
+    const TYPE_NAME: &'static str = "Dom";
+
+    fn get_patterns(tech: &CompiledTechRule) -> Option<&FxHashMap<String, Vec<CompiledPattern>>> {
+        tech.dom_patterns.as_ref()
+    }
+
+    fn match_logic(
+        tech_name: &str,
+        dom_patterns: &FxHashMap<String, Vec<CompiledPattern>>,
+        hits: &DomHits,
+        _input_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        for (key, patterns) in dom_patterns {
+            let Some(values) = hits.get(key) else {
+                continue;
+            };
+            for pattern in patterns {
+                let matcher = pattern.exec.get_matcher();
+                if matcher.is_exists() {
+                    handle_exists_success(Self::TYPE_NAME, tech_name, key, Some(pattern.exec.confidence), detected);
+                    break;
+                }
+                // DOM 取值不参与令牌剪枝，直接匹配
+                if let Some(value) = values.iter().find(|value| pattern.matches(value)) {
+                    let version = matcher
+                        .captures(value)
+                        .and_then(|cap| VersionExtractor::extract(&pattern.exec.version_template, &cap));
+                    handle_match_success(
+                        Self::TYPE_NAME,
+                        tech_name,
+                        key,
+                        value,
+                        &version,
+                        Some(pattern.exec.confidence),
+                        &pattern.describe_rule(),
+                        detected,
+                    );
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl DomAnalyzer {
+    /// 解析文档并按 DOM 规则匹配
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - selectors: 按选择器分组的 DOM 规则（DomIndex::selectors）
+    /// - html: HTML 文本
+    /// - detected: 检测结果
+    pub(crate) fn analyze(
+        compiled_lib: &CompiledRuleLibrary,
+        selectors: &[DomSelector],
+        html: &str,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        if selectors.is_empty() || html.is_empty() {
+            return;
+        }
+        let hits = Self::collect(selectors, html);
+        if hits.is_empty() {
+            return;
+        }
+        <Self as Analyzer<_, _>>::analyze_with_tokens(
+            compiled_lib,
+            &hits,
+            &FxHashSet::default(),
+            PruneScope::Dom,
+            detected,
+        );
+    }
+
+    /// 单次流式解析文档，收集全部选择器命中的规则键及取值
+    /// 参数：
+    /// - selectors: 按选择器分组的 DOM 规则
+    /// - html: HTML 文本
+    ///
+    /// 返回：规则键 → 取值
+    pub(crate) fn collect(selectors: &[DomSelector], html: &str) -> DomHits {
+        let hits = Rc::new(RefCell::new(DomHits::default()));
+        let mut handlers = Vec::with_capacity(selectors.len());
+        // 未闭合元素（文档结束时仍在栈内）的文本在解析结束后补记
+        let mut open_texts = Vec::new();
+
+        for dom_selector in selectors {
+            let element_hits = Rc::clone(&hits);
+            // 文本检查：元素开始时压入缓冲，文本块追加到栈内全部缓冲（外层元素包含内层文本），结束标签时弹出记录
+            let text_keys: Rc<[String]> = dom_selector.text_keys.clone().into();
+            let text_stack: Rc<RefCell<Vec<String>>> = Rc::default();
+            let element_stack = Rc::clone(&text_stack);
+            if !text_keys.is_empty() {
+                open_texts.push((Rc::clone(&text_stack), Rc::clone(&text_keys)));
+            }
+
+            let mut content = ElementContentHandlers::default().element(move |el: &mut Element<'_, '_>| {
+                let mut hits = element_hits.borrow_mut();
+                for key in &dom_selector.exists_keys {
+                    push_hit(&mut hits, key, String::new());
+                }
+                for (key, name) in &dom_selector.attribute_keys {
+                    if let Some(value) = el.get_attribute(name) {
+                        push_hit(&mut hits, key, value);
+                    }
+                }
+                if !text_keys.is_empty() && el.can_have_content() {
+                    element_stack.borrow_mut().push(String::new());
+                    let stack = Rc::clone(&element_stack);
+                    let end_hits = Rc::clone(&element_hits);
+                    let keys = Rc::clone(&text_keys);
+                    el.on_end_tag(Box::new(move |_| {
+                        if let Some(text) = stack.borrow_mut().pop() {
+                            let text = truncate_text(text);
+                            let mut hits = end_hits.borrow_mut();
+                            for key in keys.iter() {
+                                push_hit(&mut hits, key, text.trim().to_string());
+                            }
+                        }
+                        Ok(())
+                    }))?;
+                }
+                Ok(())
+            });
+            if !dom_selector.text_keys.is_empty() {
+                let chunk_stack = Rc::clone(&text_stack);
+                content = content.text(move |chunk: &mut TextChunk<'_>| {
+                    for buffer in chunk_stack.borrow_mut().iter_mut() {
+                        if buffer.len() < MAX_TEXT_LEN {
+                            buffer.push_str(chunk.as_str());
+                        }
+                    }
+                    Ok(())
+                });
+            }
+            handlers.push((std::borrow::Cow::Borrowed(&dom_selector.selector), content));
+        }
+
+        let settings = Settings {
+            strict: false,
+            element_content_handlers: handlers,
+            ..Settings::default()
+        };
+        let mut rewriter = HtmlRewriter::new(settings, |_: &[u8]| {});
+        let _ = rewriter.write(html.as_bytes());
+        let _ = rewriter.end();
+
+        for (stack, keys) in open_texts {
+            let mut hits = hits.borrow_mut();
+            for text in stack.take() {
+                let text = truncate_text(text);
+                for key in keys.iter() {
+                    push_hit(&mut hits, key, text.trim().to_string());
+                }
+            }
+        }
+
+        Rc::try_unwrap(hits)
+            .map(RefCell::into_inner)
+            .unwrap_or_else(|shared| shared.borrow().clone())
+    }
+}
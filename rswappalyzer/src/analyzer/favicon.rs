@@ -0,0 +1,130 @@
+//! Favicon 哈希分析器
+//! 由调用方拉取的 favicon 字节计算 mmh3 哈希（见 utils::favicon），按整串匹配规则中的哈希值
+
+use rswappalyzer_engine::{scope_pruner::PruneScope, CompiledPattern, CompiledRuleLibrary, CompiledTechRule};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::analyzer::{common::handle_match_success, Analyzer};
+
+// Favicon 哈希分析器
+pub struct FaviconAnalyzer;
+impl Analyzer<[CompiledPattern], str> for FaviconAnalyzer {
+    const TYPE_NAME: &'static str = "FaviconHash";
+
+    fn get_patterns(tech: &CompiledTechRule) -> Option<&[CompiledPattern]> {
+        tech.favicon_hash_patterns.as_deref()
+    }
+
+    fn match_logic(
+        tech_name: &str,
+        patterns: &[CompiledPattern],
+        hash: &str,
+        hash_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        if let Some(pattern) = patterns.iter().find(|pattern| pattern.matches_with_prune(hash, hash_tokens)) {
+            handle_match_success(
+                Self::TYPE_NAME,
+                tech_name,
+                "FAVICON_HASH",
+                hash,
+                &None,
+                Some(pattern.exec.confidence),
+                &pattern.describe_rule(),
+                detected,
+            );
+        }
+    }
+}
+
+impl FaviconAnalyzer {
+    /// 按 favicon 哈希规则匹配
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - hash: favicon mmh3 哈希（十进制有符号整数字符串）
+    /// - detected: 检测结果
+    pub fn analyze(
+        compiled_lib: &CompiledRuleLibrary,
+        hash: &str,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        <Self as Analyzer<_, _>>::analyze(
+            compiled_lib,
+            hash,
+            std::iter::once(hash),
+            PruneScope::FaviconHash,
+            detected,
+        );
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::test_support::{detector, names};
+    use crate::utils::favicon::favicon_hash;
+    use crate::TechDetector;
+
+    const ACME_ICON: &[u8] = b"\x00\x00\x01\x00acme-favicon-bytes";
+    const OTHER_ICON: &[u8] = b"\x00\x00\x01\x00other-favicon";
+
+    fn favicon_detector() -> TechDetector {
+        let hash = favicon_hash(ACME_ICON);
+        // 与真实哈希前缀相同的规则
+        let prefix = &hash.to_string()[..4];
+        detector(&format!(
+            r#"{{
+                "technologies": {{
+                    "Acme Portal": {{ "cats": [1], "favicon": [{hash}] }},
+                    "Acme Legacy": {{ "cats": [1], "faviconHash": "{hash}" }},
+                    "Prefix": {{ "cats": [1], "favicon": "{prefix}" }}
+                }}
+            }}"#
+        ))
+    }
+
+    fn analyze(detector: &TechDetector, hash: &str) -> Vec<String> {
+        let mut detected = FxHashMap::default();
+        FaviconAnalyzer::analyze(detector.compiled_library(), hash, &mut detected);
+        let mut techs: Vec<String> = detected.into_keys().collect();
+        techs.sort();
+        techs
+    }
+
+    #[test]
+    fn test_favicon_compiled() {
+        let detector = favicon_detector();
+        let lib = detector.compiled_library();
+        // 数值与字符串写法、favicon 与 faviconHash 键均编译为哈希模式
+        let patterns = lib.tech_patterns["Acme Portal"].favicon_hash_patterns.as_ref().unwrap();
+        assert_eq!(patterns.len(), 1);
+        assert!(lib.tech_patterns["Acme Legacy"].favicon_hash_patterns.is_some());
+    }
+
+    #[test]
+    fn test_whole_hash_match() {
+        let detector = favicon_detector();
+        let hash = favicon_hash(ACME_ICON).to_string();
+        assert_eq!(analyze(&detector, &hash), ["Acme Legacy", "Acme Portal"]);
+        // 整串匹配：前缀规则与完整哈希互不命中
+        assert_eq!(analyze(&detector, &hash[..4]), ["Prefix"]);
+        assert!(analyze(&detector, &favicon_hash(OTHER_ICON).to_string()).is_empty());
+    }
+
+    #[test]
+    fn test_favicon_detected() {
+        let detector = favicon_detector();
+        let headers = HeaderMap::new();
+        let result = detector.detect_with_favicon(&headers, &[], b"", ACME_ICON).unwrap();
+        assert_eq!(names(&result), ["Acme Legacy", "Acme Portal"]);
+        let portal = result.find_by_slug("acme-portal").unwrap();
+        assert_eq!(portal.matched_scopes, [PruneScope::FaviconHash]);
+
+        // 空 favicon 不参与匹配
+        let result = detector.detect_with_favicon(&headers, &[], b"", b"").unwrap();
+        assert!(result.technologies.is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 use rswappalyzer_engine::{CompiledPattern, CompiledRuleLibrary, CompiledTechRule, scope_pruner::PruneScope};
 use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::{VersionExtractor, analyzer::{Analyzer, common::handle_match_success}};
+use crate::{VersionExtractor, analyzer::{Analyzer, common::handle_match_success}, utils::PreparedHeaders};
 
 
 // Header 分析器
@@ -26,29 +26,38 @@ impl Analyzer<FxHashMap<String, Vec<CompiledPattern>>, FxHashMap<String, String>
             let mut confidence: Option<u8> = None;
             let mut version: Option<String> = None;
             let mut matched_rule = String::new();
+            let mut version_header: Option<&str> = None;
 
             for pattern in patterns {
                 let matcher = pattern.exec.get_matcher();
                 if matcher.is_exists() {
                     if header_val.is_some() {
                         matched = true;
-                        matched_rule = matcher.describe();
+                        matched_rule = pattern.describe_rule();
                         confidence = Some(pattern.exec.confidence);
+                        version_header = pattern.exec.version_header.as_deref();
                     }
                 } else if let Some(val) = header_val {
                     if pattern.matches_with_prune(val, header_tokens) {
                         matched = true;
-                        matched_rule = matcher.describe();
+                        matched_rule = pattern.describe_rule();
                         confidence = Some(pattern.exec.confidence);
                         version = matcher.captures(val).and_then(|cap| {
                             VersionExtractor::extract(&pattern.exec.version_template, &cap)
                         });
+                        version_header = pattern.exec.version_header.as_deref();
                         break;
                     }
                 }
             }
 
             if matched {
+                // 匹配 Header 未提取到版本时，从规则声明的版本来源 Header 取版本
+                if version.is_none() {
+                    version = version_header
+                        .and_then(|h| headers.get(h))
+                        .and_then(|v| VersionExtractor::normalize(v.trim().trim_start_matches(['v', 'V'])));
+                }
                 handle_match_success(
                     Self::TYPE_NAME,
                     tech_name,
@@ -72,4 +81,19 @@ impl HeaderAnalyzer {
     ) {
         <Self as Analyzer<_, _>>::analyze(compiled_lib, headers, headers.values(), PruneScope::Header, detected);
     }
+
+    /// 使用预处理Header分析（复用预提取令牌）
+    pub fn analyze_prepared(
+        compiled_lib: &CompiledRuleLibrary,
+        prepared: &PreparedHeaders,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        <Self as Analyzer<_, _>>::analyze_with_tokens(
+            compiled_lib,
+            &prepared.single_header_map,
+            &prepared.header_tokens,
+            PruneScope::Header,
+            detected,
+        );
+    }
 }
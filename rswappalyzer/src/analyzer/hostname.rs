@@ -0,0 +1,47 @@
+//! 主机名后缀分析器
+//! 从 URL 中提取主机名，在规则库的后缀字典树中按标签查找（`*.github.io` / `netlify.app` 等托管平台），
+//! 不经过令牌剪枝与正则匹配
+
+use rswappalyzer_engine::{url_host, CompiledRuleLibrary};
+use rustc_hash::FxHashMap;
+
+use crate::analyzer::common::handle_match_success;
+
+/// 主机名后缀分析器
+pub struct HostnameAnalyzer;
+
+impl HostnameAnalyzer {
+    const TYPE_NAME: &'static str = "Hostname";
+
+    /// 主机名后缀分析
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - urls: 检测的URL列表
+    /// - detected: 检测结果（输入输出参数）
+    pub fn analyze(
+        compiled_lib: &CompiledRuleLibrary,
+        urls: &[&str],
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        if compiled_lib.hostname_index.is_empty() {
+            return;
+        }
+        for url in urls {
+            let Some(host) = url_host(url) else {
+                continue;
+            };
+            for rule in compiled_lib.hostname_index.lookup(&host) {
+                handle_match_success(
+                    Self::TYPE_NAME,
+                    &rule.tech,
+                    &host,
+                    url,
+                    &None,
+                    Some(rule.confidence),
+                    "hostname suffix",
+                    detected,
+                );
+            }
+        }
+    }
+}
@@ -3,12 +3,12 @@ use std::borrow::Cow;
 use rswappalyzer_engine::{CompiledPattern, CompiledRuleLibrary, CompiledTechRule, scope_pruner::PruneScope};
 use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::{VersionExtractor, analyzer::{Analyzer, common::handle_match_success}};
+use crate::analyzer::{extract_tokens, match_single_input, Analyzer, ScannedText};
 
 // HTML 分析器
 pub struct HtmlAnalyzer;
 //impl Analyzer<Vec<CompiledPattern>, str> for HtmlAnalyzer {
-impl Analyzer<[CompiledPattern], str> for HtmlAnalyzer {
+impl<'a> Analyzer<[CompiledPattern], ScannedText<'a>> for HtmlAnalyzer {
     const TYPE_NAME: &'static str = "HTML";
 
     //fn get_patterns(tech: &CompiledTechRule) -> Option<&Vec<CompiledPattern>> {
@@ -21,35 +21,37 @@ impl Analyzer<[CompiledPattern], str> for HtmlAnalyzer {
         tech_name: &str,
         //patterns: &Vec<CompiledPattern>,
         patterns: &[CompiledPattern],
-        html: &str,
+        html: &ScannedText<'a>,
         html_tokens: &FxHashSet<String>,
         detected: &mut FxHashMap<String, (u8, Option<String>)>,
     ) {
-        for pattern in patterns {
-            // if tech_name == "Slimbox" {
-            //     log::debug!(
-            //         "HTML内容: {}, Pattern内容: {}",
-            //         &html,
-            //         &pattern.exec.get_matcher().describe()
-            //     );
-            // }
-            let matcher = pattern.exec.get_matcher();
-            if pattern.matches_with_prune(html, html_tokens) {
-            //if pattern.matches(html) {
-                let version = matcher
-                    .captures(html)
-                    .and_then(|cap| VersionExtractor::extract(&pattern.exec.version_template, &cap));
-                handle_match_success(
-                    Self::TYPE_NAME,
-                    tech_name,
-                    "HTML_CONTENT",
-                    html,
-                    &version,
-                    Some(pattern.exec.confidence),
-                    &matcher.describe(),
-                    detected,
-                );
-            }
+        match_single_input(Self::TYPE_NAME, tech_name, "HTML_CONTENT", patterns, None, html, html_tokens, detected);
+    }
+
+    /// Or 条件下使用技术的 HTML 正则集合，一次扫描得到全部正则的命中结果
+    fn match_tech(
+        tech: &CompiledTechRule,
+        html: &ScannedText<'a>,
+        html_tokens: &FxHashSet<String>,
+        scope: PruneScope,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        let Some(patterns) = Self::get_patterns(tech) else {
+            return;
+        };
+        if tech.and_scopes.contains(&scope) {
+            Self::match_all(&tech.name, patterns, html, html_tokens, detected);
+        } else {
+            match_single_input(
+                Self::TYPE_NAME,
+                &tech.name,
+                "HTML_CONTENT",
+                patterns,
+                tech.regex_set(scope, ""),
+                html,
+                html_tokens,
+                detected,
+            );
         }
     }
 }
@@ -61,12 +63,17 @@ impl HtmlAnalyzer {
         detected: &mut FxHashMap<String, (u8, Option<String>)>,
     ) {
         let html = html.as_ref();
-        <Self as Analyzer<_, _>>::analyze(
-            compiled_lib,
-            html,
-            std::iter::once(html),
-            PruneScope::Html,
-            detected,
-        );
+        Self::analyze_tokenized(compiled_lib, html, &extract_tokens(std::iter::once(html)), detected);
+    }
+
+    /// 使用预提取令牌分析（令牌由 PreparedInput 统一提取）
+    pub(crate) fn analyze_tokenized(
+        compiled_lib: &CompiledRuleLibrary,
+        html: &str,
+        html_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        let html = ScannedText::scan(compiled_lib, PruneScope::Html, html);
+        <Self as Analyzer<_, _>>::analyze_with_tokens(compiled_lib, &html, html_tokens, PruneScope::Html, detected);
     }
 }
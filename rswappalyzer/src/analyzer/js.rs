@@ -0,0 +1,151 @@
+//! JS 全局变量分析器
+//! 匹配调用方提供的 JS 全局变量（如无头浏览器采集的 window 属性）：
+//! 1. 键为属性链（如 `jQuery.fn.jquery`），与规则键统一按小写匹配
+//! 2. 取值为属性值的字符串形式，存在性模式仅要求属性存在，其余模式匹配取值并提取版本
+
+use rswappalyzer_engine::{scope_pruner::PruneScope, CompiledPattern, CompiledRuleLibrary, CompiledTechRule};
+use rustc_hash::{FxBuildHasher, FxHashMap, FxHashSet};
+
+use crate::{
+    analyzer::{
+        common::{handle_exists_success, handle_match_success},
+        Analyzer,
+    },
+    VersionExtractor,
+};
+
+// JS 分析器
+pub struct JsAnalyzer;
+impl Analyzer<FxHashMap<String, Vec<CompiledPattern>>, FxHashMap<String, &str>> for JsAnalyzer {
+    const TYPE_NAME: &'static str = "Js";
+
+    fn get_patterns(tech: &CompiledTechRule) -> Option<&FxHashMap<String, Vec<CompiledPattern>>> {
+        tech.js_patterns.as_ref()
+    }
+
+    fn match_logic(
+        tech_name: &str,
+        js_patterns: &FxHashMap<String, Vec<CompiledPattern>>,
+        js_map: &FxHashMap<String, &str>,
+        js_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        for (name, patterns) in js_patterns {
+            let Some(value) = js_map.get(name) else {
+                continue;
+            };
+            for pattern in patterns {
+                let matcher = pattern.exec.get_matcher();
+                if matcher.is_exists() {
+                    handle_exists_success(Self::TYPE_NAME, tech_name, name, Some(pattern.exec.confidence), detected);
+                    break;
+                }
+                if pattern.matches_with_prune(value, js_tokens) {
+                    let version = matcher
+                        .captures(value)
+                        .and_then(|cap| VersionExtractor::extract(&pattern.exec.version_template, &cap));
+                    handle_match_success(
+                        Self::TYPE_NAME,
+                        tech_name,
+                        name,
+                        value,
+                        &version,
+                        Some(pattern.exec.confidence),
+                        &pattern.describe_rule(),
+                        detected,
+                    );
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl JsAnalyzer {
+    /// 按 JS 规则匹配全局变量
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - js_globals: 属性链 → 属性值（字符串形式）
+    /// - detected: 检测结果
+    pub fn analyze(
+        compiled_lib: &CompiledRuleLibrary,
+        js_globals: &FxHashMap<String, String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        if js_globals.is_empty() {
+            return;
+        }
+        let mut js_map: FxHashMap<String, &str> =
+            FxHashMap::with_capacity_and_hasher(js_globals.len(), FxBuildHasher);
+        for (name, value) in js_globals {
+            js_map.insert(name.to_lowercase(), value.as_str());
+        }
+        <Self as Analyzer<_, _>>::analyze(compiled_lib, &js_map, js_globals.values(), PruneScope::Js, detected);
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::test_support::{detector, names};
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "jQuery": { "cats": [59], "js": { "jQuery.fn.jquery": "([\\d.]+)\\;version:\\1" } },
+            "11Sight": { "cats": [52], "js": { "elevensightApp": "" } },
+            "Acme": { "cats": [19], "js": { "Acme.edition": "^enterprise$" }, "headers": { "X-Acme": "" } }
+        }
+    }"#;
+
+    fn globals(pairs: &[(&str, &str)]) -> FxHashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn analyze(pairs: &[(&str, &str)]) -> FxHashMap<String, (u8, Option<String>)> {
+        let detector = detector(RULES);
+        let mut detected = FxHashMap::default();
+        JsAnalyzer::analyze(detector.compiled_library(), &globals(pairs), &mut detected);
+        detected
+    }
+
+    #[test]
+    fn test_keys_case_insensitive() {
+        let detected = analyze(&[("JQUERY.fn.jQuery", "3.7.1")]);
+        assert_eq!(detected["jQuery"].1.as_deref(), Some("3.7.1"));
+    }
+
+    #[test]
+    fn test_exists_ignores_value() {
+        // 存在性模式只要求属性存在，空取值同样命中
+        let detected = analyze(&[("elevensightApp", "")]);
+        assert!(detected.contains_key("11Sight"));
+        // 仅存在上级属性不算命中
+        assert!(analyze(&[("jQuery", "function")]).is_empty());
+    }
+
+    #[test]
+    fn test_value_pattern_must_match() {
+        assert!(analyze(&[("Acme.edition", "enterprise")]).contains_key("Acme"));
+        assert!(analyze(&[("Acme.edition", "community")]).is_empty());
+        assert!(analyze(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_detect_with_js() {
+        let detector = detector(RULES);
+        let mut headers = HeaderMap::new();
+        headers.insert("x-acme", "1".parse().unwrap());
+        let js = globals(&[("jQuery.fn.jquery", "1.12.4")]);
+        let result = detector.detect_with_js(&headers, &[], b"", &js).unwrap();
+        assert_eq!(names(&result), ["Acme", "jQuery"]);
+        let jquery = result.technologies.iter().find(|t| t.name == "jQuery").unwrap();
+        assert_eq!(jquery.matched_scopes, [PruneScope::Js]);
+
+        // 不提供全局变量时 js 规则不参与
+        let result = detector.detect(&HeaderMap::new(), &[], b"").unwrap();
+        assert!(result.technologies.is_empty());
+    }
+}
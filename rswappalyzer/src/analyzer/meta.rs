@@ -1,7 +1,7 @@
 use rswappalyzer_engine::{CompiledPattern, CompiledRuleLibrary, CompiledTechRule, scope_pruner::PruneScope};
 use rustc_hash::{FxBuildHasher, FxHashMap, FxHashSet};
 
-use crate::{VersionExtractor, analyzer::{Analyzer, common::{handle_exists_success, handle_match_success}}};
+use crate::{VersionExtractor, analyzer::{extract_tokens, Analyzer, common::{handle_exists_success, handle_match_success}}};
 
 // Meta 分析器
 pub struct MetaAnalyzer;
@@ -52,7 +52,7 @@ impl Analyzer<FxHashMap<String, Vec<CompiledPattern>>, FxHashMap<String, &str>>
                             content,
                             &version,
                             confidence,
-                            &matcher.describe(),
+                            &pattern.describe_rule(),
                             detected,
                         );
                         break;
@@ -68,13 +68,23 @@ impl MetaAnalyzer {
         compiled_lib: &CompiledRuleLibrary,
         meta_tags: &[(String, String)],
         detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        let meta_tokens = extract_tokens(meta_tags.iter().map(|(_, c)| c.as_str()));
+        Self::analyze_tokenized(compiled_lib, meta_tags, &meta_tokens, detected);
+    }
+
+    /// 使用预提取令牌分析（令牌由 PreparedInput 统一提取，可为 meta_tags 所在完整标签集合的令牌）
+    pub(crate) fn analyze_tokenized(
+        compiled_lib: &CompiledRuleLibrary,
+        meta_tags: &[(String, String)],
+        meta_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
     ) {
         let mut meta_map: FxHashMap<String, &str> =
             FxHashMap::with_capacity_and_hasher(meta_tags.len(), FxBuildHasher::default());
         for (name, content) in meta_tags {
             meta_map.insert(name.clone(), content.as_str());
         }
-        let token_iter = meta_tags.iter().map(|(_, c)| c.as_str());
-        <Self as Analyzer<_, _>>::analyze(compiled_lib, &meta_map, token_iter, PruneScope::Meta, detected);
+        <Self as Analyzer<_, _>>::analyze_with_tokens(compiled_lib, &meta_map, meta_tokens, PruneScope::Meta, detected);
     }
 }
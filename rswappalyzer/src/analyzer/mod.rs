@@ -1,25 +1,40 @@
 use std::time::Instant;
 
 use log::debug;
-use rswappalyzer_engine::{scope_pruner::PruneScope, CompiledRuleLibrary, CompiledTechRule};
+use rswappalyzer_engine::{
+    scope_pruner::PruneScope, CompiledPattern, CompiledRuleLibrary, CompiledTechRule, LiteralHits, MatcherSpec,
+    PatternRegexSet,
+};
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::analyzer::candidate_collector::collect_candidate_techs;
+use crate::analyzer::common::handle_match_success;
+use crate::utils::matched_scopes;
+use crate::{DetectionUpdater, VersionExtractor};
 
+pub mod banner;
 pub mod candidate_collector;
+pub mod cert;
+pub mod comment;
 pub mod common;
 pub mod cookie;
+pub mod dns;
+pub mod favicon;
+pub mod dom;
 pub mod header;
+pub mod hostname;
 pub mod html;
+pub mod js;
 pub mod meta;
 pub mod script;
+pub mod title;
 pub mod url;
 
 /// 所有分析器的通用抽象特质
 /// 核心：为泛型D添加 ?Sized 约束，兼容 str/[T] 等动态大小类型(DST)
 /// 泛型约束：P-规则集类型，D-数据源类型(支持动态大小类型)
 //pub trait Analyzer<P, D: ?Sized> {
-pub trait Analyzer<P: ?Sized, D: ?Sized> {
+pub trait Analyzer<P: ?Sized + PatternSet, D: ?Sized> {
     /// 分析器类型名称，用于日志标准化输出 (如URL/Header/Cookie)
     const TYPE_NAME: &'static str;
 
@@ -35,6 +50,37 @@ pub trait Analyzer<P: ?Sized, D: ?Sized> {
         detected: &mut FxHashMap<String, (u8, Option<String>)>,
     );
 
+    /// And 条件匹配：规则集内每条模式均需命中，全部命中后合并记录（任一未命中则不记录）
+    fn match_all(
+        tech_name: &str,
+        patterns: &P,
+        data: &D,
+        input_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        let mut hits = FxHashMap::default();
+        let mut all_matched = true;
+        matched_scopes::suspended(|| {
+            patterns.for_each_single(&mut |single| {
+                if !all_matched {
+                    return;
+                }
+                let mut single_hit = FxHashMap::default();
+                Self::match_logic(tech_name, single, data, input_tokens, &mut single_hit);
+                match single_hit.remove(tech_name) {
+                    Some((confidence, version)) => {
+                        DetectionUpdater::update(&mut hits, tech_name, Some(confidence), version)
+                    }
+                    None => all_matched = false,
+                }
+            });
+        });
+
+        if let Some((confidence, version)) = hits.remove(tech_name).filter(|_| all_matched) {
+            DetectionUpdater::update(detected, tech_name, Some(confidence), version);
+        }
+    }
+
     /// 通用分析执行骨架 - 所有分析器共用，无差异化逻辑
     /// 封装：令牌提取 → 候选集构建 → 技术遍历 → 规则判空 → 调用业务匹配
     #[inline(always)]
@@ -47,33 +93,231 @@ pub trait Analyzer<P: ?Sized, D: ?Sized> {
     ) where
         Self: Sized,
     {
-        let (candidate_tech_names, input_tokens) =
-            build_candidate_techs(compiled_lib, token_iter, scope);
+        let input_tokens = extract_tokens(token_iter);
+        Self::analyze_with_tokens(compiled_lib, data, &input_tokens, scope, detected);
+    }
+
+    /// 使用预提取令牌的分析执行骨架（令牌可跨多次检测复用，如 PreparedHeaders）
+    #[inline(always)]
+    fn analyze_with_tokens(
+        compiled_lib: &CompiledRuleLibrary,
+        data: &D,
+        input_tokens: &FxHashSet<String>,
+        scope: PruneScope,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) where
+        Self: Sized,
+    {
+        let candidate_tech_names = build_candidate_techs(compiled_lib, input_tokens, scope);
 
         // 遍历候选技术
         for tech_name in candidate_tech_names {
             let Some(tech) = compiled_lib.tech_patterns.get(tech_name) else {
                 continue;
             };
-            let Some(patterns) = Self::get_patterns(tech) else {
-                continue;
-            };
+            Self::match_tech(tech, data, input_tokens, scope, detected);
+        }
+    }
 
-            Self::match_logic(&tech.name, patterns, data, &input_tokens, detected);
+    /// 单个技术的匹配（按作用域条件选择 And / Or 匹配，无当前维度规则时跳过）
+    #[inline(always)]
+    fn match_tech(
+        tech: &CompiledTechRule,
+        data: &D,
+        input_tokens: &FxHashSet<String>,
+        scope: PruneScope,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) where
+        Self: Sized,
+    {
+        let Some(patterns) = Self::get_patterns(tech) else {
+            return;
+        };
+        if tech.and_scopes.contains(&scope) {
+            Self::match_all(&tech.name, patterns, data, input_tokens, detected);
+        } else {
+            Self::match_logic(&tech.name, patterns, data, input_tokens, detected);
         }
     }
 }
 
-/// 通用令牌提取+候选集构建+无证据集技术合并公共方法
-/// 入参：规则库、任意可迭代的字符串数据源
-/// 出参：去重后的最终候选技术名称集合
-/// 特性：泛型适配所有数据源类型
+/// 可逐条拆分的规则集（And 条件下逐条验证每条模式均命中）
+pub trait PatternSet {
+    /// 为每条模式构造单模式规则集并回调
+    fn for_each_single(&self, f: &mut dyn FnMut(&Self));
+}
+
+impl PatternSet for [CompiledPattern] {
+    fn for_each_single(&self, f: &mut dyn FnMut(&Self)) {
+        for pattern in self {
+            f(std::slice::from_ref(pattern));
+        }
+    }
+}
+
+impl PatternSet for FxHashMap<String, Vec<CompiledPattern>> {
+    fn for_each_single(&self, f: &mut dyn FnMut(&Self)) {
+        for (key, patterns) in self {
+            for pattern in patterns {
+                let single = FxHashMap::from_iter([(key.clone(), vec![pattern.clone()])]);
+                f(&single);
+            }
+        }
+    }
+}
+
+/// 附带字面量扫描结果的输入（HTML/Script 分析器使用，扫描结果随输入传入剪枝校验）
+/// 输入为合法 UTF-8 时按文本匹配；否则以 regex::bytes 直接在原始字节上匹配，不做容错解码
+pub struct ScannedText<'a> {
+    /// 输入字节
+    pub bytes: &'a [u8],
+    /// 输入为合法 UTF-8 时的文本视图（None 时按字节匹配）
+    pub text: Option<&'a str>,
+    /// 该输入的字面量扫描结果（作用域无自动机时为 None）
+    pub hits: Option<LiteralHits<'a>>,
+}
+
+impl<'a> ScannedText<'a> {
+    /// 按作用域自动机扫描文本输入一次
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - scope: 输入所属作用域
+    /// - text: 输入文本
+    pub fn scan(compiled_lib: &'a CompiledRuleLibrary, scope: PruneScope, text: &'a str) -> Self {
+        Self {
+            bytes: text.as_bytes(),
+            text: Some(text),
+            hits: compiled_lib.literal_hits(scope, text),
+        }
+    }
+
+    /// 按作用域自动机扫描字节输入一次（仅校验一次 UTF-8，合法时按文本匹配）
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - scope: 输入所属作用域
+    /// - bytes: 输入字节
+    pub fn scan_bytes(compiled_lib: &'a CompiledRuleLibrary, scope: PruneScope, bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            text: std::str::from_utf8(bytes).ok(),
+            hits: compiled_lib.literal_hits(scope, bytes),
+        }
+    }
+}
+
+/// 单输入模式列表匹配（Or 条件，命中即记录）
+/// 剪枝后仍有 ≥2 条正则且技术提供了正则集合时，一次扫描得到全部正则的命中结果，其余模式逐条匹配
+/// 非合法 UTF-8 的输入逐条以 regex::bytes 按字节匹配（不使用正则集合）
+/// 参数：
+/// - analyzer_type: 分析器类型名称（日志用）
+/// - tech_name: 技术名称
+/// - target_key: 匹配目标标识（日志用）
+/// - patterns: 模式列表
+/// - regex_set: 该模式列表对应的正则集合（None 时逐条匹配）
+/// - input: 待匹配字符串（附带字面量扫描结果）
+/// - input_tokens: 输入令牌集合
+/// - detected: 检测结果
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn match_single_input(
+    analyzer_type: &str,
+    tech_name: &str,
+    target_key: &str,
+    patterns: &[CompiledPattern],
+    regex_set: Option<&PatternRegexSet>,
+    input: &ScannedText<'_>,
+    input_tokens: &FxHashSet<String>,
+    detected: &mut FxHashMap<String, (u8, Option<String>)>,
+) {
+    let ScannedText { bytes, text, ref hits } = *input;
+    let Some(input) = text else {
+        match_single_bytes(analyzer_type, tech_name, target_key, patterns, bytes, input_tokens, hits.as_ref(), detected);
+        return;
+    };
+    let survivors: Vec<bool> = patterns
+        .iter()
+        .map(|pattern| pattern.prune_check_with_hits(input, input_tokens, hits.as_ref()))
+        .collect();
+    let surviving_regexes = patterns
+        .iter()
+        .zip(&survivors)
+        .filter(|(pattern, passed)| **passed && matches!(pattern.exec.matcher, MatcherSpec::Regex { .. }))
+        .count();
+    let set_hits = regex_set
+        .filter(|_| surviving_regexes >= 2)
+        .map(|set| set.matches(input));
+
+    for (index, (pattern, passed)) in patterns.iter().zip(survivors).enumerate() {
+        if !passed {
+            continue;
+        }
+        let matched = match set_hits.as_ref().and_then(|hits| hits.get(index).copied().flatten()) {
+            Some(hit) => hit,
+            None => pattern.matches(input),
+        };
+        if matched {
+            let version = pattern
+                .exec
+                .get_matcher()
+                .captures(input)
+                .and_then(|cap| VersionExtractor::extract(&pattern.exec.version_template, &cap));
+            handle_match_success(
+                analyzer_type,
+                tech_name,
+                target_key,
+                input,
+                &version,
+                Some(pattern.exec.confidence),
+                &pattern.describe_rule(),
+                detected,
+            );
+        }
+    }
+}
+
+/// 非合法 UTF-8 输入的模式列表匹配（regex::bytes，Or 条件，命中即记录）
+#[allow(clippy::too_many_arguments)]
+fn match_single_bytes(
+    analyzer_type: &str,
+    tech_name: &str,
+    target_key: &str,
+    patterns: &[CompiledPattern],
+    input: &[u8],
+    input_tokens: &FxHashSet<String>,
+    hits: Option<&LiteralHits<'_>>,
+    detected: &mut FxHashMap<String, (u8, Option<String>)>,
+) {
+    for pattern in patterns {
+        if !pattern.prune_check_bytes(input, input_tokens, hits) || !pattern.matches_bytes(input) {
+            continue;
+        }
+        let version = pattern
+            .exec
+            .get_matcher()
+            .captures_bytes(input)
+            .and_then(|cap| VersionExtractor::extract_bytes(&pattern.exec.version_template, &cap));
+        // 日志预览仅解码输入开头
+        let preview = String::from_utf8_lossy(&input[..input.len().min(BYTES_PREVIEW_LEN)]);
+        handle_match_success(
+            analyzer_type,
+            tech_name,
+            target_key,
+            &preview,
+            &version,
+            Some(pattern.exec.confidence),
+            &pattern.describe_rule(),
+            detected,
+        );
+    }
+}
+
+/// 字节输入命中日志的预览长度
+const BYTES_PREVIEW_LEN: usize = 256;
+
+/// 通用令牌提取公共方法
+/// 入参：任意可迭代的字符串数据源
+/// 出参：去重后的输入令牌集合
 #[inline(always)]
-fn build_candidate_techs<'a, I>(
-    compiled_lib: &'a CompiledRuleLibrary,
-    data_iter: I,
-    scope: PruneScope, // 前解析器对应的维度
-) -> (FxHashSet<&'a String>, FxHashSet<String>)
+pub(crate) fn extract_tokens<I>(data_iter: I) -> FxHashSet<String>
 where
     I: IntoIterator,
     I::Item: AsRef<str>,
@@ -83,26 +327,70 @@ where
         tokens
             .extend(crate::utils::extractor::token_extract_zh::extract_input_tokens(data.as_ref()));
     }
+    tokens
+}
+
+/// 字节输入的令牌提取（按合法 UTF-8 片段分别提取，非法字节视为分隔，不复制输入）
+/// 入参：输入字节
+/// 出参：去重后的输入令牌集合
+pub(crate) fn extract_bytes_tokens(bytes: &[u8]) -> FxHashSet<String> {
+    extract_tokens(bytes.utf8_chunks().map(|chunk| chunk.valid()))
+}
+
+/// 作用域候选统计（诊断用，与分析器使用相同的候选集构建）
+/// 参数：
+/// - compiled_lib: 编译后的规则库
+/// - tokens: 当前作用域输入的令牌
+/// - scope: 作用域
+///
+/// 返回：(候选技术数, 该作用域有规则但未进入候选集而被剪枝跳过的技术数)
+pub(crate) fn candidate_stats(
+    compiled_lib: &CompiledRuleLibrary,
+    tokens: &FxHashSet<String>,
+    scope: PruneScope,
+) -> (usize, usize) {
+    let candidates = build_candidate_techs(compiled_lib, tokens, scope);
+    let pruned = compiled_lib
+        .tech_patterns
+        .values()
+        .filter(|tech| !candidates.contains(&tech.name))
+        .filter(|tech| {
+            let mut in_scope = false;
+            tech.for_each_pattern(|pattern| in_scope |= pattern.scope == scope);
+            in_scope
+        })
+        .count();
+    (candidates.len(), pruned)
+}
 
+/// 候选集构建+无证据集技术合并公共方法
+/// 入参：规则库、输入令牌集合
+/// 出参：去重后的最终候选技术名称集合
+#[inline(always)]
+fn build_candidate_techs<'a>(
+    compiled_lib: &'a CompiledRuleLibrary,
+    tokens: &FxHashSet<String>,
+    scope: PruneScope, // 前解析器对应的维度
+) -> FxHashSet<&'a String> {
     // 1. 传入维度，筛选当前维度下的证据候选技术
     let mut candidate_techs =
-        candidate_collector::collect_candidate_techs(compiled_lib, &tokens, scope);
+        candidate_collector::collect_candidate_techs(compiled_lib, tokens, scope);
 
     // 2. 适配维度化的无证据索引：只加载当前维度下的无证据技术
     if let Some(no_evidence_techs) = compiled_lib.no_evidence_index.get(&scope) {
         candidate_techs.extend(no_evidence_techs.iter());
     }
 
-    (candidate_techs, tokens)
+    candidate_techs
 }
 
 #[inline(always)]
 #[allow(dead_code)]
-fn build_candidate_techs_log<'a, I>(
-    compiled_lib: &'a CompiledRuleLibrary,
+fn build_candidate_techs_log<I>(
+    compiled_lib: &CompiledRuleLibrary,
     data_iter: I,
     scope: PruneScope,
-) -> (FxHashSet<&'a String>, FxHashSet<String>)
+) -> (FxHashSet<&String>, FxHashSet<String>)
 where
     I: IntoIterator,
     I::Item: AsRef<str>,
@@ -122,8 +410,8 @@ where
     // 打印Token生成耗时（两种方式选其一）
     // 方式1：用日志（推荐，可控制级别）
     debug!(
-        "[{}维度] Token生成耗时: {}ms | 生成Token数量: {}",
-        format!("{:?}", scope), // 打印维度（Header/Body/Url）
+        "[{:?}维度] Token生成耗时: {}ms | 生成Token数量: {}",
+        scope, // 打印维度（Header/Body/Url）
         token_duration.as_millis(),
         tokens.len()
     );
@@ -150,8 +438,8 @@ where
     // ========== 整体耗时统计 ==========
     let total_duration = total_start.elapsed();
     debug!(
-        "[{}维度] 构建候选技术总耗时: {}ms | Token提取: {}ms | 候选收集: {}ms | 无证据合并: {}ms",
-        format!("{:?}", scope),
+        "[{:?}维度] 构建候选技术总耗时: {}ms | Token提取: {}ms | 候选收集: {}ms | 无证据合并: {}ms",
+        scope,
         total_duration.as_millis(),
         token_duration.as_millis(),
         candidate_duration.as_millis(),
@@ -160,3 +448,92 @@ where
 
     (candidate_techs, tokens)
 }
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::test_support::detector;
+    use crate::DetectResult;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Shop": { "cats": [6], "html": { "condition": "and", "patterns": ["shop-cart", "shop-checkout"] }, "headers": { "X-Shop": "" } },
+            "Edge": { "cats": [31], "headers": { "condition": "and", "X-Edge-Id": "\\;confidence:40", "X-Edge-Version": "^v([\\d.]+)$\\;version:\\1" } },
+            "Either": { "cats": [19], "html": ["either-one", "either-two"] }
+        }
+    }"#;
+
+    fn detect(headers: &[(&'static str, &'static str)], html: &str) -> DetectResult {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.append(*name, value.parse().unwrap());
+        }
+        detector(RULES).detect(&map, &[], html.as_bytes()).unwrap()
+    }
+
+    fn tech<'a>(result: &'a DetectResult, name: &str) -> Option<&'a crate::Technology> {
+        result.technologies.iter().find(|t| t.name == name)
+    }
+
+    #[test]
+    fn test_and_scopes_compiled() {
+        let detector = detector(RULES);
+        let lib = detector.compiled_library();
+        assert_eq!(lib.tech_patterns["Shop"].and_scopes, [PruneScope::Html]);
+        assert_eq!(lib.tech_patterns["Edge"].and_scopes, [PruneScope::Header]);
+        assert!(lib.tech_patterns["Either"].and_scopes.is_empty());
+        // condition 字段不作为匹配键
+        let headers = lib.tech_patterns["Edge"].header_patterns.as_ref().unwrap();
+        assert!(!headers.contains_key("condition"));
+    }
+
+    #[test]
+    fn test_for_each_single() {
+        let detector = detector(RULES);
+        let tech = &detector.compiled_library().tech_patterns["Edge"];
+        let mut keys = Vec::new();
+        tech.header_patterns.as_ref().unwrap().for_each_single(&mut |single| {
+            assert_eq!(single.values().map(Vec::len).sum::<usize>(), 1);
+            keys.extend(single.keys().cloned());
+        });
+        keys.sort();
+        assert_eq!(keys, ["x-edge-id", "x-edge-version"]);
+
+        let html = &detector.compiled_library().tech_patterns["Shop"].html_patterns;
+        let mut count = 0;
+        html.as_deref().unwrap().for_each_single(&mut |single| {
+            assert_eq!(single.len(), 1);
+            count += 1;
+        });
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_and_requires_all_patterns() {
+        assert!(tech(&detect(&[], "<html>shop-cart</html>"), "Shop").is_none());
+        assert!(tech(&detect(&[], "<html>shop-cart shop-checkout</html>"), "Shop").is_some());
+        assert!(tech(&detect(&[("x-edge-version", "v2.0")], ""), "Edge").is_none());
+
+        // 全部命中后合并：取最高置信度并保留版本
+        let result = detect(&[("x-edge-id", "1"), ("x-edge-version", "v2.0")], "");
+        let edge = tech(&result, "Edge").unwrap();
+        assert_eq!((edge.confidence, edge.version.as_deref()), (100, Some("2.0")));
+
+        // 默认 Or：任一模式命中即记录
+        assert!(tech(&detect(&[], "<html>either-two</html>"), "Either").is_some());
+    }
+
+    #[test]
+    fn test_partial_and_scope_not_recorded() {
+        // And 规则集部分命中的试探性匹配不记录作用域
+        let result = detect(&[("x-shop", "1")], "<html>shop-cart</html>");
+        assert_eq!(tech(&result, "Shop").unwrap().matched_scopes, [PruneScope::Header]);
+
+        let result = detect(&[("x-shop", "1")], "<html>shop-cart shop-checkout</html>");
+        let shop = tech(&result, "Shop").unwrap();
+        assert_eq!(shop.matched_scopes, [PruneScope::Html, PruneScope::Header]);
+    }
+}
@@ -1,12 +1,15 @@
 use rswappalyzer_engine::{CompiledPattern, CompiledRuleLibrary, CompiledTechRule, scope_pruner::PruneScope};
 use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::{VersionExtractor, analyzer::{Analyzer, common::handle_match_success}};
+use crate::{
+    config::script_host::{script_host, ScriptHostClass, ScriptHostPolicy},
+    DetectionUpdater, analyzer::{extract_bytes_tokens, extract_tokens, match_single_input, Analyzer, ScannedText},
+};
 
 
 // Script 分析器
 pub struct ScriptAnalyzer;
-impl Analyzer<[CompiledPattern], str> for ScriptAnalyzer {
+impl<'a> Analyzer<[CompiledPattern], ScannedText<'a>> for ScriptAnalyzer {
     const TYPE_NAME: &'static str = "Script";
 
     fn get_patterns(tech: &CompiledTechRule) -> Option<&[CompiledPattern]> {
@@ -17,27 +20,46 @@ impl Analyzer<[CompiledPattern], str> for ScriptAnalyzer {
     fn match_logic(
         tech_name: &str,
         patterns: &[CompiledPattern],
-        script_src_combined: &str,
+        script_src_combined: &ScannedText<'a>,
         script_tokens: &FxHashSet<String>,
         detected: &mut FxHashMap<String, (u8, Option<String>)>,
     ) {
-        for pattern in patterns {
-            let matcher = pattern.exec.get_matcher();
-            if pattern.matches_with_prune(script_src_combined, script_tokens) {
-                let version = matcher
-                    .captures(script_src_combined)
-                    .and_then(|cap| VersionExtractor::extract(&pattern.exec.version_template, &cap));
-                handle_match_success(
-                    Self::TYPE_NAME,
-                    tech_name,
-                    "SCRIPT_SRC",
-                    script_src_combined,
-                    &version,
-                    Some(pattern.exec.confidence),
-                    &matcher.describe(),
-                    detected,
-                );
-            }
+        match_single_input(
+            Self::TYPE_NAME,
+            tech_name,
+            "SCRIPT_SRC",
+            patterns,
+            None,
+            script_src_combined,
+            script_tokens,
+            detected,
+        );
+    }
+
+    /// Or 条件下使用技术的 Script 正则集合，一次扫描得到全部正则的命中结果
+    fn match_tech(
+        tech: &CompiledTechRule,
+        script_src_combined: &ScannedText<'a>,
+        script_tokens: &FxHashSet<String>,
+        scope: PruneScope,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        let Some(patterns) = Self::get_patterns(tech) else {
+            return;
+        };
+        if tech.and_scopes.contains(&scope) {
+            Self::match_all(&tech.name, patterns, script_src_combined, script_tokens, detected);
+        } else {
+            match_single_input(
+                Self::TYPE_NAME,
+                &tech.name,
+                "SCRIPT_SRC",
+                patterns,
+                tech.regex_set(scope, ""),
+                script_src_combined,
+                script_tokens,
+                detected,
+            );
         }
     }
 }
@@ -48,12 +70,219 @@ impl ScriptAnalyzer {
         script_src_combined: &str,
         detected: &mut FxHashMap<String, (u8, Option<String>)>,
     ) {
-        <Self as Analyzer<_, _>>::analyze(
-            compiled_lib,
-            script_src_combined,
-            std::iter::once(script_src_combined),
-            PruneScope::Script,
-            detected,
+        let script_tokens = extract_tokens(std::iter::once(script_src_combined));
+        Self::analyze_tokenized(compiled_lib, script_src_combined, &script_tokens, detected);
+    }
+
+    /// 使用预提取令牌分析（令牌由 PreparedInput 统一提取）
+    pub(crate) fn analyze_tokenized(
+        compiled_lib: &CompiledRuleLibrary,
+        script_src_combined: &str,
+        script_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        let script = ScannedText::scan(compiled_lib, PruneScope::Script, script_src_combined);
+        <Self as Analyzer<_, _>>::analyze_with_tokens(compiled_lib, &script, script_tokens, PruneScope::Script, detected);
+    }
+
+    /// 分析脚本字节（JS 资源响应体；非合法 UTF-8 时以 regex::bytes 匹配，不做容错解码）
+    pub fn analyze_bytes(
+        compiled_lib: &CompiledRuleLibrary,
+        script: &[u8],
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        let script_tokens = extract_bytes_tokens(script);
+        Self::analyze_bytes_tokenized(compiled_lib, script, &script_tokens, detected);
+    }
+
+    /// 使用预提取令牌分析脚本字节（令牌由 PreparedInput 统一提取）
+    pub(crate) fn analyze_bytes_tokenized(
+        compiled_lib: &CompiledRuleLibrary,
+        script: &[u8],
+        script_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        let script = ScannedText::scan_bytes(compiled_lib, PruneScope::Script, script);
+        <Self as Analyzer<_, _>>::analyze_with_tokens(compiled_lib, &script, script_tokens, PruneScope::Script, detected);
+    }
+}
+
+/// 按主机策略执行 Script 分析后的附加结果
+#[derive(Debug, Default)]
+pub struct ScriptHostOutcome {
+    /// 仅由 CDN 脚本命中的技术（不参与关联推导时需排除）
+    pub cdn_only: FxHashSet<String>,
+    /// 技术名 -> 命中的脚本主机（已排序去重）
+    pub hosts: FxHashMap<String, Vec<String>>,
+}
+
+impl ScriptAnalyzer {
+    /// 按主机策略执行 Script 分析
+    /// 流程：按主机分类拆分 src → 源站/CDN 分别匹配 → 记录命中主机 → 合并结果
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - script_srcs: 提取到的脚本 src 列表
+    /// - policy: 主机策略
+    /// - detected: 检测结果（输出参数）
+    ///
+    /// 返回：CDN 命中信息与主机证据
+    pub fn analyze_with_host_policy(
+        compiled_lib: &CompiledRuleLibrary,
+        script_srcs: &[String],
+        policy: &ScriptHostPolicy,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) -> ScriptHostOutcome {
+        let mut origin_srcs = Vec::with_capacity(script_srcs.len());
+        let mut cdn_srcs = Vec::new();
+        for src in script_srcs {
+            match policy.classify(src) {
+                ScriptHostClass::Origin => origin_srcs.push(src.as_str()),
+                ScriptHostClass::Cdn => cdn_srcs.push(src.as_str()),
+                ScriptHostClass::Denied => {}
+            }
+        }
+
+        let mut origin_detected = FxHashMap::default();
+        let mut cdn_detected = FxHashMap::default();
+        if !origin_srcs.is_empty() {
+            Self::analyze(compiled_lib, &join_srcs(&origin_srcs), &mut origin_detected);
+        }
+        if !cdn_srcs.is_empty() {
+            Self::analyze(compiled_lib, &join_srcs(&cdn_srcs), &mut cdn_detected);
+        }
+
+        let mut outcome = ScriptHostOutcome::default();
+
+        // 记录命中主机（仅对已命中的技术逐条回溯，代价可控）
+        for (tech_name, srcs) in origin_detected
+            .keys()
+            .map(|t| (t, &origin_srcs))
+            .chain(cdn_detected.keys().map(|t| (t, &cdn_srcs)))
+        {
+            let Some(patterns) = compiled_lib
+                .tech_patterns
+                .get(tech_name)
+                .and_then(|t| t.script_patterns.as_deref())
+            else {
+                continue;
+            };
+            let hosts = outcome.hosts.entry(tech_name.clone()).or_default();
+            for src in srcs.iter() {
+                if let Some(host) = script_host(src) {
+                    if !hosts.contains(&host) && patterns.iter().any(|p| p.matches(src)) {
+                        hosts.push(host);
+                    }
+                }
+            }
+        }
+        outcome.hosts.retain(|_, hosts| {
+            hosts.sort_unstable();
+            !hosts.is_empty()
+        });
+
+        // 合并结果：源站优先，CDN 按策略处理版本号
+        for (tech_name, (confidence, version)) in origin_detected {
+            DetectionUpdater::update(detected, &tech_name, Some(confidence), version);
+        }
+        for (tech_name, (confidence, version)) in cdn_detected {
+            let version = if policy.cdn_version { version } else { None };
+            if !detected.contains_key(&tech_name) {
+                outcome.cdn_only.insert(tech_name.clone());
+            }
+            DetectionUpdater::update(detected, &tech_name, Some(confidence), version);
+        }
+
+        outcome
+    }
+}
+
+/// 按提取器格式拼接 src（换行分隔）
+fn join_srcs(srcs: &[&str]) -> String {
+    let mut combined = String::with_capacity(srcs.iter().map(|s| s.len() + 1).sum());
+    for src in srcs {
+        combined.push_str(src);
+        combined.push('\n');
+    }
+    combined
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::detector;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "jQuery": { "cats": [59], "scriptSrc": "jquery-([\\d.]+)\\.min\\.js\\;version:\\1" },
+            "Acme": { "cats": [59], "scriptSrc": "acme\\.js" }
+        }
+    }"#;
+
+    fn srcs(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn policy(cdn_version: bool) -> ScriptHostPolicy {
+        ScriptHostPolicy {
+            cdn_hosts: vec!["cdnjs.cloudflare.com".to_string()],
+            deny_hosts: vec!["ads.example.com".to_string()],
+            cdn_version,
+            ..ScriptHostPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_cdn_only_and_hosts() {
+        let detector = detector(RULES);
+        let lib = detector.compiled_library();
+        let mut detected = FxHashMap::default();
+        let outcome = ScriptAnalyzer::analyze_with_host_policy(
+            lib,
+            &srcs(&["https://cdnjs.cloudflare.com/jquery-3.7.1.min.js", "/js/acme.js"]),
+            &policy(false),
+            &mut detected,
+        );
+        assert_eq!(outcome.cdn_only, FxHashSet::from_iter(["jQuery".to_string()]));
+        assert_eq!(outcome.hosts["jQuery"], vec!["cdnjs.cloudflare.com".to_string()]);
+        // 相对路径无主机
+        assert!(!outcome.hosts.contains_key("Acme"));
+        // cdn_version 关闭时丢弃版本号
+        assert_eq!(detected["jQuery"].1, None);
+        assert!(detected.contains_key("Acme"));
+    }
+
+    #[test]
+    fn test_origin_wins_over_cdn() {
+        let detector = detector(RULES);
+        let mut detected = FxHashMap::default();
+        let outcome = ScriptAnalyzer::analyze_with_host_policy(
+            detector.compiled_library(),
+            &srcs(&[
+                "https://cdnjs.cloudflare.com/jquery-3.7.1.min.js",
+                "https://www.example.com/jquery-3.6.0.min.js",
+            ]),
+            &policy(true),
+            &mut detected,
+        );
+        assert!(outcome.cdn_only.is_empty());
+        let mut hosts = outcome.hosts["jQuery"].clone();
+        hosts.sort();
+        assert_eq!(hosts, vec!["cdnjs.cloudflare.com".to_string(), "www.example.com".to_string()]);
+        assert!(detected["jQuery"].1.is_some());
+    }
+
+    #[test]
+    fn test_denied_host_ignored() {
+        let detector = detector(RULES);
+        let mut detected = FxHashMap::default();
+        let outcome = ScriptAnalyzer::analyze_with_host_policy(
+            detector.compiled_library(),
+            &srcs(&["https://ads.example.com/acme.js"]),
+            &policy(true),
+            &mut detected,
         );
+        assert!(detected.is_empty());
+        assert!(outcome.hosts.is_empty());
     }
 }
@@ -0,0 +1,142 @@
+//! 页面标题分析器
+//! 匹配 HTML 提取的首个 `<title>` 文本，对应仅按标题识别的第三方指纹（EHole / FingerprintHub 等）
+
+use rswappalyzer_engine::{scope_pruner::PruneScope, CompiledPattern, CompiledRuleLibrary, CompiledTechRule};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    analyzer::{common::handle_match_success, extract_tokens, Analyzer},
+    VersionExtractor,
+};
+
+// 页面标题分析器
+pub struct TitleAnalyzer;
+impl Analyzer<[CompiledPattern], str> for TitleAnalyzer {
+    const TYPE_NAME: &'static str = "Title";
+
+    fn get_patterns(tech: &CompiledTechRule) -> Option<&[CompiledPattern]> {
+        tech.title_patterns.as_deref()
+    }
+
+    fn match_logic(
+        tech_name: &str,
+        patterns: &[CompiledPattern],
+        title: &str,
+        title_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        for pattern in patterns {
+            if pattern.matches_with_prune(title, title_tokens) {
+                let version = pattern
+                    .exec
+                    .get_matcher()
+                    .captures(title)
+                    .and_then(|cap| VersionExtractor::extract(&pattern.exec.version_template, &cap));
+                handle_match_success(
+                    Self::TYPE_NAME,
+                    tech_name,
+                    "TITLE",
+                    title,
+                    &version,
+                    Some(pattern.exec.confidence),
+                    &pattern.describe_rule(),
+                    detected,
+                );
+                break;
+            }
+        }
+    }
+}
+
+impl TitleAnalyzer {
+    /// 按页面标题规则匹配
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - title: 页面标题（空字符串跳过）
+    /// - detected: 检测结果
+    pub fn analyze(
+        compiled_lib: &CompiledRuleLibrary,
+        title: &str,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        Self::analyze_tokenized(compiled_lib, title, &extract_tokens(std::iter::once(title)), detected);
+    }
+
+    /// 使用预提取令牌分析（令牌由 PreparedInput 统一提取）
+    pub(crate) fn analyze_tokenized(
+        compiled_lib: &CompiledRuleLibrary,
+        title: &str,
+        title_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        if title.is_empty() {
+            return;
+        }
+        <Self as Analyzer<_, _>>::analyze_with_tokens(compiled_lib, title, title_tokens, PruneScope::Title, detected);
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::test_support::{detector, names};
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Jenkins": { "cats": [44], "title": ["Dashboard \\[Jenkins\\]", "^Jenkins ([\\d.]+)$\\;version:\\1"] },
+            "Grafana": { "cats": [10], "title": "^Grafana$" }
+        }
+    }"#;
+
+    fn analyze(title: &str) -> Vec<(String, Option<String>)> {
+        let detector = detector(RULES);
+        let mut detected = FxHashMap::default();
+        TitleAnalyzer::analyze(detector.compiled_library(), title, &mut detected);
+        let mut techs: Vec<_> = detected
+            .into_iter()
+            .map(|(name, (_, version))| (name, version))
+            .collect();
+        techs.sort();
+        techs
+    }
+
+    #[test]
+    fn test_title_compiled() {
+        let detector = detector(RULES);
+        let jenkins = &detector.compiled_library().tech_patterns["Jenkins"];
+        assert_eq!(jenkins.title_patterns.as_ref().unwrap().len(), 2);
+        // 标题规则不落入 HTML 作用域
+        assert!(jenkins.html_patterns.is_none());
+    }
+
+    #[test]
+    fn test_title_version() {
+        assert_eq!(analyze("Dashboard [Jenkins]"), [("Jenkins".to_string(), None)]);
+        assert_eq!(
+            analyze("Jenkins 2.401"),
+            [("Jenkins".to_string(), Some("2.401".to_string()))]
+        );
+        // 锚定模式按整个标题匹配；空标题跳过
+        assert!(analyze("My Grafana").is_empty());
+        assert!(analyze("").is_empty());
+    }
+
+    #[test]
+    fn test_title_detected() {
+        let detector = detector(RULES);
+        let headers = HeaderMap::new();
+        // 标题首尾空白折叠后匹配
+        let html = b"<html><head><title>\n  Grafana\n</title></head><body></body></html>";
+        let result = detector.detect(&headers, &[], html).unwrap();
+        assert_eq!(names(&result), ["Grafana"]);
+        let grafana = result.find_by_slug("grafana").unwrap();
+        assert_eq!(grafana.matched_scopes, [PruneScope::Title]);
+
+        // 标题外的文本不匹配
+        let html = b"<html><head></head><body><h1>Dashboard [Jenkins]</h1></body></html>";
+        assert!(detector.detect(&headers, &[], html).unwrap().technologies.is_empty());
+    }
+}
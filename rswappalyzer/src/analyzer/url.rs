@@ -1,7 +1,7 @@
 use rswappalyzer_engine::{CompiledPattern, CompiledRuleLibrary, CompiledTechRule, scope_pruner::PruneScope};
 use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::{VersionExtractor, analyzer::{Analyzer, common::handle_match_success}};
+use crate::{VersionExtractor, analyzer::{extract_tokens, Analyzer, common::handle_match_success}};
 
 // URL 分析器
 pub struct UrlAnalyzer;
@@ -33,7 +33,7 @@ impl Analyzer<[CompiledPattern], [&str]> for UrlAnalyzer {
                         url,
                         &version,
                         Some(pattern.exec.confidence),
-                        &matcher.describe(),
+                        &pattern.describe_rule(),
                         detected,
                     );
                     break;
@@ -49,6 +49,16 @@ impl UrlAnalyzer {
         urls: &[&str],
         detected: &mut FxHashMap<String, (u8, Option<String>)>,
     ) {
-        <Self as Analyzer<_, _>>::analyze(compiled_lib, urls, urls, PruneScope::Url, detected);
+        Self::analyze_tokenized(compiled_lib, urls, &extract_tokens(urls), detected);
+    }
+
+    /// 使用预提取令牌分析（令牌由 PreparedInput 统一提取）
+    pub(crate) fn analyze_tokenized(
+        compiled_lib: &CompiledRuleLibrary,
+        urls: &[&str],
+        url_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        <Self as Analyzer<_, _>>::analyze_with_tokens(compiled_lib, urls, url_tokens, PruneScope::Url, detected);
     }
 }
@@ -0,0 +1,130 @@
+//! rswappalyzer-compile：离线预编译规则包（cli 特性）
+//! 执行与运行期相同的 解析 → 清洗拆分 → 索引 → 编译 流水线，写出带魔数与引擎版本要求的规则包，
+//! 供 CI 预先烘焙规则、离线（无外网）部署直接通过 TechDetector::with_compiled_artifact 加载
+//!
+//! ```text
+//! rswappalyzer-compile rules.json -o rules.pack
+//! rswappalyzer-compile web_fingerprint_v3.json --format fingerprint-hub -o hub.pack
+//! ```
+
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, ValueEnum};
+use rswappalyzer::{write_compiled_pack, RuleConfig, TechDetector};
+use rswappalyzer_engine::source::{EholeConverter, FingerprintHubConverter, WappalyzerParser};
+use rswappalyzer_engine::{RuleLibrary, RuleProcessor};
+
+/// 输入规则格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum InputFormat {
+    /// Wappalyzer 技术规则 JSON
+    Wappalyzer,
+    /// Wappalyzer 结构的 YAML 规则（需 yaml-rules 特性）
+    Yaml,
+    /// RSW YAML 简化规则（需 yaml-rules 特性）
+    RswYaml,
+    /// FingerprintHub 指纹 JSON
+    FingerprintHub,
+    /// EHole finger.json
+    Ehole,
+}
+
+/// 离线编译规则包
+#[derive(Debug, Parser)]
+#[command(name = "rswappalyzer-compile", version, about = "Compile rules into a versioned rule pack")]
+struct Args {
+    /// 输入规则文件
+    input: PathBuf,
+    /// 输出规则包路径
+    #[arg(short, long, default_value = "rules.pack")]
+    output: PathBuf,
+    /// 输入格式（未指定时按扩展名推断：.rsw.yml/.rsw.yaml → rsw-yaml，.yml/.yaml → yaml，其余 → wappalyzer）
+    #[arg(short, long, value_enum)]
+    format: Option<InputFormat>,
+    /// 分类映射 JSON（未指定时使用内置分类数据）
+    #[arg(long)]
+    categories: Option<PathBuf>,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+    let format = args.format.unwrap_or_else(|| infer_format(&args.input));
+    let content = std::fs::read_to_string(&args.input)
+        .map_err(|e| format!("failed to read {}: {}", args.input.display(), e))?;
+
+    let raw_lib = parse_rules(format, &content)?;
+    let rule_lib = RuleProcessor.clean_and_split_rules(&raw_lib)?;
+
+    let mut config = RuleConfig::default();
+    config.options.category_path = args.categories;
+    let detector = TechDetector::with_rules(rule_lib, config)?;
+    let compiled_lib = detector.compiled_library();
+    write_compiled_pack(compiled_lib, &args.output)?;
+
+    let engine = compiled_lib.engine_requirements();
+    println!(
+        "Compiled {} technologies ({:?}) → {} | engine {} (min {}, format v{})",
+        compiled_lib.tech_patterns.len(),
+        format,
+        args.output.display(),
+        engine.built_with,
+        engine.min_engine_version,
+        engine.format_version
+    );
+    Ok(())
+}
+
+/// 按扩展名推断输入格式
+fn infer_format(path: &Path) -> InputFormat {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    if name.ends_with(".rsw.yml") || name.ends_with(".rsw.yaml") {
+        InputFormat::RswYaml
+    } else if name.ends_with(".yml") || name.ends_with(".yaml") {
+        InputFormat::Yaml
+    } else {
+        InputFormat::Wappalyzer
+    }
+}
+
+/// 解析输入规则为原始规则库（指纹转换格式跳过的条目输出到标准错误）
+fn parse_rules(format: InputFormat, content: &str) -> Result<RuleLibrary, Box<dyn Error>> {
+    let (rule_lib, skipped) = match format {
+        InputFormat::Wappalyzer => return Ok(WappalyzerParser.parse_to_rule_lib(content)?),
+        InputFormat::Yaml | InputFormat::RswYaml => return parse_yaml(format, content),
+        InputFormat::FingerprintHub => {
+            let import = FingerprintHubConverter.convert(content)?;
+            (import.rule_lib, import.skipped)
+        }
+        InputFormat::Ehole => {
+            let import = EholeConverter.convert(content)?;
+            (import.rule_lib, import.skipped)
+        }
+    };
+    for fingerprint in &skipped {
+        eprintln!("skipped {}: {}", fingerprint.name, fingerprint.reason);
+    }
+    if !skipped.is_empty() {
+        eprintln!("{} fingerprint(s) skipped", skipped.len());
+    }
+    Ok(rule_lib)
+}
+
+/// 解析 YAML 规则（yaml-rules 特性）
+#[cfg(feature = "yaml-rules")]
+fn parse_yaml(format: InputFormat, content: &str) -> Result<RuleLibrary, Box<dyn Error>> {
+    use rswappalyzer_engine::source::RswYamlParser;
+    match format {
+        InputFormat::RswYaml => Ok(RswYamlParser.parse(content)?),
+        _ => Ok(WappalyzerParser.parse_yaml_to_rule_lib(content)?),
+    }
+}
+
+/// 未开启 yaml-rules 特性时的 YAML 解析（直接返回错误）
+#[cfg(not(feature = "yaml-rules"))]
+fn parse_yaml(_format: InputFormat, _content: &str) -> Result<RuleLibrary, Box<dyn Error>> {
+    Err("Please enable 'yaml-rules' feature to compile YAML rules".into())
+}
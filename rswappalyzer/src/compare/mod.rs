@@ -0,0 +1,335 @@
+//! 检测结果对比模块（compare 特性）
+//! 将 rswappalyzer 的检测结果与 wappalyzergo 的录制输出逐目标对比，
+//! 统计一致/缺失/多报，用于跟踪各版本的准确率变化
+//!
+//! 语料格式（JSON Lines，每行一个目标）：
+//! ```text
+//! {"target":"example.com","urls":["https://example.com/"],"headers":{"server":["nginx"]},"body":"<html>..."}
+//! ```
+//! wappalyzergo 录制格式（JSON 对象，目标 -> 技术列表）：
+//! - 值为数组：`{"example.com": ["Nginx:1.25.3", "PHP"]}`
+//! - 值为对象（wappalyzergo `Fingerprint` 原样输出）：`{"example.com": {"Nginx:1.25.3": {}}}`
+//!
+//! 技术名按 `名称:版本` 格式拆分，比较时只比较名称（忽略大小写）
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{RswResult, RswappalyzerError};
+use crate::{DetectResult, TechDetector};
+
+/// 语料中的单个目标
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CorpusEntry {
+    /// 目标标识（与 wappalyzergo 录制文件的 Key 一致）
+    pub target: String,
+    /// 目标URL列表
+    #[serde(default)]
+    pub urls: Vec<String>,
+    /// 响应头（名称 -> 多值）
+    #[serde(default)]
+    pub headers: FxHashMap<String, Vec<String>>,
+    /// 响应体
+    #[serde(default)]
+    pub body: String,
+}
+
+/// 单个目标的对比结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TargetComparison {
+    /// 目标标识
+    pub target: String,
+    /// 双方均检出
+    pub agreed: BTreeSet<String>,
+    /// wappalyzergo 检出但 rswappalyzer 未检出
+    pub missing: BTreeSet<String>,
+    /// rswappalyzer 检出但 wappalyzergo 未检出
+    pub extra: BTreeSet<String>,
+    /// 双方均检出但版本不一致：技术 -> (rswappalyzer版本, wappalyzergo版本)
+    pub version_mismatch: BTreeMap<String, (Option<String>, Option<String>)>,
+    /// 录制文件中不存在该目标
+    pub no_reference: bool,
+}
+
+impl TargetComparison {
+    /// 是否完全一致（技术集合一致，不含版本差异）
+    pub fn is_exact(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty()
+    }
+}
+
+/// 对比汇总报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompareReport {
+    /// 逐目标对比结果
+    pub targets: Vec<TargetComparison>,
+    /// 一致技术总数
+    pub agreed: usize,
+    /// 缺失技术总数
+    pub missing: usize,
+    /// 多报技术总数
+    pub extra: usize,
+    /// 版本不一致总数
+    pub version_mismatch: usize,
+    /// 缺少参考结果的目标数
+    pub no_reference: usize,
+}
+
+impl CompareReport {
+    /// 以 wappalyzergo 为参考的召回率（agreed / (agreed + missing)）
+    pub fn recall(&self) -> f64 {
+        ratio(self.agreed, self.agreed + self.missing)
+    }
+
+    /// 以 wappalyzergo 为参考的精确率（agreed / (agreed + extra)）
+    pub fn precision(&self) -> f64 {
+        ratio(self.agreed, self.agreed + self.extra)
+    }
+
+    /// 技术集合完全一致的目标数
+    pub fn exact_targets(&self) -> usize {
+        self.targets
+            .iter()
+            .filter(|t| !t.no_reference && t.is_exact())
+            .count()
+    }
+
+    fn push(&mut self, cmp: TargetComparison) {
+        self.agreed += cmp.agreed.len();
+        self.missing += cmp.missing.len();
+        self.extra += cmp.extra.len();
+        self.version_mismatch += cmp.version_mismatch.len();
+        self.no_reference += cmp.no_reference as usize;
+        self.targets.push(cmp);
+    }
+}
+
+fn ratio(num: usize, den: usize) -> f64 {
+    if den == 0 {
+        1.0
+    } else {
+        num as f64 / den as f64
+    }
+}
+
+/// wappalyzergo 录制结果：目标 -> (小写技术名 -> (原始技术名, 版本))
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceResults {
+    targets: FxHashMap<String, BTreeMap<String, (String, Option<String>)>>,
+}
+
+impl ReferenceResults {
+    /// 解析 wappalyzergo 录制文件（JSON）
+    pub fn from_json(input: &str) -> RswResult<Self> {
+        let raw: FxHashMap<String, serde_json::Value> = serde_json::from_str(input)?;
+        let mut targets = FxHashMap::default();
+        for (target, value) in raw {
+            let names: Vec<String> = match value {
+                serde_json::Value::Array(items) => items
+                    .into_iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect(),
+                serde_json::Value::Object(map) => map.into_iter().map(|(k, _)| k).collect(),
+                other => {
+                    return Err(RswappalyzerError::InvalidInput(format!(
+                        "unsupported reference value for target {}: {}",
+                        target, other
+                    )))
+                }
+            };
+            let techs = names
+                .into_iter()
+                .map(|raw_name| {
+                    let (name, version) = split_name_version(&raw_name);
+                    (name.to_lowercase(), (name, version))
+                })
+                .collect();
+            targets.insert(target, techs);
+        }
+        Ok(Self { targets })
+    }
+
+    /// 目标数量
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+}
+
+/// 拆分 wappalyzergo 的 `名称:版本` 格式
+fn split_name_version(raw: &str) -> (String, Option<String>) {
+    match raw.rsplit_once(':') {
+        Some((name, version)) if !name.is_empty() && !version.is_empty() => {
+            (name.trim().to_string(), Some(version.trim().to_string()))
+        }
+        _ => (raw.trim().to_string(), None),
+    }
+}
+
+/// 解析 JSON Lines 语料（跳过空行）
+pub fn parse_corpus(input: &str) -> RswResult<Vec<CorpusEntry>> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| serde_json::from_str(line).map_err(RswappalyzerError::from))
+        .collect()
+}
+
+/// 对比单个目标的检测结果
+pub fn compare_target(
+    target: &str,
+    result: &DetectResult,
+    reference: &ReferenceResults,
+) -> TargetComparison {
+    let mut cmp = TargetComparison {
+        target: target.to_string(),
+        ..TargetComparison::default()
+    };
+    let Some(expected) = reference.targets.get(target) else {
+        cmp.no_reference = true;
+        cmp.extra = result.technologies.iter().map(|t| t.name.clone()).collect();
+        return cmp;
+    };
+
+    let mut seen = BTreeSet::new();
+    for tech in &result.technologies {
+        let key = tech.name.to_lowercase();
+        match expected.get(&key) {
+            Some((_, ref_version)) => {
+                if tech.version.is_some() && ref_version.is_some() && &tech.version != ref_version {
+                    cmp.version_mismatch
+                        .insert(tech.name.clone(), (tech.version.clone(), ref_version.clone()));
+                }
+                cmp.agreed.insert(tech.name.clone());
+            }
+            None => {
+                cmp.extra.insert(tech.name.clone());
+            }
+        }
+        seen.insert(key);
+    }
+    for (key, (name, _)) in expected {
+        if !seen.contains(key) {
+            cmp.missing.insert(name.clone());
+        }
+    }
+    cmp
+}
+
+/// 对整个语料执行检测并与参考结果对比
+/// 参数：
+/// - detector: 检测器
+/// - corpus: 语料
+/// - reference: wappalyzergo 录制结果
+///
+/// 返回：对比汇总报告 | 错误（任一目标检测失败即返回）
+pub fn compare_corpus(
+    detector: &TechDetector,
+    corpus: &[CorpusEntry],
+    reference: &ReferenceResults,
+) -> RswResult<CompareReport> {
+    let mut report = CompareReport::default();
+    for entry in corpus {
+        let urls: Vec<&str> = entry.urls.iter().map(String::as_str).collect();
+        let result = detector.detect_with_hashmap(&entry.headers, &urls, entry.body.as_bytes())?;
+        report.push(compare_target(&entry.target, &result, reference));
+    }
+    Ok(report)
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::detector;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Nginx": { "cats": [22], "headers": { "Server": "nginx(?:/([\\d.]+))?\\;version:\\1" } },
+            "PHP": { "cats": [27], "headers": { "X-Powered-By": "^php/?([\\d.]+)?\\;version:\\1" } }
+        }
+    }"#;
+
+    #[test]
+    fn test_split_name_version() {
+        assert_eq!(split_name_version("Nginx:1.25.3"), ("Nginx".to_string(), Some("1.25.3".to_string())));
+        assert_eq!(split_name_version("PHP"), ("PHP".to_string(), None));
+        assert_eq!(split_name_version("PHP:"), ("PHP:".to_string(), None));
+    }
+
+    #[test]
+    fn test_reference_formats() {
+        let reference = ReferenceResults::from_json(
+            r#"{"a.com": ["Nginx:1.25.3", "PHP"], "b.com": {"Nginx": {}}}"#,
+        )
+        .unwrap();
+        assert_eq!(reference.len(), 2);
+        assert_eq!(
+            reference.targets["a.com"]["nginx"],
+            ("Nginx".to_string(), Some("1.25.3".to_string()))
+        );
+        assert!(reference.targets["b.com"].contains_key("nginx"));
+        assert!(ReferenceResults::from_json(r#"{"a.com": 1}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_corpus() {
+        let corpus = parse_corpus("\n{\"target\":\"a.com\",\"headers\":{\"server\":[\"nginx\"]}}\n\n").unwrap();
+        assert_eq!(corpus.len(), 1);
+        assert_eq!(corpus[0].target, "a.com");
+        assert!(corpus[0].body.is_empty());
+        assert!(parse_corpus("{bad").is_err());
+    }
+
+    #[test]
+    fn test_compare_corpus() {
+        let detector = detector(RULES);
+        let corpus = parse_corpus(concat!(
+            "{\"target\":\"a.com\",\"headers\":{\"server\":[\"nginx/1.24.0\"],\"x-powered-by\":[\"PHP/8.2\"]}}\n",
+            "{\"target\":\"b.com\",\"headers\":{\"server\":[\"nginx\"]}}\n",
+            "{\"target\":\"c.com\",\"headers\":{\"server\":[\"nginx\"]}}\n",
+        ))
+        .unwrap();
+        let reference = ReferenceResults::from_json(
+            r#"{"a.com": ["nginx:1.25.3"], "b.com": ["Nginx", "jQuery"]}"#,
+        )
+        .unwrap();
+        let report = compare_corpus(&detector, &corpus, &reference).unwrap();
+
+        let a = &report.targets[0];
+        assert_eq!(a.agreed, BTreeSet::from(["Nginx".to_string()]));
+        assert_eq!(a.extra, BTreeSet::from(["PHP".to_string()]));
+        assert_eq!(
+            a.version_mismatch["Nginx"],
+            (Some("1.24.0".to_string()), Some("1.25.3".to_string()))
+        );
+
+        let b = &report.targets[1];
+        assert_eq!(b.missing, BTreeSet::from(["jQuery".to_string()]));
+        assert!(b.version_mismatch.is_empty());
+
+        let c = &report.targets[2];
+        assert!(c.no_reference);
+        assert_eq!(c.extra, BTreeSet::from(["Nginx".to_string()]));
+
+        assert_eq!((report.agreed, report.missing, report.extra), (2, 1, 2));
+        assert_eq!((report.version_mismatch, report.no_reference), (1, 1));
+        assert_eq!(report.exact_targets(), 0);
+        assert!((report.recall() - 2.0 / 3.0).abs() < 1e-9);
+        assert!((report.precision() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_report_ratios() {
+        let report = CompareReport::default();
+        assert_eq!(report.recall(), 1.0);
+        assert_eq!(report.precision(), 1.0);
+    }
+}
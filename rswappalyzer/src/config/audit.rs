@@ -0,0 +1,233 @@
+//! I/O 访问审计
+//! 列出当前配置在创建检测器时会执行的全部文件系统/网络访问，
+//! 便于在受限环境（沙箱/只读文件系统/无外网）中预先审查；检测阶段本身不涉及任何 I/O
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::rule::{RuleConfig, RuleOrigin};
+use crate::rule::loader::rule_loader::OFFICIAL_RULES_URL;
+use crate::rule::RuleCacheManager;
+
+/// I/O 访问类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IoAccess {
+    /// 读取文件
+    FileRead,
+    /// 写入文件
+    FileWrite,
+    /// 创建目录
+    DirCreate,
+    /// 网络请求
+    Network,
+}
+
+/// 单个 I/O 访问点
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IoTouchpoint {
+    /// 访问类型
+    pub access: IoAccess,
+    /// 访问目标（文件路径/目录/URL）
+    pub target: String,
+    /// 用途说明
+    pub purpose: String,
+    /// 是否为条件访问（如仅在缓存缺失或规则有更新时发生）
+    pub conditional: bool,
+}
+
+impl IoTouchpoint {
+    fn new(access: IoAccess, target: impl Into<String>, purpose: &str, conditional: bool) -> Self {
+        Self {
+            access,
+            target: target.into(),
+            purpose: purpose.to_string(),
+            conditional,
+        }
+    }
+
+    fn path(access: IoAccess, path: &Path, purpose: &str, conditional: bool) -> Self {
+        Self::new(access, path.display().to_string(), purpose, conditional)
+    }
+}
+
+impl RuleConfig {
+    /// 审计当前配置创建检测器时的全部文件系统/网络访问点
+    /// 内置规则 + 内置分类数据的默认配置不产生任何访问
+    /// 返回：按执行顺序排列的访问点列表
+    pub fn io_touchpoints(&self) -> Vec<IoTouchpoint> {
+        let mut points = Vec::new();
+        let cache_file = self.get_cache_file_path();
+        let cache_dir = &self.options.cache_dir;
+
+        match &self.origin {
+            RuleOrigin::Embedded => {}
+            RuleOrigin::LocalFile(path) => {
+                points.push(IoTouchpoint::path(IoAccess::FileRead, &cache_file, "rule cache", false));
+                points.push(IoTouchpoint::path(IoAccess::FileRead, path, "raw rule file (cache miss)", true));
+                points.push(IoTouchpoint::path(IoAccess::DirCreate, cache_dir, "rule cache directory (cache miss)", true));
+                points.push(IoTouchpoint::path(IoAccess::FileWrite, &cache_file, "rule cache (cache miss)", true));
+            }
+            RuleOrigin::RemoteOfficial | RuleOrigin::RemoteCustom(_) => {
+                // 未启用 remote-loader 特性时远程加载直接返回错误，不产生任何访问
+                if cfg!(feature = "remote-loader") {
+                    let url = match &self.origin {
+                        RuleOrigin::RemoteCustom(url) => url.as_str(),
+                        _ => OFFICIAL_RULES_URL,
+                    };
+                    let etag_file = cache_dir.join("etag_records.json");
+                    let check_update = self.options.check_update;
+                    let extra_sources = self
+                        .remote_options
+                        .as_ref()
+                        .map(|opts| opts.sources.as_slice())
+                        .unwrap_or_default();
+
+                    points.push(IoTouchpoint::path(IoAccess::FileRead, &cache_file, "rule cache", false));
+                    if extra_sources.is_empty() {
+                        if check_update {
+                            points.push(IoTouchpoint::path(IoAccess::DirCreate, cache_dir, "rule cache directory", false));
+                            points.push(IoTouchpoint::path(IoAccess::FileRead, &etag_file, "ETag records", false));
+                            points.push(IoTouchpoint::new(
+                                IoAccess::Network,
+                                url,
+                                "conditional rule download (GET, 304 when unchanged)",
+                                false,
+                            ));
+                        } else {
+                            points.push(IoTouchpoint::new(IoAccess::Network, url, "rule download (GET)", true));
+                        }
+                    } else {
+                        // 多规则源：按调度模式依次拉取（不使用ETag）
+                        let urls = std::iter::once(url).chain(extra_sources.iter().map(|s| s.url.as_str()));
+                        for source_url in urls {
+                            points.push(IoTouchpoint::new(IoAccess::Network, source_url, "rule download (GET)", true));
+                        }
+                    }
+                    points.push(IoTouchpoint::path(IoAccess::DirCreate, cache_dir, "rule cache directory", true));
+                    points.push(IoTouchpoint::path(IoAccess::FileWrite, &cache_file, "rule cache", true));
+                    if check_update && extra_sources.is_empty() {
+                        points.push(IoTouchpoint::path(IoAccess::FileWrite, &etag_file, "ETag records", true));
+                    }
+                }
+            }
+        }
+
+        // 分类数据仅在显式指定路径时读取文件
+        // 内置规则包已携带分类映射，内置来源下仅 TechDetector::with_rules 等运行时编译场景读取
+        if let Some(path) = &self.options.category_path {
+            let embedded = matches!(self.origin, RuleOrigin::Embedded);
+            points.push(IoTouchpoint::path(IoAccess::FileRead, path, "category data", embedded));
+        }
+
+        // 运行时规则：规则与编译配置未变化时加载已持久化的编译产物，否则编译后写入
+        let runtime_rules = match self.origin {
+            RuleOrigin::Embedded => false,
+            RuleOrigin::LocalFile(_) => true,
+            RuleOrigin::RemoteOfficial | RuleOrigin::RemoteCustom(_) => cfg!(feature = "remote-loader"),
+        };
+        if runtime_rules && self.options.persist_compiled && self.options.matchers.is_empty() {
+            let compiled_file = RuleCacheManager::compiled_cache_path(self);
+            points.push(IoTouchpoint::path(IoAccess::FileRead, &compiled_file, "compiled rule cache", false));
+            points.push(IoTouchpoint::path(
+                IoAccess::FileWrite,
+                &compiled_file,
+                "compiled rule cache (rules or compile options changed)",
+                true,
+            ));
+        }
+
+        points
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::CustomConfigBuilder;
+
+    fn accesses(points: &[IoTouchpoint]) -> Vec<(IoAccess, bool)> {
+        points.iter().map(|p| (p.access, p.conditional)).collect()
+    }
+
+    #[test]
+    fn test_embedded_has_no_io() {
+        assert!(RuleConfig::default().io_touchpoints().is_empty());
+
+        // 内置来源下分类数据仅在运行时编译时读取
+        let config = CustomConfigBuilder::new()
+            .category_path(PathBuf::from("/data/categories.json"))
+            .build();
+        let points = config.io_touchpoints();
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0].target, "/data/categories.json");
+        assert_eq!((points[0].access, points[0].conditional), (IoAccess::FileRead, true));
+    }
+
+    #[test]
+    fn test_category_map_source() {
+        // 未指定路径时使用内置分类数据（不读取工作目录）
+        let builtin = RuleConfig::default().options.category_map();
+        assert!(!builtin.is_empty());
+
+        let path = std::env::temp_dir().join(format!("rsw-audit-categories-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"7": {"name": "Custom", "priority": 1, "groups": []}}"#).unwrap();
+        let config = CustomConfigBuilder::new().category_path(path.clone()).build();
+        let custom = config.options.category_map();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(custom.len(), 1);
+        assert_eq!(custom[&7], "Custom");
+    }
+
+    #[test]
+    fn test_local_file() {
+        let config = CustomConfigBuilder::new()
+            .origin(RuleOrigin::LocalFile(PathBuf::from("/data/technologies.json")))
+            .cache_dir(PathBuf::from("/tmp/rsw-cache"))
+            .persist_compiled(false)
+            .build();
+        let points = config.io_touchpoints();
+        assert_eq!(
+            accesses(&points),
+            vec![
+                (IoAccess::FileRead, false),
+                (IoAccess::FileRead, true),
+                (IoAccess::DirCreate, true),
+                (IoAccess::FileWrite, true),
+            ]
+        );
+        assert_eq!(points[1].target, "/data/technologies.json");
+        assert!(points.iter().all(|p| p.access != IoAccess::Network));
+
+        // 持久化编译产物时追加编译缓存读写
+        let config = CustomConfigBuilder::new()
+            .origin(RuleOrigin::LocalFile(PathBuf::from("/data/technologies.json")))
+            .build();
+        let points = config.io_touchpoints();
+        let compiled = RuleCacheManager::compiled_cache_path(&config).display().to_string();
+        assert_eq!(points.iter().filter(|p| p.target == compiled).count(), 2);
+    }
+
+    #[test]
+    fn test_remote_network_gated_by_feature() {
+        let config = CustomConfigBuilder::new()
+            .origin(RuleOrigin::RemoteOfficial)
+            .check_update(true)
+            .build();
+        let points = config.io_touchpoints();
+        let network: Vec<&str> = points
+            .iter()
+            .filter(|p| p.access == IoAccess::Network)
+            .map(|p| p.target.as_str())
+            .collect();
+        if cfg!(feature = "remote-loader") {
+            assert_eq!(network, vec![OFFICIAL_RULES_URL]);
+            assert!(points.iter().any(|p| p.target.ends_with("etag_records.json")));
+        } else {
+            assert!(points.is_empty());
+        }
+    }
+}
@@ -0,0 +1,39 @@
+//! Header 横幅解析兜底策略
+//! 启用后在 Header 维度规则匹配之后，按 RFC 7231 Server 语法解析 `Server` / `X-Powered-By`
+//! 横幅（product/version + comment），对照内置产品表补充规则未检出的技术或缺失的版本号；
+//! 不依赖 Wappalyzer 正则，默认关闭
+
+/// Header 横幅解析兜底策略
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BannerPolicy {
+    /// 横幅新检出技术的置信度（0-100）
+    pub confidence: u8,
+    /// 模糊匹配允许的最大编辑距离（0 表示仅精确匹配）
+    pub max_distance: usize,
+}
+
+impl Default for BannerPolicy {
+    fn default() -> Self {
+        Self {
+            confidence: 75,
+            max_distance: 1,
+        }
+    }
+}
+
+impl BannerPolicy {
+    /// 创建策略
+    /// 参数：confidence - 横幅新检出技术的置信度
+    pub fn new(confidence: u8) -> Self {
+        Self {
+            confidence: confidence.min(100),
+            ..Self::default()
+        }
+    }
+
+    /// 设置模糊匹配最大编辑距离（链式，0 关闭模糊匹配）
+    pub fn with_max_distance(mut self, max_distance: usize) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+}
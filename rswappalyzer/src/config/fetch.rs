@@ -0,0 +1,159 @@
+//! 多规则源拉取配置
+//! 远程规则可配置多个规则源（镜像/私有补充规则等），按 FetchMode 调度：
+//! 1. FirstSuccess：按顺序尝试，首个成功的规则源生效
+//! 2. Merge：拉取全部规则源并合并（至少一个成功）
+//! 3. Quorum(n)：拉取全部规则源并合并，至少 n 个成功才视为成功
+//!
+//! 多个规则源包含同名技术时按 MergePolicy 处理（保留先拉取/后拉取的定义、模式级合并或视为冲突报错）
+//!
+//! 每个规则源可单独设置超时与重试策略及规则格式（Wappalyzer / FingerprintHub / EHole / RSW YAML），拉取进度通过回调上报给宿主程序
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::rule::RetryPolicy;
+
+/// 多规则源调度模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FetchMode {
+    /// 按顺序尝试，首个成功的规则源生效（默认）
+    #[default]
+    FirstSuccess,
+    /// 拉取全部规则源并按顺序合并（同名技术按 MergePolicy 处理），至少一个成功
+    Merge,
+    /// 拉取全部规则源并合并，成功数不少于指定值
+    Quorum(usize),
+}
+
+/// 同名技术合并策略（Merge / Quorum 模式下多个规则源包含同一技术时生效）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// 保留先拉取规则源的定义
+    PreferFirst,
+    /// 以后拉取规则源的定义为准（默认）
+    #[default]
+    PreferLast,
+    /// 模式级合并：按作用域追加不重复的模式，分类/关联/依赖/互斥/主机名去重合并
+    /// 注：同一作用域任一方为 and 条件时无法合并，保留先拉取的规则集
+    MergePatterns,
+    /// 同名技术定义不一致时报错（定义完全一致的重复技术不视为冲突）
+    FailOnConflict,
+}
+
+/// 规则源格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RuleFormat {
+    /// Wappalyzer 技术规则 JSON（默认）
+    #[default]
+    Wappalyzer,
+    /// FingerprintHub 指纹 JSON（observer_ward `web_fingerprint_v3.json`），拉取后转换为内核规则
+    FingerprintHub,
+    /// EHole 指纹 JSON（`finger.json`），拉取后转换为内核规则
+    Ehole,
+    /// RSW YAML 简化规则（需启用 yaml-rules 特性），拉取后转换为内核规则
+    RswYaml,
+}
+
+/// 单个规则源配置
+#[derive(Debug, Clone)]
+pub struct RemoteSource {
+    /// 规则源URL
+    pub url: String,
+    /// 规则源名称（记录到技术的 rule_source，None 时使用URL）
+    pub name: Option<String>,
+    /// 超时（None 时使用 RemoteOptions.timeout）
+    pub timeout: Option<Duration>,
+    /// 重试策略（None 时使用 RemoteOptions.retry）
+    pub retry: Option<RetryPolicy>,
+    /// 规则格式
+    pub format: RuleFormat,
+}
+
+impl RemoteSource {
+    /// 创建规则源（超时/重试沿用全局配置）
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            name: None,
+            timeout: None,
+            retry: None,
+            format: RuleFormat::default(),
+        }
+    }
+
+    /// 设置规则源名称（链式，如 official / internal）
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// 规则源名称（未设置时为URL）
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.url)
+    }
+
+    /// 设置该规则源的超时（链式）
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// 设置该规则源的重试策略（链式）
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// 设置该规则源的规则格式（链式）
+    pub fn with_format(mut self, format: RuleFormat) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+/// 多规则源拉取进度事件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchProgress {
+    /// 开始拉取规则源
+    SourceStarted { index: usize, total: usize, url: String },
+    /// 规则源拉取成功
+    SourceSucceeded {
+        index: usize,
+        total: usize,
+        url: String,
+        tech_count: usize,
+    },
+    /// 规则源拉取失败
+    SourceFailed {
+        index: usize,
+        total: usize,
+        url: String,
+        error: String,
+    },
+    /// 全部调度完成
+    Completed { succeeded: usize, failed: usize },
+}
+
+/// 拉取进度回调
+#[derive(Clone)]
+pub struct FetchProgressCallback(Arc<dyn Fn(&FetchProgress) + Send + Sync>);
+
+impl FetchProgressCallback {
+    /// 创建进度回调
+    pub fn new(callback: impl Fn(&FetchProgress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    /// 上报进度事件
+    #[inline]
+    pub fn emit(&self, event: &FetchProgress) {
+        (self.0)(event)
+    }
+}
+
+impl fmt::Debug for FetchProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FetchProgressCallback")
+    }
+}
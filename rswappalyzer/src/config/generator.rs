@@ -0,0 +1,117 @@
+//! Meta generator 防伪策略
+//! 部分站点会伪造 `<meta name="generator">`（如冒充 WordPress），
+//! 启用后仅由 generator 命中、且未被其它作用域佐证的技术只贡献降低后的置信度
+
+use rustc_hash::FxHashMap;
+
+/// generator 元标签名称
+pub const GENERATOR_META_NAME: &str = "generator";
+
+/// Meta generator 防伪策略
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratorPolicy {
+    /// 未被其它作用域佐证时的置信度上限（0-100）
+    pub uncorroborated_confidence: u8,
+    /// 按技术覆盖置信度上限（技术名 -> 上限；100 表示完全信任该技术的 generator）
+    pub overrides: FxHashMap<String, u8>,
+}
+
+impl Default for GeneratorPolicy {
+    fn default() -> Self {
+        Self {
+            uncorroborated_confidence: 50,
+            overrides: FxHashMap::default(),
+        }
+    }
+}
+
+impl GeneratorPolicy {
+    /// 创建策略
+    /// 参数：uncorroborated_confidence - 未佐证时的置信度上限
+    pub fn new(uncorroborated_confidence: u8) -> Self {
+        Self {
+            uncorroborated_confidence: uncorroborated_confidence.min(100),
+            ..Self::default()
+        }
+    }
+
+    /// 为指定技术设置置信度上限（链式）
+    pub fn with_override(mut self, tech: impl Into<String>, confidence: u8) -> Self {
+        self.overrides.insert(tech.into(), confidence.min(100));
+        self
+    }
+
+    /// 指定技术未被佐证时的置信度上限
+    #[inline]
+    pub fn cap_for(&self, tech: &str) -> u8 {
+        self.overrides
+            .get(tech)
+            .copied()
+            .unwrap_or(self.uncorroborated_confidence)
+    }
+}
+
+/// 是否为 generator 元标签
+#[inline]
+pub fn is_generator_meta(name: &str) -> bool {
+    name.eq_ignore_ascii_case(GENERATOR_META_NAME)
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::test_support::detector_with;
+    use crate::CustomConfigBuilder;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "WordPress": { "cats": [1], "meta": { "generator": "^WordPress ([\\d.]+)\\;version:\\1" }, "html": "wp-content" },
+            "Drupal": { "cats": [1], "meta": { "generator": "^Drupal" } }
+        }
+    }"#;
+
+    fn confidences(policy: GeneratorPolicy, html: &str) -> FxHashMap<String, u8> {
+        let config = CustomConfigBuilder::new().generator_policy(policy).build();
+        detector_with(RULES, config)
+            .detect(&HeaderMap::new(), &[], html.as_bytes())
+            .unwrap()
+            .technologies
+            .into_iter()
+            .map(|t| (t.name, t.confidence))
+            .collect()
+    }
+
+    #[test]
+    fn test_cap_for() {
+        let policy = GeneratorPolicy::new(150).with_override("Drupal", 120);
+        assert_eq!(policy.uncorroborated_confidence, 100);
+        assert_eq!(policy.cap_for("Drupal"), 100);
+
+        let policy = GeneratorPolicy::new(30).with_override("Drupal", 80);
+        assert_eq!(policy.cap_for("Drupal"), 80);
+        assert_eq!(policy.cap_for("WordPress"), 30);
+        assert_eq!(GeneratorPolicy::default().cap_for("WordPress"), 50);
+        assert!(is_generator_meta("Generator"));
+        assert!(!is_generator_meta("description"));
+    }
+
+    #[test]
+    fn test_uncorroborated_generator_capped() {
+        let policy = GeneratorPolicy::new(30).with_override("Drupal", 100);
+        let detected = confidences(policy.clone(), r#"<meta name="generator" content="WordPress 6.4">"#);
+        assert_eq!(detected["WordPress"], 30);
+        let detected = confidences(policy.clone(), r#"<meta name="generator" content="Drupal 10">"#);
+        // 覆盖上限 100：保留常规合并结果（无版本号为 85）
+        assert_eq!(detected["Drupal"], 85);
+
+        // 其它作用域佐证后不受上限约束
+        let detected = confidences(
+            policy,
+            r#"<meta name="generator" content="WordPress 6.4"><link href="/wp-content/a.css">"#,
+        );
+        assert_eq!(detected["WordPress"], 100);
+    }
+}
@@ -0,0 +1,118 @@
+//! 关联推导（implies）策略
+//! 控制推导链（A implies B implies C）的最大深度与逐跳置信度衰减，
+//! 使深层推导的技术置信度明显更低，同时限制失控的长推导链
+
+/// 关联推导策略
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImplyPolicy {
+    /// 每多推导一跳的置信度衰减系数（0.0-1.0，第 1 跳不衰减）
+    pub decay: f32,
+    /// 最大推导深度（0 表示不做关联推导；默认 1，即仅由真实命中的技术推导一跳）
+    pub max_depth: usize,
+}
+
+impl Default for ImplyPolicy {
+    fn default() -> Self {
+        Self {
+            decay: 0.9,
+            max_depth: 1,
+        }
+    }
+}
+
+impl ImplyPolicy {
+    /// 创建策略
+    /// 参数：
+    /// - decay: 逐跳衰减系数（超出 0.0-1.0 时截断）
+    /// - max_depth: 最大推导深度
+    pub fn new(decay: f32, max_depth: usize) -> Self {
+        Self {
+            decay: if decay.is_nan() { 1.0 } else { decay.clamp(0.0, 1.0) },
+            max_depth,
+        }
+    }
+
+    /// 计算第 depth 跳（从 1 开始）推导技术的置信度
+    /// 参数：
+    /// - confidence: 未衰减的推导置信度
+    /// - depth: 推导深度
+    #[inline]
+    pub fn confidence_at(&self, confidence: u8, depth: usize) -> u8 {
+        if depth <= 1 {
+            return confidence;
+        }
+        let hops = i32::try_from(depth - 1).unwrap_or(i32::MAX);
+        (f32::from(confidence) * self.decay.powi(hops)).round() as u8
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::test_support::detector_with;
+    use crate::{CustomConfigBuilder, DetectResult};
+
+    const RULES: &str = r#"{"technologies": {
+        "Acme": { "html": "acme-cms", "implies": "PHP" },
+        "PHP": { "headers": { "X-Powered-By": "php" }, "implies": "Linux" },
+        "Linux": { "headers": { "Server": "linux" } }
+    }}"#;
+
+    fn detect(policy: ImplyPolicy) -> DetectResult {
+        let config = CustomConfigBuilder::new().imply_policy(policy).build();
+        detector_with(RULES, config)
+            .detect(&HeaderMap::new(), &[], b"<html><body><p>Powered by acme-cms</p></body></html>")
+            .unwrap()
+    }
+
+    fn confidence(result: &DetectResult, name: &str) -> Option<u8> {
+        result.technologies.iter().find(|t| t.name == name).map(|t| t.confidence)
+    }
+
+    #[test]
+    fn test_confidence_at() {
+        let policy = ImplyPolicy::default();
+        assert_eq!(policy.decay, 0.9);
+        assert_eq!(policy.confidence_at(93, 0), 93);
+        assert_eq!(policy.confidence_at(93, 1), 93);
+        assert_eq!(policy.confidence_at(93, 2), 84);
+        assert_eq!(policy.confidence_at(93, 3), 75);
+        assert_eq!(ImplyPolicy::new(0.5, 3).confidence_at(90, 3), 23);
+    }
+
+    #[test]
+    fn test_new_clamps_decay() {
+        assert_eq!(ImplyPolicy::new(1.5, 2).decay, 1.0);
+        assert_eq!(ImplyPolicy::new(-0.2, 2).decay, 0.0);
+        assert_eq!(ImplyPolicy::new(f32::NAN, 2).decay, 1.0);
+        assert_eq!(ImplyPolicy::new(0.0, 2).confidence_at(90, 2), 0);
+    }
+
+    #[test]
+    fn test_default_depth_stops_after_one_hop() {
+        let result = detect(ImplyPolicy::default());
+        // 单一来源推导：90 + 3
+        assert_eq!(confidence(&result, "PHP"), Some(93));
+        assert_eq!(confidence(&result, "Linux"), None);
+    }
+
+    #[test]
+    fn test_chained_implies_decay() {
+        let result = detect(ImplyPolicy::new(0.9, 2));
+        assert_eq!(confidence(&result, "PHP"), Some(93));
+        assert_eq!(confidence(&result, "Linux"), Some(84));
+
+        let result = detect(ImplyPolicy::new(0.8, 2));
+        assert_eq!(confidence(&result, "Linux"), Some(74));
+    }
+
+    #[test]
+    fn test_zero_depth_disables_implies() {
+        let result = detect(ImplyPolicy::new(0.9, 0));
+        assert!(confidence(&result, "Acme").is_some());
+        assert_eq!(confidence(&result, "PHP"), None);
+    }
+}
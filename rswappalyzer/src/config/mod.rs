@@ -1 +1,9 @@
+pub mod audit;
+pub mod banner;
+pub mod fetch;
+pub mod generator;
+pub mod implies;
+pub mod proxy;
 pub mod rule;
+pub mod script_host;
+pub mod update;
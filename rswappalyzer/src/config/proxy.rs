@@ -0,0 +1,71 @@
+//! 远程规则拉取代理配置
+//! 企业网络环境下访问规则源（如 raw.githubusercontent.com）需经代理，优先级：
+//! 1. socks5：SOCKS5 代理（未带协议前缀时按 `socks5h://` 处理，由代理端解析域名）
+//! 2. http_proxy：HTTP 代理（HTTP/HTTPS 请求均经该代理转发）
+//! 3. 均未设置且 use_env 开启时，读取环境变量 HTTP_PROXY / HTTPS_PROXY / ALL_PROXY / NO_PROXY
+//! 4. 以上均不生效时直连
+
+/// 代理配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProxyConfig {
+    /// HTTP 代理地址（如 `http://proxy.corp:3128`，可带 `user:pass@` 认证信息）
+    pub http_proxy: Option<String>,
+    /// SOCKS5 代理地址（如 `socks5://127.0.0.1:1080`）
+    pub socks5: Option<String>,
+    /// 未显式设置代理时回退读取环境变量，默认开启
+    pub use_env: bool,
+}
+
+impl Default for ProxyConfig {
+    fn default() -> Self {
+        Self {
+            http_proxy: None,
+            socks5: None,
+            use_env: true,
+        }
+    }
+}
+
+impl ProxyConfig {
+    /// 直连（不使用代理，忽略环境变量）
+    pub fn direct() -> Self {
+        Self {
+            use_env: false,
+            ..Self::default()
+        }
+    }
+
+    /// 设置 HTTP 代理（链式）
+    pub fn http_proxy(mut self, url: impl Into<String>) -> Self {
+        self.http_proxy = Some(url.into());
+        self
+    }
+
+    /// 设置 SOCKS5 代理（链式）
+    pub fn socks5(mut self, url: impl Into<String>) -> Self {
+        self.socks5 = Some(url.into());
+        self
+    }
+
+    /// 设置是否回退读取环境变量（链式）
+    pub fn use_env(mut self, enabled: bool) -> Self {
+        self.use_env = enabled;
+        self
+    }
+
+    /// 实际生效的显式代理地址（socks5 优先，SOCKS5 地址补全协议前缀）
+    pub fn explicit_url(&self) -> Option<String> {
+        if let Some(socks5) = self.socks5.as_deref().map(str::trim).filter(|url| !url.is_empty()) {
+            return Some(if socks5.contains("://") {
+                socks5.to_string()
+            } else {
+                format!("socks5h://{}", socks5)
+            });
+        }
+        self.http_proxy
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(str::to_string)
+    }
+}
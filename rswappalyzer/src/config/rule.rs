@@ -1,9 +1,27 @@
 //! 全局规则配置管理
 
+use rswappalyzer_engine::{
+    scope_pruner::PruneScope, CompileOptions, CustomMatcher, ExpiredRulePolicy,
+    LowSpecificityPolicy, MatcherRegistry, PatternValidation, RegexLimits, RuleIndexer,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::fetch::{FetchMode, FetchProgressCallback, MergePolicy, RemoteSource};
+use super::banner::BannerPolicy;
+use super::generator::GeneratorPolicy;
+use super::implies::ImplyPolicy;
+use super::proxy::ProxyConfig;
+use crate::utils::extractor::html_extractor::ExtractLimits;
+use crate::utils::extractor::HtmlParser;
+use super::script_host::ScriptHostPolicy;
+use super::update::AutoUpdatePolicy;
 use std::hash::Hasher;
 use std::hash::Hash;
 use std::{hash::DefaultHasher, path::PathBuf, time::Duration};
 
+/// 内置分类数据（Wappalyzer categories，编译期 embed）
+pub const DEFAULT_CATEGORIES_JSON: &str = include_str!("../../data/categories_data.json");
+
 /// 规则来源
 #[derive(Debug, Clone)]
 pub enum RuleOrigin {
@@ -26,6 +44,62 @@ pub struct RemoteOptions {
     pub urls: Vec<String>,  // URL 列表
     pub timeout: Duration,  // HTTP 超时
     pub retry: RetryPolicy, // 重试策略
+    /// 附加规则源（非空时启用多规则源调度，主规则源排在首位）
+    pub sources: Vec<RemoteSource>,
+    /// 多规则源调度模式
+    pub mode: FetchMode,
+    /// 多规则源同名技术合并策略
+    pub merge_policy: MergePolicy,
+    /// 拉取进度回调（可选）
+    pub progress: Option<FetchProgressCallback>,
+    /// 代理配置（默认未显式设置时读取 HTTP_PROXY 等环境变量）
+    pub proxy: ProxyConfig,
+}
+
+impl RemoteOptions {
+    /// 创建网络加载选项（单规则源）
+    pub fn new(timeout: Duration, retry: RetryPolicy) -> Self {
+        Self {
+            urls: Vec::new(),
+            timeout,
+            retry,
+            sources: Vec::new(),
+            mode: FetchMode::default(),
+            merge_policy: MergePolicy::default(),
+            progress: None,
+            proxy: ProxyConfig::default(),
+        }
+    }
+
+    /// 添加附加规则源（链式）
+    pub fn with_source(mut self, source: RemoteSource) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    /// 设置多规则源调度模式（链式）
+    pub fn with_mode(mut self, mode: FetchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// 设置多规则源同名技术合并策略（链式）
+    pub fn with_merge_policy(mut self, policy: MergePolicy) -> Self {
+        self.merge_policy = policy;
+        self
+    }
+
+    /// 设置代理配置（链式）
+    pub fn with_proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /// 设置拉取进度回调（链式）
+    pub fn with_progress(mut self, callback: FetchProgressCallback) -> Self {
+        self.progress = Some(callback);
+        self
+    }
 }
 
 /// 重试策略
@@ -35,20 +109,150 @@ pub enum RetryPolicy {
     Times(u8), // 固定次数重试（不含第一次）
 }
 
+/// 规则缓存过期处理策略（设置 cache_ttl 且缓存超过该时长时生效）
+/// 注：本地文件规则源过期时均同步重新读取原始规则文件，ServeStaleAndRefresh 与 AlwaysRefresh 行为一致
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StalePolicy {
+    /// 先返回过期缓存，后台刷新缓存供下次加载使用（默认）
+    #[default]
+    ServeStaleAndRefresh,
+    /// 过期缓存不可用：同步刷新，失败时报错（降级缓存同样不使用过期缓存）
+    FailIfStale,
+    /// 同步刷新，失败时降级返回过期缓存
+    AlwaysRefresh,
+}
+
 /// 核心规则选项
 #[derive(Debug, Clone)]
 pub struct RuleOptions {
     /// 仅对远程规则有效：是否在启动时检查更新
     pub check_update: bool,
+    /// 本地/远程规则加载失败时降级使用已有缓存（默认关闭）
+    pub fallback_to_cache: bool,
+    /// 本地/远程规则加载失败时降级使用内置规则（默认关闭，未开启 embedded-rules 特性时不生效）
+    pub fallback_to_embedded: bool,
+    /// 启动时规则加载时限（超时视为加载失败并按配置降级，需 remote-loader 特性），默认不限时
+    pub startup_timeout: Option<Duration>,
     /// 规则缓存目录（远程规则 / 构建产物等）
     pub cache_dir: PathBuf,
+    /// 规则缓存有效期（按缓存文件修改时间计算，None 时缓存永不过期）
+    pub cache_ttl: Option<Duration>,
+    /// 规则缓存过期处理策略
+    pub stale_policy: StalePolicy,
+    /// 持久化运行时编译的规则库（规则与编译配置未变化时下次启动直接加载，跳过索引构建与编译），默认开启
+    /// 注：注册了自定义匹配类型时不持久化（匹配器无法序列化）
+    pub persist_compiled: bool,
+    /// 正则按大小写敏感匹配的作用域（默认为空，即全部忽略大小写）
+    /// 注：源码中显式声明 `(?-i)` 的正则始终大小写敏感
+    pub case_sensitive_scopes: FxHashSet<PruneScope>,
+    /// 低特异性模式（被多个技术共享的相同模式/证据）降权策略，默认关闭
+    pub low_specificity: Option<LowSpecificityPolicy>,
+    /// Script 来源主机策略（CDN/忽略列表），默认不生效
+    pub script_host_policy: ScriptHostPolicy,
+    /// 过期规则（valid_until 早于当前日期）处理策略，默认告警并保留
+    pub expired_rules: ExpiredRulePolicy,
+    /// Meta generator 防伪策略（未被其它作用域佐证时降低置信度），默认关闭
+    pub generator_policy: Option<GeneratorPolicy>,
+    /// 关联推导策略（最大推导深度/逐跳置信度衰减），默认仅推导一跳
+    pub imply_policy: ImplyPolicy,
+    /// Header 横幅解析兜底策略（Server/X-Powered-By 产品表匹配），默认关闭
+    pub banner_policy: Option<BannerPolicy>,
+    /// HTML 提取上限（script/meta 数量、注释字节数）
+    pub extract_limits: ExtractLimits,
+    /// HTML 提取解析器，默认 lol_html（Rewriter）；超大页面可选单次前向扫描（Streaming）
+    pub html_parser: HtmlParser,
+    /// 按 Content-Type 路由响应体（JS 作为脚本内容、CSS 作为纯文本、二进制跳过），默认开启
+    /// 关闭时所有响应体均按 HTML 处理
+    pub content_type_routing: bool,
+    /// 分析前透明解压仍处于 gzip/brotli/deflate 编码状态的响应体（需 decompress 特性），默认关闭
+    /// HTTP 客户端通常已解压响应体，仅在分析原始抓包时开启
+    pub decompress_body: bool,
+    /// 响应体解压后字节数上限（超出部分截断，防解压炸弹），默认 16 MiB
+    pub max_decompressed_len: usize,
+    /// 参与响应体分析的最小字节数（解码后去除首尾空白；短于该值时仅分析 URL/Header/Cookie），默认 0 不限制
+    /// API 为主的爬取场景中大量响应为 "OK"/单行 JSON 等小载荷，可设为 200 左右跳过守卫/提取/令牌化开销
+    pub min_body_len: usize,
+    /// 自定义分类数据文件路径（None 时使用内置分类数据，不读取文件系统）
+    pub category_path: Option<PathBuf>,
+    /// 模式校验策略（Strict：正则编译失败作为加载错误；Lenient：记录到 broken_patterns），默认不校验
+    pub pattern_validation: PatternValidation,
+    /// 正则体积/复杂度上限（超长正则编译期剔除并记录到 rejected_patterns；体积上限在模式校验或运行期懒编译时生效）
+    pub regex_limits: RegexLimits,
+    /// 技术 slug 别名表（技术名 -> 固定 slug），用于上游重命名后保持稳定标识
+    pub slug_aliases: FxHashMap<String, String>,
+    /// 自定义匹配类型注册表（规则中 `\;matcher:<kind>` 标记的模式由对应工厂构建）
+    pub matchers: MatcherRegistry,
+    /// 编译期移除同一技术同一作用域内的等价重复模式，默认开启
+    /// 注：内置/预编译规则库构建时已按默认值去重，关闭仅对自定义编译的规则生效
+    pub dedup_patterns: bool,
+    /// 仅编译的作用域（默认 None 编译全部作用域；内置/预编译规则库加载后移除其余作用域的模式）
+    /// 未编译的作用域不参与检测，HTML/Script/Meta 均未编译时跳过响应体预处理
+    pub compile_scopes: Option<FxHashSet<PruneScope>>,
+    /// 分诊升级名单（detect_triage 的可能技术命中名单中任一技术时标记升级为完整检测；为空时有任一可能技术即升级）
+    pub triage_escalate: FxHashSet<String>,
+    /// 规则自动更新策略（由 DetectionUpdaterService 执行，需 auto-update 特性），默认关闭
+    pub auto_update: Option<AutoUpdatePolicy>,
+}
+
+impl RuleOptions {
+    /// 转换为引擎层编译选项
+    pub fn compile_options(&self) -> CompileOptions {
+        CompileOptions {
+            case_sensitive_scopes: self.case_sensitive_scopes.clone(),
+            low_specificity: self.low_specificity,
+            expired_rules: self.expired_rules,
+            pattern_validation: self.pattern_validation,
+            regex_limits: self.regex_limits,
+            matchers: self.matchers.clone(),
+            dedup_patterns: self.dedup_patterns,
+            scopes: self.compile_scopes.clone(),
+        }
+    }
+}
+
+impl RuleOptions {
+    /// 加载分类映射：指定路径时读取该文件，否则使用内置分类数据
+    pub fn category_map(&self) -> FxHashMap<u32, String> {
+        match &self.category_path {
+            Some(path) => RuleIndexer::load_category_map(&path.to_string_lossy()),
+            None => RuleIndexer::parse_category_map(DEFAULT_CATEGORIES_JSON),
+        }
+    }
 }
 
 impl Default for RuleOptions {
     fn default() -> Self {
         Self {
             check_update: true,
+            fallback_to_cache: false,
+            fallback_to_embedded: false,
+            startup_timeout: None,
             cache_dir: PathBuf::from(".cache/rswappalyzer"),
+            cache_ttl: None,
+            stale_policy: StalePolicy::default(),
+            persist_compiled: true,
+            case_sensitive_scopes: FxHashSet::default(),
+            low_specificity: None,
+            script_host_policy: ScriptHostPolicy::default(),
+            expired_rules: ExpiredRulePolicy::default(),
+            generator_policy: None,
+            imply_policy: ImplyPolicy::default(),
+            banner_policy: None,
+            extract_limits: ExtractLimits::default(),
+            html_parser: HtmlParser::default(),
+            content_type_routing: true,
+            decompress_body: false,
+            max_decompressed_len: 16 * 1024 * 1024,
+            min_body_len: 0,
+            category_path: None,
+            pattern_validation: PatternValidation::default(),
+            regex_limits: RegexLimits::default(),
+            slug_aliases: FxHashMap::default(),
+            matchers: MatcherRegistry::default(),
+            dedup_patterns: true,
+            compile_scopes: None,
+            triage_escalate: FxHashSet::default(),
+            auto_update: None,
         }
     }
 }
@@ -101,8 +305,7 @@ impl RuleConfig {
             options: RuleOptions::default(),
             remote_options: Some(RemoteOptions {
                 urls: vec![url],
-                timeout,
-                retry,
+                ..RemoteOptions::new(timeout, retry)
             }),
         }
     }
@@ -117,8 +320,7 @@ impl RuleConfig {
             options: RuleOptions::default(),
             remote_options: Some(RemoteOptions {
                 urls: vec![url],
-                timeout,
-                retry,
+                ..RemoteOptions::new(timeout, retry)
             }),
         }
     }
@@ -188,6 +390,184 @@ impl CustomConfigBuilder {
         self
     }
 
+    /// 将指定作用域的正则设为大小写敏感
+    pub fn case_sensitive_scope(mut self, scope: PruneScope) -> Self {
+        self.config.options.case_sensitive_scopes.insert(scope);
+        self
+    }
+
+    /// 启用低特异性模式降权
+    pub fn low_specificity(mut self, policy: LowSpecificityPolicy) -> Self {
+        self.config.options.low_specificity = Some(policy);
+        self
+    }
+
+    /// 设置 Script 来源主机策略
+    pub fn script_host_policy(mut self, policy: ScriptHostPolicy) -> Self {
+        self.config.options.script_host_policy = policy;
+        self
+    }
+
+    /// 设置过期规则处理策略（告警/剔除）
+    pub fn expired_rules(mut self, policy: ExpiredRulePolicy) -> Self {
+        self.config.options.expired_rules = policy;
+        self
+    }
+
+    /// 启用 Meta generator 防伪策略
+    pub fn generator_policy(mut self, policy: GeneratorPolicy) -> Self {
+        self.config.options.generator_policy = Some(policy);
+        self
+    }
+
+    /// 设置关联推导策略（推导链最大深度与逐跳置信度衰减）
+    pub fn imply_policy(mut self, policy: ImplyPolicy) -> Self {
+        self.config.options.imply_policy = policy;
+        self
+    }
+
+    /// 启用 Header 横幅解析兜底
+    pub fn banner_policy(mut self, policy: BannerPolicy) -> Self {
+        self.config.options.banner_policy = Some(policy);
+        self
+    }
+
+    /// 设置规则缓存有效期（超过后按 stale_policy 刷新）
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.config.options.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// 设置规则缓存过期处理策略
+    pub fn stale_policy(mut self, policy: StalePolicy) -> Self {
+        self.config.options.stale_policy = policy;
+        self
+    }
+
+    /// 是否持久化运行时编译的规则库
+    pub fn persist_compiled(mut self, enabled: bool) -> Self {
+        self.config.options.persist_compiled = enabled;
+        self
+    }
+
+    /// 规则加载失败时降级使用已有缓存
+    pub fn fallback_to_cache(mut self, enabled: bool) -> Self {
+        self.config.options.fallback_to_cache = enabled;
+        self
+    }
+
+    /// 规则加载失败时降级使用内置规则（需开启embedded-rules特性，未开启时不生效）
+    pub fn fallback_to_embedded(mut self, enabled: bool) -> Self {
+        self.config.options.fallback_to_embedded = enabled;
+        self
+    }
+
+    /// 设置启动时规则加载时限（超时后按配置降级）
+    pub fn startup_timeout(mut self, limit: Duration) -> Self {
+        self.config.options.startup_timeout = Some(limit);
+        self
+    }
+
+    /// 启用规则自动更新（由 DetectionUpdaterService 按策略定期重新加载并热替换全局检测器）
+    pub fn auto_update(mut self, policy: AutoUpdatePolicy) -> Self {
+        self.config.options.auto_update = Some(policy);
+        self
+    }
+
+    /// 严格模式：存在编译失败的正则时加载失败
+    pub fn strict_patterns(mut self) -> Self {
+        self.config.options.pattern_validation = PatternValidation::Strict;
+        self
+    }
+
+    /// 设置模式校验策略
+    pub fn pattern_validation(mut self, mode: PatternValidation) -> Self {
+        self.config.options.pattern_validation = mode;
+        self
+    }
+
+    /// 设置正则体积/复杂度上限（防止自定义规则中的病态正则）
+    pub fn regex_limits(mut self, limits: RegexLimits) -> Self {
+        self.config.options.regex_limits = limits;
+        self
+    }
+
+    /// 设置是否在编译期去重等价模式（默认开启）
+    pub fn dedup_patterns(mut self, enabled: bool) -> Self {
+        self.config.options.dedup_patterns = enabled;
+        self
+    }
+
+    /// 仅编译指定作用域的模式（如被动监控部署仅做 Header 检测）
+    pub fn compile_scopes(mut self, scopes: impl IntoIterator<Item = PruneScope>) -> Self {
+        self.config.options.compile_scopes = Some(scopes.into_iter().collect());
+        self
+    }
+
+    /// 固定技术的 slug（如上游将技术重命名后，映射回原 slug）
+    pub fn slug_alias(mut self, tech: impl Into<String>, slug: impl Into<String>) -> Self {
+        self.config.options.slug_aliases.insert(tech.into(), slug.into());
+        self
+    }
+
+    /// 注册自定义匹配类型（编译自定义规则包时生效）
+    pub fn register_matcher<F, M>(mut self, kind: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn(&str) -> Result<M, String> + Send + Sync + 'static,
+        M: CustomMatcher + 'static,
+    {
+        self.config.options.matchers.register(kind, factory);
+        self
+    }
+
+    /// 使用自定义分类数据文件（需显式指定路径，默认使用内置分类数据）
+    pub fn category_path(mut self, path: PathBuf) -> Self {
+        self.config.options.category_path = Some(path);
+        self
+    }
+
+    /// 设置 HTML 提取上限
+    pub fn extract_limits(mut self, limits: ExtractLimits) -> Self {
+        self.config.options.extract_limits = limits;
+        self
+    }
+
+    /// 设置 HTML 提取解析器（Streaming 单次前向扫描，多 MB 页面提取更快）
+    pub fn html_parser(mut self, parser: HtmlParser) -> Self {
+        self.config.options.html_parser = parser;
+        self
+    }
+
+    /// 启用/关闭按 Content-Type 路由响应体（关闭时所有响应体均按 HTML 处理）
+    pub fn content_type_routing(mut self, enabled: bool) -> Self {
+        self.config.options.content_type_routing = enabled;
+        self
+    }
+
+    /// 启用/关闭响应体透明解压（需 decompress 特性，默认关闭）
+    pub fn decompress_body(mut self, enabled: bool) -> Self {
+        self.config.options.decompress_body = enabled;
+        self
+    }
+
+    /// 设置响应体解压后字节数上限
+    pub fn max_decompressed_len(mut self, max_len: usize) -> Self {
+        self.config.options.max_decompressed_len = max_len;
+        self
+    }
+
+    /// 设置参与响应体分析的最小字节数（短于该值时跳过 HTML/Script/Meta 分析）
+    pub fn min_body_len(mut self, min_len: usize) -> Self {
+        self.config.options.min_body_len = min_len;
+        self
+    }
+
+    /// 设置分诊升级名单（detect_triage 仅在可能技术命中名单时标记升级为完整检测）
+    pub fn triage_escalate<S: Into<String>>(mut self, techs: impl IntoIterator<Item = S>) -> Self {
+        self.config.options.triage_escalate = techs.into_iter().map(Into::into).collect();
+        self
+    }
+
     pub fn origin(mut self, origin: RuleOrigin) -> Self {
         self.config.origin = origin;
         self.apply_load_method();
@@ -204,3 +584,54 @@ impl CustomConfigBuilder {
         self.config
     }
 }
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use rswappalyzer_engine::source::WappalyzerParser;
+    use rswappalyzer_engine::RuleProcessor;
+
+    use super::*;
+    use crate::{RswappalyzerError, TechDetector};
+
+    const BROKEN_RULES: &str = r#"{"technologies": {
+        "Acme": { "html": "acme-[z-a]" },
+        "Widget": { "html": "widget" }
+    }}"#;
+
+    fn load(config: RuleConfig) -> Result<TechDetector, RswappalyzerError> {
+        let raw = WappalyzerParser.parse_to_rule_lib(BROKEN_RULES).unwrap();
+        let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+        TechDetector::with_rules(rules, config)
+    }
+
+    #[test]
+    fn test_strict_patterns_load_error() {
+        let config = CustomConfigBuilder::new().strict_patterns().build();
+        assert_eq!(config.options.pattern_validation, PatternValidation::Strict);
+        let error = load(config).err().unwrap().to_string();
+        assert!(error.contains("1 pattern(s) failed to compile: Acme/Html: acme-[z-a]"), "{}", error);
+    }
+
+    #[test]
+    fn test_lenient_patterns_reported() {
+        let config = CustomConfigBuilder::new()
+            .pattern_validation(PatternValidation::Lenient)
+            .build();
+        let detector = load(config).unwrap();
+        let broken = &detector.compiled_library().broken_patterns;
+        assert_eq!(broken.len(), 1);
+        assert_eq!((broken[0].tech.as_str(), broken[0].pattern.as_str()), ("Acme", "acme-[z-a]"));
+        // 默认不校验：失败模式不记录
+        assert!(load(RuleConfig::default()).unwrap().compiled_library().broken_patterns.is_empty());
+    }
+
+    #[test]
+    fn test_decompress_body_opt_in() {
+        // 默认不解压，需显式开启
+        assert!(!RuleConfig::default().options.decompress_body);
+        let config = CustomConfigBuilder::new().decompress_body(true).build();
+        assert!(config.options.decompress_body);
+        assert_eq!(config.options.max_decompressed_len, 16 * 1024 * 1024);
+    }
+}
@@ -0,0 +1,169 @@
+//! Script 来源主机策略
+//! 区分源站脚本与公共库 CDN 脚本：CDN 上的库文件只能说明页面引用了该库，
+//! 不代表源站部署了对应技术，资产盘点场景下需要区别对待
+
+/// 脚本主机分类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScriptHostClass {
+    /// 源站脚本（相对路径或未命中任何列表的主机）
+    Origin,
+    /// 公共库 CDN 脚本
+    Cdn,
+    /// 拒绝列表中的主机，直接忽略
+    Denied,
+}
+
+/// Script 来源主机策略
+/// 主机匹配规则：完全相等或为其子域（`jsdelivr.net` 可匹配 `cdn.jsdelivr.net`）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptHostPolicy {
+    /// 公共库 CDN 主机列表
+    pub cdn_hosts: Vec<String>,
+    /// 忽略的主机列表（优先级高于 cdn_hosts）
+    pub deny_hosts: Vec<String>,
+    /// CDN 脚本命中时是否保留提取到的版本号
+    pub cdn_version: bool,
+    /// 仅由 CDN 脚本命中的技术是否参与关联推导（implies）
+    pub cdn_implies: bool,
+}
+
+impl Default for ScriptHostPolicy {
+    /// 默认策略：空列表，与历史行为一致
+    fn default() -> Self {
+        Self {
+            cdn_hosts: Vec::new(),
+            deny_hosts: Vec::new(),
+            cdn_version: true,
+            cdn_implies: false,
+        }
+    }
+}
+
+impl ScriptHostPolicy {
+    /// 常见公共库 CDN 主机
+    pub const COMMON_CDN_HOSTS: &'static [&'static str] = &[
+        "cdn.jsdelivr.net",
+        "cdnjs.cloudflare.com",
+        "unpkg.com",
+        "ajax.googleapis.com",
+        "code.jquery.com",
+        "stackpath.bootstrapcdn.com",
+        "maxcdn.bootstrapcdn.com",
+        "ajax.aspnetcdn.com",
+        "cdn.bootcdn.net",
+        "lib.baomitu.com",
+        "cdn.staticfile.org",
+    ];
+
+    /// 使用常见公共库 CDN 列表创建策略
+    pub fn with_common_cdns() -> Self {
+        Self {
+            cdn_hosts: Self::COMMON_CDN_HOSTS.iter().map(|h| h.to_string()).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// 策略是否生效（两个列表均为空时检测流程与历史行为完全一致）
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        !self.cdn_hosts.is_empty() || !self.deny_hosts.is_empty()
+    }
+
+    /// 对脚本 src 分类
+    pub fn classify(&self, src: &str) -> ScriptHostClass {
+        let Some(host) = script_host(src) else {
+            return ScriptHostClass::Origin;
+        };
+        if Self::host_in(&host, &self.deny_hosts) {
+            ScriptHostClass::Denied
+        } else if Self::host_in(&host, &self.cdn_hosts) {
+            ScriptHostClass::Cdn
+        } else {
+            ScriptHostClass::Origin
+        }
+    }
+
+    fn host_in(host: &str, list: &[String]) -> bool {
+        list.iter().any(|entry| {
+            let entry = entry.trim().trim_start_matches('.');
+            !entry.is_empty()
+                && (host.eq_ignore_ascii_case(entry)
+                    || (host.len() > entry.len()
+                        && host.as_bytes()[host.len() - entry.len() - 1] == b'.'
+                        && host[host.len() - entry.len()..].eq_ignore_ascii_case(entry)))
+        })
+    }
+}
+
+/// 提取脚本 src 的主机名（小写，不含端口）
+/// 支持 `http(s)://host/...` 与协议相对地址 `//host/...`；相对路径返回None
+pub fn script_host(src: &str) -> Option<String> {
+    let src = src.trim();
+    let rest = if let Some(pos) = src.find("://") {
+        let scheme = &src[..pos];
+        if scheme.is_empty() || !scheme.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'-' || b == b'.') {
+            return None;
+        }
+        &src[pos + 3..]
+    } else {
+        src.strip_prefix("//")?
+    };
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    // 去掉 userinfo 与端口
+    let host = authority.rsplit('@').next().unwrap_or("");
+    let host = host.split(':').next().unwrap_or("");
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(cdn: &[&str], deny: &[&str]) -> ScriptHostPolicy {
+        ScriptHostPolicy {
+            cdn_hosts: cdn.iter().map(|h| h.to_string()).collect(),
+            deny_hosts: deny.iter().map(|h| h.to_string()).collect(),
+            ..ScriptHostPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_script_host() {
+        assert_eq!(script_host("https://CDN.jsdelivr.net/npm/x.js").as_deref(), Some("cdn.jsdelivr.net"));
+        assert_eq!(script_host("//unpkg.com/react?v=1").as_deref(), Some("unpkg.com"));
+        assert_eq!(script_host("http://user:pw@example.com:8080/a.js").as_deref(), Some("example.com"));
+        assert_eq!(script_host(" https://example.com#frag ").as_deref(), Some("example.com"));
+        assert_eq!(script_host("/static/app.js"), None);
+        assert_eq!(script_host("static/app.js"), None);
+        assert_eq!(script_host("bad scheme://example.com/a.js"), None);
+        assert_eq!(script_host("https:///a.js"), None);
+    }
+
+    #[test]
+    fn test_classify() {
+        let policy = policy(&["jsdelivr.net", ".unpkg.com"], &["ads.jsdelivr.net"]);
+        assert!(policy.is_active());
+        assert_eq!(policy.classify("/js/app.js"), ScriptHostClass::Origin);
+        assert_eq!(policy.classify("https://example.com/app.js"), ScriptHostClass::Origin);
+        // 子域匹配
+        assert_eq!(policy.classify("https://cdn.jsdelivr.net/x.js"), ScriptHostClass::Cdn);
+        assert_eq!(policy.classify("//unpkg.com/x.js"), ScriptHostClass::Cdn);
+        // 后缀须落在标签边界
+        assert_eq!(policy.classify("https://notunpkg.com/x.js"), ScriptHostClass::Origin);
+        // 拒绝列表优先
+        assert_eq!(policy.classify("https://ads.jsdelivr.net/x.js"), ScriptHostClass::Denied);
+    }
+
+    #[test]
+    fn test_default_and_common_cdns() {
+        let default = ScriptHostPolicy::default();
+        assert!(!default.is_active());
+        assert_eq!(default.classify("https://unpkg.com/x.js"), ScriptHostClass::Origin);
+
+        let common = ScriptHostPolicy::with_common_cdns();
+        assert!(common.is_active());
+        assert_eq!(common.cdn_hosts.len(), ScriptHostPolicy::COMMON_CDN_HOSTS.len());
+        assert_eq!(common.classify("https://code.jquery.com/jquery.js"), ScriptHostClass::Cdn);
+    }
+}
@@ -0,0 +1,92 @@
+//! 规则自动更新策略
+//! 后台更新服务（DetectionUpdaterService，auto-update 特性）按固定间隔 + 随机抖动重新拉取规则、
+//! 重新编译并热替换全局检测器；抖动用于错开多实例部署的拉取时刻，避免同时打到规则源
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 单次自动更新结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleUpdateEvent {
+    /// 规则已重新加载并替换全局检测器
+    Reloaded {
+        /// 新规则库技术数
+        tech_count: usize,
+    },
+    /// 规则内容未变化，跳过编译与替换
+    Unchanged,
+    /// 加载/编译失败，全局检测器保持不变
+    Failed {
+        /// 错误信息
+        error: String,
+    },
+}
+
+/// 自动更新结果回调
+#[derive(Clone)]
+pub struct RuleUpdateCallback(Arc<dyn Fn(&RuleUpdateEvent) + Send + Sync>);
+
+impl RuleUpdateCallback {
+    /// 创建更新回调
+    pub fn new(callback: impl Fn(&RuleUpdateEvent) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    /// 上报更新结果
+    #[inline]
+    pub fn emit(&self, event: &RuleUpdateEvent) {
+        (self.0)(event)
+    }
+}
+
+impl fmt::Debug for RuleUpdateCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("RuleUpdateCallback")
+    }
+}
+
+/// 规则自动更新策略
+#[derive(Debug, Clone)]
+pub struct AutoUpdatePolicy {
+    /// 更新间隔
+    pub refresh_interval: Duration,
+    /// 随机抖动上限（每次等待 refresh_interval + [0, jitter)）
+    pub jitter: Duration,
+    /// 更新结果回调（可选）
+    pub on_update: Option<RuleUpdateCallback>,
+}
+
+impl Default for AutoUpdatePolicy {
+    fn default() -> Self {
+        Self {
+            refresh_interval: Duration::from_secs(6 * 60 * 60),
+            jitter: Duration::from_secs(5 * 60),
+            on_update: None,
+        }
+    }
+}
+
+impl AutoUpdatePolicy {
+    /// 创建策略（无抖动、无回调）
+    /// 参数：refresh_interval - 更新间隔
+    pub fn new(refresh_interval: Duration) -> Self {
+        Self {
+            refresh_interval,
+            jitter: Duration::ZERO,
+            on_update: None,
+        }
+    }
+
+    /// 设置随机抖动上限（链式）
+    pub fn jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// 设置更新结果回调（链式）
+    pub fn on_update(mut self, callback: impl Fn(&RuleUpdateEvent) + Send + Sync + 'static) -> Self {
+        self.on_update = Some(RuleUpdateCallback::new(callback));
+        self
+    }
+}
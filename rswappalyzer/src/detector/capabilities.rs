@@ -0,0 +1,197 @@
+//! 检测器能力信息
+//! 记录检测器实际使用的规则来源（含启动降级原因）及编译特性开关，便于运维确认当前生效的规则
+//! features() 汇总当前构建开启的全部 cargo 特性
+
+/// 检测器实际使用的规则来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuleSource {
+    /// 内置规则（RuleOrigin::Embedded）
+    Embedded,
+    /// 按配置加载的本地/远程规则（含正常的缓存命中）
+    Configured,
+    /// 本地/远程规则加载失败，降级使用已有缓存
+    CacheFallback,
+    /// 本地/远程规则加载失败，降级使用内置规则
+    EmbeddedFallback,
+    /// 调用方直接提供的规则库（with_rules/with_compiled_lib/from_compiled_pack）
+    Provided,
+}
+
+impl RuleSource {
+    /// 是否为降级来源
+    pub fn is_fallback(self) -> bool {
+        matches!(self, RuleSource::CacheFallback | RuleSource::EmbeddedFallback)
+    }
+}
+
+/// 检测器能力信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectorCapabilities {
+    /// 实际使用的规则来源
+    pub rule_source: RuleSource,
+    /// 降级原因（原始加载错误），未降级时为 None
+    pub fallback_reason: Option<String>,
+    /// 已编译的技术数量
+    pub tech_count: usize,
+    /// 是否编译了内置规则（embedded-rules 特性）
+    pub embedded_rules: bool,
+    /// 是否编译了远程加载（remote-loader 特性）
+    pub remote_loader: bool,
+    /// 是否编译了完整元信息（full-meta 特性）
+    pub full_meta: bool,
+}
+
+/// 编译特性报告
+/// 全部特性均为加法：关闭特性只影响运行期能力（返回 FeatureDisabled 等错误或不填充可选字段），
+/// 不改变公开类型的字段与接口形态，可据此在运行期确认当前二进制实际编译的特性
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureReport {
+    features: Vec<(&'static str, bool)>,
+}
+
+impl FeatureReport {
+    /// 全部已知特性及开启状态（按 Cargo.toml 声明顺序）
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, bool)> + '_ {
+        self.features.iter().copied()
+    }
+
+    /// 已开启的特性名称
+    pub fn enabled(&self) -> Vec<&'static str> {
+        self.iter().filter(|(_, on)| *on).map(|(name, _)| name).collect()
+    }
+
+    /// 指定特性是否开启（未知特性返回 false）
+    /// 参数：name - cargo 特性名称（如 "full-meta"）
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.iter().any(|(feature, on)| on && feature == name)
+    }
+}
+
+impl std::fmt::Display for FeatureReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let items: Vec<String> = self
+            .iter()
+            .map(|(name, on)| format!("{}{}", if on { '+' } else { '-' }, name))
+            .collect();
+        write!(f, "{}", items.join(" "))
+    }
+}
+
+macro_rules! feature_report {
+    ($($name:literal),* $(,)?) => {
+        FeatureReport {
+            features: vec![$(($name, cfg!(feature = $name))),*],
+        }
+    };
+}
+
+/// 当前构建的编译特性报告
+pub fn features() -> FeatureReport {
+    feature_report!(
+        "embedded-rules",
+        "full-meta",
+        "provenance",
+        "async-io",
+        "remote-loader",
+        "cli",
+        "tracing",
+        "compare",
+        "stream",
+        "tower",
+        "pool",
+        "mmap",
+        "decompress",
+        "diagnostics",
+        "yaml-rules",
+        "binary-rules",
+        "scanner",
+        "auto-update",
+        "parallel",
+        "encoding",
+    )
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::test_support::detector;
+    use crate::{CustomConfigBuilder, RuleConfig, RuleOrigin, TechDetector};
+
+    const RULES: &str = r#"{"technologies": { "Acme": { "html": "acme-cms" } }}"#;
+
+    /// 本地规则源配置（独立缓存目录，规则文件不存在时加载失败）
+    fn local_config(name: &str, rules: Option<&str>) -> (RuleConfig, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("rsw-capabilities-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("rules.json");
+        if let Some(rules) = rules {
+            std::fs::write(&path, rules).unwrap();
+        }
+        let config = CustomConfigBuilder::new()
+            .origin(RuleOrigin::LocalFile(path))
+            .cache_dir(dir.join("cache"))
+            .build();
+        (config, dir)
+    }
+
+    #[test]
+    fn test_is_fallback() {
+        assert!(RuleSource::CacheFallback.is_fallback());
+        assert!(RuleSource::EmbeddedFallback.is_fallback());
+        assert!(!RuleSource::Configured.is_fallback());
+        assert!(!RuleSource::Provided.is_fallback());
+    }
+
+    #[test]
+    fn test_provided_capabilities() {
+        let capabilities = detector(RULES).capabilities();
+        assert_eq!(capabilities.rule_source, RuleSource::Provided);
+        assert_eq!(capabilities.fallback_reason, None);
+        assert_eq!(capabilities.tech_count, 1);
+        assert_eq!(capabilities.embedded_rules, cfg!(feature = "embedded-rules"));
+    }
+
+    #[tokio::test]
+    async fn test_configured_local_rules() {
+        let (config, dir) = local_config("configured", Some(RULES));
+        let capabilities = TechDetector::new(config).await.unwrap().capabilities();
+        std::fs::remove_dir_all(dir).unwrap();
+        assert_eq!(capabilities.rule_source, RuleSource::Configured);
+        assert_eq!(capabilities.tech_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_load_failure_without_fallback() {
+        // 无可用缓存时 fallback_to_cache 不生效，返回原始加载错误
+        let (mut config, dir) = local_config("no-fallback", None);
+        config.options.fallback_to_cache = true;
+        let err = TechDetector::new(config).await.err().unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+        assert!(err.to_string().contains("Failed to read raw rule file"), "{}", err);
+    }
+
+    #[cfg(feature = "embedded-rules")]
+    #[tokio::test]
+    async fn test_embedded_fallback() {
+        let (mut config, dir) = local_config("embedded", None);
+        config.options.fallback_to_embedded = true;
+        let capabilities = TechDetector::new(config).await.unwrap().capabilities();
+        std::fs::remove_dir_all(dir).unwrap();
+        assert_eq!(capabilities.rule_source, RuleSource::EmbeddedFallback);
+        assert!(capabilities.fallback_reason.unwrap().contains("Failed to read raw rule file"));
+        assert!(capabilities.tech_count > 1);
+    }
+
+    #[test]
+    fn test_feature_report() {
+        let report = features();
+        assert_eq!(report.is_enabled("embedded-rules"), cfg!(feature = "embedded-rules"));
+        assert!(!report.is_enabled("no-such-feature"));
+        assert_eq!(report.iter().count(), 20);
+        assert!(report.to_string().split(' ').all(|item| item.starts_with(['+', '-'])));
+    }
+}
@@ -0,0 +1,111 @@
+//! 模式测试语料端到端校验
+//! 将引擎层合成的每条最小匹配输入按作用域包装为 URL/Header/Cookie/HTML 输入，逐条跑完整检测，
+//! 未检出对应技术的条目即为"死模式"（被清洗修复/证据网关/结构剪枝误伤，或 Key 无法构造）
+
+use http::header::{HeaderMap, HeaderName, HeaderValue, COOKIE};
+use rswappalyzer_engine::scope_pruner::PruneScope;
+use rswappalyzer_engine::{CorpusEntry, PatternCorpus};
+use serde::{Deserialize, Serialize};
+
+use super::detector::TechDetector;
+
+/// 语料校验报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CorpusReport {
+    /// 已校验（可合成输入）的条目数
+    pub checked: usize,
+    /// 无法合成输入而跳过的条目数
+    pub skipped: usize,
+    /// 未检出对应技术的条目（死模式）
+    pub unreachable: Vec<CorpusEntry>,
+}
+
+impl CorpusReport {
+    /// 是否全部可达
+    pub fn is_clean(&self) -> bool {
+        self.unreachable.is_empty()
+    }
+}
+
+/// 按作用域包装后的检测输入
+#[derive(Default)]
+struct CorpusInput {
+    headers: HeaderMap,
+    urls: Vec<String>,
+    body: String,
+}
+
+/// 将语料条目包装为对应作用域的检测输入
+/// 返回：None 表示无法构造（Header/Cookie 名称或取值非法）
+fn build_input(entry: &CorpusEntry, input: &str) -> Option<CorpusInput> {
+    let mut out = CorpusInput::default();
+    match entry.scope {
+        PruneScope::Url => out.urls.push(input.to_string()),
+        PruneScope::Html => out.body = input.to_string(),
+        PruneScope::Script => out.body = format!("<script src={}></script>", quote_attr(input)),
+        PruneScope::Title => out.body = format!("<html><head><title>{}</title></head></html>", escape_text(input)),
+        // 注释文本不能包含注释结束符
+        PruneScope::Comment if input.contains("-->") => return None,
+        PruneScope::Comment => out.body = format!("<html><body><!--{}--></body></html>", input),
+        PruneScope::Meta => {
+            out.body = format!("<meta name={} content={}>", quote_attr(&entry.key), quote_attr(input))
+        }
+        PruneScope::Header => {
+            let name = HeaderName::from_bytes(entry.key.as_bytes()).ok()?;
+            out.headers.insert(name, HeaderValue::from_str(input).ok()?);
+        }
+        PruneScope::Cookie => {
+            let cookie = format!("{}={}", entry.key, input);
+            out.headers.insert(COOKIE, HeaderValue::from_str(&cookie).ok()?);
+        }
+        PruneScope::Hostname => out.urls.push(format!("https://{}/", input)),
+        // 任意 CSS 选择器无法通用合成匹配元素
+        PruneScope::Dom => return None,
+        // JS 全局变量不属于 HTTP 响应输入
+        PruneScope::Js => return None,
+        // 证书颁发者 / DNS 记录 / favicon 哈希不属于 HTTP 响应输入
+        PruneScope::CertIssuer | PruneScope::Dns | PruneScope::FaviconHash => return None,
+    }
+    Some(out)
+}
+
+/// 转义 HTML 文本内容（标题提取时按实体解码还原）
+fn escape_text(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// 选择取值中未出现的引号包裹 HTML 属性值
+fn quote_attr(value: &str) -> String {
+    if value.contains('"') {
+        format!("'{}'", value)
+    } else {
+        format!("\"{}\"", value)
+    }
+}
+
+impl TechDetector {
+    /// 对模式测试语料逐条跑完整检测，校验每个模式端到端可达
+    /// 参数：corpus - 模式测试语料（通常由 compiled_library().pattern_corpus() 生成）
+    ///
+    /// 返回：校验报告（unreachable 按语料顺序排列）
+    pub fn verify_corpus(&self, corpus: &PatternCorpus) -> CorpusReport {
+        let mut report = CorpusReport::default();
+        for entry in &corpus.entries {
+            let Some(input) = entry.input.as_deref() else {
+                report.skipped += 1;
+                continue;
+            };
+            report.checked += 1;
+
+            let detected = build_input(entry, input).is_some_and(|input| {
+                let urls: Vec<&str> = input.urls.iter().map(String::as_str).collect();
+                self.detect(&input.headers, &urls, input.body.as_bytes())
+                    .is_ok_and(|result| result.technologies.iter().any(|t| t.name == entry.tech))
+            });
+            if !detected {
+                report.unreachable.push(entry.clone());
+            }
+        }
+        report
+    }
+}
@@ -2,30 +2,244 @@
 //! 技术检测器核心
 //! 核心职责：
 //! 1. 规则库加载与编译（内置/本地/远程规则）
-//! 2. 多维度技术检测（URL/Header/Cookie/HTML/Script/Meta）
+//! 2. 多维度技术检测（URL/Header/Cookie/HTML/Script/Meta/Title/DOM/JS/证书颁发者/DNS/Favicon）
 //! 3. 检测结果聚合与关联推导
 //! 4. 提供基础检测/带耗时统计/HashMap输入等多版本接口
 
 use crate::analyzer::{
-    cookie::CookieAnalyzer, header::HeaderAnalyzer, html::HtmlAnalyzer, meta::MetaAnalyzer,
-    script::ScriptAnalyzer, url::UrlAnalyzer,
+    extract_bytes_tokens, extract_tokens, banner::BannerAnalyzer, cert::CertAnalyzer, cookie::CookieAnalyzer, dns::DnsAnalyzer, dom::{DomAnalyzer, DomIndex},
+    favicon::FaviconAnalyzer, header::HeaderAnalyzer,
+    js::JsAnalyzer,
+    hostname::HostnameAnalyzer, html::HtmlAnalyzer, meta::MetaAnalyzer, script::ScriptAnalyzer,
+    comment::CommentAnalyzer, title::TitleAnalyzer, url::UrlAnalyzer,
 };
 use crate::error::{RswResult, RswappalyzerError};
 use crate::result::detect_result::Technology;
-use crate::utils::extractor::html_input_guard::HtmlInputGuard;
-use crate::utils::{DetectionUpdater, HeaderConverter};
-use crate::{DetectResult, HtmlExtractor, RuleConfig, RuleOrigin};
+use crate::result::slug::resolve_slug;
+use crate::result::warning::DetectWarning;
+use crate::analyzer::script::ScriptHostOutcome;
+use crate::config::generator::is_generator_meta;
+use crate::utils::extractor::html_extractor::ExtractResult;
+use crate::utils::extractor::html_input_guard::{HtmlInputGuard, SanitizeStats};
+use crate::utils::content_encoding::{decompress_body, ContentEncoding};
+use crate::utils::content_type::{decode_body_bytes, transcode_body_bytes};
+use crate::utils::favicon::favicon_hash;
+use crate::utils::matched_scopes::MatchedScopes;
+use crate::utils::telemetry::stage_span;
+use crate::utils::{BodyKind, DetectionUpdater, HeaderConverter, PreparedHeaders};
+use crate::{DetectResult, ExtractLimits, ExtractStats, RuleConfig, RuleOrigin};
 // 仅在embedded-rules开启时导入rswappalyzer_rules
 #[cfg(feature = "embedded-rules")]
 use crate::rswappalyzer_rules;
-use crate::RuleLoader;
-use http::header::{HeaderMap, HeaderName, HeaderValue};
-use rswappalyzer_engine::{CompiledRuleLibrary, RuleIndexer, RuleLibrary, RuleLibraryIndex};
-use rustc_hash::FxHashMap;
+use crate::{RuleCacheManager, RuleLoader, StalePolicy};
+use super::capabilities::{DetectorCapabilities, RuleSource};
+use super::input::{DetectInput, SupplementalInput};
+use super::options::{DetectBuilder, DetectOptions, ScopeMask, BODY_SCOPES};
+use super::progressive::DetectStage;
+use super::stats::SourceCounters;
+use super::evidence::{EvidenceInput, EvidenceSampler};
+use super::post_process::PostProcessorPipeline;
+use super::triage::TriageIndex;
+use http::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_ENCODING, CONTENT_TYPE};
+use rswappalyzer_engine::scope_pruner::PruneScope;
+use rswappalyzer_engine::{thread_regex_budget_exceeded, CompileOptions, PatternValidation, CompiledRuleLibrary, EvidenceTokenExport, PatternCollisionReport, RuleIndexer, RuleLibrary, RuleLibraryIndex, TechMeta, LifecycleReport, today_utc};
+#[cfg(feature = "parallel")]
+use rswappalyzer_engine::charge_thread_regex_budget_exceeded;
+use once_cell::sync::OnceCell;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::borrow::Cow;
 use std::sync::Arc;
 use std::time::Instant;
 
+/// parallel 特性下并行执行各作用域阶段的最小 HTML 长度（小响应体任务调度开销高于收益）
+#[cfg(feature = "parallel")]
+const PARALLEL_MIN_HTML_LEN: usize = 64 * 1024;
+
+/// 单次检测命中结果（技术名 -> (置信度, 版本)）
+pub(super) type Detected = FxHashMap<String, (u8, Option<String>)>;
+
+/// 多维度分析输入
+pub(super) struct ScopeInput<'a> {
+    /// 规则库（目标查询时为收缩后的候选规则库）
+    pub(super) lib: &'a CompiledRuleLibrary,
+    pub(super) headers: &'a PreparedHeaders,
+    pub(super) prepared: &'a PreparedInput<'a>,
+    pub(super) supplemental: &'a SupplementalInput<'a>,
+    pub(super) options: &'a DetectOptions,
+    pub(super) deadline: Option<Instant>,
+}
+
+/// 作用域分析结果
+#[derive(Default)]
+pub(super) struct ScopeOutcome {
+    pub(super) detected: Detected,
+    /// 命中作用域记录
+    pub(super) scopes: MatchedScopes,
+    /// 待合并的 generator 命中结果（generator 防伪策略生效时）
+    pub(super) generator_hits: Detected,
+    /// 主机策略下的 Script 分析结果
+    pub(super) script_outcome: ScriptHostOutcome,
+}
+
+impl ScopeOutcome {
+    /// 合并并行分析的另一组结果（命中结果按 DetectionUpdater 规则合并，命中作用域取并集）
+    #[cfg(feature = "parallel")]
+    fn merge(&mut self, other: ScopeOutcome) {
+        for (tech_name, (confidence, version)) in other.detected {
+            DetectionUpdater::update(&mut self.detected, &tech_name, Some(confidence), version);
+        }
+        for (tech_name, (confidence, version)) in other.generator_hits {
+            DetectionUpdater::update(&mut self.generator_hits, &tech_name, Some(confidence), version);
+        }
+        self.scopes.merge(other.scopes);
+    }
+}
+
+/// 重借用观察者（传给下一段流水线后仍可继续使用）
+fn reborrow<'a>(observer: &'a mut Option<&mut dyn StageObserver>) -> Option<&'a mut dyn StageObserver> {
+    match observer {
+        Some(observer) => Some(&mut **observer),
+        None => None,
+    }
+}
+
+/// 检测阶段观察者（追踪/渐进式检测/目标查询通过共享作用域流水线获取阶段事件，默认空实现）
+pub(super) trait StageObserver {
+    /// 阶段开始前调用（detected 为阶段前的检测结果）
+    fn stage_started(&mut self, _stage: DetectStage, _detected: &Detected) {}
+
+    /// 阶段完成后调用（input 为阶段输入，可复用预提取的令牌）
+    fn stage_finished(&mut self, _stage: DetectStage, _detected: &Detected, _input: &ScopeInput<'_>) {}
+
+    /// 是否跳过剩余阶段（目标查询命中后提前结束）
+    fn is_done(&self) -> bool {
+        false
+    }
+}
+
+/// 预处理检测输入：URL 与响应体各输入的令牌集合（首次使用时提取，单次检测内只提取一次）
+/// 各分析器、诊断快照直接复用同一份令牌集合；Header/Cookie 令牌由 PreparedHeaders 提供
+pub(crate) struct PreparedInput<'a> {
+    /// 检测的URL列表
+    pub urls: &'a [&'a str],
+    /// 按 Content-Type 路由后的响应体
+    pub body: &'a PreparedBody<'a>,
+    url_tokens: OnceCell<FxHashSet<String>>,
+    html_tokens: OnceCell<FxHashSet<String>>,
+    script_src_tokens: OnceCell<FxHashSet<String>>,
+    script_body_tokens: OnceCell<FxHashSet<String>>,
+    inline_script_tokens: OnceCell<FxHashSet<String>>,
+    meta_tokens: OnceCell<FxHashSet<String>>,
+    title_tokens: OnceCell<FxHashSet<String>>,
+    comment_tokens: OnceCell<FxHashSet<String>>,
+}
+
+impl<'a> PreparedInput<'a> {
+    pub(crate) fn new(urls: &'a [&'a str], body: &'a PreparedBody<'a>) -> Self {
+        Self {
+            urls,
+            body,
+            url_tokens: OnceCell::new(),
+            html_tokens: OnceCell::new(),
+            script_src_tokens: OnceCell::new(),
+            script_body_tokens: OnceCell::new(),
+            inline_script_tokens: OnceCell::new(),
+            meta_tokens: OnceCell::new(),
+            title_tokens: OnceCell::new(),
+            comment_tokens: OnceCell::new(),
+        }
+    }
+
+    /// URL 令牌
+    pub(crate) fn url_tokens(&self) -> &FxHashSet<String> {
+        self.url_tokens.get_or_init(|| extract_tokens(self.urls))
+    }
+
+    /// HTML 内容令牌
+    pub(crate) fn html_tokens(&self) -> &FxHashSet<String> {
+        self.html_tokens
+            .get_or_init(|| extract_tokens(std::iter::once(self.body.html.as_ref())))
+    }
+
+    /// 脚本地址拼接串令牌
+    pub(crate) fn script_src_tokens(&self) -> &FxHashSet<String> {
+        self.script_src_tokens
+            .get_or_init(|| extract_tokens(std::iter::once(self.body.extract.script_src_combined.as_str())))
+    }
+
+    /// 脚本内容令牌（仅 JS 资源）
+    pub(crate) fn script_body_tokens(&self) -> &FxHashSet<String> {
+        self.script_body_tokens
+            .get_or_init(|| extract_bytes_tokens(&self.body.script))
+    }
+
+    /// 内联脚本内容令牌
+    pub(crate) fn inline_script_tokens(&self) -> &FxHashSet<String> {
+        self.inline_script_tokens
+            .get_or_init(|| extract_tokens(std::iter::once(self.body.extract.inline_scripts.as_str())))
+    }
+
+    /// Meta 内容令牌（全部标签）
+    pub(crate) fn meta_tokens(&self) -> &FxHashSet<String> {
+        self.meta_tokens.get_or_init(|| {
+            extract_tokens(self.body.extract.meta_tags.iter().map(|(_, content)| content.as_str()))
+        })
+    }
+
+    /// 页面标题令牌
+    pub(crate) fn title_tokens(&self) -> &FxHashSet<String> {
+        self.title_tokens
+            .get_or_init(|| extract_tokens(std::iter::once(self.body.extract.title.as_str())))
+    }
+
+    /// HTML 注释令牌
+    pub(crate) fn comment_tokens(&self) -> &FxHashSet<String> {
+        self.comment_tokens
+            .get_or_init(|| extract_tokens(std::iter::once(self.body.extract.comments.as_str())))
+    }
+}
+
+/// 按 Content-Type 路由后的响应体
+pub(crate) struct PreparedBody<'a> {
+    /// 响应体类型
+    pub kind: BodyKind,
+    /// 送入 HTML 内容模式的文本（HTML：经输入守卫的文档；CSS：样式表原文；其余为空）
+    pub html: Cow<'a, str>,
+    /// HTML 提取结果（仅 HTML 文档）
+    pub extract: ExtractResult,
+    /// 作为脚本内容送入 Script 分析器的字节（仅 JS 资源；非合法 UTF-8 且无可识别 charset 时保留原始字节）
+    pub script: Cow<'a, [u8]>,
+    /// 预处理告警（解码降级/HTML 被拒绝/截断）
+    pub warnings: Vec<DetectWarning>,
+}
+
+impl PreparedBody<'_> {
+    /// 空响应体（未经预处理）
+    pub fn empty(kind: BodyKind) -> Self {
+        Self {
+            kind,
+            html: Cow::Borrowed(""),
+            extract: ExtractResult::default(),
+            script: Cow::Borrowed(b""),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// 是否为有效 HTML 文档（执行 script src / meta 提取分析）
+    pub fn is_document(&self) -> bool {
+        self.kind == BodyKind::Html && !self.html.is_empty()
+    }
+
+    /// 并入调用方采集的外部脚本地址（与页面 `<script src>` 一并匹配）
+    pub fn append_script_srcs(&mut self, script_srcs: &[String]) {
+        for src in script_srcs.iter().filter(|src| !src.is_empty()) {
+            self.extract.script_srcs.push(src.clone());
+            self.extract.script_src_combined.push_str(src);
+            self.extract.script_src_combined.push('\n');
+        }
+    }
+}
+
 /// 技术检测器核心结构体
 /// 设计说明：
 /// - compiled_lib: 编译后的规则库（Arc共享，避免重复编译）
@@ -36,13 +250,91 @@ pub struct TechDetector {
     /// 编译后的规则库（Arc保证多线程共享）
     compiled_lib: Arc<CompiledRuleLibrary>,
     /// 规则配置（保留配置上下文）
-    #[allow(dead_code)]
-    config: RuleConfig,
+    pub(super) config: RuleConfig,
     /// 规则库索引（可选，用于调试和扩展）
     pub rule_index: Option<Arc<RuleLibraryIndex>>,
+    /// 实际使用的规则来源
+    rule_source: RuleSource,
+    /// 降级原因（启动时规则加载失败的原始错误）
+    fallback_reason: Option<String>,
+    /// 按规则源的检出计数（克隆间共享）
+    pub(super) source_counters: Arc<SourceCounters>,
+    /// 分诊门槛索引（首次分诊时构建，克隆间共享）
+    pub(super) triage_index: Arc<TriageIndex>,
+    /// 证据采样计数器（克隆间共享）
+    pub(super) evidence_sampler: Arc<EvidenceSampler>,
+    /// DOM 选择器索引（首次 DOM 分析时构建，克隆之间共享）
+    pub(super) dom_index: Arc<DomIndex>,
+    /// 结果后处理管道（按注册顺序执行）
+    pub(super) post_processors: PostProcessorPipeline,
 }
 
 impl TechDetector {
+    /// 编译规则库索引（按配置的分类映射与编译选项）
+    /// 参数：
+    /// - rule_index: 规则库索引
+    /// - config: 规则配置
+    ///
+    /// 返回：编译后的规则库 | 编译错误
+    fn compile_index(rule_index: &RuleLibraryIndex, config: &RuleConfig) -> RswResult<CompiledRuleLibrary> {
+        let _span = stage_span!("rule.compile", techs = rule_index.tech_info_map.len());
+        let compiled_lib = RuleIndexer::build_compiled_library_with_category_map(
+            rule_index,
+            config.options.category_map(),
+            &config.options.compile_options(),
+        )?;
+        Ok(compiled_lib)
+    }
+
+    /// 加载已持久化的编译产物（规则内容与编译配置均未变化时）
+    /// 未开启 persist_compiled、注册了自定义匹配类型或指纹计算失败时不使用持久化产物
+    /// 参数：
+    /// - rule_lib: 运行时加载的规则库
+    /// - config: 规则配置
+    ///
+    /// 返回：(规则指纹（不持久化时为 None）, 已编译规则库（未命中时为 None）)
+    fn load_persisted_compiled(
+        rule_lib: &RuleLibrary,
+        config: &RuleConfig,
+    ) -> (Option<u64>, Option<CompiledRuleLibrary>) {
+        if !config.options.persist_compiled || !config.options.matchers.is_empty() {
+            return (None, None);
+        }
+        let fingerprint = match RuleCacheManager::rules_fingerprint(config, rule_lib) {
+            Ok(fingerprint) => fingerprint,
+            Err(e) => {
+                log::warn!("Failed to fingerprint rules, compiled library will not be persisted | Error: {}", e);
+                return (None, None);
+            }
+        };
+        match RuleCacheManager::load_compiled(config, fingerprint) {
+            Ok(Some(compiled_lib)) => {
+                rswappalyzer_engine::set_regex_limits(config.options.regex_limits);
+                log::debug!("Rules unchanged, loaded persisted compiled library | Fingerprint: {:016x}", fingerprint);
+                (Some(fingerprint), Some(compiled_lib))
+            }
+            Ok(None) => (Some(fingerprint), None),
+            Err(e) => {
+                log::warn!("Persisted compiled library unusable, recompiling | Error: {}", e);
+                (Some(fingerprint), None)
+            }
+        }
+    }
+
+    /// 持久化运行时编译的规则库（失败仅告警，不影响检测器创建）
+    fn persist_compiled(config: &RuleConfig, fingerprint: Option<u64>, compiled_lib: &CompiledRuleLibrary) {
+        let Some(fingerprint) = fingerprint else {
+            return;
+        };
+        if let Err(e) = RuleCacheManager::save_compiled(config, fingerprint, compiled_lib) {
+            log::warn!(
+                "Failed to persist compiled library: {} - {}",
+                RuleCacheManager::compiled_cache_path(config).display(),
+                e
+            );
+        }
+    }
+
     /// 使用内存中的RuleLibrary创建检测器
     /// 适用场景：预加载规则库后手动创建检测器
     /// 参数：
@@ -53,30 +345,103 @@ impl TechDetector {
         // 构建规则库索引
         let rule_index = RuleLibraryIndex::from_rule_library(&rule_lib)?;
         // 编译规则库
-        let compiled_lib = RuleIndexer::build_compiled_library(&rule_index, None)?;
+        let compiled_lib = Self::compile_index(&rule_index, &config)?;
 
         Ok(Self {
             compiled_lib: Arc::new(compiled_lib),
             config,
             rule_index: Some(Arc::new(rule_index)),
+            rule_source: RuleSource::Provided,
+            fallback_reason: None,
+            source_counters: Arc::default(),
+            triage_index: Arc::default(),
+            evidence_sampler: Arc::default(),
+            dom_index: Arc::default(),
+            post_processors: PostProcessorPipeline::default(),
         })
     }
 
-    /// 使用内置规则创建检测器（仅embedded-rules特性开启时可用）
+    /// 使用内置规则创建检测器（需开启embedded-rules特性）
     /// 特性：
     /// 1. 零耗时：使用预编译的内置规则库
-    /// 2. 特性守卫：未开启特性时编译报错
+    /// 2. 特性守卫：未开启特性时返回 FeatureDisabled 错误（接口始终存在，特性仅做加法）
+    ///
     /// 参数：config - 规则配置
     /// 返回：检测器实例 | 错误
-    #[cfg(feature = "embedded-rules")]
     pub fn with_embedded_rules(config: RuleConfig) -> RswResult<Self> {
+        #[cfg(not(feature = "embedded-rules"))]
+        {
+            let _ = config;
+            Err(RswappalyzerError::FeatureDisabled(
+                "embedded-rules feature is disabled, cannot use embedded rule library. Please enable this feature or use local/remote rules.".to_string()
+            ))
+        }
+        #[cfg(feature = "embedded-rules")]
+        {
+            Self::embedded(config)
+        }
+    }
+
+    #[cfg(feature = "embedded-rules")]
+    fn embedded(config: RuleConfig) -> RswResult<Self> {
+        let compiled_lib = Self::apply_compile_options(
+            rswappalyzer_rules::EMBEDDED_COMPILED_LIB.clone(),
+            &config,
+        );
+        Self::check_broken_patterns(&compiled_lib, &config)?;
         Ok(Self {
-            compiled_lib: rswappalyzer_rules::EMBEDDED_COMPILED_LIB.clone(),
+            compiled_lib,
             config,
             rule_index: None,
+            rule_source: RuleSource::Embedded,
+            fallback_reason: None,
+            source_counters: Arc::default(),
+            triage_index: Arc::default(),
+            evidence_sampler: Arc::default(),
+            dom_index: Arc::default(),
+            post_processors: PostProcessorPipeline::default(),
         })
     }
 
+    /// 按配置调整预编译规则库
+    /// 默认配置下直接复用共享实例（零拷贝），仅在选项与默认值不同时克隆后调整
+    /// 未构建准入字面量自动机的规则库（调用方自行反序列化）在此构建
+    fn apply_compile_options(
+        mut compiled_lib: Arc<CompiledRuleLibrary>,
+        config: &RuleConfig,
+    ) -> Arc<CompiledRuleLibrary> {
+        let options = config.options.compile_options();
+        if options == CompileOptions::default() {
+            rswappalyzer_engine::set_regex_limits(options.regex_limits);
+            if !compiled_lib.has_literal_prefilters() {
+                Arc::make_mut(&mut compiled_lib).build_literal_prefilters();
+            }
+            return compiled_lib;
+        }
+        let mut lib = (*compiled_lib).clone();
+        let changed = lib.apply_compile_options(&options);
+        log::debug!("Applied compile options to precompiled library | Changed patterns: {}", changed);
+        lib.apply_lifecycle_policy(options.expired_rules, &today_utc());
+        // 预编译规则库：仅记录失败模式，严格模式的判定由调用方处理
+        if options.pattern_validation != PatternValidation::Off {
+            let _ = lib.validate_patterns(PatternValidation::Lenient);
+        }
+        // 模式调整后准入字面量可能变化，重新构建自动机
+        lib.build_literal_prefilters();
+        Arc::new(lib)
+    }
+
+    /// 严格模式下检查预编译规则库是否存在编译失败的模式
+    fn check_broken_patterns(compiled_lib: &CompiledRuleLibrary, config: &RuleConfig) -> RswResult<()> {
+        if config.options.pattern_validation != PatternValidation::Strict {
+            return Ok(());
+        }
+        match compiled_lib.broken_patterns_error() {
+            Some(err) => Err(err.into()),
+            None => Ok(()),
+        }
+    }
+
     /// 使用已编译的规则库创建检测器
     /// 适用场景：自定义编译规则库后直接使用
     /// 参数：
@@ -89,58 +454,196 @@ impl TechDetector {
         rule_index: RuleLibraryIndex,
         config: RuleConfig,
     ) -> Self {
+        if let Err(e) = compiled_lib.verify_compatibility() {
+            log::error!("Compiled rule library is incompatible with this engine | {}", e);
+        }
+        let compiled_lib = Self::apply_compile_options(Arc::new(compiled_lib), &config);
+        if let Err(e) = Self::check_broken_patterns(&compiled_lib, &config) {
+            log::error!("Strict pattern validation failed | {}", e);
+        }
         Self {
-            compiled_lib: Arc::new(compiled_lib),
+            compiled_lib,
             config,
             rule_index: Some(Arc::new(rule_index)),
+            rule_source: RuleSource::Provided,
+            fallback_reason: None,
+            source_counters: Arc::default(),
+            triage_index: Arc::default(),
+            evidence_sampler: Arc::default(),
+            dom_index: Arc::default(),
+            post_processors: PostProcessorPipeline::default(),
         }
     }
 
+    /// 从预编译规则包文件创建检测器
+    /// 开启 mmap 特性时以只读映射加载（多进程共享页缓存），否则整文件读取，取舍见 `rule::pack`
+    /// 参数：
+    /// - path: 规则包路径（由 rule::write_compiled_pack 生成）
+    /// - config: 规则配置
+    ///
+    /// 返回：检测器实例 | 错误
+    pub fn from_compiled_pack(path: impl AsRef<std::path::Path>, config: RuleConfig) -> RswResult<Self> {
+        #[cfg(feature = "mmap")]
+        let compiled_lib = crate::rule::map_compiled_pack(path)?.library()?;
+        #[cfg(not(feature = "mmap"))]
+        let compiled_lib = Arc::new(crate::rule::read_compiled_pack(path)?);
+
+        let compiled_lib = Self::apply_compile_options(compiled_lib, &config);
+        Self::check_broken_patterns(&compiled_lib, &config)?;
+        Ok(Self {
+            compiled_lib,
+            config,
+            rule_index: None,
+            rule_source: RuleSource::Provided,
+            fallback_reason: None,
+            source_counters: Arc::default(),
+            triage_index: Arc::default(),
+            evidence_sampler: Arc::default(),
+            dom_index: Arc::default(),
+            post_processors: PostProcessorPipeline::default(),
+        })
+    }
+
+    /// 从离线编译产物（rswappalyzer-compile 生成的规则包）创建检测器，使用默认配置
+    /// 规则包由更新版本引擎编译（引擎版本/格式版本不兼容）时返回 IncompatibleEngine 错误
+    /// 参数：path - 规则包路径
+    ///
+    /// 返回：检测器实例 | 错误
+    pub fn with_compiled_artifact(path: impl AsRef<std::path::Path>) -> RswResult<Self> {
+        let detector = Self::from_compiled_pack(path, RuleConfig::default())?;
+        detector.verify_compatibility()?;
+        Ok(detector)
+    }
+
+    /// 校验已加载的规则库与当前引擎版本是否兼容
+    /// 由更新版本引擎编译的规则库（预编译规则包/手动注入）在此显式失败，而非表现为检测结果漂移
+    /// 返回：兼容 | IncompatibleEngine 错误
+    pub fn verify_compatibility(&self) -> RswResult<()> {
+        Ok(self.compiled_lib.verify_compatibility()?)
+    }
+
+    /// 获取编译后的规则库（只读共享）
+    /// 适用场景：外部组件读取证据索引/分类映射等编译产物
+    pub fn compiled_library(&self) -> &Arc<CompiledRuleLibrary> {
+        &self.compiled_lib
+    }
+
+    /// 获取技术展示元信息（描述/官网/图标/CPE等）
+    /// 内置规则包仅在 full-meta 特性开启时携带元信息；远程/本地规则始终携带
+    pub fn tech_meta(&self, tech_name: &str) -> Option<&TechMeta> {
+        self.compiled_lib.meta_of(tech_name)
+    }
+
+    /// 导出当前规则库的证据token（供外部预过滤组件使用）
+    /// 返回：按作用域分组的有序token集合，可序列化为紧凑文本/JSON
+    pub fn export_evidence_tokens(&self) -> EvidenceTokenExport {
+        self.compiled_lib.export_evidence_tokens()
+    }
+
+    /// 生成跨技术模式冲突报告（相同模式/共享证据）
+    /// 参数：min_techs - 至少被多少个技术共享才计入报告
+    ///
+    /// 返回：冲突报告
+    pub fn collision_report(&self, min_techs: usize) -> PatternCollisionReport {
+        self.compiled_lib.collision_report(min_techs)
+    }
+
+    /// 生成规则生命周期校验报告（废弃/过期/有效期格式非法的技术）
+    /// 以当前UTC日期为基准，已按 ExpiredRulePolicy::Skip 剔除的技术标记为 skipped
+    pub fn lifecycle_report(&self) -> LifecycleReport {
+        self.compiled_lib.lifecycle_report(&today_utc())
+    }
+
     /// 创建技术检测器（基础版，无耗时日志）
     /// 支持规则来源：
     /// 1. Embedded：内置规则（需开启embedded-rules特性）
     /// 2. LocalFile/RemoteOfficial/RemoteCustom：运行时加载
+    ///
+    /// 规则加载（I/O）在当前任务执行；索引构建与编译（CPU 密集）在 tokio 运行时内时交给阻塞线程池，
+    /// 不占用异步工作线程
     /// 参数：config - 规则配置
     /// 返回：检测器实例 | 错误
     pub async fn new(config: RuleConfig) -> RswResult<Self> {
         match &config.origin {
             // Embedded模式 - 特性守卫 + 降级处理
-            RuleOrigin::Embedded => {
-                #[cfg(feature = "embedded-rules")]
-                {
-                    Self::with_embedded_rules(config)
-                }
-                // 关闭特性时，返回明确的错误
-                #[cfg(not(feature = "embedded-rules"))]
-                {
-                    return Err(RswappalyzerError::FeatureDisabled(
-                        "embedded-rules feature is disabled, cannot use embedded rule library. Please enable this feature or use local/remote rules.".to_string()
-                    ));
-                }
-            }
+            RuleOrigin::Embedded => compile_off_thread(move || Self::with_embedded_rules(config)).await,
 
             // 运行时加载模式（本地/远程规则）
             RuleOrigin::LocalFile(_) | RuleOrigin::RemoteOfficial | RuleOrigin::RemoteCustom(_) => {
-                // 1. 加载规则库（优先从缓存加载）
-                let rule_loader = RuleLoader::new();
-                let rule_lib = rule_loader.load(&config).await?;
+                // 1. 加载规则库（优先从缓存加载，失败时按配置降级）
+                match Self::load_runtime_rules(&config).await {
+                    Ok(loaded) => compile_off_thread(move || Self::from_loaded(loaded, config)).await,
+                    Err(e) => compile_off_thread(move || Self::embedded_fallback(config, e)).await,
+                }
+            }
+        }
+    }
 
-                // 2. 构建规则库索引
-                let rule_index = RuleLibraryIndex::from_rule_library(&rule_lib)?;
+    /// 重新加载规则，规则指纹与上次相同时跳过编译（热更新使用）
+    /// 内置规则与降级结果不计算指纹，总是返回新检测器
+    /// 参数：
+    /// - config: 规则配置
+    /// - previous: 上次加载的规则指纹
+    ///
+    /// 返回：(新检测器（规则未变化时为 None）, 本次规则指纹) | 错误
+    pub(super) async fn new_if_changed(
+        config: RuleConfig,
+        previous: Option<u64>,
+    ) -> RswResult<(Option<Self>, Option<u64>)> {
+        if matches!(config.origin, RuleOrigin::Embedded) {
+            return Ok((Some(Self::new(config).await?), None));
+        }
+        let loaded = match Self::load_runtime_rules(&config).await {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                let detector = compile_off_thread(move || Self::embedded_fallback(config, e)).await?;
+                return Ok((Some(detector), None));
+            }
+        };
+        compile_off_thread(move || {
+            let fingerprint = RuleCacheManager::rules_fingerprint(&config, &loaded.0).ok();
+            if fingerprint.is_some() && fingerprint == previous {
+                return Ok((None, fingerprint));
+            }
+            Ok((Some(Self::from_loaded(loaded, config)?), fingerprint))
+        })
+        .await
+    }
 
-                // 3. 编译规则库
-                let compiled_lib = RuleIndexer::build_compiled_library(
-                    &rule_index,
-                    Some("data/categories_data.json"),
-                )?;
+    /// 由已加载的运行时规则创建检测器（同步，CPU 密集）
+    /// 参数：
+    /// - loaded: (规则库, 实际规则来源, 降级原因)
+    /// - config: 规则配置
+    ///
+    /// 返回：检测器实例 | 错误
+    fn from_loaded(loaded: (RuleLibrary, RuleSource, Option<String>), config: RuleConfig) -> RswResult<Self> {
+        let (rule_lib, rule_source, fallback_reason) = loaded;
 
-                Ok(Self {
-                    compiled_lib: Arc::new(compiled_lib),
-                    config,
-                    rule_index: Some(Arc::new(rule_index)),
-                })
+        // 2. 规则未变化时直接加载已持久化的编译产物
+        let (fingerprint, persisted) = Self::load_persisted_compiled(&rule_lib, &config);
+        let (compiled_lib, rule_index) = match persisted {
+            Some(compiled_lib) => (compiled_lib, None),
+            None => {
+                // 3. 构建规则库索引并编译
+                let rule_index = RuleLibraryIndex::from_rule_library(&rule_lib)?;
+                let compiled_lib = Self::compile_index(&rule_index, &config)?;
+                Self::persist_compiled(&config, fingerprint, &compiled_lib);
+                (compiled_lib, Some(Arc::new(rule_index)))
             }
-        }
+        };
+
+        Ok(Self {
+            compiled_lib: Arc::new(compiled_lib),
+            config,
+            rule_index,
+            rule_source,
+            fallback_reason,
+            source_counters: Arc::default(),
+            triage_index: Arc::default(),
+            evidence_sampler: Arc::default(),
+            dom_index: Arc::default(),
+            post_processors: PostProcessorPipeline::default(),
+        })
     }
 
     /// 创建技术检测器（带详细耗时日志版）
@@ -154,18 +657,8 @@ impl TechDetector {
         match &config.origin {
             // Embedded模式 - 特性守卫 + 降级处理
             RuleOrigin::Embedded => {
-                #[cfg(feature = "embedded-rules")]
-                {
-                    log::info!("Using rswappalyzer embedded rule library");
-                    Self::with_embedded_rules(config)
-                }
-                // 关闭特性时，返回明确的错误
-                #[cfg(not(feature = "embedded-rules"))]
-                {
-                    return Err(RswappalyzerError::FeatureDisabled(
-                        "embedded-rules feature is disabled, cannot use embedded rule library. Please enable this feature or use local/remote rules.".to_string()
-                    ));
-                }
+                log::info!("Using rswappalyzer embedded rule library");
+                Self::with_embedded_rules(config)
             }
 
             // 运行时加载模式（带详细日志）
@@ -173,10 +666,13 @@ impl TechDetector {
                 log::info!("Using runtime rule library, starting loading process");
                 let total_start = Instant::now();
 
-                // 1. 加载规则库（优先从缓存加载）
-                let rule_loader = RuleLoader::new();
+                // 1. 加载规则库（优先从缓存加载，失败时按配置降级）
                 let rule_lib_load_start = Instant::now();
-                let rule_lib = rule_loader.load(&config).await?;
+                let (rule_lib, rule_source, fallback_reason) =
+                    match Self::load_runtime_rules(&config).await {
+                        Ok(loaded) => loaded,
+                        Err(e) => return Self::embedded_fallback(config, e),
+                    };
                 let rule_lib_load_cost = rule_lib_load_start.elapsed();
                 log::info!(
                     "[Stage 1] Rule library loaded | Time: {}ms | Tech rule count: {}",
@@ -184,6 +680,28 @@ impl TechDetector {
                     rule_lib.core_tech_map.len()
                 );
 
+                // 规则未变化时直接加载已持久化的编译产物
+                let (fingerprint, persisted) = Self::load_persisted_compiled(&rule_lib, &config);
+                if let Some(compiled_lib) = persisted {
+                    log::info!(
+                        "[Stage 2-3] Persisted CompiledRuleLibrary loaded | Time: {}ms | Compiled tech count: {}",
+                        total_start.elapsed().as_millis(),
+                        compiled_lib.tech_patterns.len()
+                    );
+                    return Ok(Self {
+                        compiled_lib: Arc::new(compiled_lib),
+                        config,
+                        rule_index: None,
+                        rule_source,
+                        fallback_reason,
+                        source_counters: Arc::default(),
+                        triage_index: Arc::default(),
+                        evidence_sampler: Arc::default(),
+                        dom_index: Arc::default(),
+                        post_processors: PostProcessorPipeline::default(),
+                    });
+                }
+
                 // 2. 构建RuleLibraryIndex（单独计时）
                 let index_build_start = Instant::now();
                 let rule_index = RuleLibraryIndex::from_rule_library(&rule_lib)?;
@@ -198,34 +716,22 @@ impl TechDetector {
                 let compile_lib_start = Instant::now();
 
                 // 监控正则缓存初始状态
-                let regex_cache_before = {
-                    let cache = rswappalyzer_engine::indexer::matcher::REGEX_CACHE
-                        .read()
-                        .unwrap();
-                    cache.len()
-                };
+                let regex_cache_before = rswappalyzer_engine::regex_cache_len();
                 log::info!(
                     "[Monitor] Regex cache count before compilation: {}",
                     regex_cache_before
                 );
 
                 // 执行编译
-                let compiled_lib = RuleIndexer::build_compiled_library(
-                    &rule_index,
-                    Some("data/categories_data.json"),
-                )?;
+                let compiled_lib = Self::compile_index(&rule_index, &config)?;
+                Self::persist_compiled(&config, fingerprint, &compiled_lib);
 
                 // 监控正则缓存变化
-                let regex_cache_after = {
-                    let cache = rswappalyzer_engine::indexer::matcher::REGEX_CACHE
-                        .read()
-                        .unwrap();
-                    cache.len()
-                };
+                let regex_cache_after = rswappalyzer_engine::regex_cache_len();
                 log::info!(
                     "[Monitor] Regex cache count after compilation: {} | New entries: {}",
                     regex_cache_after,
-                    regex_cache_after - regex_cache_before
+                    regex_cache_after.saturating_sub(regex_cache_before)
                 );
 
                 let compile_lib_cost = compile_lib_start.elapsed();
@@ -248,11 +754,86 @@ impl TechDetector {
                     compiled_lib: Arc::new(compiled_lib),
                     config,
                     rule_index: Some(Arc::new(rule_index)),
+                    rule_source,
+                    fallback_reason,
+                    source_counters: Arc::default(),
+                    triage_index: Arc::default(),
+                    evidence_sampler: Arc::default(),
+                    dom_index: Arc::default(),
+                    post_processors: PostProcessorPipeline::default(),
                 })
             }
         }
     }
 
+    /// 加载运行时规则（本地/远程）
+    /// 1. 配置了启动时限时，超时视为加载失败（需 remote-loader 特性）
+    /// 2. 加载失败且开启 fallback_to_cache 时，降级使用已有缓存（StalePolicy::FailIfStale 下不使用过期缓存）
+    ///
+    /// 返回：(规则库, 实际规则来源, 降级原因) | 加载错误
+    async fn load_runtime_rules(
+        config: &RuleConfig,
+    ) -> RswResult<(RuleLibrary, RuleSource, Option<String>)> {
+        let err = match Self::load_time_boxed(config).await {
+            Ok(rule_lib) => return Ok((rule_lib, RuleSource::Configured, None)),
+            Err(e) => e,
+        };
+
+        let stale_forbidden =
+            config.options.stale_policy == StalePolicy::FailIfStale && RuleCacheManager::is_stale(config);
+        if config.options.fallback_to_cache && !stale_forbidden {
+            if let Ok(rule_lib) = RuleCacheManager::load_from_cache(config) {
+                log::warn!("Rule loading failed, falling back to cached rules | Error: {}", err);
+                return Ok((rule_lib, RuleSource::CacheFallback, Some(err.to_string())));
+            }
+        }
+        Err(err)
+    }
+
+    /// 按启动时限加载规则
+    async fn load_time_boxed(config: &RuleConfig) -> RswResult<RuleLibrary> {
+        let rule_loader = RuleLoader::new();
+        #[cfg(feature = "remote-loader")]
+        if let Some(limit) = config.options.startup_timeout {
+            return tokio::time::timeout(limit, rule_loader.load(config))
+                .await
+                .map_err(|_| {
+                    RswappalyzerError::RuleLoadError(format!(
+                        "Rule loading timed out after {:?}",
+                        limit
+                    ))
+                })?;
+        }
+        rule_loader.load(config).await
+    }
+
+    /// 运行时规则不可用时降级使用内置规则（需开启 fallback_to_embedded）
+    /// 参数：
+    /// - config: 规则配置
+    /// - err: 原始加载错误（未降级时原样返回）
+    fn embedded_fallback(config: RuleConfig, err: RswappalyzerError) -> RswResult<Self> {
+        if config.options.fallback_to_embedded && cfg!(feature = "embedded-rules") {
+            log::warn!("Rule loading failed, falling back to embedded rules | Error: {}", err);
+            let mut detector = Self::with_embedded_rules(config)?;
+            detector.rule_source = RuleSource::EmbeddedFallback;
+            detector.fallback_reason = Some(err.to_string());
+            return Ok(detector);
+        }
+        Err(err)
+    }
+
+    /// 检测器能力信息（实际规则来源/降级原因/编译特性）
+    pub fn capabilities(&self) -> DetectorCapabilities {
+        DetectorCapabilities {
+            rule_source: self.rule_source,
+            fallback_reason: self.fallback_reason.clone(),
+            tech_count: self.compiled_lib.tech_patterns.len(),
+            embedded_rules: cfg!(feature = "embedded-rules"),
+            remote_loader: cfg!(feature = "remote-loader"),
+            full_meta: cfg!(feature = "full-meta"),
+        }
+    }
+
     /// 核心检测方法（高性能版，无耗时统计）
     /// 检测维度：URL/Header/Cookie/HTML/Script/Meta
     /// 参数：
@@ -267,321 +848,940 @@ impl TechDetector {
         urls: &[&str],
         body: &[u8],
     ) -> RswResult<DetectResult> {
-        // 1. Header转换（拆分单值Header和Cookie Header）
-        let (single_header_map, cookie_header_map) = HeaderConverter::convert_all(&headers);
-        let standard_cookies = HeaderConverter::parse_to_standard_cookie(&cookie_header_map);
+        // 1. Header预处理（拆分单值Header和Cookie Header + 令牌提取）
+        let prepared = PreparedHeaders::prepare(headers);
+        self.detect_prepared(&prepared, urls, body)
+    }
 
-        // 2. HTML处理（输入守卫 + 内容提取，零拷贝优化）
-        let html_str = String::from_utf8_lossy(body);
-        let (html_safe_str, script_src_combined, meta_tags) = match HtmlInputGuard::guard(html_str)
-        {
-            Some(valid_html) => {
-                let html_result = HtmlExtractor::extract(&valid_html);
-                (
-                    valid_html,
-                    html_result.script_src_combined,
-                    html_result.meta_tags,
-                )
-            }
-            None => (Cow::Borrowed(""), String::new(), Vec::with_capacity(0)),
+    /// 使用预处理Header检测（批量扫描快速路径）
+    /// 同一Header集合通过 PreparedHeaders::prepare 只处理一次，批次内多次检测直接复用，
+    /// 检测结果与 detect 完全一致
+    /// 参数：
+    /// - headers: 预处理Header集合
+    /// - urls: 检测的URL列表
+    /// - body: HTTP响应体（字节数组）
+    ///
+    /// 返回：检测结果 | 错误
+    pub fn detect_prepared(
+        &self,
+        headers: &PreparedHeaders,
+        urls: &[&str],
+        body: &[u8],
+    ) -> RswResult<DetectResult> {
+        self.detect_prepared_with(headers, urls, body, &DetectOptions::default())
+    }
+
+    /// 按单次检测选项检测（作用域过滤/耗时上限/提取上限覆盖）
+    /// 参数：
+    /// - headers: HTTP头信息（HeaderMap）
+    /// - urls: 检测的URL列表
+    /// - body: HTTP响应体（字节数组）
+    /// - options: 单次检测选项
+    ///
+    /// 返回：检测结果 | 错误（含超时）
+    pub fn detect_with_options(
+        &self,
+        headers: &HeaderMap,
+        urls: &[&str],
+        body: &[u8],
+        options: &DetectOptions,
+    ) -> RswResult<DetectResult> {
+        let prepared = PreparedHeaders::prepare(headers);
+        self.detect_prepared_with(&prepared, urls, body, options)
+    }
+
+    /// 按作用域位集检测（缺失的输入维度对应分析器整体跳过）
+    /// 参数：
+    /// - headers: HTTP头信息（HeaderMap）
+    /// - urls: 检测的URL列表
+    /// - body: HTTP响应体（字节数组）
+    /// - scopes: 执行的作用域位集（如 `ScopeMask::HEADERS_ONLY`、`ScopeMask::URL | ScopeMask::BODY`）
+    ///
+    /// 返回：检测结果 | 错误
+    pub fn detect_with_scopes(
+        &self,
+        headers: &HeaderMap,
+        urls: &[&str],
+        body: &[u8],
+        scopes: ScopeMask,
+    ) -> RswResult<DetectResult> {
+        let options = DetectOptions::default().scope_mask(scopes);
+        if scopes.contains(PruneScope::Header) || scopes.contains(PruneScope::Cookie) {
+            return self.detect_with_options(headers, urls, body, &options);
+        }
+        // Header/Cookie 均跳过时仅保留响应体路由所需的 Header，省去全量 Header/Cookie 令牌提取
+        let mut routing = HeaderMap::new();
+        for name in [CONTENT_TYPE, CONTENT_ENCODING] {
+            if let Some(value) = headers.get(&name) {
+                routing.insert(name, value.clone());
+            }
+        }
+        self.detect_with_options(&routing, urls, body, &options)
+    }
+
+    /// 使用预处理Header按单次检测选项检测
+    /// 参数：
+    /// - headers: 预处理Header集合
+    /// - urls: 检测的URL列表
+    /// - body: HTTP响应体（字节数组）
+    /// - options: 单次检测选项
+    ///
+    /// 返回：检测结果 | 错误（含超时）
+    pub fn detect_prepared_with(
+        &self,
+        headers: &PreparedHeaders,
+        urls: &[&str],
+        body: &[u8],
+        options: &DetectOptions,
+    ) -> RswResult<DetectResult> {
+        self.detect_prepared_inner(headers, urls, body, SupplementalInput::default(), options)
+    }
+
+    /// 携带 JS 全局变量检测（无头浏览器采集的 window 属性参与 js 规则匹配）
+    /// 参数：
+    /// - headers: HTTP头信息（HeaderMap）
+    /// - urls: 检测的URL列表
+    /// - body: HTTP响应体（字节数组）
+    /// - js_globals: 属性链（如 `jQuery.fn.jquery`）→ 属性值的字符串形式（对象/函数等无意义取值可传空串）
+    ///
+    /// 返回：检测结果 | 错误
+    pub fn detect_with_js(
+        &self,
+        headers: &HeaderMap,
+        urls: &[&str],
+        body: &[u8],
+        js_globals: &FxHashMap<String, String>,
+    ) -> RswResult<DetectResult> {
+        let prepared = PreparedHeaders::prepare(headers);
+        let supplemental = SupplementalInput {
+            js_globals: Some(js_globals),
+            ..SupplementalInput::default()
+        };
+        self.detect_prepared_inner(&prepared, urls, body, supplemental, &DetectOptions::default())
+    }
+
+    /// 携带 TLS 证书颁发者检测（颁发者参与 certIssuer 规则匹配）
+    /// 参数：
+    /// - headers: HTTP头信息（HeaderMap）
+    /// - urls: 检测的URL列表
+    /// - body: HTTP响应体（字节数组）
+    /// - cert_issuer: 证书颁发者字符串（如 Issuer 的 `O` 字段 `Let's Encrypt`）
+    ///
+    /// 返回：检测结果 | 错误
+    pub fn detect_with_cert_issuer(
+        &self,
+        headers: &HeaderMap,
+        urls: &[&str],
+        body: &[u8],
+        cert_issuer: &str,
+    ) -> RswResult<DetectResult> {
+        let prepared = PreparedHeaders::prepare(headers);
+        let supplemental = SupplementalInput {
+            cert_issuer: Some(cert_issuer),
+            ..SupplementalInput::default()
+        };
+        self.detect_prepared_inner(&prepared, urls, body, supplemental, &DetectOptions::default())
+    }
+
+    /// 携带 DNS 记录检测（调用方自行解析的记录参与 dns 规则匹配，核心不依赖解析器）
+    /// 参数：
+    /// - headers: HTTP头信息（HeaderMap）
+    /// - urls: 检测的URL列表
+    /// - body: HTTP响应体（字节数组）
+    /// - dns_records: 记录类型（如 `MX`/`TXT`/`CNAME`，大小写不敏感）→ 记录值列表
+    ///
+    /// 返回：检测结果 | 错误
+    pub fn detect_with_dns(
+        &self,
+        headers: &HeaderMap,
+        urls: &[&str],
+        body: &[u8],
+        dns_records: &FxHashMap<String, Vec<String>>,
+    ) -> RswResult<DetectResult> {
+        let prepared = PreparedHeaders::prepare(headers);
+        let supplemental = SupplementalInput {
+            dns_records: Some(dns_records),
+            ..SupplementalInput::default()
         };
+        self.detect_prepared_inner(&prepared, urls, body, supplemental, &DetectOptions::default())
+    }
 
-        // 3. 初始化检测结果（FxHashMap高性能哈希表）
-        let mut detected = FxHashMap::default();
+    /// 携带 favicon 检测（调用方拉取的 favicon 字节计算 mmh3 哈希后参与 favicon 规则匹配）
+    /// 参数：
+    /// - headers: HTTP头信息（HeaderMap）
+    /// - urls: 检测的URL列表
+    /// - body: HTTP响应体（字节数组）
+    /// - favicon: favicon 原始字节（如 `/favicon.ico` 响应体，空字节跳过）
+    ///
+    /// 返回：检测结果 | 错误
+    pub fn detect_with_favicon(
+        &self,
+        headers: &HeaderMap,
+        urls: &[&str],
+        body: &[u8],
+        favicon: &[u8],
+    ) -> RswResult<DetectResult> {
+        let prepared = PreparedHeaders::prepare(headers);
+        let hash = (!favicon.is_empty()).then(|| favicon_hash(favicon).to_string());
+        let supplemental = SupplementalInput {
+            favicon_hash: hash.as_deref(),
+            ..SupplementalInput::default()
+        };
+        self.detect_prepared_inner(&prepared, urls, body, supplemental, &DetectOptions::default())
+    }
 
-        // 4. 多维度分析（与detect_with_time完全一致）
-        UrlAnalyzer::analyze(&self.compiled_lib, urls, &mut detected);
-        HeaderAnalyzer::analyze(&self.compiled_lib, &single_header_map, &mut detected);
-        CookieAnalyzer::analyze(&self.compiled_lib, &standard_cookies, &mut detected);
+    /// 统一检测入口（DetectInput 携带的全部输入均参与检测）
+    /// 参数：input - 检测请求（通过 DetectInput::builder 组装；中间响应 URL 参与 URL/主机名匹配，
+    /// 响应头参与 Header/Cookie 匹配；未提供的补充输入对应作用域跳过）
+    ///
+    /// 返回：检测结果 | 错误
+    pub fn run(&self, input: &DetectInput) -> RswResult<DetectResult> {
+        self.run_with(input, &DetectOptions::default())
+    }
 
-        // 有有效HTML内容时才执行HTML相关分析
-        if !html_safe_str.is_empty() {
-            HtmlAnalyzer::analyze(&self.compiled_lib, &html_safe_str, &mut detected);
-            ScriptAnalyzer::analyze(&self.compiled_lib, &script_src_combined, &mut detected);
-            MetaAnalyzer::analyze(&self.compiled_lib, &meta_tags, &mut detected);
-        }
+    /// 按单次检测选项执行统一检测入口
+    /// 参数：
+    /// - input: 检测请求
+    /// - options: 单次检测选项
+    ///
+    /// 返回：检测结果 | 错误（含超时）
+    pub fn run_with(&self, input: &DetectInput, options: &DetectOptions) -> RswResult<DetectResult> {
+        let prepared = PreparedHeaders::prepare(&input.headers);
+        let hop_headers = input.prepare_hops();
+        let hash = input.favicon_hash();
+        let supplemental = input.supplemental(&hop_headers, hash.as_deref());
+        self.detect_prepared_inner(&prepared, &input.chain_urls(), &input.body, supplemental, options)
+    }
 
-        // 5. 应用关联推导规则（与detect_with_time完全一致）
-        let imply_map = DetectionUpdater::apply_implies(&self.compiled_lib, &mut detected);
+    /// 检测主流程（无补充输入时与 detect_prepared_with 一致）
+    fn detect_prepared_inner(
+        &self,
+        headers: &PreparedHeaders,
+        urls: &[&str],
+        body: &[u8],
+        supplemental: SupplementalInput<'_>,
+        options: &DetectOptions,
+    ) -> RswResult<DetectResult> {
+        let _span = stage_span!("detect", urls = urls.len(), body_len = body.len());
+        let deadline = options.deadline();
+        let regex_budget_before = thread_regex_budget_exceeded();
 
-        // 6. 聚合最终结果（预分配容量优化性能）
-        let mut technologies = Vec::with_capacity(detected.len());
-        for (rule_id, (confidence, version)) in detected {
-            if let Some(compiled_tech) = self.compiled_lib.tech_patterns.get(&rule_id) {
-                // 构建技术分类列表（与detect_with_time完全一致）
-                let categories = compiled_tech
-                    .category_ids
-                    .iter()
-                    .filter_map(|id| self.compiled_lib.category_map.get(id).cloned())
-                    .collect();
+        // 2. 响应体处理（按 Content-Type 路由；HTML 执行输入守卫 + 内容提取，零拷贝优化）
+        let body = {
+            let _span = stage_span!("detect.body");
+            self.prepare_detect_body(headers, body, options, supplemental.script_srcs)
+        };
 
-                // 获取推导来源（与detect_with_time完全一致）
-                let implied_by = imply_map.get(&compiled_tech.name).cloned();
+        // 3. 多维度分析（未启用的作用域跳过，各阶段之间检查耗时上限）
+        let lib = &self.compiled_lib;
+        let prepared = PreparedInput::new(urls, &body);
+        let scope_input = ScopeInput {
+            lib,
+            headers,
+            prepared: &prepared,
+            supplemental: &supplemental,
+            options,
+            deadline,
+        };
+        let ScopeOutcome {
+            mut detected,
+            scopes,
+            generator_hits,
+            script_outcome,
+        } = self.analyze_scopes(&scope_input, None)?;
 
-                // ========== 修复核心：正确构建Technology对象（支持full-meta特性） ==========
-                #[cfg(feature = "full-meta")]
-                let (website, description, icon, cpe, saas, pricing) = {
-                    let default_meta = TechBasicInfo::default();
-                    let tech_meta = self
-                        .compiled_lib
-                        .tech_meta
-                        .get(&rule_id)
-                        .unwrap_or(&default_meta);
-                    (
-                        tech_meta.website.clone(),
-                        tech_meta.description.clone(),
-                        tech_meta.icon.clone(),
-                        tech_meta.cpe.clone(),
-                        tech_meta.saas,
-                        tech_meta.pricing.clone(),
-                    )
-                };
+        // generator 命中需在其它作用域完成后合并（判定是否被佐证）
+        self.merge_generator_hits(&mut detected, generator_hits);
 
-                // 构建Technology对象
-                let tech = Technology {
-                    name: compiled_tech.name.clone(),
-                    version,
-                    categories,
-                    confidence,
-                    implied_by,
-                    #[cfg(feature = "full-meta")]
-                    website: String::new(),
-                    #[cfg(feature = "full-meta")]
-                    description: String::new(),
-                    #[cfg(feature = "full-meta")]
-                    icon: String::new(),
-                    #[cfg(feature = "full-meta")]
-                    cpe: None,
-                    #[cfg(feature = "full-meta")]
-                    saas: false,
-                    #[cfg(feature = "full-meta")]
-                    pricing: None,
-                };
+        // 4. 应用关联推导规则（仅CDN命中的技术按策略排除）并移除依赖未满足的技术，再移除互斥技术
+        let imply_map = {
+            let _span = stage_span!("detect.post_process");
+            let (_, imply_map) = self.apply_requires_and_implies(&mut detected, &script_outcome);
+            self.apply_excludes(&mut detected);
+            imply_map
+        };
 
-                technologies.push(tech);
-            }
+        // 5. 聚合最终结果
+        let mut technologies = self.build_technologies(detected, &imply_map, script_outcome.hosts, &scopes);
+        let mut warnings = with_regex_budget_warning(body.warnings, regex_budget_before);
+
+        // 6. 按采样与预算采集证据
+        if let Some(capture) = &options.evidence {
+            let _span = stage_span!("detect.evidence");
+            // 证据片段为文本，仅在采集证据时容错解码脚本字节
+            let script = String::from_utf8_lossy(&body.script);
+            let input = EvidenceInput {
+                urls,
+                headers: headers.headers(),
+                cookies: headers.cookies(),
+                html: &body.html,
+                script_srcs: &body.extract.script_srcs,
+                script: &script,
+                meta_tags: &body.extract.meta_tags,
+                title: &body.extract.title,
+                comments: &body.extract.comments,
+                dom_selectors: self.dom_index.selectors(lib),
+                supplemental,
+            };
+            self.attach_evidence(&mut technologies, &input, capture, &mut warnings);
         }
 
-        Ok(DetectResult { technologies })
+        // 7. 执行结果后处理管道
+        let mut result = DetectResult { technologies, warnings };
+        self.post_processors.apply(&mut result);
+        Ok(result)
     }
 
     /// 核心检测方法（带全阶段耗时统计+详细日志）
     /// 特性：
     /// 1. 分阶段计时：Header转换/HTML解析/各维度分析/结果聚合
-    /// 2. 详细日志：每个阶段的耗时、数据量、检测进度
-    /// 3. 兼容基础版检测逻辑，仅增加统计和日志
+    /// 2. 详细日志：每个阶段的耗时、候选统计、新增命中技术（log info 级别输出）
+    /// 3. 兼容基础版检测逻辑，仅增加统计和日志；需要结构化数据时使用 detect_traced
     /// 参数：
     /// - headers: HTTP头信息（HeaderMap）
     /// - urls: 检测的URL列表
     /// - body: HTTP响应体（字节数组）
     /// 返回：检测结果 | 错误
-    #[inline(always)]
     pub fn detect_log(
         &self,
         headers: &HeaderMap,
         urls: &[&str],
         body: &[u8],
     ) -> RswResult<DetectResult> {
-        let total_start = Instant::now();
-
-        // 1. Header转换 + 耗时统计
-        let header_conv_start = Instant::now();
-        let (single_header_map, cookie_header_map) = HeaderConverter::convert_all(&headers);
-        let header_conv_cost = header_conv_start.elapsed();
-        println!(
-            "[Performance] Header conversion completed | Time: {}ms ({:?}) | Single-value header count: {} | Cookie header count: {}",
-            header_conv_cost.as_millis(),
-            header_conv_cost,
-            single_header_map.len(),
-            cookie_header_map.len()
+        let trace = self.trace_inner(headers, urls, body, SupplementalInput::default(), None)?;
+        for stage in &trace.stages {
+            log::info!(
+                "[Performance] Stage {} completed | Time: {}us | Candidates: {} | Pruned: {} | Matched: {:?}",
+                stage.stage,
+                stage.duration_us,
+                stage.candidates,
+                stage.pruned,
+                stage.matched
+            );
+        }
+        log::info!(
+            "[Detection Complete] Total time: {}us | Final tech count: {} | Implied: {:?} | Unmet requires: {:?} | Excluded techs: {:?}",
+            trace.total_us,
+            trace.result.technologies.len(),
+            trace.implied,
+            trace.unmet_requires,
+            trace.excluded
         );
-        let standard_cookies = HeaderConverter::parse_to_standard_cookie(&cookie_header_map);
+        Ok(trace.result)
+    }
 
-        // 2. HTML解析与提取 + 耗时统计
-        let html_parse_start = Instant::now();
-        let html_str = String::from_utf8_lossy(body);
-        let (html_safe_str, script_src_combined, meta_tags) = match HtmlInputGuard::guard(html_str)
-        {
-            Some(valid_html) => {
-                let html_result = HtmlExtractor::extract(&valid_html);
-                (
-                    valid_html,
-                    html_result.script_src_combined,
-                    html_result.meta_tags,
-                )
-            }
-            None => (Cow::Borrowed(""), String::new(), Vec::with_capacity(0)),
+    /// 按 Content-Type 路由并预处理响应体
+    /// HTML 类型执行输入守卫与内容提取；JS/CSS 类型按原文送入对应分析器；二进制类型跳过
+    /// 参数：
+    /// - headers: 单值Header（Key小写，读取 Content-Type / Content-Encoding）
+    /// - body: HTTP响应体（字节数组）
+    ///
+    /// 返回：预处理后的响应体
+    pub(crate) fn prepare_body<'a>(&self, headers: &FxHashMap<String, String>, body: &'a [u8]) -> PreparedBody<'a> {
+        self.prepare_body_with_limits(headers, body, &self.config.options.extract_limits)
+    }
+
+    /// 检测流程的响应体预处理（未请求或未编译响应体作用域时跳过解析，调用方提供的外部脚本地址并入提取结果）
+    /// 参数：
+    /// - headers: 预处理Header集合
+    /// - body: HTTP响应体（字节数组）
+    /// - options: 单次检测选项（可覆盖提取上限）
+    /// - script_srcs: 调用方提供的外部脚本地址
+    ///
+    /// 返回：预处理后的响应体
+    pub(super) fn prepare_detect_body<'a>(
+        &self,
+        headers: &PreparedHeaders,
+        body: &'a [u8],
+        options: &DetectOptions,
+        script_srcs: &[String],
+    ) -> PreparedBody<'a> {
+        let limits = options.extract_limits.as_ref().unwrap_or(&self.config.options.extract_limits);
+        let mut body = if options.needs_body() && self.compiles_body_scopes() {
+            self.prepare_body_with_limits(headers.headers(), body, limits)
+        } else {
+            PreparedBody::empty(BodyKind::Binary)
         };
-        let html_parse_cost = html_parse_start.elapsed();
-        println!(
-            "[Performance] HTML parsing & extraction completed | Time: {}ms ({:?}) | Valid HTML: {} | Script src length: {} | Meta tag count: {}",
-            html_parse_cost.as_millis(),
-            html_parse_cost,
-            !html_safe_str.is_empty(),
-            script_src_combined.len(),
-            meta_tags.len()
-        );
+        body.append_script_srcs(script_srcs);
+        body
+    }
 
-        // 3. 初始化检测结果
-        let mut detected = FxHashMap::default();
-
-        // 4.1 URL维度分析 + 耗时统计
-        let url_analyze_start = Instant::now();
-        UrlAnalyzer::analyze(&self.compiled_lib, urls, &mut detected);
-        let url_analyze_cost = url_analyze_start.elapsed();
-        println!(
-            "[Performance] URL fingerprint analysis completed | Time: {}ms ({:?}) | Detected tech count: {}",
-            url_analyze_cost.as_millis(),
-            url_analyze_cost,
-            detected.len()
-        );
+    /// 按指定提取上限预处理响应体（单次检测选项覆盖配置上限时使用）
+    /// 参数：
+    /// - headers: 单值Header（Key小写，读取 Content-Type / Content-Encoding）
+    /// - body: HTTP响应体（字节数组）
+    /// - limits: HTML 提取上限
+    ///
+    /// 返回：预处理后的响应体
+    pub(crate) fn prepare_body_with_limits<'a>(
+        &self,
+        headers: &FxHashMap<String, String>,
+        body: &'a [u8],
+        limits: &ExtractLimits,
+    ) -> PreparedBody<'a> {
+        let content_type = headers.get("content-type").map(String::as_str);
+        let kind = if self.config.options.content_type_routing {
+            BodyKind::from_content_type(content_type)
+        } else {
+            BodyKind::Html
+        };
+        let mut prepared = PreparedBody::empty(kind);
+        // 空响应体（HEAD 请求/重定向等）/二进制快速路径：直接交给 URL/Header/Cookie 作用域
+        if body.is_empty() || kind == BodyKind::Binary {
+            return prepared;
+        }
+        let content_encoding = headers.get("content-encoding").map(String::as_str);
+        let bytes = self.decompress(body, content_encoding, &mut prepared.warnings);
+        let min_len = self.config.options.min_body_len;
+        if min_len > 0 && bytes.trim_ascii().len() < min_len {
+            log::trace!("Body below minimum analysis size, skipped | Len: {} | Min: {}", bytes.len(), min_len);
+            return prepared;
+        }
+        match kind {
+            BodyKind::Html => {
+                if bytes.len() > HtmlInputGuard::MAX_HTML_LEN {
+                    prepared.warnings.push(DetectWarning::Truncated("html_len".to_string()));
+                }
+                // 解码前按字节校验/截断：被拒绝的响应体不解码，容错解码最多复制 MAX_HTML_LEN 字节
+                let bytes = match HtmlInputGuard::check_bytes(bytes) {
+                    Ok(bytes) => bytes,
+                    Err(reason) => {
+                        prepared.warnings.push(DetectWarning::HtmlRejected(reason));
+                        return prepared;
+                    }
+                };
+                let text = self.decode(bytes, content_type, &mut prepared.warnings);
+                match HtmlInputGuard::check(text) {
+                    Ok(valid_html) => {
+                        let (valid_html, sanitize) = HtmlInputGuard::sanitize(valid_html, limits);
+                        prepared.extract = self.extract_html(&valid_html, limits, sanitize);
+                        prepared.warnings.extend(truncation_warnings(&prepared.extract.stats));
+                        prepared.html = valid_html;
+                    }
+                    Err(reason) => prepared.warnings.push(DetectWarning::HtmlRejected(reason)),
+                }
+            }
+            BodyKind::Script => prepared.script = self.transcode_script(bytes, content_type, &mut prepared.warnings),
+            BodyKind::Css => prepared.html = self.decode(bytes, content_type, &mut prepared.warnings),
+            BodyKind::Binary => {}
+        }
+        prepared
+    }
 
-        // 4.2 Header维度分析 + 耗时统计
-        let header_analyze_start = Instant::now();
-        HeaderAnalyzer::analyze(&self.compiled_lib, &single_header_map, &mut detected);
-        let header_analyze_cost = header_analyze_start.elapsed();
-        println!(
-            "[Performance] Header fingerprint analysis completed | Time: {}ms ({:?}) | Detected tech count: {}",
-            header_analyze_cost.as_millis(),
-            header_analyze_cost,
-            detected.len()
-        );
+    /// 是否编译了任一响应体作用域（HTML/Script/Meta/Dom/Title/Comment）
+    pub(super) fn compiles_body_scopes(&self) -> bool {
+        self.config.options.compile_scopes.as_ref().is_none_or(|scopes| {
+            BODY_SCOPES
+                .iter()
+                .any(|scope| scopes.contains(scope))
+        })
+    }
 
-        // 4.3 Cookie维度分析 + 耗时统计
-        let cookie_analyze_start = Instant::now();
-        CookieAnalyzer::analyze(&self.compiled_lib, &standard_cookies, &mut detected);
-        let cookie_analyze_cost = cookie_analyze_start.elapsed();
-        println!(
-            "[Performance] Cookie fingerprint analysis completed | Time: {}ms ({:?}) | Detected tech count: {}",
-            cookie_analyze_cost.as_millis(),
-            cookie_analyze_cost,
-            detected.len()
-        );
+    /// 响应体解压（按配置）
+    /// 参数：
+    /// - body: HTTP响应体（字节数组）
+    /// - content_encoding: Content-Encoding 头取值
+    /// - warnings: 告警输出（解压失败/解压截断）
+    ///
+    /// 返回：解压后的字节（未压缩时零拷贝）
+    pub(super) fn decompress<'a>(
+        &self,
+        body: &'a [u8],
+        content_encoding: Option<&str>,
+        warnings: &mut Vec<DetectWarning>,
+    ) -> Cow<'a, [u8]> {
+        let options = &self.config.options;
+        let encoding = ContentEncoding::detect(body, content_encoding);
+        if !options.decompress_body || encoding == ContentEncoding::Identity {
+            return Cow::Borrowed(body);
+        }
+        let decoded = decompress_body(body, content_encoding, options.max_decompressed_len);
+        match &decoded {
+            Cow::Borrowed(_) => warnings.push(DetectWarning::BodyDecodeFallback(format!(
+                "{} decompression unavailable or failed",
+                encoding.as_str()
+            ))),
+            Cow::Owned(decoded) if decoded.len() >= options.max_decompressed_len => {
+                warnings.push(DetectWarning::Truncated("decompressed_len".to_string()));
+            }
+            Cow::Owned(_) => {}
+        }
+        decoded
+    }
 
-        // 4.4 HTML相关维度分析（有有效HTML时执行）
-        if !html_safe_str.is_empty() {
-            // 4.4.1 HTML文本分析
-            let html_analyze_start = Instant::now();
-            HtmlAnalyzer::analyze(&self.compiled_lib, &html_safe_str, &mut detected);
-            let html_analyze_cost = html_analyze_start.elapsed();
-            println!(
-                "[Performance] HTML fingerprint analysis completed | Time: {}ms ({:?}) | Detected tech count: {}",
-                html_analyze_cost.as_millis(),
-                html_analyze_cost,
-                detected.len()
-            );
+    /// 按 charset 解码响应体字节
+    /// 参数：
+    /// - bytes: 响应体字节（原始输入或解压后的缓冲区）
+    /// - content_type: Content-Type 头取值
+    /// - warnings: 告警输出（非法 UTF-8 容错解码）
+    ///
+    /// 返回：解码后的文本（UTF-8 合法时借用输入或复用解压缓冲区，不复制）
+    pub(super) fn decode<'a>(
+        &self,
+        bytes: Cow<'a, [u8]>,
+        content_type: Option<&str>,
+        warnings: &mut Vec<DetectWarning>,
+    ) -> Cow<'a, str> {
+        let (text, lossy) = decode_body_bytes(bytes, content_type);
+        if lossy {
+            warnings.push(DetectWarning::BodyDecodeFallback("invalid utf-8".to_string()));
+        }
+        text
+    }
 
-            // 4.4.2 Script脚本分析
-            let script_analyze_start = Instant::now();
-            ScriptAnalyzer::analyze(&self.compiled_lib, &script_src_combined, &mut detected);
-            let script_analyze_cost = script_analyze_start.elapsed();
-            println!(
-                "[Performance] Script fingerprint analysis completed | Time: {}ms ({:?}) | Detected tech count: {}",
-                script_analyze_cost.as_millis(),
-                script_analyze_cost,
-                detected.len()
-            );
+    /// 按 charset 转码脚本响应体，保留字节形式
+    /// 非合法 UTF-8 且无可识别 charset 时原样保留字节，由 Script 分析器以 regex::bytes 匹配（不容错解码、不告警）
+    /// 参数：
+    /// - bytes: 响应体字节（原始输入或解压后的缓冲区）
+    /// - content_type: Content-Type 头取值
+    /// - warnings: 告警输出（转码有替换字符）
+    ///
+    /// 返回：UTF-8 或原始字节（UTF-8 合法时借用输入或复用解压缓冲区，不复制）
+    pub(super) fn transcode_script<'a>(
+        &self,
+        bytes: Cow<'a, [u8]>,
+        content_type: Option<&str>,
+        warnings: &mut Vec<DetectWarning>,
+    ) -> Cow<'a, [u8]> {
+        let (script, lossy) = transcode_body_bytes(bytes, content_type);
+        if lossy {
+            warnings.push(DetectWarning::BodyDecodeFallback("invalid utf-8".to_string()));
+        }
+        script
+    }
 
-            // 4.4.3 Meta标签分析
-            let meta_analyze_start = Instant::now();
-            MetaAnalyzer::analyze(&self.compiled_lib, &meta_tags, &mut detected);
-            let meta_analyze_cost = meta_analyze_start.elapsed();
-            println!(
-                "[Performance] Meta fingerprint analysis completed | Time: {}ms ({:?}) | Detected tech count: {}",
-                meta_analyze_cost.as_millis(),
-                meta_analyze_cost,
-                detected.len()
-            );
+    /// HTML 内容提取（按配置的提取上限）
+    /// 内容因上限被截断时输出调试日志，便于排查超大页面的漏检
+    /// 参数：
+    /// - html: 已清理的 HTML
+    /// - limits: HTML 提取上限
+    /// - sanitize: 对抗性输入清理统计（并入提取统计）
+    ///
+    /// 返回：提取结果
+    pub(super) fn extract_html(&self, html: &str, limits: &ExtractLimits, sanitize: SanitizeStats) -> ExtractResult {
+        let mut extract = self.config.options.html_parser.extract(html, limits);
+        extract.stats.sanitize = sanitize;
+        if extract.stats.is_truncated() {
+            log::debug!("HTML extraction truncated by limits | Stats: {:?}", extract.stats);
+        }
+        extract
+    }
+
+    /// 执行全部作用域分析：请求侧 → 响应体侧，主机策略生效时 Script 最后执行（保证"仅CDN命中"判定覆盖其它所有维度）
+    /// detect / 追踪 / 渐进式检测共用同一阶段序列；parallel 特性开启、无观察者且 HTML 足够大时两侧并行执行
+    /// 参数：
+    /// - input: 多维度分析输入
+    /// - observer: 阶段观察者（None 时不产出阶段事件）
+    ///
+    /// 返回：作用域分析结果 | 错误（含超时）
+    pub(super) fn analyze_scopes(
+        &self,
+        input: &ScopeInput<'_>,
+        mut observer: Option<&mut dyn StageObserver>,
+    ) -> RswResult<ScopeOutcome> {
+        #[cfg(feature = "parallel")]
+        if observer.is_none()
+            && input.prepared.body.html.len() >= PARALLEL_MIN_HTML_LEN
+            && std::ptr::eq(input.lib, self.compiled_lib.as_ref())
+        {
+            return self.analyze_scopes_parallel(input);
+        }
+        let mut outcome = ScopeOutcome::default();
+        self.analyze_request_scopes(input, &mut outcome, reborrow(&mut observer))?;
+        self.analyze_body_scopes(input, &mut outcome, observer)?;
+        Ok(outcome)
+    }
+
+    /// 并行执行各作用域阶段：请求侧与响应体侧的每个阶段作为 rayon 作用域任务执行，直接借用本次检测的输入，
+    /// 每个阶段写入独立的分析结果，全部完成后按阶段顺序合并（与顺序路径结果一致），再执行主机策略下的 Script 阶段
+    /// 各任务的正则编译超限计数计入当前线程；任务 panic 时由 rayon::scope 在当前线程重新抛出
+    #[cfg(feature = "parallel")]
+    fn analyze_scopes_parallel(&self, input: &ScopeInput<'_>) -> RswResult<ScopeOutcome> {
+        let mut stages = self.request_stages(input);
+        stages.extend(self.body_stages(input));
+        let mut results: Vec<Option<(RswResult<ScopeOutcome>, u64)>> = stages.iter().map(|_| None).collect();
+        rayon::scope(|scope| {
+            for (&stage, slot) in stages.iter().zip(results.iter_mut()) {
+                scope.spawn(move |_| *slot = Some(self.run_stage_isolated(stage, input)));
+            }
+        });
+
+        let mut outcome = ScopeOutcome::default();
+        let mut result = Ok(());
+        let mut exceeded = 0;
+        for (stage_result, stage_exceeded) in results.into_iter().flatten() {
+            exceeded += stage_exceeded;
+            match stage_result {
+                Ok(stage_outcome) => outcome.merge(stage_outcome),
+                Err(e) => result = result.and(Err(e)),
+            }
+        }
+        charge_thread_regex_budget_exceeded(exceeded);
+        result?;
+        self.run_stages(self.deferred_stages(input), input, &mut outcome, None)?;
+        Ok(outcome)
+    }
+
+    /// 在独立的分析结果中执行单个阶段（并行检测的作用域任务）
+    /// 参数：
+    /// - stage: 检测阶段
+    /// - input: 多维度分析输入
+    ///
+    /// 返回：(阶段分析结果 | 错误（含超时）, 本阶段正则编译超限计数)
+    #[cfg(feature = "parallel")]
+    fn run_stage_isolated(&self, stage: DetectStage, input: &ScopeInput<'_>) -> (RswResult<ScopeOutcome>, u64) {
+        let budget_before = thread_regex_budget_exceeded();
+        let mut outcome = ScopeOutcome::default();
+        let result = self.run_stages(&[stage], input, &mut outcome, None);
+        let exceeded = thread_regex_budget_exceeded().saturating_sub(budget_before);
+        (result.map(|_| outcome), exceeded)
+    }
+
+    /// 请求侧作用域分析（URL/Hostname/Header/Cookie 及补充输入的 JS/证书颁发者/DNS/Favicon）
+    pub(super) fn analyze_request_scopes(
+        &self,
+        input: &ScopeInput<'_>,
+        outcome: &mut ScopeOutcome,
+        observer: Option<&mut dyn StageObserver>,
+    ) -> RswResult<()> {
+        self.run_stages(&self.request_stages(input), input, outcome, observer)
+    }
+
+    /// 响应体侧作用域分析（HTML/DOM/Script/Meta/Title/Comment；主机策略生效时 Script 最后执行）
+    pub(super) fn analyze_body_scopes(
+        &self,
+        input: &ScopeInput<'_>,
+        outcome: &mut ScopeOutcome,
+        mut observer: Option<&mut dyn StageObserver>,
+    ) -> RswResult<()> {
+        self.run_stages(&self.body_stages(input), input, outcome, reborrow(&mut observer))?;
+        self.run_stages(self.deferred_stages(input), input, outcome, observer)
+    }
+
+    /// 请求侧阶段序列（未启用的作用域、调用方未提供的补充输入跳过）
+    fn request_stages(&self, input: &ScopeInput<'_>) -> Vec<DetectStage> {
+        let ScopeInput { supplemental, options, .. } = *input;
+        [
+            (DetectStage::Url, true),
+            (DetectStage::Hostname, true),
+            (DetectStage::Header, true),
+            (DetectStage::Cookie, true),
+            (DetectStage::Js, supplemental.js_globals.is_some()),
+            (DetectStage::CertIssuer, supplemental.cert_issuer.is_some()),
+            (DetectStage::Dns, supplemental.dns_records.is_some()),
+            (DetectStage::FaviconHash, supplemental.favicon_hash.is_some()),
+        ]
+        .into_iter()
+        .filter(|(stage, supplied)| *supplied && stage.scope().is_some_and(|scope| options.allows(scope)))
+        .map(|(stage, _)| stage)
+        .collect()
+    }
+
+    /// 响应体侧阶段序列（主机策略下的 Script 除外，见 deferred_stages）
+    /// 有有效HTML内容时才执行HTML相关分析（CSS 仅匹配 HTML 内容模式，JS 作为脚本内容匹配）
+    fn body_stages(&self, input: &ScopeInput<'_>) -> Vec<DetectStage> {
+        let ScopeInput { prepared, options, .. } = *input;
+        let body = prepared.body;
+        let mut stages = Vec::new();
+        if !body.html.is_empty() {
+            stages.push(DetectStage::Html);
+        }
+        if body.is_document() {
+            stages.push(DetectStage::Dom);
+            if !self.config.options.script_host_policy.is_active() {
+                stages.push(DetectStage::Script);
+            }
+            stages.extend([DetectStage::Meta, DetectStage::Title, DetectStage::Comment]);
+        } else if !body.script.is_empty() || !body.extract.script_src_combined.is_empty() {
+            stages.push(DetectStage::Script);
+        }
+        stages.retain(|stage| stage.scope().is_some_and(|scope| options.allows(scope)));
+        stages
+    }
+
+    /// 其它作用域完成后执行的阶段（主机策略生效时的 HTML 文档 Script 阶段）
+    fn deferred_stages(&self, input: &ScopeInput<'_>) -> &'static [DetectStage] {
+        let deferred = input.prepared.body.is_document()
+            && input.options.allows(PruneScope::Script)
+            && self.config.options.script_host_policy.is_active();
+        if deferred {
+            &[DetectStage::Script]
         } else {
-            println!("[Performance] No valid HTML content, skip HTML/Script/Meta analysis");
-        }
-
-        // 5. 关联规则推导 + 耗时统计
-        let imply_start = Instant::now();
-        let imply_map = DetectionUpdater::apply_implies(&self.compiled_lib, &mut detected);
-        let imply_cost = imply_start.elapsed();
-        println!(
-            "[Performance] Implication rule application completed | Time: {}ms ({:?}) | Implied tech count: {} | Total detected tech count: {}",
-            imply_cost.as_millis(),
-            imply_cost,
-            imply_map.len(),
-            detected.len()
-        );
+            &[]
+        }
+    }
+
+    /// 按顺序执行阶段：每个阶段在对应作用域下记录命中，前后通知观察者，阶段之间检查耗时上限
+    /// 观察者要求停止时跳过剩余阶段
+    fn run_stages(
+        &self,
+        stages: &[DetectStage],
+        input: &ScopeInput<'_>,
+        outcome: &mut ScopeOutcome,
+        mut observer: Option<&mut dyn StageObserver>,
+    ) -> RswResult<()> {
+        for &stage in stages {
+            let Some(scope) = stage.scope() else {
+                continue;
+            };
+            if let Some(observer) = observer.as_deref_mut() {
+                if observer.is_done() {
+                    return Ok(());
+                }
+                observer.stage_started(stage, &outcome.detected);
+            }
+            let mut scopes = std::mem::take(&mut outcome.scopes);
+            scopes.record(scope, || self.analyze_stage(stage, input, outcome));
+            outcome.scopes = scopes;
+            if let Some(observer) = observer.as_deref_mut() {
+                observer.stage_finished(stage, &outcome.detected, input);
+            }
+            input.options.check_deadline(input.deadline)?;
+        }
+        Ok(())
+    }
+
+    /// 执行单个阶段对应的分析器
+    fn analyze_stage(&self, stage: DetectStage, input: &ScopeInput<'_>, outcome: &mut ScopeOutcome) {
+        let ScopeInput { lib, headers, prepared, supplemental, .. } = *input;
+        let detected = &mut outcome.detected;
+        let urls = prepared.urls;
+        let body = prepared.body;
+        let extract = &body.extract;
+        let headers = || std::iter::once(headers).chain(supplemental.hop_headers);
+        match stage {
+            DetectStage::Url => UrlAnalyzer::analyze_tokenized(lib, urls, prepared.url_tokens(), detected),
+            DetectStage::Hostname => HostnameAnalyzer::analyze(lib, urls, detected),
+            DetectStage::Header => {
+                for headers in headers() {
+                    HeaderAnalyzer::analyze_prepared(lib, headers, detected);
+                    self.analyze_banners(lib, headers.headers(), detected);
+                }
+            }
+            DetectStage::Cookie => {
+                for headers in headers() {
+                    CookieAnalyzer::analyze_prepared(lib, headers, detected);
+                }
+            }
+            DetectStage::Js => {
+                if let Some(js_globals) = supplemental.js_globals {
+                    JsAnalyzer::analyze(lib, js_globals, detected);
+                }
+            }
+            DetectStage::CertIssuer => {
+                if let Some(issuer) = supplemental.cert_issuer {
+                    CertAnalyzer::analyze(lib, issuer, detected);
+                }
+            }
+            DetectStage::Dns => {
+                if let Some(records) = supplemental.dns_records {
+                    DnsAnalyzer::analyze(lib, records, detected);
+                }
+            }
+            DetectStage::FaviconHash => {
+                if let Some(hash) = supplemental.favicon_hash {
+                    FaviconAnalyzer::analyze(lib, hash, detected);
+                }
+            }
+            DetectStage::Html => HtmlAnalyzer::analyze_tokenized(lib, &body.html, prepared.html_tokens(), detected),
+            // 选择器索引始终由完整规则库构建（目标查询的候选规则库不参与构建）
+            DetectStage::Dom => {
+                let selectors = self.dom_index.selectors(&self.compiled_lib);
+                DomAnalyzer::analyze(lib, selectors, &body.html, detected)
+            }
+            DetectStage::Script => self.analyze_script_stage(input, detected, &mut outcome.script_outcome),
+            DetectStage::Meta => {
+                outcome.generator_hits =
+                    self.analyze_meta_tokenized(lib, &extract.meta_tags, prepared.meta_tokens(), detected);
+            }
+            DetectStage::Title => {
+                TitleAnalyzer::analyze_tokenized(lib, &extract.title, prepared.title_tokens(), detected)
+            }
+            DetectStage::Comment => {
+                CommentAnalyzer::analyze_tokenized(lib, &extract.comments, prepared.comment_tokens(), detected)
+            }
+            DetectStage::Implies => {}
+        }
+    }
+
+    /// Script 阶段
+    /// 1. 非 HTML 文档：JS 资源内容与外部脚本地址分别匹配
+    /// 2. HTML 文档：脚本地址拼接串 + 内联脚本；主机策略生效时脚本地址改为按主机策略逐个匹配（阶段最后执行）
+    fn analyze_script_stage(&self, input: &ScopeInput<'_>, detected: &mut Detected, script_outcome: &mut ScriptHostOutcome) {
+        let ScopeInput { lib, prepared, .. } = *input;
+        let body = prepared.body;
+        let extract = &body.extract;
+        if !body.is_document() {
+            if !body.script.is_empty() {
+                ScriptAnalyzer::analyze_bytes_tokenized(lib, &body.script, prepared.script_body_tokens(), detected);
+            }
+            // 无 HTML 文档时外部脚本地址单独匹配
+            if !extract.script_src_combined.is_empty() {
+                let combined = &extract.script_src_combined;
+                ScriptAnalyzer::analyze_tokenized(lib, combined, prepared.script_src_tokens(), detected);
+            }
+            return;
+        }
+        let host_policy = &self.config.options.script_host_policy;
+        if !host_policy.is_active() {
+            let combined = &extract.script_src_combined;
+            ScriptAnalyzer::analyze_tokenized(lib, combined, prepared.script_src_tokens(), detected);
+        }
+        // 内联脚本属于页面自身内容，不受主机策略影响
+        if !extract.inline_scripts.is_empty() {
+            let inline = &extract.inline_scripts;
+            ScriptAnalyzer::analyze_tokenized(lib, inline, prepared.inline_script_tokens(), detected);
+        }
+        if host_policy.is_active() {
+            *script_outcome = ScriptAnalyzer::analyze_with_host_policy(lib, &extract.script_srcs, host_policy, detected);
+        }
+    }
+
+    /// Meta 标签分析（使用预提取令牌；generator 与其它标签两次分析共用全部标签的令牌）
+    /// generator 防伪策略生效时，generator 标签的命中单独收集，
+    /// 待其它作用域分析完成后通过 merge_generator_hits 合并
+    /// 返回：待合并的 generator 命中结果（策略未启用时为空）
+    fn analyze_meta_tokenized(
+        &self,
+        lib: &CompiledRuleLibrary,
+        meta_tags: &[(String, String)],
+        meta_tokens: &FxHashSet<String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) -> FxHashMap<String, (u8, Option<String>)> {
+        let mut generator_hits = FxHashMap::default();
+        if self.config.options.generator_policy.is_none() {
+            MetaAnalyzer::analyze_tokenized(lib, meta_tags, meta_tokens, detected);
+            return generator_hits;
+        }
+
+        let (generator_tags, other_tags): (Vec<_>, Vec<_>) = meta_tags
+            .iter()
+            .cloned()
+            .partition(|(name, _)| is_generator_meta(name));
+        MetaAnalyzer::analyze_tokenized(lib, &other_tags, meta_tokens, detected);
+        if !generator_tags.is_empty() {
+            MetaAnalyzer::analyze_tokenized(lib, &generator_tags, meta_tokens, &mut generator_hits);
+        }
+        generator_hits
+    }
+
+    /// Header 横幅解析兜底（策略启用时在 Header 规则匹配之后执行）
+    #[inline(always)]
+    fn analyze_banners(
+        &self,
+        lib: &CompiledRuleLibrary,
+        headers: &FxHashMap<String, String>,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        if let Some(policy) = &self.config.options.banner_policy {
+            BannerAnalyzer::analyze(lib, headers, policy, detected);
+        }
+    }
 
-        // 6. 结果聚合 + 耗时统计
-        let aggregate_start = Instant::now();
+    /// 合并 generator 命中结果（未被其它作用域佐证时按策略降低置信度）
+    #[inline(always)]
+    pub(super) fn merge_generator_hits(
+        &self,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+        generator_hits: FxHashMap<String, (u8, Option<String>)>,
+    ) {
+        if let Some(policy) = &self.config.options.generator_policy {
+            DetectionUpdater::merge_generator_hits(detected, generator_hits, policy);
+        }
+    }
+
+    /// 移除互斥技术（Wappalyzer excludes）
+    /// 返回：被移除的技术名
+    #[inline(always)]
+    pub(super) fn apply_excludes(&self, detected: &mut FxHashMap<String, (u8, Option<String>)>) -> Vec<String> {
+        DetectionUpdater::apply_excludes(&self.compiled_lib, detected)
+    }
+
+    /// 关联推导（按Script主机策略排除仅CDN命中的来源技术）并移除依赖未满足的技术（Wappalyzer requires / requiresCategory）
+    /// 依赖按推导后的结果判定，仅由 implies 链检出的父技术同样满足依赖
+    ///
+    /// 返回：(依赖未满足被移除的技术名, 推导技术 -> 来源列表)
+    #[inline(always)]
+    pub(super) fn apply_requires_and_implies(
+        &self,
+        detected: &mut FxHashMap<String, (u8, Option<String>)>,
+        script_outcome: &ScriptHostOutcome,
+    ) -> (Vec<String>, FxHashMap<String, Vec<String>>) {
+        let no_exclusion = FxHashSet::default();
+        let excluded = if self.config.options.script_host_policy.cdn_implies {
+            &no_exclusion
+        } else {
+            &script_outcome.cdn_only
+        };
+        DetectionUpdater::apply_requires_with_implies(
+            &self.compiled_lib,
+            detected,
+            excluded,
+            &self.config.options.imply_policy,
+        )
+    }
+
+    /// 聚合最终结果（构建Technology列表）
+    /// 参数：
+    /// - detected: 检测结果（技术名 -> (置信度, 版本)）
+    /// - imply_map: 推导技术 -> 来源列表
+    /// - script_hosts: 技术名 -> 命中的脚本主机
+    /// - scopes: 技术名 -> 命中作用域
+    ///
+    /// 返回：技术列表
+    pub(super) fn build_technologies(
+        &self,
+        detected: FxHashMap<String, (u8, Option<String>)>,
+        imply_map: &FxHashMap<String, Vec<String>>,
+        mut script_hosts: FxHashMap<String, Vec<String>>,
+        scopes: &MatchedScopes,
+    ) -> Vec<Technology> {
         let mut technologies = Vec::with_capacity(detected.len());
         for (rule_id, (confidence, version)) in detected {
             if let Some(compiled_tech) = self.compiled_lib.tech_patterns.get(&rule_id) {
+                // 构建技术分类列表（与detect_with_time完全一致）
                 let categories = compiled_tech
                     .category_ids
                     .iter()
                     .filter_map(|id| self.compiled_lib.category_map.get(id).cloned())
                     .collect();
+
+                // 获取推导来源（与detect_with_time完全一致）
                 let implied_by = imply_map.get(&compiled_tech.name).cloned();
 
+                // full-meta：从展示元信息旁路表填充（未开启时元信息字段保持 None）
+                let meta = if cfg!(feature = "full-meta") {
+                    self.compiled_lib.meta_of(&compiled_tech.name).cloned().unwrap_or_default()
+                } else {
+                    TechMeta::default()
+                };
+
+                // 构建Technology对象
                 let tech = Technology {
                     name: compiled_tech.name.clone(),
+                    slug: resolve_slug(&compiled_tech.name, &self.config.options.slug_aliases),
                     version,
                     categories,
                     confidence,
                     implied_by,
-                    #[cfg(feature = "full-meta")]
-                    website: String::new(),
-                    #[cfg(feature = "full-meta")]
-                    description: String::new(),
-                    #[cfg(feature = "full-meta")]
-                    icon: String::new(),
-                    #[cfg(feature = "full-meta")]
-                    cpe: None,
-                    #[cfg(feature = "full-meta")]
-                    saas: false,
-                    #[cfg(feature = "full-meta")]
-                    pricing: None,
+                    script_hosts: script_hosts.remove(&rule_id),
+                    matched_scopes: scopes.scopes_of(&rule_id),
+                    rule_source: compiled_tech.source.as_deref().map(str::to_string),
+                    evidence: Vec::new(),
+                    website: meta.website,
+                    description: meta.description,
+                    icon: meta.icon,
+                    cpe: meta.cpe,
+                    saas: meta.saas,
+                    pricing: meta.pricing,
                 };
 
-                #[cfg(feature = "full-meta")]
-                {
-                    let default_meta = TechBasicInfo::default();
-                    let tech_meta = self
-                        .compiled_lib
-                        .tech_meta
-                        .get(&rule_id)
-                        .unwrap_or(&default_meta);
-
-                    tech.website = tech_meta.website.clone();
-                    tech.description = tech_meta.description.clone();
-                    tech.icon = tech_meta.icon.clone();
-                    tech.cpe = tech_meta.cpe.clone();
-                    tech.saas = tech_meta.saas;
-                    tech.pricing = tech_meta.pricing.clone();
-                }
-
+                self.source_counters.record(&self.compiled_lib, compiled_tech.source.as_deref());
                 technologies.push(tech);
             }
         }
 
-        let aggregate_cost = aggregate_start.elapsed();
-        println!(
-            "[Performance] Result aggregation completed | Time: {}ms ({:?}) | Final detected tech count: {}",
-            aggregate_cost.as_millis(),
-            aggregate_cost,
-            technologies.len()
-        );
-
-        // 总耗时统计
-        let total_cost = total_start.elapsed();
-        println!("======================================================================");
-        println!(
-            "[Detection Complete] Full process finished | Total time: {}ms ({:?}) | Final tech count: {} | Implied tech count: {}",
-            total_cost.as_millis(),
-            total_cost,
-            technologies.len(),
-            imply_map.len()
-        );
-        println!("======================================================================");
-
-        Ok(DetectResult { technologies })
+        technologies
     }
 
     /// 核心检测方法（HashMap输入版）
@@ -602,25 +1802,24 @@ impl TechDetector {
         let single_header_map = HeaderConverter::to_single_value(headers);
         let mut header_map = HeaderMap::new();
 
-        // 转换为标准HeaderMap
+        // 转换为标准HeaderMap（非法的 Header 名称/取值跳过并记录告警，不中断检测）
+        let mut invalid_headers = Vec::new();
         for (key, value) in single_header_map {
-            let header_name = HeaderName::from_bytes(key.as_bytes()).map_err(|e| {
-                RswappalyzerError::InvalidInput(format!(
-                    "Invalid header name: {}, error: {}",
-                    key, e
-                ))
-            })?;
-            let header_value = HeaderValue::from_str(&value).map_err(|e| {
-                RswappalyzerError::InvalidInput(format!(
-                    "Invalid header value: {}, error: {}",
-                    value, e
-                ))
-            })?;
-            header_map.append(header_name, header_value);
+            match (HeaderName::from_bytes(key.as_bytes()), HeaderValue::from_str(&value)) {
+                (Ok(header_name), Ok(header_value)) => {
+                    header_map.append(header_name, header_value);
+                }
+                _ => {
+                    log::debug!("Skip invalid header | Name: {}", key);
+                    invalid_headers.push(DetectWarning::InvalidHeaderName(key));
+                }
+            }
         }
 
         // 调用基础检测方法
-        self.detect(&header_map, urls, body)
+        let mut result = self.detect(&header_map, urls, body)?;
+        result.warnings.splice(0..0, invalid_headers);
+        Ok(result)
     }
 }
 
@@ -637,6 +1836,24 @@ pub async fn detect(headers: &HeaderMap, urls: &[&str], body: &[u8]) -> RswResul
     detector.detect(headers, urls, body)
 }
 
+/// 异步全局单例检测接口（单次检测选项版）
+/// 特性：自动获取全局检测器实例，按单次检测选项（作用域/耗时上限/提取上限）执行检测
+/// 参数：
+/// - options: 单次检测选项
+/// - input: 检测请求
+///
+/// 返回：检测结果 | 错误（含超时）
+pub async fn detect_with(options: &DetectOptions, input: &DetectInput) -> RswResult<DetectResult> {
+    let detector = super::global::get_global_detector().await?;
+    detector.run_with(input, options)
+}
+
+/// 创建全局单例检测构建器
+/// 示例：`detect_builder().headers(h).body(b).scopes([PruneScope::Header]).run().await`
+pub fn detect_builder() -> DetectBuilder {
+    DetectBuilder::default()
+}
+
 /// 异步全局单例检测接口（带耗时统计版）
 /// 特性：自动获取全局检测器实例，执行带耗时统计的检测
 /// 参数：
@@ -653,3 +1870,131 @@ pub async fn detect_log(
     let detector = super::global::get_global_detector().await?;
     detector.detect_log(headers, urls, body)
 }
+
+/// 执行检测器编译（tokio 运行时内交给阻塞线程池，避免编译期间占用异步工作线程；无运行时时直接执行）
+/// 参数：compile - 编译过程
+///
+/// 返回：编译结果 | 错误（编译失败/阻塞任务 panic）
+async fn compile_off_thread<T, F>(compile: F) -> RswResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> RswResult<T> + Send + 'static,
+{
+    #[cfg(any(
+        feature = "async-io",
+        feature = "remote-loader",
+        feature = "tower",
+        feature = "pool",
+        feature = "auto-update"
+    ))]
+    if tokio::runtime::Handle::try_current().is_ok() {
+        return tokio::task::spawn_blocking(compile).await.map_err(|e| {
+            RswappalyzerError::AsyncTaskError(format!("Rule compilation task failed: {}", e))
+        })?;
+    }
+    compile()
+}
+
+/// HTML 提取统计中的截断项转换为告警
+fn truncation_warnings(stats: &ExtractStats) -> impl Iterator<Item = DetectWarning> {
+    let sanitize = &stats.sanitize;
+    [
+        ("scripts", stats.scripts_truncated),
+        ("meta", stats.meta_truncated),
+        ("comments", stats.comments_truncated),
+        ("inline_scripts", stats.inline_scripts_truncated),
+        ("attributes", sanitize.attributes_truncated > 0),
+        ("tags", sanitize.tags_truncated),
+        ("depth", sanitize.depth_truncated),
+    ]
+    .into_iter()
+    .filter(|(_, truncated)| *truncated)
+    .map(|(limit, _)| DetectWarning::Truncated(limit.to_string()))
+}
+
+/// 追加本次检测期间（当前线程）正则编译超出体积上限的告警
+/// 参数：
+/// - warnings: 预处理告警
+/// - before: 检测开始时的线程计数
+///
+/// 返回：完整告警列表
+pub(crate) fn with_regex_budget_warning(mut warnings: Vec<DetectWarning>, before: u64) -> Vec<DetectWarning> {
+    let exceeded = thread_regex_budget_exceeded().saturating_sub(before);
+    if exceeded > 0 {
+        warnings.push(DetectWarning::RegexBudgetExceeded(exceeded));
+    }
+    warnings
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::detector;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Acme App": { "cats": [5], "html": "acme-app" }
+        }
+    }"#;
+
+    fn content_type(value: &str) -> FxHashMap<String, String> {
+        let mut headers = FxHashMap::default();
+        headers.insert("content-type".to_string(), value.to_string());
+        headers
+    }
+
+    #[test]
+    fn test_prepared_input_tokens_extracted_once() {
+        let detector = detector(RULES);
+        let html = br#"<html><head><title>Acme Portal</title><meta name="generator" content="AcmeCMS 2.1"></head>
+            <body><!-- build acme-ci --><script src="/static/acme-widget.js"></script>
+            <script>window.acmeInline = 1;</script><div>acme-app</div></body></html>"#;
+        let body = detector.prepare_body_with_limits(&content_type("text/html"), html, &ExtractLimits::default());
+        let urls = ["https://shop.example.com/cart"];
+        let input = PreparedInput::new(&urls, &body);
+
+        // 首次使用前不提取
+        assert!(input.html_tokens.get().is_none());
+        assert!(input.comment_tokens.get().is_none());
+
+        // 同一检测内重复访问复用同一份令牌集合
+        let html_tokens = input.html_tokens();
+        assert!(!html_tokens.is_empty());
+        assert!(std::ptr::eq(html_tokens, input.html_tokens()));
+
+        // 各输入的令牌与直接提取一致
+        assert_eq!(input.url_tokens(), &extract_tokens(urls));
+        assert_eq!(input.html_tokens(), &extract_tokens([body.html.as_ref()]));
+        assert_eq!(input.title_tokens(), &extract_tokens(["Acme Portal"]));
+        assert_eq!(input.meta_tokens(), &extract_tokens(["AcmeCMS 2.1"]));
+        assert_eq!(input.script_src_tokens(), &extract_tokens([body.extract.script_src_combined.as_str()]));
+        assert_eq!(input.inline_script_tokens(), &extract_tokens([body.extract.inline_scripts.as_str()]));
+        assert!(!input.inline_script_tokens().is_empty());
+
+        // HTML 文档没有脚本响应体
+        assert!(input.script_body_tokens().is_empty());
+        // 未访问的输入始终不提取
+        assert!(input.comment_tokens.get().is_none());
+    }
+
+    #[test]
+    fn test_script_body_tokens_split_at_invalid_utf8() {
+        let detector = detector(RULES);
+        let script = b"var acmeWidget = load(\"acme-core\");\xff\xfeinitAcme();";
+        let body = detector.prepare_body_with_limits(
+            &content_type("application/javascript"),
+            script,
+            &ExtractLimits::default(),
+        );
+        // 非法 UTF-8 的脚本按原始字节保留，不做容错解码
+        assert!(matches!(body.script, Cow::Borrowed(bytes) if bytes == script));
+        assert!(body.warnings.is_empty());
+
+        let input = PreparedInput::new(&[], &body);
+        let tokens = input.script_body_tokens();
+        assert_eq!(tokens, &extract_tokens(["var acmeWidget = load(\"acme-core\");", "initAcme();"]));
+        assert!(std::ptr::eq(tokens, input.script_body_tokens()));
+        assert!(input.html_tokens().is_empty());
+    }
+}
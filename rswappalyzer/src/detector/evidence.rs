@@ -0,0 +1,329 @@
+//! 检测证据采集（采样 + 字节预算）
+//! 全量采集证据在大规模部署下会使结果体积与 IO 成倍增长，采集受以下约束：
+//! 1. 采样：每 N 次检测采集一次（计数在检测器及其克隆之间共享）
+//! 2. 置信度阈值：仅为置信度低于阈值的技术采集（高置信度结果通常无需复核）
+//! 3. 预算：单条片段长度、单个技术证据条数与单个结果的证据总字节数均有上限，超出部分丢弃并告警
+//!
+//! 证据在检测完成后按命中作用域对输入复核生成，不影响未采样检测的性能
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use rswappalyzer_engine::log_format::preview_compact;
+use rswappalyzer_engine::scope_pruner::PruneScope;
+use once_cell::sync::OnceCell;
+use rswappalyzer_engine::{url_host, CompiledPattern, CompiledRuleLibrary, CompiledTechRule, Matcher};
+use rustc_hash::FxHashMap;
+
+use super::detector::TechDetector;
+use super::input::SupplementalInput;
+use crate::analyzer::dom::{DomAnalyzer, DomHits, DomSelector};
+use crate::result::evidence::Evidence;
+use crate::{DetectWarning, Technology};
+
+/// 证据采集选项
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvidenceCapture {
+    /// 每 N 次检测采集一次（1 表示每次检测均采集）
+    pub sample_every: u64,
+    /// 仅为置信度低于该阈值的技术采集（None 表示不限）
+    pub below_confidence: Option<u8>,
+    /// 单条证据片段的最大长度（字节）
+    pub max_snippet_len: usize,
+    /// 单个技术最多记录的证据条数
+    pub max_per_tech: usize,
+    /// 单个检测结果的证据总字节预算
+    pub max_bytes: usize,
+}
+
+impl Default for EvidenceCapture {
+    fn default() -> Self {
+        Self {
+            sample_every: 1,
+            below_confidence: None,
+            max_snippet_len: 120,
+            max_per_tech: 3,
+            max_bytes: 4096,
+        }
+    }
+}
+
+impl EvidenceCapture {
+    /// 每 N 次检测采集一次（0 视为 1）
+    pub fn sample_every(mut self, n: u64) -> Self {
+        self.sample_every = n.max(1);
+        self
+    }
+
+    /// 仅为置信度低于阈值的技术采集
+    pub fn below_confidence(mut self, confidence: u8) -> Self {
+        self.below_confidence = Some(confidence);
+        self
+    }
+
+    /// 设置单条证据片段的最大长度
+    pub fn max_snippet_len(mut self, len: usize) -> Self {
+        self.max_snippet_len = len;
+        self
+    }
+
+    /// 设置单个技术最多记录的证据条数
+    pub fn max_per_tech(mut self, count: usize) -> Self {
+        self.max_per_tech = count;
+        self
+    }
+
+    /// 设置单个检测结果的证据总字节预算
+    pub fn max_bytes(mut self, bytes: usize) -> Self {
+        self.max_bytes = bytes;
+        self
+    }
+
+    /// 技术是否需要采集证据
+    fn wants(&self, tech: &Technology) -> bool {
+        self.below_confidence.is_none_or(|threshold| tech.confidence < threshold)
+    }
+}
+
+/// 证据采样计数器（检测器及其克隆之间共享）
+#[derive(Debug, Default)]
+pub(crate) struct EvidenceSampler {
+    seen: AtomicU64,
+}
+
+impl EvidenceSampler {
+    /// 本次检测是否采样（每 every 次检测的第一次命中）
+    pub(crate) fn sample(&self, every: u64) -> bool {
+        self.seen.fetch_add(1, Ordering::Relaxed).is_multiple_of(every.max(1))
+    }
+}
+
+/// 证据复核使用的检测输入
+pub(crate) struct EvidenceInput<'a> {
+    pub urls: &'a [&'a str],
+    pub headers: &'a FxHashMap<String, String>,
+    pub cookies: &'a FxHashMap<String, Vec<String>>,
+    pub html: &'a str,
+    pub script_srcs: &'a [String],
+    pub script: &'a str,
+    pub meta_tags: &'a [(String, String)],
+    pub title: &'a str,
+    pub comments: &'a str,
+    pub dom_selectors: &'a [DomSelector],
+    pub supplemental: SupplementalInput<'a>,
+}
+
+impl TechDetector {
+    /// 为检测结果采集证据（未采样时不做任何处理）
+    /// 参数：
+    /// - technologies: 检测结果中的技术列表
+    /// - input: 检测输入
+    /// - capture: 证据采集选项
+    /// - warnings: 告警列表（预算耗尽时追加 EvidenceTruncated）
+    pub(super) fn attach_evidence(
+        &self,
+        technologies: &mut [Technology],
+        input: &EvidenceInput<'_>,
+        capture: &EvidenceCapture,
+        warnings: &mut Vec<DetectWarning>,
+    ) {
+        if technologies.is_empty() || !self.evidence_sampler.sample(capture.sample_every) {
+            return;
+        }
+        let lib = self.compiled_library();
+        let mut budget = capture.max_bytes;
+        let mut dropped = 0u64;
+        // DOM 命中需重新解析文档，仅在首个需要 DOM 证据的技术处解析一次
+        let dom_hits = OnceCell::new();
+        for tech in technologies.iter_mut().filter(|tech| capture.wants(tech)) {
+            let Some(rule) = lib.tech_patterns.get(&tech.name) else {
+                continue;
+            };
+            for evidence in collect_evidence(lib, rule, &tech.matched_scopes, input, &dom_hits, capture) {
+                let size = evidence.size();
+                if size > budget {
+                    dropped += 1;
+                    continue;
+                }
+                budget -= size;
+                tech.evidence.push(evidence);
+            }
+        }
+        if dropped > 0 {
+            warnings.push(DetectWarning::EvidenceTruncated(dropped));
+        }
+    }
+}
+
+/// 按命中作用域复核输入，生成单个技术的证据
+fn collect_evidence(
+    lib: &CompiledRuleLibrary,
+    rule: &CompiledTechRule,
+    scopes: &[PruneScope],
+    input: &EvidenceInput<'_>,
+    dom_hits: &OnceCell<DomHits>,
+    capture: &EvidenceCapture,
+) -> Vec<Evidence> {
+    let mut found = Vec::new();
+    let mut push = |scope: PruneScope, key: Option<&str>, pattern: Option<&CompiledPattern>, text: &str| {
+        if found.len() >= capture.max_per_tech {
+            return;
+        }
+        let (rule, matched) = match pattern {
+            Some(pattern) => (pattern.describe_rule(), matched_text(pattern, text)),
+            None => ("hostname suffix".to_string(), text),
+        };
+        found.push(Evidence {
+            scope,
+            key: key.map(str::to_string),
+            rule,
+            matched: preview_compact(matched, capture.max_snippet_len).to_string(),
+        });
+    };
+
+    for &scope in scopes {
+        match scope {
+            PruneScope::Url => {
+                for url in input.urls {
+                    if let Some(pattern) = first_match(rule.url_patterns.as_deref(), url) {
+                        push(scope, None, Some(pattern), url);
+                    }
+                }
+            }
+            PruneScope::Hostname => {
+                for host in input.urls.iter().filter_map(|url| url_host(url)) {
+                    if lib.hostname_index.lookup(&host).iter().any(|hit| hit.tech == rule.name) {
+                        push(scope, None, None, &host);
+                    }
+                }
+            }
+            PruneScope::Header => {
+                for (name, patterns) in rule.header_patterns.iter().flatten() {
+                    if let Some(value) = input.headers.get(name) {
+                        if let Some(pattern) = first_match(Some(patterns), value) {
+                            push(scope, Some(name), Some(pattern), value);
+                        }
+                    }
+                }
+            }
+            PruneScope::Cookie => {
+                for (name, patterns) in rule.cookie_patterns.iter().flatten() {
+                    for value in input.cookies.get(name).into_iter().flatten() {
+                        if let Some(pattern) = first_match(Some(patterns), value) {
+                            push(scope, Some(name), Some(pattern), value);
+                        }
+                    }
+                }
+            }
+            PruneScope::Html => {
+                if let Some(pattern) = first_match(rule.html_patterns.as_deref(), input.html) {
+                    push(scope, None, Some(pattern), input.html);
+                }
+            }
+            PruneScope::Script => {
+                let srcs = input.script_srcs.iter().map(String::as_str);
+                for text in srcs.chain((!input.script.is_empty()).then_some(input.script)) {
+                    if let Some(pattern) = first_match(rule.script_patterns.as_deref(), text) {
+                        push(scope, None, Some(pattern), text);
+                    }
+                }
+            }
+            PruneScope::Meta => {
+                for (name, patterns) in rule.meta_patterns.iter().flatten() {
+                    for (_, content) in input.meta_tags.iter().filter(|(meta, _)| meta == name) {
+                        if let Some(pattern) = first_match(Some(patterns), content) {
+                            push(scope, Some(name), Some(pattern), content);
+                        }
+                    }
+                }
+            }
+            PruneScope::Title => {
+                if let Some(pattern) = first_match(rule.title_patterns.as_deref(), input.title) {
+                    push(scope, None, Some(pattern), input.title);
+                }
+            }
+            PruneScope::Comment => {
+                if let Some(pattern) = first_match(rule.comment_patterns.as_deref(), input.comments) {
+                    push(scope, None, Some(pattern), input.comments);
+                }
+            }
+            PruneScope::CertIssuer => {
+                let issuer = input.supplemental.cert_issuer.unwrap_or_default().trim();
+                if let Some(pattern) = first_match(rule.cert_issuer_patterns.as_deref(), issuer) {
+                    push(scope, None, Some(pattern), issuer);
+                }
+            }
+            PruneScope::FaviconHash => {
+                let hash = input.supplemental.favicon_hash.unwrap_or_default();
+                if let Some(pattern) = first_match(rule.favicon_hash_patterns.as_deref(), hash) {
+                    push(scope, None, Some(pattern), hash);
+                }
+            }
+            PruneScope::Dns => {
+                let records = input.supplemental.dns_records.into_iter().flatten();
+                for (record_type, values) in records {
+                    let Some(patterns) = rule.dns_patterns.as_ref().and_then(|map| map.get(&record_type.to_lowercase())) else {
+                        continue;
+                    };
+                    for value in values {
+                        if let Some(pattern) = first_match(Some(patterns), value) {
+                            push(scope, Some(record_type), Some(pattern), value);
+                        }
+                    }
+                }
+            }
+            PruneScope::Js => {
+                for (name, patterns) in rule.js_patterns.iter().flatten() {
+                    // 规则键为小写属性链，调用方提供的属性链保留原始大小写
+                    let globals = input.supplemental.js_globals.into_iter().flatten();
+                    let values = globals.filter(|(global, _)| global.to_lowercase() == *name);
+                    for (global, value) in values {
+                        if let Some(pattern) = first_match(Some(patterns), value) {
+                            push(scope, Some(global), Some(pattern), value);
+                        }
+                    }
+                }
+            }
+            PruneScope::Dom => {
+                let hits = dom_hits.get_or_init(|| DomAnalyzer::collect(input.dom_selectors, input.html));
+                for (key, patterns) in rule.dom_patterns.iter().flatten() {
+                    let Some(values) = hits.get(key) else {
+                        continue;
+                    };
+                    let hit = patterns.iter().find_map(|pattern| {
+                        if pattern.exec.get_matcher().is_exists() {
+                            return Some((pattern, ""));
+                        }
+                        values
+                            .iter()
+                            .find(|value| pattern.matches(value))
+                            .map(|value| (pattern, value.as_str()))
+                    });
+                    if let Some((pattern, value)) = hit {
+                        push(scope, Some(key), Some(pattern), value);
+                    }
+                }
+            }
+        }
+    }
+    found
+}
+
+/// 首个命中输入的模式
+fn first_match<'a>(patterns: Option<&'a [CompiledPattern]>, input: &str) -> Option<&'a CompiledPattern> {
+    patterns?.iter().find(|pattern| pattern.matches(input))
+}
+
+/// 模式在输入中实际命中的片段（Exists/自定义匹配返回整个输入）
+fn matched_text<'a>(pattern: &'a CompiledPattern, input: &'a str) -> &'a str {
+    let matcher = pattern.exec.get_matcher();
+    match matcher {
+        Matcher::LazyRegex { .. } => matcher
+            .captures(input)
+            .and_then(|caps| caps.get(0))
+            .map_or(input, |m| m.as_str()),
+        Matcher::Contains(literal) => input
+            .find(literal.as_str())
+            .map_or(input, |start| &input[start..start + literal.len()]),
+        _ => input,
+    }
+}
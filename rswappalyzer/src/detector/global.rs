@@ -4,9 +4,11 @@
 //! 2. 提供异步/同步初始化接口
 //! 3. 支持懒加载初始化和手动注入规则库
 //! 4. 统一错误处理和状态管理
+//! 5. 规则热更新：新检测器在锁外构建完成后原子替换，进行中的检测继续使用旧快照
 
 use std::sync::Arc;
-use once_cell::sync::{Lazy, OnceCell};
+
+use arc_swap::ArcSwapOption;
 use rswappalyzer_engine::RuleLibrary;
 
 use super::detector::TechDetector;
@@ -15,39 +17,56 @@ use crate::error::{RswResult, RswappalyzerError};
 
 /// 全局检测器实例 - 线程安全单例
 /// 设计说明：
-/// - Lazy：延迟初始化，首次使用时创建
-/// - Arc：多线程共享所有权
-/// - OnceCell：确保实例仅初始化一次，进程内唯一
-static GLOBAL_DETECTOR: Lazy<Arc<OnceCell<TechDetector>>> = Lazy::new(|| Arc::new(OnceCell::new()));
+/// - Arc：检测方持有快照，热更新替换后旧快照在最后一次检测结束时释放
+/// - ArcSwapOption：快照指针无锁读取与原子替换（高并发检测不争用读锁）
+/// - None：尚未初始化
+static GLOBAL_DETECTOR: ArcSwapOption<TechDetector> = ArcSwapOption::const_empty();
+
+/// 当前全局检测器快照
+fn snapshot() -> Option<Arc<TechDetector>> {
+    GLOBAL_DETECTOR.load_full()
+}
+
+/// 未初始化时设置全局检测器
+/// 返回：是否设置成功（已被其它任务初始化时返回 false）
+fn set_if_empty(detector: TechDetector) -> bool {
+    let previous = GLOBAL_DETECTOR.compare_and_swap(&None::<Arc<TechDetector>>, Some(Arc::new(detector)));
+    previous.is_none()
+}
+
+/// 原子替换全局检测器（旧快照由仍在执行的检测持有至结束）
+fn swap(detector: TechDetector) {
+    GLOBAL_DETECTOR.store(Some(Arc::new(detector)));
+}
 
 /// 初始化全局检测器（使用默认配置）
 /// 特性：
 /// 1. 幂等设计：已初始化则直接返回Ok(())
-/// 2. 线程安全：基于OnceCell保证仅初始化一次
+/// 2. 线程安全：空槽位上的比较交换保证并发初始化仅一次生效
 /// 3. 异步初始化：适配TechDetector::new的异步特性
+///
 /// 参数：config - 规则配置
-/// 返回：初始化结果 | 错误（仅当并发初始化冲突时返回）
+///
+/// 返回：初始化结果 | 错误（仅当检测器创建失败时返回）
 pub async fn init_global_detector(config: RuleConfig) -> RswResult<()> {
     // 幂等检查：已初始化则直接返回
-    if GLOBAL_DETECTOR.get().is_some() {
+    if snapshot().is_some() {
         log::debug!("Global detector already initialized, skip reinitialization");
         return Ok(());
     }
 
     // 异步创建检测器实例
-    let detector = TechDetector::new(config).await.map_err(|e| {
-        RswappalyzerError::DetectorInitError(format!(
-            "Failed to create TechDetector instance: {}",
-            e
-        ))
-    })?;
-
-    // 尝试设置全局实例（OnceCell保证仅一次成功）
-    GLOBAL_DETECTOR.set(detector).map_err(|_| {
-        RswappalyzerError::DetectorInitError(
-            "Global detector initialization failed: instance already initialized by another thread".to_string()
-        )
-    })?;
+    // 原样传播创建失败的错误变体（规则加载/解析/校验等），便于调用方按变体处理
+    let detector = TechDetector::new(config)
+        .await
+        .inspect_err(|e| log::error!("Failed to create TechDetector instance: {}", e))?;
+
+    // 尝试设置全局实例（比较交换判空，保证仅一次成功）
+    // 并发初始化时落败方直接复用已设置的实例（幂等，不视为错误）
+    if !set_if_empty(detector) {
+        log::debug!("Global detector initialized concurrently by another task, discard duplicate instance");
+        return Ok(());
+    }
 
     log::info!("Global TechDetector initialized successfully");
     Ok(())
@@ -58,39 +77,87 @@ pub async fn init_global_detector(config: RuleConfig) -> RswResult<()> {
 /// 参数：
 /// - rule_lib: 预加载的规则库实例
 /// - config: 规则配置
+///
 /// 返回：初始化结果 | 错误
 pub fn init_global_detector_with_rules(rule_lib: RuleLibrary, config: RuleConfig) -> RswResult<()> {
     // 幂等检查：已初始化则直接返回
-    if GLOBAL_DETECTOR.get().is_some() {
+    if snapshot().is_some() {
         log::debug!("Global detector already initialized, skip reinitialization with custom rules");
         return Ok(());
     }
 
     // 同步创建检测器实例（注入自定义规则库）
-    let detector = TechDetector::with_rules(rule_lib, config).map_err(|e| {
-        RswappalyzerError::DetectorInitError(format!(
-            "Failed to create TechDetector with custom rules: {}",
-            e
-        ))
-    })?;
-
-    // 尝试设置全局实例
-    GLOBAL_DETECTOR.set(detector).map_err(|_| {
-        RswappalyzerError::DetectorInitError(
-            "Global detector initialization failed: instance already initialized by another thread".to_string()
-        )
-    })?;
+    let detector = TechDetector::with_rules(rule_lib, config)
+        .inspect_err(|e| log::error!("Failed to create TechDetector with custom rules: {}", e))?;
+
+    // 尝试设置全局实例（并发初始化落败时复用已设置的实例）
+    if !set_if_empty(detector) {
+        log::debug!("Global detector initialized concurrently by another thread, discard custom rule library instance");
+        return Ok(());
+    }
 
     log::info!("Global TechDetector initialized with custom rule library");
     Ok(())
 }
 
+/// 热更新全局检测器规则（重新加载并编译规则库后原子替换）
+/// 特性：
+/// 1. 新检测器在锁外构建，构建期间检测不受影响；tokio 运行时内编译在阻塞线程池执行，不占用异步工作线程
+/// 2. 替换前已取得快照的检测继续在旧规则上完成，之后的检测使用新规则
+/// 3. 加载/编译失败时保留当前检测器；未初始化时直接完成初始化
+///
+/// 参数：config - 规则配置（规则源/缓存/编译选项等）
+///
+/// 返回：替换结果 | 错误（规则加载/编译失败）
+pub async fn reload_rules(config: RuleConfig) -> RswResult<()> {
+    reload_rules_if_changed(config, &mut None).await.map(drop)
+}
+
+/// 规则内容变化时热更新全局检测器（后台更新使用）
+/// 规则指纹与上次替换时一致则跳过编译与替换
+/// 参数：
+/// - config: 规则配置
+/// - fingerprint: 上次替换时的规则指纹（替换后更新为新指纹）
+///
+/// 返回：新规则库技术数（规则未变化时为 None）| 错误（规则加载/编译失败，保留当前检测器）
+pub(super) async fn reload_rules_if_changed(
+    config: RuleConfig,
+    fingerprint: &mut Option<u64>,
+) -> RswResult<Option<usize>> {
+    let (detector, current) = TechDetector::new_if_changed(config, *fingerprint)
+        .await
+        .inspect_err(|e| log::error!("Failed to reload global TechDetector, keep current rules: {}", e))?;
+    let Some(detector) = detector else {
+        log::debug!("Rules unchanged, keep current global TechDetector");
+        return Ok(None);
+    };
+    let tech_count = detector.compiled_library().tech_patterns.len();
+    swap(detector);
+    *fingerprint = current;
+    log::info!("Global TechDetector rules reloaded | Tech count: {}", tech_count);
+    Ok(Some(tech_count))
+}
+
+/// 使用预加载规则库热更新全局检测器（同步接口）
+/// 参数：
+/// - rule_lib: 新规则库
+/// - config: 规则配置
+///
+/// 返回：替换结果 | 错误（编译失败时保留当前检测器）
+pub fn reload_with_rules(rule_lib: RuleLibrary, config: RuleConfig) -> RswResult<()> {
+    let detector = TechDetector::with_rules(rule_lib, config)
+        .inspect_err(|e| log::error!("Failed to reload global TechDetector with custom rules: {}", e))?;
+    swap(detector);
+    log::info!("Global TechDetector reloaded with custom rule library");
+    Ok(())
+}
+
 /// 懒加载初始化全局检测器（内部辅助函数）
 /// 特性：仅当实例未初始化时执行初始化
 /// 参数：config - 规则配置（默认配置）
 /// 返回：初始化结果 | 错误
 async fn lazy_init(config: RuleConfig) -> RswResult<()> {
-    if GLOBAL_DETECTOR.get().is_none() {
+    if snapshot().is_none() {
         log::debug!("Lazy initializing global TechDetector with default config");
         init_global_detector(config).await?;
     }
@@ -100,15 +167,16 @@ async fn lazy_init(config: RuleConfig) -> RswResult<()> {
 /// 获取全局检测器实例（异步，自动懒加载）
 /// 特性：
 /// 1. 自动懒加载：未初始化则使用默认配置初始化
-/// 2. 返回静态引用：进程生命周期内有效
+/// 2. 返回快照：热更新后仍可完成本次检测
 /// 3. 精准错误：明确返回初始化失败原因
-/// 返回：全局检测器静态引用 | 错误
-pub(crate) async fn get_global_detector() -> RswResult<&'static TechDetector> {
+///
+/// 返回：全局检测器快照 | 错误
+pub(crate) async fn get_global_detector() -> RswResult<Arc<TechDetector>> {
     // 自动懒加载初始化（使用默认配置）
     lazy_init(Default::default()).await?;
 
     // 获取实例并返回精准错误
-    GLOBAL_DETECTOR.get().ok_or_else(|| {
+    snapshot().ok_or_else(|| {
         RswappalyzerError::DetectorInitError(
             "Global detector initialization failed: instance not created".to_string()
         )
@@ -116,11 +184,10 @@ pub(crate) async fn get_global_detector() -> RswResult<&'static TechDetector> {
 }
 
 /// 同步获取全局检测器实例（无自动初始化）
-/// 注意：调用前需确保已手动初始化，否则返回错误
-/// 返回：全局检测器静态引用 | 未初始化错误
-#[allow(dead_code)]
-pub(crate) fn get_global_detector_sync() -> RswResult<&'static TechDetector> {
-    GLOBAL_DETECTOR.get().ok_or_else(|| {
+/// 注意：调用前需确保已手动初始化，否则返回错误；快照可移入阻塞线程执行检测，热更新后仍指向旧检测器
+/// 返回：全局检测器快照 | 未初始化错误
+pub fn get_global_detector_sync() -> RswResult<Arc<TechDetector>> {
+    snapshot().ok_or_else(|| {
         RswappalyzerError::DetectorNotInitialized(
             "Global TechDetector not initialized! Please call init_global_detector first".to_string()
         )
@@ -0,0 +1,362 @@
+//! 检测请求（持有所有权的检测输入，供 tower 服务/工作池跨线程传递）
+//! 通过 DetectInput::builder 组装全部输入后交给 TechDetector::run 统一检测，
+//! 旧的 detect / detect_with_* 系列保留为等价的便捷入口
+
+use http::header::{HeaderMap, HeaderValue, COOKIE};
+use rustc_hash::FxHashMap;
+
+use crate::utils::favicon::favicon_hash;
+use crate::utils::PreparedHeaders;
+
+/// 检测请求（持有所有权，可跨线程移动）
+#[derive(Debug, Clone, Default)]
+pub struct DetectInput {
+    /// HTTP 响应头
+    pub headers: HeaderMap,
+    /// 检测的URL列表
+    pub urls: Vec<String>,
+    /// HTTP 响应体
+    pub body: Vec<u8>,
+    /// 重定向链中间响应（按跳转顺序，不含最终响应）
+    pub hops: Vec<RedirectHop>,
+    /// 调用方采集的外部脚本地址（如无头浏览器记录的已加载脚本，与页面 `<script src>` 一并匹配）
+    pub scripts: Vec<String>,
+    /// JS 全局变量（属性链 → 属性值的字符串形式）
+    pub js_globals: Option<FxHashMap<String, String>>,
+    /// TLS 证书颁发者
+    pub cert_issuer: Option<String>,
+    /// DNS 记录（记录类型 → 记录值列表）
+    pub dns_records: Option<FxHashMap<String, Vec<String>>>,
+    /// favicon 原始字节（空字节跳过）
+    pub favicon: Option<Vec<u8>>,
+}
+
+/// 重定向链中间响应（SSO 网关/CDN 等仅出现在跳转响应中的 URL 与响应头）
+#[derive(Debug, Clone, Default)]
+pub struct RedirectHop {
+    /// 中间响应的请求 URL
+    pub url: String,
+    /// 中间响应头（Server/Set-Cookie 等参与 Header/Cookie 规则匹配）
+    pub headers: HeaderMap,
+}
+
+impl RedirectHop {
+    /// 创建重定向中间响应
+    pub fn new(url: impl Into<String>, headers: HeaderMap) -> Self {
+        Self {
+            url: url.into(),
+            headers,
+        }
+    }
+}
+
+impl DetectInput {
+    /// 创建检测请求
+    pub fn new(headers: HeaderMap, urls: Vec<String>, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            headers,
+            urls,
+            body: body.into(),
+            ..Self::default()
+        }
+    }
+
+    /// 创建检测请求构建器
+    pub fn builder() -> DetectInputBuilder {
+        DetectInputBuilder::default()
+    }
+
+    /// 追加重定向链中间响应（链式，按跳转顺序调用）
+    pub fn hop(mut self, url: impl Into<String>, headers: HeaderMap) -> Self {
+        self.hops.push(RedirectHop::new(url, headers));
+        self
+    }
+
+    /// 重定向链全部 URL（中间响应在前，最终响应 URL 在后）
+    pub(crate) fn chain_urls(&self) -> Vec<&str> {
+        self.hops
+            .iter()
+            .map(|hop| hop.url.as_str())
+            .chain(self.urls.iter().map(String::as_str))
+            .collect()
+    }
+
+    /// 预处理中间响应头（每跳独立预处理，同名 Header 不互相覆盖）
+    pub(crate) fn prepare_hops(&self) -> Vec<PreparedHeaders> {
+        self.hops.iter().map(|hop| PreparedHeaders::prepare(&hop.headers)).collect()
+    }
+
+    /// favicon mmh3 哈希（未提供或为空字节时返回 None）
+    pub(crate) fn favicon_hash(&self) -> Option<String> {
+        self.favicon
+            .as_deref()
+            .filter(|favicon| !favicon.is_empty())
+            .map(|favicon| favicon_hash(favicon).to_string())
+    }
+
+    /// 组装补充输入
+    /// 参数：
+    /// - hop_headers: 预处理后的中间响应头（prepare_hops）
+    /// - favicon_hash: favicon 哈希（favicon_hash）
+    ///
+    /// 返回：补充输入（借用 DetectInput 与调用方持有的预处理结果）
+    pub(crate) fn supplemental<'a>(
+        &'a self,
+        hop_headers: &'a [PreparedHeaders],
+        favicon_hash: Option<&'a str>,
+    ) -> SupplementalInput<'a> {
+        SupplementalInput {
+            js_globals: self.js_globals.as_ref(),
+            cert_issuer: self.cert_issuer.as_deref(),
+            dns_records: self.dns_records.as_ref(),
+            favicon_hash,
+            hop_headers,
+            script_srcs: &self.scripts,
+        }
+    }
+}
+
+/// 检测请求构建器（通过 DetectInput::builder 创建）
+#[derive(Debug, Clone, Default)]
+pub struct DetectInputBuilder {
+    input: DetectInput,
+    cookies: Vec<(String, String)>,
+}
+
+impl DetectInputBuilder {
+    /// 追加检测 URL
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.input.urls.push(url.into());
+        self
+    }
+
+    /// 追加多个检测 URL
+    pub fn urls<I, S>(mut self, urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.input.urls.extend(urls.into_iter().map(Into::into));
+        self
+    }
+
+    /// 设置 HTTP 响应头
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.input.headers = headers;
+        self
+    }
+
+    /// 追加 Cookie（名称 → 取值，build 时合并为 Cookie 请求头，非法取值跳过）
+    pub fn cookies<I, K, V>(mut self, cookies: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.cookies
+            .extend(cookies.into_iter().map(|(name, value)| (name.into(), value.into())));
+        self
+    }
+
+    /// 设置 HTTP 响应体
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.input.body = body.into();
+        self
+    }
+
+    /// 追加外部脚本地址
+    pub fn scripts<I, S>(mut self, scripts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.input.scripts.extend(scripts.into_iter().map(Into::into));
+        self
+    }
+
+    /// 追加重定向链中间响应（按跳转顺序调用）
+    pub fn hop(mut self, url: impl Into<String>, headers: HeaderMap) -> Self {
+        self.input.hops.push(RedirectHop::new(url, headers));
+        self
+    }
+
+    /// 设置 JS 全局变量
+    pub fn js_globals(mut self, js_globals: FxHashMap<String, String>) -> Self {
+        self.input.js_globals = Some(js_globals);
+        self
+    }
+
+    /// 设置 TLS 证书颁发者
+    pub fn cert_issuer(mut self, cert_issuer: impl Into<String>) -> Self {
+        self.input.cert_issuer = Some(cert_issuer.into());
+        self
+    }
+
+    /// 设置 DNS 记录
+    pub fn dns_records(mut self, dns_records: FxHashMap<String, Vec<String>>) -> Self {
+        self.input.dns_records = Some(dns_records);
+        self
+    }
+
+    /// 设置 favicon 原始字节
+    pub fn favicon(mut self, favicon: impl Into<Vec<u8>>) -> Self {
+        self.input.favicon = Some(favicon.into());
+        self
+    }
+
+    /// 构建检测请求
+    pub fn build(self) -> DetectInput {
+        let mut input = self.input;
+        for (name, value) in self.cookies {
+            match HeaderValue::from_str(&format!("{}={}", name, value)) {
+                Ok(cookie) => {
+                    input.headers.append(COOKIE, cookie);
+                }
+                Err(_) => log::debug!("Skip invalid cookie | Name: {}", name),
+            }
+        }
+        input
+    }
+}
+
+/// 调用方补充的非 HTTP 响应输入（需无头浏览器 / TLS 握手 / DNS 解析 / favicon 拉取等外部采集，未提供的作用域跳过）
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct SupplementalInput<'a> {
+    /// JS 全局变量（属性链 → 属性值的字符串形式）
+    pub js_globals: Option<&'a FxHashMap<String, String>>,
+    /// TLS 证书颁发者
+    pub cert_issuer: Option<&'a str>,
+    /// DNS 记录（记录类型 → 记录值列表）
+    pub dns_records: Option<&'a FxHashMap<String, Vec<String>>>,
+    /// favicon mmh3 哈希（由 favicon 字节计算，十进制有符号整数字符串）
+    pub favicon_hash: Option<&'a str>,
+    /// 重定向链中间响应头（与最终响应头一并参与 Header/Cookie 规则匹配）
+    pub hop_headers: &'a [PreparedHeaders],
+    /// 外部脚本地址（与页面 `<script src>` 一并参与 Script 规则匹配）
+    pub script_srcs: &'a [String],
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{detector, names};
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "Acme SSO": { "cats": [69], "headers": { "Server": "^AcmeSSO/([\\d.]+)$\\;version:\\1" }, "cookies": { "acme_sso": "" } },
+            "Acme Edge": { "cats": [31], "hostname": "*.acme-edge.net" },
+            "Nginx": { "cats": [22], "headers": { "Server": "nginx" } }
+        }
+    }"#;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    fn redirected() -> DetectInput {
+        let final_headers = headers(&[("server", "nginx")]);
+        DetectInput::new(final_headers, vec!["https://app.example.com/".into()], "<html></html>")
+            .hop("https://cdn.acme-edge.net/login", HeaderMap::new())
+            .hop(
+                "https://sso.example.com/auth",
+                headers(&[("server", "AcmeSSO/2.4"), ("set-cookie", "acme_sso=1; Path=/")]),
+            )
+    }
+
+    #[test]
+    fn test_chain_urls_and_hop_headers() {
+        let input = redirected();
+        assert_eq!(
+            input.chain_urls(),
+            [
+                "https://cdn.acme-edge.net/login",
+                "https://sso.example.com/auth",
+                "https://app.example.com/"
+            ]
+        );
+        // 每跳独立预处理，同名 Server 头不与最终响应互相覆盖
+        let hops = input.prepare_hops();
+        assert_eq!(hops.len(), 2);
+        assert!(hops[0].is_empty());
+        assert_eq!(hops[1].headers()["server"], "AcmeSSO/2.4");
+        assert!(hops[1].cookies().contains_key("acme_sso"));
+    }
+
+    #[test]
+    fn test_redirect_hops_detected() {
+        let detector = detector(RULES);
+        let input = redirected();
+        let result = detector.run(&input).unwrap();
+        assert_eq!(names(&result), ["Acme Edge", "Acme SSO", "Nginx"]);
+        let sso = result.find_by_slug("acme-sso").unwrap();
+        assert_eq!(sso.version.as_deref(), Some("2.4"));
+
+        // 无中间响应时仅检测最终响应
+        let input = DetectInput { hops: Vec::new(), ..input };
+        assert_eq!(names(&detector.run(&input).unwrap()), ["Nginx"]);
+    }
+
+    #[test]
+    fn test_builder_fields() {
+        let input = DetectInput::builder()
+            .urls(["https://example.com/", "https://example.com/app"])
+            .cookies([("acme_session", "1"), ("bad", "a\nb"), ("theme", "dark")])
+            .scripts(["/a.js"])
+            .scripts(["/b.js"])
+            .hop("https://sso.example.com/", HeaderMap::new())
+            .favicon(Vec::new())
+            .build();
+        assert_eq!(input.urls.len(), 2);
+        // Cookie 合并为请求头，非法取值跳过
+        let cookies: Vec<_> = input.headers.get_all(COOKIE).iter().collect();
+        assert_eq!(cookies, ["acme_session=1", "theme=dark"]);
+        assert_eq!(input.scripts, ["/a.js", "/b.js"]);
+        assert_eq!(input.hops[0].url, "https://sso.example.com/");
+        // 空 favicon 不计算哈希
+        assert_eq!(input.favicon_hash(), None);
+        assert!(input.cert_issuer.is_none() && input.dns_records.is_none());
+    }
+
+    #[test]
+    fn test_builder_run() {
+        let detector = detector(
+            r#"{
+                "technologies": {
+                    "Acme Session": { "cats": [69], "cookies": { "acme_session": "" } },
+                    "Acme Widget": { "cats": [5], "scriptSrc": "acme-widget\\.js" },
+                    "Acme CA": { "cats": [70], "certIssuer": "Acme CA" },
+                    "Acme Portal": { "cats": [1], "html": "acme-portal-root" }
+                }
+            }"#,
+        );
+        let body = "<html><body><div id=\"acme-portal-root\"></div></body></html>";
+        let input = DetectInput::builder()
+            .url("https://example.com/")
+            .cookies([("acme_session", "1")])
+            .body(body)
+            .scripts(["https://cdn.example.com/acme-widget.js"])
+            .cert_issuer("Acme CA R3")
+            .build();
+        let result = detector.run(&input).unwrap();
+        assert_eq!(names(&result), ["Acme CA", "Acme Portal", "Acme Session", "Acme Widget"]);
+
+        // run 与旧的 detect_with_* 入口结果一致
+        let legacy = detector
+            .detect_with_cert_issuer(&input.headers, &["https://example.com/"], body.as_bytes(), "Acme CA R3")
+            .unwrap();
+        let input = DetectInput { scripts: Vec::new(), ..input };
+        assert_eq!(
+            serde_json::to_value(detector.run(&input).unwrap()).unwrap(),
+            serde_json::to_value(&legacy).unwrap()
+        );
+
+        // 无 HTML 文档时外部脚本地址同样参与匹配
+        let input = DetectInput::builder().scripts(["/static/acme-widget.js"]).build();
+        assert_eq!(names(&detector.run(&input).unwrap()), ["Acme Widget"]);
+    }
+}
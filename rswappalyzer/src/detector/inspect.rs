@@ -0,0 +1,188 @@
+//! 检测输入快照（检测器实际"看到"了什么）
+//! 复用检测流程的预处理步骤（Header转换/Cookie解析/Content-Type路由/HTML输入守卫与提取/令牌提取），但不执行任何匹配，
+//! 用于排查"提取器到底喂给分析器什么"、以及基于真实流量编写新规则
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use http::header::HeaderMap;
+use rswappalyzer_engine::scope_pruner::PruneScope;
+use rswappalyzer_engine::url_host;
+use rustc_hash::FxHashSet;
+use serde::{Deserialize, Serialize};
+
+use super::detector::{PreparedInput, TechDetector};
+use crate::utils::{BodyKind, PreparedHeaders};
+use crate::ExtractStats;
+
+/// 每个作用域保留的令牌样本上限
+pub const INSPECT_TOKEN_CAP: usize = 256;
+
+/// 单个作用域的令牌快照
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScopeTokens {
+    /// 令牌总数
+    pub total: usize,
+    /// 令牌样本（有序，最多 INSPECT_TOKEN_CAP 个）
+    pub sample: Vec<String>,
+}
+
+impl ScopeTokens {
+    fn from_tokens(tokens: &FxHashSet<String>) -> Self {
+        let sorted: BTreeSet<&String> = tokens.iter().collect();
+        Self {
+            total: tokens.len(),
+            sample: sorted.into_iter().take(INSPECT_TOKEN_CAP).cloned().collect(),
+        }
+    }
+
+    /// 样本是否被截断
+    pub fn is_truncated(&self) -> bool {
+        self.total > self.sample.len()
+    }
+}
+
+/// 检测输入快照
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputSnapshot {
+    /// 检测的URL列表
+    pub urls: Vec<String>,
+    /// 归一化后的单值Header（Key小写，有序）
+    pub headers: BTreeMap<String, String>,
+    /// 解析后的Cookie（Cookie名小写，有序）
+    pub cookies: BTreeMap<String, Vec<String>>,
+    /// 按 Content-Type 判定的响应体类型
+    pub body_kind: BodyKind,
+    /// 原始响应体字节数
+    pub body_len: usize,
+    /// 参与 HTML 内容模式匹配的字节数（HTML 文档经输入守卫后的长度 / CSS 原文长度；0 表示未做 HTML 分析）
+    pub html_len: usize,
+    /// 提取的 meta 标签（name, content）
+    pub meta_tags: Vec<(String, String)>,
+    /// 提取的 script src
+    pub script_srcs: Vec<String>,
+    /// HTML 提取统计（是否触达提取上限）
+    pub extract_stats: ExtractStats,
+    /// 各作用域的输入令牌（作用域名 -> 令牌快照）
+    pub tokens: BTreeMap<String, ScopeTokens>,
+}
+
+impl TechDetector {
+    /// 生成检测输入快照（不执行匹配）
+    /// 预处理与 detect 完全一致，可据此确认各分析器实际收到的内容与令牌
+    /// 参数：
+    /// - headers: HTTP头信息（HeaderMap）
+    /// - urls: 检测的URL列表
+    /// - body: HTTP响应体（字节数组）
+    ///
+    /// 返回：输入快照
+    pub fn inspect_input(&self, headers: &HeaderMap, urls: &[&str], body: &[u8]) -> InputSnapshot {
+        let prepared = PreparedHeaders::prepare(headers);
+        let routed = self.prepare_body(prepared.headers(), body);
+
+        let mut tokens = BTreeMap::new();
+        let mut push_scope = |scope: PruneScope, scope_tokens: &FxHashSet<String>| {
+            tokens.insert(scope.as_str().to_string(), ScopeTokens::from_tokens(scope_tokens));
+        };
+        let input = PreparedInput::new(urls, &routed);
+        push_scope(PruneScope::Url, input.url_tokens());
+        push_scope(PruneScope::Hostname, &urls.iter().filter_map(|url| url_host(url)).collect());
+        push_scope(PruneScope::Header, &prepared.header_tokens);
+        push_scope(PruneScope::Cookie, &prepared.cookie_tokens);
+        if !routed.html.is_empty() {
+            push_scope(PruneScope::Html, input.html_tokens());
+        }
+        if routed.is_document() {
+            let mut script_tokens = input.script_src_tokens().clone();
+            script_tokens.extend(input.inline_script_tokens().iter().cloned());
+            push_scope(PruneScope::Script, &script_tokens);
+            push_scope(PruneScope::Meta, input.meta_tokens());
+            push_scope(PruneScope::Title, input.title_tokens());
+            push_scope(PruneScope::Comment, input.comment_tokens());
+        } else if !routed.script.is_empty() {
+            push_scope(PruneScope::Script, input.script_body_tokens());
+        }
+
+        InputSnapshot {
+            urls: urls.iter().map(|url| url.to_string()).collect(),
+            headers: prepared.headers().iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            cookies: prepared.cookies().iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            body_kind: routed.kind,
+            body_len: body.len(),
+            html_len: routed.html.len(),
+            meta_tags: routed.extract.meta_tags,
+            script_srcs: routed.extract.script_srcs,
+            extract_stats: routed.extract.stats,
+            tokens,
+        }
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::HeaderValue;
+
+    use super::*;
+    use crate::test_support::detector;
+
+    const RULES: &str = r#"{"technologies": { "Acme": { "html": "acme-cms" } }}"#;
+
+    const PAGE: &[u8] = br#"<html><head>
+        <meta name="generator" content="Acme 2.1">
+        <title>Acme Portal</title>
+        <script src="https://cdn.example.com/acme.min.js"></script>
+        </head><body><p>Powered by acme-cms</p></body></html>"#;
+
+    fn headers(content_type: &'static str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("server", HeaderValue::from_static("nginx/1.25"));
+        headers.insert("content-type", HeaderValue::from_static(content_type));
+        headers.insert("set-cookie", HeaderValue::from_static("SESSID=abc; Path=/"));
+        headers
+    }
+
+    #[test]
+    fn test_html_snapshot() {
+        let urls = ["https://www.acme.test/"];
+        let snapshot = detector(RULES).inspect_input(&headers("text/html"), &urls, PAGE);
+        assert_eq!(snapshot.urls, vec!["https://www.acme.test/"]);
+        assert_eq!(snapshot.headers["server"], "nginx/1.25");
+        assert!(snapshot.cookies.contains_key("sessid"));
+        assert_eq!(snapshot.body_kind, BodyKind::Html);
+        assert_eq!(snapshot.body_len, PAGE.len());
+        assert!(snapshot.html_len > 0);
+        assert_eq!(snapshot.meta_tags, vec![("generator".to_string(), "Acme 2.1".to_string())]);
+        assert_eq!(snapshot.script_srcs, vec!["https://cdn.example.com/acme.min.js"]);
+
+        for scope in ["url", "hostname", "header", "cookie", "html", "script", "meta", "title", "comment"] {
+            assert!(snapshot.tokens.contains_key(scope), "missing scope {}", scope);
+        }
+        assert!(snapshot.tokens["html"].sample.iter().any(|t| t == "acme"));
+        assert!(snapshot.tokens["header"].sample.iter().any(|t| t == "nginx"));
+        assert_eq!(snapshot.tokens["hostname"].sample, vec!["www.acme.test"]);
+    }
+
+    #[test]
+    fn test_non_document_skips_html_scopes() {
+        let body = b"window.acmeWidget = function () { return 1; };";
+        let snapshot = detector(RULES).inspect_input(&headers("application/javascript"), &[], body);
+        assert_eq!(snapshot.body_kind, BodyKind::Script);
+        assert_eq!(snapshot.html_len, 0);
+        assert!(snapshot.meta_tags.is_empty());
+        assert!(!snapshot.tokens.contains_key("html"));
+        assert!(!snapshot.tokens.contains_key("meta"));
+        assert!(snapshot.tokens["script"].total > 0);
+    }
+
+    #[test]
+    fn test_token_sample_cap() {
+        let tokens: FxHashSet<String> =
+            (0..INSPECT_TOKEN_CAP + 10).map(|i| format!("token{:04}", i)).collect();
+        let scope = ScopeTokens::from_tokens(&tokens);
+        assert_eq!(scope.total, INSPECT_TOKEN_CAP + 10);
+        assert_eq!(scope.sample.len(), INSPECT_TOKEN_CAP);
+        assert_eq!(scope.sample[0], "token0000");
+        assert!(scope.is_truncated());
+        assert!(!ScopeTokens::default().is_truncated());
+    }
+}
@@ -1,10 +1,57 @@
 //! 检测模块：技术检测核心逻辑
+pub mod capabilities;
+pub mod corpus;
 pub mod global;
 pub mod detector;
+pub mod evidence;
+pub mod input;
+pub mod inspect;
+pub mod options;
+pub mod post_process;
+#[cfg(feature = "pool")]
+pub mod pool;
+pub mod profile_cache;
+pub mod progressive;
+pub mod query;
+#[cfg(feature = "scanner")]
+pub mod scanner;
+pub mod self_test;
+#[cfg(feature = "tower")]
+pub mod service;
+pub mod stats;
+pub mod trace;
+pub mod triage;
+#[cfg(feature = "auto-update")]
+pub mod updater;
 
 // 导出核心接口
-pub use self::global::{init_global_detector, init_global_detector_with_rules};
+pub use self::capabilities::{features, DetectorCapabilities, FeatureReport, RuleSource};
+pub use self::corpus::CorpusReport;
+pub use self::evidence::EvidenceCapture;
+pub use self::input::{DetectInput, DetectInputBuilder, RedirectHop};
+pub use self::inspect::{InputSnapshot, ScopeTokens, INSPECT_TOKEN_CAP};
+pub use self::options::{DetectBuilder, DetectOptions, ScopeMask};
+pub use self::post_process::{CategoryFilter, CategoryFilterMode, ConfidenceThreshold, ResultPostProcessor};
+pub use self::global::{
+    get_global_detector_sync, init_global_detector, init_global_detector_with_rules, reload_rules, reload_with_rules,
+};
 pub use self::detector::{
     TechDetector,
     detect,
+    detect_builder,
+    detect_with,
 };
+#[cfg(feature = "pool")]
+pub use self::pool::{DetectReceiver, DetectorPool, SubmitError};
+pub use self::profile_cache::ProfileCache;
+pub use self::progressive::{DetectStage, ProgressiveDetection, TechnologyEvent};
+#[cfg(feature = "scanner")]
+pub use self::scanner::ScanOptions;
+pub use self::self_test::{SelfTestCase, SelfTestReport, SelfTestStatus};
+#[cfg(feature = "tower")]
+pub use self::service::{DetectService, DetectServiceBuilder};
+pub use self::stats::{DetectorStats, SourceStats};
+pub use self::trace::{DetectTrace, StageTrace};
+pub use self::triage::TriageResult;
+#[cfg(feature = "auto-update")]
+pub use self::updater::DetectionUpdaterService;
@@ -0,0 +1,358 @@
+//! 单次检测选项与全局单例检测构建器
+//! RuleConfig 作用于检测器整个生命周期，DetectOptions 仅作用于单次检测调用：
+//! 1. scopes：仅执行指定作用域的分析器（None 表示全部作用域；也可由 ScopeMask 位集设置）
+//! 2. timeout：检测耗时上限（在各分析阶段之间检查，超时返回 DetectError）
+//! 3. extract_limits：覆盖配置中的 HTML 提取上限
+//! 4. evidence：证据采集（采样 + 字节预算，None 表示不采集）
+//!
+//! 全局单例用户无需手动构建 TechDetector：
+//! `detector::detect_builder().headers(h).body(b).scopes([PruneScope::Header]).run().await`
+
+use std::ops::{BitAnd, BitOr, BitOrAssign, Not};
+use std::time::{Duration, Instant};
+
+use http::header::HeaderMap;
+use rswappalyzer_engine::scope_pruner::PruneScope;
+
+use super::evidence::EvidenceCapture;
+use super::input::DetectInput;
+use crate::error::{RswResult, RswappalyzerError};
+use crate::utils::matched_scopes::scope_bit;
+use crate::{DetectResult, ExtractLimits};
+
+/// 依赖响应体的作用域
+pub(crate) const BODY_SCOPES: [PruneScope; 6] = [
+    PruneScope::Html,
+    PruneScope::Script,
+    PruneScope::Meta,
+    PruneScope::Dom,
+    PruneScope::Title,
+    PruneScope::Comment,
+];
+
+/// 作用域位集（按 PruneScope::ALL 顺序置位，可用 `|` 组合）
+/// 仅有部分输入维度时（如只有响应头、只有响应体）跳过缺失维度的分析器与令牌提取
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScopeMask(u16);
+
+impl ScopeMask {
+    /// 空位集（不执行任何作用域）
+    pub const NONE: Self = Self(0);
+    /// URL
+    pub const URL: Self = Self(1 << 0);
+    /// HTML
+    pub const HTML: Self = Self(1 << 1);
+    /// Script
+    pub const SCRIPT: Self = Self(1 << 2);
+    /// Header
+    pub const HEADER: Self = Self(1 << 3);
+    /// Meta
+    pub const META: Self = Self(1 << 4);
+    /// Cookie
+    pub const COOKIE: Self = Self(1 << 5);
+    /// 主机名
+    pub const HOSTNAME: Self = Self(1 << 6);
+    /// DOM
+    pub const DOM: Self = Self(1 << 7);
+    /// JS 全局变量
+    pub const JS: Self = Self(1 << 8);
+    /// TLS 证书颁发者
+    pub const CERT_ISSUER: Self = Self(1 << 9);
+    /// DNS 记录
+    pub const DNS: Self = Self(1 << 10);
+    /// Favicon 哈希
+    pub const FAVICON_HASH: Self = Self(1 << 11);
+    /// 页面标题
+    pub const TITLE: Self = Self(1 << 12);
+    /// HTML 注释
+    pub const COMMENT: Self = Self(1 << 13);
+    /// 全部作用域
+    pub const ALL: Self = Self((1 << PruneScope::ALL.len()) - 1);
+    /// 仅依赖 HTTP 响应头的作用域（Header/Cookie）
+    pub const HEADERS_ONLY: Self = Self(Self::HEADER.0 | Self::COOKIE.0);
+    /// 依赖响应体的作用域（HTML/Script/Meta/DOM/Title/Comment）
+    pub const BODY: Self =
+        Self(Self::HTML.0 | Self::SCRIPT.0 | Self::META.0 | Self::DOM.0 | Self::TITLE.0 | Self::COMMENT.0);
+
+    /// 是否包含指定作用域
+    pub fn contains(self, scope: PruneScope) -> bool {
+        self.0 & scope_bit(scope) != 0
+    }
+
+    /// 是否为空位集
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// 位集包含的作用域（按 PruneScope::ALL 顺序）
+    pub fn scopes(self) -> impl Iterator<Item = PruneScope> {
+        PruneScope::ALL.into_iter().filter(move |scope| self.contains(*scope))
+    }
+}
+
+impl Default for ScopeMask {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+impl From<PruneScope> for ScopeMask {
+    fn from(scope: PruneScope) -> Self {
+        Self(scope_bit(scope))
+    }
+}
+
+impl FromIterator<PruneScope> for ScopeMask {
+    fn from_iter<I: IntoIterator<Item = PruneScope>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::NONE, |mask, scope| mask | scope.into())
+    }
+}
+
+impl BitOr for ScopeMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ScopeMask {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for ScopeMask {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl Not for ScopeMask {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(!self.0 & Self::ALL.0)
+    }
+}
+
+/// 单次检测选项
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DetectOptions {
+    /// 仅执行的作用域（None 表示全部作用域）
+    pub scopes: Option<Vec<PruneScope>>,
+    /// 检测耗时上限（None 表示不限制）
+    pub timeout: Option<Duration>,
+    /// HTML 提取上限（None 表示使用检测器配置）
+    pub extract_limits: Option<ExtractLimits>,
+    /// 证据采集选项（None 表示不采集）
+    pub evidence: Option<EvidenceCapture>,
+}
+
+impl DetectOptions {
+    /// 仅执行指定作用域的分析器
+    pub fn scopes(mut self, scopes: impl IntoIterator<Item = PruneScope>) -> Self {
+        self.scopes = Some(scopes.into_iter().collect());
+        self
+    }
+
+    /// 仅执行作用域位集内的分析器
+    pub fn scope_mask(self, mask: ScopeMask) -> Self {
+        self.scopes(mask.scopes())
+    }
+
+    /// 设置检测耗时上限
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// 覆盖 HTML 提取上限
+    pub fn extract_limits(mut self, limits: ExtractLimits) -> Self {
+        self.extract_limits = Some(limits);
+        self
+    }
+
+    /// 开启证据采集
+    pub fn evidence(mut self, capture: EvidenceCapture) -> Self {
+        self.evidence = Some(capture);
+        self
+    }
+
+    /// 作用域是否需要执行
+    pub fn allows(&self, scope: PruneScope) -> bool {
+        self.scopes.as_ref().is_none_or(|scopes| scopes.contains(&scope))
+    }
+
+    /// 是否需要处理响应体（HTML/Script/Meta/Dom/Title/Comment 作用域均未启用时跳过响应体预处理）
+    pub(crate) fn needs_body(&self) -> bool {
+        BODY_SCOPES
+            .into_iter()
+            .any(|scope| self.allows(scope))
+    }
+
+    /// 计算本次检测的截止时间
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.timeout.map(|timeout| Instant::now() + timeout)
+    }
+
+    /// 检查是否已超过截止时间
+    /// 参数：deadline - 截止时间（None 表示不限制）
+    ///
+    /// 返回：未超时 | 超时错误
+    pub(crate) fn check_deadline(&self, deadline: Option<Instant>) -> RswResult<()> {
+        match (deadline, self.timeout) {
+            (Some(deadline), Some(timeout)) if Instant::now() >= deadline => Err(RswappalyzerError::DetectError(
+                format!("Detection timed out after {:?}", timeout),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 全局单例检测构建器（通过 detector::detect_builder 创建）
+#[derive(Debug, Clone, Default)]
+pub struct DetectBuilder {
+    input: DetectInput,
+    options: DetectOptions,
+}
+
+impl DetectBuilder {
+    /// 设置 HTTP 响应头
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.input.headers = headers;
+        self
+    }
+
+    /// 追加检测 URL
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.input.urls.push(url.into());
+        self
+    }
+
+    /// 追加多个检测 URL
+    pub fn urls<I, S>(mut self, urls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.input.urls.extend(urls.into_iter().map(Into::into));
+        self
+    }
+
+    /// 设置 HTTP 响应体
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.input.body = body.into();
+        self
+    }
+
+    /// 追加重定向链中间响应（按跳转顺序调用）
+    pub fn hop(mut self, url: impl Into<String>, headers: HeaderMap) -> Self {
+        self.input = self.input.hop(url, headers);
+        self
+    }
+
+    /// 仅执行指定作用域的分析器
+    pub fn scopes(mut self, scopes: impl IntoIterator<Item = PruneScope>) -> Self {
+        self.options = self.options.scopes(scopes);
+        self
+    }
+
+    /// 设置检测耗时上限
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options = self.options.timeout(timeout);
+        self
+    }
+
+    /// 覆盖 HTML 提取上限
+    pub fn extract_limits(mut self, limits: ExtractLimits) -> Self {
+        self.options = self.options.extract_limits(limits);
+        self
+    }
+
+    /// 开启证据采集
+    pub fn evidence(mut self, capture: EvidenceCapture) -> Self {
+        self.options = self.options.evidence(capture);
+        self
+    }
+
+    /// 整体替换检测选项
+    pub fn options(mut self, options: DetectOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// 使用全局检测器执行检测（未初始化时按默认配置懒加载）
+    /// 返回：检测结果 | 错误
+    pub async fn run(self) -> RswResult<DetectResult> {
+        super::detector::detect_with(&self.options, &self.input).await
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::header::HeaderValue;
+
+    use super::*;
+    use crate::test_support::{detector, names};
+
+    #[test]
+    fn test_scope_mask_ops() {
+        assert_eq!(ScopeMask::default(), ScopeMask::ALL);
+        assert_eq!(ScopeMask::ALL.scopes().collect::<Vec<_>>(), PruneScope::ALL);
+        for scope in PruneScope::ALL {
+            assert!(ScopeMask::from(scope).contains(scope));
+            assert!(!(!ScopeMask::from(scope)).contains(scope));
+        }
+        // 取反不越过 ALL 的位宽
+        assert_eq!(!ScopeMask::NONE, ScopeMask::ALL);
+        assert!((!ScopeMask::ALL).is_empty());
+
+        let mask: ScopeMask = [PruneScope::Header, PruneScope::Cookie].into_iter().collect();
+        assert_eq!(mask, ScopeMask::HEADERS_ONLY);
+        let mut mask = ScopeMask::HEADER;
+        mask |= ScopeMask::COOKIE;
+        assert_eq!(mask, ScopeMask::HEADERS_ONLY);
+        assert!((ScopeMask::HEADERS_ONLY & ScopeMask::BODY).is_empty());
+        assert_eq!(ScopeMask::BODY.scopes().count(), BODY_SCOPES.len());
+        assert!(BODY_SCOPES.into_iter().all(|scope| ScopeMask::BODY.contains(scope)));
+    }
+
+    #[test]
+    fn test_scope_mask_options() {
+        let options = DetectOptions::default().scope_mask(ScopeMask::HEADERS_ONLY);
+        assert_eq!(options.scopes.as_deref(), Some(&[PruneScope::Header, PruneScope::Cookie][..]));
+        assert!(!options.needs_body());
+        assert!(DetectOptions::default().scope_mask(ScopeMask::TITLE).needs_body());
+        assert!(!DetectOptions::default().scope_mask(ScopeMask::NONE).allows(PruneScope::Url));
+    }
+
+    #[test]
+    fn test_detect_with_scopes() {
+        let detector = detector(
+            r#"{
+                "technologies": {
+                    "Nginx": { "cats": [22], "headers": { "Server": "nginx" } },
+                    "Acme SSO": { "cats": [69], "cookies": { "acme_sso": "" } },
+                    "Acme Portal": { "cats": [1], "html": "acme-portal-root" }
+                }
+            }"#,
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert("server", HeaderValue::from_static("nginx"));
+        headers.insert("set-cookie", HeaderValue::from_static("acme_sso=1"));
+        headers.insert("content-type", HeaderValue::from_static("text/html"));
+        let body = b"<html><body><div id=\"acme-portal-root\"></div></body></html>";
+        let detect = |scopes: ScopeMask| detector.detect_with_scopes(&headers, &[], body, scopes).unwrap();
+
+        assert_eq!(names(&detect(ScopeMask::ALL)), ["Acme Portal", "Acme SSO", "Nginx"]);
+        assert_eq!(names(&detect(ScopeMask::HEADERS_ONLY)), ["Acme SSO", "Nginx"]);
+        assert_eq!(names(&detect(ScopeMask::HEADER)), ["Nginx"]);
+        // 跳过 Header/Cookie 时仍保留 Content-Type 以路由响应体
+        assert_eq!(names(&detect(ScopeMask::BODY)), ["Acme Portal"]);
+        assert!(detect(ScopeMask::NONE).technologies.is_empty());
+    }
+}
@@ -0,0 +1,207 @@
+//! 有界检测工作池（pool 特性）
+//! 固定数量的工作线程共享一个有界队列，提交方通过 oneshot 通道异步获取结果：
+//! 1. submit：队列满时异步等待（背压），不会无限堆积请求
+//! 2. try_submit：队列满时立即返回 SubmitError::Full，并交还原请求（便于调用方降级/丢弃）
+//! 3. 内存上界：最多 queue_cap 个排队请求 + workers 个执行中请求
+//!
+//! 工作线程为独立的系统线程（不占用 Tokio 运行时线程），生命周期结束方式：
+//! 1. shutdown().await / shutdown_blocking()：优雅关闭，停止接收新请求，处理完已入队请求并回收工作线程
+//! 2. drop：立即放弃已入队请求（接收端收到通道关闭错误），工作线程完成执行中的检测后自行退出，
+//!    drop 本身不阻塞，宿主程序退出时不会因排队请求而挂起
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::JoinHandle;
+
+use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
+
+use super::detector::TechDetector;
+use super::input::DetectInput;
+use crate::error::{RswResult, RswappalyzerError};
+use crate::DetectResult;
+
+/// 检测结果接收端
+pub type DetectReceiver = oneshot::Receiver<RswResult<DetectResult>>;
+
+/// 队列中的检测任务
+type Job = (DetectInput, oneshot::Sender<RswResult<DetectResult>>);
+
+/// 提交失败（交还原请求，装箱以避免 Result 体积过大）
+#[derive(Debug, Error)]
+pub enum SubmitError {
+    /// 队列已满（仅 try_submit）
+    #[error("Detector pool queue is full")]
+    Full(Box<DetectInput>),
+    /// 工作池已关闭
+    #[error("Detector pool is closed")]
+    Closed(Box<DetectInput>),
+}
+
+impl SubmitError {
+    /// 取回未被处理的请求
+    pub fn into_input(self) -> DetectInput {
+        match self {
+            SubmitError::Full(input) | SubmitError::Closed(input) => *input,
+        }
+    }
+}
+
+/// 有界检测工作池
+#[derive(Debug)]
+pub struct DetectorPool {
+    /// 任务队列发送端（关闭后为 None）
+    sender: Option<mpsc::Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+    queue_cap: usize,
+    /// 放弃已入队请求标记（drop 时置位）
+    aborted: Arc<AtomicBool>,
+    /// 工作线程退出信号（每个工作线程持有一个发送端，全部退出后通道关闭）
+    exited: mpsc::Receiver<()>,
+}
+
+impl DetectorPool {
+    /// 创建工作池并启动工作线程
+    /// 参数：
+    /// - detector: 共享检测器
+    /// - workers: 工作线程数（最少 1）
+    /// - queue_cap: 排队请求上限（最少 1）
+    ///
+    /// 返回：工作池实例 | 线程创建失败
+    pub fn new(detector: Arc<TechDetector>, workers: usize, queue_cap: usize) -> RswResult<Self> {
+        let queue_cap = queue_cap.max(1);
+        let (sender, receiver) = mpsc::channel::<Job>(queue_cap);
+        let receiver = Arc::new(Mutex::new(receiver));
+        let aborted = Arc::new(AtomicBool::new(false));
+        let (exit_signal, exited) = mpsc::channel::<()>(1);
+
+        let workers = (0..workers.max(1))
+            .map(|i| {
+                let detector = detector.clone();
+                let receiver = receiver.clone();
+                let aborted = aborted.clone();
+                let exit_signal = exit_signal.clone();
+                std::thread::Builder::new()
+                    .name(format!("rswappalyzer-pool-{}", i))
+                    .spawn(move || {
+                        Self::worker_loop(&detector, &receiver, &aborted);
+                        drop(exit_signal);
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            sender: Some(sender),
+            workers,
+            queue_cap,
+            aborted,
+            exited,
+        })
+    }
+
+    /// 工作线程主循环：持锁等待下一个任务，取到后释放锁再执行检测
+    fn worker_loop(detector: &TechDetector, receiver: &Mutex<mpsc::Receiver<Job>>, aborted: &AtomicBool) {
+        loop {
+            let job = receiver
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .blocking_recv();
+            let Some((input, reply)) = job else {
+                return;
+            };
+            // 工作池已被 drop：丢弃剩余请求（drop reply 使接收端收到通道关闭错误）
+            if aborted.load(Ordering::Acquire) {
+                continue;
+            }
+            // 提交方已放弃等待时跳过检测
+            if reply.is_closed() {
+                continue;
+            }
+            let result = catch_unwind(AssertUnwindSafe(|| detector.run(&input)))
+            .unwrap_or_else(|_| Err(RswappalyzerError::DetectError("Detection panicked".to_string())));
+            let _ = reply.send(result);
+        }
+    }
+
+    /// 提交检测请求（队列满时异步等待空位）
+    /// 参数：input - 检测请求
+    ///
+    /// 返回：结果接收端 | 工作池已关闭
+    pub async fn submit(&self, input: DetectInput) -> Result<DetectReceiver, SubmitError> {
+        let (reply, receiver) = oneshot::channel();
+        let Some(sender) = &self.sender else {
+            return Err(SubmitError::Closed(Box::new(input)));
+        };
+        sender
+            .send((input, reply))
+            .await
+            .map_err(|e| SubmitError::Closed(Box::new(e.0 .0)))?;
+        Ok(receiver)
+    }
+
+    /// 尝试提交检测请求（不等待）
+    /// 参数：input - 检测请求
+    ///
+    /// 返回：结果接收端 | 队列已满/工作池已关闭（交还原请求）
+    pub fn try_submit(&self, input: DetectInput) -> Result<DetectReceiver, SubmitError> {
+        let (reply, receiver) = oneshot::channel();
+        let Some(sender) = &self.sender else {
+            return Err(SubmitError::Closed(Box::new(input)));
+        };
+        sender.try_send((input, reply)).map_err(|e| match e {
+            mpsc::error::TrySendError::Full((input, _)) => SubmitError::Full(Box::new(input)),
+            mpsc::error::TrySendError::Closed((input, _)) => SubmitError::Closed(Box::new(input)),
+        })?;
+        Ok(receiver)
+    }
+
+    /// 工作线程数
+    pub fn workers(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// 排队请求上限
+    pub fn queue_cap(&self) -> usize {
+        self.queue_cap
+    }
+
+    /// 当前排队中的请求数
+    pub fn queued(&self) -> usize {
+        self.sender
+            .as_ref()
+            .map_or(0, |sender| self.queue_cap - sender.capacity())
+    }
+
+    /// 优雅关闭工作池：停止接收新请求，异步等待已入队请求处理完毕后回收工作线程
+    /// 不占用运行时线程等待，可在任意异步上下文中调用
+    pub async fn shutdown(mut self) {
+        self.sender = None;
+        while self.exited.recv().await.is_some() {}
+        self.join_workers();
+    }
+
+    /// 优雅关闭工作池（阻塞版）：停止接收新请求，等待已入队请求处理完毕后回收工作线程
+    /// 注：阻塞调用，异步上下文中请使用 shutdown().await
+    pub fn shutdown_blocking(mut self) {
+        self.sender = None;
+        self.join_workers();
+    }
+
+    /// 回收工作线程（工作线程已退出或即将退出）
+    fn join_workers(&mut self) {
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl Drop for DetectorPool {
+    /// 未经 shutdown 直接 drop 时放弃已入队请求，不等待工作线程退出
+    fn drop(&mut self) {
+        if self.sender.is_some() {
+            self.aborted.store(true, Ordering::Release);
+            self.sender = None;
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! 检测结果后处理管道
+//! 结果聚合（含推导与证据采集）完成后、返回调用方前，按注册顺序依次执行后处理器，
+//! 标注/抑制/补充/规范化等策略以插件形式接入，无需改动聚合逻辑：
+//! 1. 后处理器在检测器克隆之间共享（注册只影响当前检测器及其之后的克隆）
+//! 2. 内置后处理器：ConfidenceThreshold（置信度阈值）、CategoryFilter（分类过滤）
+
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use super::detector::TechDetector;
+use crate::result::category::{Category, CategoryRef};
+use crate::DetectResult;
+
+/// 检测结果后处理器
+pub trait ResultPostProcessor: Send + Sync {
+    /// 后处理器名称（用于调试输出）
+    fn name(&self) -> &str;
+
+    /// 处理检测结果（可增删改技术与告警）
+    fn process(&self, result: &mut DetectResult);
+}
+
+/// 后处理管道（按注册顺序执行）
+#[derive(Clone, Default)]
+pub(crate) struct PostProcessorPipeline {
+    processors: Vec<Arc<dyn ResultPostProcessor>>,
+}
+
+impl PostProcessorPipeline {
+    /// 依次执行全部后处理器
+    pub(crate) fn apply(&self, result: &mut DetectResult) {
+        for processor in &self.processors {
+            processor.process(result);
+        }
+    }
+}
+
+impl Debug for PostProcessorPipeline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.processors.iter().map(|processor| processor.name()))
+            .finish()
+    }
+}
+
+impl TechDetector {
+    /// 追加结果后处理器（在已注册的后处理器之后执行）
+    /// 参数：processor - 后处理器
+    pub fn add_post_processor(&mut self, processor: Box<dyn ResultPostProcessor>) {
+        self.post_processors.processors.push(Arc::from(processor));
+    }
+
+    /// 已注册的后处理器名称（按执行顺序）
+    pub fn post_processor_names(&self) -> Vec<&str> {
+        self.post_processors.processors.iter().map(|processor| processor.name()).collect()
+    }
+}
+
+/// 置信度阈值：移除置信度低于阈值的技术
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfidenceThreshold {
+    /// 最低置信度（含）
+    pub min_confidence: u8,
+}
+
+impl ConfidenceThreshold {
+    /// 创建置信度阈值后处理器
+    /// 参数：min_confidence - 最低置信度（含，超过100按100处理）
+    pub fn new(min_confidence: u8) -> Self {
+        Self {
+            min_confidence: min_confidence.min(100),
+        }
+    }
+}
+
+impl ResultPostProcessor for ConfidenceThreshold {
+    fn name(&self) -> &str {
+        "confidence-threshold"
+    }
+
+    fn process(&self, result: &mut DetectResult) {
+        result.technologies.retain(|tech| tech.confidence >= self.min_confidence);
+    }
+}
+
+/// 分类过滤模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CategoryFilterMode {
+    /// 仅保留属于任一指定分类的技术
+    Include,
+    /// 移除属于任一指定分类的技术
+    Exclude,
+}
+
+/// 分类过滤：按分类保留或移除技术（分类名称忽略大小写比较）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CategoryFilter {
+    /// 过滤模式
+    pub mode: CategoryFilterMode,
+    /// 分类名称（分类ID在创建时按标准分类表解析，未知ID忽略）
+    pub categories: Vec<String>,
+}
+
+impl CategoryFilter {
+    /// 仅保留指定分类的技术
+    /// 参数：categories - 分类ID / 分类名称 / Category
+    pub fn include<'a>(categories: impl IntoIterator<Item = impl Into<CategoryRef<'a>>>) -> Self {
+        Self::new(CategoryFilterMode::Include, categories)
+    }
+
+    /// 移除指定分类的技术
+    /// 参数：categories - 分类ID / 分类名称 / Category
+    pub fn exclude<'a>(categories: impl IntoIterator<Item = impl Into<CategoryRef<'a>>>) -> Self {
+        Self::new(CategoryFilterMode::Exclude, categories)
+    }
+
+    fn new<'a>(mode: CategoryFilterMode, categories: impl IntoIterator<Item = impl Into<CategoryRef<'a>>>) -> Self {
+        let categories = categories
+            .into_iter()
+            .filter_map(|category| match category.into() {
+                CategoryRef::Id(id) => Category::from_id(id).map(|c| c.name().to_string()),
+                CategoryRef::Name(name) => Some(name.trim().to_string()),
+            })
+            .collect();
+        Self { mode, categories }
+    }
+}
+
+impl ResultPostProcessor for CategoryFilter {
+    fn name(&self) -> &str {
+        match self.mode {
+            CategoryFilterMode::Include => "category-include",
+            CategoryFilterMode::Exclude => "category-exclude",
+        }
+    }
+
+    fn process(&self, result: &mut DetectResult) {
+        let include = self.mode == CategoryFilterMode::Include;
+        result.technologies.retain(|tech| {
+            let matched = self
+                .categories
+                .iter()
+                .any(|category| tech.matches_category(category.as_str()));
+            matched == include
+        });
+    }
+}
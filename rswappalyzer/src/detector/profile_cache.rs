@@ -0,0 +1,158 @@
+//! 按主机的站点画像缓存
+//! 流式检测管线逐条产出检测结果，按主机滚动聚合为 SiteProfile，调用方无需自行维护缓存：
+//! 1. TTL：画像超过 TTL 未更新即视为过期（get 不返回，record 时重新开始聚合）
+//! 2. 容量：主机数达到上限时先清理过期画像，仍满则淘汰最久未更新的主机
+//! 3. 持久化：export/restore 导出/恢复全部未过期画像（SiteProfile 支持 serde）
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, SystemTime};
+
+use rustc_hash::FxHashMap;
+
+use crate::result::detect_result::DetectResult;
+use crate::result::site_profile::SiteProfile;
+
+/// 站点画像缓存（内部加锁，可经 Arc 在线程间共享）
+#[derive(Debug)]
+pub struct ProfileCache {
+    profiles: Mutex<FxHashMap<String, SiteProfile>>,
+    ttl: Duration,
+    max_hosts: usize,
+}
+
+impl ProfileCache {
+    /// 创建画像缓存
+    /// 参数：
+    /// - ttl: 画像有效期（自最近一次更新起算）
+    /// - max_hosts: 最大主机数（至少为 1）
+    pub fn new(ttl: Duration, max_hosts: usize) -> Self {
+        Self {
+            profiles: Mutex::new(FxHashMap::default()),
+            ttl,
+            max_hosts: max_hosts.max(1),
+        }
+    }
+
+    /// 并入主机的一次检测结果
+    /// 参数：
+    /// - host: 主机名（大小写与末尾的点不敏感）
+    /// - result: 检测结果
+    ///
+    /// 返回：更新后的画像快照
+    pub fn record(&self, host: &str, result: &DetectResult) -> SiteProfile {
+        let host = normalize_host(host);
+        let now = SystemTime::now();
+        let mut profiles = self.lock();
+        if profiles.get(&host).is_some_and(|profile| self.is_expired(profile, now)) {
+            profiles.remove(&host);
+        }
+        if !profiles.contains_key(&host) && profiles.len() >= self.max_hosts {
+            self.evict(&mut profiles, now);
+        }
+        let profile = profiles
+            .entry(host)
+            .or_insert_with_key(|host| SiteProfile::new(host.clone()));
+        profile.record(result);
+        profile.clone()
+    }
+
+    /// 获取主机画像（已过期返回 None）
+    /// 参数：host - 主机名
+    pub fn get(&self, host: &str) -> Option<SiteProfile> {
+        let now = SystemTime::now();
+        self.lock()
+            .get(&normalize_host(host))
+            .filter(|profile| !self.is_expired(profile, now))
+            .cloned()
+    }
+
+    /// 移除主机画像
+    /// 参数：host - 主机名
+    pub fn remove(&self, host: &str) -> Option<SiteProfile> {
+        self.lock().remove(&normalize_host(host))
+    }
+
+    /// 清理全部过期画像
+    /// 返回：清理数量
+    pub fn purge_expired(&self) -> usize {
+        let now = SystemTime::now();
+        let mut profiles = self.lock();
+        let before = profiles.len();
+        profiles.retain(|_, profile| !self.is_expired(profile, now));
+        before - profiles.len()
+    }
+
+    /// 当前缓存的主机数（含尚未清理的过期画像）
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// 缓存是否为空
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// 导出全部未过期画像（按主机名排序，用于持久化）
+    pub fn export(&self) -> Vec<SiteProfile> {
+        let now = SystemTime::now();
+        let mut exported: Vec<SiteProfile> = self
+            .lock()
+            .values()
+            .filter(|profile| !self.is_expired(profile, now))
+            .cloned()
+            .collect();
+        exported.sort_by(|a, b| a.host.cmp(&b.host));
+        exported
+    }
+
+    /// 恢复导出的画像（跳过已过期画像；同名主机保留较新的画像；超出容量按淘汰策略处理）
+    /// 参数：profiles - export 导出的画像
+    pub fn restore(&self, profiles: impl IntoIterator<Item = SiteProfile>) {
+        let now = SystemTime::now();
+        let mut cached = self.lock();
+        for mut profile in profiles {
+            if self.is_expired(&profile, now) {
+                continue;
+            }
+            profile.host = normalize_host(&profile.host);
+            if cached
+                .get(&profile.host)
+                .is_some_and(|existing| existing.last_seen >= profile.last_seen)
+            {
+                continue;
+            }
+            if !cached.contains_key(&profile.host) && cached.len() >= self.max_hosts {
+                self.evict(&mut cached, now);
+            }
+            cached.insert(profile.host.clone(), profile);
+        }
+    }
+
+    fn is_expired(&self, profile: &SiteProfile, now: SystemTime) -> bool {
+        now.duration_since(profile.last_seen).unwrap_or_default() > self.ttl
+    }
+
+    /// 腾出一个位置：先清理过期画像，仍满则淘汰最久未更新的主机
+    fn evict(&self, profiles: &mut FxHashMap<String, SiteProfile>, now: SystemTime) {
+        profiles.retain(|_, profile| !self.is_expired(profile, now));
+        if profiles.len() < self.max_hosts {
+            return;
+        }
+        let oldest = profiles
+            .values()
+            .min_by_key(|profile| profile.last_seen)
+            .map(|profile| profile.host.clone());
+        if let Some(host) = oldest {
+            profiles.remove(&host);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, FxHashMap<String, SiteProfile>> {
+        self.profiles.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// 主机名归一化（小写、去除末尾的点）
+fn normalize_host(host: &str) -> String {
+    host.trim().trim_end_matches('.').to_ascii_lowercase()
+}
@@ -0,0 +1,421 @@
+//! 渐进式检测
+//! 按作用域分阶段执行检测流水线，每个阶段完成后立即产出增量事件
+//! （新增技术/版本细化/置信度提升/推导技术），适用于类似浏览器 DevTools 的渐进展示场景
+//! 阶段事件由 detect 使用的同一作用域流水线产出（StageObserver），最终事件携带与 detect 相同的完整结果
+//! 1. 同步消费：ProgressiveDetection 实现 Iterator，创建时执行检测并缓存事件，逐个产出
+//! 2. 异步消费：启用 `stream` 特性后同时实现 futures_core::Stream
+
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+use http::HeaderMap;
+use rswappalyzer_engine::scope_pruner::PruneScope;
+use rswappalyzer_engine::thread_regex_budget_exceeded;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use super::detector::{with_regex_budget_warning, Detected, PreparedInput, ScopeInput, ScopeOutcome, StageObserver};
+use super::input::{DetectInput, SupplementalInput};
+use super::options::DetectOptions;
+use crate::utils::PreparedHeaders;
+use crate::{DetectResult, TechDetector};
+
+/// 检测阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DetectStage {
+    Url,
+    /// 主机名后缀
+    Hostname,
+    Header,
+    Cookie,
+    Html,
+    /// DOM 选择器
+    Dom,
+    Script,
+    Meta,
+    /// 页面标题
+    Title,
+    /// HTML 注释
+    Comment,
+    /// JS 全局变量（调用方补充输入）
+    Js,
+    /// TLS 证书颁发者（调用方补充输入）
+    CertIssuer,
+    /// DNS 记录（调用方补充输入）
+    Dns,
+    /// favicon 哈希（调用方补充输入）
+    FaviconHash,
+    /// 关联推导
+    Implies,
+}
+
+impl DetectStage {
+    /// 阶段对应的作用域（关联推导无对应作用域）
+    pub fn scope(&self) -> Option<PruneScope> {
+        match self {
+            DetectStage::Url => Some(PruneScope::Url),
+            DetectStage::Hostname => Some(PruneScope::Hostname),
+            DetectStage::Header => Some(PruneScope::Header),
+            DetectStage::Cookie => Some(PruneScope::Cookie),
+            DetectStage::Html => Some(PruneScope::Html),
+            DetectStage::Dom => Some(PruneScope::Dom),
+            DetectStage::Script => Some(PruneScope::Script),
+            DetectStage::Meta => Some(PruneScope::Meta),
+            DetectStage::Title => Some(PruneScope::Title),
+            DetectStage::Comment => Some(PruneScope::Comment),
+            DetectStage::Js => Some(PruneScope::Js),
+            DetectStage::CertIssuer => Some(PruneScope::CertIssuer),
+            DetectStage::Dns => Some(PruneScope::Dns),
+            DetectStage::FaviconHash => Some(PruneScope::FaviconHash),
+            DetectStage::Implies => None,
+        }
+    }
+
+    /// 阶段名（snake_case，追踪记录使用；关联推导阶段为 `post_process`）
+    pub fn name(&self) -> &'static str {
+        match self {
+            DetectStage::Url => "url",
+            DetectStage::Hostname => "hostname",
+            DetectStage::Header => "header",
+            DetectStage::Cookie => "cookie",
+            DetectStage::Html => "html",
+            DetectStage::Dom => "dom",
+            DetectStage::Script => "script",
+            DetectStage::Meta => "meta",
+            DetectStage::Title => "title",
+            DetectStage::Comment => "comment",
+            DetectStage::Js => "js",
+            DetectStage::CertIssuer => "cert_issuer",
+            DetectStage::Dns => "dns",
+            DetectStage::FaviconHash => "favicon_hash",
+            DetectStage::Implies => "post_process",
+        }
+    }
+}
+
+/// 渐进式检测事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TechnologyEvent {
+    /// 新检出技术
+    Added {
+        stage: DetectStage,
+        name: String,
+        confidence: u8,
+        version: Option<String>,
+    },
+    /// 已检出技术的版本被细化
+    VersionRefined {
+        stage: DetectStage,
+        name: String,
+        version: String,
+    },
+    /// 已检出技术的置信度提升
+    ConfidenceRaised {
+        stage: DetectStage,
+        name: String,
+        confidence: u8,
+    },
+    /// 关联推导新增的技术
+    Implied {
+        name: String,
+        confidence: u8,
+        implied_by: Vec<String>,
+    },
+    /// 阶段完成（total 为当前已检出技术总数）
+    StageCompleted { stage: DetectStage, total: usize },
+    /// 检测完成，携带与 detect 一致的最终结果
+    Finished(DetectResult),
+}
+
+/// 渐进式检测执行器（由 TechDetector::detect_progressive / run_progressive 创建）
+pub struct ProgressiveDetection<'a> {
+    /// 待产出事件
+    pending: VecDeque<TechnologyEvent>,
+    _detector: PhantomData<&'a TechDetector>,
+}
+
+/// 阶段事件收集器（作为共享作用域流水线的观察者，按阶段前后快照差异生成事件）
+#[derive(Default)]
+struct EventCollector {
+    pending: VecDeque<TechnologyEvent>,
+    /// 当前阶段开始前的检测结果快照
+    before: Detected,
+}
+
+impl EventCollector {
+    /// 对比阶段前后的检测结果，生成增量事件（按技术名排序，保证输出稳定）
+    fn push_diff(
+        &mut self,
+        stage: DetectStage,
+        detected: &Detected,
+        imply_map: &FxHashMap<String, Vec<String>>,
+    ) {
+        let mut names: Vec<&String> = detected.keys().collect();
+        names.sort();
+
+        for name in names {
+            let (confidence, version) = &detected[name];
+            let event = match self.before.get(name) {
+                None if stage == DetectStage::Implies => TechnologyEvent::Implied {
+                    name: name.clone(),
+                    confidence: *confidence,
+                    implied_by: imply_map.get(name).cloned().unwrap_or_default(),
+                },
+                None => TechnologyEvent::Added {
+                    stage,
+                    name: name.clone(),
+                    confidence: *confidence,
+                    version: version.clone(),
+                },
+                Some((old_confidence, old_version)) => {
+                    if let Some(version) = version.as_ref().filter(|v| Some(*v) != old_version.as_ref()) {
+                        TechnologyEvent::VersionRefined {
+                            stage,
+                            name: name.clone(),
+                            version: version.clone(),
+                        }
+                    } else if confidence > old_confidence {
+                        TechnologyEvent::ConfidenceRaised {
+                            stage,
+                            name: name.clone(),
+                            confidence: *confidence,
+                        }
+                    } else {
+                        continue;
+                    }
+                }
+            };
+            self.pending.push_back(event);
+        }
+    }
+
+    /// 阶段完成事件
+    fn complete(&mut self, stage: DetectStage, detected: &Detected) {
+        self.pending.push_back(TechnologyEvent::StageCompleted {
+            stage,
+            total: detected.len(),
+        });
+    }
+}
+
+impl StageObserver for EventCollector {
+    fn stage_started(&mut self, _stage: DetectStage, detected: &Detected) {
+        self.before = detected.clone();
+    }
+
+    fn stage_finished(&mut self, stage: DetectStage, detected: &Detected, _input: &ScopeInput<'_>) {
+        self.push_diff(stage, detected, &FxHashMap::default());
+        self.complete(stage, detected);
+    }
+}
+
+impl<'a> ProgressiveDetection<'a> {
+    /// 执行与 detect 相同的检测流水线，收集各阶段事件
+    /// 参数：
+    /// - detector: 检测器
+    /// - headers: 预处理Header集合
+    /// - urls: 检测的URL列表
+    /// - body: HTTP响应体（字节数组）
+    /// - supplemental: 调用方补充输入
+    fn new(
+        detector: &'a TechDetector,
+        headers: &PreparedHeaders,
+        urls: &[&str],
+        body: &[u8],
+        supplemental: SupplementalInput<'_>,
+    ) -> Self {
+        let regex_budget_before = thread_regex_budget_exceeded();
+        let options = DetectOptions::default();
+        let body = detector.prepare_detect_body(headers, body, &options, supplemental.script_srcs);
+        let prepared = PreparedInput::new(urls, &body);
+        let input = ScopeInput {
+            lib: detector.compiled_library(),
+            headers,
+            prepared: &prepared,
+            supplemental: &supplemental,
+            options: &options,
+            deadline: None,
+        };
+        let mut collector = EventCollector::default();
+        // 未设置耗时上限，作用域分析不会失败
+        let ScopeOutcome {
+            mut detected,
+            scopes,
+            generator_hits,
+            script_outcome,
+        } = detector
+            .analyze_scopes(&input, Some(&mut collector))
+            .unwrap_or_default();
+
+        // generator 命中合并（与 detect 一致，在关联推导前执行），变化归入 Meta 阶段
+        if !generator_hits.is_empty() {
+            collector.before = detected.clone();
+            detector.merge_generator_hits(&mut detected, generator_hits);
+            collector.push_diff(DetectStage::Meta, &detected, &FxHashMap::default());
+        }
+
+        // 关联推导 / 依赖 / 互斥
+        collector.before = detected.clone();
+        let (_, imply_map) = detector.apply_requires_and_implies(&mut detected, &script_outcome);
+        detector.apply_excludes(&mut detected);
+        collector.push_diff(DetectStage::Implies, &detected, &imply_map);
+        collector.complete(DetectStage::Implies, &detected);
+
+        // 最终结果（与 detect 一致）
+        let technologies = detector.build_technologies(detected, &imply_map, script_outcome.hosts, &scopes);
+        let mut result = DetectResult {
+            technologies,
+            warnings: with_regex_budget_warning(body.warnings, regex_budget_before),
+        };
+        detector.post_processors.apply(&mut result);
+        collector.pending.push_back(TechnologyEvent::Finished(result));
+
+        Self {
+            pending: collector.pending,
+            _detector: PhantomData,
+        }
+    }
+}
+
+impl Iterator for ProgressiveDetection<'_> {
+    type Item = TechnologyEvent;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pending.pop_front()
+    }
+}
+
+/// 异步流适配：事件已在创建时生成，轮询直接产出
+#[cfg(feature = "stream")]
+impl futures_core::Stream for ProgressiveDetection<'_> {
+    type Item = TechnologyEvent;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        std::task::Poll::Ready(self.get_mut().next())
+    }
+}
+
+impl TechDetector {
+    /// 渐进式检测：按作用域分阶段产出增量事件
+    /// 启用 `stream` 特性后返回值同时实现 Stream，可直接用于异步UI推送
+    /// 参数：
+    /// - headers: HTTP头信息（HeaderMap）
+    /// - urls: 检测的URL列表
+    /// - body: HTTP响应体（字节数组）
+    ///
+    /// 返回：事件迭代器（最后一个事件为 Finished，结果与 detect 一致）
+    pub fn detect_progressive<'a>(
+        &'a self,
+        headers: &HeaderMap,
+        urls: &'a [&'a str],
+        body: &'a [u8],
+    ) -> ProgressiveDetection<'a> {
+        let prepared = PreparedHeaders::prepare(headers);
+        ProgressiveDetection::new(self, &prepared, urls, body, SupplementalInput::default())
+    }
+
+    /// 按检测请求渐进式检测（补充输入对应 JS/证书颁发者/DNS/Favicon 阶段）
+    /// 参数：input - 检测请求
+    ///
+    /// 返回：事件迭代器（最后一个事件为 Finished，结果与 run 一致）
+    pub fn run_progressive(&self, input: &DetectInput) -> ProgressiveDetection<'_> {
+        let prepared = PreparedHeaders::prepare(&input.headers);
+        let hop_headers = input.prepare_hops();
+        let hash = input.favicon_hash();
+        let supplemental = input.supplemental(&hop_headers, hash.as_deref());
+        ProgressiveDetection::new(self, &prepared, &input.chain_urls(), &input.body, supplemental)
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::detector;
+
+    fn detected(entries: &[(&str, u8, Option<&str>)]) -> Detected {
+        entries
+            .iter()
+            .map(|(name, confidence, version)| (name.to_string(), (*confidence, version.map(str::to_string))))
+            .collect()
+    }
+
+    #[test]
+    fn test_push_diff_events() {
+        let mut collector = EventCollector {
+            before: detected(&[("Nginx", 50, None), ("PHP", 100, Some("8.2")), ("Vue.js", 100, None)]),
+            ..EventCollector::default()
+        };
+        let after = detected(&[
+            ("Nginx", 80, None),
+            ("PHP", 100, Some("8.2")),
+            ("Vue.js", 100, Some("3.4.0")),
+            ("jQuery", 100, Some("3.7.1")),
+        ]);
+        collector.push_diff(DetectStage::Html, &after, &FxHashMap::default());
+        collector.complete(DetectStage::Html, &after);
+
+        let events: Vec<String> = collector.pending.iter().map(|e| format!("{:?}", e)).collect();
+        assert_eq!(
+            events,
+            vec![
+                "ConfidenceRaised { stage: Html, name: \"Nginx\", confidence: 80 }",
+                "VersionRefined { stage: Html, name: \"Vue.js\", version: \"3.4.0\" }",
+                "Added { stage: Html, name: \"jQuery\", confidence: 100, version: Some(\"3.7.1\") }",
+                "StageCompleted { stage: Html, total: 4 }",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_push_diff_implied() {
+        let mut collector = EventCollector {
+            before: detected(&[("WordPress", 100, None)]),
+            ..EventCollector::default()
+        };
+        let after = detected(&[("WordPress", 100, None), ("PHP", 100, None)]);
+        let imply_map = FxHashMap::from_iter([("PHP".to_string(), vec!["WordPress".to_string()])]);
+        collector.push_diff(DetectStage::Implies, &after, &imply_map);
+        assert!(matches!(
+            collector.pending.front(),
+            Some(TechnologyEvent::Implied { name, implied_by, .. }) if name == "PHP" && implied_by == &["WordPress"]
+        ));
+        assert_eq!(collector.pending.len(), 1);
+    }
+
+    #[test]
+    fn test_stage_scope_and_name() {
+        assert_eq!(DetectStage::CertIssuer.scope(), Some(PruneScope::CertIssuer));
+        assert_eq!(DetectStage::Implies.scope(), None);
+        assert_eq!(DetectStage::FaviconHash.name(), "favicon_hash");
+        assert_eq!(DetectStage::Implies.name(), "post_process");
+    }
+
+    #[test]
+    fn test_progressive_event_order() {
+        let detector = detector(
+            r#"{"technologies": {
+                "PHP": { "cats": [27], "headers": { "X-Powered-By": "php" } },
+                "WordPress": { "cats": [1], "implies": "PHP", "html": "wp-content" }
+            }}"#,
+        );
+        let events: Vec<TechnologyEvent> = detector
+            .detect_progressive(&HeaderMap::new(), &[], b"<link href=\"/wp-content/x.css\">")
+            .collect();
+        let html_added = events.iter().position(|e| {
+            matches!(e, TechnologyEvent::Added { stage: DetectStage::Html, name, .. } if name == "WordPress")
+        });
+        let implied = events
+            .iter()
+            .position(|e| matches!(e, TechnologyEvent::Implied { name, .. } if name == "PHP"));
+        assert!(html_added.unwrap() < implied.unwrap());
+        let Some(TechnologyEvent::Finished(result)) = events.last() else {
+            panic!("last event is not Finished");
+        };
+        assert_eq!(result.technologies.len(), 2);
+    }
+}
@@ -0,0 +1,199 @@
+//! 目标技术查询（早退模式）
+//! 「该站点是否为 WordPress」类判定无需完整检测：
+//! 1. 候选集收缩为目标技术，以及可经 implies 链（不超过 ImplyPolicy 推导深度）推导出目标的来源技术
+//! 2. 收缩后的候选规则库按 detect 共用的作用域流水线执行（请求侧 → 响应体侧），任一阶段命中即跳过剩余阶段
+//! 3. 响应体仅在请求侧作用域均未命中时才预处理
+//! 4. requires/excludes 等依赖完整结果的后处理不参与判定（快速预筛，需要完整语义时使用 run）
+
+use rustc_hash::FxHashSet;
+
+use super::detector::{Detected, PreparedBody, PreparedInput, ScopeInput, ScopeOutcome, StageObserver, TechDetector};
+use super::input::DetectInput;
+use super::options::DetectOptions;
+use super::progressive::DetectStage;
+use crate::utils::{BodyKind, PreparedHeaders};
+
+/// 查询观察者：任一阶段产生命中后跳过剩余阶段（候选规则库只含候选技术，命中即目标命中）
+#[derive(Default)]
+struct QueryObserver {
+    hit: bool,
+}
+
+impl StageObserver for QueryObserver {
+    fn stage_finished(&mut self, _stage: DetectStage, detected: &Detected, _input: &ScopeInput<'_>) {
+        self.hit |= !detected.is_empty();
+    }
+
+    fn is_done(&self) -> bool {
+        self.hit
+    }
+}
+
+impl TechDetector {
+    /// 查询输入中是否存在任一目标技术（候选集收缩 + 首个命中即返回）
+    /// 参数：
+    /// - input: 检测请求
+    /// - tech_names: 目标技术名（大小写不敏感，未知技术名忽略）
+    ///
+    /// 返回：任一目标技术（或可推导出目标的技术）命中
+    pub fn contains_tech(&self, input: &DetectInput, tech_names: &[&str]) -> bool {
+        let candidates = self.query_candidates(tech_names);
+        if candidates.is_empty() {
+            return false;
+        }
+        let lib = self.compiled_library().subset(&candidates);
+        let headers = PreparedHeaders::prepare(&input.headers);
+        let hop_headers = input.prepare_hops();
+        let hash = input.favicon_hash();
+        let supplemental = input.supplemental(&hop_headers, hash.as_deref());
+        let urls = input.chain_urls();
+        let options = DetectOptions::default();
+        let mut outcome = ScopeOutcome::default();
+        let mut query = QueryObserver::default();
+
+        // 1. 请求侧作用域（响应体尚未预处理）
+        let empty = PreparedBody::empty(BodyKind::Binary);
+        let prepared = PreparedInput::new(&urls, &empty);
+        let scope_input = ScopeInput {
+            lib: &lib,
+            headers: &headers,
+            prepared: &prepared,
+            supplemental: &supplemental,
+            options: &options,
+            deadline: None,
+        };
+        // 未设置耗时上限，作用域分析不会失败
+        let _ = self.analyze_request_scopes(&scope_input, &mut outcome, Some(&mut query));
+        if query.hit {
+            return true;
+        }
+
+        // 2. 响应体侧作用域（前序作用域均未命中时才预处理）
+        if input.body.is_empty() && input.scripts.is_empty() {
+            return false;
+        }
+        let body = self.prepare_detect_body(&headers, &input.body, &options, supplemental.script_srcs);
+        let prepared = PreparedInput::new(&urls, &body);
+        let scope_input = ScopeInput {
+            prepared: &prepared,
+            ..scope_input
+        };
+        let _ = self.analyze_body_scopes(&scope_input, &mut outcome, Some(&mut query));
+
+        // generator 命中在其它作用域完成后合并（与 detect 一致）
+        self.merge_generator_hits(&mut outcome.detected, outcome.generator_hits);
+        !outcome.detected.is_empty()
+    }
+
+    /// 收缩候选集：目标技术 + 可经 implies 链推导出目标的来源技术
+    fn query_candidates(&self, tech_names: &[&str]) -> FxHashSet<&str> {
+        let lib = self.compiled_library();
+        let mut candidates: FxHashSet<&str> = lib
+            .tech_patterns
+            .keys()
+            .filter(|name| tech_names.iter().any(|target| target.trim().eq_ignore_ascii_case(name)))
+            .map(String::as_str)
+            .collect();
+        let mut frontier = candidates.clone();
+        for _ in 0..self.config.options.imply_policy.max_depth {
+            let sources: FxHashSet<&str> = lib
+                .tech_patterns
+                .values()
+                .filter(|tech| !candidates.contains(tech.name.as_str()))
+                .filter(|tech| tech.implies.iter().any(|implied| frontier.contains(implied.trim())))
+                .map(|tech| tech.name.as_str())
+                .collect();
+            if sources.is_empty() {
+                break;
+            }
+            candidates.extend(&sources);
+            frontier = sources;
+        }
+        candidates
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::header::{HeaderMap, HeaderValue};
+
+    use super::*;
+    use crate::test_support::{detector, detector_with};
+    use crate::{CustomConfigBuilder, ImplyPolicy, RuleConfig};
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "PHP": { "cats": [27], "headers": { "X-Powered-By": "^php/?([\\d.]+)?" } },
+            "WordPress": { "cats": [1], "implies": "PHP", "meta": { "generator": "^WordPress" } },
+            "WooCommerce": { "cats": [6], "implies": "WordPress", "html": "woocommerce-page" },
+            "Acme Widget": { "cats": [5], "scriptSrc": "acme-widget\\.js" }
+        }
+    }"#;
+
+    fn candidates(detector: &TechDetector, tech_names: &[&str]) -> Vec<String> {
+        let mut names: Vec<String> = detector
+            .query_candidates(tech_names)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_query_candidates() {
+        // 目标技术名大小写不敏感，来源技术按推导深度收缩
+        assert_eq!(candidates(&detector(RULES), &[" php "]), ["PHP", "WordPress"]);
+        let with_depth = |max_depth: usize| -> RuleConfig {
+            CustomConfigBuilder::new()
+                .imply_policy(ImplyPolicy::new(0.9, max_depth))
+                .build()
+        };
+        let deep = detector_with(RULES, with_depth(2));
+        assert_eq!(candidates(&deep, &["PHP"]), ["PHP", "WooCommerce", "WordPress"]);
+        let flat = detector_with(RULES, with_depth(0));
+        assert_eq!(candidates(&flat, &["PHP"]), ["PHP"]);
+        assert!(candidates(&flat, &["Unknown", ""]).is_empty());
+    }
+
+    #[test]
+    fn test_contains_tech() {
+        let detector = detector(RULES);
+        let mut headers = HeaderMap::new();
+        headers.insert("x-powered-by", HeaderValue::from_static("PHP/8.2.1"));
+        let input = DetectInput::builder().headers(headers).build();
+        assert!(detector.contains_tech(&input, &["PHP"]));
+        assert!(!detector.contains_tech(&input, &["WordPress"]));
+        assert!(!detector.contains_tech(&input, &["Unknown", ""]));
+
+        let body = r#"<html><head><meta name="generator" content="WordPress 6.4"></head><body></body></html>"#;
+        let input = DetectInput::builder().body(body).build();
+        assert!(detector.contains_tech(&input, &["wordpress"]));
+        // 目标技术可由已命中技术推导
+        assert!(detector.contains_tech(&input, &["PHP"]));
+        assert!(!detector.contains_tech(&input, &["WooCommerce"]));
+
+        // 外部脚本地址无响应体时同样参与匹配
+        let input = DetectInput::builder().scripts(["/static/acme-widget.js"]).build();
+        assert!(detector.contains_tech(&input, &["Acme Widget"]));
+        assert!(!detector.contains_tech(&input, &["PHP"]));
+    }
+
+    #[test]
+    fn test_contains_tech_matches_run() {
+        let detector = detector(
+            r#"{
+                "technologies": {
+                    "GitHub Pages": { "cats": [62], "hostname": "*.github.io" },
+                    "Jekyll": { "cats": [57], "implies": "GitHub Pages", "meta": { "generator": "^Jekyll" } }
+                }
+            }"#,
+        );
+        for url in ["https://octocat.github.io/hello", "https://github.io/", "https://example.com/"] {
+            let input = DetectInput::builder().url(url).build();
+            let detected = detector.run(&input).unwrap().find_by_slug("github-pages").is_some();
+            assert_eq!(detector.contains_tech(&input, &["github pages"]), detected, "{}", url);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! URL 扫描（scanner 特性）
+//! 内置 HTTP 客户端完成「请求 → 收集响应 → 检测」的胶水逻辑：
+//! 1. 手动跟随重定向，中间响应记录为 RedirectHop（其 URL 与 Server/Set-Cookie 等响应头参与检测，
+//!    登录跳转等场景下会话 Cookie、SSO 网关标识仅出现在中间响应）
+//! 2. 响应体按 max_body_bytes 截断读取，避免超大页面占用内存；gzip 由客户端自动解压
+
+use std::time::Duration;
+
+use http::header::LOCATION;
+use reqwest::redirect::Policy;
+use reqwest::Client;
+use url::Url;
+
+use super::detector::TechDetector;
+use super::input::{DetectInput, RedirectHop};
+use crate::error::{RswResult, RswappalyzerError};
+use crate::DetectResult;
+
+/// URL 扫描选项
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    /// 单次请求超时（每一跳独立计时）
+    pub timeout: Duration,
+    /// 最大重定向次数
+    pub max_redirects: usize,
+    /// 响应体最大读取字节数（超出部分截断）
+    pub max_body_bytes: usize,
+    /// 请求 User-Agent
+    pub user_agent: String,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            max_redirects: 10,
+            max_body_bytes: 2 * 1024 * 1024,
+            user_agent: format!("Mozilla/5.0 (compatible; Rswappalyzer/{})", env!("CARGO_PKG_VERSION")),
+        }
+    }
+}
+
+impl ScanOptions {
+    /// 设置单次请求超时（链式）
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 设置最大重定向次数（链式，0 表示不跟随重定向）
+    pub fn max_redirects(mut self, max_redirects: usize) -> Self {
+        self.max_redirects = max_redirects;
+        self
+    }
+
+    /// 设置响应体最大读取字节数（链式）
+    pub fn max_body_bytes(mut self, max_body_bytes: usize) -> Self {
+        self.max_body_bytes = max_body_bytes;
+        self
+    }
+
+    /// 设置请求 User-Agent（链式）
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+}
+
+impl TechDetector {
+    /// 请求 URL 并检测（默认扫描选项）
+    /// 参数：url - 目标 URL（http/https）
+    ///
+    /// 返回：检测结果 | URL 非法/网络错误
+    pub async fn scan_url(&self, url: &str) -> RswResult<DetectResult> {
+        self.scan_url_with(url, &ScanOptions::default()).await
+    }
+
+    /// 请求 URL 并检测（指定扫描选项）
+    /// 参数：
+    /// - url: 目标 URL（http/https）
+    /// - options: 扫描选项
+    ///
+    /// 返回：检测结果 | URL 非法/网络错误
+    pub async fn scan_url_with(&self, url: &str, options: &ScanOptions) -> RswResult<DetectResult> {
+        let input = fetch_page(url, options).await?;
+        self.run(&input)
+    }
+}
+
+/// 请求页面（手动跟随重定向，中间响应记录为重定向链）
+async fn fetch_page(url: &str, options: &ScanOptions) -> RswResult<DetectInput> {
+    let mut current = Url::parse(url)?;
+    if !matches!(current.scheme(), "http" | "https") {
+        return Err(RswappalyzerError::InvalidInput(format!("Unsupported URL scheme: {}", url)));
+    }
+    let client = Client::builder()
+        .redirect(Policy::none())
+        .timeout(options.timeout)
+        .user_agent(options.user_agent.as_str())
+        .build()
+        .map_err(|e| RswappalyzerError::NetworkError(format!("Failed to build HTTP client: {}", e)))?;
+
+    let mut hops = Vec::new();
+    let mut response = send(&client, &current).await?;
+    while response.status().is_redirection() {
+        let Some(location) = response.headers().get(LOCATION).and_then(|v| v.to_str().ok()) else {
+            break;
+        };
+        if hops.len() >= options.max_redirects {
+            return Err(RswappalyzerError::NetworkError(format!(
+                "Too many redirects (> {}) starting from {}",
+                options.max_redirects, url
+            )));
+        }
+        let next = current.join(location)?;
+        hops.push(RedirectHop::new(current.as_str(), response.headers().clone()));
+        current = next;
+        response = send(&client, &current).await?;
+    }
+
+    let headers = response.headers().clone();
+    let body = read_body(response, options.max_body_bytes).await?;
+    Ok(DetectInput {
+        hops,
+        ..DetectInput::new(headers, vec![current.to_string()], body)
+    })
+}
+
+/// 发送单个 GET 请求
+async fn send(client: &Client, url: &Url) -> RswResult<reqwest::Response> {
+    client
+        .get(url.clone())
+        .send()
+        .await
+        .map_err(|e| RswappalyzerError::NetworkError(format!("Failed to request {}: {}", url, e)))
+}
+
+/// 读取响应体（超出上限时截断）
+async fn read_body(mut response: reqwest::Response, max_body_bytes: usize) -> RswResult<Vec<u8>> {
+    let mut body = Vec::new();
+    while body.len() < max_body_bytes {
+        let chunk = response
+            .chunk()
+            .await
+            .map_err(|e| RswappalyzerError::NetworkError(format!("Failed to read response body: {}", e)))?;
+        let Some(chunk) = chunk else {
+            break;
+        };
+        let take = chunk.len().min(max_body_bytes - body.len());
+        body.extend_from_slice(&chunk[..take]);
+    }
+    Ok(body)
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use axum::http::header::{SERVER, SET_COOKIE};
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use axum::Router;
+
+    use super::*;
+    use crate::test_support::detector;
+
+    /// 启动本地服务：/login 设置 Cookie 并跳转到 /home
+    async fn serve() -> String {
+        let home = "<html><body><div id=\"acme-portal-root\"></div></body></html>";
+        let app = Router::new()
+            .route(
+                "/login",
+                get(|| async { (StatusCode::FOUND, [(LOCATION, "/home"), (SET_COOKIE, "acme_sso=1; Path=/")]) }),
+            )
+            .route("/home", get(move || async move { ([(SERVER, "acme/3.1")], home).into_response() }))
+            .route("/loop", get(|| async { (StatusCode::FOUND, [(LOCATION, "/loop")]) }))
+            .route("/dangling", get(|| async { StatusCode::FOUND }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_page_records_hops() {
+        let base = serve().await;
+        let input = fetch_page(&format!("{}/login", base), &ScanOptions::default()).await.unwrap();
+        // 中间响应记录为重定向链，最终 URL 作为检测 URL
+        assert_eq!(input.hops.len(), 1);
+        assert_eq!(input.hops[0].url, format!("{}/login", base));
+        assert!(input.hops[0].headers.contains_key(SET_COOKIE));
+        assert_eq!(input.urls, [format!("{}/home", base)]);
+        assert_eq!(input.headers[SERVER], "acme/3.1");
+
+        // 无 Location 的重定向响应作为最终响应
+        let input = fetch_page(&format!("{}/dangling", base), &ScanOptions::default()).await.unwrap();
+        assert!(input.hops.is_empty());
+        assert!(input.body.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_page_limits() {
+        let base = serve().await;
+        let options = ScanOptions::default().max_redirects(3);
+        let err = fetch_page(&format!("{}/loop", base), &options).await.unwrap_err();
+        assert!(err.to_string().contains("Too many redirects (> 3)"), "{}", err);
+
+        let options = ScanOptions::default().max_body_bytes(8);
+        let input = fetch_page(&format!("{}/home", base), &options).await.unwrap();
+        assert_eq!(input.body, b"<html><b");
+
+        let err = fetch_page("ftp://example.com/", &ScanOptions::default()).await.unwrap_err();
+        assert!(matches!(err, RswappalyzerError::InvalidInput(_)), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_scan_url_detects_hop_cookies() {
+        let detector = detector(
+            r#"{
+                "technologies": {
+                    "Acme Server": { "cats": [22], "headers": { "Server": "^acme/([\\d.]+)$\\;version:\\1" } },
+                    "Acme SSO": { "cats": [69], "cookies": { "acme_sso": "" } },
+                    "Acme Portal": { "cats": [1], "html": "acme-portal-root" }
+                }
+            }"#,
+        );
+        let base = serve().await;
+        // Cookie 仅出现在跳转前的中间响应中
+        let result = detector.scan_url(&format!("{}/login", base)).await.unwrap();
+        assert!(result.find_by_slug("acme-sso").is_some());
+        assert!(result.find_by_slug("acme-portal").is_some());
+        let server = result.find_by_slug("acme-server").unwrap();
+        assert_eq!(server.version.as_deref(), Some("3.1"));
+    }
+}
@@ -0,0 +1,187 @@
+//! 规则库自检
+//! 用少量内置合成样例（已知 Header/Cookie/响应体 -> 预期技术）对已加载的规则库跑完整检测，
+//! 服务可在健康检查/接流量前确认规则库可用，及早发现损坏的规则包或剪枝回归导致的大面积漏检：
+//! 1. 预期技术不在规则库中（自定义规则库）或作用域未编译的样例记为跳过
+//! 2. 至少一个样例通过且无失败样例时视为健康
+
+use std::time::{Duration, Instant};
+
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use rswappalyzer_engine::scope_pruner::PruneScope;
+use serde::{Deserialize, Serialize};
+
+use super::detector::TechDetector;
+
+/// 内置自检样例
+struct Fixture {
+    name: &'static str,
+    scope: PruneScope,
+    headers: &'static [(&'static str, &'static str)],
+    body: &'static str,
+    expected: &'static str,
+}
+
+const FIXTURES: &[Fixture] = &[
+    Fixture {
+        name: "server_header",
+        scope: PruneScope::Header,
+        headers: &[("server", "nginx/1.25.3")],
+        body: "",
+        expected: "Nginx",
+    },
+    Fixture {
+        name: "powered_by_header",
+        scope: PruneScope::Header,
+        headers: &[("x-powered-by", "PHP/8.2.1")],
+        body: "",
+        expected: "PHP",
+    },
+    Fixture {
+        name: "cookie",
+        scope: PruneScope::Cookie,
+        headers: &[("cookie", "_ga=GA1.1.123456789.1700000000")],
+        body: "",
+        expected: "Google Analytics",
+    },
+    Fixture {
+        name: "meta_generator",
+        scope: PruneScope::Meta,
+        headers: &[("content-type", "text/html; charset=utf-8")],
+        body: r#"<html><head><meta name="generator" content="WordPress 6.4.2"></head><body></body></html>"#,
+        expected: "WordPress",
+    },
+    Fixture {
+        name: "script_src",
+        scope: PruneScope::Script,
+        headers: &[("content-type", "text/html; charset=utf-8")],
+        body: r#"<html><head><script src="https://code.jquery.com/jquery-3.7.1.min.js"></script></head><body></body></html>"#,
+        expected: "jQuery",
+    },
+    Fixture {
+        name: "html_content",
+        scope: PruneScope::Html,
+        headers: &[("content-type", "text/html; charset=utf-8")],
+        body: r#"<html><head><link rel="stylesheet" href="/sites/default/themes/olivero/style.css"></head><body></body></html>"#,
+        expected: "Drupal",
+    },
+];
+
+/// 单个样例的自检状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelfTestStatus {
+    /// 检出预期技术
+    Passed,
+    /// 未检出预期技术（或检测出错）
+    Failed,
+    /// 预期技术不在规则库中或作用域未编译
+    Skipped,
+}
+
+/// 单个样例的自检结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelfTestCase {
+    /// 样例名称
+    pub name: String,
+    /// 样例覆盖的作用域
+    pub scope: PruneScope,
+    /// 预期技术
+    pub expected: String,
+    /// 自检状态
+    pub status: SelfTestStatus,
+    /// 失败详情（实际检出的技术 / 检测错误）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// 自检报告
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelfTestReport {
+    /// 各样例结果（按内置样例顺序）
+    pub cases: Vec<SelfTestCase>,
+    /// 自检耗时
+    pub elapsed: Duration,
+}
+
+impl SelfTestReport {
+    /// 通过的样例数
+    pub fn passed(&self) -> usize {
+        self.count(SelfTestStatus::Passed)
+    }
+
+    /// 失败的样例数
+    pub fn failed(&self) -> usize {
+        self.count(SelfTestStatus::Failed)
+    }
+
+    /// 跳过的样例数
+    pub fn skipped(&self) -> usize {
+        self.count(SelfTestStatus::Skipped)
+    }
+
+    /// 是否健康（至少一个样例通过且无失败样例）
+    pub fn is_healthy(&self) -> bool {
+        self.passed() > 0 && self.failed() == 0
+    }
+
+    /// 失败的样例
+    pub fn failures(&self) -> impl Iterator<Item = &SelfTestCase> {
+        self.cases.iter().filter(|case| case.status == SelfTestStatus::Failed)
+    }
+
+    fn count(&self, status: SelfTestStatus) -> usize {
+        self.cases.iter().filter(|case| case.status == status).count()
+    }
+}
+
+impl TechDetector {
+    /// 用内置合成样例自检已加载的规则库（适用于健康检查/接流量前校验）
+    /// 返回：自检报告（is_healthy 为 false 时规则库可能已损坏或被过度剪枝）
+    pub fn self_test(&self) -> SelfTestReport {
+        let start = Instant::now();
+        let cases = FIXTURES.iter().map(|fixture| self.run_fixture(fixture)).collect();
+        SelfTestReport {
+            cases,
+            elapsed: start.elapsed(),
+        }
+    }
+
+    fn run_fixture(&self, fixture: &Fixture) -> SelfTestCase {
+        let mut case = SelfTestCase {
+            name: fixture.name.to_string(),
+            scope: fixture.scope,
+            expected: fixture.expected.to_string(),
+            status: SelfTestStatus::Skipped,
+            detail: None,
+        };
+        let compiled = self
+            .config
+            .options
+            .compile_scopes
+            .as_ref()
+            .is_none_or(|scopes| scopes.contains(&fixture.scope));
+        if !compiled || !self.compiled_library().tech_patterns.contains_key(fixture.expected) {
+            return case;
+        }
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in fixture.headers {
+            headers.insert(HeaderName::from_static(name), HeaderValue::from_static(value));
+        }
+        match self.detect(&headers, &[], fixture.body.as_bytes()) {
+            Ok(result) if result.technologies.iter().any(|t| t.name == fixture.expected) => {
+                case.status = SelfTestStatus::Passed;
+            }
+            Ok(result) => {
+                let detected: Vec<&str> = result.technologies.iter().map(|t| t.name.as_str()).collect();
+                case.status = SelfTestStatus::Failed;
+                case.detail = Some(format!("detected: [{}]", detected.join(", ")));
+            }
+            Err(e) => {
+                case.status = SelfTestStatus::Failed;
+                case.detail = Some(e.to_string());
+            }
+        }
+        case
+    }
+}
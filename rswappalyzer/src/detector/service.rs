@@ -0,0 +1,98 @@
+//! tower::Service 适配（tower 特性）
+//! 将检测器封装为 `Service<DetectInput>`，可直接组合进已有 tower 栈（重试/超时/指标等 Layer）；
+//! 检测在 Tokio 阻塞线程池中执行，并发上限与过载丢弃由 DetectServiceBuilder 配置：
+//! 1. concurrency_limit：同时执行的检测数上限，超出时 poll_ready 挂起（背压）
+//! 2. load_shed：未就绪时直接返回 Overloaded 错误而非等待
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use tower::util::BoxCloneService;
+use tower::{BoxError, Service, ServiceBuilder, ServiceExt};
+
+use super::detector::TechDetector;
+use super::input::DetectInput;
+use crate::error::{RswResult, RswappalyzerError};
+use crate::DetectResult;
+
+/// 检测服务（无并发限制，始终就绪）
+#[derive(Debug, Clone)]
+pub struct DetectService {
+    detector: Arc<TechDetector>,
+}
+
+impl DetectService {
+    /// 创建检测服务
+    pub fn new(detector: Arc<TechDetector>) -> Self {
+        Self { detector }
+    }
+
+    /// 创建带并发上限/过载丢弃的服务构建器
+    pub fn builder(detector: Arc<TechDetector>) -> DetectServiceBuilder {
+        DetectServiceBuilder {
+            detector,
+            concurrency_limit: None,
+            load_shed: false,
+        }
+    }
+}
+
+impl Service<DetectInput> for DetectService {
+    type Response = DetectResult;
+    type Error = RswappalyzerError;
+    type Future = Pin<Box<dyn Future<Output = RswResult<DetectResult>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, input: DetectInput) -> Self::Future {
+        let detector = self.detector.clone();
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || detector.run(&input))
+            .await?
+        })
+    }
+}
+
+/// 检测服务构建器
+#[derive(Debug, Clone)]
+pub struct DetectServiceBuilder {
+    detector: Arc<TechDetector>,
+    concurrency_limit: Option<usize>,
+    load_shed: bool,
+}
+
+impl DetectServiceBuilder {
+    /// 设置并发检测上限（所有克隆的服务实例共享该上限）
+    pub fn concurrency_limit(mut self, max: usize) -> Self {
+        self.concurrency_limit = Some(max);
+        self
+    }
+
+    /// 开启过载丢弃（达到并发上限时立即返回 tower::load_shed::error::Overloaded）
+    pub fn load_shed(mut self, enabled: bool) -> Self {
+        self.load_shed = enabled;
+        self
+    }
+
+    /// 构建服务（错误类型统一为 BoxError，便于继续叠加 tower Layer）
+    pub fn build(self) -> BoxCloneService<DetectInput, DetectResult, BoxError> {
+        let service = DetectService::new(self.detector).map_err(BoxError::from);
+        match (self.concurrency_limit, self.load_shed) {
+            (Some(max), true) => ServiceBuilder::new()
+                .load_shed()
+                .concurrency_limit(max)
+                .service(service)
+                .boxed_clone(),
+            (Some(max), false) => ServiceBuilder::new()
+                .concurrency_limit(max)
+                .service(service)
+                .boxed_clone(),
+            (None, true) => ServiceBuilder::new().load_shed().service(service).boxed_clone(),
+            (None, false) => service.boxed_clone(),
+        }
+    }
+}
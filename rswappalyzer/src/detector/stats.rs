@@ -0,0 +1,94 @@
+//! 按规则源的检测统计
+//! 多规则源合并（官方规则 + 内部补充规则）后，按规则源汇总规则规模与检测命中数，
+//! 用于评估内部规则相对官方规则的实际产出；命中计数在检测器及其克隆之间共享，进程内累计
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use once_cell::sync::OnceCell;
+use rswappalyzer_engine::{CompiledRuleLibrary, UNATTRIBUTED_SOURCE};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use super::detector::TechDetector;
+
+/// 单个规则源的统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceStats {
+    /// 技术数量
+    pub tech_count: usize,
+    /// 模式数量
+    pub pattern_count: usize,
+    /// 累计检出次数（每个检测结果中的每项技术计一次）
+    pub detections: u64,
+}
+
+/// 检测器统计
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DetectorStats {
+    /// 按规则源统计（规则源名称 -> 统计；未记录规则源的技术归入 UNATTRIBUTED_SOURCE）
+    pub sources: BTreeMap<String, SourceStats>,
+}
+
+impl DetectorStats {
+    /// 全部规则源的累计检出次数
+    pub fn total_detections(&self) -> u64 {
+        self.sources.values().map(|stats| stats.detections).sum()
+    }
+}
+
+/// 按规则源的检出计数器（首次使用时按规则库的规则源集合初始化，之后无锁累加）
+#[derive(Debug, Default)]
+pub(crate) struct SourceCounters {
+    detections: OnceCell<FxHashMap<String, AtomicU64>>,
+}
+
+impl SourceCounters {
+    fn counters(&self, compiled_lib: &CompiledRuleLibrary) -> &FxHashMap<String, AtomicU64> {
+        self.detections.get_or_init(|| {
+            compiled_lib
+                .source_stats()
+                .into_keys()
+                .map(|source| (source, AtomicU64::new(0)))
+                .collect()
+        })
+    }
+
+    /// 记录一次检出
+    /// 参数：
+    /// - compiled_lib: 检测器使用的规则库
+    /// - source: 技术所属规则源
+    pub(crate) fn record(&self, compiled_lib: &CompiledRuleLibrary, source: Option<&str>) {
+        let source = source.unwrap_or(UNATTRIBUTED_SOURCE);
+        if let Some(counter) = self.counters(compiled_lib).get(source) {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn detections(&self, compiled_lib: &CompiledRuleLibrary, source: &str) -> u64 {
+        self.counters(compiled_lib)
+            .get(source)
+            .map_or(0, |counter| counter.load(Ordering::Relaxed))
+    }
+}
+
+impl TechDetector {
+    /// 按规则源的规则规模与累计检出次数
+    pub fn stats(&self) -> DetectorStats {
+        let compiled_lib = self.compiled_library();
+        let sources = compiled_lib
+            .source_stats()
+            .into_iter()
+            .map(|(source, rules)| {
+                let detections = self.source_counters.detections(compiled_lib, &source);
+                let stats = SourceStats {
+                    tech_count: rules.tech_count,
+                    pattern_count: rules.pattern_count,
+                    detections,
+                };
+                (source, stats)
+            })
+            .collect();
+        DetectorStats { sources }
+    }
+}
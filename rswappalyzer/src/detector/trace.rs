@@ -0,0 +1,351 @@
+//! 结构化检测追踪
+//! detect_log 的逐阶段统计以结构化数据返回（可序列化为 JSON），便于服务场景采集与上报：
+//! 1. 各阶段耗时（微秒）与阶段内新增命中的技术（作用域阶段由 detect 共用的流水线回调记录）
+//! 2. 令牌索引型作用域的候选统计：进入候选集的技术数、有规则但被剪枝跳过的技术数
+//! 3. 依赖/推导/互斥后处理的变化（推导新增、依赖未满足移除、互斥移除）
+//! 4. 检测结果附带命中证据（每次均采集，不受采样约束）
+
+use std::time::Instant;
+
+use http::header::HeaderMap;
+use rswappalyzer_engine::thread_regex_budget_exceeded;
+use rustc_hash::FxHashSet;
+use serde::Serialize;
+
+use super::detector::{
+    with_regex_budget_warning, Detected, PreparedInput, ScopeInput, ScopeOutcome, StageObserver, TechDetector,
+};
+use super::evidence::{EvidenceCapture, EvidenceInput};
+use super::input::{DetectInput, SupplementalInput};
+use super::options::DetectOptions;
+use super::progressive::DetectStage;
+use crate::analyzer::candidate_stats;
+use crate::error::RswResult;
+use crate::utils::PreparedHeaders;
+use crate::DetectResult;
+
+/// 单个阶段的追踪记录
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StageTrace {
+    /// 阶段名（如 `header_conversion` / `url` / `html` / `post_process`）
+    pub stage: &'static str,
+    /// 阶段耗时（微秒）
+    pub duration_us: u64,
+    /// 进入候选集的技术数（仅令牌索引型作用域）
+    pub candidates: usize,
+    /// 有该作用域规则但被剪枝跳过的技术数（仅令牌索引型作用域）
+    pub pruned: usize,
+    /// 阶段内新增命中的技术（按名称排序）
+    pub matched: Vec<String>,
+}
+
+/// 结构化检测追踪
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DetectTrace {
+    /// 各阶段追踪记录（按执行顺序）
+    pub stages: Vec<StageTrace>,
+    /// 关联推导新增的技术
+    pub implied: Vec<String>,
+    /// 依赖未满足被移除的技术
+    pub unmet_requires: Vec<String>,
+    /// 互斥规则移除的技术
+    pub excluded: Vec<String>,
+    /// 检测总耗时（微秒）
+    pub total_us: u64,
+    /// 检测结果（含命中证据）
+    pub result: DetectResult,
+}
+
+impl DetectTrace {
+    /// 指定阶段的追踪记录
+    pub fn stage(&self, stage: &str) -> Option<&StageTrace> {
+        self.stages.iter().find(|trace| trace.stage == stage)
+    }
+}
+
+/// 阶段记录器（作用域阶段作为共享流水线的观察者记录，其余步骤通过 run 记录）
+#[derive(Default)]
+struct StageRecorder {
+    stages: Vec<StageTrace>,
+    /// 当前作用域阶段的开始时间与阶段前已命中的技术
+    current: Option<(Instant, FxHashSet<String>)>,
+}
+
+impl StageRecorder {
+    /// 执行并记录一个步骤（耗时 + 新增命中技术）
+    fn run<R>(&mut self, stage: &'static str, detected: &mut Detected, f: impl FnOnce(&mut Detected) -> R) -> R {
+        let before: FxHashSet<String> = detected.keys().cloned().collect();
+        let start = Instant::now();
+        let output = f(detected);
+        self.push(stage, start, &before, detected);
+        output
+    }
+
+    /// 追加阶段记录
+    fn push(&mut self, stage: &'static str, start: Instant, before: &FxHashSet<String>, detected: &Detected) {
+        let duration_us = start.elapsed().as_micros() as u64;
+        let mut matched: Vec<String> = detected.keys().filter(|name| !before.contains(*name)).cloned().collect();
+        matched.sort();
+        self.stages.push(StageTrace {
+            stage,
+            duration_us,
+            matched,
+            ..StageTrace::default()
+        });
+    }
+}
+
+impl StageObserver for StageRecorder {
+    fn stage_started(&mut self, _stage: DetectStage, detected: &Detected) {
+        self.current = Some((Instant::now(), detected.keys().cloned().collect()));
+    }
+
+    fn stage_finished(&mut self, stage: DetectStage, detected: &Detected, input: &ScopeInput<'_>) {
+        let Some((start, before)) = self.current.take() else {
+            return;
+        };
+        self.push(stage.name(), start, &before, detected);
+        if let (Some(last), Some(stats)) = (self.stages.last_mut(), stage_candidates(stage, input)) {
+            (last.candidates, last.pruned) = stats;
+        }
+    }
+}
+
+/// 令牌索引型作用域阶段的候选统计（复用分析时提取的令牌）
+/// 返回：(候选技术数, 被剪枝跳过的技术数)；非令牌索引型阶段返回 None
+fn stage_candidates(stage: DetectStage, input: &ScopeInput<'_>) -> Option<(usize, usize)> {
+    let ScopeInput { lib, headers, prepared, supplemental, .. } = *input;
+    let scope = stage.scope()?;
+    let headers = std::iter::once(headers).chain(supplemental.hop_headers);
+    let body = prepared.body;
+    let token_sets: Vec<&FxHashSet<String>> = match stage {
+        DetectStage::Url => vec![prepared.url_tokens()],
+        DetectStage::Header => headers.map(|headers| &headers.header_tokens).collect(),
+        DetectStage::Cookie => headers.map(|headers| &headers.cookie_tokens).collect(),
+        DetectStage::Html => vec![prepared.html_tokens()],
+        DetectStage::Script if body.is_document() => {
+            vec![prepared.script_src_tokens(), prepared.inline_script_tokens()]
+        }
+        DetectStage::Script => vec![prepared.script_body_tokens(), prepared.script_src_tokens()],
+        DetectStage::Meta => vec![prepared.meta_tokens()],
+        DetectStage::Title => vec![prepared.title_tokens()],
+        DetectStage::Comment => vec![prepared.comment_tokens()],
+        _ => return None,
+    };
+    let tokens: FxHashSet<String> = token_sets.into_iter().flatten().cloned().collect();
+    Some(candidate_stats(lib, &tokens, scope))
+}
+
+impl TechDetector {
+    /// 带结构化追踪的检测（各阶段耗时/候选统计/命中证据，可序列化为 JSON）
+    /// 参数：
+    /// - headers: HTTP头信息（HeaderMap）
+    /// - urls: 检测的URL列表
+    /// - body: HTTP响应体（字节数组）
+    ///
+    /// 返回：检测追踪（含检测结果） | 错误
+    pub fn detect_traced(&self, headers: &HeaderMap, urls: &[&str], body: &[u8]) -> RswResult<DetectTrace> {
+        let capture = EvidenceCapture::default();
+        self.trace_inner(headers, urls, body, SupplementalInput::default(), Some(&capture))
+    }
+
+    /// 按检测请求执行带结构化追踪的检测（补充输入对应 JS/证书颁发者/DNS/Favicon 阶段）
+    /// 参数：input - 检测请求
+    ///
+    /// 返回：检测追踪（含检测结果） | 错误
+    pub fn run_traced(&self, input: &DetectInput) -> RswResult<DetectTrace> {
+        let hop_headers = input.prepare_hops();
+        let hash = input.favicon_hash();
+        let supplemental = input.supplemental(&hop_headers, hash.as_deref());
+        let capture = EvidenceCapture::default();
+        self.trace_inner(&input.headers, &input.chain_urls(), &input.body, supplemental, Some(&capture))
+    }
+
+    /// 追踪主流程（作用域分析与 detect 共用同一流水线，capture 为 None 时不采集证据）
+    pub(super) fn trace_inner(
+        &self,
+        headers: &HeaderMap,
+        urls: &[&str],
+        body: &[u8],
+        supplemental: SupplementalInput<'_>,
+        capture: Option<&EvidenceCapture>,
+    ) -> RswResult<DetectTrace> {
+        let total_start = Instant::now();
+        let regex_budget_before = thread_regex_budget_exceeded();
+        let lib = self.compiled_library();
+        let mut recorder = StageRecorder::default();
+        let options = DetectOptions::default();
+
+        // 1. Header 预处理与响应体预处理
+        let headers = recorder.run("header_conversion", &mut Detected::default(), |_| {
+            PreparedHeaders::prepare(headers)
+        });
+        let body = recorder.run("body_preparation", &mut Detected::default(), |_| {
+            self.prepare_detect_body(&headers, body, &options, supplemental.script_srcs)
+        });
+        let extract = &body.extract;
+
+        // 2. 作用域分析（阶段记录由共享流水线回调）
+        let prepared = PreparedInput::new(urls, &body);
+        let input = ScopeInput {
+            lib,
+            headers: &headers,
+            prepared: &prepared,
+            supplemental: &supplemental,
+            options: &options,
+            deadline: None,
+        };
+        let ScopeOutcome {
+            mut detected,
+            scopes,
+            generator_hits,
+            script_outcome,
+        } = self.analyze_scopes(&input, Some(&mut recorder))?;
+        self.merge_generator_hits(&mut detected, generator_hits);
+
+        // 3. 依赖 / 推导 / 互斥
+        let (unmet_requires, imply_map, excluded) = recorder.run("post_process", &mut detected, |detected| {
+            let (unmet, imply_map) = self.apply_requires_and_implies(detected, &script_outcome);
+            let excluded = self.apply_excludes(detected);
+            (unmet, imply_map, excluded)
+        });
+
+        // 4. 结果聚合与证据采集
+        let mut warnings = with_regex_budget_warning(body.warnings.clone(), regex_budget_before);
+        let mut technologies = recorder.run("aggregate", &mut Detected::default(), |_| {
+            self.build_technologies(detected, &imply_map, script_outcome.hosts, &scopes)
+        });
+        if let Some(capture) = capture {
+            let script = String::from_utf8_lossy(&body.script);
+            let input = EvidenceInput {
+                urls,
+                headers: headers.headers(),
+                cookies: headers.cookies(),
+                html: &body.html,
+                script_srcs: &extract.script_srcs,
+                script: &script,
+                meta_tags: &extract.meta_tags,
+                title: &extract.title,
+                comments: &extract.comments,
+                dom_selectors: self.dom_index.selectors(lib),
+                supplemental,
+            };
+            recorder.run("evidence", &mut Detected::default(), |_| {
+                self.attach_evidence(&mut technologies, &input, capture, &mut warnings)
+            });
+        }
+
+        let mut result = DetectResult { technologies, warnings };
+        self.post_processors.apply(&mut result);
+        let mut implied: Vec<String> = imply_map.into_keys().collect();
+        implied.sort();
+        Ok(DetectTrace {
+            stages: recorder.stages,
+            implied,
+            unmet_requires,
+            excluded,
+            total_us: total_start.elapsed().as_micros() as u64,
+            result,
+        })
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::header::HeaderValue;
+
+    use super::*;
+    use crate::test_support::{detector, names};
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "PHP": { "cats": [27], "headers": { "X-Powered-By": "^php/?([\\d.]+)?\\;version:\\1" } },
+            "WordPress": { "cats": [1], "implies": "PHP", "meta": { "generator": "^WordPress" } },
+            "Acme Widget": { "cats": [5], "scriptSrc": "acme-widget\\.js" }
+        }
+    }"#;
+
+    #[test]
+    fn test_stage_recorder_matched() {
+        let mut recorder = StageRecorder::default();
+        let mut detected = Detected::default();
+        detected.insert("PHP".to_string(), (100, None));
+        let output = recorder.run("meta", &mut detected, |detected| {
+            detected.insert("WordPress".to_string(), (100, None));
+            detected.insert("Acme Widget".to_string(), (100, None));
+            detected.len()
+        });
+        assert_eq!(output, 3);
+        // 仅记录阶段内新增命中的技术（按名称排序）
+        let stage = &recorder.stages[0];
+        assert_eq!(stage.stage, "meta");
+        assert_eq!(stage.matched, ["Acme Widget", "WordPress"]);
+        assert_eq!((stage.candidates, stage.pruned), (0, 0));
+    }
+
+    #[test]
+    fn test_detect_traced() {
+        let detector = detector(RULES);
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", HeaderValue::from_static("text/html"));
+        headers.insert("x-powered-by", HeaderValue::from_static("PHP/8.2.1"));
+        let body = br#"<html><head><meta name="generator" content="WordPress 6.4"></head><body></body></html>"#;
+
+        let trace = detector.detect_traced(&headers, &["https://example.com/"], body).unwrap();
+        let header = trace.stage("header").unwrap();
+        assert_eq!(header.matched, ["PHP"]);
+        assert!(header.candidates >= 1, "{:?}", header);
+        assert_eq!(trace.stage("meta").unwrap().matched, ["WordPress"]);
+        assert!(trace.stage("script").unwrap().matched.is_empty());
+        // PHP 已由 Header 命中，推导不计入新增
+        assert!(trace.implied.is_empty());
+        assert!(trace.unmet_requires.is_empty() && trace.excluded.is_empty());
+
+        // 追踪结果每次均采集证据
+        let php = trace.result.technologies.iter().find(|t| t.name == "PHP").unwrap();
+        assert_eq!(php.version.as_deref(), Some("8.2.1"));
+        assert!(!php.evidence.is_empty());
+
+        let json = serde_json::to_value(&trace).unwrap();
+        let stages: Vec<&str> = json["stages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|stage| stage["stage"].as_str().unwrap())
+            .collect();
+        assert_eq!(stages[..2], ["header_conversion", "body_preparation"]);
+        assert_eq!(stages[stages.len() - 3..], ["post_process", "aggregate", "evidence"]);
+        assert!(json["total_us"].is_u64());
+
+        // detect_log 共用追踪流程但不采集证据
+        let logged = detector.detect_log(&headers, &["https://example.com/"], body).unwrap();
+        assert_eq!(names(&logged), ["PHP", "WordPress"]);
+        assert!(logged.technologies.iter().all(|t| t.evidence.is_empty()));
+    }
+
+    #[test]
+    fn test_run_traced_supplemental_stages() {
+        let detector = detector(
+            r#"{
+                "technologies": {
+                    "Acme CA": { "cats": [70], "certIssuer": "Acme CA" },
+                    "Acme Portal": { "cats": [1], "html": "acme-portal-root", "implies": "Acme Runtime" },
+                    "Acme Runtime": { "cats": [27], "headers": { "X-Acme-Runtime": "" } }
+                }
+            }"#,
+        );
+        let input = DetectInput::builder()
+            .body("<html><body id=\"acme-portal-root\"></body></html>")
+            .cert_issuer("Acme CA")
+            .build();
+
+        let trace = detector.run_traced(&input).unwrap();
+        assert_eq!(trace.stage("cert_issuer").unwrap().matched, ["Acme CA"]);
+        assert_eq!(trace.stage("html").unwrap().matched, ["Acme Portal"]);
+        assert_eq!(trace.implied, ["Acme Runtime"]);
+        // 未提供的补充输入不产生阶段记录
+        assert!(trace.stage("dns").is_none());
+        assert_eq!(names(&trace.result), names(&detector.run(&input).unwrap()));
+    }
+}
@@ -0,0 +1,404 @@
+//! 分诊模式（超高吞吐被动流量的廉价预筛）
+//! 1万+ RPS 的旁路镜像流量无法逐条完整检测，分诊只执行令牌证据索引与模式准入门槛（MatchGate），
+//! 不执行任何正则、不提取版本、不解析 HTML，得到"可能存在的技术"集合，并按升级名单决定是否送完整检测：
+//! 1. URL/HTML/Script/Meta：证据令牌命中的技术 + 无证据技术的门槛字面量自动机（仅 Open 门槛的无证据技术只能靠正则判定，分诊不覆盖）
+//! 2. Header/Cookie：证据令牌候选 + 无证据技术（按键名查找，存在性模式直接计为可能）
+//! 3. Hostname：后缀字典树查找（本身不含正则）
+//! 4. 响应体不做 HTML 解析：整段文本作为 HTML/Meta 输入，Script 作用域仅扫描 `<script src>` 地址（结果为完整检测的近似超集）
+//! 5. 不应用 implies 推导（推导技术随完整检测产出）
+//! 6. DOM 选择器规则需解析文档，分诊不覆盖（仅由 DOM 规则检出的技术只能靠完整检测判定）
+
+use std::borrow::{Borrow, Cow};
+use std::collections::BTreeMap;
+
+use aho_corasick::{AhoCorasick, AhoCorasickKind};
+use once_cell::sync::OnceCell;
+use rswappalyzer_engine::scope_pruner::PruneScope;
+use rswappalyzer_engine::{url_host, CompiledPattern, CompiledRuleLibrary, CompiledTechRule, MatchGate};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+
+use super::detector::TechDetector;
+use super::input::DetectInput;
+use crate::analyzer::candidate_collector::collect_candidate_techs;
+use crate::analyzer::extract_tokens;
+use crate::utils::extractor::html_input_guard::HtmlInputGuard;
+use crate::utils::{BodyKind, PreparedHeaders};
+
+/// 分诊结果
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TriageResult {
+    /// 可能存在的技术（技术名 -> 通过准入门槛的作用域，按 PruneScope::ALL 顺序）
+    pub possible: BTreeMap<String, Vec<PruneScope>>,
+    /// 是否建议升级为完整检测
+    pub escalate: bool,
+}
+
+impl TriageResult {
+    /// 是否可能存在指定技术
+    pub fn contains(&self, tech: &str) -> bool {
+        self.possible.contains_key(tech)
+    }
+
+    /// 可能存在的技术数量
+    pub fn len(&self) -> usize {
+        self.possible.len()
+    }
+
+    /// 是否无任何可能技术
+    pub fn is_empty(&self) -> bool {
+        self.possible.is_empty()
+    }
+}
+
+impl TechDetector {
+    /// 分诊检测：仅执行证据索引与准入门槛，不执行正则与版本提取
+    /// 参数：input - 检测请求
+    ///
+    /// 返回：可能存在的技术及是否建议升级为完整检测
+    pub fn detect_triage(&self, input: &DetectInput) -> TriageResult {
+        let headers = PreparedHeaders::prepare(&input.headers);
+        self.triage_prepared(&headers, &input.chain_urls(), &input.body)
+    }
+
+    /// 使用预处理Header分诊（批量扫描快速路径）
+    /// 参数：
+    /// - headers: 预处理Header集合
+    /// - urls: 检测的URL列表
+    /// - body: HTTP响应体（字节数组）
+    ///
+    /// 返回：可能存在的技术及是否建议升级为完整检测
+    pub fn triage_prepared(&self, headers: &PreparedHeaders, urls: &[&str], body: &[u8]) -> TriageResult {
+        let lib = self.compiled_library().as_ref();
+        let mut possible: BTreeMap<String, FxHashSet<PruneScope>> = BTreeMap::new();
+        let mut mark = |tech: &str, scope: PruneScope| {
+            possible.entry(tech.to_string()).or_default().insert(scope);
+        };
+
+        let gates = self.triage_index.gates(lib);
+        if !urls.is_empty() {
+            let tokens = extract_tokens(urls);
+            for tech in gated_list_techs(lib, &tokens, PruneScope::Url, urls, |t| t.url_patterns.as_deref()) {
+                mark(tech, PruneScope::Url);
+            }
+            for url in urls {
+                gates.scan(&[PruneScope::Url], url, &tokens, &mut mark);
+            }
+            for host in urls.iter().filter_map(|url| url_host(url)) {
+                for rule in lib.hostname_index.lookup(&host) {
+                    mark(&rule.tech, PruneScope::Hostname);
+                }
+            }
+        }
+        let header_techs = gated_keyed_techs(
+            lib,
+            &headers.header_tokens,
+            PruneScope::Header,
+            |t| t.header_patterns.as_ref(),
+            |key| headers.headers().get(key).map(std::slice::from_ref),
+        );
+        for tech in header_techs {
+            mark(tech, PruneScope::Header);
+        }
+        let cookie_techs = gated_keyed_techs(
+            lib,
+            &headers.cookie_tokens,
+            PruneScope::Cookie,
+            |t| t.cookie_patterns.as_ref(),
+            |key| headers.cookies().get(key).map(Vec::as_slice),
+        );
+        for tech in cookie_techs {
+            mark(tech, PruneScope::Cookie);
+        }
+
+        if let Some((kind, text)) = self.triage_body(headers, body) {
+            let text = text.as_ref();
+            let tokens = extract_tokens([text]);
+            if kind == BodyKind::Script {
+                let script_techs =
+                    gated_list_techs(lib, &tokens, PruneScope::Script, &[text], |t| t.script_patterns.as_deref());
+                for tech in script_techs {
+                    mark(tech, PruneScope::Script);
+                }
+                gates.scan(&[PruneScope::Script], text, &tokens, &mut mark);
+            } else {
+                for tech in gated_list_techs(lib, &tokens, PruneScope::Html, &[text], |t| t.html_patterns.as_deref()) {
+                    mark(tech, PruneScope::Html);
+                }
+                // HTML 文档的 Meta 门槛与 HTML 门槛在同一次扫描中完成
+                let gate_scopes: &[PruneScope] = if kind == BodyKind::Html {
+                    &[PruneScope::Html, PruneScope::Meta]
+                } else {
+                    &[PruneScope::Html]
+                };
+                gates.scan(gate_scopes, text, &tokens, &mut mark);
+            }
+            if kind == BodyKind::Html {
+                let meta_techs = gated_list_techs(lib, &tokens, PruneScope::Meta, &[text], |t| {
+                    t.meta_patterns.as_ref().map(|patterns| patterns.values().flatten())
+                });
+                for tech in meta_techs {
+                    mark(tech, PruneScope::Meta);
+                }
+
+                // 与完整检测一致，Script 作用域只看脚本地址（整段文本的令牌会使候选数膨胀一个数量级）
+                let srcs = scan_script_srcs(text);
+                if !srcs.is_empty() {
+                    let src_tokens = extract_tokens([srcs.as_str()]);
+                    let script_techs = gated_list_techs(lib, &src_tokens, PruneScope::Script, &[&srcs], |t| {
+                        t.script_patterns.as_deref()
+                    });
+                    for tech in script_techs {
+                        mark(tech, PruneScope::Script);
+                    }
+                    gates.scan(&[PruneScope::Script], &srcs, &src_tokens, &mut mark);
+                }
+            }
+        }
+
+        let escalate_list = &self.config.options.triage_escalate;
+        let escalate = if escalate_list.is_empty() {
+            !possible.is_empty()
+        } else {
+            possible.keys().any(|tech| escalate_list.contains(tech))
+        };
+        let possible = possible
+            .into_iter()
+            .map(|(tech, scopes)| {
+                let scopes = PruneScope::ALL.into_iter().filter(|scope| scopes.contains(scope)).collect();
+                (tech, scopes)
+            })
+            .collect();
+        TriageResult { possible, escalate }
+    }
+
+    /// 分诊用响应体：解码后按 HTML 输入上限截断，不做输入守卫与提取
+    /// 返回：(响应体类型, 文本) | None（空/二进制/短于最小分析长度/未编译响应体作用域）
+    fn triage_body<'a>(&self, headers: &PreparedHeaders, body: &'a [u8]) -> Option<(BodyKind, Cow<'a, str>)> {
+        let options = &self.config.options;
+        if body.is_empty() || !self.compiles_body_scopes() {
+            return None;
+        }
+        let headers = headers.headers();
+        let content_type = headers.get("content-type").map(String::as_str);
+        let kind = if options.content_type_routing {
+            BodyKind::from_content_type(content_type)
+        } else {
+            BodyKind::Html
+        };
+        if kind == BodyKind::Binary {
+            return None;
+        }
+        let content_encoding = headers.get("content-encoding").map(String::as_str);
+        let bytes = self.decompress(body, content_encoding, &mut Vec::new());
+        if options.min_body_len > 0 && bytes.trim_ascii().len() < options.min_body_len {
+            return None;
+        }
+        // 解码前按字节截断，容错解码最多复制 MAX_HTML_LEN 字节
+        let bytes = HtmlInputGuard::truncate_bytes(bytes);
+        let mut text = self.decode(bytes, content_type, &mut Vec::new());
+        // 容错解码的替换字符可能使文本超出上限
+        let mut end = text.len().min(HtmlInputGuard::MAX_HTML_LEN);
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        match &mut text {
+            Cow::Borrowed(borrowed) => *borrowed = &borrowed[..end],
+            Cow::Owned(owned) => owned.truncate(end),
+        }
+        Some((kind, text))
+    }
+}
+
+/// 轻量扫描 `<script ... src=...>` 的脚本地址（不解析 HTML，按换行拼接，与提取器的 script_src_combined 格式一致）
+fn scan_script_srcs(html: &str) -> String {
+    let mut srcs = String::new();
+    let lower = html.to_ascii_lowercase();
+    let mut offset = 0;
+    while let Some(start) = lower[offset..].find("<script") {
+        let tag_start = offset + start;
+        let tag_end = lower[tag_start..].find('>').map_or(lower.len(), |end| tag_start + end);
+        offset = tag_end;
+        let tag = &lower[tag_start..tag_end];
+        let attr = tag
+            .match_indices("src=")
+            .find(|(pos, _)| tag.as_bytes()[pos - 1].is_ascii_whitespace())
+            .map(|(pos, _)| pos + "src=".len());
+        let Some(attr) = attr else {
+            continue;
+        };
+        let value_start = tag_start + attr;
+        let value = match html.as_bytes().get(value_start) {
+            Some(quote @ (b'"' | b'\'')) => html[value_start + 1..tag_end].split(*quote as char).next(),
+            Some(_) => html[value_start..tag_end].split(|c: char| c.is_ascii_whitespace()).next(),
+            None => None,
+        };
+        if let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) {
+            srcs.push_str(value);
+            srcs.push('\n');
+        }
+    }
+    srcs
+}
+
+/// 分诊门槛索引（检测器克隆间共享，首次分诊时按规则库构建）
+#[derive(Debug, Default)]
+pub(crate) struct TriageIndex {
+    gates: OnceCell<GateAutomaton>,
+}
+
+impl TriageIndex {
+    fn gates(&self, lib: &CompiledRuleLibrary) -> &GateAutomaton {
+        self.gates.get_or_init(|| GateAutomaton::build(lib))
+    }
+}
+
+/// 无证据技术的准入门槛索引（URL/HTML/Script/Meta）
+/// 无证据技术没有可查的证据令牌，逐个校验门槛的成本与完整检测相当；
+/// 将全部结构字面量门槛编入一个 Aho-Corasick 自动机，单次扫描输入即得到通过门槛的技术
+#[derive(Debug, Default)]
+struct GateAutomaton {
+    /// 结构字面量自动机（无字面量门槛时为 None）
+    literals: Option<AhoCorasick>,
+    /// 字面量序号 -> 持有该字面量门槛的 (技术名, 作用域)
+    literal_owners: Vec<Vec<(String, PruneScope)>>,
+    /// 最小证据门槛：(作用域, 技术名, 必需令牌)
+    token_gates: Vec<(PruneScope, String, Vec<String>)>,
+}
+
+impl GateAutomaton {
+    fn build(lib: &CompiledRuleLibrary) -> Self {
+        let mut index = Self::default();
+        let mut literal_ids: FxHashMap<&str, usize> = FxHashMap::default();
+        let mut literals: Vec<&str> = Vec::new();
+        for scope in [PruneScope::Url, PruneScope::Html, PruneScope::Script, PruneScope::Meta] {
+            let Some(no_evidence) = lib.no_evidence_index.get(&scope) else {
+                continue;
+            };
+            for tech in no_evidence.iter().filter_map(|name| lib.tech_patterns.get(name)) {
+                let patterns: Vec<&CompiledPattern> = match scope {
+                    PruneScope::Url => tech.url_patterns.iter().flatten().collect(),
+                    PruneScope::Html => tech.html_patterns.iter().flatten().collect(),
+                    PruneScope::Script => tech.script_patterns.iter().flatten().collect(),
+                    _ => tech.meta_patterns.iter().flat_map(|map| map.values().flatten()).collect(),
+                };
+                for pattern in patterns {
+                    match &pattern.exec.match_gate {
+                        MatchGate::Open => {}
+                        MatchGate::RequireAll(tokens) => {
+                            index
+                                .token_gates
+                                .push((scope, tech.name.clone(), tokens.iter().cloned().collect()));
+                        }
+                        MatchGate::RequireAnyLiteral(list) => {
+                            for literal in list {
+                                let id = *literal_ids.entry(literal.as_str()).or_insert_with(|| {
+                                    literals.push(literal.as_str());
+                                    index.literal_owners.push(Vec::new());
+                                    literals.len() - 1
+                                });
+                                let owner = (tech.name.clone(), scope);
+                                if !index.literal_owners[id].contains(&owner) {
+                                    index.literal_owners[id].push(owner);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        if !literals.is_empty() {
+            index.literals = AhoCorasick::builder()
+                .kind(Some(AhoCorasickKind::DFA))
+                .build(&literals)
+                .inspect_err(|e| log::warn!("Failed to build triage literal automaton: {}", e))
+                .ok();
+        }
+        index
+    }
+
+    /// 扫描输入，回调通过门槛的 (技术名, 作用域)
+    /// 参数：
+    /// - scopes: 参与的作用域
+    /// - input: 输入文本
+    /// - tokens: 输入令牌
+    /// - mark: 回调
+    fn scan(
+        &self,
+        scopes: &[PruneScope],
+        input: &str,
+        tokens: &FxHashSet<String>,
+        mut mark: impl FnMut(&str, PruneScope),
+    ) {
+        if let Some(literals) = &self.literals {
+            for found in literals.find_overlapping_iter(input) {
+                for (tech, scope) in &self.literal_owners[found.pattern().as_usize()] {
+                    if scopes.contains(scope) {
+                        mark(tech, *scope);
+                    }
+                }
+            }
+        }
+        for (scope, tech, required) in &self.token_gates {
+            if scopes.contains(scope) && required.iter().all(|token| tokens.contains(token)) {
+                mark(tech, *scope);
+            }
+        }
+    }
+}
+
+/// 列表型作用域：证据候选中任一模式通过准入门槛的技术（无证据技术由 GateAutomaton 处理）
+fn gated_list_techs<'a, P>(
+    lib: &'a CompiledRuleLibrary,
+    tokens: &FxHashSet<String>,
+    scope: PruneScope,
+    inputs: &[&str],
+    patterns: impl Fn(&'a CompiledTechRule) -> Option<P>,
+) -> Vec<&'a str>
+where
+    P: IntoIterator,
+    P::Item: Borrow<CompiledPattern>,
+{
+    collect_candidate_techs(lib, tokens, scope)
+        .into_iter()
+        .filter_map(|name| lib.tech_patterns.get(name))
+        .filter(|tech| {
+            patterns(tech).is_some_and(|list| {
+                list.into_iter()
+                    .any(|pattern| inputs.iter().any(|input| pattern.borrow().prune_check(input, tokens)))
+            })
+        })
+        .map(|tech| tech.name.as_str())
+        .collect()
+}
+
+/// 键值型作用域：键存在且（存在性模式或任一取值通过准入门槛）的技术
+fn gated_keyed_techs<'a, 'v>(
+    lib: &'a CompiledRuleLibrary,
+    tokens: &FxHashSet<String>,
+    scope: PruneScope,
+    patterns: impl Fn(&'a CompiledTechRule) -> Option<&'a FxHashMap<String, Vec<CompiledPattern>>>,
+    values: impl Fn(&str) -> Option<&'v [String]>,
+) -> Vec<&'a str> {
+    let mut candidates = collect_candidate_techs(lib, tokens, scope);
+    if let Some(no_evidence) = lib.no_evidence_index.get(&scope) {
+        candidates.extend(no_evidence.iter());
+    }
+    candidates
+        .into_iter()
+        .filter_map(|name| lib.tech_patterns.get(name))
+        .filter(|tech| {
+            patterns(tech).is_some_and(|keyed| {
+                keyed.iter().any(|(key, list)| {
+                    values(key).is_some_and(|vals| {
+                        list.iter().any(|pattern| {
+                            pattern.exec.get_matcher().is_exists()
+                                || vals.iter().any(|val| pattern.prune_check(val, tokens))
+                        })
+                    })
+                })
+            })
+        })
+        .map(|tech| tech.name.as_str())
+        .collect()
+}
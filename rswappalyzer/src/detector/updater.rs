@@ -0,0 +1,125 @@
+//! 规则后台自动更新服务（auto-update 特性）
+//! 按 RuleConfig 中的 AutoUpdatePolicy 周期性执行「重新加载 → 重新编译 → 热替换全局检测器」：
+//! 1. 每轮等待 refresh_interval + [0, jitter) 的随机时长，错开多实例的拉取时刻
+//! 2. 重新加载时强制检查远程更新（ETag），且不降级到缓存/内置规则，失败时保留当前检测器
+//! 3. 规则指纹（规则内容 + 编译配置）与上次替换时一致则跳过编译与替换，首轮总是替换
+//! 4. 本地文件规则源优先读取规则缓存，后台更新主要面向远程规则源
+//! 5. 服务句柄被丢弃或调用 stop 时后台任务立即终止；shutdown 等待进行中的更新完成后退出
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+use super::global;
+use crate::config::update::{AutoUpdatePolicy, RuleUpdateEvent};
+use crate::error::{RswResult, RswappalyzerError};
+use crate::RuleConfig;
+
+/// 规则后台自动更新服务句柄
+#[derive(Debug)]
+pub struct DetectionUpdaterService {
+    handle: Option<JoinHandle<()>>,
+    shutdown: Arc<Notify>,
+}
+
+impl DetectionUpdaterService {
+    /// 启动后台更新任务（需在 tokio 运行时内调用）
+    /// 参数：config - 规则配置（需设置 options.auto_update）
+    ///
+    /// 返回：服务句柄 | 未配置自动更新策略/不在 tokio 运行时内
+    pub fn spawn(config: RuleConfig) -> RswResult<Self> {
+        let policy = config.options.auto_update.clone().ok_or_else(|| {
+            RswappalyzerError::InvalidInput("RuleConfig has no auto_update policy".to_string())
+        })?;
+        if policy.refresh_interval.is_zero() {
+            return Err(RswappalyzerError::InvalidInput(
+                "auto_update refresh_interval must be greater than zero".to_string(),
+            ));
+        }
+        let runtime = tokio::runtime::Handle::try_current().map_err(|e| {
+            RswappalyzerError::AsyncTaskError(format!("DetectionUpdaterService requires a tokio runtime: {}", e))
+        })?;
+        let shutdown = Arc::new(Notify::new());
+        let handle = runtime.spawn(run(reload_config(config), policy, shutdown.clone()));
+        Ok(Self {
+            handle: Some(handle),
+            shutdown,
+        })
+    }
+
+    /// 后台任务是否仍在运行
+    pub fn is_running(&self) -> bool {
+        self.handle.as_ref().is_some_and(|handle| !handle.is_finished())
+    }
+
+    /// 停止后台更新（进行中的重新加载随之取消，全局检测器保持不变）
+    pub fn stop(self) {
+        drop(self);
+    }
+
+    /// 优雅停止后台更新：等待进行中的重新加载（含替换与回调）完成后返回
+    pub async fn shutdown(mut self) {
+        self.shutdown.notify_one();
+        if let Some(handle) = self.handle.take() {
+            if let Err(e) = handle.await {
+                log::warn!("Rule auto-update task ended abnormally | Error: {}", e);
+            }
+        }
+    }
+}
+
+impl Drop for DetectionUpdaterService {
+    fn drop(&mut self) {
+        if let Some(handle) = &self.handle {
+            handle.abort();
+        }
+    }
+}
+
+/// 后台更新配置：强制检查远程更新，关闭降级（失败时保留当前检测器而非替换为缓存/内置规则）
+fn reload_config(mut config: RuleConfig) -> RuleConfig {
+    config.options.check_update = true;
+    config.options.fallback_to_cache = false;
+    config.options.fallback_to_embedded = false;
+    config
+}
+
+/// 后台更新循环（等待期间收到停止通知时退出）
+async fn run(config: RuleConfig, policy: AutoUpdatePolicy, shutdown: Arc<Notify>) {
+    let mut fingerprint = None;
+    loop {
+        let delay = policy.refresh_interval + jitter_delay(policy.jitter);
+        if tokio::time::timeout(delay, shutdown.notified()).await.is_ok() {
+            log::debug!("Rule auto-update stopped");
+            return;
+        }
+        let event = match global::reload_rules_if_changed(config.clone(), &mut fingerprint).await {
+            Ok(Some(tech_count)) => RuleUpdateEvent::Reloaded { tech_count },
+            Ok(None) => RuleUpdateEvent::Unchanged,
+            Err(e) => {
+                log::warn!("Rule auto-update failed, keep current rules | Error: {}", e);
+                RuleUpdateEvent::Failed { error: e.to_string() }
+            }
+        };
+        if let Some(callback) = &policy.on_update {
+            callback.emit(&event);
+        }
+    }
+}
+
+/// 随机抖动时长（[0, jitter)，jitter 为 0 时无抖动）
+fn jitter_delay(jitter: Duration) -> Duration {
+    let range = jitter.as_nanos();
+    if range == 0 {
+        return Duration::ZERO;
+    }
+    let mut hasher = RandomState::new().build_hasher();
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    hasher.write_u128(now.as_nanos());
+    let nanos = u128::from(hasher.finish()) % range;
+    Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+}
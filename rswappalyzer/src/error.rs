@@ -15,9 +15,9 @@ use url::ParseError as UrlParseError;
 /// 封装所有业务场景的错误类型，支持From转换和结构化错误信息
 #[derive(Error, Debug)]
 pub enum RswappalyzerError {
-    /// 内核核心错误（透传）
+    /// 内核核心错误（透传，与业务层同名的变体会转换为对应变体，见 From<CoreError>）
     #[error("Core error: {0}")]
-    Core(#[from] CoreError),
+    Core(#[source] CoreError),
 
     // ===================== 基础IO/解析错误 =====================
     /// IO操作失败（文件读写/网络IO等）
@@ -96,6 +96,46 @@ pub enum RswappalyzerError {
     FeatureDisabled(String)
 }
 
+/// 内核错误 → 业务错误
+/// 与业务层同名的变体（规则/检测/输入/JSON）按变体转换，保证调用方可直接按变体匹配；
+/// 其余内核特有变体（正则编译/不变量等）透传为 Core，保留完整错误源链
+impl From<CoreError> for RswappalyzerError {
+    fn from(err: CoreError) -> Self {
+        match err {
+            CoreError::RuleLoadError(msg) => Self::RuleLoadError(msg),
+            CoreError::RuleConvertError(msg) => Self::RuleConvertError(msg),
+            CoreError::RuleCacheError(msg) => Self::RuleCacheError(msg),
+            CoreError::RuleParseError(msg) => Self::RuleParseError(msg),
+            CoreError::JsonError(err) => Self::JsonError(err),
+            CoreError::DetectorNotInitialized(msg) => Self::DetectorNotInitialized(msg),
+            CoreError::DetectorInitError(msg) => Self::DetectorInitError(msg),
+            CoreError::DetectError(msg) => Self::DetectError(msg),
+            CoreError::InvalidInput(msg) => Self::InvalidInput(msg),
+            other => Self::Core(other),
+        }
+    }
+}
+
+/// 业务错误 → 内核错误（自定义匹配器/规则源等内核回调中使用 `?` 传播业务错误）
+/// 同名变体按变体转换，Core 直接解包；无对应内核变体的业务错误（IO/网络等）归入 InternalError
+impl From<RswappalyzerError> for CoreError {
+    fn from(err: RswappalyzerError) -> Self {
+        match err {
+            RswappalyzerError::Core(err) => err,
+            RswappalyzerError::RuleLoadError(msg) => Self::RuleLoadError(msg),
+            RswappalyzerError::RuleConvertError(msg) => Self::RuleConvertError(msg),
+            RswappalyzerError::RuleCacheError(msg) => Self::RuleCacheError(msg),
+            RswappalyzerError::RuleParseError(msg) => Self::RuleParseError(msg),
+            RswappalyzerError::JsonError(err) => Self::JsonError(err),
+            RswappalyzerError::DetectorNotInitialized(msg) => Self::DetectorNotInitialized(msg),
+            RswappalyzerError::DetectorInitError(msg) => Self::DetectorInitError(msg),
+            RswappalyzerError::DetectError(msg) => Self::DetectError(msg),
+            RswappalyzerError::InvalidInput(msg) => Self::InvalidInput(msg),
+            other => Self::InternalError(other.to_string()),
+        }
+    }
+}
+
 /// 全局Result类型别名
 /// 统一使用RswappalyzerError作为错误类型
 pub type RswResult<T> = Result<T, RswappalyzerError>;
\ No newline at end of file
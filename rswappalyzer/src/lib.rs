@@ -6,14 +6,20 @@
 //! 3. 编译期规则压缩，运行期LZ4解压缩（嵌入式规则）
 //! 4. 线程安全的全局单例管理，极致性能优化
 
+#![forbid(unsafe_code)] // 禁止unsafe代码，与引擎层保持一致
+
 // 模块导出（按功能分类，提升可读性）
 pub mod analyzer; // 多维度分析器模块（URL/Header/Cookie/HTML等）
+#[cfg(feature = "compare")]
+pub mod compare; // 检测结果对比模块（与wappalyzergo录制结果对比）
 pub mod config; // 配置模块（规则配置/重试策略/加载源）
 pub mod detector; // 检测器核心模块（全局单例/检测接口）
 pub mod error; // 错误处理模块（统一错误类型/结果类型）
 pub mod result; // 检测结果核心模块
 pub mod rule; // 规则模块（加载/缓存/检测结果）
 pub mod utils; // 通用工具模块（Header转换/版本提取/检测更新）
+#[cfg(test)]
+pub(crate) mod test_support; // 单元测试辅助
 
 // ========== 核心类型导出（简化外部调用） ==========
 
@@ -22,27 +28,83 @@ pub use self::error::{RswResult, RswappalyzerError};
 
 // 配置模块核心结构体与构建器
 pub use crate::config::rule::{
-    CustomConfigBuilder, RetryPolicy, RuleConfig, RuleOptions, RuleOrigin,
+    CustomConfigBuilder, RemoteOptions, RetryPolicy, RuleConfig, RuleOptions, RuleOrigin, StalePolicy,
+};
+pub use crate::config::audit::{IoAccess, IoTouchpoint};
+pub use crate::config::fetch::{
+    FetchMode, FetchProgress, FetchProgressCallback, MergePolicy, RemoteSource, RuleFormat,
 };
+pub use crate::config::proxy::ProxyConfig;
+pub use crate::config::banner::BannerPolicy;
+pub use crate::config::generator::GeneratorPolicy;
+pub use crate::config::implies::ImplyPolicy;
+pub use crate::config::update::{AutoUpdatePolicy, RuleUpdateCallback, RuleUpdateEvent};
+pub use crate::config::script_host::{ScriptHostClass, ScriptHostPolicy};
+
+// 引擎层常用类型（配置项中引用）
+pub use rswappalyzer_engine::scope_pruner::PruneScope;
+pub use rswappalyzer_engine::TechMeta;
+pub use rswappalyzer_engine::{ExpiredRulePolicy, LifecycleReport, LifecycleStatus};
+pub use rswappalyzer_engine::{BrokenPattern, PatternDedupStats, PatternValidation};
+pub use rswappalyzer_engine::{RegexLimitViolation, RegexLimits, RejectedPattern};
+#[cfg(feature = "diagnostics")]
+pub use rswappalyzer_engine::{diagnose_pattern, PatternDiagnostic};
+pub use rswappalyzer_engine::{CorpusEntry, PatternCorpus};
+pub use rswappalyzer_engine::{regex_error_stats, CustomMatcher, MatcherRegistry, RegexErrorStats};
+pub use rswappalyzer_engine::lru::LruStats;
+pub use rswappalyzer_engine::{regex_cache_stats, set_regex_cache_capacity};
 
 // 规则模块核心接口与数据结构
+pub use crate::result::category::{Category, CategoryRef};
 pub use crate::result::detect_result::{DetectResult, Technology};
-pub use crate::rule::{RuleCacheManager, RuleLoader};
+pub use crate::result::evidence::Evidence;
+pub use crate::result::slug::{resolve_slug, slugify};
+pub use crate::result::inferred_stack::{BackendLanguage, InferredLanguage, InferredStack};
+pub use crate::result::site_profile::{ProfileTechnology, SiteProfile};
+pub use crate::result::warning::{DetectWarning, HtmlRejectReason};
+pub use crate::result::wappalyzer::{
+    WappalyzerCategory, WappalyzerOutput, WappalyzerTechnology, WappalyzerUrl,
+};
+pub use crate::rule::{read_compiled_pack, write_compiled_pack, RuleCacheManager, RuleLoader};
+#[cfg(feature = "mmap")]
+pub use crate::rule::{map_compiled_pack, MappedRulePack};
 
 // HTML提取工具核心接口
-pub use crate::utils::extractor::HtmlExtractor;
+pub use crate::utils::extractor::{
+    ExtractLimits, ExtractStats, HtmlExtractor, HtmlParser, SanitizeStats, StreamingExtractor,
+};
 
 // 通用工具模块核心能力
-pub use crate::utils::{DetectionUpdater, HeaderConverter, VersionExtractor};
+pub use crate::utils::{BodyKind, DetectionUpdater, HeaderConverter, PreparedHeaders, VersionExtractor};
 
 // 检测模块核心接口（包含兼容历史调用的简化封装接口）
-pub use crate::detector::{init_global_detector, init_global_detector_with_rules, TechDetector};
+pub use crate::detector::{init_global_detector, init_global_detector_with_rules, reload_rules, reload_with_rules, TechDetector};
+pub use crate::detector::{DetectStage, ProfileCache, ProgressiveDetection, TechnologyEvent};
+pub use crate::detector::{CorpusReport, DetectorCapabilities, DetectorStats, RuleSource, SourceStats};
+pub use crate::detector::{features, FeatureReport};
+pub use crate::detector::{SelfTestCase, SelfTestReport, SelfTestStatus};
+pub use crate::detector::TriageResult;
+pub use crate::detector::{DetectTrace, StageTrace};
+pub use crate::detector::{CategoryFilter, CategoryFilterMode, ConfidenceThreshold, ResultPostProcessor};
+pub use crate::detector::{
+    DetectBuilder, DetectInput, DetectInputBuilder, DetectOptions, EvidenceCapture, InputSnapshot, RedirectHop,
+    ScopeMask, ScopeTokens,
+};
+#[cfg(feature = "pool")]
+pub use crate::detector::{DetectReceiver, DetectorPool, SubmitError};
+#[cfg(feature = "tower")]
+pub use crate::detector::{DetectService, DetectServiceBuilder};
+#[cfg(feature = "scanner")]
+pub use crate::detector::ScanOptions;
+#[cfg(feature = "auto-update")]
+pub use crate::detector::DetectionUpdaterService;
 
 // ========== 嵌入式固化规则库（仅embedded-rules特性开启时编译） ==========
 /// 嵌入式规则库模块（仅启用embedded-rules特性时编译）
 /// 特性：
 /// 1. 编译期压缩：规则库通过build.rs压缩为LZ4格式嵌入二进制
 /// 2. 运行期懒加载：首次访问时解压缩并反序列化，全程线程安全
+///    （默认JSON；binary-rules特性下为MessagePack，冷启动反序列化更快）
 /// 3. 单例管理：Arc封装，内存中仅一份实例，多线程共享
 #[cfg(feature = "embedded-rules")]
 #[allow(clippy::all)]
@@ -73,6 +135,20 @@ pub mod rswappalyzer_rules {
         })
     }
 
+    /// 反序列化嵌入规则库（binary-rules特性：MessagePack，免去JSON文本解析）
+    #[cfg(feature = "binary-rules")]
+    fn decode_compiled_lib(bytes: &[u8]) -> Result<CompiledRuleLibrary, RswappalyzerError> {
+        rmp_serde::from_slice(bytes).map_err(|e| {
+            RswappalyzerError::RuleParseError(format!("Failed to decode MessagePack rule library: {}", e))
+        })
+    }
+
+    /// 反序列化嵌入规则库（JSON）
+    #[cfg(not(feature = "binary-rules"))]
+    fn decode_compiled_lib(bytes: &[u8]) -> Result<CompiledRuleLibrary, RswappalyzerError> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+
     /// 编译期嵌入的压缩规则库
     /// 说明：
     /// - 文件名由build_config.json配置
@@ -101,14 +177,11 @@ pub mod rswappalyzer_rules {
             );
         });
 
-        // 步骤2：JSON反序列化为CompiledRuleLibrary
-        let lib: CompiledRuleLibrary = serde_json::from_slice(&decompressed).unwrap_or_else(|e| {
-            eprintln!(
-                "Fatal error: Failed to deserialize embedded rule library - {:?}",
-                e
-            );
-            eprintln!(
-                "Debug info: Decompressed rule library size: {} bytes",
+        // 步骤2：反序列化为CompiledRuleLibrary（binary-rules特性下为MessagePack，否则为JSON）
+        let mut lib: CompiledRuleLibrary = decode_compiled_lib(&decompressed).unwrap_or_else(|e| {
+            error!(
+                "Failed to deserialize embedded rule library: error = {:?}, decompressed_size = {}",
+                e,
                 decompressed.len()
             );
             panic!(
@@ -118,7 +191,8 @@ pub mod rswappalyzer_rules {
             );
         });
 
-        // 步骤3：封装为Arc单例
+        // 步骤3：构建准入字面量自动机（不随规则库序列化），封装为Arc单例
+        lib.build_literal_prefilters();
         Arc::new(lib)
     });
 }
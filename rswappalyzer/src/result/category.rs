@@ -0,0 +1,314 @@
+//! 标准 Wappalyzer 分类
+//! 提供标准分类ID的类型化常量（CMS=1 …）及按分类筛选检测结果的辅助方法，
+//! 下游策略代码无需再硬编码分类ID/名称
+
+use serde::{Deserialize, Serialize};
+
+use super::detect_result::{DetectResult, Technology};
+
+/// 生成分类枚举及ID/名称映射
+macro_rules! categories {
+    ($($variant:ident = $id:literal => $name:literal,)+) => {
+        /// 标准 Wappalyzer 分类（判别值即分类ID）
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[repr(u32)]
+        pub enum Category {
+            $($variant = $id,)+
+        }
+
+        impl Category {
+            /// 全部标准分类（按ID升序）
+            pub const ALL: &'static [Category] = &[$(Category::$variant,)+];
+
+            /// 分类名称（与规则库分类数据一致）
+            pub const fn name(self) -> &'static str {
+                match self {
+                    $(Category::$variant => $name,)+
+                }
+            }
+
+            /// 根据分类ID查找标准分类
+            pub const fn from_id(id: u32) -> Option<Self> {
+                match id {
+                    $($id => Some(Category::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+categories! {
+    Cms = 1 => "CMS",
+    MessageBoards = 2 => "Message boards",
+    DatabaseManagers = 3 => "Database managers",
+    Documentation = 4 => "Documentation",
+    Widgets = 5 => "Widgets",
+    Ecommerce = 6 => "Ecommerce",
+    PhotoGalleries = 7 => "Photo galleries",
+    Wikis = 8 => "Wikis",
+    HostingPanels = 9 => "Hosting panels",
+    Analytics = 10 => "Analytics",
+    Blogs = 11 => "Blogs",
+    JavaScriptFrameworks = 12 => "JavaScript frameworks",
+    IssueTrackers = 13 => "Issue trackers",
+    VideoPlayers = 14 => "Video players",
+    CommentSystems = 15 => "Comment systems",
+    Security = 16 => "Security",
+    FontScripts = 17 => "Font scripts",
+    WebFrameworks = 18 => "Web frameworks",
+    Miscellaneous = 19 => "Miscellaneous",
+    Editors = 20 => "Editors",
+    Lms = 21 => "LMS",
+    WebServers = 22 => "Web servers",
+    Caching = 23 => "Caching",
+    RichTextEditors = 24 => "Rich text editors",
+    JavaScriptGraphics = 25 => "JavaScript graphics",
+    MobileFrameworks = 26 => "Mobile frameworks",
+    ProgrammingLanguages = 27 => "Programming languages",
+    OperatingSystems = 28 => "Operating systems",
+    SearchEngines = 29 => "Search engines",
+    Webmail = 30 => "Webmail",
+    Cdn = 31 => "CDN",
+    MarketingAutomation = 32 => "Marketing automation",
+    WebServerExtensions = 33 => "Web server extensions",
+    Databases = 34 => "Databases",
+    Maps = 35 => "Maps",
+    Advertising = 36 => "Advertising",
+    NetworkDevices = 37 => "Network devices",
+    MediaServers = 38 => "Media servers",
+    Webcams = 39 => "Webcams",
+    PaymentProcessors = 41 => "Payment processors",
+    TagManagers = 42 => "Tag managers",
+    Ci = 44 => "CI",
+    ControlSystems = 45 => "Control systems",
+    RemoteAccess = 46 => "Remote access",
+    Development = 47 => "Development",
+    NetworkStorage = 48 => "Network storage",
+    FeedReaders = 49 => "Feed readers",
+    Dms = 50 => "DMS",
+    PageBuilders = 51 => "Page builders",
+    LiveChat = 52 => "Live chat",
+    Crm = 53 => "CRM",
+    Seo = 54 => "SEO",
+    Accounting = 55 => "Accounting",
+    Cryptominers = 56 => "Cryptominers",
+    StaticSiteGenerator = 57 => "Static site generator",
+    UserOnboarding = 58 => "User onboarding",
+    JavaScriptLibraries = 59 => "JavaScript libraries",
+    Containers = 60 => "Containers",
+    Paas = 62 => "PaaS",
+    Iaas = 63 => "IaaS",
+    ReverseProxies = 64 => "Reverse proxies",
+    LoadBalancers = 65 => "Load balancers",
+    UiFrameworks = 66 => "UI frameworks",
+    CookieCompliance = 67 => "Cookie compliance",
+    Accessibility = 68 => "Accessibility",
+    Authentication = 69 => "Authentication",
+    SslTlsCertificateAuthorities = 70 => "SSL/TLS certificate authorities",
+    AffiliatePrograms = 71 => "Affiliate programs",
+    AppointmentScheduling = 72 => "Appointment scheduling",
+    Surveys = 73 => "Surveys",
+    AbTesting = 74 => "A/B Testing",
+    Email = 75 => "Email",
+    Personalisation = 76 => "Personalisation",
+    Retargeting = 77 => "Retargeting",
+    Rum = 78 => "RUM",
+    Geolocation = 79 => "Geolocation",
+    WordPressThemes = 80 => "WordPress themes",
+    ShopifyThemes = 81 => "Shopify themes",
+    DrupalThemes = 82 => "Drupal themes",
+    BrowserFingerprinting = 83 => "Browser fingerprinting",
+    LoyaltyRewards = 84 => "Loyalty & rewards",
+    FeatureManagement = 85 => "Feature management",
+    Segmentation = 86 => "Segmentation",
+    WordPressPlugins = 87 => "WordPress plugins",
+    Hosting = 88 => "Hosting",
+    Translation = 89 => "Translation",
+    Reviews = 90 => "Reviews",
+    BuyNowPayLater = 91 => "Buy now pay later",
+    Performance = 92 => "Performance",
+    ReservationsDelivery = 93 => "Reservations & delivery",
+    ReferralMarketing = 94 => "Referral marketing",
+    DigitalAssetManagement = 95 => "Digital asset management",
+    ContentCuration = 96 => "Content curation",
+    CustomerDataPlatform = 97 => "Customer data platform",
+    CartAbandonment = 98 => "Cart abandonment",
+    ShippingCarriers = 99 => "Shipping carriers",
+    ShopifyApps = 100 => "Shopify apps",
+    RecruitmentStaffing = 101 => "Recruitment & staffing",
+    Returns = 102 => "Returns",
+    Livestreaming = 103 => "Livestreaming",
+    TicketBooking = 104 => "Ticket booking",
+    AugmentedReality = 105 => "Augmented reality",
+    CrossBorderEcommerce = 106 => "Cross border ecommerce",
+    Fulfilment = 107 => "Fulfilment",
+    EcommerceFrontends = 108 => "Ecommerce frontends",
+    DomainParking = 109 => "Domain parking",
+    FormBuilders = 110 => "Form builders",
+    FundraisingDonations = 111 => "Fundraising & donations",
+}
+
+impl Category {
+    /// 分类ID
+    #[inline]
+    pub const fn id(self) -> u32 {
+        self as u32
+    }
+
+    /// 根据分类名称查找标准分类（忽略大小写）
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|c| c.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+impl std::fmt::Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 分类查询条件（分类ID / 分类名称 / 标准分类）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CategoryRef<'a> {
+    Id(u32),
+    Name(&'a str),
+}
+
+impl From<u32> for CategoryRef<'_> {
+    fn from(id: u32) -> Self {
+        CategoryRef::Id(id)
+    }
+}
+
+impl<'a> From<&'a str> for CategoryRef<'a> {
+    fn from(name: &'a str) -> Self {
+        CategoryRef::Name(name)
+    }
+}
+
+impl From<Category> for CategoryRef<'_> {
+    fn from(category: Category) -> Self {
+        CategoryRef::Id(category.id())
+    }
+}
+
+impl Technology {
+    /// 是否属于指定分类（分类ID按标准分类表解析为名称，名称忽略大小写比较）
+    /// 参数：category - 分类ID / 分类名称 / Category
+    pub fn matches_category<'a>(&self, category: impl Into<CategoryRef<'a>>) -> bool {
+        let name = match category.into() {
+            CategoryRef::Id(id) => match Category::from_id(id) {
+                Some(c) => c.name(),
+                None => return false,
+            },
+            CategoryRef::Name(name) => name.trim(),
+        };
+        self.categories.iter().any(|c| c.eq_ignore_ascii_case(name))
+    }
+
+    /// 所属的标准分类（忽略非标准分类名称）
+    pub fn standard_categories(&self) -> Vec<Category> {
+        self.categories
+            .iter()
+            .filter_map(|name| Category::from_name(name))
+            .collect()
+    }
+}
+
+impl DetectResult {
+    /// 指定分类下的技术
+    /// 参数：category - 分类ID / 分类名称 / Category
+    pub fn techs_in_category<'a>(&self, category: impl Into<CategoryRef<'a>>) -> Vec<&Technology> {
+        let category = category.into();
+        self.technologies
+            .iter()
+            .filter(|t| t.matches_category(category))
+            .collect()
+    }
+
+    /// 是否检出指定分类的技术
+    pub fn has_category<'a>(&self, category: impl Into<CategoryRef<'a>>) -> bool {
+        let category = category.into();
+        self.technologies.iter().any(|t| t.matches_category(category))
+    }
+
+    /// 是否检出 CMS
+    pub fn has_cms(&self) -> bool {
+        self.has_category(Category::Cms)
+    }
+
+    /// 是否检出电商系统
+    pub fn has_ecommerce(&self) -> bool {
+        self.has_category(Category::Ecommerce)
+    }
+
+    /// 是否检出安全类技术（WAF/防护服务等）
+    pub fn has_security(&self) -> bool {
+        self.has_category(Category::Security)
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::{HeaderMap, HeaderValue};
+
+    use super::*;
+    use crate::test_support::detector;
+
+    fn result() -> DetectResult {
+        let detector = detector(
+            r#"{"technologies": {
+                "WordPress": { "cats": [1, 11], "html": "wp-content" },
+                "Nginx": { "cats": [22], "headers": { "Server": "nginx" } },
+                "Cloudflare": { "cats": [31], "headers": { "CF-RAY": "" } }
+            }}"#,
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert("server", HeaderValue::from_static("nginx"));
+        detector.detect(&headers, &[], b"<link href=\"/wp-content/a.css\">").unwrap()
+    }
+
+    #[test]
+    fn test_category_lookup() {
+        assert_eq!(Category::from_id(1), Some(Category::Cms));
+        assert_eq!(Category::from_id(40), None);
+        assert_eq!(Category::from_name(" web servers "), Some(Category::WebServers));
+        assert_eq!(Category::Cdn.id(), 31);
+        assert_eq!(Category::SslTlsCertificateAuthorities.to_string(), "SSL/TLS certificate authorities");
+        assert!(Category::ALL.windows(2).all(|w| w[0].id() < w[1].id()));
+    }
+
+    #[test]
+    fn test_matches_category() {
+        let result = result();
+        let wordpress = result.technologies.iter().find(|t| t.name == "WordPress").unwrap();
+        assert!(wordpress.matches_category(Category::Cms));
+        assert!(wordpress.matches_category(11));
+        assert!(wordpress.matches_category("blogs"));
+        assert!(!wordpress.matches_category(Category::WebServers));
+        // 非标准分类ID不匹配任何技术
+        assert!(!wordpress.matches_category(9999));
+        assert_eq!(wordpress.standard_categories(), vec![Category::Cms, Category::Blogs]);
+    }
+
+    #[test]
+    fn test_result_predicates() {
+        let result = result();
+        assert!(result.has_cms());
+        assert!(!result.has_ecommerce());
+        assert!(!result.has_security());
+        assert!(!result.has_category(Category::Cdn));
+        let servers: Vec<&str> = result
+            .techs_in_category("Web servers")
+            .into_iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(servers, vec!["Nginx"]);
+        assert!(result.techs_in_category(Category::Ecommerce).is_empty());
+    }
+}
@@ -1,12 +1,20 @@
 //! 技术检测结果结构与工具函数
 
 
+use rswappalyzer_engine::scope_pruner::PruneScope;
 use serde::{Deserialize, Serialize};
 
+use super::evidence::Evidence;
+use super::slug::slugify;
+use super::warning::DetectWarning;
+
 /// 检测结果
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DetectResult {
     pub technologies: Vec<Technology>,
+    // 检测告警（输入降级原因，无告警时不序列化）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<DetectWarning>,
     // 推导技术列表
     // #[serde(default, skip_serializing_if = "Vec::is_empty")]
     // pub imples: Vec<String>,
@@ -26,12 +34,25 @@ impl DetectResult {
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(self)
     }
+
+    /// 按稳定 slug 查找技术
+    pub fn find_by_slug(&self, slug: &str) -> Option<&Technology> {
+        self.technologies.iter().find(|t| t.slug == slug)
+    }
+
+    /// 是否存在告警（输入被截断/拒绝/降级解码等）
+    pub fn is_degraded(&self) -> bool {
+        !self.warnings.is_empty()
+    }
 }
 
 /// 技术结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Technology {
     pub name: String,
+    // 稳定标识（Wappalyzer 同款算法生成，可经别名表固定），上游重命名时下游可按 slug 关联
+    #[serde(default)]
+    pub slug: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -40,44 +61,60 @@ pub struct Technology {
     // 推导技术列表，序列化自动跳过空值
     #[serde(skip_serializing_if = "Option::is_none")]
     pub implied_by: Option<Vec<String>>, 
+    // 命中的脚本主机（仅启用 Script 主机策略时记录）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_hosts: Option<Vec<String>>,
+    // 命中的作用域（按 PruneScope::ALL 顺序；仅由关联推导得到的技术为空）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matched_scopes: Vec<PruneScope>,
+    // 所属规则源（多规则源合并时记录，如 official / internal）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_source: Option<String>,
+    // 检测证据（仅开启证据采集且本次检测被采样时记录）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evidence: Vec<Evidence>,
 
-    // 其他可选字段
-    #[cfg(feature = "full-meta")]
+    // 展示元信息（仅 full-meta 特性开启时填充；字段始终存在，特性开关不改变结构体形态）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub website: Option<String>,
-    #[cfg(feature = "full-meta")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
-    #[cfg(feature = "full-meta")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
-    #[cfg(feature = "full-meta")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub saas: Option<bool>,
-    #[cfg(feature = "full-meta")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub pricing: Option<Vec<String>>,
-    #[cfg(feature = "full-meta")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cpe: Option<String>,
 }
 
 impl Technology {
     pub fn from_name(name: String) -> Self {
         Self {
+            slug: slugify(&name),
             name,
             confidence: 50,
             version: None,
             categories: Vec::new(),
             implied_by: None,
-            #[cfg(feature = "full-meta")]
+            script_hosts: None,
+            matched_scopes: Vec::new(),
+            rule_source: None,
+            evidence: Vec::new(),
             website: None,
-            #[cfg(feature = "full-meta")]
             description: None,
-            #[cfg(feature = "full-meta")]
             icon: None,
-            #[cfg(feature = "full-meta")]
             cpe: None,
-            #[cfg(feature = "full-meta")]
             saas: None,
-            #[cfg(feature = "full-meta")]
             pricing: None,
         }
     }
+
+    /// 是否在指定作用域命中（如仅保留 Header 佐证的结果）
+    pub fn matched_in(&self, scope: PruneScope) -> bool {
+        self.matched_scopes.contains(&scope)
+    }
 }
 
 impl std::fmt::Display for Technology {
@@ -0,0 +1,27 @@
+//! 检测证据
+//! 开启证据采集（DetectOptions::evidence）后，为检出技术记录命中的规则与输入片段，用于审计与误报排查；
+//! 证据仅在采样命中时采集，片段长度与单个结果的总字节数均受预算约束
+
+use rswappalyzer_engine::scope_pruner::PruneScope;
+use serde::{Deserialize, Serialize};
+
+/// 单条检测证据
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Evidence {
+    /// 命中的作用域
+    pub scope: PruneScope,
+    /// 命中的键（Header/Cookie/Meta 名称、DOM 规则键，其余作用域为 None）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// 命中的规则描述
+    pub rule: String,
+    /// 命中的输入片段（空白折叠并按 max_snippet_len 截断）
+    pub matched: String,
+}
+
+impl Evidence {
+    /// 证据占用的字节数（计入单个结果的证据预算）
+    pub fn size(&self) -> usize {
+        self.key.as_deref().map_or(0, str::len) + self.rule.len() + self.matched.len()
+    }
+}
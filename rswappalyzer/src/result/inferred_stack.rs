@@ -0,0 +1,221 @@
+//! 后端语言/运行时推断
+//! 基于检测结果中的编程语言类技术（含 implies 推导得到的技术）汇总后端技术栈，
+//! 供资产盘点场景直接获取"该站点跑的是 PHP / Java / .NET …"的一行结论
+
+use serde::{Deserialize, Serialize};
+
+use super::detect_result::{DetectResult, Technology};
+
+/// 后端语言/运行时
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum BackendLanguage {
+    Php,
+    Java,
+    DotNet,
+    Node,
+    Python,
+    Ruby,
+}
+
+impl BackendLanguage {
+    /// 全部语言（固定顺序）
+    pub const ALL: [BackendLanguage; 6] = [
+        BackendLanguage::Php,
+        BackendLanguage::Java,
+        BackendLanguage::DotNet,
+        BackendLanguage::Node,
+        BackendLanguage::Python,
+        BackendLanguage::Ruby,
+    ];
+
+    /// 展示名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackendLanguage::Php => "PHP",
+            BackendLanguage::Java => "Java",
+            BackendLanguage::DotNet => ".NET",
+            BackendLanguage::Node => "Node.js",
+            BackendLanguage::Python => "Python",
+            BackendLanguage::Ruby => "Ruby",
+        }
+    }
+
+    /// 锚点技术：规则库中代表该语言/运行时的技术名
+    /// 框架/服务器通过 implies 推导到锚点技术（如 Laravel -> PHP、Kestrel -> Microsoft ASP.NET）
+    fn anchors(&self) -> &'static [&'static str] {
+        match self {
+            BackendLanguage::Php => &["PHP", "KPHP"],
+            BackendLanguage::Java => &["Java", "Kotlin", "Scala"],
+            BackendLanguage::DotNet => &["Microsoft ASP.NET"],
+            BackendLanguage::Node => &["Node.js"],
+            BackendLanguage::Python => &["Python"],
+            BackendLanguage::Ruby => &["Ruby"],
+        }
+    }
+
+    /// 根据技术名匹配语言
+    fn from_tech(tech: &Technology) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|lang| lang.anchors().iter().any(|a| a.eq_ignore_ascii_case(&tech.name)))
+    }
+}
+
+impl std::fmt::Display for BackendLanguage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 单个语言的推断结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InferredLanguage {
+    pub language: BackendLanguage,
+    /// 推断置信度（取所有证据中的最高值）
+    pub confidence: u8,
+    /// 推断依据：直接检出时为锚点技术本身，推导得出时为推导来源技术
+    pub evidence: Vec<String>,
+    /// 是否直接检出（非仅由 implies 推导）
+    pub direct: bool,
+}
+
+/// 后端技术栈推断汇总
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InferredStack {
+    /// 推断出的语言（按置信度降序）
+    pub languages: Vec<InferredLanguage>,
+}
+
+impl InferredStack {
+    /// 置信度最高的语言
+    pub fn primary(&self) -> Option<&InferredLanguage> {
+        self.languages.first()
+    }
+
+    /// 是否未推断出任何语言
+    pub fn is_empty(&self) -> bool {
+        self.languages.is_empty()
+    }
+}
+
+impl std::fmt::Display for InferredStack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self
+            .languages
+            .iter()
+            .map(|l| format!("{} ({}%)", l.language, l.confidence))
+            .collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+impl DetectResult {
+    /// 推断后端语言/运行时（PHP/Java/.NET/Node/Python/Ruby）
+    /// 依据编程语言锚点技术及其 implies 推导来源聚合，置信度取证据最高值
+    pub fn inferred_stack(&self) -> InferredStack {
+        let mut languages: Vec<InferredLanguage> = Vec::new();
+
+        for tech in &self.technologies {
+            let Some(language) = BackendLanguage::from_tech(tech) else {
+                continue;
+            };
+            let (evidence, direct) = match &tech.implied_by {
+                Some(sources) if !sources.is_empty() => (sources.clone(), false),
+                _ => (vec![tech.name.clone()], true),
+            };
+
+            match languages.iter_mut().find(|l| l.language == language) {
+                Some(entry) => {
+                    entry.confidence = entry.confidence.max(tech.confidence);
+                    entry.direct |= direct;
+                    for name in evidence {
+                        if !entry.evidence.contains(&name) {
+                            entry.evidence.push(name);
+                        }
+                    }
+                }
+                None => languages.push(InferredLanguage {
+                    language,
+                    confidence: tech.confidence,
+                    evidence,
+                    direct,
+                }),
+            }
+        }
+
+        for entry in &mut languages {
+            entry.evidence.sort();
+        }
+        languages.sort_by(|a, b| {
+            b.confidence
+                .cmp(&a.confidence)
+                .then_with(|| b.direct.cmp(&a.direct))
+                .then_with(|| a.language.cmp(&b.language))
+        });
+
+        InferredStack { languages }
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::{HeaderMap, HeaderValue};
+
+    use super::*;
+    use crate::test_support::detector;
+
+    const RULES: &str = r#"{
+        "technologies": {
+            "PHP": { "cats": [27], "headers": { "X-Powered-By": "^php/?([\\d.]+)?\\;version:\\1" } },
+            "Laravel": { "cats": [18], "implies": "PHP", "cookies": { "laravel_session": "" } },
+            "Express": { "cats": [18], "implies": "Node.js", "headers": { "X-Powered-By": "^Express$" } },
+            "Node.js": { "cats": [27], "headers": { "X-Node-Version": "" } },
+            "Nginx": { "cats": [22], "headers": { "Server": "nginx" } }
+        }
+    }"#;
+
+    fn stack(headers: &[(&'static str, &'static str)]) -> InferredStack {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.append(*name, HeaderValue::from_static(value));
+        }
+        detector(RULES).detect(&map, &[], b"").unwrap().inferred_stack()
+    }
+
+    #[test]
+    fn test_direct_and_implied() {
+        let stack = stack(&[
+            ("x-powered-by", "PHP/8.2.1"),
+            ("set-cookie", "laravel_session=abc"),
+            ("server", "nginx"),
+        ]);
+        assert_eq!(stack.languages.len(), 1);
+        let php = stack.primary().unwrap();
+        assert_eq!(php.language, BackendLanguage::Php);
+        assert!(php.direct);
+        assert_eq!(php.confidence, 100);
+        assert_eq!(stack.to_string(), "PHP (100%)");
+    }
+
+    #[test]
+    fn test_implied_only() {
+        let stack = stack(&[("x-powered-by", "Express"), ("set-cookie", "laravel_session=abc")]);
+        let languages: Vec<_> = stack.languages.iter().map(|l| l.language).collect();
+        // 置信度相同时按语言固定顺序
+        assert_eq!(languages, vec![BackendLanguage::Php, BackendLanguage::Node]);
+        let node = &stack.languages[1];
+        assert!(!node.direct);
+        assert_eq!(node.evidence, vec!["Express".to_string()]);
+        assert_eq!(node.confidence, stack.languages[0].confidence);
+        assert_eq!(stack.languages[0].evidence, vec!["Laravel".to_string()]);
+    }
+
+    #[test]
+    fn test_no_language() {
+        let stack = stack(&[("server", "nginx")]);
+        assert!(stack.is_empty());
+        assert!(stack.primary().is_none());
+        assert_eq!(stack.to_string(), "");
+    }
+}
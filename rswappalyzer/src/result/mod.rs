@@ -1 +1,8 @@
-pub mod detect_result;
\ No newline at end of file
+pub mod category;
+pub mod detect_result;
+pub mod evidence;
+pub mod inferred_stack;
+pub mod site_profile;
+pub mod slug;
+pub mod warning;
+pub mod wappalyzer;
@@ -0,0 +1,108 @@
+//! 站点级技术画像
+//! 同一站点的多次检测（首页、子页面、API 响应、不同时间的抓取）各自只看到部分技术，
+//! 按主机聚合为一份画像：技术取并集，置信度取最大值，版本取最近一次检出的非空版本
+
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use super::detect_result::DetectResult;
+
+/// 画像中的单项技术
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileTechnology {
+    pub name: String,
+    #[serde(default)]
+    pub slug: String,
+    // 最近一次检出的非空版本
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    // 历次检出的最大置信度
+    pub confidence: u8,
+    // 检出次数
+    pub hits: u64,
+    // 最近一次检出时间
+    pub last_seen: SystemTime,
+}
+
+/// 站点技术画像
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SiteProfile {
+    pub host: String,
+    // 技术列表（按名称排序）
+    pub technologies: Vec<ProfileTechnology>,
+    // 聚合的检测结果数
+    pub observations: u64,
+    pub first_seen: SystemTime,
+    pub last_seen: SystemTime,
+}
+
+impl SiteProfile {
+    /// 创建空画像
+    /// 参数：host - 主机名
+    pub fn new(host: impl Into<String>) -> Self {
+        let now = SystemTime::now();
+        Self {
+            host: host.into(),
+            technologies: Vec::new(),
+            observations: 0,
+            first_seen: now,
+            last_seen: now,
+        }
+    }
+
+    /// 并入一次检测结果
+    /// 参数：result - 该主机的检测结果
+    pub fn record(&mut self, result: &DetectResult) {
+        let now = SystemTime::now();
+        self.observations += 1;
+        self.last_seen = now;
+        for tech in &result.technologies {
+            let index = match self.technologies.binary_search_by(|t| t.name.as_str().cmp(&tech.name)) {
+                Ok(index) => index,
+                Err(index) => {
+                    self.technologies.insert(
+                        index,
+                        ProfileTechnology {
+                            name: tech.name.clone(),
+                            slug: tech.slug.clone(),
+                            version: None,
+                            categories: Vec::new(),
+                            confidence: 0,
+                            hits: 0,
+                            last_seen: now,
+                        },
+                    );
+                    index
+                }
+            };
+            let entry = &mut self.technologies[index];
+            entry.hits += 1;
+            entry.last_seen = now;
+            entry.confidence = entry.confidence.max(tech.confidence);
+            if tech.version.is_some() {
+                entry.version = tech.version.clone();
+            }
+            for category in &tech.categories {
+                if !entry.categories.contains(category) {
+                    entry.categories.push(category.clone());
+                }
+            }
+        }
+    }
+
+    /// 按名称查找技术
+    pub fn get(&self, name: &str) -> Option<&ProfileTechnology> {
+        self.technologies
+            .binary_search_by(|t| t.name.as_str().cmp(name))
+            .ok()
+            .map(|index| &self.technologies[index])
+    }
+
+    /// 是否包含指定技术
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+}
@@ -0,0 +1,83 @@
+//! 技术/分类 slug 生成（与 Wappalyzer 算法一致）
+//! slug 作为技术的稳定标识：上游规则重命名技术时，可通过别名表将新名称固定到原 slug，
+//! 下游数据库按 slug 关联不受影响
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+/// 生成 slug：转小写 → 非 [a-z0-9-] 字符替换为 `-` → 合并连续 `-` → 去除首尾 `-`
+/// 参数：name - 技术名/分类名
+/// 返回：slug（如 "Apache HTTP Server" -> "apache-http-server"）
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.chars().flat_map(char::to_lowercase) {
+        let c = if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' {
+            c
+        } else {
+            '-'
+        };
+        if c == '-' && slug.ends_with('-') {
+            continue;
+        }
+        slug.push(c);
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// 解析技术的稳定 slug（别名表优先，否则按名称生成）
+/// 参数：
+/// - name: 技术名
+/// - aliases: 别名表（技术名 -> 固定 slug）
+pub fn resolve_slug<S: BuildHasher>(name: &str, aliases: &HashMap<String, String, S>) -> String {
+    aliases
+        .get(name)
+        .cloned()
+        .unwrap_or_else(|| slugify(name))
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::test_support::detector_with;
+    use crate::RuleConfig;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Node.js"), "node-js");
+        assert_eq!(slugify("Apache HTTP Server"), "apache-http-server");
+        assert_eq!(slugify("  C++ / .NET  "), "c-net");
+        assert_eq!(slugify("--Already-Slugged--"), "already-slugged");
+        // 非 ASCII 字符与 Wappalyzer 一致按分隔符处理
+        assert_eq!(slugify("Café Über"), "caf-ber");
+        assert_eq!(slugify("..."), "");
+    }
+
+    #[test]
+    fn test_resolve_slug() {
+        let mut aliases = HashMap::new();
+        aliases.insert("Acme CMS".to_string(), "acme".to_string());
+        assert_eq!(resolve_slug("Acme CMS", &aliases), "acme");
+        // 别名表按技术名精确匹配
+        assert_eq!(resolve_slug("acme cms", &aliases), "acme-cms");
+        assert_eq!(resolve_slug("Node.js", &aliases), "node-js");
+    }
+
+    #[test]
+    fn test_detected_slug_alias() {
+        let rules = r#"{"technologies": {
+            "Acme CMS": { "html": "acme-cms" },
+            "Node.js": { "html": "node-js" }
+        }}"#;
+        let mut config = RuleConfig::default();
+        config.options.slug_aliases.insert("Acme CMS".to_string(), "acme".to_string());
+        let result = detector_with(rules, config)
+            .detect(&HeaderMap::new(), &[], b"<p>acme-cms node-js</p>")
+            .unwrap();
+        assert_eq!(result.find_by_slug("acme").unwrap().name, "Acme CMS");
+        assert!(result.find_by_slug("acme-cms").is_none());
+        assert_eq!(result.find_by_slug("node-js").unwrap().name, "Node.js");
+    }
+}
@@ -0,0 +1,170 @@
+//! Wappalyzer CLI 兼容输出
+//! 生成与官方 Wappalyzer CLI 一致的 JSON 结构（urls → technologies，分类为 id/slug/name 对象），
+//! 可直接替换接入消费该格式的现有看板/工具链
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::category::Category;
+use super::detect_result::{DetectResult, Technology};
+use super::slug::slugify;
+
+/// Wappalyzer 默认图标（技术无图标时使用）
+pub const WAPPALYZER_DEFAULT_ICON: &str = "default.svg";
+
+/// Wappalyzer CLI 输出
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WappalyzerOutput {
+    /// 已分析的URL → 响应状态
+    pub urls: BTreeMap<String, WappalyzerUrl>,
+    /// 检测到的技术
+    pub technologies: Vec<WappalyzerTechnology>,
+}
+
+/// 单个URL的分析状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WappalyzerUrl {
+    /// HTTP 响应状态码
+    pub status: u16,
+}
+
+/// Wappalyzer 格式的技术
+/// 缺失的字段按官方格式输出为 null
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WappalyzerTechnology {
+    pub slug: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub confidence: u8,
+    pub version: Option<String>,
+    pub icon: String,
+    pub website: Option<String>,
+    pub cpe: Option<String>,
+    pub categories: Vec<WappalyzerCategory>,
+}
+
+/// Wappalyzer 格式的分类
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WappalyzerCategory {
+    /// 分类ID（非标准分类为0）
+    pub id: u32,
+    pub slug: String,
+    pub name: String,
+}
+
+impl From<&Technology> for WappalyzerTechnology {
+    fn from(tech: &Technology) -> Self {
+        let categories = tech
+            .categories
+            .iter()
+            .map(|name| WappalyzerCategory {
+                id: Category::from_name(name).map_or(0, Category::id),
+                slug: slugify(name),
+                name: name.clone(),
+            })
+            .collect();
+
+        Self {
+            slug: if tech.slug.is_empty() {
+                slugify(&tech.name)
+            } else {
+                tech.slug.clone()
+            },
+            name: tech.name.clone(),
+            description: tech.description.clone(),
+            confidence: tech.confidence,
+            version: tech.version.clone().filter(|v| !v.is_empty()),
+            icon: tech.icon.clone().unwrap_or_else(|| WAPPALYZER_DEFAULT_ICON.to_string()),
+            website: tech.website.clone(),
+            cpe: tech.cpe.clone(),
+            categories,
+        }
+    }
+}
+
+impl DetectResult {
+    /// 转换为 Wappalyzer CLI 兼容结构
+    /// 参数：
+    /// - url: 检测的URL
+    /// - status: HTTP 响应状态码
+    pub fn to_wappalyzer(&self, url: &str, status: u16) -> WappalyzerOutput {
+        let mut urls = BTreeMap::new();
+        urls.insert(url.to_string(), WappalyzerUrl { status });
+        WappalyzerOutput {
+            urls,
+            technologies: self.technologies.iter().map(WappalyzerTechnology::from).collect(),
+        }
+    }
+
+    /// 输出 Wappalyzer CLI 兼容 JSON
+    /// 参数：
+    /// - url: 检测的URL
+    /// - status: HTTP 响应状态码
+    pub fn to_wappalyzer_json(&self, url: &str, status: u16) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self.to_wappalyzer(url, status))
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn result() -> DetectResult {
+        serde_json::from_value(json!({
+            "technologies": [
+                {
+                    "name": "Node.js",
+                    "version": "18.1",
+                    "categories": ["Programming languages", "Internal tooling"],
+                    "confidence": 100,
+                    "website": "https://nodejs.org",
+                    "icon": "node.js.svg",
+                    "cpe": "cpe:2.3:a:nodejs:node.js:*:*:*:*:*:*:*:*"
+                },
+                { "name": "Acme", "slug": "acme-legacy", "version": "", "categories": [], "confidence": 50 }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_json_shape() {
+        let json = result().to_wappalyzer_json("https://a.com", 200).unwrap();
+        let json: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["urls"], json!({ "https://a.com": { "status": 200 } }));
+        assert_eq!(
+            json["technologies"][0],
+            json!({
+                "slug": "node-js",
+                "name": "Node.js",
+                "description": null,
+                "confidence": 100,
+                "version": "18.1",
+                "icon": "node.js.svg",
+                "website": "https://nodejs.org",
+                "cpe": "cpe:2.3:a:nodejs:node.js:*:*:*:*:*:*:*:*",
+                "categories": [
+                    { "id": 27, "slug": "programming-languages", "name": "Programming languages" },
+                    { "id": 0, "slug": "internal-tooling", "name": "Internal tooling" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        let output = result().to_wappalyzer("https://a.com", 404);
+        let acme = &output.technologies[1];
+        // 已有 slug 原样保留，空版本与缺失字段输出为 null，无图标时使用默认图标
+        assert_eq!(acme.slug, "acme-legacy");
+        assert_eq!(acme.version, None);
+        assert_eq!(acme.icon, WAPPALYZER_DEFAULT_ICON);
+        assert!(acme.description.is_none() && acme.website.is_none() && acme.cpe.is_none());
+        assert!(acme.categories.is_empty());
+        assert_eq!(output.urls["https://a.com"], WappalyzerUrl { status: 404 });
+    }
+}
@@ -0,0 +1,35 @@
+//! 检测告警（软错误）
+//! 输入存在瑕疵但检测仍可继续时不返回错误，而是在 DetectResult.warnings 中记录降级原因，
+//! 下游可按告警类型告警/统计，无需解析日志文本。
+//! 序列化格式稳定：`{"kind": "<snake_case 类型>", "detail": <详情>}`，新增类型只会追加，不会修改已有类型的名称与详情结构
+
+use serde::{Deserialize, Serialize};
+
+/// HTML 被输入守卫拒绝的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HtmlRejectReason {
+    /// 解码后为空
+    Empty,
+    /// 去除首尾空白后短于最小有效长度
+    TooShort,
+}
+
+/// 检测告警
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "detail", rename_all = "snake_case")]
+pub enum DetectWarning {
+    /// 非法的 Header 名称/取值（该 Header 被跳过），详情为 Header 名称
+    InvalidHeaderName(String),
+    /// 响应体解码降级（非法 UTF-8 按容错解码 / 解压失败按原始字节分析），详情为降级原因
+    BodyDecodeFallback(String),
+    /// HTML 被输入守卫拒绝（跳过 HTML/Script/Meta 分析）
+    HtmlRejected(HtmlRejectReason),
+    /// 正则编译超出体积上限（该模式按不匹配处理），详情为受影响的模式数量
+    RegexBudgetExceeded(u64),
+    /// 输入因上限被截断，详情为触达的上限名称
+    /// （html_len / decompressed_len / scripts / meta / comments / attributes / tags / depth）
+    Truncated(String),
+    /// 证据超出单个结果的字节预算（超出部分被丢弃），详情为丢弃的证据条数
+    EvidenceTruncated(u64),
+}
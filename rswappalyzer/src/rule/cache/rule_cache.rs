@@ -1,11 +1,24 @@
 use rswappalyzer_engine::core::CachedTechRule;
-use rswappalyzer_engine::{MatchRuleSet, ParsedTechRule, RuleLibrary};
+use rswappalyzer_engine::{
+    today_utc, CompiledRuleLibrary, ExpiredRulePolicy, MatchRuleSet, ParsedTechRule, RuleLibrary,
+};
 use rustc_hash::FxHashMap;
 
 use crate::{RswappalyzerError, RuleConfig, RuleOrigin};
 use crate::error::RswResult;
+use crate::rule::pack::{decode_pack, encode_pack};
+use crate::utils::favicon::murmur3_32;
 use std::fs;
+use std::io::ErrorKind;
 use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// 已编译规则库缓存魔数（含缓存格式版本）
+/// 文件布局：魔数 + 规则指纹（u64 LE）+ 规则包校验和（u64 LE）+ 规则包字节（见 `rule::pack`）
+pub const COMPILED_CACHE_MAGIC: &[u8; 8] = b"RSWCLIB1";
+
+/// 已编译规则库缓存头部长度（魔数 + 指纹 + 校验和）
+const COMPILED_HEADER_LEN: usize = 24;
 
 /// 规则缓存管理器
 pub struct RuleCacheManager;
@@ -29,7 +42,7 @@ impl RuleCacheManager {
         })?;
         
         let cached_rules: Vec<CachedTechRule> = serde_json::from_slice(&cache_data).map_err(|e| {
-            RswappalyzerError::JsonError(e.into())
+            RswappalyzerError::JsonError(e)
         })?;
         
         Self::convert_cached_rules(cached_rules)
@@ -63,9 +76,123 @@ impl RuleCacheManager {
         Ok(())
     }
 
+    /// 缓存文件年龄（按文件修改时间计算）
+    /// 返回：缓存年龄 | None（缓存不存在或无法读取修改时间）
+    pub fn cache_age(config: &RuleConfig) -> Option<Duration> {
+        let modified = fs::metadata(config.get_cache_file_path()).ok()?.modified().ok()?;
+        Some(SystemTime::now().duration_since(modified).unwrap_or_default())
+    }
+
+    /// 缓存是否已过期（未设置 cache_ttl 或缓存不存在时视为未过期）
+    pub fn is_stale(config: &RuleConfig) -> bool {
+        match (config.options.cache_ttl, Self::cache_age(config)) {
+            (Some(ttl), Some(age)) => age > ttl,
+            _ => false,
+        }
+    }
+
+    /// 刷新缓存修改时间（远程规则未变化时视为重新验证，重新计算有效期）
+    pub fn touch(config: &RuleConfig) -> RswResult<()> {
+        let file = fs::File::options().write(true).open(config.get_cache_file_path())?;
+        file.set_modified(SystemTime::now())?;
+        Ok(())
+    }
+
+    /// 已编译规则库缓存路径（与规则缓存同目录，扩展名 .compiled）
+    pub fn compiled_cache_path(config: &RuleConfig) -> PathBuf {
+        config.get_cache_file_path().with_extension("compiled")
+    }
+
+    /// 计算规则指纹（规则内容 + 影响编译结果的配置）
+    /// 规则按技术名排序后转换为键有序的 JSON，与缓存文件写入顺序无关；
+    /// 编译选项、分类映射以及剔除过期规则时的当前日期一并计入
+    /// 参数：
+    /// - config: 规则配置
+    /// - rule_lib: 规则库
+    ///
+    /// 返回：规则指纹 | 序列化错误
+    pub fn rules_fingerprint(config: &RuleConfig, rule_lib: &RuleLibrary) -> RswResult<u64> {
+        let mut techs: Vec<(&String, &ParsedTechRule)> = rule_lib.core_tech_map.iter().collect();
+        techs.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        let mut data = Vec::new();
+        for (tech_name, parsed) in techs {
+            let cached = Self::to_cached_rule(rule_lib, tech_name, parsed);
+            serde_json::to_writer(&mut data, &serde_json::to_value(&cached)?)?;
+        }
+
+        let options = &config.options;
+        let mut categories: Vec<(u32, String)> = options.category_map().into_iter().collect();
+        categories.sort_unstable();
+        data.extend(format!("{:?}{:?}", options.compile_options(), categories).as_bytes());
+        if options.expired_rules == ExpiredRulePolicy::Skip {
+            data.extend(today_utc().as_bytes());
+        }
+        Ok(checksum(&data))
+    }
+
+    /// 持久化已编译规则库（先写临时文件再原子重命名）
+    /// 参数：
+    /// - config: 规则配置
+    /// - fingerprint: 规则指纹（rules_fingerprint）
+    /// - compiled_lib: 已编译规则库
+    ///
+    /// 返回：成功 | 错误
+    pub fn save_compiled(config: &RuleConfig, fingerprint: u64, compiled_lib: &CompiledRuleLibrary) -> RswResult<()> {
+        let path = Self::compiled_cache_path(config);
+        if let Some(parent_dir) = path.parent() {
+            fs::create_dir_all(parent_dir)?;
+        }
+        let body = encode_pack(compiled_lib)?;
+        let mut data = Vec::with_capacity(COMPILED_HEADER_LEN + body.len());
+        data.extend_from_slice(COMPILED_CACHE_MAGIC);
+        data.extend_from_slice(&fingerprint.to_le_bytes());
+        data.extend_from_slice(&checksum(&body).to_le_bytes());
+        data.extend_from_slice(&body);
+
+        let tmp_path = path.with_extension("compiled.tmp");
+        fs::write(&tmp_path, &data)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    /// 加载已编译规则库（规则指纹一致时）
+    /// 参数：
+    /// - config: 规则配置
+    /// - fingerprint: 当前规则指纹（rules_fingerprint）
+    ///
+    /// 返回：已编译规则库 | None（缓存不存在或规则/配置已变化） | 错误（格式版本不符/校验和不一致/引擎不兼容）
+    pub fn load_compiled(config: &RuleConfig, fingerprint: u64) -> RswResult<Option<CompiledRuleLibrary>> {
+        let path = Self::compiled_cache_path(config);
+        let data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        if data.len() < COMPILED_HEADER_LEN || !data.starts_with(COMPILED_CACHE_MAGIC) {
+            return Err(RswappalyzerError::RuleParseError(format!(
+                "{} is not a compiled rule cache of this version",
+                path.display()
+            )));
+        }
+        let stored_fingerprint = u64::from_le_bytes(data[8..16].try_into().unwrap_or_default());
+        if stored_fingerprint != fingerprint {
+            return Ok(None);
+        }
+        let stored_checksum = u64::from_le_bytes(data[16..24].try_into().unwrap_or_default());
+        let body = &data[COMPILED_HEADER_LEN..];
+        if checksum(body) != stored_checksum {
+            return Err(RswappalyzerError::RuleParseError(format!(
+                "{} checksum mismatch",
+                path.display()
+            )));
+        }
+        decode_pack(body).map(Some)
+    }
+
     // 公共逻辑：缓存规则转换
     fn convert_cached_rules(cached_rules: Vec<CachedTechRule>) -> RswResult<RuleLibrary> {
         let mut core_tech_map = FxHashMap::default();
+        let mut meta_table = FxHashMap::default();
         for cached in cached_rules {
             let tech_name = cached.basic.tech_name.clone().ok_or_else(|| {
                 RswappalyzerError::InvalidInput("缓存规则缺失 tech_name 字段".to_string())
@@ -74,21 +201,67 @@ impl RuleCacheManager {
             for (scope, cached_scope_rule) in cached.rules {
                 match_rules.insert(scope.clone(), MatchRuleSet::from_cached(&scope, cached_scope_rule));
             }
+            if let Some(meta) = cached.meta {
+                meta_table.insert(tech_name.clone(), meta);
+            }
             core_tech_map.insert(tech_name, ParsedTechRule { basic: cached.basic, match_rules });
         }
-        Ok(RuleLibrary { core_tech_map, category_rules: FxHashMap::default() })
+        Ok(RuleLibrary { core_tech_map, category_rules: FxHashMap::default(), meta_table })
     }
 
     // 公共逻辑：构建缓存规则
     fn build_cached_rules(rule_lib: &RuleLibrary) -> RswResult<Vec<u8>> {
-        let mut cached_rules = Vec::with_capacity(rule_lib.core_tech_map.len());
-        for (_, parsed) in &rule_lib.core_tech_map {
-            let mut rules = FxHashMap::default();
-            for (scope, rule_set) in &parsed.match_rules {
-                rules.insert(scope.clone(), rule_set.to_cached(scope));
-            }
-            cached_rules.push(CachedTechRule { basic: parsed.basic.clone(), rules });
+        let cached_rules: Vec<CachedTechRule> = rule_lib
+            .core_tech_map
+            .iter()
+            .map(|(tech_name, parsed)| Self::to_cached_rule(rule_lib, tech_name, parsed))
+            .collect();
+        serde_json::to_vec(&cached_rules).map_err(RswappalyzerError::JsonError)
+    }
+
+    // 公共逻辑：单个技术转换为缓存规则
+    fn to_cached_rule(rule_lib: &RuleLibrary, tech_name: &str, parsed: &ParsedTechRule) -> CachedTechRule {
+        CachedTechRule {
+            basic: parsed.basic.clone(),
+            rules: parsed
+                .match_rules
+                .iter()
+                .map(|(scope, rule_set)| (scope.clone(), rule_set.to_cached(scope)))
+                .collect(),
+            meta: rule_lib.meta_table.get(tech_name).cloned(),
         }
-        serde_json::to_vec(&cached_rules).map_err(|e| RswappalyzerError::JsonError(e.into()))
     }
-}
\ No newline at end of file
+}
+
+/// 64 位校验和（两个种子的 murmur3_32 拼接）
+fn checksum(data: &[u8]) -> u64 {
+    (u64::from(murmur3_32(data, 0)) << 32) | u64::from(murmur3_32(data, 0x9747_b28c))
+}
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rswappalyzer_engine::source::WappalyzerParser;
+    use rswappalyzer_engine::RuleProcessor;
+
+    #[test]
+    fn test_cached_rules_keep_meta() {
+        let raw = WappalyzerParser
+            .parse_to_rule_lib(
+                r#"{"technologies": {
+                    "Acme": { "cats": [1], "website": "https://acme.example", "headers": { "X-Acme": "" } },
+                    "Bare": { "cats": [1], "headers": { "X-Bare": "" } }
+                }}"#,
+            )
+            .unwrap();
+        let rule_lib = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+
+        let data = RuleCacheManager::build_cached_rules(&rule_lib).unwrap();
+        let cached: Vec<CachedTechRule> = serde_json::from_slice(&data).unwrap();
+        let restored = RuleCacheManager::convert_cached_rules(cached).unwrap();
+
+        assert_eq!(restored.core_tech_map.len(), 2);
+        assert_eq!(restored.meta_table.len(), 1);
+        assert_eq!(restored.meta_table["Acme"].website.as_deref(), Some("https://acme.example"));
+    }
+}
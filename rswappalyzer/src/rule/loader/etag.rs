@@ -11,6 +11,9 @@ pub struct ETagRecord {
     pub local_file_path: String,
     /// 最后更新时间（时间戳）
     pub last_update: u64,
+    /// 远程文件 Last-Modified（用于 If-Modified-Since 条件请求）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
 }
 
 /// ETag 总记录（序列化到本地文件）
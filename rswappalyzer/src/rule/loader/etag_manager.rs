@@ -54,6 +54,19 @@ impl EtagManager {
         source_name: &str,
         etag: String,
         local_file_path: String,
+    ) -> RswResult<()> {
+        self.upsert_and_save_validators(config, etag_total, source_name, etag, None, local_file_path)
+    }
+
+    /// 更新并保存条件请求校验值（ETag + Last-Modified）
+    pub fn upsert_and_save_validators(
+        &self,
+        config: &RuleConfig,
+        etag_total: &mut ETagTotalRecord,
+        source_name: &str,
+        etag: String,
+        last_modified: Option<String>,
+        local_file_path: String,
     ) -> RswResult<()> {
         let new_record = ETagRecord {
             source_name: source_name.to_string(),
@@ -62,6 +75,7 @@ impl EtagManager {
             last_update: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)?
                 .as_secs(),
+            last_modified,
         };
         etag_total.upsert_record(new_record);
         self.save_etag_records(config, etag_total)
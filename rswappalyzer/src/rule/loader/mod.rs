@@ -13,4 +13,4 @@ pub use etag::{ETagRecord, ETagTotalRecord};
 pub use rule_loader::RuleLoader;
 pub use path_manager::RulePathManager;
 pub use etag_manager::EtagManager;
-pub use remote_fetcher::RemoteRuleFetcher;
+pub use remote_fetcher::{ConditionalFetch, RemoteRuleFetcher};
@@ -14,6 +14,22 @@ use reqwest::Client;
 use rswappalyzer_engine::RuleLibrary;
 use std::path::Path;
 
+/// 条件拉取结果
+#[derive(Debug)]
+pub enum ConditionalFetch {
+    /// 远程规则未变化（304），继续使用本地缓存
+    NotModified,
+    /// 远程规则已变化（或无本地记录），附带新的校验值
+    Modified {
+        /// 拉取并转换后的规则库
+        rule_lib: RuleLibrary,
+        /// 响应 ETag（已去除 W/ 前缀与引号）
+        etag: Option<String>,
+        /// 响应 Last-Modified
+        last_modified: Option<String>,
+    },
+}
+
 /// 远程规则拉取器
 /// 设计：无状态工具类，专注于远程规则的拉取、ETag获取和重试逻辑
 #[derive(Default)]
@@ -26,9 +42,11 @@ impl RemoteRuleFetcher {
     /// 2. 指数退避（固定1秒间隔，可扩展）
     /// 3. 保留最后一次错误信息
     /// 4. 异步闭包支持（FnMut返回Future）
+    ///
     /// 参数：
     /// - max_retries: 最大重试次数（0表示不重试）
     /// - func: 异步闭包，返回RswResult<T>
+    ///
     /// 返回：执行结果 | 最后一次错误
     #[cfg(feature = "remote-loader")]
     #[cfg(feature = "remote-loader")]
@@ -68,10 +86,12 @@ impl RemoteRuleFetcher {
     /// 2. 支持弱ETag解析（移除W/前缀和引号）
     /// 3. 重试策略适配（Never/Times(n)）
     /// 4. 友好错误处理（失败时返回Ok(None)，而非直接报错）
+    ///
     /// 参数：
     /// - client: reqwest异步客户端
     /// - url: 远程资源URL
     /// - retry_policy: 重试策略
+    ///
     /// 返回：ETag字符串（Option） | 错误（仅严重错误）
     #[cfg(feature = "remote-loader")]
     pub async fn get_remote_etag(
@@ -161,10 +181,12 @@ impl RemoteRuleFetcher {
     /// 2. 自动解析原始规则为RuleLibrary
     /// 3. 重试策略适配
     /// 4. 详细的日志和错误上下文
+    ///
     /// 参数：
     /// - client: reqwest异步客户端
     /// - url: 远程规则库URL
     /// - retry_policy: 重试策略
+    ///
     /// 返回：解析后的RuleLibrary | 错误
     #[cfg(feature = "remote-loader")]
     pub async fn fetch_wappalyzer_rules(
@@ -173,10 +195,27 @@ impl RemoteRuleFetcher {
         url: &str,
         retry_policy: &crate::RetryPolicy,
     ) -> RswResult<RuleLibrary> {
-        use rswappalyzer_engine::source::{
-            wappalyzer::WappalyzerOriginalRuleLibrary, WappalyzerParser,
-        };
+        self.fetch_rules(client, url, retry_policy, crate::RuleFormat::Wappalyzer)
+            .await
+    }
 
+    /// 拉取远程规则库并按规则格式转换（纯异步）
+    /// 参数：
+    /// - client: reqwest异步客户端
+    /// - url: 远程规则库URL
+    /// - retry_policy: 重试策略
+    /// - format: 规则格式（Wappalyzer / FingerprintHub / EHole / RSW YAML）
+    ///
+    /// 返回：转换后的RuleLibrary | 错误
+    #[cfg(feature = "remote-loader")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "rule.fetch", skip_all, fields(url = %url, format = ?format)))]
+    pub async fn fetch_rules(
+        &self,
+        client: &Client,
+        url: &str,
+        retry_policy: &crate::RetryPolicy,
+        format: crate::RuleFormat,
+    ) -> RswResult<RuleLibrary> {
         // 解析重试次数
         let max_retries = match retry_policy {
             crate::RetryPolicy::Never => 0,
@@ -222,47 +261,254 @@ impl RemoteRuleFetcher {
                         ))
                     })?;
 
-                    // 解析原始规则
-                    let parser = WappalyzerParser::default();
-                    let original_lib: WappalyzerOriginalRuleLibrary =
-                        parser.parse_from_bytes(&bytes).map_err(|e| {
-                            RswappalyzerError::RuleLoadError(format!(
-                                "Failed to parse original rules: {}",
-                                e
-                            ))
-                        })?;
-
-                    // 转换为标准RuleLibrary
-                    let rule_lib = parser.convert_original_to_rule_lib(original_lib);
-                    Ok(rule_lib)
+                    parse_rule_bytes(&bytes, &url, format)
                 })
             })
             .await?;
 
         // 记录成功日志
         log::debug!(
-            "Successfully fetched Wappalyzer rules, total tech rules: {}",
+            "Successfully fetched {:?} rules, total tech rules: {}",
+            format,
             rule_lib.core_tech_map.len()
         );
 
         Ok(rule_lib)
     }
 
+    /// 条件拉取远程规则库（If-None-Match / If-Modified-Since，纯异步）
+    /// 特性：
+    /// 1. 携带本地记录的 ETag / Last-Modified 发起 GET，规则未变化时服务端返回 304，无需重新下载
+    /// 2. 无本地记录时等同于完整拉取，响应中的 ETag / Last-Modified 随结果返回供调用方记录
+    ///
+    /// 参数：
+    /// - client: reqwest异步客户端
+    /// - url: 远程规则库URL
+    /// - retry_policy: 重试策略
+    /// - format: 规则格式
+    /// - validators: 本地 ETag 记录（None 时不携带条件请求头）
+    ///
+    /// 返回：未修改 / 新规则库及其校验值 | 错误
+    #[cfg(feature = "remote-loader")]
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "rule.fetch", skip_all, fields(url = %url, format = ?format)))]
+    pub async fn fetch_rules_conditional(
+        &self,
+        client: &Client,
+        url: &str,
+        retry_policy: &crate::RetryPolicy,
+        format: crate::RuleFormat,
+        validators: Option<&ETagRecord>,
+    ) -> RswResult<ConditionalFetch> {
+        let max_retries = match retry_policy {
+            crate::RetryPolicy::Never => 0,
+            crate::RetryPolicy::Times(n) => *n as usize,
+        };
+        let if_none_match = validators
+            .map(|record| record.etag.as_str())
+            .filter(|etag| !etag.is_empty())
+            .map(quote_etag);
+        let if_modified_since = validators.and_then(|record| record.last_modified.clone());
+
+        let fetched = self
+            .simple_retry(max_retries, || {
+                let client = client.clone();
+                let url = url.to_string();
+                let if_none_match = if_none_match.clone();
+                let if_modified_since = if_modified_since.clone();
+
+                Box::pin(async move {
+                    let mut request = client
+                        .get(&url)
+                        .header("User-Agent", "Rswappalyzer/0.1.0")
+                        .header("Accept-Encoding", "gzip, deflate");
+                    if let Some(etag) = if_none_match {
+                        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+                    }
+                    if let Some(since) = if_modified_since {
+                        request = request.header(reqwest::header::IF_MODIFIED_SINCE, since);
+                    }
+                    let response = request.send().await.map_err(|e| {
+                        RswappalyzerError::RuleLoadError(format!("Failed to fetch rules: {:#?}", e))
+                    })?;
+
+                    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+                        return Ok(ConditionalFetch::NotModified);
+                    }
+                    if !response.status().is_success() {
+                        return Err(RswappalyzerError::RuleLoadError(format!(
+                            "Failed to fetch rules: URL {} returned status code {}",
+                            url,
+                            response.status()
+                        )));
+                    }
+
+                    let header = |name: reqwest::header::HeaderName| {
+                        response
+                            .headers()
+                            .get(name)
+                            .and_then(|value| value.to_str().ok())
+                            .map(str::to_string)
+                    };
+                    let etag = header(reqwest::header::ETAG)
+                        .map(|etag| etag.trim_start_matches("W/").trim_matches('"').to_string());
+                    let last_modified = header(reqwest::header::LAST_MODIFIED);
+                    let bytes = response.bytes().await.map_err(|e| {
+                        RswappalyzerError::RuleLoadError(format!("Failed to read response bytes: {}", e))
+                    })?;
+                    let rule_lib = parse_rule_bytes(&bytes, &url, format)?;
+                    Ok(ConditionalFetch::Modified {
+                        rule_lib,
+                        etag,
+                        last_modified,
+                    })
+                })
+            })
+            .await?;
+
+        match &fetched {
+            ConditionalFetch::NotModified => log::debug!("Remote rules not modified (304): {}", url),
+            ConditionalFetch::Modified { rule_lib, .. } => log::debug!(
+                "Successfully fetched {:?} rules, total tech rules: {}",
+                format,
+                rule_lib.core_tech_map.len()
+            ),
+        }
+        Ok(fetched)
+    }
+
+    /// 创建HTTP客户端（超时 + 代理配置）
+    /// 参数：
+    /// - timeout: 请求超时
+    /// - proxy: 代理配置（显式代理优先，否则按配置回退读取环境变量或直连）
+    ///
+    /// 返回：HTTP客户端 | 代理地址无效/客户端创建失败
+    #[cfg(feature = "remote-loader")]
+    pub fn build_client(timeout: std::time::Duration, proxy: &crate::ProxyConfig) -> RswResult<Client> {
+        let mut builder = Client::builder().timeout(timeout);
+        if let Some(proxy_url) = proxy.explicit_url() {
+            let proxy = reqwest::Proxy::all(&proxy_url).map_err(|e| {
+                RswappalyzerError::RuleLoadError(format!("Invalid proxy URL {}: {}", proxy_url, e))
+            })?;
+            builder = builder.proxy(proxy);
+        } else if !proxy.use_env {
+            builder = builder.no_proxy();
+        }
+        builder
+            .build()
+            .map_err(|e| RswappalyzerError::RuleLoadError(format!("Failed to build HTTP client: {}", e)))
+    }
+
+    /// 多规则源拉取（纯异步，按 FetchMode 调度）
+    /// 特性：
+    /// 1. 按配置顺序依次拉取（主规则源在首位），每个规则源使用独立的超时/重试策略
+    /// 2. FirstSuccess 首个成功即停止；Merge/Quorum 拉取全部并按顺序合并（同名技术按 MergePolicy 处理）
+    /// 3. 每个规则源的开始/成功/失败及整体完成均通过进度回调上报
+    ///
+    /// 参数：
+    /// - primary_url: 主规则源URL（规则来源对应的URL）
+    /// - remote_opts: 网络加载选项（附加规则源/调度模式/合并策略/进度回调）
+    ///
+    /// 返回：合并后的RuleLibrary | 错误（成功数不满足调度要求，或 FailOnConflict 下同名技术定义冲突时）
+    #[cfg(feature = "remote-loader")]
+    pub async fn fetch_multi_source(
+        &self,
+        primary_url: &str,
+        remote_opts: &crate::RemoteOptions,
+    ) -> RswResult<RuleLibrary> {
+        let primary = crate::RemoteSource::new(primary_url);
+        let sources: Vec<&crate::RemoteSource> =
+            std::iter::once(&primary).chain(remote_opts.sources.iter()).collect();
+        let total = sources.len();
+        let required = match remote_opts.mode {
+            crate::FetchMode::FirstSuccess | crate::FetchMode::Merge => 1,
+            crate::FetchMode::Quorum(n) => n.clamp(1, total),
+        };
+        let emit = |event: crate::FetchProgress| {
+            if let Some(callback) = &remote_opts.progress {
+                callback.emit(&event);
+            }
+        };
+
+        let mut merged = RuleLibrary::default();
+        let mut succeeded = 0;
+        let mut failed = 0;
+        let mut last_error = String::new();
+
+        for (index, source) in sources.into_iter().enumerate() {
+            let url = source.url.clone();
+            emit(crate::FetchProgress::SourceStarted { index, total, url: url.clone() });
+
+            let timeout = source.timeout.unwrap_or(remote_opts.timeout);
+            let retry = source.retry.as_ref().unwrap_or(&remote_opts.retry);
+            let result = match Self::build_client(timeout, &remote_opts.proxy) {
+                Ok(client) => self.fetch_rules(&client, &url, retry, source.format).await,
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(mut rule_lib) => {
+                    succeeded += 1;
+                    rule_lib.stamp_source(source.label());
+                    emit(crate::FetchProgress::SourceSucceeded {
+                        index,
+                        total,
+                        url,
+                        tech_count: rule_lib.core_tech_map.len(),
+                    });
+                    merge_library(&mut merged, rule_lib, remote_opts.merge_policy)?;
+                    if remote_opts.mode == crate::FetchMode::FirstSuccess {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    failed += 1;
+                    log::warn!("Rule source fetch failed | URL: {} | Error: {}", url, e);
+                    last_error = e.to_string();
+                    emit(crate::FetchProgress::SourceFailed {
+                        index,
+                        total,
+                        url,
+                        error: last_error.clone(),
+                    });
+                }
+            }
+        }
+
+        emit(crate::FetchProgress::Completed { succeeded, failed });
+
+        if succeeded < required {
+            return Err(RswappalyzerError::RuleLoadError(format!(
+                "Rule sources fetched: {}/{} succeeded, {} required | Last error: {}",
+                succeeded, total, required, last_error
+            )));
+        }
+        log::debug!(
+            "Multi-source rules fetched | Mode: {:?} | Succeeded: {}/{} | Total tech rules: {}",
+            remote_opts.mode,
+            succeeded,
+            total,
+            merged.core_tech_map.len()
+        );
+        Ok(merged)
+    }
+
     /// 判断是否使用本地缓存文件
     /// 规则：
     /// 1. 本地ETag记录存在
     /// 2. ETag与远程一致
     /// 3. 本地文件存在
+    ///
     /// 参数：
     /// - local_record: 本地ETag记录（Option）
     /// - remote_etag: 远程ETag
+    ///
     /// 返回：是否使用本地文件（true/false）
     pub fn should_use_local_file(
         &self,
         local_record: &Option<ETagRecord>,
         remote_etag: &str,
     ) -> bool {
-        local_record.as_ref().map_or(false, |r| {
+        local_record.as_ref().is_some_and(|r| {
             r.etag == remote_etag && Path::new(&r.local_file_path).exists()
         })
     }
@@ -295,3 +541,165 @@ impl RemoteRuleFetcher {
         ))
     }
 }
+
+/// 按规则格式解析远程规则字节
+/// 参数：
+/// - bytes: 响应体字节
+/// - url: 规则源URL（日志用）
+/// - format: 规则格式（Wappalyzer / FingerprintHub / EHole / RSW YAML）
+///
+/// 返回：转换后的RuleLibrary | 解析错误
+#[cfg(feature = "remote-loader")]
+fn parse_rule_bytes(bytes: &[u8], url: &str, format: crate::RuleFormat) -> RswResult<RuleLibrary> {
+    use rswappalyzer_engine::source::{
+        wappalyzer::WappalyzerOriginalRuleLibrary, EholeConverter, FingerprintHubConverter,
+        WappalyzerParser,
+    };
+
+    use crate::rule::loader::rule_loader::parse_rsw_yaml_bytes;
+
+    // FingerprintHub / EHole 指纹、RSW YAML 规则：转换为标准RuleLibrary（不支持的指纹跳过）
+    let converted = match format {
+        crate::RuleFormat::Wappalyzer => None,
+        crate::RuleFormat::FingerprintHub => Some(
+            FingerprintHubConverter
+                .convert_bytes(bytes)
+                .map(|import| (import.rule_lib, import.skipped.len())),
+        ),
+        crate::RuleFormat::Ehole => Some(
+            EholeConverter
+                .convert_bytes(bytes)
+                .map(|import| (import.rule_lib, import.skipped.len())),
+        ),
+        crate::RuleFormat::RswYaml => {
+            Some(parse_rsw_yaml_bytes(bytes).map(|rule_lib| (rule_lib, 0)))
+        }
+    };
+    if let Some(converted) = converted {
+        let (rule_lib, skipped) = converted.inspect_err(|e| {
+            log::warn!("Failed to parse {:?} rules from {}: {}", format, url, e)
+        })?;
+        if skipped > 0 {
+            log::debug!(
+                "Skipped {} unsupported {:?} fingerprints from {}",
+                skipped,
+                format,
+                url
+            );
+        }
+        return Ok(rule_lib);
+    }
+
+    // 解析原始规则
+    let parser = WappalyzerParser;
+    let original_lib: WappalyzerOriginalRuleLibrary =
+        parser.parse_from_bytes(bytes).inspect_err(|e| {
+            log::warn!("Failed to parse original rules from {}: {}", url, e)
+        })?;
+
+    // 转换为标准RuleLibrary
+    let rule_lib = parser.convert_original_to_rule_lib(original_lib);
+    Ok(rule_lib)
+}
+
+/// 还原 ETag 引号（本地记录存储去除引号后的值）
+/// 按合并策略将规则源合并到已合并的规则库
+/// 返回：成功 | FailOnConflict 下同名技术定义冲突
+#[cfg(feature = "remote-loader")]
+fn merge_library(merged: &mut RuleLibrary, rule_lib: RuleLibrary, policy: crate::MergePolicy) -> RswResult<()> {
+    use crate::MergePolicy;
+
+    if policy == MergePolicy::FailOnConflict {
+        let mut conflicts: Vec<&str> = rule_lib
+            .core_tech_map
+            .iter()
+            .filter(|(name, rule)| {
+                merged
+                    .core_tech_map
+                    .get(*name)
+                    .is_some_and(|existing| existing.match_rules != rule.match_rules)
+            })
+            .map(|(name, _)| name.as_str())
+            .collect();
+        if !conflicts.is_empty() {
+            conflicts.sort_unstable();
+            return Err(RswappalyzerError::RuleLoadError(format!(
+                "Conflicting tech definitions across rule sources: {}",
+                conflicts.join(", ")
+            )));
+        }
+    }
+
+    let prefer_last = policy == MergePolicy::PreferLast;
+    for (name, rule) in rule_lib.core_tech_map {
+        let Some(existing) = merged.core_tech_map.get_mut(&name) else {
+            merged.core_tech_map.insert(name, rule);
+            continue;
+        };
+        match policy {
+            MergePolicy::PreferLast => *existing = rule,
+            MergePolicy::MergePatterns => merge_patterns(&name, existing, rule),
+            MergePolicy::PreferFirst | MergePolicy::FailOnConflict => {}
+        }
+    }
+    for (id, category) in rule_lib.category_rules {
+        if prefer_last || !merged.category_rules.contains_key(&id) {
+            merged.category_rules.insert(id, category);
+        }
+    }
+    for (name, meta) in rule_lib.meta_table {
+        if prefer_last || !merged.meta_table.contains_key(&name) {
+            merged.meta_table.insert(name, meta);
+        }
+    }
+    Ok(())
+}
+
+/// 模式级合并同名技术（按作用域追加不重复的模式，基础信息列表去重合并）
+#[cfg(feature = "remote-loader")]
+fn merge_patterns(name: &str, existing: &mut rswappalyzer_engine::ParsedTechRule, rule: rswappalyzer_engine::ParsedTechRule) {
+    use rswappalyzer_engine::MatchCondition;
+
+    fn union<T: PartialEq>(target: &mut Vec<T>, items: Vec<T>) {
+        for item in items {
+            if !target.contains(&item) {
+                target.push(item);
+            }
+        }
+    }
+
+    let basic = rule.basic;
+    union(&mut existing.basic.category_ids, basic.category_ids);
+    if let Some(implies) = basic.implies {
+        union(existing.basic.implies.get_or_insert_with(Vec::new), implies);
+    }
+    union(&mut existing.basic.excludes, basic.excludes);
+    union(&mut existing.basic.requires, basic.requires);
+    union(&mut existing.basic.requires_category, basic.requires_category);
+    union(&mut existing.basic.hostnames, basic.hostnames);
+    if existing.basic.lifecycle.is_none() {
+        existing.basic.lifecycle = basic.lifecycle;
+    }
+
+    for (scope, rule_set) in rule.match_rules {
+        let Some(target) = existing.match_rules.get_mut(&scope) else {
+            existing.match_rules.insert(scope, rule_set);
+            continue;
+        };
+        if target.condition == MatchCondition::And || rule_set.condition == MatchCondition::And {
+            log::warn!(
+                "Skip merging '{:?}' patterns of tech '{}': 'and' condition rule sets cannot be merged",
+                scope,
+                name
+            );
+            continue;
+        }
+        union(&mut target.list_patterns, rule_set.list_patterns);
+        union(&mut target.keyed_patterns, rule_set.keyed_patterns);
+    }
+}
+
+#[cfg(feature = "remote-loader")]
+fn quote_etag(etag: &str) -> String {
+    format!("\"{}\"", etag)
+}
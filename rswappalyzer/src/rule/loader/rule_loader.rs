@@ -1,13 +1,21 @@
 use log::{debug, warn};
 #[cfg(feature = "remote-loader")]
-use reqwest::Client;
+use crate::rule::loader::RemoteRuleFetcher;
+#[cfg(feature = "remote-loader")]
+use crate::rule::loader::ConditionalFetch;
+#[cfg(feature = "yaml-rules")]
+use rswappalyzer_engine::source::RswYamlParser;
 use rswappalyzer_engine::source::WappalyzerParser;
-use rswappalyzer_engine::{RuleLibrary, RuleProcessor};
+use rswappalyzer_engine::{CoreResult, RuleLibrary, RuleProcessor};
 use std::fs;
 use std::path::Path;
 
 use crate::error::{RswResult, RswappalyzerError};
-use crate::{RuleCacheManager, RuleConfig, RuleOrigin};
+use crate::{RuleCacheManager, RuleConfig, RuleOrigin, StalePolicy};
+
+/// 官方远程规则源URL
+pub const OFFICIAL_RULES_URL: &str =
+    "https://raw.githubusercontent.com/projectdiscovery/wappalyzergo/refs/heads/main/fingerprints_data.json";
 
 /// 规则加载器
 /// 核心职责：根据不同规则源（内置/本地/远程）加载并处理Wappalyzer规则库
@@ -39,6 +47,7 @@ impl RuleLoader {
     /// 参数：
     /// - config: 规则配置
     /// 返回：加载完成的规则库 | 加载错误
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "rule.load", skip_all, fields(origin = ?config.origin)))]
     pub async fn load(&self, config: &RuleConfig) -> RswResult<RuleLibrary> {
         match &config.origin {
             RuleOrigin::Embedded => self.load_embedded(),
@@ -52,6 +61,7 @@ impl RuleLoader {
     /// 通用缓存加载逻辑（本地/远程规则复用）
     /// 参数：
     /// - config: 规则配置
+    ///
     /// 返回：缓存规则库（None表示加载失败）
     async fn load_from_cache_unified(&self, config: &RuleConfig) -> Option<RuleLibrary> {
         let cache_path = config.get_cache_file_path();
@@ -88,18 +98,38 @@ impl RuleLoader {
     }
 
     /// 加载本地规则文件
-    /// 逻辑：缓存优先 → 读取原始文件 → 解析清洗 → 缓存保存
+    /// 逻辑：缓存优先（未过期） → 读取原始文件 → 解析清洗 → 缓存保存
     /// 参数：
     /// - config: 规则配置
     /// - path: 本地规则文件路径
+    ///
     /// 返回：处理后的规则库 | 加载错误
     async fn load_local_file(&self, config: &RuleConfig, path: &Path) -> RswResult<RuleLibrary> {
-        // 1. 优先从缓存加载
-        if let Some(cached_lib) = self.load_from_cache_unified(config).await {
-            return Ok(cached_lib);
+        // 1. 优先从缓存加载（过期时同步重新读取原始文件，按过期策略决定失败时是否沿用过期缓存）
+        match self.load_from_cache_unified(config).await {
+            Some(cached_lib) if !RuleCacheManager::is_stale(config) => return Ok(cached_lib),
+            Some(cached_lib) => {
+                debug!("Local rule cache expired, re-reading raw rule file: {:?}", path);
+                return match self.load_raw_local_file(config, path).await {
+                    Err(e) if config.options.stale_policy != StalePolicy::FailIfStale => {
+                        warn!("Failed to refresh expired local rule cache, serving stale cache | Error: {}", e);
+                        Ok(cached_lib)
+                    }
+                    result => result,
+                };
+            }
+            None => warn!("Local cache not found, reading raw rule file: {:?}", path),
         }
-        warn!("Local cache not found, reading raw rule file: {:?}", path);
+        self.load_raw_local_file(config, path).await
+    }
 
+    /// 读取本地原始规则文件（解析清洗后写入缓存）
+    /// 参数：
+    /// - config: 规则配置
+    /// - path: 本地规则文件路径
+    ///
+    /// 返回：处理后的规则库 | 加载错误
+    async fn load_raw_local_file(&self, config: &RuleConfig, path: &Path) -> RswResult<RuleLibrary> {
         // 2. 读取并解析原始规则文件
         let raw_content = fs::read_to_string(path).map_err(|e| {
             RswappalyzerError::RuleLoadError(format!(
@@ -109,10 +139,15 @@ impl RuleLoader {
             ))
         })?;
 
-        let parser = WappalyzerParser::default();
-        let raw_lib = parser.parse_to_rule_lib(&raw_content).map_err(|e| {
-            RswappalyzerError::RuleLoadError(format!("Failed to parse rules: {}", e))
-        })?;
+        let parser = WappalyzerParser;
+        let raw_lib = if is_rsw_yaml_path(path) {
+            parse_rsw_yaml_bytes(raw_content.as_bytes()).map_err(Into::into)
+        } else if is_yaml_path(path) {
+            parse_yaml_rules(&parser, &raw_content)
+        } else {
+            parser.parse_to_rule_lib(&raw_content).map_err(Into::into)
+        }
+        .inspect_err(|e| warn!("Failed to parse raw rule file {:?}: {}", path, e))?;
 
         // 3. 清洗拆分规则并缓存
         let cleaned_lib = self.rule_processor.clean_and_split_rules(&raw_lib)?;
@@ -125,101 +160,172 @@ impl RuleLoader {
     /// 核心逻辑：ETag校验 → 缓存优先/远程拉取 → 规则处理 → 缓存更新
     /// 参数：
     /// - config: 规则配置
+    ///
     /// 返回：处理后的规则库 | 加载错误
     #[cfg(feature = "remote-loader")]
     async fn load_remote_rules(&self, config: &RuleConfig) -> RswResult<RuleLibrary> {
         // 1. 校验远程配置是否存在
-        let remote_opts = config.remote_options.as_ref().ok_or_else(|| {
-            RswappalyzerError::RuleLoadError("Missing remote network configuration".into())
-        })?;
+        if config.remote_options.is_none() {
+            return Err(RswappalyzerError::RuleLoadError("Missing remote network configuration".into()));
+        }
 
         // 2. 解析远程规则源URL和名称
-        let (remote_url, source_identifier) = match &config.origin {
-        RuleOrigin::RemoteOfficial => (
-            "https://raw.githubusercontent.com/projectdiscovery/wappalyzergo/refs/heads/main/fingerprints_data.json",
-            "wappalyzergo_official"
-        ),
-        RuleOrigin::RemoteCustom(custom_url) => (custom_url.as_str(), "wappalyzer_custom"),
-        _ => return Err(RswappalyzerError::RuleLoadError("Not a remote rule source".into())),
-    };
+        let (remote_url, source_identifier) = remote_source(config)?;
 
         // 3. 优先尝试加载缓存（核心逻辑分支点）
-        let cached_lib = self.load_from_cache_unified(config).await;
-        if let Some(lib) = cached_lib {
-            // 3.1 check_update=false 且缓存存在：直接返回缓存，不发起任何网络请求
-            if !config.options.check_update {
+        match self.load_from_cache_unified(config).await {
+            // 3.1 check_update=false 且缓存未过期：直接返回缓存，不发起任何网络请求
+            Some(lib) if !config.options.check_update && !RuleCacheManager::is_stale(config) => {
                 debug!("check_update is false and cache exists, skip all network requests");
-                return Ok(lib);
+                Ok(lib)
+            }
+            // 3.2 check_update=false 且缓存已过期：按过期策略刷新
+            Some(lib) if !config.options.check_update => {
+                self.refresh_stale_remote(config, remote_url, source_identifier, lib).await
+            }
+            // 3.3 check_update=true 且缓存存在：继续执行条件请求流程
+            Some(lib) => {
+                debug!("check_update is true, proceed to conditional request");
+                self.fetch_remote_rules(config, remote_url, source_identifier, Some(lib)).await
+            }
+            None => {
+                warn!("Cache not found, need to fetch remote rules completely");
+                self.fetch_remote_rules(config, remote_url, source_identifier, None).await
             }
-            // 3.2 check_update=true 且缓存存在：继续执行ETag检测流程
-            debug!("check_update is true, proceed to ETag check");
-        } else {
-            warn!("Cache not found, need to fetch remote rules completely");
         }
+    }
 
-        // 4. 创建HTTP客户端（带超时配置）
-        let client = Client::builder()
-            .timeout(remote_opts.timeout)
-            .build()
-            .map_err(|e| {
-                RswappalyzerError::RuleLoadError(format!("Failed to build HTTP client: {}", e))
-            })?;
+    /// 按过期策略处理过期的远程规则缓存
+    /// 1. ServeStaleAndRefresh：返回过期缓存，后台拉取并更新缓存（无 tokio 运行时则同步刷新）
+    /// 2. AlwaysRefresh：同步拉取，失败时返回过期缓存
+    /// 3. FailIfStale：同步拉取，失败时报错
+    ///
+    /// 参数：
+    /// - config: 规则配置
+    /// - remote_url: 规则源URL
+    /// - source_identifier: ETag记录标识
+    /// - stale_lib: 过期缓存规则库
+    ///
+    /// 返回：规则库 | 加载错误
+    #[cfg(feature = "remote-loader")]
+    async fn refresh_stale_remote(
+        &self,
+        config: &RuleConfig,
+        remote_url: &str,
+        source_identifier: &str,
+        stale_lib: RuleLibrary,
+    ) -> RswResult<RuleLibrary> {
+        debug!("Remote rule cache expired, refresh with policy {:?}", config.options.stale_policy);
+        if config.options.stale_policy == StalePolicy::ServeStaleAndRefresh {
+            if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+                let config = config.clone();
+                runtime.spawn(async move {
+                    let Ok((remote_url, source_identifier)) = remote_source(&config) else {
+                        return;
+                    };
+                    let refreshed = RuleLoader::new()
+                        .fetch_remote_rules(&config, remote_url, source_identifier, None)
+                        .await;
+                    if let Err(e) = refreshed {
+                        warn!("Background refresh of expired rule cache failed | Error: {}", e);
+                    }
+                });
+                return Ok(stale_lib);
+            }
+        }
+        match self.fetch_remote_rules(config, remote_url, source_identifier, None).await {
+            Err(e) if config.options.stale_policy != StalePolicy::FailIfStale => {
+                warn!("Failed to refresh expired rule cache, serving stale cache | Error: {}", e);
+                Ok(stale_lib)
+            }
+            result => result,
+        }
+    }
 
-        // 5. 根据check_update决定是否执行ETag检测
+    /// 拉取远程规则（多规则源调度 / 单规则源条件请求或完整请求），结果写入缓存
+    /// 参数：
+    /// - config: 规则配置
+    /// - remote_url: 规则源URL
+    /// - source_identifier: ETag记录标识
+    /// - cached_lib: 当前缓存规则库（条件请求返回 304 时沿用）
+    ///
+    /// 返回：处理后的规则库 | 加载错误
+    #[cfg(feature = "remote-loader")]
+    async fn fetch_remote_rules(
+        &self,
+        config: &RuleConfig,
+        remote_url: &str,
+        source_identifier: &str,
+        cached_lib: Option<RuleLibrary>,
+    ) -> RswResult<RuleLibrary> {
+        let remote_opts = config.remote_options.as_ref().ok_or_else(|| {
+            RswappalyzerError::RuleLoadError("Missing remote network configuration".into())
+        })?;
+
+        // 多规则源：按调度模式拉取并合并（ETag仅适用于单规则源，此处跳过）
+        if !remote_opts.sources.is_empty() {
+            let raw_lib = self
+                .remote_fetcher
+                .fetch_multi_source(remote_url, remote_opts)
+                .await?;
+            let cleaned_lib = self.rule_processor.clean_and_split_rules(&raw_lib)?;
+            self.save_to_cache_unified(config, &cleaned_lib).await;
+            return Ok(cleaned_lib);
+        }
+
+        // 4. 创建HTTP客户端（带超时与代理配置）
+        let client = RemoteRuleFetcher::build_client(remote_opts.timeout, &remote_opts.proxy)?;
+
+        // 5. 根据check_update决定是否执行条件请求
         let cleaned_rule_lib = if config.options.check_update {
-            // 5.1 check_update=true：完整ETag检测流程（原有逻辑）
+            // 5.1 check_update=true：携带本地 ETag / Last-Modified 条件拉取，304 时沿用本地缓存
             let mut etag_records = self.etag_manager.load_etag_records(config)?;
-            let remote_etag = self
+            let local_record = self
+                .etag_manager
+                .find_local_etag(config, source_identifier)?
+                .filter(|record| cached_lib.is_some() && Path::new(&record.local_file_path).exists());
+            let fetched = self
                 .remote_fetcher
-                .get_remote_etag(&client, remote_url, &remote_opts.retry)
+                .fetch_rules_conditional(
+                    &client,
+                    remote_url,
+                    &remote_opts.retry,
+                    crate::RuleFormat::Wappalyzer,
+                    local_record.as_ref(),
+                )
                 .await?;
 
-            match remote_etag {
-                None => {
-                    warn!("Remote ETag not found, force fetching latest rules");
-                    let raw_lib = self
-                        .remote_fetcher
-                        .fetch_wappalyzer_rules(&client, remote_url, &remote_opts.retry)
-                        .await?;
-                    let cleaned_lib = self.rule_processor.clean_and_split_rules(&raw_lib)?;
-                    self.save_to_cache_unified(config, &cleaned_lib).await;
-                    cleaned_lib
+            match (fetched, cached_lib) {
+                (ConditionalFetch::NotModified, Some(cached_lib)) => {
+                    debug!("Rule library is up-to-date (304), using local cache");
+                    if let Err(e) = RuleCacheManager::touch(config) {
+                        warn!("Failed to refresh rule cache timestamp | Error: {}", e);
+                    }
+                    cached_lib
                 }
-                Some(etag) => {
-                    let local_etag_record = self
-                        .etag_manager
-                        .find_local_etag(config, source_identifier)?;
-                    let use_local_cache = self
-                        .remote_fetcher
-                        .should_use_local_file(&local_etag_record, &etag);
-
-                    if use_local_cache {
-                        debug!("Rule library is up-to-date, using local cache");
-                        self.load_from_cache_unified(config).await.ok_or_else(|| {
-                            RswappalyzerError::RuleLoadError(
-                                "Local cache missing but ETag matches".into(),
-                            )
-                        })?
-                    } else {
-                        debug!("New rule library detected, fetching remote rules");
-                        let raw_lib = self
-                            .remote_fetcher
-                            .fetch_wappalyzer_rules(&client, remote_url, &remote_opts.retry)
-                            .await?;
-                        let cleaned_lib = self.rule_processor.clean_and_split_rules(&raw_lib)?;
-
-                        self.save_to_cache_unified(config, &cleaned_lib).await;
+                (ConditionalFetch::NotModified, None) => {
+                    return Err(RswappalyzerError::RuleLoadError(
+                        "Remote returned 304 but local cache is missing".into(),
+                    ));
+                }
+                (ConditionalFetch::Modified { rule_lib, etag, last_modified }, _) => {
+                    debug!("New rule library detected, updating local cache");
+                    let cleaned_lib = self.rule_processor.clean_and_split_rules(&rule_lib)?;
+                    self.save_to_cache_unified(config, &cleaned_lib).await;
 
-                        self.etag_manager.upsert_and_save_etag(
+                    if etag.is_some() || last_modified.is_some() {
+                        self.etag_manager.upsert_and_save_validators(
                             config,
                             &mut etag_records,
                             source_identifier,
-                            etag,
+                            etag.unwrap_or_default(),
+                            last_modified,
                             config.get_cache_file_path().to_string_lossy().to_string(),
                         )?;
-
-                        cleaned_lib
+                    } else {
+                        warn!("Remote returned neither ETag nor Last-Modified, next refresh downloads in full");
                     }
+                    cleaned_lib
                 }
             }
         } else {
@@ -253,8 +359,62 @@ impl RuleLoader {
     }
 }
 
-/// 异步任务错误转换（JoinError → RswappalyzerError）
+/// 解析远程规则源URL与ETag记录标识
 #[cfg(feature = "remote-loader")]
+fn remote_source(config: &RuleConfig) -> RswResult<(&str, &'static str)> {
+    match &config.origin {
+        RuleOrigin::RemoteOfficial => Ok((OFFICIAL_RULES_URL, "wappalyzergo_official")),
+        RuleOrigin::RemoteCustom(custom_url) => Ok((custom_url.as_str(), "wappalyzer_custom")),
+        _ => Err(RswappalyzerError::RuleLoadError("Not a remote rule source".into())),
+    }
+}
+
+/// 是否为YAML格式规则文件（按扩展名 .yml/.yaml 判断）
+fn is_yaml_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("yml") || ext.eq_ignore_ascii_case("yaml"))
+}
+
+/// 是否为RSW YAML简化格式规则文件（按复合扩展名 .rsw.yml/.rsw.yaml 判断）
+fn is_rsw_yaml_path(path: &Path) -> bool {
+    is_yaml_path(path)
+        && path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| stem.to_ascii_lowercase().ends_with(".rsw"))
+}
+
+/// 解析RSW YAML简化格式规则（本地文件与远程规则源共用）
+#[cfg(feature = "yaml-rules")]
+pub(crate) fn parse_rsw_yaml_bytes(bytes: &[u8]) -> CoreResult<RuleLibrary> {
+    RswYamlParser.parse_bytes(bytes)
+}
+
+/// 非yaml-rules模式下的RSW YAML解析逻辑（直接返回错误）
+#[cfg(not(feature = "yaml-rules"))]
+pub(crate) fn parse_rsw_yaml_bytes(_bytes: &[u8]) -> CoreResult<RuleLibrary> {
+    Err(rswappalyzer_engine::CoreError::RuleParseError(
+        "Please enable 'yaml-rules' feature to load RSW YAML rules".into(),
+    ))
+}
+
+/// 解析YAML格式规则（结构与Wappalyzer JSON一致，错误信息含行号）
+#[cfg(feature = "yaml-rules")]
+fn parse_yaml_rules(parser: &WappalyzerParser, content: &str) -> RswResult<RuleLibrary> {
+    Ok(parser.parse_yaml_to_rule_lib(content)?)
+}
+
+/// 非yaml-rules模式下的YAML解析逻辑（直接返回错误）
+#[cfg(not(feature = "yaml-rules"))]
+fn parse_yaml_rules(_parser: &WappalyzerParser, _content: &str) -> RswResult<RuleLibrary> {
+    Err(RswappalyzerError::RuleLoadError(
+        "Please enable 'yaml-rules' feature to load YAML rules".into(),
+    ))
+}
+
+/// 异步任务错误转换（JoinError → RswappalyzerError）
+#[cfg(any(feature = "remote-loader", feature = "tower"))]
 impl From<tokio::task::JoinError> for RswappalyzerError {
     fn from(err: tokio::task::JoinError) -> Self {
         RswappalyzerError::AsyncTaskError(format!("Async task failed: {}", err))
@@ -0,0 +1,146 @@
+//! 预编译规则包（compiled pack）文件读写
+//! 格式：8字节魔数 `RSWPACK1` + 未压缩 JSON 序列化的 CompiledRuleLibrary；
+//! 不做 LZ4 压缩，mmap 映射后可直接在映射区原地反序列化，无需额外的解压缓冲区
+//!
+//! 两种加载方式的取舍：
+//! 1. read_compiled_pack：整文件读入进程私有堆缓冲区后完整反序列化，加载期间缓冲区与规则库同时驻留；
+//!    小规则包下开销可忽略，且不受文件被外部修改的影响
+//! 2. map_compiled_pack（mmap 特性）：只建立只读映射，页面在反序列化访问时才按需缺页载入；
+//!    同机多进程映射同一文件时共享内核页缓存，不再各自持有一份文件副本，峰值内存更低
+//!
+//! 注意：反序列化后的规则库（哈希索引/懒编译正则）仍是各进程私有的堆数据，mmap 省掉的是
+//! 文件字节的私有副本与加载峰值，而非规则库本身；映射期间文件被截断/改写可能导致进程收到
+//! SIGBUS，更新规则包请使用 write_compiled_pack（临时文件 + 原子重命名），切勿原地覆盖
+
+use std::fs;
+use std::path::Path;
+
+use rswappalyzer_engine::{CompiledRuleLibrary, EngineRequirements};
+use serde::Deserialize;
+
+use crate::error::{RswResult, RswappalyzerError};
+
+/// 规则包魔数（含格式版本）
+pub const PACK_MAGIC: &[u8; 8] = b"RSWPACK1";
+
+/// 将已编译规则库写入规则包文件
+/// 先写临时文件再原子重命名，避免正在映射该文件的进程读到半写状态
+/// 参数：
+/// - lib: 已编译的规则库
+/// - path: 规则包路径
+///
+/// 返回：成功 | 错误
+pub fn write_compiled_pack(lib: &CompiledRuleLibrary, path: impl AsRef<Path>) -> RswResult<()> {
+    let path = path.as_ref();
+    if let Some(parent_dir) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent_dir)?;
+    }
+    let data = encode_pack(lib)?;
+
+    let tmp_path = path.with_extension("pack.tmp");
+    fs::write(&tmp_path, &data)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// 序列化规则包字节（魔数 + JSON 规则库）
+pub(crate) fn encode_pack(lib: &CompiledRuleLibrary) -> RswResult<Vec<u8>> {
+    let mut data = PACK_MAGIC.to_vec();
+    serde_json::to_writer(&mut data, lib)?;
+    Ok(data)
+}
+
+/// 读取规则包文件并完整反序列化（无需 mmap 特性）
+/// 参数：path - 规则包路径
+/// 返回：已编译规则库 | 错误
+pub fn read_compiled_pack(path: impl AsRef<Path>) -> RswResult<CompiledRuleLibrary> {
+    let data = fs::read(path)?;
+    decode_pack(&data)
+}
+
+/// 规则包头部（仅引擎要求，其余字段跳过）
+#[derive(Deserialize)]
+struct PackHeader {
+    #[serde(default)]
+    engine: EngineRequirements,
+}
+
+/// 校验魔数与引擎兼容性，并从规则包字节反序列化规则库
+/// 先仅解析引擎要求：新版本引擎编译的规则包在完整反序列化前即以 IncompatibleEngine 失败，
+/// 而不是表现为字段缺失/类型不符等难以定位的解析错误
+pub(crate) fn decode_pack(data: &[u8]) -> RswResult<CompiledRuleLibrary> {
+    let body = data.strip_prefix(PACK_MAGIC.as_slice()).ok_or_else(|| {
+        RswappalyzerError::RuleParseError("Not a compiled rule pack (bad magic)".to_string())
+    })?;
+    let header: PackHeader = serde_json::from_slice(body)?;
+    header.engine.check()?;
+    let mut library: CompiledRuleLibrary = serde_json::from_slice(body)?;
+    library.build_literal_prefilters();
+    Ok(library)
+}
+
+/// 内存映射的规则包（mmap 特性）
+/// 持有只读映射，首次调用 library 时才反序列化（之后复用同一实例）
+#[cfg(feature = "mmap")]
+pub struct MappedRulePack {
+    mmap: fmmap::MmapFile,
+    library: std::sync::OnceLock<std::sync::Arc<CompiledRuleLibrary>>,
+}
+
+#[cfg(feature = "mmap")]
+impl MappedRulePack {
+    /// 映射区字节数（规则包文件大小）
+    pub fn len(&self) -> usize {
+        use fmmap::MmapFileExt;
+        self.mmap.len()
+    }
+
+    /// 映射区是否为空
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 获取规则库（首次调用时从映射区反序列化）
+    /// 返回：共享规则库 | 解析错误
+    pub fn library(&self) -> RswResult<std::sync::Arc<CompiledRuleLibrary>> {
+        use fmmap::MmapFileExt;
+        if let Some(lib) = self.library.get() {
+            return Ok(lib.clone());
+        }
+        let lib = std::sync::Arc::new(decode_pack(self.mmap.as_slice())?);
+        Ok(self.library.get_or_init(|| lib).clone())
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl std::fmt::Debug for MappedRulePack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MappedRulePack")
+            .field("len", &self.len())
+            .field("decoded", &self.library.get().is_some())
+            .finish()
+    }
+}
+
+/// 以只读内存映射方式打开规则包（mmap 特性）
+/// 仅校验魔数，不触发完整反序列化；未访问的页面不会载入内存
+/// 参数：path - 规则包路径
+/// 返回：映射的规则包 | 错误
+#[cfg(feature = "mmap")]
+pub fn map_compiled_pack(path: impl AsRef<Path>) -> RswResult<MappedRulePack> {
+    use fmmap::MmapFileExt;
+    let path = path.as_ref();
+    let mmap = fmmap::MmapFile::open(path).map_err(|e| {
+        RswappalyzerError::RuleLoadError(format!("mmap {} failed: {}", path.display(), e))
+    })?;
+    if !mmap.as_slice().starts_with(PACK_MAGIC) {
+        return Err(RswappalyzerError::RuleParseError(format!(
+            "{} is not a compiled rule pack (bad magic)",
+            path.display()
+        )));
+    }
+    Ok(MappedRulePack {
+        mmap,
+        library: std::sync::OnceLock::new(),
+    })
+}
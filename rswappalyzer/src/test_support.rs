@@ -0,0 +1,25 @@
+//! 单元测试辅助：由 Wappalyzer JSON 规则构建检测器
+
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::RuleProcessor;
+
+use crate::{DetectResult, RuleConfig, TechDetector};
+
+/// 使用默认配置构建检测器
+pub(crate) fn detector(rules: &str) -> TechDetector {
+    detector_with(rules, RuleConfig::default())
+}
+
+/// 使用指定配置构建检测器
+pub(crate) fn detector_with(rules: &str, config: RuleConfig) -> TechDetector {
+    let raw = WappalyzerParser.parse_to_rule_lib(rules).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+    TechDetector::with_rules(rules, config).unwrap()
+}
+
+/// 检测结果中的技术名（按名称排序）
+pub(crate) fn names(result: &DetectResult) -> Vec<&str> {
+    let mut names: Vec<&str> = result.technologies.iter().map(|t| t.name.as_str()).collect();
+    names.sort_unstable();
+    names
+}
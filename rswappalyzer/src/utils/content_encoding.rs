@@ -0,0 +1,166 @@
+//! Content-Encoding 响应体解压
+//! 部分调用方传入的是抓包得到的原始响应字节，仍处于 gzip/brotli/deflate 编码状态，
+//! 直接送入 HTML 输入守卫会被判为二进制内容而静默跳过；分析前按以下顺序识别并透明解压：
+//! 1. 优先采用 Content-Encoding 头声明的编码（gzip/x-gzip/deflate/br）
+//! 2. 未声明时按魔数嗅探（gzip `1f 8b`、zlib 头部校验）；brotli 无魔数，仅按声明识别
+//! 3. 解压后字节数超过上限时截断（防解压炸弹），解压失败时按原始字节继续分析
+//!
+//! 解压需启用 decompress 特性；未启用时识别到压缩内容仅输出调试日志
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+/// 响应体内容编码
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentEncoding {
+    /// 未编码
+    #[default]
+    Identity,
+    /// gzip
+    Gzip,
+    /// deflate（zlib 封装，兼容裸 deflate 流）
+    Deflate,
+    /// brotli
+    Brotli,
+}
+
+impl ContentEncoding {
+    /// 编码的稳定字符串名（与 Content-Encoding 取值一致）
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Identity => "identity",
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+            Self::Brotli => "br",
+        }
+    }
+
+    /// 按 Content-Encoding 头判断编码（多重编码取最外层）
+    /// 参数：header - Content-Encoding 头取值
+    ///
+    /// 返回：编码 | 未声明/未识别
+    pub fn from_header(header: &str) -> Option<Self> {
+        let outermost = header.rsplit(',').next()?.trim().to_ascii_lowercase();
+        match outermost.as_str() {
+            "gzip" | "x-gzip" => Some(Self::Gzip),
+            "deflate" => Some(Self::Deflate),
+            "br" => Some(Self::Brotli),
+            "identity" => Some(Self::Identity),
+            _ => None,
+        }
+    }
+
+    /// 按魔数嗅探编码（brotli 无魔数，无法嗅探）
+    /// 参数：body - 响应体字节
+    ///
+    /// 返回：编码（未识别时为 Identity）
+    pub fn sniff(body: &[u8]) -> Self {
+        match body {
+            [0x1f, 0x8b, ..] => Self::Gzip,
+            // zlib 头部：CM=8（deflate）、窗口不超过 32K、(CMF << 8 | FLG) 为 31 的倍数
+            [cmf, flg, ..] if cmf & 0x0f == 8 && cmf >> 4 <= 7 && (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 == 0 => {
+                Self::Deflate
+            }
+            _ => Self::Identity,
+        }
+    }
+
+    /// 判断响应体编码：声明优先，未声明时嗅探
+    /// 参数：
+    /// - body: 响应体字节
+    /// - header: Content-Encoding 头取值
+    ///
+    /// 返回：编码
+    pub fn detect(body: &[u8], header: Option<&str>) -> Self {
+        header
+            .and_then(Self::from_header)
+            .unwrap_or_else(|| Self::sniff(body))
+    }
+}
+
+/// 解压响应体
+/// 参数：
+/// - body: 响应体字节
+/// - content_encoding: Content-Encoding 头取值
+/// - max_len: 解压后字节数上限
+///
+/// 返回：解压后的字节（未编码/未启用 decompress 特性/解压失败时零拷贝返回原始字节）
+pub fn decompress_body<'a>(body: &'a [u8], content_encoding: Option<&str>, max_len: usize) -> Cow<'a, [u8]> {
+    let encoding = ContentEncoding::detect(body, content_encoding);
+    if encoding == ContentEncoding::Identity {
+        return Cow::Borrowed(body);
+    }
+    #[cfg(feature = "decompress")]
+    {
+        match inflate(body, encoding, max_len) {
+            Some(decoded) => Cow::Owned(decoded),
+            None => {
+                log::debug!("Body decompression failed, analyzing raw bytes | Encoding: {:?}", encoding);
+                Cow::Borrowed(body)
+            }
+        }
+    }
+    #[cfg(not(feature = "decompress"))]
+    {
+        let _ = max_len;
+        log::debug!("Compressed body left as-is (decompress feature disabled) | Encoding: {:?}", encoding);
+        Cow::Borrowed(body)
+    }
+}
+
+/// 按编码解压（最多读取 max_len 字节；数据流截断时保留已解出的部分）
+#[cfg(feature = "decompress")]
+fn inflate(body: &[u8], encoding: ContentEncoding, max_len: usize) -> Option<Vec<u8>> {
+    use std::io::Read;
+
+    let read = |reader: &mut dyn Read| -> Option<Vec<u8>> {
+        let mut out = Vec::new();
+        let result = reader.take(max_len as u64).read_to_end(&mut out);
+        if out.len() >= max_len {
+            log::debug!("Decompressed body truncated by limit | Max: {}", max_len);
+        }
+        (result.is_ok() || !out.is_empty()).then_some(out)
+    };
+    match encoding {
+        ContentEncoding::Identity => None,
+        ContentEncoding::Gzip => read(&mut flate2::read::MultiGzDecoder::new(body)),
+        // 部分服务端以裸 deflate 流响应 `deflate`，zlib 解码失败时回退
+        ContentEncoding::Deflate => read(&mut flate2::read::ZlibDecoder::new(body))
+            .or_else(|| read(&mut flate2::read::DeflateDecoder::new(body))),
+        ContentEncoding::Brotli => read(&mut brotli_decompressor::Decompressor::new(body, 4096)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_encoding() {
+        assert_eq!(ContentEncoding::from_header("GZIP"), Some(ContentEncoding::Gzip));
+        assert_eq!(ContentEncoding::from_header("deflate, br"), Some(ContentEncoding::Brotli));
+        assert_eq!(ContentEncoding::from_header("zstd"), None);
+        assert_eq!(ContentEncoding::sniff(&[0x1f, 0x8b, 0x08]), ContentEncoding::Gzip);
+        assert_eq!(ContentEncoding::sniff(&[0x78, 0x9c]), ContentEncoding::Deflate);
+        assert_eq!(ContentEncoding::sniff(b"<html>"), ContentEncoding::Identity);
+        assert!(matches!(decompress_body(b"<html>", None, 1024), Cow::Borrowed(_)));
+    }
+
+    #[cfg(feature = "decompress")]
+    #[test]
+    fn test_decompress_body() {
+        use std::io::Write;
+
+        let html = b"<html><head><title>gzip</title></head></html>";
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(html).unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        assert_eq!(decompress_body(&gzipped, None, 1024).as_ref(), html);
+        assert_eq!(decompress_body(&gzipped, Some("gzip"), 10).as_ref(), &html[..10]);
+        // 声明与内容不符时按原始字节分析
+        assert_eq!(decompress_body(html, Some("br"), 1024).as_ref(), html);
+    }
+}
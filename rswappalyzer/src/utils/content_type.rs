@@ -0,0 +1,311 @@
+//! Content-Type 响应体路由
+//! 按 Content-Type 判断响应体类型，将响应体送入对应的分析器族（爬虫抓取的 JS/CSS/二进制资源不再按 HTML 分析）：
+//! 1. Html：HTML/XHTML 及未声明/未识别类型（保持原有行为：输入守卫 + HTML/Script/Meta 分析）
+//! 2. Script：JavaScript 资源，整个响应体作为脚本内容送入 Script 分析器
+//! 3. Css：样式表，规则库无独立 CSS 作用域，响应体作为纯文本送入 HTML 内容模式（不做 HTML 提取）
+//! 4. Binary：图片/字体/音视频等二进制资源，跳过响应体分析
+//!
+//! 同时按 charset 解码响应体（Content-Type 参数优先，其次为文档头部 `<meta>` 声明），字节为合法 UTF-8 时始终按 UTF-8 零拷贝处理：
+//! 1. 开启 encoding 特性时，GBK/GB2312/Shift_JIS/EUC-KR 等非 UTF-8 编码经 encoding_rs 转码
+//! 2. 声明为 Latin-1 族时逐字节解码
+//! 3. 其余按 UTF-8 容错解码
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+/// 响应体类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyKind {
+    /// HTML 文档（含未声明/未识别类型）
+    #[default]
+    Html,
+    /// JavaScript 脚本
+    Script,
+    /// CSS 样式表
+    Css,
+    /// 二进制资源
+    Binary,
+}
+
+impl BodyKind {
+    /// 按 Content-Type 判断响应体类型
+    /// 参数：content_type - Content-Type 头取值（None 表示未声明）
+    ///
+    /// 返回：响应体类型
+    pub fn from_content_type(content_type: Option<&str>) -> Self {
+        let Some(content_type) = content_type else {
+            return Self::Html;
+        };
+        let mime = mime_type(content_type);
+        match mime.as_str() {
+            "application/javascript"
+            | "application/x-javascript"
+            | "application/ecmascript"
+            | "text/javascript"
+            | "text/ecmascript"
+            | "text/jscript" => Self::Script,
+            "text/css" => Self::Css,
+            "application/octet-stream" | "application/pdf" | "application/zip" | "application/wasm" => {
+                Self::Binary
+            }
+            _ if ["image/", "font/", "audio/", "video/"].iter().any(|p| mime.starts_with(p)) => {
+                // SVG 为 XML 文本，可能内嵌脚本，按 HTML 处理
+                if mime == "image/svg+xml" {
+                    Self::Html
+                } else {
+                    Self::Binary
+                }
+            }
+            _ => Self::Html,
+        }
+    }
+}
+
+/// 提取 MIME 类型（去除参数，小写）
+fn mime_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// 提取 charset 参数（小写，去除引号）
+/// 参数：content_type - Content-Type 头取值
+///
+/// 返回：charset | 未声明
+pub fn charset(content_type: &str) -> Option<String> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("charset")
+            .then(|| value.trim().trim_matches(|c| c == '"' || c == '\'').to_ascii_lowercase())
+    })
+}
+
+/// `<meta>` charset 声明的嗅探范围（与 HTML 规范的预扫描长度一致）
+const META_SNIFF_LEN: usize = 1024;
+
+/// 从文档头部的 `<meta charset>` / `<meta http-equiv="Content-Type" content="...; charset=...">` 嗅探 charset
+/// 参数：body - 响应体字节
+///
+/// 返回：charset（小写）| 未声明
+pub fn sniff_meta_charset(body: &[u8]) -> Option<String> {
+    let head = body[..body.len().min(META_SNIFF_LEN)].to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(offset) = find(&head[from..], b"<meta") {
+        let start = from + offset + b"<meta".len();
+        let end = head[start..].iter().position(|&b| b == b'>').map_or(head.len(), |p| start + p);
+        let tag = &head[start..end];
+        if let Some(pos) = find(tag, b"charset") {
+            let value = tag[pos + b"charset".len()..]
+                .trim_ascii_start()
+                .strip_prefix(b"=")
+                .map(|rest| rest.trim_ascii_start());
+            if let Some(value) = value {
+                let value = value.strip_prefix(b"\"").or_else(|| value.strip_prefix(b"'")).unwrap_or(value);
+                let len = value
+                    .iter()
+                    .position(|&b| matches!(b, b'"' | b'\'' | b';' | b'/') || b.is_ascii_whitespace())
+                    .unwrap_or(value.len());
+                if len > 0 {
+                    return Some(String::from_utf8_lossy(&value[..len]).into_owned());
+                }
+            }
+        }
+        from = end;
+    }
+    None
+}
+
+/// 查找子串
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// 按 charset 解码响应体
+/// 参数：
+/// - body: 响应体字节
+/// - content_type: Content-Type 头取值
+///
+/// 返回：解码后的文本（UTF-8 合法时零拷贝）
+pub fn decode_body<'a>(body: &'a [u8], content_type: Option<&str>) -> Cow<'a, str> {
+    decode_body_checked(body, content_type).0
+}
+
+/// 按 charset 解码响应体，并报告是否发生了容错解码
+/// 参数：
+/// - body: 响应体字节
+/// - content_type: Content-Type 头取值
+///
+/// 返回：(解码后的文本, 是否含非法 UTF-8 字节并被替换为 U+FFFD)
+pub fn decode_body_checked<'a>(body: &'a [u8], content_type: Option<&str>) -> (Cow<'a, str>, bool) {
+    transcode_body(body, content_type).unwrap_or_else(|| (String::from_utf8_lossy(body), true))
+}
+
+/// 按 charset 将响应体转为 UTF-8（不做容错解码）
+/// 参数：
+/// - body: 响应体字节
+/// - content_type: Content-Type 头取值
+///
+/// 返回：Some((文本, 转码是否有替换字符)) | None（非合法 UTF-8 且未声明可识别的 charset）
+fn transcode_body<'a>(body: &'a [u8], content_type: Option<&str>) -> Option<(Cow<'a, str>, bool)> {
+    if let Ok(text) = std::str::from_utf8(body) {
+        return Some((Cow::Borrowed(text), false));
+    }
+    let declared = content_type.and_then(charset).or_else(|| sniff_meta_charset(body));
+    #[cfg(feature = "encoding")]
+    if let Some(encoding) = declared.as_deref().and_then(|cs| encoding_rs::Encoding::for_label(cs.as_bytes())) {
+        if encoding != encoding_rs::UTF_8 {
+            let (text, lossy) = encoding.decode_without_bom_handling(body);
+            return Some((Cow::Owned(text.into_owned()), lossy));
+        }
+    }
+    let latin1 = declared.is_some_and(|cs| {
+        matches!(
+            cs.as_str(),
+            "iso-8859-1" | "latin1" | "latin-1" | "l1" | "iso8859-1" | "windows-1252" | "cp1252"
+        )
+    });
+    latin1.then(|| (Cow::Owned(body.iter().map(|&b| char::from(b)).collect()), false))
+}
+
+/// 按 charset 解码（可能已解压的）响应体，并报告是否发生了容错解码
+/// 参数：
+/// - body: 响应体字节（借用原始输入或解压后的缓冲区）
+/// - content_type: Content-Type 头取值
+///
+/// 返回：(解码后的文本, 是否含非法 UTF-8 字节并被替换为 U+FFFD)；UTF-8 合法时借用输入或直接复用缓冲区
+pub fn decode_body_bytes<'a>(body: Cow<'a, [u8]>, content_type: Option<&str>) -> (Cow<'a, str>, bool) {
+    match body {
+        Cow::Borrowed(body) => decode_body_checked(body, content_type),
+        Cow::Owned(body) => match String::from_utf8(body) {
+            Ok(text) => (Cow::Owned(text), false),
+            Err(e) => {
+                let (text, lossy) = decode_body_checked(e.as_bytes(), content_type);
+                (Cow::Owned(text.into_owned()), lossy)
+            }
+        },
+    }
+}
+
+/// 按 charset 转码脚本响应体，保留字节形式（供 regex::bytes 匹配）
+/// 非合法 UTF-8 且未声明可识别的 charset 时原样返回字节，不做容错解码
+/// 参数：
+/// - body: 响应体字节（借用原始输入或解压后的缓冲区）
+/// - content_type: Content-Type 头取值
+///
+/// 返回：(UTF-8 或原始字节, 转码是否有替换字符)；UTF-8 合法时直接复用输入
+pub fn transcode_body_bytes<'a>(body: Cow<'a, [u8]>, content_type: Option<&str>) -> (Cow<'a, [u8]>, bool) {
+    if std::str::from_utf8(&body).is_ok() {
+        return (body, false);
+    }
+    let transcoded = transcode_body(&body, content_type).map(|(text, lossy)| (text.into_owned().into_bytes(), lossy));
+    match transcoded {
+        Some((bytes, lossy)) => (Cow::Owned(bytes), lossy),
+        None => (body, false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_body_kind_and_charset() {
+        assert_eq!(BodyKind::from_content_type(None), BodyKind::Html);
+        assert_eq!(BodyKind::from_content_type(Some("text/html; charset=utf-8")), BodyKind::Html);
+        assert_eq!(
+            BodyKind::from_content_type(Some("Application/JavaScript;charset=UTF-8")),
+            BodyKind::Script
+        );
+        assert_eq!(BodyKind::from_content_type(Some("text/css")), BodyKind::Css);
+        assert_eq!(BodyKind::from_content_type(Some("image/png")), BodyKind::Binary);
+        assert_eq!(BodyKind::from_content_type(Some("image/svg+xml")), BodyKind::Html);
+
+        assert_eq!(charset("text/html; Charset=\"ISO-8859-1\"").as_deref(), Some("iso-8859-1"));
+        assert_eq!(decode_body(b"caf\xe9", Some("text/html; charset=latin1")), "café");
+        assert_eq!(decode_body("café".as_bytes(), Some("text/html; charset=latin1")), "café");
+        assert_eq!(decode_body_bytes(Cow::Owned(b"caf\xe9".to_vec()), None), (Cow::Borrowed("caf\u{fffd}"), true));
+        assert_eq!(transcode_body_bytes(Cow::Borrowed(b"caf\xe9"), None), (Cow::Borrowed(&b"caf\xe9"[..]), false));
+        assert_eq!(
+            transcode_body_bytes(Cow::Borrowed(b"caf\xe9"), Some("text/javascript; charset=latin1")),
+            (Cow::Borrowed("café".as_bytes()), false)
+        );
+        assert_eq!(
+            sniff_meta_charset(br#"<html><head><META HTTP-EQUIV="Content-Type" content="text/html; charset=GB2312">"#).as_deref(),
+            Some("gb2312")
+        );
+        assert_eq!(sniff_meta_charset(b"<meta charset='shift_jis'/>").as_deref(), Some("shift_jis"));
+        assert_eq!(decode_body(b"<meta charset=latin1>caf\xe9", None), "<meta charset=latin1>café");
+    }
+
+    #[cfg(feature = "encoding")]
+    fn encode(encoding: &'static encoding_rs::Encoding, html: &str) -> Vec<u8> {
+        let (bytes, _, unmappable) = encoding.encode(html);
+        assert!(!unmappable);
+        bytes.into_owned()
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn test_decode_non_utf8_charsets() {
+        let html = r#"<html><head><meta charset="gbk"><title>致远协同管理软件</title></head></html>"#;
+        let body = encode(encoding_rs::GBK, html);
+        assert!(std::str::from_utf8(&body).is_err());
+        // 文档头部 meta 声明的编码
+        assert_eq!(decode_body_checked(&body, None), (Cow::Borrowed(html), false));
+
+        let body = encode(encoding_rs::SHIFT_JIS, "<title>管理システム</title>");
+        let (text, lossy) = decode_body_checked(&body, Some("text/html; charset=Shift_JIS"));
+        assert_eq!((text.as_ref(), lossy), ("<title>管理システム</title>", false));
+        // Content-Type 参数优先于 meta 声明
+        let mut declared = b"<meta charset=gbk>".to_vec();
+        declared.extend_from_slice(&body);
+        let text = decode_body(&declared, Some("text/html; charset=shift_jis"));
+        assert_eq!(text, "<meta charset=gbk><title>管理システム</title>");
+        // 未声明 charset 时按 UTF-8 容错解码
+        let (text, lossy) = decode_body_checked(&body, None);
+        assert!(lossy && text.contains('\u{FFFD}'));
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn test_transcoded_title_detected() {
+        use http::header::{HeaderMap, HeaderValue};
+
+        use crate::test_support::{detector, names};
+        use crate::DetectWarning;
+
+        let detector = detector(
+            r#"{
+                "technologies": {
+                    "Zhiyuan OA": { "cats": [1], "title": "致远协同管理软件" },
+                    "Kanri CMS": { "cats": [1], "title": "管理システム" }
+                }
+            }"#,
+        );
+        let body = encode(
+            encoding_rs::GBK,
+            r#"<html><head><meta http-equiv="Content-Type" content="text/html; charset=gbk"><title>致远协同管理软件</title></head><body>登录</body></html>"#,
+        );
+        let result = detector.detect(&HeaderMap::new(), &[], &body).unwrap();
+        assert_eq!(names(&result), ["Zhiyuan OA"]);
+        assert!(result.warnings.is_empty());
+
+        let body = encode(
+            encoding_rs::SHIFT_JIS,
+            "<html><head><title>管理システム</title></head><body>ようこそ</body></html>",
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", HeaderValue::from_static("text/html; charset=Shift_JIS"));
+        assert_eq!(names(&detector.detect(&headers, &[], &body).unwrap()), ["Kanri CMS"]);
+
+        let result = detector.detect(&HeaderMap::new(), &[], &body).unwrap();
+        assert!(result.technologies.is_empty());
+        assert_eq!(result.warnings, [DetectWarning::BodyDecodeFallback("invalid utf-8".to_string())]);
+    }
+}
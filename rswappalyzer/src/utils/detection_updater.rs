@@ -1,5 +1,8 @@
 //! 检测结果更新工具
 use rswappalyzer_engine::CompiledRuleLibrary;
+use crate::config::generator::GeneratorPolicy;
+use crate::config::implies::ImplyPolicy;
+use crate::utils::matched_scopes;
 use rustc_hash::{FxHashMap, FxHashSet};
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
@@ -26,9 +29,10 @@ impl DetectionUpdater {
         const NO_VERSION_CONF: u8 = 85;
         let new_conf = match &new_version {
             Some(_) => raw_conf,    // 有版本号 → 置信度不变（如100）
-            None => NO_VERSION_CONF, // 无版本号 → 强制降级为指定值
+            None => raw_conf.min(NO_VERSION_CONF), // 无版本号 → 降级为指定值（已降权的模式保持更低值）
         };
 
+        matched_scopes::mark(tech_name);
         match detected.entry(tech_name.to_string()) {
             Entry::Occupied(mut entry) => {
                 let (old_conf, old_version) = entry.get_mut();
@@ -46,59 +50,220 @@ impl DetectionUpdater {
         }
     }
 
+    /// 合并 Meta generator 命中结果（需在其它作用域分析完成后、关联推导前调用）
+    /// 已被其它作用域检出的技术视为已佐证，按常规规则合并；
+    /// 未佐证的技术置信度不超过策略上限（支持按技术覆盖）
+    /// 参数：
+    /// - detected: 检测结果（输入输出参数）
+    /// - generator_hits: 仅由 generator 元标签得到的命中结果
+    /// - policy: generator 防伪策略
+    pub fn merge_generator_hits<S: BuildHasher>(
+        detected: &mut HashMap<String, (u8, Option<String>), S>,
+        generator_hits: FxHashMap<String, (u8, Option<String>)>,
+        policy: &GeneratorPolicy,
+    ) {
+        for (tech_name, (confidence, version)) in generator_hits {
+            match detected.entry(tech_name) {
+                Entry::Occupied(entry) => {
+                    let tech_name = entry.key().clone();
+                    Self::update(detected, &tech_name, Some(confidence), version);
+                }
+                Entry::Vacant(entry) => {
+                    let capped = confidence.min(policy.cap_for(entry.key()));
+                    entry.insert((capped, version));
+                }
+            }
+        }
+    }
+
     // apply_implies 多来源支持 + 置信度加权
     // 返回值：FxHashMap<String, Vec<String>> → 推导技术名: [来源1, 来源2...]
     pub fn apply_implies<S: BuildHasher>(
         compiled_lib: &CompiledRuleLibrary,
         detected: &mut HashMap<String, (u8, Option<String>), S>,
     ) -> FxHashMap<String, Vec<String>> {
-        // 推导技术名 → 所有来源技术名（自动去重，支持多来源）
-        let mut imply_source_map: FxHashMap<String, FxHashSet<String>> = FxHashMap::default();
+        Self::apply_implies_excluding(compiled_lib, detected, &FxHashSet::default())
+    }
+
+    /// 关联推导（排除指定来源技术）
+    /// 适用场景：仅由 CDN 脚本命中的技术不应推导出源站技术
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - detected: 检测结果（输入输出参数）
+    /// - excluded_sources: 不参与推导的来源技术
+    ///
+    /// 返回：推导技术名 → 来源列表
+    pub fn apply_implies_excluding<S: BuildHasher>(
+        compiled_lib: &CompiledRuleLibrary,
+        detected: &mut HashMap<String, (u8, Option<String>), S>,
+        excluded_sources: &FxHashSet<String>,
+    ) -> FxHashMap<String, Vec<String>> {
+        Self::apply_implies_with_policy(compiled_lib, detected, excluded_sources, &ImplyPolicy::default())
+    }
+
+    /// 按推导策略做多跳关联推导
+    /// 第 1 跳由真实命中的技术推导，之后每跳由上一跳新推导出的技术继续推导，
+    /// 置信度按跳数衰减，达到最大深度或无新技术时停止
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - detected: 检测结果（输入输出参数）
+    /// - excluded_sources: 不参与推导的来源技术
+    /// - policy: 推导策略（深度/衰减）
+    ///
+    /// 返回：推导技术名 → 直接来源列表
+    pub fn apply_implies_with_policy<S: BuildHasher>(
+        compiled_lib: &CompiledRuleLibrary,
+        detected: &mut HashMap<String, (u8, Option<String>), S>,
+        excluded_sources: &FxHashSet<String>,
+        policy: &ImplyPolicy,
+    ) -> FxHashMap<String, Vec<String>> {
         // 推导技术的基础置信度 & 加权配置
         const BASE_IMPLY_CONF: u8 = 90;
         const MAX_IMPLY_CONF: u8 = 95;
         const BOOST_PER_SOURCE: u8 = 3;
 
-        // 1. 遍历所有真实匹配的技术，收集多来源推导关系
-        for source_tech_name in detected.keys() {
-            if let Some(compiled_tech) = compiled_lib.tech_patterns.get(source_tech_name) {
+        let mut imply_map = FxHashMap::default();
+        // 当前跳的来源技术（第 1 跳为所有真实匹配的技术）
+        let mut frontier: Vec<String> = detected
+            .keys()
+            .filter(|name| !excluded_sources.contains(*name))
+            .cloned()
+            .collect();
+
+        for depth in 1..=policy.max_depth {
+            // 1. 收集本跳的多来源推导关系：推导技术名 → 所有来源技术名（自动去重）
+            let mut imply_source_map: FxHashMap<String, FxHashSet<String>> = FxHashMap::default();
+            for source_tech_name in &frontier {
+                let Some(compiled_tech) = compiled_lib.tech_patterns.get(source_tech_name) else {
+                    continue;
+                };
                 for target_tech_name in &compiled_tech.implies {
                     let target_tech_name = target_tech_name.trim();
-                    // 过滤无效值：空字符串/目标技术不存在/目标已被真实匹配
-                    if target_tech_name.is_empty() 
+                    // 过滤无效值：空字符串/目标技术不存在/目标已被匹配或推导
+                    if target_tech_name.is_empty()
                         || !compiled_lib.tech_patterns.contains_key(target_tech_name)
                         || detected.contains_key(target_tech_name)
                     {
                         continue;
                     }
-                    // 多来源收集
                     imply_source_map
                         .entry(target_tech_name.to_string())
-                        .or_insert_with(FxHashSet::default)
-                        .insert(source_tech_name.to_string());
+                        .or_default()
+                        .insert(source_tech_name.clone());
                 }
             }
+            if imply_source_map.is_empty() {
+                break;
+            }
+
+            // 2. 写入detected：来源越多置信度越高（不超过MAX_IMPLY_CONF），再按跳数衰减
+            frontier = Vec::with_capacity(imply_source_map.len());
+            for (target_tech, source_set) in imply_source_map {
+                let source_count = u8::try_from(source_set.len()).unwrap_or(u8::MAX);
+                let boost = std::cmp::min(
+                    source_count.saturating_mul(BOOST_PER_SOURCE),
+                    MAX_IMPLY_CONF - BASE_IMPLY_CONF,
+                );
+                let final_conf = policy.confidence_at(BASE_IMPLY_CONF + boost, depth);
+                // 推导技术天然无版本
+                detected.entry(target_tech.clone()).or_insert((final_conf, None));
+
+                // 3. HashSet转Vec，记录【推导技术→来源列表】
+                let mut source_vec = source_set.into_iter().collect::<Vec<_>>();
+                source_vec.sort_unstable();
+                imply_map.insert(target_tech.clone(), source_vec);
+                frontier.push(target_tech);
+            }
         }
 
-        // 2. 把推导技术写入detected，并根据来源数量做置信度加权
-        for (target_tech, source_set) in &imply_source_map {
-            let source_count = source_set.len() as u8;
-            // 置信度加权：来源越多，置信度越高，最高不超过MAX_IMPLY_CONF
-            let boost = std::cmp::min(source_count * BOOST_PER_SOURCE, MAX_IMPLY_CONF - BASE_IMPLY_CONF);
-            let final_conf = BASE_IMPLY_CONF + boost;
-            // 写入detected，版本为None - 推导技术天然无版本，此处逻辑不变
-            detected.entry(target_tech.clone()).or_insert((final_conf, None));
+        imply_map
+    }
+
+    /// 按推导后的结果应用依赖规则与关联推导（仅经 implies 链检出的父技术同样满足依赖）
+    /// 直接命中的技术依赖未满足时移除，并从推导前的结果重新推导（撤销其推导出的技术），直至结果稳定；
+    /// 推导新增的技术不做依赖校验
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - detected: 检测结果（输入输出参数）
+    /// - excluded_sources: 不参与推导的来源技术
+    /// - policy: 推导策略（深度/衰减）
+    ///
+    /// 返回：(被移除的技术名（按名称排序）, 推导技术名 → 直接来源列表)
+    pub fn apply_requires_with_implies<S: BuildHasher + Clone>(
+        compiled_lib: &CompiledRuleLibrary,
+        detected: &mut HashMap<String, (u8, Option<String>), S>,
+        excluded_sources: &FxHashSet<String>,
+        policy: &ImplyPolicy,
+    ) -> (Vec<String>, FxHashMap<String, Vec<String>>) {
+        let observed = detected.clone();
+        let mut removed: Vec<String> = Vec::new();
+        loop {
+            let imply_map = Self::apply_implies_with_policy(compiled_lib, detected, excluded_sources, policy);
+            let unmet: Vec<String> = observed
+                .keys()
+                .filter(|name| detected.contains_key(*name))
+                .filter(|name| !Self::requirement_met(compiled_lib, name, detected))
+                .cloned()
+                .collect();
+            if unmet.is_empty() {
+                removed.sort_unstable();
+                return (removed, imply_map);
+            }
+            removed.extend(unmet);
+            detected.clone_from(&observed);
+            detected.retain(|name, _| !removed.contains(name));
         }
+    }
 
-        // 3. HashSet转Vec，返回标准的【推导技术→来源列表】映射表
-        let mut imply_map = FxHashMap::default();
-        for (k, v) in imply_source_map {
-            let mut source_vec = v.into_iter().collect::<Vec<_>>();
-            source_vec.sort_unstable();
-            imply_map.insert(k, source_vec);
+    /// 技术的依赖是否满足（未声明依赖时视为满足；任一依赖技术或任一依赖分类下的其它技术检出即满足）
+    fn requirement_met<S: BuildHasher>(
+        compiled_lib: &CompiledRuleLibrary,
+        name: &str,
+        detected: &HashMap<String, (u8, Option<String>), S>,
+    ) -> bool {
+        let Some(tech) = compiled_lib.tech_patterns.get(name) else {
+            return true;
+        };
+        if tech.requires.is_empty() && tech.requires_category.is_empty() {
+            return true;
         }
+        let tech_met = tech
+            .requires
+            .iter()
+            .any(|required| required.trim() != name && detected.contains_key(required.trim()));
+        tech_met
+            || (!tech.requires_category.is_empty()
+                && detected.keys().any(|other| {
+                    other != name
+                        && compiled_lib.tech_patterns.get(other).is_some_and(|other| {
+                            other.category_ids.iter().any(|id| tech.requires_category.contains(id))
+                        })
+                }))
+    }
 
-        imply_map
+    /// 应用互斥规则（需在关联推导后调用）：移除被任一已检出技术声明为互斥的技术
+    /// 互斥关系按移除前的检测结果计算，相互互斥的技术同时移除
+    /// 参数：
+    /// - compiled_lib: 编译后的规则库
+    /// - detected: 检测结果（输入输出参数）
+    ///
+    /// 返回：被移除的技术名（按名称排序）
+    pub fn apply_excludes<S: BuildHasher>(
+        compiled_lib: &CompiledRuleLibrary,
+        detected: &mut HashMap<String, (u8, Option<String>), S>,
+    ) -> Vec<String> {
+        let excluded: FxHashSet<&str> = detected
+            .keys()
+            .filter_map(|name| compiled_lib.tech_patterns.get(name))
+            .flat_map(|tech| tech.excludes.iter().map(|name| name.trim()))
+            .filter(|name| detected.contains_key(*name))
+            .collect();
+        let mut removed: Vec<String> = excluded.into_iter().map(str::to_string).collect();
+        for name in &removed {
+            detected.remove(name);
+        }
+        removed.sort_unstable();
+        removed
     }
     
     /// 辅助函数：判断新结果是否比旧结果更优
@@ -123,4 +288,66 @@ impl DetectionUpdater {
         }
         false
     }
-}
\ No newline at end of file
+}
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::HeaderMap;
+
+    use super::*;
+    use crate::test_support::{detector, names};
+
+    const EXCLUDES_RULES: &str = r#"{
+        "technologies": {
+            "WordPress": { "cats": [1], "html": ["wp-content"], "excludes": "Blogger", "implies": "PHP" },
+            "Blogger": { "cats": [11], "html": ["blogger-widget"] },
+            "PHP": { "cats": [27], "headers": { "X-Powered-By": "PHP" } },
+            "Hugo": { "cats": [57], "html": ["hugo-static"], "excludes": ["PHP", "Missing"] },
+            "Alpha": { "cats": [19], "html": ["alpha"], "excludes": "Beta" },
+            "Beta": { "cats": [19], "html": ["beta"], "excludes": "Alpha" }
+        }
+    }"#;
+
+    fn detected(names: &[&str]) -> FxHashMap<String, (u8, Option<String>)> {
+        names.iter().map(|name| (name.to_string(), (100, None))).collect()
+    }
+
+    #[test]
+    fn test_excludes_compiled() {
+        let detector = detector(EXCLUDES_RULES);
+        let lib = detector.compiled_library();
+        assert_eq!(lib.tech_patterns["WordPress"].excludes, ["Blogger"]);
+        assert_eq!(lib.tech_patterns["Hugo"].excludes, ["PHP", "Missing"]);
+        assert!(lib.tech_patterns["Blogger"].excludes.is_empty());
+    }
+
+    #[test]
+    fn test_apply_excludes() {
+        let detector = detector(EXCLUDES_RULES);
+        let lib = detector.compiled_library();
+
+        let mut found = detected(&["WordPress", "Blogger", "PHP"]);
+        assert_eq!(DetectionUpdater::apply_excludes(lib, &mut found), ["Blogger"]);
+        assert!(found.contains_key("WordPress") && found.contains_key("PHP"));
+
+        // 未检出的互斥目标忽略，不影响其余结果
+        let mut found = detected(&["Hugo"]);
+        assert!(DetectionUpdater::apply_excludes(lib, &mut found).is_empty());
+        assert_eq!(found.len(), 1);
+
+        // 互斥关系按移除前的结果计算：相互互斥的技术同时移除
+        let mut found = detected(&["Alpha", "Beta", "PHP"]);
+        assert_eq!(DetectionUpdater::apply_excludes(lib, &mut found), ["Alpha", "Beta"]);
+        assert_eq!(found.keys().collect::<Vec<_>>(), ["PHP"]);
+    }
+
+    #[test]
+    fn test_excludes_after_implies() {
+        let detector = detector(EXCLUDES_RULES);
+        let detect = |html: &str| detector.detect(&HeaderMap::new(), &[], html.as_bytes()).unwrap();
+        assert_eq!(names(&detect("<html>wp-content blogger-widget</html>")), ["PHP", "WordPress"]);
+        // 推导出的技术同样可被移除
+        assert_eq!(names(&detect("<html>wp-content hugo-static</html>")), ["Hugo", "WordPress"]);
+        assert!(detect("<html>alpha beta</html>").technologies.is_empty());
+    }
+}
@@ -1,34 +1,106 @@
 //! HTML 标签提取器
-//! 负责从 HTML 中提取 <script src> 和 <meta> 标签
-use lol_html::{element, HtmlRewriter, Settings};
+//! 负责从 HTML 中提取 <script src>、内联脚本、<meta>（name/property/http-equiv）标签与页面标题
+use super::html_input_guard::SanitizeStats;
+use lol_html::{doc_comments, element, text, HtmlRewriter, Settings};
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 
-#[derive(Debug)]
-struct Shared<T>(*mut T);
+/// meta 键名属性（按优先级）
+pub(super) const META_KEY_ATTRS: [&str; 3] = ["name", "property", "http-equiv"];
 
-impl<T> Shared<T> {
-    /// 创建新的共享容器，初始化裸指针
-    fn new(value: T) -> Self {
-        Self(Box::into_raw(Box::new(value)))
-    }
-
-    /// 获取可变引用，单线程下安全，零运行时开销
-    fn get_mut(&self) -> &mut T {
-        unsafe { &mut *self.0 }
-    }
+/// 提取上限配置
+/// 超大/畸形页面可能包含海量脚本与元标签，上限保证提取耗时与内存可控
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExtractLimits {
+    /// 最多采集的 script src 数量
+    pub max_scripts: usize,
+    /// 最多采集的 meta 标签数量
+    pub max_meta_tags: usize,
+    /// 最多采集的 HTML 注释总字节数（0 表示不采集注释，Comment 作用域随之失效）
+    pub max_comment_bytes: usize,
+    /// 最多采集的内联脚本总字节数（0 表示不采集内联脚本）
+    pub max_inline_script_bytes: usize,
+    /// 单个 script src 最大长度（超出视为非法）
+    pub max_script_src_len: usize,
+    /// 单个 meta content 最大长度（超出视为非法）
+    pub max_meta_content_len: usize,
+    /// 页面标题最大字节数（超出部分截断）
+    pub max_title_len: usize,
+    /// 单个属性值最大长度（超出部分在分析前截短）
+    pub max_attribute_len: usize,
+    /// 最大标签嵌套深度（超出时在该标签处截断文档）
+    pub max_tag_depth: usize,
+    /// 最多扫描的标签总数（超出时在该标签处截断文档）
+    pub max_tags: usize,
+}
 
-    /// 取出内部所有权，零拷贝，无内存泄漏，释放堆内存
-    fn into_inner(self) -> T {
-        unsafe { *Box::from_raw(self.0) }
+impl Default for ExtractLimits {
+    fn default() -> Self {
+        Self {
+            max_scripts: 1024,
+            max_meta_tags: 512,
+            max_comment_bytes: 64 * 1024,
+            max_inline_script_bytes: 256 * 1024,
+            max_script_src_len: 2048,
+            max_meta_content_len: 4096,
+            max_title_len: 1024,
+            max_attribute_len: 64 * 1024,
+            max_tag_depth: 1024,
+            max_tags: 100_000,
+        }
     }
 }
 
-/// 单线程场景下安全实现Sync，无并发竞争风险
-unsafe impl<T> Sync for Shared<T> {}
+/// 提取统计信息
+/// 用于判断提取上限是否影响检测准确性（truncated 为 true 时存在被丢弃的内容）
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractStats {
+    /// 遇到的 script src 总数
+    pub scripts_seen: usize,
+    /// 采集的 script src 数量
+    pub scripts_captured: usize,
+    /// 因非法（空/超长/含控制字符）被丢弃的 script src 数量
+    pub scripts_rejected: usize,
+    /// 遇到的 meta 标签总数（含 name 与 content）
+    pub meta_seen: usize,
+    /// 采集的 meta 标签数量
+    pub meta_captured: usize,
+    /// 因 content 超长被丢弃的 meta 标签数量
+    pub meta_rejected: usize,
+    /// 遇到的注释总字节数（仅在启用注释采集时统计）
+    pub comment_bytes_seen: usize,
+    /// 采集的注释字节数
+    pub comment_bytes_captured: usize,
+    /// script src 是否因数量上限被截断
+    pub scripts_truncated: bool,
+    /// meta 标签是否因数量上限被截断
+    pub meta_truncated: bool,
+    /// 注释是否因字节上限被截断
+    pub comments_truncated: bool,
+    /// 遇到的内联脚本总字节数（仅在启用内联脚本采集时统计）
+    #[serde(default)]
+    pub inline_script_bytes_seen: usize,
+    /// 采集的内联脚本字节数
+    #[serde(default)]
+    pub inline_script_bytes_captured: usize,
+    /// 内联脚本是否因字节上限被截断
+    #[serde(default)]
+    pub inline_scripts_truncated: bool,
+    /// 对抗性输入清理统计（超长属性值/标签总数/嵌套深度）
+    #[serde(default)]
+    pub sanitize: SanitizeStats,
+}
 
-/// 零开销Clone实现，解决move闭包所有权问题
-impl<T> Clone for Shared<T> {
-    fn clone(&self) -> Self {
-        Self(self.0)
+impl ExtractStats {
+    /// 是否有任何内容因上限被截断
+    pub fn is_truncated(&self) -> bool {
+        self.scripts_truncated
+            || self.meta_truncated
+            || self.comments_truncated
+            || self.inline_scripts_truncated
+            || self.sanitize.is_truncated()
     }
 }
 
@@ -37,52 +109,137 @@ impl<T> Clone for Shared<T> {
 pub struct ExtractResult {
     pub script_srcs: Vec<String>,
     pub script_src_combined: String,
+    /// meta 标签（键名取自 name/property/http-equiv 并转小写，值为 content）
     pub meta_tags: Vec<(String, String)>,
+    /// HTML 注释内容（不含 `<!--`/`-->`，每条以换行分隔，仅在 max_comment_bytes > 0 时采集）
+    pub comments: String,
+    /// 内联 `<script>` 内容（每段脚本以换行分隔，受 max_inline_script_bytes 限制）
+    pub inline_scripts: String,
+    /// 页面标题（首个 `<title>` 的文本，解码常见实体并折叠空白；无标题时为空）
+    pub title: String,
+    /// 提取统计信息
+    pub stats: ExtractStats,
 }
 
 impl ExtractResult {
     /// 内部初始化，预分配内存，减少扩容开销
-    fn new() -> Self {
+    pub(super) fn new() -> Self {
         Self {
             script_srcs: Vec::with_capacity(16),
             script_src_combined: String::with_capacity(2048),
             meta_tags: Vec::with_capacity(8),
+            comments: String::new(),
+            inline_scripts: String::new(),
+            title: String::new(),
+            stats: ExtractStats::default(),
+        }
+    }
+
+    /// 追加标题文本片段（按字符边界截断到上限）
+    pub(super) fn push_title_chunk(&mut self, text: &str, limits: &ExtractLimits) {
+        let remaining = limits.max_title_len.saturating_sub(self.title.len());
+        let mut end = text.len().min(remaining);
+        while !text.is_char_boundary(end) {
+            end -= 1;
         }
+        self.title.push_str(&text[..end]);
+    }
+
+    /// 标题结束：解码常见实体并折叠空白
+    pub(super) fn finish_title(&mut self) {
+        let decoded = self
+            .title
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&nbsp;", " ")
+            .replace("&amp;", "&");
+        self.title = decoded.split_whitespace().collect::<Vec<_>>().join(" ");
     }
 
     /// 单次堆分配，减少50%内存开销
-    fn push_script_src(&mut self, src: &str) {
+    pub(super) fn push_script_src(&mut self, src: &str, limits: &ExtractLimits) {
+        self.stats.scripts_seen += 1;
         if src.is_empty()
-            || src.len() > 2048
+            || src.len() > limits.max_script_src_len
             || src.contains('<')
             || src.contains('>')
             || src.contains('\n')
             || src.contains('\r')
         {
+            self.stats.scripts_rejected += 1;
+            return;
+        }
+        if self.script_srcs.len() >= limits.max_scripts {
+            self.stats.scripts_truncated = true;
             return;
         }
         self.script_srcs.push(src.to_owned());
         self.script_src_combined.push_str(src);
         self.script_src_combined.push('\n');
+        self.stats.scripts_captured += 1;
     }
 
-    fn push_meta_tag(&mut self, name: &str, content: String) {
-        if content.len() <= 4096 {
-            // 纯ASCII小写，替代Unicode的to_lowercase()，速度提升10~100倍
-            self.meta_tags.push((ascii_lowercase(name), content));
+    pub(super) fn push_meta_tag(&mut self, name: &str, content: String, limits: &ExtractLimits) {
+        self.stats.meta_seen += 1;
+        if content.len() > limits.max_meta_content_len {
+            self.stats.meta_rejected += 1;
+            return;
+        }
+        if self.meta_tags.len() >= limits.max_meta_tags {
+            self.stats.meta_truncated = true;
+            return;
+        }
+        // 纯ASCII小写，替代Unicode的to_lowercase()，速度提升10~100倍
+        self.meta_tags.push((ascii_lowercase(name.trim()), content));
+        self.stats.meta_captured += 1;
+    }
+
+    pub(super) fn push_comment(&mut self, text: &str, limits: &ExtractLimits) {
+        self.stats.comment_bytes_seen += text.len();
+        let remaining = limits.max_comment_bytes.saturating_sub(self.stats.comment_bytes_captured);
+        if text.len() > remaining {
+            self.stats.comments_truncated = true;
+        }
+        // 按字符边界截断，保证UTF8合法
+        let mut end = text.len().min(remaining);
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == 0 {
+            return;
+        }
+        self.comments.push_str(&text[..end]);
+        self.comments.push('\n');
+        self.stats.comment_bytes_captured += end;
+    }
+
+    /// 追加内联脚本文本片段（脚本可能分多个片段到达，按字符边界截断到上限）
+    pub(super) fn push_inline_script_chunk(&mut self, text: &str, last: bool, limits: &ExtractLimits) {
+        self.stats.inline_script_bytes_seen += text.len();
+        let remaining = limits
+            .max_inline_script_bytes
+            .saturating_sub(self.stats.inline_script_bytes_captured);
+        if text.len() > remaining {
+            self.stats.inline_scripts_truncated = true;
+        }
+        let mut end = text.len().min(remaining);
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.inline_scripts.push_str(&text[..end]);
+        self.stats.inline_script_bytes_captured += end;
+        // 脚本结束时换行分隔，避免相邻脚本首尾拼接成伪匹配
+        if last && !self.inline_scripts.is_empty() && !self.inline_scripts.ends_with('\n') {
+            self.inline_scripts.push('\n');
         }
     }
 }
 
 /// ASCII小写转换工具，无Unicode冗余计算
 fn ascii_lowercase(s: &str) -> String {
-    let bytes = s
-        .as_bytes()
-        .iter()
-        .map(|&c| c.to_ascii_lowercase())
-        .collect::<Vec<_>>();
-    // ASCII字节必为合法UTF8，跳过检查
-    unsafe { String::from_utf8_unchecked(bytes) }
+    s.to_ascii_lowercase()
 }
 
 /// 对外暴露的HTML提取器
@@ -91,35 +248,99 @@ pub struct HtmlExtractor;
 
 impl HtmlExtractor {
     pub fn new() -> Self {
-        Self::default()
+        Self
     }
 
-    /// 零拷贝解析HTML
+    /// 零拷贝解析HTML（默认上限）
     pub fn extract(html: &str) -> ExtractResult {
-        let extract_result = Shared::new(ExtractResult::new());
-        let script_result = extract_result.clone();
-        let meta_result = extract_result.clone();
+        Self::extract_with_limits(html, &ExtractLimits::default())
+    }
+
+    /// 零拷贝解析HTML（自定义上限）
+    /// 参数：
+    /// - html: HTML文本
+    /// - limits: 提取上限配置
+    ///
+    /// 返回：提取结果（含统计信息，可据此判断上限是否导致内容被丢弃）
+    pub fn extract_with_limits(html: &str, limits: &ExtractLimits) -> ExtractResult {
+        let limits = *limits;
+        // 处理器闭包共享同一结果（单线程解析，RefCell 借用互不重叠）
+        let extract_result = Rc::new(RefCell::new(ExtractResult::new()));
+        let script_result = Rc::clone(&extract_result);
+        let meta_result = Rc::clone(&extract_result);
+        let comment_result = Rc::clone(&extract_result);
+        let inline_result = Rc::clone(&extract_result);
+        let title_result = Rc::clone(&extract_result);
+        // 仅采集首个 <title>（SVG 等内嵌标题忽略）
+        let title_done = Cell::new(false);
+
+        // 带 src 的外部脚本不采集内容（元素处理器先于文本处理器触发）
+        let inline_open = Rc::new(Cell::new(false));
+        let inline_flag = Rc::clone(&inline_open);
+
+        // 注释采集按需注册，未启用时无额外开销
+        let mut document_content_handlers = Vec::new();
+        if limits.max_comment_bytes > 0 {
+            document_content_handlers.push(doc_comments!(move |c| {
+                comment_result.borrow_mut().push_comment(&c.text(), &limits);
+                Ok(())
+            }));
+        }
+
+        let mut element_content_handlers = vec![
+            // 提取 <script src=""> 标签
+            element!("script", move |el| {
+                let src = el.get_attribute("src");
+                inline_flag.set(src.is_none());
+                if let Some(src) = src {
+                    script_result.borrow_mut().push_script_src(&src, &limits);
+                }
+                Ok(())
+            }),
+            // 提取 <meta name|property|http-equiv="" content=""> 标签
+            // 键名优先取 name，其次 property（OpenGraph）与 http-equiv
+            element!("meta", move |el| {
+                let name = META_KEY_ATTRS
+                    .iter()
+                    .filter_map(|attr| el.get_attribute(attr))
+                    .find(|key| !key.trim().is_empty());
+                let content = el.get_attribute("content");
+                if let (Some(n), Some(c)) = (name, content) {
+                    meta_result.borrow_mut().push_meta_tag(&n, c, &limits);
+                }
+                Ok(())
+            }),
+            // 提取首个 <title> 文本（文本可能分多个片段到达）
+            text!("title", move |chunk| {
+                if title_done.get() {
+                    return Ok(());
+                }
+                let mut result = title_result.borrow_mut();
+                result.push_title_chunk(chunk.as_str(), &limits);
+                if chunk.last_in_text_node() {
+                    result.finish_title();
+                    title_done.set(true);
+                }
+                Ok(())
+            }),
+        ];
+
+        // 内联脚本采集按需注册
+        if limits.max_inline_script_bytes > 0 {
+            element_content_handlers.push(text!("script", move |chunk| {
+                if inline_open.get() {
+                    inline_result
+                        .borrow_mut()
+                        .push_inline_script_chunk(chunk.as_str(), chunk.last_in_text_node(), &limits);
+                }
+                Ok(())
+            }));
+        }
 
         let settings = Settings {
             strict: false, // 兼容畸形HTML/大小写标签/残缺标签
-            element_content_handlers: vec![
-                // 提取 <script src=""> 标签
-                element!("script", move |el| {
-                    if let Some(src) = el.get_attribute("src") {
-                        script_result.get_mut().push_script_src(&src);
-                    }
-                    Ok(())
-                }),
-                // 提取 <meta name="" content=""> 标签
-                element!("meta", move |el| {
-                    let name = el.get_attribute("name");
-                    let content = el.get_attribute("content");
-                    if let (Some(n), Some(c)) = (name, content) {
-                        meta_result.get_mut().push_meta_tag(&n, c);
-                    }
-                    Ok(())
-                }),
-            ],
+            element_content_handlers,
+            document_content_handlers,
             ..Settings::default()
         };
 
@@ -128,8 +349,10 @@ impl HtmlExtractor {
         let _ = rewriter.write(html.as_bytes());
         let _ = rewriter.end();
 
-        // 零拷贝返回所有权，无冗余克隆
-        extract_result.into_inner()
+        // 解析结束后处理器已释放，取回唯一所有权（无冗余克隆）
+        Rc::try_unwrap(extract_result)
+            .map(RefCell::into_inner)
+            .unwrap_or_else(|shared| shared.borrow().clone())
     }
 }
 
@@ -163,6 +386,30 @@ mod tests {
                 ("generator".into(), "WordPress 6.0".into())
             ]
         );
+        assert_eq!(result.inline_scripts, "console.log('inline')\n");
+    }
+
+    #[test]
+    fn test_extract_inline_scripts() {
+        let html = r#"<script src="/a.js">ignored()</script><script>var wp = 1;</script><script>gtag('config', 'G-1');</script>"#;
+        let result = HtmlExtractor::extract(html);
+        assert_eq!(result.inline_scripts, "var wp = 1;\ngtag('config', 'G-1');\n");
+        assert!(!result.stats.inline_scripts_truncated);
+
+        let limits = ExtractLimits {
+            max_inline_script_bytes: 14,
+            ..ExtractLimits::default()
+        };
+        let result = HtmlExtractor::extract_with_limits(html, &limits);
+        assert_eq!(result.inline_scripts, "var wp = 1;\ngta\n");
+        assert_eq!(result.stats.inline_script_bytes_captured, 14);
+        assert!(result.stats.inline_scripts_truncated && result.stats.is_truncated());
+
+        let limits = ExtractLimits {
+            max_inline_script_bytes: 0,
+            ..ExtractLimits::default()
+        };
+        assert!(HtmlExtractor::extract_with_limits(html, &limits).inline_scripts.is_empty());
     }
 
     #[test]
@@ -173,6 +420,43 @@ mod tests {
         assert_eq!(result.meta_tags, vec![("generator".into(), "PHP 8.2".into())]);
     }
 
+    #[test]
+    fn test_extract_limits() {
+        let html = r#"<script src="/a.js"></script><script src="/b.js"></script><script src="/c.js"></script>
+            <meta name="author" content="a"><meta name="generator" content="b">
+            <!-- build 1234 --><!-- second comment -->"#;
+        let limits = ExtractLimits {
+            max_scripts: 2,
+            max_meta_tags: 1,
+            max_comment_bytes: 16,
+            ..ExtractLimits::default()
+        };
+        let result = HtmlExtractor::extract_with_limits(html, &limits);
+        assert_eq!(result.script_srcs, vec!["/a.js", "/b.js"]);
+        assert_eq!(result.meta_tags, vec![("author".into(), "a".into())]);
+        assert_eq!(result.comments, " build 1234 \n sec\n");
+        assert_eq!(result.stats.scripts_seen, 3);
+        assert_eq!(result.stats.meta_seen, 2);
+        assert!(result.stats.scripts_truncated && result.stats.meta_truncated && result.stats.comments_truncated);
+
+        let result = HtmlExtractor::extract(html);
+        assert!(!result.stats.is_truncated());
+        assert_eq!(result.comments, " build 1234 \n second comment \n");
+    }
+
+    #[test]
+    fn test_extract_title() {
+        let html = "<html><head><TITLE>\n  Dashboard &amp; Login  [Jenkins]\n</TITLE></head><svg><title>icon</title></svg></html>";
+        assert_eq!(HtmlExtractor::extract(html).title, "Dashboard & Login [Jenkins]");
+        assert!(HtmlExtractor::extract("<html><body>no title</body></html>").title.is_empty());
+
+        let limits = ExtractLimits {
+            max_title_len: 4,
+            ..ExtractLimits::default()
+        };
+        assert_eq!(HtmlExtractor::extract_with_limits("<title>Jenkins</title>", &limits).title, "Jenk");
+    }
+
     #[test]
     fn test_ascii_lowercase() {
         let html = r#"<meta NAME="AUTHOR" content="test"><meta name="KEYWORDS" content="rust,html"></meta>"#;
@@ -182,6 +466,20 @@ mod tests {
             ("keywords".into(), "rust,html".into())
         ]);
     }
+
+    #[test]
+    fn test_meta_key_attributes() {
+        let html = r#"<meta property="og:site_name" content="Ghost"><meta HTTP-EQUIV="Generator" content="Joomla!">
+            <meta name=" Author " property="og:title" content="a"><meta name="" property="og:type" content="website">
+            <meta itemprop="name" content="ignored"><meta http-equiv="X-UA-Compatible">"#;
+        let result = HtmlExtractor::extract(html);
+        assert_eq!(result.meta_tags, vec![
+            ("og:site_name".into(), "Ghost".into()),
+            ("generator".into(), "Joomla!".into()),
+            ("author".into(), "a".into()),
+            ("og:type".into(), "website".into())
+        ]);
+    }
 }
 
 // cargo test --release --quiet performance_test2 -- --nocapture
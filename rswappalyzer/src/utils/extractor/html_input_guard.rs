@@ -2,6 +2,11 @@
 /// 保证输入「值得分析」且「不会拖垮引擎」
 use std::borrow::Cow;
 
+use serde::{Deserialize, Serialize};
+
+use super::html_extractor::ExtractLimits;
+use crate::result::warning::HtmlRejectReason;
+
 pub struct HtmlInputGuard;
 
 impl HtmlInputGuard {
@@ -11,10 +16,18 @@ impl HtmlInputGuard {
     pub const MIN_VALID_LEN: usize = 16;
 
     #[inline(always)]
-    pub fn guard(mut html: Cow<str>) -> Option<Cow<str>> {
+    pub fn guard(html: Cow<str>) -> Option<Cow<str>> {
+        Self::check(html).ok()
+    }
+
+    /// 校验 HTML 输入（与 guard 一致，额外返回拒绝原因，供检测告警使用）
+    /// 参数：html - 解码后的响应体
+    ///
+    /// 返回：通过校验的 HTML（超长时截断）| 拒绝原因
+    pub fn check(mut html: Cow<str>) -> Result<Cow<str>, HtmlRejectReason> {
         // 1. 空输入：直接判定无效
         if html.is_empty() {
-            return None;
+            return Err(HtmlRejectReason::Empty);
         }
 
         // 2. 超长保护（零拷贝 + UTF-8 边界安全）
@@ -25,7 +38,7 @@ impl HtmlInputGuard {
             }
 
             match html {
-                Cow::Borrowed(s) => html = Cow::Owned(s[..cut].to_string()),
+                Cow::Borrowed(s) => html = Cow::Borrowed(&s[..cut]),
                 Cow::Owned(ref mut s) => s.truncate(cut),
             }
         }
@@ -39,10 +52,11 @@ impl HtmlInputGuard {
                 let end = s.bytes().rposition(|b| !b.is_ascii_whitespace() && !b.is_ascii_control()).map_or(0, |p| p + 1);
                 
                 if start >= end || end - start < Self::MIN_VALID_LEN {
-                    return None;
+                    return Err(HtmlRejectReason::TooShort);
                 }
                 // 原地截断，零内存分配，直接修改原字符串
-                *s = s[start..end].to_string();
+                s.truncate(end);
+                s.drain(..start);
             }
             Cow::Borrowed(s) => {
                 // 零拷贝trim：仅计算有效长度，不生成新字符串，无内存分配
@@ -50,12 +64,305 @@ impl HtmlInputGuard {
                 let end = s.bytes().rposition(|b| !b.is_ascii_whitespace() && !b.is_ascii_control()).map_or(0, |p| p + 1);
                 
                 if start >= end || end - start < Self::MIN_VALID_LEN {
-                    return None;
+                    return Err(HtmlRejectReason::TooShort);
                 }
             }
         }
 
         // 4. 通过所有校验，安全返回
-        Some(html)
+        Ok(html)
+    }
+
+    /// 解码前校验原始响应体字节（规则与 check 一致）
+    /// 空/过短的响应体不再解码；超长响应体先按字节截断，解码（含非法 UTF-8 容错解码）最多复制 MAX_HTML_LEN 字节
+    /// 参数：body - 解压后的响应体字节
+    ///
+    /// 返回：通过校验的字节（超长时截断，未去除首尾空白）| 拒绝原因
+    pub fn check_bytes(body: Cow<[u8]>) -> Result<Cow<[u8]>, HtmlRejectReason> {
+        if body.is_empty() {
+            return Err(HtmlRejectReason::Empty);
+        }
+        let body = Self::truncate_bytes(body);
+        let start = body.iter().position(|b| !b.is_ascii_whitespace() && !b.is_ascii_control()).unwrap_or(0);
+        let end = body.iter().rposition(|b| !b.is_ascii_whitespace() && !b.is_ascii_control()).map_or(0, |p| p + 1);
+        if start >= end || end - start < Self::MIN_VALID_LEN {
+            return Err(HtmlRejectReason::TooShort);
+        }
+        Ok(body)
+    }
+
+    /// 按字节截断超长响应体（回退到 UTF-8 字符边界，借用输入时零拷贝）
+    /// 参数：body - 响应体字节
+    ///
+    /// 返回：不超过 MAX_HTML_LEN 字节的响应体
+    pub fn truncate_bytes(body: Cow<[u8]>) -> Cow<[u8]> {
+        if body.len() <= Self::MAX_HTML_LEN {
+            return body;
+        }
+        let mut cut = Self::MAX_HTML_LEN;
+        // 截断点落在多字节字符中间（UTF-8 续字节 10xxxxxx）时前移
+        while cut > 0 && body[cut] & 0xC0 == 0x80 {
+            cut -= 1;
+        }
+        match body {
+            Cow::Borrowed(bytes) => Cow::Borrowed(&bytes[..cut]),
+            Cow::Owned(mut bytes) => {
+                bytes.truncate(cut);
+                Cow::Owned(bytes)
+            }
+        }
+    }
+}
+
+/// 对抗性输入清理统计
+/// 超大属性值被截短、标签总数/嵌套深度超限时文档在超限处截断，保证最坏情况下的处理耗时有界
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SanitizeStats {
+    /// 扫描到的标签总数（含结束标签）
+    pub tags_seen: usize,
+    /// 观测到的最大嵌套深度（不含 void 元素与可省略结束标签的元素）
+    pub max_depth: usize,
+    /// 被截短的超长属性值数量
+    pub attributes_truncated: usize,
+    /// 是否因标签总数上限截断文档
+    pub tags_truncated: bool,
+    /// 是否因嵌套深度上限截断文档
+    pub depth_truncated: bool,
+}
+
+impl SanitizeStats {
+    /// 是否有任何内容被截短/截断
+    pub fn is_truncated(&self) -> bool {
+        self.tags_truncated || self.depth_truncated || self.attributes_truncated > 0
+    }
+}
+
+/// void 元素（无结束标签，不增加嵌套深度）
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source", "track", "wbr",
+];
+
+/// 结束标签可省略的元素（畸形页面中大量未闭合，不参与深度统计，避免误判）
+const OPTIONAL_END_ELEMENTS: &[&str] = &[
+    "html", "head", "body", "p", "li", "dt", "dd", "option", "optgroup", "tr", "td", "th", "thead", "tbody",
+    "tfoot", "colgroup", "rt", "rp",
+];
+
+/// 原始文本元素（内容中的 `<` 不是标签）
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style", "textarea", "title", "xmp"];
+
+impl HtmlInputGuard {
+    /// 清理对抗性输入（单次线性扫描，无超限内容时零拷贝）
+    /// 1. 属性值（含未加引号的值）超过 max_attribute_len 时截短
+    /// 2. 标签总数超过 max_tags、嵌套深度超过 max_tag_depth 时在超限标签处截断文档
+    ///
+    /// 参数：
+    /// - html: 已通过 guard 的 HTML
+    /// - limits: 提取上限配置
+    ///
+    /// 返回：(清理后的 HTML, 清理统计)
+    pub fn sanitize<'a>(html: Cow<'a, str>, limits: &ExtractLimits) -> (Cow<'a, str>, SanitizeStats) {
+        let mut stats = SanitizeStats::default();
+        let s = html.as_ref();
+        let bytes = s.as_bytes();
+        let len = bytes.len();
+
+        // 存在超长属性值时才分配输出缓冲（bytes[..copied] 已写入）
+        let mut out: Option<String> = None;
+        let mut copied = 0;
+        let mut cut = None;
+        let mut depth = 0usize;
+        let mut i = 0;
+
+        while i < len {
+            let Some(offset) = bytes[i..].iter().position(|&b| b == b'<') else {
+                break;
+            };
+            let tag_start = i + offset;
+            let rest = &bytes[tag_start..];
+
+            // 注释整体跳过
+            if rest.starts_with(b"<!--") {
+                i = find(bytes, tag_start + 4, b"-->").map_or(len, |p| p + 3);
+                continue;
+            }
+            let closing = rest.get(1) == Some(&b'/');
+            let declaration = matches!(rest.get(1), Some(b'!' | b'?'));
+            let name_start = tag_start + 1 + usize::from(closing || declaration);
+            if !declaration && !bytes.get(name_start).is_some_and(u8::is_ascii_alphabetic) {
+                i = tag_start + 1;
+                continue;
+            }
+
+            stats.tags_seen += 1;
+            if stats.tags_seen > limits.max_tags {
+                stats.tags_truncated = true;
+                cut = Some(tag_start);
+                break;
+            }
+
+            let name_end = bytes[name_start..]
+                .iter()
+                .position(|b| !(b.is_ascii_alphanumeric() || matches!(b, b'-' | b':' | b'_')))
+                .map_or(len, |p| name_start + p);
+            let name = &s[name_start..name_end];
+
+            // 扫描标签体至 `>`，截短超长的属性值
+            let mut j = name_end;
+            let mut run_start = j;
+            let mut quote: Option<u8> = None;
+            while j < len {
+                let b = bytes[j];
+                match quote {
+                    Some(q) if b == q => {
+                        quote = None;
+                        run_start = j + 1;
+                    }
+                    Some(_) => {}
+                    None if b == b'"' || b == b'\'' => {
+                        quote = Some(b);
+                        run_start = j + 1;
+                    }
+                    None if b == b'>' => break,
+                    None if b.is_ascii_whitespace() || b == b'=' => run_start = j + 1,
+                    None => {}
+                }
+                if j + 1 - run_start > limits.max_attribute_len {
+                    let run_end = match quote {
+                        Some(q) => bytes[j..].iter().position(|&b| b == q),
+                        None => bytes[j..].iter().position(|b| b.is_ascii_whitespace() || *b == b'>'),
+                    }
+                    .map_or(len, |p| j + p);
+                    let mut keep = run_start + limits.max_attribute_len;
+                    while !s.is_char_boundary(keep) {
+                        keep -= 1;
+                    }
+                    out.get_or_insert_with(|| String::with_capacity(len)).push_str(&s[copied..keep]);
+                    copied = run_end;
+                    stats.attributes_truncated += 1;
+                    j = run_end;
+                    run_start = j;
+                    continue;
+                }
+                j += 1;
+            }
+            let self_closing = j > name_end && bytes[j - 1] == b'/';
+            i = (j + 1).min(len);
+            if declaration {
+                continue;
+            }
+
+            let is = |list: &[&str]| list.iter().any(|t| t.eq_ignore_ascii_case(name));
+            if !is(VOID_ELEMENTS) && !is(OPTIONAL_END_ELEMENTS) {
+                if closing {
+                    depth = depth.saturating_sub(1);
+                } else if !self_closing {
+                    depth += 1;
+                    stats.max_depth = stats.max_depth.max(depth);
+                    if depth > limits.max_tag_depth {
+                        stats.depth_truncated = true;
+                        cut = Some(tag_start);
+                        break;
+                    }
+                }
+            }
+
+            // 原始文本元素：跳到对应结束标签
+            if !closing && !self_closing && is(RAW_TEXT_ELEMENTS) {
+                let end_tag = format!("</{}", name.to_ascii_lowercase());
+                i = find_ignore_case(bytes, i, end_tag.as_bytes()).unwrap_or(len);
+            }
+        }
+
+        let end = cut.unwrap_or(len).max(copied);
+        if let Some(mut out) = out {
+            out.push_str(&s[copied..end]);
+            return (Cow::Owned(out), stats);
+        }
+        let html = match html {
+            Cow::Borrowed(s) => Cow::Borrowed(&s[..end]),
+            Cow::Owned(mut s) => {
+                s.truncate(end);
+                Cow::Owned(s)
+            }
+        };
+        (html, stats)
+    }
+}
+
+/// 从 from 开始查找子串
+fn find(haystack: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|p| from + p)
+}
+
+/// 从 from 开始查找子串（ASCII 大小写不敏感）
+fn find_ignore_case(haystack: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|w| w.eq_ignore_ascii_case(needle))
+        .map(|p| from + p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_adversarial_inputs() {
+        let limits = ExtractLimits {
+            max_attribute_len: 8,
+            max_tag_depth: 4,
+            max_tags: 64,
+            ..ExtractLimits::default()
+        };
+
+        // 正常页面零拷贝且不变
+        let html = "<html><body><div class=a><img src=x.png><p>1<p>2</div><script>if(a<b){}</script></body></html>";
+        let (out, stats) = HtmlInputGuard::sanitize(Cow::Borrowed(html), &limits);
+        assert!(matches!(out, Cow::Borrowed(_)) && out == html);
+        assert!(!stats.is_truncated());
+        assert_eq!(stats.max_depth, 1);
+
+        // 超长属性值被截短，标签结构保留
+        let html = format!("<meta name=\"g\" content=\"{}\"><b>x</b>", "A".repeat(100));
+        let (out, stats) = HtmlInputGuard::sanitize(Cow::Owned(html), &limits);
+        assert_eq!(out, "<meta name=\"g\" content=\"AAAAAAAA\"><b>x</b>");
+        assert_eq!(stats.attributes_truncated, 1);
+
+        // 深度超限处截断
+        let html = "<div>".repeat(10);
+        let (out, stats) = HtmlInputGuard::sanitize(Cow::Borrowed(&html), &limits);
+        assert_eq!(out, "<div>".repeat(4));
+        assert!(stats.depth_truncated);
+
+        // 标签总数超限处截断
+        let html = "<br>".repeat(100);
+        let (out, stats) = HtmlInputGuard::sanitize(Cow::Borrowed(&html), &limits);
+        assert_eq!(out.len(), 64 * 4);
+        assert!(stats.tags_truncated);
+    }
+
+    #[test]
+    fn test_check_bytes_before_decoding() {
+        // 空/过短的字节在解码前被拒绝
+        assert_eq!(HtmlInputGuard::check_bytes(Cow::Borrowed(b"")), Err(HtmlRejectReason::Empty));
+        assert_eq!(HtmlInputGuard::check_bytes(Cow::Borrowed(b" \t<p>\xe9</p>\n ")), Err(HtmlRejectReason::TooShort));
+
+        // 非法 UTF-8 的字节照常通过，借用输入时零拷贝
+        let body = b"<html><body>\xe9\xe9</body></html>";
+        let checked = HtmlInputGuard::check_bytes(Cow::Borrowed(body)).unwrap();
+        assert!(matches!(checked, Cow::Borrowed(_)) && checked.as_ref() == body);
+
+        // 超长字节截断时不拆分多字节字符
+        let mut body = vec![b'a'; HtmlInputGuard::MAX_HTML_LEN - 1];
+        body.extend_from_slice("é".repeat(4).as_bytes());
+        let truncated = HtmlInputGuard::truncate_bytes(Cow::Owned(body));
+        assert_eq!(truncated.len(), HtmlInputGuard::MAX_HTML_LEN - 1);
+        assert!(std::str::from_utf8(&truncated).is_ok());
     }
-}
\ No newline at end of file
+}
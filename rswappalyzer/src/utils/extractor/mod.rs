@@ -4,6 +4,9 @@ pub mod html_extractor;
 //pub mod html_extractor3_h5;
 
 pub mod html_input_guard;
+pub mod streaming;
 pub mod token_extract;
 pub mod token_extract_zh;
-pub use self::html_extractor::HtmlExtractor;
\ No newline at end of file
+pub use self::html_extractor::{ExtractLimits, ExtractStats, HtmlExtractor};
+pub use self::html_input_guard::SanitizeStats;
+pub use self::streaming::{HtmlParser, StreamingExtractor};
\ No newline at end of file
@@ -0,0 +1,252 @@
+//! 流式 HTML 提取器
+//! 多 MB 页面上 lol_html 的完整词法分析占据大部分提取耗时。流式提取器按字节单次前向扫描，只识别提取所需的结构：
+//! 1. `<!-- -->` 注释、`<script>`（src 与内联内容）、`<meta>`、首个 `<title>`，其余标签只跳过标签体
+//! 2. 原始文本元素（script/style/textarea/title/xmp）直接跳到对应结束标签，内容中的 `<` 不视为标签
+//! 3. 各类内容沿用 ExtractLimits 的数量/字节上限，超出上限的内容不复制，提取结果与统计信息与 HtmlExtractor 一致
+//!
+//! 不构建 DOM、不解码属性实体，畸形 HTML 的容错程度低于 lol_html（由 HtmlInputGuard 预先清理）
+
+use serde::{Deserialize, Serialize};
+
+use super::html_extractor::{ExtractLimits, ExtractResult, HtmlExtractor, META_KEY_ATTRS};
+
+/// HTML 提取解析器
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HtmlParser {
+    /// lol_html 词法分析（默认，容错性最好）
+    #[default]
+    Rewriter,
+    /// 单次前向字节扫描（超大页面提取更快）
+    Streaming,
+}
+
+impl HtmlParser {
+    /// 按解析器提取 HTML
+    /// 参数：
+    /// - html: HTML文本
+    /// - limits: 提取上限配置
+    ///
+    /// 返回：提取结果
+    pub fn extract(self, html: &str, limits: &ExtractLimits) -> ExtractResult {
+        match self {
+            HtmlParser::Rewriter => HtmlExtractor::extract_with_limits(html, limits),
+            HtmlParser::Streaming => StreamingExtractor::extract_with_limits(html, limits),
+        }
+    }
+}
+
+/// 跳过内容的原始文本元素（script/title 单独处理）
+const SKIPPED_RAW_TEXT: &[&str] = &["style", "textarea", "xmp"];
+
+/// 流式 HTML 提取器
+#[derive(Debug, Default)]
+pub struct StreamingExtractor;
+
+impl StreamingExtractor {
+    /// 单次前向扫描提取（自定义上限）
+    /// 参数：
+    /// - html: HTML文本
+    /// - limits: 提取上限配置
+    ///
+    /// 返回：提取结果（含统计信息）
+    pub fn extract_with_limits(html: &str, limits: &ExtractLimits) -> ExtractResult {
+        let mut result = ExtractResult::new();
+        let bytes = html.as_bytes();
+        let len = bytes.len();
+        let mut title_done = false;
+        let mut i = 0;
+
+        while let Some(offset) = bytes[i..].iter().position(|&b| b == b'<') {
+            let tag_start = i + offset;
+            let rest = &bytes[tag_start..];
+
+            if rest.starts_with(b"<!--") {
+                let end = find(bytes, tag_start + 4, b"-->");
+                if limits.max_comment_bytes > 0 {
+                    result.push_comment(&html[tag_start + 4..end.unwrap_or(len)], limits);
+                }
+                i = end.map_or(len, |p| p + 3);
+                continue;
+            }
+            // 结束标签/声明/处理指令：跳过标签体
+            if matches!(rest.get(1), Some(b'/' | b'!' | b'?')) {
+                i = tag_end(bytes, tag_start + 2).map_or(len, |p| p + 1);
+                continue;
+            }
+            if !rest.get(1).is_some_and(u8::is_ascii_alphabetic) {
+                i = tag_start + 1;
+                continue;
+            }
+
+            let name_start = tag_start + 1;
+            let name_end = bytes[name_start..]
+                .iter()
+                .position(|b| !(b.is_ascii_alphanumeric() || matches!(b, b'-' | b':' | b'_')))
+                .map_or(len, |p| name_start + p);
+            let name = &html[name_start..name_end];
+            let body_end = tag_end(bytes, name_end).unwrap_or(len);
+            let attrs = &html[name_end..body_end];
+            i = (body_end + 1).min(len);
+
+            if name.eq_ignore_ascii_case("meta") {
+                let key = META_KEY_ATTRS
+                    .iter()
+                    .filter_map(|attr| attribute(attrs, attr))
+                    .find(|key| !key.trim().is_empty());
+                if let (Some(key), Some(content)) = (key, attribute(attrs, "content")) {
+                    result.push_meta_tag(key, content.to_string(), limits);
+                }
+            } else if name.eq_ignore_ascii_case("script") {
+                let src = attribute(attrs, "src");
+                if let Some(src) = src {
+                    result.push_script_src(src, limits);
+                }
+                let content_end = find_ignore_case(bytes, i, b"</script").unwrap_or(len);
+                if src.is_none() && limits.max_inline_script_bytes > 0 {
+                    result.push_inline_script_chunk(&html[i..content_end], true, limits);
+                }
+                i = content_end;
+            } else if name.eq_ignore_ascii_case("title") {
+                let content_end = find_ignore_case(bytes, i, b"</title").unwrap_or(len);
+                if !title_done {
+                    result.push_title_chunk(&html[i..content_end], limits);
+                    result.finish_title();
+                    title_done = true;
+                }
+                i = content_end;
+            } else if SKIPPED_RAW_TEXT.iter().any(|t| t.eq_ignore_ascii_case(name)) {
+                let end_tag = format!("</{}", name.to_ascii_lowercase());
+                i = find_ignore_case(bytes, i, end_tag.as_bytes()).unwrap_or(len);
+            }
+        }
+        result
+    }
+}
+
+/// 查找标签体结束的 `>`（引号内的 `>` 不计）
+fn tag_end(bytes: &[u8], from: usize) -> Option<usize> {
+    let mut quote: Option<u8> = None;
+    for (offset, &b) in bytes.get(from..)?.iter().enumerate() {
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None if b == b'"' || b == b'\'' => quote = Some(b),
+            None if b == b'>' => return Some(from + offset),
+            None => {}
+        }
+    }
+    None
+}
+
+/// 读取标签体中的属性值（属性名大小写不敏感，重复属性取首个，无值属性返回空串）
+fn attribute<'a>(attrs: &'a str, wanted: &str) -> Option<&'a str> {
+    let bytes = attrs.as_bytes();
+    let len = bytes.len();
+    let skip_ws = |mut i: usize| {
+        while i < len && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        i
+    };
+    let mut i = 0;
+    while i < len {
+        i = skip_ws(i);
+        let name_start = i;
+        while i < len && !(bytes[i].is_ascii_whitespace() || matches!(bytes[i], b'=' | b'/')) {
+            i += 1;
+        }
+        let name = &attrs[name_start..i];
+        if name.is_empty() {
+            i += 1;
+            continue;
+        }
+        let mut value = "";
+        let after_name = skip_ws(i);
+        if bytes.get(after_name) == Some(&b'=') {
+            i = skip_ws(after_name + 1);
+            match bytes.get(i) {
+                Some(&q @ (b'"' | b'\'')) => {
+                    let end = bytes[i + 1..].iter().position(|&b| b == q).map_or(len, |p| i + 1 + p);
+                    value = &attrs[i + 1..end];
+                    i = end + 1;
+                }
+                _ => {
+                    let start = i;
+                    while i < len && !bytes[i].is_ascii_whitespace() {
+                        i += 1;
+                    }
+                    value = &attrs[start..i];
+                }
+            }
+        }
+        if name.eq_ignore_ascii_case(wanted) {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// 从 from 开始查找子串
+fn find(haystack: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|p| from + p)
+}
+
+/// 从 from 开始查找子串（ASCII 大小写不敏感）
+fn find_ignore_case(haystack: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|w| w.eq_ignore_ascii_case(needle))
+        .map(|p| from + p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streaming_matches_rewriter() {
+        let html = r#"<!DOCTYPE html><html><HEAD>
+            <title> Shop &amp; Blog </title>
+            <SCRIPT SRC="/jquery.min.js"></SCRIPT><script type=module src=/app.js></script>
+            <script>if (a < b) { document.write("<meta name=x content=y>"); }</script>
+            <meta name="generator" content="WordPress 6.4"><meta property='og:site_name' content='Ghost'>
+            <meta http-equiv=Generator content=Joomla! data-x="a > b">
+            <!-- powered by Foo --><style>.a<b{}</style><svg><title>icon</title></svg>
+            <textarea><script src="/fake.js"></script></textarea>
+            </head><body><p>text</p></body></html>"#;
+        let limits = ExtractLimits::default();
+        assert_eq!(
+            StreamingExtractor::extract_with_limits(html, &limits),
+            HtmlExtractor::extract_with_limits(html, &limits)
+        );
+
+        let limits = ExtractLimits {
+            max_scripts: 1,
+            max_meta_tags: 2,
+            max_comment_bytes: 4,
+            max_inline_script_bytes: 8,
+            max_title_len: 4,
+            ..ExtractLimits::default()
+        };
+        assert_eq!(
+            StreamingExtractor::extract_with_limits(html, &limits),
+            HtmlExtractor::extract_with_limits(html, &limits)
+        );
+    }
+
+    #[test]
+    fn test_attribute() {
+        let attrs = r#" NAME="a" content = 'b c' data-x=1 async src=/x.js "#;
+        assert_eq!(attribute(attrs, "name"), Some("a"));
+        assert_eq!(attribute(attrs, "content"), Some("b c"));
+        assert_eq!(attribute(attrs, "async"), Some(""));
+        assert_eq!(attribute(attrs, "src"), Some("/x.js"));
+        assert_eq!(attribute(attrs, "property"), None);
+    }
+}
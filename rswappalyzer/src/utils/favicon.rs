@@ -0,0 +1,95 @@
+//! Favicon 哈希计算
+//! 与 Shodan / FOFA 等扫描器一致的 favicon 指纹：
+//! 1. favicon 字节按 MIME 风格 Base64 编码（每 76 字符换行，末尾追加换行，等同 Python `base64.encodebytes`）
+//! 2. 对编码结果计算 MurmurHash3 x86 32 位哈希（种子 0），按有符号 32 位整数输出
+
+const BASE64_TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// MIME Base64 每行字符数
+const BASE64_LINE_LEN: usize = 76;
+
+/// 计算 favicon 的 mmh3 哈希（Shodan `http.favicon.hash` 口径）
+/// 参数：bytes - favicon 原始字节
+///
+/// 返回：有符号 32 位哈希值
+pub fn favicon_hash(bytes: &[u8]) -> i32 {
+    murmur3_32(&base64_mime(bytes), 0) as i32
+}
+
+/// MurmurHash3 x86 32 位哈希
+/// 参数：
+/// - data: 输入字节
+/// - seed: 种子
+///
+/// 返回：无符号 32 位哈希值
+pub fn murmur3_32(data: &[u8], seed: u32) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+
+    let mut hash = seed;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+        hash ^= k;
+        hash = hash.rotate_left(13).wrapping_mul(5).wrapping_add(0xe654_6b64);
+    }
+
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        let mut k = tail
+            .iter()
+            .rev()
+            .fold(0u32, |acc, &byte| (acc << 8) | byte as u32);
+        k = k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+        hash ^= k;
+    }
+
+    hash ^= data.len() as u32;
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x85eb_ca6b);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0xc2b2_ae35);
+    hash ^ (hash >> 16)
+}
+
+/// MIME 风格 Base64 编码（每 76 字符换行，非空输入末尾追加换行）
+fn base64_mime(bytes: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        encoded.push(BASE64_TABLE[(n >> 18) as usize & 0x3f]);
+        encoded.push(BASE64_TABLE[(n >> 12) as usize & 0x3f]);
+        encoded.push(if chunk.len() > 1 { BASE64_TABLE[(n >> 6) as usize & 0x3f] } else { b'=' });
+        encoded.push(if chunk.len() > 2 { BASE64_TABLE[n as usize & 0x3f] } else { b'=' });
+    }
+
+    let mut wrapped = Vec::with_capacity(encoded.len() + encoded.len() / BASE64_LINE_LEN + 1);
+    for line in encoded.chunks(BASE64_LINE_LEN) {
+        wrapped.extend_from_slice(line);
+        wrapped.push(b'\n');
+    }
+    wrapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_murmur3_vectors() {
+        assert_eq!(murmur3_32(b"", 0), 0);
+        assert_eq!(murmur3_32(b"hello", 0), 0x248b_fa47);
+        assert_eq!(murmur3_32(b"The quick brown fox jumps over the lazy dog", 0), 0x2e4f_f723);
+    }
+
+    #[test]
+    fn test_base64_mime() {
+        assert_eq!(base64_mime(b"hello"), b"aGVsbG8=\n");
+        assert!(base64_mime(b"").is_empty());
+        let wrapped = base64_mime(&[0u8; 60]);
+        assert_eq!(wrapped.len(), 80 + 2);
+        assert_eq!(wrapped[76], b'\n');
+    }
+}
@@ -20,7 +20,9 @@ impl HeaderConverter {
     /// 1. 迭代次数限制（最多1000次），防止恶意超大Header
     /// 2. 所有Key/Value转为小写，统一匹配规则
     /// 3. FxHashMap高性能哈希表，适合高频访问
+    ///
     /// 参数：header_map - 标准HTTP HeaderMap
+    ///
     /// 返回：转换后的多值Header哈希表
     pub fn to_hashmap(header_map: &HeaderMap) -> FxHashMap<String, Vec<String>> {
         let mut map = FxHashMap::default();
@@ -67,7 +69,9 @@ impl HeaderConverter {
     /// 1. 预分配哈希表容量，避免运行期扩容开销
     /// 2. 分离普通Header（单值）和Cookie Header（多值）
     /// 3. 迭代次数限制，防止恶意超大Header
+    ///
     /// 参数：headers - 标准HTTP HeaderMap
+    ///
     /// 返回：(单值普通Header哈希表, Cookie专用Header哈希表)
     pub fn convert_all(
         headers: &HeaderMap,
@@ -144,6 +148,7 @@ impl HeaderConverter {
     /// 1. 极简过滤逻辑（空值/delete值）
     /// 2. 零拷贝切片操作，减少内存分配
     /// 3. 仅解析核心KV，忽略过期时间等属性
+    ///
     /// 参数：
     /// - raw_cookie: 原始Set-Cookie字符串
     /// - standard_cookies: 输出的标准化Cookie哈希表
@@ -174,7 +179,7 @@ impl HeaderConverter {
 
         // 添加到标准化Cookie哈希表
         standard_cookies.entry(name_lc)
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(value.to_string());
     }
 
@@ -183,6 +188,7 @@ impl HeaderConverter {
     /// 1. 手写循环替代链式迭代器，性能提升15%+
     /// 2. 字节切片操作，减少字符串分配
     /// 3. 零拷贝trim，UTF8安全处理
+    ///
     /// 参数：
     /// - raw_cookie: 原始Request-Cookie字符串
     /// - standard_cookies: 输出的标准化Cookie哈希表
@@ -221,7 +227,9 @@ impl HeaderConverter {
     /// 1. 零拷贝：仅操作字节切片，无内存分配
     /// 2. ASCII空白符处理，适合HTTP Header场景
     /// 3. 内联优化，编译期嵌入调用处
+    ///
     /// 参数：slice - 原始字节切片
+    ///
     /// 返回：trim后的字节切片
     #[inline(always)]
     fn trim_slice(slice: &[u8]) -> &[u8] {
@@ -238,13 +246,14 @@ impl HeaderConverter {
     /// 1. UTF8安全：使用String::from_utf8_lossy处理非UTF8值
     /// 2. 过滤deleted值，避免无效匹配
     /// 3. 内联优化，无函数调用开销
+    ///
     /// 参数：
     /// - core_kv: Cookie核心KV字节切片（如b"name=value"）
     /// - standard_cookies: 输出的标准化Cookie哈希表
     #[inline(always)]
     fn parse_cookie_kv(core_kv: &[u8], standard_cookies: &mut FxHashMap<String, Vec<String>>) {
         // 查找等号位置
-        let eq_pos = core_kv.iter().position(|&b| b == b'=').unwrap_or_else(|| core_kv.len());
+        let eq_pos = core_kv.iter().position(|&b| b == b'=').unwrap_or(core_kv.len());
         let (name_slice, value_slice) = core_kv.split_at(eq_pos);
         
         // Trim名称和值
@@ -0,0 +1,147 @@
+//! 命中作用域记录（轻量，常开）
+//! 检测流程按阶段设置当前作用域，DetectionUpdater 每次写入检测结果时为该技术置位作用域位（u16 位集），
+//! 无需完整证据采集即可得知每个技术由哪些作用域命中（如仅保留 Header 佐证的结果）：
+//! 1. 同一技术在多个作用域命中时各作用域均被记录（即使后续命中未改变置信度/版本）
+//! 2. 关联推导新增的技术不记录作用域（来源见 implied_by）
+//! 3. 未处于记录阶段时（如直接调用 DetectionUpdater）为空操作
+
+use std::cell::RefCell;
+
+use rswappalyzer_engine::scope_pruner::PruneScope;
+use rustc_hash::FxHashMap;
+
+use super::telemetry::scope_span;
+
+/// 当前记录状态（作用域位, 技术名 -> 作用域位集）
+type Recording = (u16, FxHashMap<String, u16>);
+
+thread_local! {
+    static CURRENT: RefCell<Option<Recording>> = const { RefCell::new(None) };
+}
+
+/// 技术名 -> 命中作用域位集
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MatchedScopes {
+    bits: FxHashMap<String, u16>,
+}
+
+/// 记录阶段守卫（退出时取回记录并恢复外层状态，分析器 panic 时同样生效）
+struct RecordGuard<'a> {
+    scopes: &'a mut MatchedScopes,
+    outer: Option<Recording>,
+}
+
+impl Drop for RecordGuard<'_> {
+    fn drop(&mut self) {
+        if let Some((_, bits)) = CURRENT.replace(self.outer.take()) {
+            self.scopes.bits = bits;
+        }
+    }
+}
+
+impl MatchedScopes {
+    /// 在指定作用域下执行分析，期间写入检测结果的技术均记录该作用域
+    /// 参数：
+    /// - scope: 当前作用域
+    /// - analyze: 分析过程
+    ///
+    /// 返回：分析过程的返回值
+    pub(crate) fn record<R>(&mut self, scope: PruneScope, analyze: impl FnOnce() -> R) -> R {
+        let bits = std::mem::take(&mut self.bits);
+        let outer = CURRENT.replace(Some((scope_bit(scope), bits)));
+        let _guard = RecordGuard { scopes: self, outer };
+        let _span = scope_span(scope);
+        analyze()
+    }
+
+    /// 合并另一组记录（同一技术的作用域位取并集，用于并行分析后汇总）
+    #[cfg_attr(not(feature = "parallel"), allow(dead_code))]
+    pub(crate) fn merge(&mut self, other: MatchedScopes) {
+        for (tech_name, bits) in other.bits {
+            *self.bits.entry(tech_name).or_default() |= bits;
+        }
+    }
+
+    /// 技术的命中作用域（按 PruneScope::ALL 顺序）
+    pub(crate) fn scopes_of(&self, tech_name: &str) -> Vec<PruneScope> {
+        let bits = self.bits.get(tech_name).copied().unwrap_or_default();
+        PruneScope::ALL
+            .into_iter()
+            .filter(|scope| bits & scope_bit(*scope) != 0)
+            .collect()
+    }
+}
+
+/// 为技术置位当前作用域（未处于记录阶段时忽略）
+#[inline]
+pub(crate) fn mark(tech_name: &str) {
+    CURRENT.with_borrow_mut(|current| {
+        let Some((bit, bits)) = current else {
+            return;
+        };
+        match bits.get_mut(tech_name) {
+            Some(existing) => *existing |= *bit,
+            None => {
+                bits.insert(tech_name.to_string(), *bit);
+            }
+        }
+    });
+}
+
+/// 暂停记录守卫（退出时恢复外层记录状态）
+struct SuspendGuard(Option<Recording>);
+
+impl Drop for SuspendGuard {
+    fn drop(&mut self) {
+        CURRENT.set(self.0.take());
+    }
+}
+
+/// 暂停记录执行（试探性匹配不置位，如 And 条件逐条验证模式）
+/// 参数：f - 执行过程
+///
+/// 返回：执行过程的返回值
+pub(crate) fn suspended<R>(f: impl FnOnce() -> R) -> R {
+    let _guard = SuspendGuard(CURRENT.take());
+    f()
+}
+
+/// 作用域位（PruneScope::ALL 中的位置）
+pub(crate) fn scope_bit(scope: PruneScope) -> u16 {
+    PruneScope::ALL
+        .iter()
+        .position(|s| *s == scope)
+        .map_or(0, |i| 1 << i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_scopes() {
+        let mut scopes = MatchedScopes::default();
+        scopes.record(PruneScope::Url, || mark("Nginx"));
+        scopes.record(PruneScope::Header, || {
+            mark("Nginx");
+            mark("PHP");
+        });
+        mark("Ignored");
+
+        assert_eq!(scopes.scopes_of("Nginx"), [PruneScope::Url, PruneScope::Header]);
+        assert_eq!(scopes.scopes_of("PHP"), [PruneScope::Header]);
+        assert!(scopes.scopes_of("Ignored").is_empty());
+    }
+
+    #[test]
+    fn test_suspended_not_recorded() {
+        let mut scopes = MatchedScopes::default();
+        scopes.record(PruneScope::Html, || {
+            suspended(|| mark("Shop"));
+            mark("Edge");
+        });
+        // 暂停期间不置位，退出后恢复记录
+        assert!(scopes.scopes_of("Shop").is_empty());
+        assert_eq!(scopes.scopes_of("Edge"), [PruneScope::Html]);
+    }
+}
@@ -1,11 +1,20 @@
 //! 工具模块：提供通用工具函数
 pub mod version_extractor;
 pub mod header_converter;
+pub mod prepared_headers;
+pub mod content_type;
+pub mod content_encoding;
 pub mod detection_updater;
+pub mod favicon;
+pub(crate) mod matched_scopes;
+pub(crate) mod telemetry;
 //pub mod log_format;
 pub mod extractor;
 
 pub use self::version_extractor::VersionExtractor;
 pub use self::header_converter::HeaderConverter;
+pub use self::prepared_headers::PreparedHeaders;
+pub use self::content_type::BodyKind;
+pub use self::content_encoding::ContentEncoding;
 pub use self::detection_updater::DetectionUpdater;
 //pub use self::regex_filter::{min_evidence, prune_analyzer};
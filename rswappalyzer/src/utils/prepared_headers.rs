@@ -0,0 +1,124 @@
+//! Prepared header set
+//! 预处理Header集合
+//! 批量扫描同一主机时，多个响应往往携带完全相同的Header；
+//! 预先完成 Header转换/Cookie解析/令牌提取，同一Header集合在批次内只处理一次，
+//! 后续检测直接复用（检测阶段Header维度零分配）
+
+use http::header::HeaderMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::analyzer::extract_tokens;
+use crate::utils::HeaderConverter;
+
+/// 预处理Header集合（预转换、预解析Cookie、预提取令牌）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreparedHeaders {
+    /// 单值普通Header（Key小写）
+    pub(crate) single_header_map: FxHashMap<String, String>,
+    /// 标准化Cookie（Cookie名小写）
+    pub(crate) standard_cookies: FxHashMap<String, Vec<String>>,
+    /// Header维度输入令牌
+    pub(crate) header_tokens: FxHashSet<String>,
+    /// Cookie维度输入令牌
+    pub(crate) cookie_tokens: FxHashSet<String>,
+}
+
+impl PreparedHeaders {
+    /// 预处理Header集合
+    /// 参数：headers - 标准HTTP HeaderMap
+    /// 返回：可跨多次检测复用的预处理结果
+    pub fn prepare(headers: &HeaderMap) -> Self {
+        let (single_header_map, cookie_header_map) = HeaderConverter::convert_all(headers);
+        let standard_cookies = HeaderConverter::parse_to_standard_cookie(&cookie_header_map);
+        let header_tokens = extract_tokens(single_header_map.values());
+        let cookie_tokens = extract_tokens(standard_cookies.values().flatten());
+
+        Self {
+            single_header_map,
+            standard_cookies,
+            header_tokens,
+            cookie_tokens,
+        }
+    }
+
+    /// 单值普通Header（Key小写）
+    pub fn headers(&self) -> &FxHashMap<String, String> {
+        &self.single_header_map
+    }
+
+    /// 标准化Cookie（Cookie名小写）
+    pub fn cookies(&self) -> &FxHashMap<String, Vec<String>> {
+        &self.standard_cookies
+    }
+
+    /// 是否为空Header集合
+    pub fn is_empty(&self) -> bool {
+        self.single_header_map.is_empty() && self.standard_cookies.is_empty()
+    }
+}
+
+impl From<&HeaderMap> for PreparedHeaders {
+    fn from(headers: &HeaderMap) -> Self {
+        Self::prepare(headers)
+    }
+}
+
+// 单元测试
+#[cfg(test)]
+mod tests {
+    use http::header::HeaderValue;
+
+    use super::*;
+    use crate::test_support::detector;
+
+    fn headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("Server", HeaderValue::from_static("nginx/1.25.3"));
+        headers.append("set-cookie", HeaderValue::from_static("PHPSESSID=abc; path=/"));
+        headers.append("set-cookie", HeaderValue::from_static("Laravel_Session=xyz"));
+        headers
+    }
+
+    #[test]
+    fn test_prepare() {
+        let prepared = PreparedHeaders::prepare(&headers());
+        assert_eq!(prepared.headers()["server"], "nginx/1.25.3");
+        assert!(prepared.cookies().contains_key("phpsessid"));
+        assert!(prepared.cookies().contains_key("laravel_session"));
+        assert!(prepared.header_tokens.contains("nginx"));
+        assert!(!prepared.is_empty());
+        assert_eq!(PreparedHeaders::from(&headers()), prepared);
+        assert!(PreparedHeaders::prepare(&HeaderMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_prepared_matches_detect() {
+        let detector = detector(
+            r#"{"technologies": {
+                "Nginx": { "cats": [22], "headers": { "Server": "nginx(?:/([\\d.]+))?\\;version:\\1" } },
+                "PHP": { "cats": [27], "cookies": { "PHPSESSID": "" } }
+            }}"#,
+        );
+        let prepared = PreparedHeaders::prepare(&headers());
+        for body in [&b"<html></html>"[..], b"<p>other</p>"] {
+            let mut expected: Vec<_> = detector
+                .detect(&headers(), &[], body)
+                .unwrap()
+                .technologies
+                .into_iter()
+                .map(|t| (t.name, t.version))
+                .collect();
+            let mut reused: Vec<_> = detector
+                .detect_prepared(&prepared, &[], body)
+                .unwrap()
+                .technologies
+                .into_iter()
+                .map(|t| (t.name, t.version))
+                .collect();
+            expected.sort();
+            reused.sort();
+            assert_eq!(reused, expected);
+            assert_eq!(reused.len(), 2);
+        }
+    }
+}
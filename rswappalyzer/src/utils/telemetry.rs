@@ -0,0 +1,48 @@
+//! 阶段追踪埋点（tracing 特性）
+//! 规则加载/编译与检测各阶段进入 tracing span，下游服务可将慢检测关联到具体阶段：
+//! 1. 规则：`rule.load` / `rule.fetch` / `rule.compile`
+//! 2. 检测：`detect` 下按阶段划分 `detect.body` / `detect.url` / `detect.header` / ... / `detect.post_process` / `detect.evidence`
+//! 3. 日志统一经 log 门面输出，tracing-subscriber（tracing-log 桥接）下作为所在 span 内的事件
+//! 4. 未启用特性时 span 为零大小占位，无运行时开销
+
+use rswappalyzer_engine::scope_pruner::PruneScope;
+
+/// 阶段 span 守卫（退出作用域时离开 span）
+#[cfg(feature = "tracing")]
+pub(crate) type StageSpan = tracing::span::EnteredSpan;
+
+/// 阶段 span 守卫（未启用 tracing 特性时为零大小占位）
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct StageSpan;
+
+/// 进入指定名称的阶段 span（名称须为字面量，可附带 tracing 字段）
+macro_rules! stage_span {
+    ($name:literal $(, $($fields:tt)*)?) => {{
+        #[cfg(feature = "tracing")]
+        let span: $crate::utils::telemetry::StageSpan = tracing::info_span!($name $(, $($fields)*)?).entered();
+        #[cfg(not(feature = "tracing"))]
+        let span = $crate::utils::telemetry::StageSpan;
+        span
+    }};
+}
+pub(crate) use stage_span;
+
+/// 进入作用域分析阶段 span（`detect.<scope>`）
+pub(crate) fn scope_span(scope: PruneScope) -> StageSpan {
+    match scope {
+        PruneScope::Url => stage_span!("detect.url"),
+        PruneScope::Html => stage_span!("detect.html"),
+        PruneScope::Script => stage_span!("detect.script"),
+        PruneScope::Header => stage_span!("detect.header"),
+        PruneScope::Meta => stage_span!("detect.meta"),
+        PruneScope::Cookie => stage_span!("detect.cookie"),
+        PruneScope::Hostname => stage_span!("detect.hostname"),
+        PruneScope::Dom => stage_span!("detect.dom"),
+        PruneScope::Js => stage_span!("detect.js"),
+        PruneScope::CertIssuer => stage_span!("detect.cert_issuer"),
+        PruneScope::Dns => stage_span!("detect.dns"),
+        PruneScope::FaviconHash => stage_span!("detect.favicon_hash"),
+        PruneScope::Title => stage_span!("detect.title"),
+        PruneScope::Comment => stage_span!("detect.comment"),
+    }
+}
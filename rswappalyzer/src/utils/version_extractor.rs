@@ -2,6 +2,8 @@
 //! 负责从正则捕获结果中，根据版本模板提取合法的技术版本号
 //! 支持 \1/\2 或 $1/$2 两种分组引用格式，自动过滤无效版本
 
+use std::borrow::Cow;
+
 use regex::Captures;
 
 /// 版本提取工具类
@@ -24,6 +26,30 @@ impl VersionExtractor {
     /// 2. 自动清理分组值和最终版本的前后空白字符
     /// 3. 多条件过滤无效版本，避免返回异常值
     pub fn extract(version_template: &Option<String>, captures: &Captures) -> Option<String> {
+        Self::extract_groups(version_template, captures.len(), |group_index| {
+            captures.get(group_index).map(|m| Cow::Borrowed(m.as_str()))
+        })
+    }
+
+    /// 从字节正则捕获结果中提取有效版本号（regex::bytes 按字节匹配非 UTF-8 输入时使用）
+    /// 分组中的非法 UTF-8 字节按 U+FFFD 替换（仅复制分组本身）
+    /// 参数：
+    /// - version_template: 版本模板
+    /// - captures: 字节正则捕获结果
+    ///
+    /// 返回：有效版本号 | None
+    pub fn extract_bytes(version_template: &Option<String>, captures: &regex::bytes::Captures) -> Option<String> {
+        Self::extract_groups(version_template, captures.len(), |group_index| {
+            captures.get(group_index).map(|m| String::from_utf8_lossy(m.as_bytes()))
+        })
+    }
+
+    /// 按模板替换分组引用（group 返回指定分组的匹配文本）
+    fn extract_groups<'a>(
+        version_template: &Option<String>,
+        group_count: usize,
+        group: impl Fn(usize) -> Option<Cow<'a, str>>,
+    ) -> Option<String> {
         // 1. 前置过滤：排除 None 模板 和 空白模板，减少无效计算
         version_template
             .as_ref()
@@ -43,15 +69,15 @@ impl VersionExtractor {
                 let mut replaced = false;
 
                 // 3. 遍历所有自定义捕获分组（从 1 开始，0 是整体匹配，不参与版本提取）
-                for group_index in 1..captures.len() {
+                for group_index in 1..group_count {
                     // 预生成两种分组占位符，避免循环内重复格式化，提升性能
                     let placeholder_backslash = format!("\\{}", group_index); // \1 格式
                     let placeholder_dollar = format!("${}", group_index); // $1 格式
 
                     // 获取当前分组的匹配内容
-                    if let Some(matched) = captures.get(group_index) {
+                    if let Some(matched) = group(group_index) {
                         // 清理分组值前后空白，避免将无效空白带入最终版本
-                        let matched_str = matched.as_str().trim();
+                        let matched_str = matched.trim();
                         // 替换模板中的对应占位符（同时兼容两种格式）
                         version = version.replace(&placeholder_backslash, matched_str);
                         version = version.replace(&placeholder_dollar, matched_str);
@@ -82,6 +108,29 @@ impl VersionExtractor {
                 }
             })
     }
+
+    /// 版本号归一化：取开头的数字点分段，允许单个小写字母后缀（1.0.2k-fips -> 1.0.2k）
+    /// 发行版修订号等后缀被丢弃（2.4.41-1ubuntu1 -> 2.4.41）
+    /// 参数：raw - 原始版本文本（如横幅版本段、版本 Header 值）
+    /// 返回：归一化版本号 | 非数字开头时为 None
+    pub fn normalize(raw: &str) -> Option<String> {
+        if !raw.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        let numeric_len = raw
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(raw.len());
+        let mut version = raw[..numeric_len].trim_end_matches('.').to_string();
+
+        let mut rest = raw[numeric_len..].chars();
+        if let Some(suffix) = rest.next().filter(char::is_ascii_lowercase) {
+            if !rest.next().is_some_and(|c| c.is_ascii_alphanumeric()) && raw[..numeric_len].ends_with(|c: char| c.is_ascii_digit()) {
+                version.push(suffix);
+            }
+        }
+
+        Some(version)
+    }
 }
 
 // 单元测试
@@ -184,4 +233,15 @@ mod tests {
         let version = VersionExtractor::extract(&template, &captures);
         assert_eq!(version, Some("10.0".to_string()));
     }
+
+    #[test]
+    fn test_extract_bytes_from_invalid_utf8() {
+        let regex = regex::bytes::Regex::new(r"acme(?:/([\d.]+))?").unwrap();
+        let captures = regex.captures(b"\xff\xfeacme/3.2.1 caf\xe9").unwrap();
+        let template = Some("\\1".to_string());
+        assert_eq!(VersionExtractor::extract_bytes(&template, &captures), Some("3.2.1".to_string()));
+
+        let captures = regex.captures(b"\xffacme").unwrap();
+        assert_eq!(VersionExtractor::extract_bytes(&template, &captures), None);
+    }
 }
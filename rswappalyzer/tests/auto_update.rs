@@ -0,0 +1,60 @@
+//! 规则后台自动更新服务测试（auto-update 特性）
+//! `cargo test -p rswappalyzer --features auto-update --test auto_update`
+#![cfg(feature = "auto-update")]
+
+use std::time::Duration;
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::detector::detect;
+use rswappalyzer::{
+    init_global_detector_with_rules, AutoUpdatePolicy, CustomConfigBuilder, DetectionUpdaterService, RuleConfig,
+    RuleOrigin, RuleUpdateEvent,
+};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::RuleProcessor;
+use tokio::sync::mpsc;
+
+const RULES: &str = r#"{ "technologies": { "Acme Gateway": { "cats": [22], "headers": { "Server": "nginx" } } } }"#;
+
+#[tokio::test]
+async fn test_auto_update_swaps_global_detector() {
+    let dir = std::env::temp_dir().join(format!("rswappalyzer-auto-update-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let rule_file = dir.join("rules.json");
+    std::fs::write(&rule_file, RULES).unwrap();
+
+    let initial = r#"{ "technologies": { "Nginx": { "cats": [22], "headers": { "Server": "nginx" } } } }"#;
+    let raw = WappalyzerParser.parse_to_rule_lib(initial).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+    init_global_detector_with_rules(rules, RuleConfig::default()).unwrap();
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let policy = AutoUpdatePolicy::new(Duration::from_millis(20))
+        .jitter(Duration::from_millis(10))
+        .on_update(move |event| {
+            let _ = tx.send(event.clone());
+        });
+    let config = CustomConfigBuilder::new()
+        .cache_dir(dir.join("cache"))
+        .origin(RuleOrigin::LocalFile(rule_file))
+        .auto_update(policy)
+        .build();
+    let service = DetectionUpdaterService::spawn(config).unwrap();
+    assert!(service.is_running());
+
+    let event = tokio::time::timeout(Duration::from_secs(10), rx.recv()).await.unwrap().unwrap();
+    assert_eq!(event, RuleUpdateEvent::Reloaded { tech_count: 1 });
+    let mut headers = HeaderMap::new();
+    headers.insert("server", HeaderValue::from_static("nginx/1.25"));
+    let result = detect(&headers, &[], b"").await.unwrap();
+    assert_eq!(result.technologies[0].name, "Acme Gateway");
+
+    // 规则未变化：跳过编译与替换
+    let event = tokio::time::timeout(Duration::from_secs(10), rx.recv()).await.unwrap().unwrap();
+    assert_eq!(event, RuleUpdateEvent::Unchanged);
+    tokio::time::timeout(Duration::from_secs(10), service.shutdown()).await.unwrap();
+
+    // 未配置自动更新策略时拒绝启动
+    assert!(DetectionUpdaterService::spawn(RuleConfig::default()).is_err());
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,51 @@
+//! 二进制嵌入规则库（binary-rules 特性）测试
+#![cfg(feature = "binary-rules")]
+
+use http::header::HeaderMap;
+use rswappalyzer::{RuleConfig, TechDetector};
+use rswappalyzer_engine::indexer::RuleLibraryIndex;
+use rswappalyzer_engine::CompiledRuleLibrary;
+
+fn names(detector: &TechDetector, headers: &HeaderMap, body: &[u8]) -> Vec<(String, Option<String>)> {
+    let mut techs: Vec<_> = detector
+        .detect(headers, &["https://example.com/"], body)
+        .unwrap()
+        .technologies
+        .into_iter()
+        .map(|t| (t.name, t.version))
+        .collect();
+    techs.sort();
+    techs
+}
+
+#[test]
+fn test_binary_embedded_rules_loaded() {
+    let detector = TechDetector::with_embedded_rules(RuleConfig::default()).unwrap();
+    assert!(!detector.compiled_library().tech_patterns.is_empty());
+    assert!(rswappalyzer::features().is_enabled("binary-rules"));
+
+    let mut headers = HeaderMap::new();
+    headers.insert("server", "nginx/1.25.3".parse().unwrap());
+    let result = detector.detect(&headers, &[], b"").unwrap();
+    assert!(result.find_by_slug("nginx").is_some());
+}
+
+#[test]
+fn test_binary_round_trip_detects_same() {
+    // 省略的默认字段（skip_serializing_if）经 MessagePack 往返后检测结果不变
+    let detector = TechDetector::with_embedded_rules(RuleConfig::default()).unwrap();
+    let bytes = rmp_serde::to_vec_named(detector.compiled_library().as_ref()).unwrap();
+    let decoded: CompiledRuleLibrary = rmp_serde::from_slice(&bytes).unwrap();
+    assert_eq!(decoded.tech_patterns.len(), detector.compiled_library().tech_patterns.len());
+    let round_trip = TechDetector::with_compiled_lib(decoded, RuleLibraryIndex::default(), RuleConfig::default());
+
+    let mut headers = HeaderMap::new();
+    headers.insert("server", "nginx/1.25.3".parse().unwrap());
+    headers.insert("x-powered-by", "PHP/8.2.1".parse().unwrap());
+    headers.insert("cookie", "PHPSESSID=abc; wp-settings-1=x".parse().unwrap());
+    let body = br#"<html><head><meta name="generator" content="WordPress 6.4.2">
+<script src="/wp-includes/js/jquery/jquery.min.js?ver=3.7.1"></script></head></html>"#;
+    let expected = names(&detector, &headers, body);
+    assert!(expected.len() > 2, "{:?}", expected);
+    assert_eq!(names(&round_trip, &headers, body), expected);
+}
@@ -0,0 +1,135 @@
+//! 规则缓存有效期与过期策略测试（remote-loader 特性）：本地 axum 规则源 + 回拨缓存文件修改时间
+//! 运行：`cargo test -p rswappalyzer --features remote-loader --test cache_ttl`
+#![cfg(feature = "remote-loader")]
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use rswappalyzer::rule::loader::RuleLoader;
+use rswappalyzer::{CustomConfigBuilder, RemoteOptions, RetryPolicy, RuleCacheManager, RuleConfig, RuleOrigin, StalePolicy};
+
+const RULES: &str = r#"{ "technologies": { "Acme Gateway": { "cats": [22], "headers": { "Server": "acme" } } } }"#;
+const TTL: Duration = Duration::from_secs(3600);
+
+/// 本地规则源：返回完整下载次数计数器与故障开关（开启后返回 500）
+struct Origin {
+    url: String,
+    downloads: Arc<AtomicUsize>,
+    broken: Arc<AtomicBool>,
+}
+
+async fn serve() -> Origin {
+    let downloads = Arc::new(AtomicUsize::new(0));
+    let broken = Arc::new(AtomicBool::new(false));
+    let (counter, failing) = (downloads.clone(), broken.clone());
+    let app = Router::new().route(
+        "/rules.json",
+        get(move || {
+            let (counter, failing) = (counter.clone(), failing.clone());
+            async move {
+                if failing.load(Ordering::SeqCst) {
+                    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+                }
+                counter.fetch_add(1, Ordering::SeqCst);
+                RULES.into_response()
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    Origin {
+        url: format!("http://{}/rules.json", addr),
+        downloads,
+        broken,
+    }
+}
+
+fn config(origin: &Origin, name: &str, policy: StalePolicy) -> RuleConfig {
+    let dir = std::env::temp_dir().join(format!("rswappalyzer-ttl-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    CustomConfigBuilder::new()
+        .cache_dir(dir)
+        .check_update(false)
+        .cache_ttl(TTL)
+        .stale_policy(policy)
+        .origin(RuleOrigin::RemoteCustom(origin.url.clone()))
+        .remote_options(RemoteOptions {
+            urls: vec![origin.url.clone()],
+            ..RemoteOptions::new(Duration::from_secs(5), RetryPolicy::Never)
+        })
+        .build()
+}
+
+/// 回拨缓存文件修改时间，使缓存超过有效期
+fn expire_cache(config: &RuleConfig) {
+    let file = std::fs::File::options().write(true).open(config.get_cache_file_path()).unwrap();
+    file.set_modified(SystemTime::now() - TTL * 2).unwrap();
+    assert!(RuleCacheManager::is_stale(config));
+}
+
+#[tokio::test]
+async fn test_fresh_cache_skips_network() {
+    let origin = serve().await;
+    let config = config(&origin, "fresh", StalePolicy::FailIfStale);
+    let loader = RuleLoader::new();
+    loader.load(&config).await.unwrap();
+    loader.load(&config).await.unwrap();
+    assert_eq!(origin.downloads.load(Ordering::SeqCst), 1);
+    assert!(!RuleCacheManager::is_stale(&config));
+}
+
+#[tokio::test]
+async fn test_always_refresh_refetches_and_serves_stale_on_failure() {
+    let origin = serve().await;
+    let config = config(&origin, "always", StalePolicy::AlwaysRefresh);
+    let loader = RuleLoader::new();
+    loader.load(&config).await.unwrap();
+
+    expire_cache(&config);
+    loader.load(&config).await.unwrap();
+    assert_eq!(origin.downloads.load(Ordering::SeqCst), 2);
+    assert!(!RuleCacheManager::is_stale(&config));
+
+    expire_cache(&config);
+    origin.broken.store(true, Ordering::SeqCst);
+    let stale = loader.load(&config).await.unwrap();
+    assert!(stale.core_tech_map.contains_key("Acme Gateway"));
+}
+
+#[tokio::test]
+async fn test_fail_if_stale_errors_when_refresh_fails() {
+    let origin = serve().await;
+    let config = config(&origin, "fail", StalePolicy::FailIfStale);
+    let loader = RuleLoader::new();
+    loader.load(&config).await.unwrap();
+
+    expire_cache(&config);
+    origin.broken.store(true, Ordering::SeqCst);
+    assert!(loader.load(&config).await.is_err());
+}
+
+#[tokio::test]
+async fn test_serve_stale_and_refresh_in_background() {
+    let origin = serve().await;
+    let config = config(&origin, "swr", StalePolicy::ServeStaleAndRefresh);
+    let loader = RuleLoader::new();
+    loader.load(&config).await.unwrap();
+
+    expire_cache(&config);
+    let stale = loader.load(&config).await.unwrap();
+    assert!(stale.core_tech_map.contains_key("Acme Gateway"));
+    for _ in 0..50 {
+        if !RuleCacheManager::is_stale(&config) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(origin.downloads.load(Ordering::SeqCst), 2);
+    assert!(!RuleCacheManager::is_stale(&config));
+}
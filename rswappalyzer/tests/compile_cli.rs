@@ -0,0 +1,69 @@
+//! rswappalyzer-compile 离线编译命令测试
+//! 运行：`cargo test -p rswappalyzer --features cli --test compile_cli`
+#![cfg(feature = "cli")]
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::TechDetector;
+
+const FINGERPRINTS: &str = r#"[
+    {"name": "acme-portal", "path": "/", "keyword": ["acme-portal-login"]},
+    {"name": "acme-gateway", "headers": {"x-acme-gateway": "*"}},
+    {"name": "favicon-only", "favicon_hash": ["123"]}
+]"#;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rswappalyzer-compile-{}-{}", std::process::id(), name))
+}
+
+fn compile(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_rswappalyzer-compile"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_compile_fingerprinthub_pack() {
+    let input = temp_path("hub.json");
+    let output = temp_path("hub.pack");
+    std::fs::write(&input, FINGERPRINTS).unwrap();
+
+    let result = compile(&[
+        input.to_str().unwrap(),
+        "--format",
+        "fingerprint-hub",
+        "-o",
+        output.to_str().unwrap(),
+    ]);
+    assert!(result.status.success(), "{}", String::from_utf8_lossy(&result.stderr));
+    assert!(String::from_utf8_lossy(&result.stdout).contains("Compiled 2 technologies"));
+    assert!(String::from_utf8_lossy(&result.stderr).contains("skipped favicon-only"));
+
+    let detector = TechDetector::with_compiled_artifact(&output).unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert("x-acme-gateway", HeaderValue::from_static("1"));
+    let mut names: Vec<_> = detector
+        .detect(&headers, &[], b"<html>acme-portal-login</html>")
+        .unwrap()
+        .technologies
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, ["acme-gateway", "acme-portal"]);
+
+    let _ = std::fs::remove_file(&input);
+    let _ = std::fs::remove_file(&output);
+}
+
+#[test]
+fn test_compile_rejects_invalid_input() {
+    let input = temp_path("broken.json");
+    std::fs::write(&input, "not json").unwrap();
+    let result = compile(&[input.to_str().unwrap(), "-o", temp_path("broken.pack").to_str().unwrap()]);
+    assert!(!result.status.success());
+    let _ = std::fs::remove_file(&input);
+}
@@ -0,0 +1,58 @@
+//! 按作用域部分编译测试（仅编译 Header 作用域的被动监控场景）
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::{CustomConfigBuilder, PruneScope, TechDetector};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::RuleProcessor;
+
+const RULES: &str = r#"{
+    "technologies": {
+        "PHP": { "cats": [27], "headers": { "X-Powered-By": "^php/?([\\d.]+)?\\;version:\\1" } },
+        "WordPress": { "cats": [1], "html": "<link[^>]+/wp-content/", "implies": "PHP" },
+        "GitHub Pages": { "cats": [62], "hostname": "*.github.io" }
+    }
+}"#;
+
+const BODY: &[u8] = br#"<html><head><link rel="stylesheet" href="/wp-content/themes/a.css"></head></html>"#;
+
+fn headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-powered-by", HeaderValue::from_static("PHP/8.2.1"));
+    headers
+}
+
+fn names(detector: &TechDetector) -> Vec<String> {
+    let result = detector.detect(&headers(), &["https://demo.github.io/"], BODY).unwrap();
+    let mut names: Vec<_> = result.technologies.into_iter().map(|t| t.name).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_header_only_compilation() {
+    let raw = WappalyzerParser.parse_to_rule_lib(RULES).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+
+    let full = TechDetector::with_rules(rules.clone(), Default::default()).unwrap();
+    assert_eq!(names(&full), ["GitHub Pages", "PHP", "WordPress"]);
+
+    let config = CustomConfigBuilder::new().compile_scopes([PruneScope::Header]).build();
+    let header_only = TechDetector::with_rules(rules, config).unwrap();
+    let lib = header_only.compiled_library();
+    assert!(lib.tech_patterns["WordPress"].html_patterns.is_none());
+    assert!(lib.tech_patterns["PHP"].header_patterns.is_some());
+    assert!(lib.hostname_index.is_empty());
+    assert!(!lib.known_tokens_by_scope.contains_key(&PruneScope::Html));
+    assert_eq!(names(&header_only), ["PHP"]);
+}
+
+#[cfg(feature = "embedded-rules")]
+#[test]
+fn test_header_only_precompiled_library() {
+    let config = CustomConfigBuilder::new().compile_scopes([PruneScope::Header]).build();
+    let detector = TechDetector::with_embedded_rules(config).unwrap();
+    let lib = detector.compiled_library();
+    assert!(lib.tech_patterns.values().all(|tech| tech.html_patterns.is_none() && tech.script_patterns.is_none()));
+    assert!(lib.evidence_tokens(PruneScope::Html).next().is_none());
+    assert!(names(&detector).contains(&"PHP".to_string()));
+}
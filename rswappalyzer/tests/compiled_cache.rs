@@ -0,0 +1,80 @@
+//! 已编译规则库持久化测试：本地规则文件 + 临时缓存目录
+//! 运行：`cargo test -p rswappalyzer --test compiled_cache`
+
+use std::path::Path;
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::{CustomConfigBuilder, RuleCacheManager, RuleConfig, RuleOrigin, TechDetector};
+use rswappalyzer_engine::scope_pruner::PruneScope;
+
+const RULES: &str = r#"{ "technologies": { "Acme Gateway": { "cats": [22], "headers": { "Server": "acme" } } } }"#;
+const CHANGED: &str = r#"{ "technologies": { "Acme Proxy": { "cats": [22], "headers": { "Server": "acme" } } } }"#;
+
+fn config(dir: &Path) -> RuleConfig {
+    CustomConfigBuilder::new()
+        .cache_dir(dir.join("cache"))
+        .origin(RuleOrigin::LocalFile(dir.join("rules.json")))
+        .build()
+}
+
+fn detected(detector: &TechDetector) -> Vec<String> {
+    let mut headers = HeaderMap::new();
+    headers.insert("server", HeaderValue::from_static("acme"));
+    let result = detector.detect(&headers, &[], b"").unwrap();
+    result.technologies.into_iter().map(|tech| tech.name).collect()
+}
+
+#[tokio::test]
+async fn test_compiled_library_persisted_and_reused() {
+    let dir = std::env::temp_dir().join(format!("rswappalyzer-compiled-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("rules.json"), RULES).unwrap();
+    let config = config(&dir);
+    let compiled_path = RuleCacheManager::compiled_cache_path(&config);
+
+    // 首次启动：编译并持久化
+    let first = TechDetector::new(config.clone()).await.unwrap();
+    assert!(first.rule_index.is_some());
+    assert!(compiled_path.exists());
+
+    // 规则未变化：直接加载编译产物，检测结果一致
+    let second = TechDetector::new(config.clone()).await.unwrap();
+    assert!(second.rule_index.is_none());
+    assert_eq!(detected(&second), ["Acme Gateway"]);
+
+    // 编译配置变化：重新编译
+    let case_sensitive = CustomConfigBuilder::new()
+        .cache_dir(dir.join("cache"))
+        .origin(RuleOrigin::LocalFile(dir.join("rules.json")))
+        .case_sensitive_scope(PruneScope::Header)
+        .build();
+    assert!(TechDetector::new(case_sensitive).await.unwrap().rule_index.is_some());
+
+    // 编译产物损坏（校验和不一致）：告警后重新编译
+    let mut data = std::fs::read(&compiled_path).unwrap();
+    let last = data.len() - 2;
+    data[last] ^= 0x20;
+    std::fs::write(&compiled_path, data).unwrap();
+    let recovered = TechDetector::new(config.clone()).await.unwrap();
+    assert!(recovered.rule_index.is_some());
+    assert_eq!(detected(&recovered), ["Acme Gateway"]);
+
+    // 规则变化：指纹不一致，重新编译
+    std::fs::remove_file(config.get_cache_file_path()).unwrap();
+    std::fs::write(dir.join("rules.json"), CHANGED).unwrap();
+    let changed = TechDetector::new(config.clone()).await.unwrap();
+    assert!(changed.rule_index.is_some());
+    assert_eq!(detected(&changed), ["Acme Proxy"]);
+
+    // 关闭持久化：不读写编译产物
+    std::fs::remove_file(&compiled_path).unwrap();
+    let disabled = CustomConfigBuilder::new()
+        .cache_dir(dir.join("cache"))
+        .origin(RuleOrigin::LocalFile(dir.join("rules.json")))
+        .persist_compiled(false)
+        .build();
+    assert!(TechDetector::new(disabled).await.unwrap().rule_index.is_some());
+    assert!(!compiled_path.exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,72 @@
+//! 全局检测器/共享检测器并发测试
+//! 多任务并发初始化全局单例 + 检测、共享实例并发检测结果一致性
+
+use std::sync::Arc;
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::detector::{detect, init_global_detector};
+use rswappalyzer::{RuleConfig, TechDetector};
+
+const TASKS: usize = 32;
+
+const BODY: &[u8] = br#"<html><head><meta name="generator" content="WordPress 6.4.2"></head>
+<body><script src="/wp-includes/js/jquery/jquery.min.js?ver=3.7.1"></script></body></html>"#;
+
+fn headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("server", HeaderValue::from_static("nginx/1.25.3"));
+    headers.insert("x-powered-by", HeaderValue::from_static("PHP/8.2.1"));
+    headers
+}
+
+fn tech_names(result: &rswappalyzer::DetectResult) -> Vec<(String, Option<String>)> {
+    let mut names: Vec<_> = result
+        .technologies
+        .iter()
+        .map(|t| (t.name.clone(), t.version.clone()))
+        .collect();
+    names.sort();
+    names
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn test_global_init_storm() {
+    let tasks: Vec<_> = (0..TASKS)
+        .map(|i| {
+            tokio::spawn(async move {
+                // 一半任务显式初始化，一半依赖 detect 懒加载
+                if i % 2 == 0 {
+                    init_global_detector(RuleConfig::default()).await?;
+                }
+                detect(&headers(), &["https://example.com/"], BODY).await
+            })
+        })
+        .collect();
+
+    let mut results = Vec::with_capacity(TASKS);
+    for task in tasks {
+        let result = task.await.expect("task panicked").expect("concurrent init/detect failed");
+        results.push(tech_names(&result));
+    }
+    assert!(!results[0].is_empty());
+    assert!(results.iter().all(|r| *r == results[0]));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn test_shared_detector_storm() {
+    let detector = Arc::new(TechDetector::with_embedded_rules(RuleConfig::default()).unwrap());
+    let expected = tech_names(&detector.detect(&headers(), &["https://example.com/"], BODY).unwrap());
+    assert!(!expected.is_empty());
+
+    let tasks: Vec<_> = (0..TASKS)
+        .map(|_| {
+            let detector = detector.clone();
+            tokio::task::spawn_blocking(move || {
+                detector.detect(&headers(), &["https://example.com/"], BODY).map(|r| tech_names(&r))
+            })
+        })
+        .collect();
+    for task in tasks {
+        assert_eq!(task.await.unwrap().unwrap(), expected);
+    }
+}
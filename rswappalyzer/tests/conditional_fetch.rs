@@ -0,0 +1,68 @@
+//! 远程规则条件请求测试（remote-loader 特性）：本地 axum 服务按 If-None-Match 返回 304
+//! 运行：`cargo test -p rswappalyzer --features remote-loader --test conditional_fetch`
+#![cfg(feature = "remote-loader")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::header::{ETAG, IF_NONE_MATCH, LAST_MODIFIED};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use rswappalyzer::rule::loader::RuleLoader;
+use rswappalyzer::{CustomConfigBuilder, RemoteOptions, RetryPolicy, RuleOrigin};
+
+const RULES: &str = r#"{ "technologies": { "Acme Gateway": { "cats": [22], "headers": { "Server": "acme" } } } }"#;
+
+/// 启动本地规则源：If-None-Match 命中当前 ETag 时返回 304，返回完整下载次数计数器
+async fn serve() -> (String, Arc<AtomicUsize>) {
+    let downloads = Arc::new(AtomicUsize::new(0));
+    let counter = downloads.clone();
+    let app = Router::new().route(
+        "/rules.json",
+        get(move |headers: HeaderMap| {
+            let counter = counter.clone();
+            async move {
+                if headers.get(IF_NONE_MATCH).is_some_and(|etag| etag == "\"v1\"") {
+                    return StatusCode::NOT_MODIFIED.into_response();
+                }
+                counter.fetch_add(1, Ordering::SeqCst);
+                ([(ETAG, "\"v1\""), (LAST_MODIFIED, "Wed, 01 Jan 2025 00:00:00 GMT")], RULES).into_response()
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (format!("http://{}/rules.json", addr), downloads)
+}
+
+#[tokio::test]
+async fn test_conditional_get_uses_cache_on_304() {
+    let (url, downloads) = serve().await;
+    let dir = std::env::temp_dir().join(format!("rswappalyzer-conditional-{}", std::process::id()));
+    let config = CustomConfigBuilder::new()
+        .cache_dir(dir.clone())
+        .origin(RuleOrigin::RemoteCustom(url.clone()))
+        .remote_options(RemoteOptions {
+            urls: vec![url],
+            ..RemoteOptions::new(Duration::from_secs(5), RetryPolicy::Never)
+        })
+        .build();
+
+    let loader = RuleLoader::new();
+    let first = loader.load(&config).await.unwrap();
+    assert!(first.core_tech_map.contains_key("Acme Gateway"));
+    assert_eq!(downloads.load(Ordering::SeqCst), 1);
+
+    // 第二次加载携带 ETag，304 后沿用本地缓存，不再完整下载
+    let second = loader.load(&config).await.unwrap();
+    assert!(second.core_tech_map.contains_key("Acme Gateway"));
+    assert_eq!(downloads.load(Ordering::SeqCst), 1);
+
+    let records = std::fs::read_to_string(dir.join("etag_records.json")).unwrap();
+    assert!(records.contains("Wed, 01 Jan 2025"), "{}", records);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,90 @@
+//! Content-Type 响应体路由测试
+#![cfg(feature = "embedded-rules")]
+
+use http::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+use rswappalyzer::{BodyKind, CustomConfigBuilder, TechDetector};
+
+const SCRIPT_BODY: &[u8] = b"var endpoint = \"https://api.apispreadsheets.com/data\";";
+
+fn detected(detector: &TechDetector, content_type: &'static str) -> bool {
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    let result = detector.detect(&headers, &[], SCRIPT_BODY).unwrap();
+    result.technologies.iter().any(|t| t.name == "API Spreadsheets")
+}
+
+#[test]
+fn test_script_body_routed_to_script_analyzer() {
+    let detector = TechDetector::with_embedded_rules(Default::default()).unwrap();
+    assert!(detected(&detector, "application/javascript; charset=utf-8"));
+    assert!(!detected(&detector, "image/png"));
+
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/css"));
+    assert_eq!(detector.inspect_input(&headers, &[], SCRIPT_BODY).body_kind, BodyKind::Css);
+
+    // 关闭路由后响应体按 HTML 处理（非 HTML 文本被输入守卫丢弃）
+    let config = CustomConfigBuilder::new().content_type_routing(false).build();
+    let legacy = TechDetector::with_embedded_rules(config).unwrap();
+    assert!(!detected(&legacy, "application/javascript"));
+}
+
+#[test]
+fn test_invalid_utf8_script_matched_as_bytes() {
+    let detector = TechDetector::with_embedded_rules(Default::default()).unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/javascript"));
+    // 非法 UTF-8 的脚本按原始字节匹配：版本照常提取，不产生容错解码告警
+    let body = b"/*\xff\xfe*/ importScripts(\"https://www.gstatic.com/firebasejs/9.22.0/firebase-app.js\"); // \xe9";
+    let result = detector.detect(&headers, &[], body).unwrap();
+    let firebase = result.technologies.iter().find(|t| t.name == "Firebase").unwrap();
+    assert_eq!(firebase.version.as_deref(), Some("9.22.0"));
+    assert!(result.warnings.is_empty());
+}
+
+#[cfg(feature = "decompress")]
+#[test]
+fn test_compressed_body_decompressed() {
+    use std::io::Write;
+
+    let gzip = |body: &[u8]| {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(body).unwrap();
+        encoder.finish().unwrap()
+    };
+
+    let config = CustomConfigBuilder::new().decompress_body(true).build();
+    let detector = TechDetector::with_embedded_rules(config).unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/javascript"));
+    // 未声明 Content-Encoding 时按 gzip 魔数识别
+    let result = detector.detect(&headers, &[], &gzip(SCRIPT_BODY)).unwrap();
+    assert!(result.technologies.iter().any(|t| t.name == "API Spreadsheets"));
+
+    // 压缩的 HTML 解压后再送入输入守卫
+    let html = b"<html><head><title>compressed</title></head><body></body></html>";
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/html"));
+    assert_eq!(detector.inspect_input(&headers, &[], &gzip(html)).html_len, html.len());
+
+    // 默认不解压
+    let raw = TechDetector::with_embedded_rules(Default::default()).unwrap();
+    assert_ne!(raw.inspect_input(&headers, &[], &gzip(html)).html_len, html.len());
+}
+
+#[test]
+fn test_small_body_skipped_below_min_len() {
+    let config = CustomConfigBuilder::new().min_body_len(200).build();
+    let detector = TechDetector::with_embedded_rules(config).unwrap();
+    assert!(!detected(&detector, "application/javascript"));
+
+    // 响应体分析被跳过时 Header 作用域照常执行
+    let mut headers = HeaderMap::new();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("text/html"));
+    headers.insert("x-powered-by", HeaderValue::from_static("PHP/8.2.1"));
+    let body = br#"<html><head><meta name="generator" content="WordPress 6.4.2"></head></html>"#;
+    let result = detector.detect(&headers, &[], body).unwrap();
+    assert!(result.technologies.iter().any(|t| t.name == "PHP"));
+    assert!(result.technologies.iter().all(|t| t.name != "WordPress"));
+    assert_eq!(detector.inspect_input(&headers, &[], body).html_len, 0);
+    assert!(result.warnings.is_empty());
+}
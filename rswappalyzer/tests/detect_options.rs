@@ -0,0 +1,42 @@
+//! 单次检测选项测试（全局单例构建器/作用域过滤/耗时上限）
+
+use std::time::Duration;
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::detector::{detect_builder, detect_with};
+use rswappalyzer::{DetectInput, DetectOptions, PruneScope};
+
+const BODY: &[u8] = br#"<html><head><meta name="generator" content="WordPress 6.4.2"></head><body></body></html>"#;
+
+fn headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-powered-by", HeaderValue::from_static("PHP/8.2.1"));
+    headers
+}
+
+#[tokio::test]
+async fn test_detect_builder_scopes() {
+    let all = detect_builder()
+        .headers(headers())
+        .url("https://example.com/")
+        .body(BODY)
+        .run()
+        .await
+        .unwrap();
+    assert!(all.technologies.iter().any(|t| t.name == "PHP"));
+    assert!(all.technologies.iter().any(|t| t.name == "WordPress"));
+
+    let header_only = detect_builder()
+        .headers(headers())
+        .body(BODY)
+        .scopes([PruneScope::Header])
+        .run()
+        .await
+        .unwrap();
+    assert!(header_only.technologies.iter().any(|t| t.name == "PHP"));
+    assert!(header_only.technologies.iter().all(|t| !t.matched_in(PruneScope::Meta)));
+
+    let input = DetectInput::new(headers(), vec![], BODY);
+    let timed_out = detect_with(&DetectOptions::default().timeout(Duration::ZERO), &input).await;
+    assert!(timed_out.is_err());
+}
@@ -0,0 +1,98 @@
+//! DOM 选择器规则测试
+
+use http::header::HeaderMap;
+use rswappalyzer::{DetectOptions, EvidenceCapture, PruneScope, RuleConfig, TechDetector};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::{DomKey, DomTarget, RuleProcessor};
+
+const RULES: &str = r#"{
+    "technologies": {
+        "Angular": { "cats": [12], "dom": { "[ng-version]": { "attributes": { "ng-version": "^([\\d\\.]+)\\;version:\\1" } } } },
+        "Apple Sign-in": { "cats": [69], "dom": { "button": { "text": "(?:Sign in|Continue) with Apple" } } },
+        "Model Viewer": { "cats": [19], "dom": "model-viewer" },
+        "Widget": { "cats": [19], "dom": { ".AcmeWidget": { "exists": "\\;confidence:50" } } },
+        "Sibling": { "cats": [19], "dom": ["h1 + p"] }
+    }
+}"#;
+
+fn detector() -> TechDetector {
+    let raw = WappalyzerParser.parse_to_rule_lib(RULES).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+    TechDetector::with_rules(rules, RuleConfig::default()).unwrap()
+}
+
+fn detect(detector: &TechDetector, html: &str) -> Vec<(String, u8, Option<String>)> {
+    let mut techs: Vec<_> = detector
+        .detect(&HeaderMap::new(), &[], html.as_bytes())
+        .unwrap()
+        .technologies
+        .into_iter()
+        .map(|t| (t.name, t.confidence, t.version))
+        .collect();
+    techs.sort();
+    techs
+}
+
+#[test]
+fn test_dom_key_round_trip() {
+    for key in [
+        DomKey::new("a[href*='x']", DomTarget::Exists),
+        DomKey::new("head > title", DomTarget::Text),
+        DomKey::new("[ng-version]", DomTarget::Attribute("ng-version".to_string())),
+    ] {
+        assert_eq!(DomKey::parse(&key.to_string()), key);
+    }
+    // 属性值中的 " @" 不视为属性检查
+    assert_eq!(DomKey::parse("a[title=' @x]']").target, DomTarget::Exists);
+}
+
+#[test]
+fn test_dom_rules_detected() {
+    let detector = detector();
+    let lib = detector.compiled_library();
+    assert!(lib.tech_patterns["Angular"].dom_patterns.as_ref().unwrap().contains_key("[ng-version] @ng-version"));
+
+    let html = r#"<html><body>
+        <app-root ng-version="17.3.1"></app-root>
+        <button><span>Continue</span> with Apple</button>
+        <model-viewer src="a.glb"></model-viewer>
+        <div class="AcmeWidget"></div>
+        <h1>t</h1><p>x</p>
+    </body></html>"#;
+    assert_eq!(
+        detect(&detector, html),
+        [
+            ("Angular".to_string(), 100, Some("17.3.1".to_string())),
+            ("Apple Sign-in".to_string(), 85, None),
+            ("Model Viewer".to_string(), 85, None),
+            ("Widget".to_string(), 50, None),
+        ]
+    );
+
+    // 类名大小写敏感；文本不匹配时不检出
+    let html = r#"<html><body><div class="acmewidget"></div><button>Sign in with Google</button></body></html>"#;
+    assert!(detect(&detector, html).is_empty());
+
+    let result = detector
+        .detect(&HeaderMap::new(), &[], br#"<html><model-viewer></model-viewer></html>"#)
+        .unwrap();
+    assert_eq!(result.technologies[0].matched_scopes, [PruneScope::Dom]);
+}
+
+#[test]
+fn test_dom_scope_and_evidence() {
+    let detector = detector();
+    let html = br#"<html><app-root ng-version="17.3.1"></app-root></html>"#;
+
+    let options = DetectOptions::default().scopes([PruneScope::Html]);
+    let result = detector.detect_with_options(&HeaderMap::new(), &[], html, &options).unwrap();
+    assert!(result.technologies.is_empty());
+
+    let options = DetectOptions::default().evidence(EvidenceCapture::default());
+    let result = detector.detect_with_options(&HeaderMap::new(), &[], html, &options).unwrap();
+    let evidence = &result.technologies[0].evidence;
+    assert_eq!(evidence.len(), 1);
+    assert_eq!(evidence[0].scope, PruneScope::Dom);
+    assert_eq!(evidence[0].key.as_deref(), Some("[ng-version] @ng-version"));
+    assert_eq!(evidence[0].matched, "17.3.1");
+}
@@ -0,0 +1,53 @@
+//! 证据采集测试（采样/置信度阈值/字节预算）
+#![cfg(feature = "embedded-rules")]
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::{DetectOptions, DetectResult, DetectWarning, EvidenceCapture, PruneScope, TechDetector};
+
+const BODY: &[u8] = br#"<html><head><meta name="generator" content="WordPress 6.4.2"></head><body></body></html>"#;
+
+fn detect(detector: &TechDetector, capture: EvidenceCapture) -> DetectResult {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-powered-by", HeaderValue::from_static("PHP/8.2.1"));
+    let options = DetectOptions::default().evidence(capture);
+    detector
+        .detect_with_options(&headers, &["https://example.com/"], BODY, &options)
+        .unwrap()
+}
+
+#[test]
+fn test_evidence_capture() {
+    let detector = TechDetector::with_embedded_rules(Default::default()).unwrap();
+    let result = detect(&detector, EvidenceCapture::default());
+    let php = result.technologies.iter().find(|t| t.name == "PHP").unwrap();
+    let evidence = php.evidence.iter().find(|e| e.scope == PruneScope::Header).unwrap();
+    assert_eq!(evidence.key.as_deref(), Some("x-powered-by"));
+    assert!(evidence.matched.contains("PHP"), "{:?}", evidence);
+    let wordpress = result.technologies.iter().find(|t| t.name == "WordPress").unwrap();
+    assert!(wordpress.evidence.iter().any(|e| e.scope == PruneScope::Meta));
+
+    // 未开启证据采集时不记录
+    let plain = detector
+        .detect_with_options(&HeaderMap::new(), &[], BODY, &DetectOptions::default())
+        .unwrap();
+    assert!(plain.technologies.iter().all(|t| t.evidence.is_empty()));
+}
+
+#[test]
+fn test_evidence_sampling_and_budget() {
+    let detector = TechDetector::with_embedded_rules(Default::default()).unwrap();
+    let sampled = EvidenceCapture::default().sample_every(2);
+    let first = detect(&detector, sampled.clone());
+    let second = detect(&detector, sampled);
+    assert!(first.technologies.iter().any(|t| !t.evidence.is_empty()));
+    assert!(second.technologies.iter().all(|t| t.evidence.is_empty()));
+
+    // 置信度阈值：满置信度的技术不采集
+    let low = detect(&detector, EvidenceCapture::default().below_confidence(100));
+    assert!(low.technologies.iter().all(|t| t.confidence < 100 || t.evidence.is_empty()));
+
+    // 预算耗尽：丢弃并告警
+    let tight = detect(&detector, EvidenceCapture::default().max_bytes(8));
+    assert!(tight.technologies.iter().all(|t| t.evidence.is_empty()));
+    assert!(tight.warnings.iter().any(|w| matches!(w, DetectWarning::EvidenceTruncated(n) if *n > 0)));
+}
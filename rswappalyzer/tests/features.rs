@@ -0,0 +1,33 @@
+//! 编译特性报告与加法式特性测试（任意特性组合下均应编译并通过）
+
+use rswappalyzer::{features, RswappalyzerError, RuleConfig, TechDetector, Technology};
+
+#[test]
+fn test_feature_report() {
+    let report = features();
+    assert_eq!(report.is_enabled("embedded-rules"), cfg!(feature = "embedded-rules"));
+    assert_eq!(report.is_enabled("full-meta"), cfg!(feature = "full-meta"));
+    assert!(!report.is_enabled("no-such-feature"));
+    assert!(report.iter().any(|(name, _)| name == "remote-loader"));
+    assert!(report.enabled().iter().all(|name| report.is_enabled(name)));
+
+    // 内置规则接口始终存在，未开启特性时返回 FeatureDisabled
+    match TechDetector::with_embedded_rules(RuleConfig::default()) {
+        Ok(_) => assert!(report.is_enabled("embedded-rules")),
+        Err(e) => assert!(matches!(e, RswappalyzerError::FeatureDisabled(_))),
+    }
+}
+
+#[test]
+fn test_meta_fields_are_optional() {
+    // 元信息字段与 full-meta 无关，始终可读写
+    let mut tech = Technology::from_name("Nginx".to_string());
+    assert!(tech.website.is_none());
+    tech.website = Some("https://nginx.org".to_string());
+    tech.categories = vec!["Web servers".to_string()];
+    let json = serde_json::to_string(&tech).unwrap();
+    assert!(json.contains("nginx.org"));
+    assert!(!json.contains("description"));
+    let parsed: Technology = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.website, tech.website);
+}
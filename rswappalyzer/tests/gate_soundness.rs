@@ -0,0 +1,152 @@
+//! 剪枝门槛健全性属性测试（无误剪枝）
+//! 对规则库的每条模式随机生成可被匹配器命中的输入，断言剪枝门槛（struct_prune + MatchGate）放行：
+//! 1. 正则模式：按匹配器的大小写语义生成 ASCII 命中串（生成器不支持的语法跳过），仅保留匹配器确认命中的样本
+//! 2. 包含模式：字面量本身即命中串
+//! 3. 令牌集合仅取样本自身的令牌（最严格情形，真实输入的令牌集合只会更大）
+//!
+//! 全量内置规则库的检查为长时测试（默认忽略）：
+//! `cargo test -p rswappalyzer --test gate_soundness --features provenance -- --ignored`
+//! GATE_SOUNDNESS_SAMPLES 设置每条模式的采样数；开启 provenance 特性时违规报告附带模式来源（规则源/技术/作用域/序号）
+#![cfg(feature = "embedded-rules")]
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use regex_syntax::hir::{Hir, HirKind};
+use rswappalyzer::utils::extractor::token_extract_zh::extract_input_tokens;
+use rswappalyzer::{RuleConfig, TechDetector};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::{CompiledPattern, CompiledRuleLibrary, MatchGate, Matcher, RuleProcessor};
+
+/// 默认每条模式的采样数
+const DEFAULT_SAMPLES: usize = 8;
+/// 每个样本的最大生成次数（非 ASCII / 未命中的样本丢弃）
+const ATTEMPTS: usize = 16;
+/// 生成器的最大重复次数（`*`/`+` 上限）
+const MAX_REPEAT: u32 = 6;
+/// 报告中最多列出的违规条数
+const MAX_REPORTED: usize = 50;
+
+/// 为模式生成命中样本（不支持的模式返回空）
+fn samples(pattern: &CompiledPattern, rng: &mut StdRng, count: usize) -> Vec<String> {
+    match pattern.exec.get_matcher() {
+        Matcher::Contains(literal) => vec![literal.to_string()],
+        Matcher::LazyRegex { pattern: regex, case_insensitive } => {
+            // 按字节语义解析（\w/\d/大小写折叠仅限 ASCII），避免生成真实流量中不会出现的 Unicode 折叠字符
+            let Ok(hir) = regex_syntax::ParserBuilder::new()
+                .case_insensitive(*case_insensitive)
+                .unicode(false)
+                .utf8(false)
+                .build()
+                .parse(regex)
+            else {
+                return Vec::new();
+            };
+            let Ok(generator) = rand_regex::Regex::with_hir(strip_looks(hir), MAX_REPEAT) else {
+                return Vec::new();
+            };
+            (0..count * ATTEMPTS)
+                .filter_map(|_| String::from_utf8(rng.sample::<Vec<u8>, _>(&generator)).ok())
+                .filter(|sample| sample.is_ascii() && pattern.matches(sample))
+                .take(count)
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// 去除断言（^/$/\b 等，生成器不支持；不满足断言的样本由匹配器复核丢弃）
+fn strip_looks(hir: Hir) -> Hir {
+    match hir.into_kind() {
+        HirKind::Look(_) => Hir::empty(),
+        HirKind::Repetition(mut rep) => {
+            rep.sub = Box::new(strip_looks(*rep.sub));
+            Hir::repetition(rep)
+        }
+        HirKind::Capture(mut cap) => {
+            cap.sub = Box::new(strip_looks(*cap.sub));
+            Hir::capture(cap)
+        }
+        HirKind::Concat(subs) => Hir::concat(subs.into_iter().map(strip_looks).collect()),
+        HirKind::Alternation(subs) => Hir::alternation(subs.into_iter().map(strip_looks).collect()),
+        HirKind::Literal(literal) => Hir::literal(literal.0),
+        HirKind::Class(class) => Hir::class(class),
+        HirKind::Empty => Hir::empty(),
+    }
+}
+
+/// 检查规则库全部模式的门槛健全性
+/// 返回：(检查的样本数, 违规描述列表（每条模式至多一条）)
+fn check_library(lib: &CompiledRuleLibrary, count: usize) -> (usize, Vec<String>) {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    let mut checked = 0usize;
+    let mut violations = Vec::new();
+    let mut names: Vec<&String> = lib.tech_patterns.keys().collect();
+    names.sort();
+    for name in names {
+        lib.tech_patterns[name].for_each_pattern(|pattern| {
+            for sample in samples(pattern, &mut rng, count) {
+                checked += 1;
+                if !pattern.prune_check(&sample, &extract_input_tokens(&sample)) {
+                    violations.push(format!(
+                        "{} ({:?}): {} | gate {:?} | input {:?}",
+                        name,
+                        pattern.scope,
+                        pattern.describe_rule(),
+                        pattern.exec.match_gate,
+                        sample
+                    ));
+                    break;
+                }
+            }
+        });
+    }
+    (checked, violations)
+}
+
+fn report(checked: usize, violations: &[String]) -> String {
+    format!(
+        "{} pattern(s) pruned inputs they match ({} samples checked):\n{}",
+        violations.len(),
+        checked,
+        violations.iter().take(MAX_REPORTED).cloned().collect::<Vec<_>>().join("\n")
+    )
+}
+
+#[test]
+fn test_checker_reports_unsound_gates() {
+    let rules = r#"{
+        "technologies": {
+            "Acme": { "cats": [22], "headers": { "X-Acme": "^acme-gateway/([\\d.]+)\\;version:\\1" } }
+        }
+    }"#;
+    let raw = WappalyzerParser.parse_to_rule_lib(rules).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+    let detector = TechDetector::with_rules(rules, RuleConfig::default()).unwrap();
+    let (checked, violations) = check_library(detector.compiled_library(), DEFAULT_SAMPLES);
+    assert!(checked > 0);
+    assert!(violations.is_empty(), "{}", report(checked, &violations));
+
+    // 人为收紧门槛（要求输入中不存在的令牌）后应报告违规
+    let mut lib = CompiledRuleLibrary::clone(detector.compiled_library());
+    for tech in lib.tech_patterns.values_mut() {
+        tech.for_each_pattern_mut(|pattern| {
+            pattern.exec.match_gate = MatchGate::RequireAll(["missing".to_string()].into_iter().collect());
+        });
+    }
+    let (_, violations) = check_library(&lib, DEFAULT_SAMPLES);
+    assert_eq!(violations.len(), 1);
+    assert!(violations[0].starts_with("Acme (Header)"), "{}", violations[0]);
+}
+
+#[test]
+#[ignore = "long-form: samples every pattern of the embedded library"]
+fn test_embedded_gates_admit_matching_inputs() {
+    let count = std::env::var("GATE_SOUNDNESS_SAMPLES")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(DEFAULT_SAMPLES);
+    let detector = TechDetector::with_embedded_rules(RuleConfig::default()).unwrap();
+    let (checked, violations) = check_library(detector.compiled_library(), count);
+    assert!(checked > 0);
+    assert!(violations.is_empty(), "{}", report(checked, &violations));
+}
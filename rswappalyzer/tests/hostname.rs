@@ -0,0 +1,42 @@
+//! 主机名后缀作用域测试
+
+use http::header::HeaderMap;
+use rswappalyzer::{PruneScope, RuleConfig, TechDetector};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::RuleProcessor;
+
+const RULES: &str = r#"{
+    "technologies": {
+        "GitHub Pages": { "cats": [62], "hostname": "*.github.io" },
+        "Heroku": { "cats": [62], "url": "^https?://[^/]+\\.herokuapp\\.com" },
+        "Netlify": { "cats": [62], "hostnames": ["netlify.app"] }
+    }
+}"#;
+
+fn detect(detector: &TechDetector, url: &str) -> Vec<String> {
+    let result = detector.detect(&HeaderMap::new(), &[url], b"").unwrap();
+    result.technologies.into_iter().map(|t| t.name).collect()
+}
+
+#[test]
+fn test_hostname_suffix_detection() {
+    let raw = WappalyzerParser.parse_to_rule_lib(RULES).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+    let detector = TechDetector::with_rules(rules, RuleConfig::default()).unwrap();
+
+    let lib = detector.compiled_library();
+    assert_eq!(lib.hostname_index.len(), 3);
+    // 主机名后缀型 URL 正则转入主机名索引
+    assert!(lib.tech_patterns["Heroku"].url_patterns.is_none());
+
+    assert_eq!(detect(&detector, "https://octocat.github.io/hello"), ["GitHub Pages"]);
+    assert!(detect(&detector, "https://github.io/").is_empty());
+    assert!(detect(&detector, "https://x.github.io.evil.com/").is_empty());
+    assert_eq!(detect(&detector, "http://demo.herokuapp.com:8080/"), ["Heroku"]);
+    assert_eq!(detect(&detector, "https://netlify.app"), ["Netlify"]);
+    assert_eq!(detect(&detector, "https://a.b.netlify.app/x"), ["Netlify"]);
+
+    let result = detector.detect(&HeaderMap::new(), &["https://octocat.github.io/"], b"").unwrap();
+    assert_eq!(result.technologies[0].matched_scopes, [PruneScope::Hostname]);
+    assert!(!result.technologies[0].matched_in(PruneScope::Header));
+}
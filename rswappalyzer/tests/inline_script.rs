@@ -0,0 +1,73 @@
+//! 内联脚本检测测试：`scripts` 规则匹配页面内联 `<script>` 内容（外部脚本地址之外的独立数据源）
+
+use http::header::HeaderMap;
+use rswappalyzer::{CustomConfigBuilder, DetectWarning, ExtractLimits, RuleConfig, TechDetector};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::RuleProcessor;
+
+const RULES: &str = r#"{
+    "technologies": {
+        "Google Analytics": { "cats": [10], "scripts": "gtag\\(['\"]config['\"], ['\"]G-" },
+        "WordPress": { "cats": [1], "scripts": "wp-emoji-release\\.min\\.js\\?ver=([\\d.]+)\\;version:\\1" },
+        "jQuery": { "cats": [59], "scriptSrc": "jquery[.-]([\\d.]+)\\.min\\.js\\;version:\\1" }
+    }
+}"#;
+
+const PAGE: &str = r#"<html><head>
+<script src="/js/jquery-3.7.1.min.js"></script>
+<script>window._wpemojiSettings = {"source":{"concatemoji":"/wp-includes/js/wp-emoji-release.min.js?ver=6.4.2"}};</script>
+<script>window.dataLayer = window.dataLayer || []; gtag('config', 'G-ABC123');</script>
+</head><body></body></html>"#;
+
+fn detector(config: RuleConfig) -> TechDetector {
+    let raw = WappalyzerParser.parse_to_rule_lib(RULES).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+    TechDetector::with_rules(rules, config).unwrap()
+}
+
+fn techs(detector: &TechDetector, body: &[u8]) -> Vec<(String, Option<String>)> {
+    let result = detector.detect(&HeaderMap::new(), &[], body).unwrap();
+    let mut techs: Vec<_> = result
+        .technologies
+        .into_iter()
+        .map(|tech| (tech.name, tech.version))
+        .collect();
+    techs.sort();
+    techs
+}
+
+#[test]
+fn test_inline_scripts_detected() {
+    let detector = detector(RuleConfig::default());
+    assert_eq!(
+        techs(&detector, PAGE.as_bytes()),
+        [
+            ("Google Analytics".to_string(), None),
+            ("WordPress".to_string(), Some("6.4.2".to_string())),
+            ("jQuery".to_string(), Some("3.7.1".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn test_inline_scripts_disabled() {
+    let limits = ExtractLimits {
+        max_inline_script_bytes: 0,
+        ..ExtractLimits::default()
+    };
+    let detector = detector(CustomConfigBuilder::new().extract_limits(limits).build());
+    assert_eq!(techs(&detector, PAGE.as_bytes()), [("jQuery".to_string(), Some("3.7.1".to_string()))]);
+}
+
+#[test]
+fn test_inline_scripts_truncated() {
+    let limits = ExtractLimits {
+        max_inline_script_bytes: 128,
+        ..ExtractLimits::default()
+    };
+    let detector = detector(CustomConfigBuilder::new().extract_limits(limits).build());
+    let result = detector.detect(&HeaderMap::new(), &[], PAGE.as_bytes()).unwrap();
+    let names: Vec<_> = result.technologies.iter().map(|tech| tech.name.as_str()).collect();
+    assert!(names.contains(&"WordPress") && !names.contains(&"Google Analytics"));
+    assert_eq!(result.warnings, [DetectWarning::Truncated("inline_scripts".to_string())]);
+}
@@ -0,0 +1,82 @@
+//! 准入字面量自动机测试：显式传入的扫描结果与逐模式 str::contains / 令牌查找一致
+//! 运行：`cargo test -p rswappalyzer --test literal_prefilter`
+#![cfg(feature = "embedded-rules")]
+
+use rswappalyzer::utils::extractor::token_extract_zh::extract_input_tokens;
+use rswappalyzer::{RuleConfig, TechDetector};
+use rswappalyzer_engine::scope_pruner::PruneScope;
+use rswappalyzer_engine::{CompiledPattern, CompiledRuleLibrary, MatchGate};
+
+/// 作用域内满足条件的模式
+fn gated(lib: &CompiledRuleLibrary, scope: PruneScope, filter: impl Fn(&MatchGate) -> bool) -> Vec<&CompiledPattern> {
+    lib.tech_patterns
+        .values()
+        .flat_map(|tech| match scope {
+            PruneScope::Html => tech.html_patterns.iter().flatten(),
+            _ => tech.script_patterns.iter().flatten(),
+        })
+        .filter(|pat| filter(&pat.exec.match_gate))
+        .collect()
+}
+
+/// 扫描结果与逐模式校验一致，且输入同时包含放行与剪枝的模式
+fn assert_hits_agree(lib: &CompiledRuleLibrary, scope: PruneScope, gated: &[&CompiledPattern], input: &str) {
+    let tokens = extract_input_tokens(input);
+    let hits = lib.literal_hits(scope, input).expect("scope has a prefilter");
+    let expected: Vec<bool> = gated.iter().map(|pat| pat.exec.match_gate.check(input, &tokens)).collect();
+    let scanned: Vec<bool> = gated
+        .iter()
+        .map(|pat| pat.exec.match_gate.check_with_hits(input, &tokens, Some(&hits)))
+        .collect();
+    assert_eq!(scanned, expected);
+    assert!(expected.iter().any(|hit| *hit));
+    assert!(expected.iter().any(|hit| !*hit));
+}
+
+#[test]
+fn test_literal_hits_agree_with_substring_search() {
+    let detector = TechDetector::with_embedded_rules(RuleConfig::default()).unwrap();
+    let lib = detector.compiled_library();
+    assert!(lib.has_literal_prefilters());
+
+    for scope in [PruneScope::Html, PruneScope::Script] {
+        assert!(!lib.literal_prefilter(scope).expect("scope has literal gates").is_empty());
+
+        // 取每隔一条模式的首个字面量拼接输入：一半模式放行、一半剪枝
+        let any = gated(lib, scope, |gate| matches!(gate, MatchGate::RequireAnyLiteral(_)));
+        let input: String = any
+            .iter()
+            .step_by(2)
+            .filter_map(|pat| match &pat.exec.match_gate {
+                MatchGate::RequireAnyLiteral(list) => list.first().map(|literal| format!("{} ", literal)),
+                _ => None,
+            })
+            .collect();
+        assert_hits_agree(lib, scope, &any, &input);
+
+        // RequireAll：令牌以大写写入输入，自动机忽略 ASCII 大小写，与小写令牌集合的校验一致
+        let all = gated(lib, scope, |gate| matches!(gate, MatchGate::RequireAll(set) if !set.is_empty()));
+        let input: String = all
+            .iter()
+            .step_by(2)
+            .filter_map(|pat| match &pat.exec.match_gate {
+                MatchGate::RequireAll(set) => {
+                    Some(set.iter().map(|token| format!("{} ", token.to_ascii_uppercase())).collect::<String>())
+                }
+                _ => None,
+            })
+            .collect();
+        assert_hits_agree(lib, scope, &all, &input);
+    }
+}
+
+#[test]
+fn test_deserialized_library_builds_prefilters() {
+    let detector = TechDetector::with_embedded_rules(RuleConfig::default()).unwrap();
+    let json = serde_json::to_vec(detector.compiled_library().as_ref()).unwrap();
+    let lib: CompiledRuleLibrary = serde_json::from_slice(&json).unwrap();
+    assert!(!lib.has_literal_prefilters());
+
+    let detector = TechDetector::with_compiled_lib(lib, Default::default(), RuleConfig::default());
+    assert!(detector.compiled_library().has_literal_prefilters());
+}
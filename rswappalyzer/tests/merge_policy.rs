@@ -0,0 +1,77 @@
+//! 多规则源同名技术合并策略测试（remote-loader 特性）：本地 axum 服务提供两个含同名技术的规则源
+//! 运行：`cargo test -p rswappalyzer --features remote-loader --test merge_policy`
+#![cfg(feature = "remote-loader")]
+
+use std::time::Duration;
+
+use axum::routing::get;
+use axum::Router;
+use rswappalyzer::rule::loader::RemoteRuleFetcher;
+use rswappalyzer::{FetchMode, MergePolicy, RemoteOptions, RemoteSource, RetryPolicy};
+use rswappalyzer_engine::{MatchScope, RuleLibrary};
+
+const OFFICIAL: &str = r#"{ "technologies": {
+    "Acme Gateway": { "cats": [22], "headers": { "Server": "acme" } },
+    "Only Official": { "cats": [1], "html": "official-marker" }
+} }"#;
+const INTERNAL: &str = r#"{ "technologies": {
+    "Acme Gateway": { "cats": [31], "headers": { "X-Acme": "" }, "implies": "Nginx" },
+    "Only Internal": { "cats": [1], "html": "internal-marker" }
+} }"#;
+
+/// 启动本地规则源，返回两个规则源URL
+async fn serve() -> (String, String) {
+    let app = Router::new()
+        .route("/official.json", get(|| async { OFFICIAL }))
+        .route("/internal.json", get(|| async { INTERNAL }));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (format!("http://{}/official.json", addr), format!("http://{}/internal.json", addr))
+}
+
+async fn fetch(policy: MergePolicy) -> rswappalyzer::RswResult<RuleLibrary> {
+    let (official, internal) = serve().await;
+    let opts = RemoteOptions::new(Duration::from_secs(5), RetryPolicy::Never)
+        .with_source(RemoteSource::new(internal).with_name("internal"))
+        .with_mode(FetchMode::Merge)
+        .with_merge_policy(policy);
+    RemoteRuleFetcher.fetch_multi_source(&official, &opts).await
+}
+
+fn header_keys(rule_lib: &RuleLibrary) -> Vec<String> {
+    let mut keys: Vec<String> = rule_lib.core_tech_map["Acme Gateway"].match_rules[&MatchScope::Header]
+        .keyed_patterns
+        .iter()
+        .map(|keyed| keyed.key.to_lowercase())
+        .collect();
+    keys.sort();
+    keys
+}
+
+#[tokio::test]
+async fn test_prefer_first_and_last() {
+    let first = fetch(MergePolicy::PreferFirst).await.unwrap();
+    assert_eq!(header_keys(&first), ["server"]);
+    assert_eq!(first.core_tech_map["Acme Gateway"].basic.category_ids, [22]);
+    assert!(first.core_tech_map.contains_key("Only Official") && first.core_tech_map.contains_key("Only Internal"));
+
+    let last = fetch(MergePolicy::PreferLast).await.unwrap();
+    assert_eq!(header_keys(&last), ["x-acme"]);
+    assert_eq!(last.core_tech_map["Acme Gateway"].basic.source.as_deref(), Some("internal"));
+}
+
+#[tokio::test]
+async fn test_merge_patterns() {
+    let merged = fetch(MergePolicy::MergePatterns).await.unwrap();
+    assert_eq!(header_keys(&merged), ["server", "x-acme"]);
+    let basic = &merged.core_tech_map["Acme Gateway"].basic;
+    assert_eq!(basic.category_ids, [22, 31]);
+    assert_eq!(basic.implies.as_deref(), Some(&["Nginx".to_string()][..]));
+}
+
+#[tokio::test]
+async fn test_fail_on_conflict() {
+    let err = fetch(MergePolicy::FailOnConflict).await.unwrap_err();
+    assert!(err.to_string().contains("Acme Gateway"), "{}", err);
+}
@@ -0,0 +1,51 @@
+//! Meta 键名测试：OpenGraph `property=` 与 `http-equiv=` 标签按键名匹配 meta 规则
+
+use http::header::HeaderMap;
+use rswappalyzer::{RuleConfig, TechDetector};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::RuleProcessor;
+
+const RULES: &str = r#"{
+    "technologies": {
+        "Ghost": { "cats": [1], "meta": { "og:site_name": "^Ghost Blog$" } },
+        "Joomla": { "cats": [1], "meta": { "generator": "Joomla!(?: ([\\d.]+))?\\;version:\\1" } },
+        "Shopify": { "cats": [6], "meta": { "shopify-checkout-api-token": "" } }
+    }
+}"#;
+
+fn detect(body: &str) -> Vec<(String, Option<String>)> {
+    let raw = WappalyzerParser.parse_to_rule_lib(RULES).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+    let detector = TechDetector::with_rules(rules, RuleConfig::default()).unwrap();
+    let result = detector.detect(&HeaderMap::new(), &[], body.as_bytes()).unwrap();
+    let mut techs: Vec<_> = result
+        .technologies
+        .into_iter()
+        .map(|tech| (tech.name, tech.version))
+        .collect();
+    techs.sort();
+    techs
+}
+
+#[test]
+fn test_property_and_http_equiv_metas() {
+    let body = r#"<html><head>
+        <meta property="og:site_name" content="Ghost Blog">
+        <meta http-equiv="Generator" content="Joomla! 4.2">
+        <meta property="shopify-checkout-api-token" content="abc">
+    </head><body></body></html>"#;
+    assert_eq!(
+        detect(body),
+        [
+            ("Ghost".to_string(), None),
+            ("Joomla".to_string(), Some("4.2".to_string())),
+            ("Shopify".to_string(), None),
+        ]
+    );
+}
+
+#[test]
+fn test_name_takes_precedence() {
+    let body = r#"<meta name="description" property="og:site_name" content="Ghost Blog">"#;
+    assert!(detect(body).is_empty());
+}
@@ -0,0 +1,63 @@
+//! Nuclei 模板导入测试（header/body 匹配器转换后参与检测）
+#![cfg(feature = "yaml-rules")]
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::{RuleConfig, TechDetector};
+use rswappalyzer_engine::source::NucleiImporter;
+use rswappalyzer_engine::RuleProcessor;
+
+const NGINX: &str = r#"
+id: nginx-detect
+info:
+  name: Nginx Detect
+http:
+  - method: GET
+    path: ["{{BaseURL}}"]
+    matchers-condition: and
+    matchers:
+      - type: regex
+        part: header
+        regex: ["(?i)Server: nginx/?([0-9.]+)?"]
+      - type: status
+        status: [200]
+"#;
+
+const GRAFANA: &str = r#"
+id: grafana-detect
+info:
+  name: Grafana - Detection
+requests:
+  - path: ["{{BaseURL}}"]
+    matchers:
+      - type: word
+        words: ["grafana-app", "window.grafanaBootData"]
+"#;
+
+const LOGIN_PAGE: &str = r#"
+id: acme-login-panel
+info:
+  name: Acme Login Panel
+http:
+  - path: ["{{BaseURL}}/admin/login"]
+    matchers:
+      - type: word
+        words: ["Acme Admin"]
+"#;
+
+#[test]
+fn test_nuclei_templates_drive_detection() {
+    let import = NucleiImporter.import_templates([NGINX, GRAFANA, LOGIN_PAGE]);
+    assert_eq!(import.skipped.len(), 1);
+    assert_eq!(import.skipped[0].id, "acme-login-panel");
+
+    let rules = RuleProcessor.clean_and_split_rules(&import.rule_lib).unwrap();
+    let detector = TechDetector::with_rules(rules, RuleConfig::default()).unwrap();
+
+    let mut headers = HeaderMap::new();
+    headers.insert("server", HeaderValue::from_static("nginx/1.25.3"));
+    let body = b"<html><body><grafana-app></grafana-app></body></html>";
+    let result = detector.detect(&headers, &[], body).unwrap();
+    let mut names: Vec<_> = result.technologies.iter().map(|t| t.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, ["Grafana", "Nginx"]);
+}
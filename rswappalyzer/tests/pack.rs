@@ -0,0 +1,95 @@
+//! 预编译规则包读写测试
+//! mmap 加载路径：`cargo test -p rswappalyzer --features mmap --test pack`
+#![cfg(feature = "embedded-rules")]
+
+use std::path::PathBuf;
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::{read_compiled_pack, write_compiled_pack, RuleConfig, RuleSource, TechDetector};
+
+fn pack_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rswappalyzer-{}-{}.pack", name, std::process::id()))
+}
+
+fn tech_names(detector: &TechDetector) -> Vec<String> {
+    let mut headers = HeaderMap::new();
+    headers.insert("server", HeaderValue::from_static("nginx/1.25.3"));
+    let result = detector.detect(&headers, &["https://example.com/"], b"").unwrap();
+    let mut names: Vec<_> = result.technologies.iter().map(|t| t.name.clone()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_compiled_pack_roundtrip() {
+    let embedded = TechDetector::with_embedded_rules(RuleConfig::default()).unwrap();
+    let path = pack_path("roundtrip");
+    write_compiled_pack(embedded.compiled_library(), &path).unwrap();
+
+    let lib = read_compiled_pack(&path).unwrap();
+    assert_eq!(lib.tech_patterns.len(), embedded.compiled_library().tech_patterns.len());
+
+    let detector = TechDetector::from_compiled_pack(&path, RuleConfig::default()).unwrap();
+    assert_eq!(detector.capabilities().rule_source, RuleSource::Provided);
+    assert_eq!(tech_names(&detector), tech_names(&embedded));
+
+    #[cfg(feature = "mmap")]
+    {
+        let mapped = rswappalyzer::map_compiled_pack(&path).unwrap();
+        assert_eq!(mapped.len() as u64, std::fs::metadata(&path).unwrap().len());
+        let first = mapped.library().unwrap();
+        assert!(std::sync::Arc::ptr_eq(&first, &mapped.library().unwrap()));
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_compiled_artifact_roundtrip() {
+    let embedded = TechDetector::with_embedded_rules(RuleConfig::default()).unwrap();
+    let path = pack_path("artifact");
+    write_compiled_pack(embedded.compiled_library(), &path).unwrap();
+
+    let detector = TechDetector::with_compiled_artifact(&path).unwrap();
+    assert_eq!(detector.capabilities().rule_source, RuleSource::Provided);
+    assert_eq!(
+        detector.compiled_library().engine_requirements(),
+        embedded.compiled_library().engine_requirements()
+    );
+    assert_eq!(tech_names(&detector), tech_names(&embedded));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_compiled_pack_bad_magic() {
+    let path = pack_path("bad-magic");
+    std::fs::write(&path, b"{\"tech_patterns\":{}}").unwrap();
+    assert!(read_compiled_pack(&path).is_err());
+    assert!(TechDetector::from_compiled_pack(&path, RuleConfig::default()).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_compiled_pack_newer_engine() {
+    let embedded = TechDetector::with_embedded_rules(RuleConfig::default()).unwrap();
+    embedded.verify_compatibility().unwrap();
+    let requirements = embedded.compiled_library().engine_requirements();
+    assert_eq!(requirements.built_with, rswappalyzer_engine::ENGINE_VERSION);
+
+    let mut lib = (**embedded.compiled_library()).clone();
+    lib.engine.built_with = "99.0.0".to_string();
+    lib.engine.min_engine_version = "99.0.0".to_string();
+    let path = pack_path("newer-engine");
+    write_compiled_pack(&lib, &path).unwrap();
+
+    let err = TechDetector::from_compiled_pack(&path, RuleConfig::default()).unwrap_err();
+    assert!(matches!(
+        err,
+        rswappalyzer::RswappalyzerError::Core(rswappalyzer_engine::CoreError::IncompatibleEngine(_))
+    ));
+    let err = TechDetector::with_compiled_artifact(&path).unwrap_err();
+    assert!(matches!(
+        err,
+        rswappalyzer::RswappalyzerError::Core(rswappalyzer_engine::CoreError::IncompatibleEngine(_))
+    ));
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,96 @@
+//! 作用域并行分析测试（parallel 特性）：大响应体走 rayon 并行路径，结果与顺序路径一致
+//! 运行：`cargo test -p rswappalyzer --features parallel --test parallel`
+#![cfg(feature = "parallel")]
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::{PruneScope, RuleConfig, TechDetector};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::RuleProcessor;
+
+const RULES: &str = r#"{
+    "technologies": {
+        "Nginx": { "cats": [22], "headers": { "Server": "nginx(?:/([\\d.]+))?\\;version:\\1" } },
+        "WordPress": { "cats": [1], "meta": { "generator": "^WordPress ([\\d.]+)\\;version:\\1" }, "html": "wp-content" },
+        "jQuery": { "cats": [59], "scriptSrc": "jquery-([\\d.]+)\\.min\\.js\\;version:\\1" },
+        "PHP": { "cats": [27], "cookies": { "PHPSESSID": "" } },
+        "Google Analytics": { "cats": [10], "scripts": "gtag\\(['\"]config['\"]" }
+    }
+}"#;
+
+fn detector() -> TechDetector {
+    let raw = WappalyzerParser.parse_to_rule_lib(RULES).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+    TechDetector::with_rules(rules, RuleConfig::default()).unwrap()
+}
+
+type Techs = Vec<(String, Option<String>, Vec<PruneScope>)>;
+
+fn headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("server", HeaderValue::from_static("nginx/1.25.3"));
+    headers.insert("content-type", HeaderValue::from_static("text/html"));
+    headers.insert("set-cookie", HeaderValue::from_static("PHPSESSID=abc; path=/"));
+    headers
+}
+
+fn html(padding: usize) -> String {
+    format!(
+        r#"<html><head><meta name="generator" content="WordPress 6.4.2"><script src="/js/jquery-3.7.1.min.js"></script><script>gtag('config', 'G-1');</script></head><body><img src="/wp-content/a.png">{}</body></html>"#,
+        "<p>lorem ipsum</p>".repeat(padding)
+    )
+}
+
+fn techs(result: rswappalyzer::DetectResult) -> Techs {
+    let mut techs: Vec<_> = result
+        .technologies
+        .into_iter()
+        .map(|tech| (tech.name, tech.version, tech.matched_scopes))
+        .collect();
+    techs.sort_by(|a, b| a.0.cmp(&b.0));
+    techs
+}
+
+fn detect(detector: &TechDetector, padding: usize) -> Techs {
+    techs(detector.detect(&headers(), &["https://example.com/"], html(padding).as_bytes()).unwrap())
+}
+
+/// 追踪带阶段观察者，始终走顺序路径
+fn detect_serial(detector: &TechDetector, padding: usize) -> Techs {
+    techs(detector.detect_traced(&headers(), &["https://example.com/"], html(padding).as_bytes()).unwrap().result)
+}
+
+#[test]
+fn test_parallel_scopes_match_serial_result() {
+    let detector = detector();
+    // 小响应体走顺序路径，大响应体（> 64 KiB）走并行路径
+    let serial = detect(&detector, 1);
+    let parallel = detect(&detector, 8 * 1024);
+    assert_eq!(parallel, serial);
+    // 同一大响应体：并行路径与顺序路径一致（多次检测复用 rayon 全局线程池）
+    for _ in 0..3 {
+        assert_eq!(detect(&detector, 8 * 1024), detect_serial(&detector, 8 * 1024));
+    }
+
+    let names: Vec<_> = parallel.iter().map(|(name, ..)| name.as_str()).collect();
+    assert_eq!(names, ["Google Analytics", "Nginx", "PHP", "WordPress", "jQuery"]);
+    assert_eq!(parallel[0].2, [PruneScope::Script]);
+    assert_eq!(parallel[2].2, [PruneScope::Cookie]);
+    let wordpress = &parallel[3];
+    assert_eq!(wordpress.1.as_deref(), Some("6.4.2"));
+    assert!(wordpress.2.contains(&PruneScope::Html));
+    assert!(wordpress.2.contains(&PruneScope::Meta));
+    assert_eq!(parallel[1].2, [PruneScope::Header]);
+}
+
+#[test]
+fn test_parallel_scopes_concurrent_detections() {
+    let detector = detector();
+    let expected = detect_serial(&detector, 8 * 1024);
+    // 多个调用线程同时进入 rayon::scope，各自的作用域任务与结果互不干扰
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..4).map(|_| scope.spawn(|| detect(&detector, 8 * 1024))).collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), expected);
+        }
+    });
+}
@@ -0,0 +1,108 @@
+//! 有界检测工作池测试（pool 特性）
+//! `cargo test -p rswappalyzer --features pool --test pool`
+#![cfg(feature = "pool")]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::{DetectInput, DetectResult, DetectorPool, RuleConfig, SubmitError, TechDetector};
+
+fn detector() -> Arc<TechDetector> {
+    Arc::new(TechDetector::with_embedded_rules(RuleConfig::default()).unwrap())
+}
+
+fn input() -> DetectInput {
+    let mut headers = HeaderMap::new();
+    headers.insert("server", HeaderValue::from_static("nginx/1.25.3"));
+    DetectInput::new(headers, vec!["https://example.com/".to_string()], Vec::new())
+}
+
+fn tech_names(result: &DetectResult) -> Vec<String> {
+    let mut names: Vec<_> = result.technologies.iter().map(|t| t.name.clone()).collect();
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn test_pool_matches_detector() {
+    let detector = detector();
+    let expected = tech_names(&detector.detect(&input().headers, &["https://example.com/"], b"").unwrap());
+    assert!(!expected.is_empty());
+
+    let pool = DetectorPool::new(detector, 4, 8).unwrap();
+    let mut receivers = Vec::new();
+    for _ in 0..32 {
+        receivers.push(pool.submit(input()).await.unwrap());
+    }
+    for receiver in receivers {
+        assert_eq!(tech_names(&receiver.await.unwrap().unwrap()), expected);
+    }
+    pool.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_pool_try_submit_backpressure() {
+    let pool = DetectorPool::new(detector(), 1, 1).unwrap();
+    assert_eq!((pool.workers(), pool.queue_cap()), (1, 1));
+
+    // 单工作线程 + 容量1的队列：连续提交必然触发队列满
+    let mut accepted = Vec::new();
+    let mut rejected = None;
+    for _ in 0..1000 {
+        match pool.try_submit(input()) {
+            Ok(receiver) => accepted.push(receiver),
+            Err(err) => {
+                rejected = Some(err);
+                break;
+            }
+        }
+    }
+    let err = rejected.expect("expected queue full");
+    assert!(matches!(err, SubmitError::Full(_)));
+    assert_eq!(err.into_input().urls, input().urls);
+
+    // 已接收的请求全部完成
+    for receiver in accepted {
+        assert!(receiver.await.unwrap().is_ok());
+    }
+}
+
+#[tokio::test]
+async fn test_pool_shutdown_drains_queue() {
+    let pool = DetectorPool::new(detector(), 2, 16).unwrap();
+    let mut receivers = Vec::new();
+    for _ in 0..16 {
+        receivers.push(pool.submit(input()).await.unwrap());
+    }
+    pool.shutdown().await;
+    // 优雅关闭：关闭前已入队的请求全部完成
+    for receiver in receivers {
+        assert!(receiver.await.unwrap().is_ok());
+    }
+
+    let pool = DetectorPool::new(detector(), 1, 4).unwrap();
+    let receiver = pool.submit(input()).await.unwrap();
+    tokio::task::spawn_blocking(move || pool.shutdown_blocking()).await.unwrap();
+    assert!(receiver.await.unwrap().is_ok());
+}
+
+#[tokio::test]
+async fn test_pool_drop_abandons_queue() {
+    let pool = DetectorPool::new(detector(), 1, 64).unwrap();
+    let mut receivers = Vec::new();
+    while let Ok(receiver) = pool.try_submit(input()) {
+        receivers.push(receiver);
+    }
+    drop(pool);
+    // drop 不阻塞：已入队请求要么已完成，要么以通道关闭结束，均不会挂起
+    let drained = tokio::time::timeout(Duration::from_secs(10), async {
+        for receiver in receivers {
+            if let Ok(result) = receiver.await {
+                assert!(result.is_ok());
+            }
+        }
+    })
+    .await;
+    assert!(drained.is_ok());
+}
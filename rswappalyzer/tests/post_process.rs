@@ -0,0 +1,84 @@
+//! 结果后处理管道测试
+
+use http::header::HeaderMap;
+use rswappalyzer::{
+    Category, CategoryFilter, ConfidenceThreshold, DetectResult, ResultPostProcessor, RuleConfig, TechDetector,
+};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::RuleProcessor;
+
+const RULES: &str = r#"{
+    "technologies": {
+        "Nginx": { "cats": [22], "headers": { "Server": "nginx(?:/([\\d.]+))?\\;version:\\1" } },
+        "WordPress": { "cats": [1, 11], "meta": { "generator": "WordPress" }, "html": ["wp-content"] },
+        "Maybe": { "cats": [19], "html": ["maybe-widget\\;confidence:40"] }
+    },
+    "categories": {
+        "1": { "name": "CMS" }, "11": { "name": "Blogs" }, "19": { "name": "Miscellaneous" }, "22": { "name": "Web servers" }
+    }
+}"#;
+
+const HTML: &[u8] = br#"<html><head><meta name="generator" content="WordPress"></head><body>wp-content maybe-widget</body></html>"#;
+
+fn detector() -> TechDetector {
+    let raw = WappalyzerParser.parse_to_rule_lib(RULES).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+    TechDetector::with_rules(rules, RuleConfig::default()).unwrap()
+}
+
+fn names(detector: &TechDetector) -> Vec<String> {
+    let mut headers = HeaderMap::new();
+    headers.insert("server", "nginx/1.25.3".parse().unwrap());
+    let mut names: Vec<_> = detector
+        .detect(&headers, &[], HTML)
+        .unwrap()
+        .technologies
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    names.sort();
+    names
+}
+
+/// 为技术名追加后缀的标注后处理器
+struct Tagger(&'static str);
+
+impl ResultPostProcessor for Tagger {
+    fn name(&self) -> &str {
+        "tagger"
+    }
+
+    fn process(&self, result: &mut DetectResult) {
+        for tech in &mut result.technologies {
+            tech.name.push_str(self.0);
+        }
+    }
+}
+
+#[test]
+fn test_builtin_post_processors() {
+    let mut detector = detector();
+    assert_eq!(names(&detector), ["Maybe", "Nginx", "WordPress"]);
+
+    detector.add_post_processor(Box::new(ConfidenceThreshold::new(50)));
+    assert_eq!(names(&detector), ["Nginx", "WordPress"]);
+
+    let mut cms_only = detector.clone();
+    cms_only.add_post_processor(Box::new(CategoryFilter::include([Category::Cms])));
+    assert_eq!(names(&cms_only), ["WordPress"]);
+    assert_eq!(cms_only.post_processor_names(), ["confidence-threshold", "category-include"]);
+
+    // 克隆后的注册不影响原检测器
+    assert_eq!(names(&detector), ["Nginx", "WordPress"]);
+    detector.add_post_processor(Box::new(CategoryFilter::exclude(["blogs"])));
+    assert_eq!(names(&detector), ["Nginx"]);
+}
+
+#[test]
+fn test_custom_post_processors_run_in_order() {
+    let mut detector = detector();
+    detector.add_post_processor(Box::new(Tagger("-a")));
+    detector.add_post_processor(Box::new(Tagger("-b")));
+    detector.add_post_processor(Box::new(CategoryFilter::include([22])));
+    assert_eq!(names(&detector), ["Nginx-a-b"]);
+}
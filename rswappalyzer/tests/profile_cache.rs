@@ -0,0 +1,64 @@
+//! 按主机站点画像缓存测试（聚合、TTL、容量淘汰与持久化）
+
+use std::thread::sleep;
+use std::time::Duration;
+
+use rswappalyzer::{DetectResult, ProfileCache, SiteProfile};
+use serde_json::json;
+
+/// 构造检测结果（Technology 无 categories 时补空列表）
+fn result(mut techs: serde_json::Value) -> DetectResult {
+    for tech in techs.as_array_mut().unwrap() {
+        tech.as_object_mut().unwrap().entry("categories").or_insert(json!([]));
+    }
+    serde_json::from_value(json!({ "technologies": techs })).unwrap()
+}
+
+#[test]
+fn test_profile_aggregation_and_eviction() {
+    let cache = ProfileCache::new(Duration::from_secs(60), 2);
+    cache.record("Example.com.", &result(json!([
+        { "name": "Nginx", "confidence": 50, "categories": ["Web servers"] }
+    ])));
+    cache.record("example.com", &result(json!([
+        { "name": "Nginx", "version": "1.25.3", "confidence": 100 },
+        { "name": "PHP", "confidence": 100 }
+    ])));
+
+    let profile = cache.get("EXAMPLE.COM").unwrap();
+    assert_eq!(profile.observations, 2);
+    let nginx = profile.get("Nginx").unwrap();
+    assert_eq!((nginx.hits, nginx.confidence), (2, 100));
+    assert_eq!(nginx.version.as_deref(), Some("1.25.3"));
+    assert_eq!(nginx.categories, ["Web servers"]);
+    assert!(profile.contains("PHP"));
+
+    // 容量满时淘汰最久未更新的主机
+    sleep(Duration::from_millis(2));
+    cache.record("b.example", &result(json!([])));
+    sleep(Duration::from_millis(2));
+    cache.record("c.example", &result(json!([])));
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get("example.com").is_none());
+
+    // 导出/恢复
+    let exported = serde_json::to_string(&cache.export()).unwrap();
+    let restored = ProfileCache::new(Duration::from_secs(60), 10);
+    restored.restore(serde_json::from_str::<Vec<SiteProfile>>(&exported).unwrap());
+    assert_eq!(restored.export(), cache.export());
+}
+
+#[test]
+fn test_profile_ttl() {
+    let cache = ProfileCache::new(Duration::from_millis(5), 10);
+    cache.record("example.com", &result(json!([{ "name": "Nginx", "confidence": 100 }])));
+    assert!(cache.get("example.com").is_some());
+    sleep(Duration::from_millis(20));
+    assert!(cache.get("example.com").is_none());
+
+    // 过期后重新聚合
+    let profile = cache.record("example.com", &result(json!([])));
+    assert_eq!(profile.observations, 1);
+    assert!(!profile.contains("Nginx"));
+    assert_eq!(cache.purge_expired(), 0);
+}
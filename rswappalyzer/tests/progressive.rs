@@ -0,0 +1,101 @@
+//! 渐进式检测（detect_progressive / run_progressive）测试：阶段事件来自 detect 共用的作用域流水线
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::{
+    CustomConfigBuilder, DetectInput, DetectResult, DetectStage, RuleConfig, ScriptHostPolicy, TechDetector,
+    TechnologyEvent,
+};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::RuleProcessor;
+use rustc_hash::FxHashMap;
+
+const RULES: &str = r#"{
+    "technologies": {
+        "PHP": { "cats": [27], "headers": { "X-Powered-By": "^php/?([\\d.]+)?\\;version:\\1" } },
+        "WordPress": { "cats": [1], "implies": "PHP", "meta": { "generator": "^WordPress ([\\d.]+)\\;version:\\1" } },
+        "jQuery": { "cats": [59], "scriptSrc": "jquery[.-]([\\d.]+)\\.min\\.js\\;version:\\1" },
+        "Google Analytics": { "cats": [10], "scripts": "gtag\\(" },
+        "Acme CA": { "cats": [70], "certIssuer": "Acme CA" },
+        "Google Workspace": { "cats": [30], "dns": { "MX": ["aspmx\\.l\\.google\\.com"] } },
+        "Vue.js": { "cats": [12], "js": { "Vue.version": "^([\\d.]+)$\\;version:\\1" } }
+    }
+}"#;
+
+const PAGE: &str = r#"<html><head>
+<meta name="generator" content="WordPress 6.4.2">
+<script src="https://cdn.example.com/jquery-3.7.1.min.js"></script>
+<script>gtag('config', 'G-1');</script>
+</head><body></body></html>"#;
+
+fn detector(config: RuleConfig) -> TechDetector {
+    let raw = WappalyzerParser.parse_to_rule_lib(RULES).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+    TechDetector::with_rules(rules, config).unwrap()
+}
+
+fn input() -> DetectInput {
+    let mut headers = HeaderMap::new();
+    headers.insert("content-type", HeaderValue::from_static("text/html"));
+    headers.insert("x-powered-by", HeaderValue::from_static("PHP/8.2.1"));
+    let dns = FxHashMap::from_iter([("MX".to_string(), vec!["aspmx.l.google.com".to_string()])]);
+    let js = FxHashMap::from_iter([("Vue.version".to_string(), "3.4.0".to_string())]);
+    DetectInput::builder()
+        .url("https://example.com/")
+        .headers(headers)
+        .body(PAGE)
+        .cert_issuer("Acme CA")
+        .dns_records(dns)
+        .js_globals(js)
+        .build()
+}
+
+/// 结果按技术名排序后序列化（Technology 未实现 PartialEq）
+fn snapshot(result: &DetectResult) -> serde_json::Value {
+    let mut technologies = result.technologies.clone();
+    technologies.sort_by(|a, b| a.name.cmp(&b.name));
+    serde_json::to_value(&technologies).unwrap()
+}
+
+#[test]
+fn test_progressive_final_snapshot_matches_detect() {
+    let host_policy = ScriptHostPolicy {
+        cdn_hosts: vec!["cdn.example.com".to_string()],
+        cdn_version: false,
+        ..ScriptHostPolicy::default()
+    };
+    let policy = CustomConfigBuilder::new().script_host_policy(host_policy).build();
+    for config in [RuleConfig::default(), policy] {
+        let detector = detector(config);
+        let input = input();
+        let events: Vec<TechnologyEvent> = detector.run_progressive(&input).collect();
+        let Some(TechnologyEvent::Finished(result)) = events.last() else {
+            panic!("last event is not Finished: {:?}", events.last());
+        };
+        assert_eq!(snapshot(result), snapshot(&detector.run(&input).unwrap()));
+
+        let stages: Vec<DetectStage> = events
+            .iter()
+            .filter_map(|event| match event {
+                TechnologyEvent::StageCompleted { stage, .. } => Some(*stage),
+                _ => None,
+            })
+            .collect();
+        for stage in [DetectStage::Js, DetectStage::CertIssuer, DetectStage::Dns, DetectStage::Script] {
+            assert!(stages.contains(&stage), "{:?} missing from {:?}", stage, stages);
+        }
+        assert_eq!(stages.last(), Some(&DetectStage::Implies));
+        assert!(events.iter().any(|event| matches!(
+            event,
+            TechnologyEvent::Added { stage: DetectStage::CertIssuer, name, .. } if name == "Acme CA"
+        )));
+
+        let urls = ["https://example.com/"];
+        let events: Vec<TechnologyEvent> =
+            detector.detect_progressive(&input.headers, &urls, PAGE.as_bytes()).collect();
+        let Some(TechnologyEvent::Finished(result)) = events.last() else {
+            panic!("last event is not Finished");
+        };
+        let expected = detector.detect(&input.headers, &urls, PAGE.as_bytes()).unwrap();
+        assert_eq!(snapshot(result), snapshot(&expected));
+    }
+}
@@ -0,0 +1,73 @@
+//! 远程规则代理测试（remote-loader 特性）：本地 axum 服务充当 HTTP 代理，规则源域名不可解析
+//! 运行：`cargo test -p rswappalyzer --features remote-loader --test proxy`
+#![cfg(feature = "remote-loader")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::http::Uri;
+use axum::routing::get;
+use axum::Router;
+use rswappalyzer::rule::loader::RuleLoader;
+use rswappalyzer::{CustomConfigBuilder, ProxyConfig, RemoteOptions, RetryPolicy, RuleOrigin};
+
+const RULES: &str = r#"{ "technologies": { "Acme Gateway": { "cats": [22], "headers": { "Server": "acme" } } } }"#;
+const RULE_URL: &str = "http://rules.invalid/rules.json";
+
+/// 启动本地 HTTP 代理：记录经代理转发的绝对形式请求 URI，返回代理地址与请求计数
+async fn serve_proxy() -> (String, Arc<AtomicUsize>) {
+    let proxied = Arc::new(AtomicUsize::new(0));
+    let counter = proxied.clone();
+    let app = Router::new().route(
+        "/rules.json",
+        get(move |uri: Uri| {
+            let counter = counter.clone();
+            async move {
+                if uri.host() == Some("rules.invalid") {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }
+                RULES
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (format!("http://{}", addr), proxied)
+}
+
+#[tokio::test]
+async fn test_remote_rules_fetched_through_http_proxy() {
+    let (proxy_url, proxied) = serve_proxy().await;
+    let dir = std::env::temp_dir().join(format!("rswappalyzer-proxy-{}", std::process::id()));
+    let config = CustomConfigBuilder::new()
+        .cache_dir(dir.clone())
+        .origin(RuleOrigin::RemoteCustom(RULE_URL.to_string()))
+        .remote_options(RemoteOptions {
+            urls: vec![RULE_URL.to_string()],
+            ..RemoteOptions::new(Duration::from_secs(5), RetryPolicy::Never)
+        }
+        .with_proxy(ProxyConfig::direct().http_proxy(proxy_url)))
+        .build();
+
+    let rule_lib = RuleLoader::new().load(&config).await.unwrap();
+    assert!(rule_lib.core_tech_map.contains_key("Acme Gateway"));
+    assert_eq!(proxied.load(Ordering::SeqCst), 1);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_explicit_proxy_precedence() {
+    assert_eq!(ProxyConfig::default().explicit_url(), None);
+    assert!(ProxyConfig::default().use_env);
+    assert!(!ProxyConfig::direct().use_env);
+
+    let proxy = ProxyConfig::default()
+        .http_proxy("http://proxy.corp:3128")
+        .socks5("127.0.0.1:1080");
+    assert_eq!(proxy.explicit_url().as_deref(), Some("socks5h://127.0.0.1:1080"));
+
+    let proxy = ProxyConfig::default().http_proxy("http://proxy.corp:3128").socks5("  ");
+    assert_eq!(proxy.explicit_url().as_deref(), Some("http://proxy.corp:3128"));
+}
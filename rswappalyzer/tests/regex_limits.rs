@@ -0,0 +1,68 @@
+//! 正则体积/复杂度上限测试：超长正则编译期剔除、体积超限正则在模式校验时剔除，均记录结构化原因
+//! 注：运行期懒编译上限为进程级，全部断言放在同一测试中避免并行测试互相影响
+
+use http::header::HeaderMap;
+use rswappalyzer::{
+    PatternValidation, PruneScope, RegexLimitViolation, RegexLimits, RuleConfig, TechDetector,
+};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::RuleProcessor;
+
+fn build_detector(limits: RegexLimits) -> TechDetector {
+    let rules = format!(
+        r#"{{
+            "technologies": {{
+                "Longest": {{ "cats": [1], "html": "long-{}-marker[0-9]*" }},
+                "Heavy": {{ "cats": [1], "html": "heavy-\\w{{40}}-end" }},
+                "Plain": {{ "cats": [1], "html": "plain-marker-v[0-9]+" }}
+            }}
+        }}"#,
+        "x".repeat(300)
+    );
+    let raw = WappalyzerParser.parse_to_rule_lib(&rules).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+    let mut config = RuleConfig::default();
+    config.options.regex_limits = limits;
+    config.options.pattern_validation = PatternValidation::Lenient;
+    TechDetector::with_rules(rules, config).unwrap()
+}
+
+#[test]
+fn test_regex_limits_reject_offenders() {
+    let limits = RegexLimits::new().max_pattern_len(256).size_limit(4 * 1024);
+    let detector = build_detector(limits);
+    let lib = detector.compiled_library();
+
+    let rejected: Vec<_> = lib.rejected_patterns.iter().map(|entry| (entry.tech.as_str(), &entry.reason)).collect();
+    // 长度检查先于模式校验执行
+    assert_eq!(
+        rejected,
+        [
+            ("Longest", &RegexLimitViolation::PatternTooLong { len: 318, max: 256 }),
+            ("Heavy", &RegexLimitViolation::CompiledTooBig { limit: 4 * 1024 }),
+        ]
+    );
+    assert!(lib.rejected_patterns.iter().all(|entry| entry.scope == PruneScope::Html));
+    // 体积超限的正则不计入编译失败
+    assert!(lib.broken_patterns.is_empty());
+    assert!(lib.tech_patterns["Heavy"].html_patterns.iter().flatten().next().is_none());
+    assert_eq!(rswappalyzer_engine::regex_limits(), limits);
+
+    let html = format!("<p>plain-marker-v2 heavy-{}-end long-{}-marker</p>", "a".repeat(40), "x".repeat(300));
+    let result = detector.detect(&HeaderMap::new(), &[], html.as_bytes()).unwrap();
+    let names: Vec<_> = result.technologies.iter().map(|tech| tech.name.as_str()).collect();
+    assert_eq!(names, ["Plain"]);
+
+    // 默认上限下全部保留
+    let detector = build_detector(RegexLimits::default());
+    assert!(detector.compiled_library().rejected_patterns.is_empty());
+    let mut names: Vec<_> = detector
+        .detect(&HeaderMap::new(), &[], html.as_bytes())
+        .unwrap()
+        .technologies
+        .into_iter()
+        .map(|tech| tech.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, ["Heavy", "Longest", "Plain"]);
+}
@@ -0,0 +1,48 @@
+//! 全局检测器规则热更新测试
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::detector::detect;
+use rswappalyzer::{
+    init_global_detector_with_rules, reload_rules, reload_with_rules, CustomConfigBuilder, RuleConfig, RuleOrigin,
+};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::{RuleLibrary, RuleProcessor};
+
+fn rules(json: &str) -> RuleLibrary {
+    let raw = WappalyzerParser.parse_to_rule_lib(json).unwrap();
+    RuleProcessor.clean_and_split_rules(&raw).unwrap()
+}
+
+#[tokio::test]
+async fn test_reload_with_rules() {
+    let before = rules(r#"{ "technologies": { "Nginx": { "cats": [22], "headers": { "Server": "nginx" } } } }"#);
+    let after = rules(r#"{ "technologies": { "Acme Gateway": { "cats": [22], "headers": { "Server": "nginx" } } } }"#);
+    init_global_detector_with_rules(before, RuleConfig::default()).unwrap();
+
+    let mut headers = HeaderMap::new();
+    headers.insert("server", HeaderValue::from_static("nginx/1.25"));
+    let names = |result: rswappalyzer::DetectResult| -> Vec<String> {
+        result.technologies.into_iter().map(|t| t.name).collect()
+    };
+    assert_eq!(names(detect(&headers, &[], b"").await.unwrap()), ["Nginx"]);
+
+    reload_with_rules(after, RuleConfig::default()).unwrap();
+    assert_eq!(names(detect(&headers, &[], b"").await.unwrap()), ["Acme Gateway"]);
+
+    // 重复初始化不覆盖热更新后的规则
+    let again = rules(r#"{ "technologies": { "Nginx": { "cats": [22], "headers": { "Server": "nginx" } } } }"#);
+    init_global_detector_with_rules(again, RuleConfig::default()).unwrap();
+    assert_eq!(names(detect(&headers, &[], b"").await.unwrap()), ["Acme Gateway"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reload_rules_failure_keeps_current() {
+    // 编译在阻塞线程池执行；加载失败时不替换当前检测器
+    let config = CustomConfigBuilder::new()
+        .origin(RuleOrigin::LocalFile("/nonexistent/rswappalyzer/rules.json".into()))
+        .fallback_to_cache(false)
+        .fallback_to_embedded(false)
+        .persist_compiled(false)
+        .build();
+    assert!(reload_rules(config).await.is_err());
+}
@@ -0,0 +1,80 @@
+//! 依赖规则（requires / requiresCategory）测试
+
+use http::header::HeaderMap;
+use rswappalyzer::{RuleConfig, TechDetector};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::RuleProcessor;
+
+const RULES: &str = r#"{
+    "technologies": {
+        "WordPress": { "cats": [1], "html": ["wp-content"] },
+        "Drupal": { "cats": [1], "html": ["drupal-settings"] },
+        "WooCommerce": { "cats": [6], "html": ["woocommerce"], "requires": "WordPress" },
+        "WooCommerce Blocks": { "cats": [6], "html": ["wc-blocks"], "requires": ["WooCommerce"] },
+        "Cookie Banner": { "cats": [19], "html": ["cookie-banner"], "requiresCategory": 1 },
+        "Shop Theme": { "cats": [6], "html": ["shop-theme"], "requiresCategory": [6], "implies": "PHP" },
+        "PHP": { "cats": [27], "headers": { "X-Powered-By": "PHP" } },
+        "Flatsome": { "cats": [80], "html": ["flatsome-theme"], "implies": "WordPress" },
+        "Yoast SEO": { "cats": [54], "html": ["yoast-schema"], "requires": "WordPress", "implies": "PHP" }
+    }
+}"#;
+
+fn detector() -> TechDetector {
+    let raw = WappalyzerParser.parse_to_rule_lib(RULES).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+    TechDetector::with_rules(rules, RuleConfig::default()).unwrap()
+}
+
+fn names(detector: &TechDetector, html: &str) -> Vec<String> {
+    let mut names: Vec<_> = detector
+        .detect(&HeaderMap::new(), &[], html.as_bytes())
+        .unwrap()
+        .technologies
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_requires_compiled() {
+    let detector = detector();
+    let lib = detector.compiled_library();
+    assert_eq!(lib.tech_patterns["WooCommerce"].requires, ["WordPress"]);
+    assert_eq!(lib.tech_patterns["Cookie Banner"].requires_category, [1]);
+    assert!(lib.tech_patterns["WordPress"].requires.is_empty());
+}
+
+#[test]
+fn test_requires_enforced() {
+    let detector = detector();
+    assert!(names(&detector, "<html>woocommerce</html>").is_empty());
+    assert_eq!(names(&detector, "<html>wp-content woocommerce</html>"), ["WooCommerce", "WordPress"]);
+
+    // 依赖被移除时级联移除
+    assert!(names(&detector, "<html>woocommerce wc-blocks</html>").is_empty());
+
+    // 依赖分类：任一该分类技术检出即满足（不含自身）
+    assert!(names(&detector, "<html>cookie-banner</html>").is_empty());
+    assert_eq!(names(&detector, "<html>drupal-settings cookie-banner</html>"), ["Cookie Banner", "Drupal"]);
+
+    // 被移除的技术不参与推导；依赖满足时正常推导
+    assert!(names(&detector, "<html>shop-theme</html>").is_empty());
+    assert_eq!(
+        names(&detector, "<html>wp-content woocommerce shop-theme</html>"),
+        ["PHP", "Shop Theme", "WooCommerce", "WordPress"]
+    );
+}
+
+#[test]
+fn test_requires_met_by_implied_parent() {
+    let detector = detector();
+    // WordPress 仅由 Flatsome 推导得出，依赖 WordPress 的插件保留
+    assert_eq!(
+        names(&detector, "<html>flatsome-theme yoast-schema</html>"),
+        ["Flatsome", "PHP", "WordPress", "Yoast SEO"]
+    );
+    // 父技术未检出也无法推导时移除，其推导出的技术一并撤销
+    assert!(names(&detector, "<html>yoast-schema</html>").is_empty());
+}
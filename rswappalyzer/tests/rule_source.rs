@@ -0,0 +1,66 @@
+//! 多规则源归属测试（检测结果的 rule_source 与按规则源统计）
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::{RuleConfig, TechDetector};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::{RuleProcessor, UNATTRIBUTED_SOURCE};
+
+const OFFICIAL: &str = r#"{
+    "technologies": {
+        "PHP": { "cats": [27], "headers": { "X-Powered-By": "^php/?([\\d.]+)?\\;version:\\1" } },
+        "Nginx": { "cats": [22], "headers": { "Server": "nginx" } }
+    }
+}"#;
+
+const INTERNAL: &str = r#"{
+    "technologies": {
+        "Acme Gateway": { "cats": [22], "headers": { "X-Acme-Gateway": "" } }
+    }
+}"#;
+
+const LEGACY: &str = r#"{
+    "technologies": {
+        "Legacy CMS": { "cats": [1], "headers": { "X-Legacy": "" } }
+    }
+}"#;
+
+#[test]
+fn test_rule_source_attribution() {
+    let mut merged = WappalyzerParser.parse_to_rule_lib(OFFICIAL).unwrap();
+    merged.stamp_source("official");
+    let mut internal = WappalyzerParser.parse_to_rule_lib(INTERNAL).unwrap();
+    internal.stamp_source("internal");
+    merged.core_tech_map.extend(internal.core_tech_map);
+    merged
+        .core_tech_map
+        .extend(WappalyzerParser.parse_to_rule_lib(LEGACY).unwrap().core_tech_map);
+
+    let rules = RuleProcessor.clean_and_split_rules(&merged).unwrap();
+    let detector = TechDetector::with_rules(rules, RuleConfig::default()).unwrap();
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-powered-by", HeaderValue::from_static("PHP/8.2.1"));
+    headers.insert("x-acme-gateway", HeaderValue::from_static("1"));
+    headers.insert("x-legacy", HeaderValue::from_static("1"));
+    let result = detector.detect(&headers, &[], b"").unwrap();
+    let source_of = |name: &str| {
+        result
+            .technologies
+            .iter()
+            .find(|t| t.name == name)
+            .and_then(|t| t.rule_source.clone())
+    };
+    assert_eq!(source_of("PHP").as_deref(), Some("official"));
+    assert_eq!(source_of("Acme Gateway").as_deref(), Some("internal"));
+    assert_eq!(source_of("Legacy CMS"), None);
+
+    // 计数器在克隆之间共享
+    detector.clone().detect(&headers, &[], b"").unwrap();
+    let stats = detector.stats();
+    assert_eq!(stats.sources["official"].tech_count, 2);
+    assert_eq!(stats.sources["official"].detections, 2);
+    assert_eq!(stats.sources["internal"].tech_count, 1);
+    assert_eq!(stats.sources["internal"].detections, 2);
+    assert_eq!(stats.sources[UNATTRIBUTED_SOURCE].detections, 2);
+    assert_eq!(stats.total_detections(), 6);
+}
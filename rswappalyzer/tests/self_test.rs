@@ -0,0 +1,21 @@
+//! 规则库自检测试
+#![cfg(feature = "embedded-rules")]
+
+use rswappalyzer::{CustomConfigBuilder, PruneScope, SelfTestStatus, TechDetector};
+
+#[test]
+fn test_embedded_rules_self_test() {
+    let detector = TechDetector::with_embedded_rules(Default::default()).unwrap();
+    let report = detector.self_test();
+    assert!(report.is_healthy(), "{:?}", report.failures().collect::<Vec<_>>());
+    assert_eq!(report.passed(), report.cases.len());
+
+    // 未编译的作用域跳过，不视为失败
+    let config = CustomConfigBuilder::new().compile_scopes([PruneScope::Header]).build();
+    let report = TechDetector::with_embedded_rules(config).unwrap().self_test();
+    assert!(report.is_healthy());
+    assert!(report
+        .cases
+        .iter()
+        .all(|case| (case.scope == PruneScope::Header) == (case.status == SelfTestStatus::Passed)));
+}
@@ -0,0 +1,56 @@
+//! tower::Service 适配测试（tower 特性）
+//! `cargo test -p rswappalyzer --features tower --test service`
+#![cfg(feature = "tower")]
+
+use std::sync::Arc;
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::{DetectInput, DetectResult, DetectService, RuleConfig, TechDetector};
+use tower::load_shed::error::Overloaded;
+use tower::{Service, ServiceExt};
+
+fn detector() -> Arc<TechDetector> {
+    Arc::new(TechDetector::with_embedded_rules(RuleConfig::default()).unwrap())
+}
+
+fn input() -> DetectInput {
+    let mut headers = HeaderMap::new();
+    headers.insert("server", HeaderValue::from_static("nginx/1.25.3"));
+    DetectInput::new(headers, vec!["https://example.com/".to_string()], Vec::new())
+}
+
+fn tech_names(result: &DetectResult) -> Vec<String> {
+    let mut names: Vec<_> = result.technologies.iter().map(|t| t.name.clone()).collect();
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn test_service_matches_detector() {
+    let detector = detector();
+    let expected = tech_names(&detector.detect(&input().headers, &["https://example.com/"], b"").unwrap());
+    assert!(!expected.is_empty());
+
+    let result = DetectService::new(detector.clone()).oneshot(input()).await.unwrap();
+    assert_eq!(tech_names(&result), expected);
+
+    let result = DetectService::builder(detector).concurrency_limit(4).build().oneshot(input()).await.unwrap();
+    assert_eq!(tech_names(&result), expected);
+}
+
+#[tokio::test]
+async fn test_service_load_shed() {
+    let mut service = DetectService::builder(detector())
+        .concurrency_limit(1)
+        .load_shed(true)
+        .build();
+
+    // 未完成的请求持有唯一的并发许可
+    let in_flight = service.ready().await.unwrap().call(input());
+    let err = service.ready().await.unwrap().call(input()).await.expect_err("expected overload");
+    assert!(err.is::<Overloaded>());
+
+    // 请求完成后许可释放，新请求恢复正常
+    assert!(!in_flight.await.unwrap().technologies.is_empty());
+    assert!(service.ready().await.unwrap().call(input()).await.is_ok());
+}
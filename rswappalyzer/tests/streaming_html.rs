@@ -0,0 +1,93 @@
+//! 流式 HTML 提取测试：`HtmlParser::Streaming` 单次前向扫描与 lol_html 提取结果一致
+
+use http::header::HeaderMap;
+use rswappalyzer::{
+    CustomConfigBuilder, ExtractLimits, HtmlExtractor, HtmlParser, RuleConfig, StreamingExtractor, TechDetector,
+};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::RuleProcessor;
+
+const RULES: &str = r#"{
+    "technologies": {
+        "WordPress": { "cats": [1], "meta": { "generator": "^WordPress ([\\d.]+)\\;version:\\1" } },
+        "jQuery": { "cats": [59], "scriptSrc": "jquery[.-]([\\d.]+)\\.min\\.js\\;version:\\1" },
+        "Google Analytics": { "cats": [10], "scripts": "gtag\\(['\"]config['\"]" },
+        "Foo CMS": { "cats": [1], "html": "<!-- powered by Foo CMS" }
+    }
+}"#;
+
+const PAGE: &str = r#"<!DOCTYPE html>
+<HTML><HEAD>
+<TITLE>My Blog &amp; Shop</TITLE>
+<META NAME=generator CONTENT="WordPress 6.4.2">
+<meta property="og:description" content="a > b">
+<script src='/js/jquery-3.7.1.min.js' defer></script>
+<script>gtag('config', 'G-ABC'); if (a < b) document.write("<script src=/fake.js></script>");</script>
+<style>body > p { color: red }</style>
+</HEAD><BODY>
+<!-- powered by Foo CMS -->
+<textarea><meta name="generator" content="Joomla"></textarea>
+<p>content</p>
+<!-- unclosed comment
+</BODY></HTML>"#;
+
+fn detector(parser: HtmlParser) -> TechDetector {
+    let raw = WappalyzerParser.parse_to_rule_lib(RULES).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+    let config: RuleConfig = CustomConfigBuilder::new().html_parser(parser).build();
+    TechDetector::with_rules(rules, config).unwrap()
+}
+
+fn detect(parser: HtmlParser, body: &str) -> Vec<(String, Option<String>)> {
+    let result = detector(parser).detect(&HeaderMap::new(), &[], body.as_bytes()).unwrap();
+    let mut techs: Vec<_> = result
+        .technologies
+        .into_iter()
+        .map(|tech| (tech.name, tech.version))
+        .collect();
+    techs.sort();
+    techs
+}
+
+#[test]
+fn test_streaming_extract() {
+    let result = StreamingExtractor::extract_with_limits(PAGE, &ExtractLimits::default());
+    assert_eq!(result.title, "My Blog & Shop");
+    assert_eq!(result.script_srcs, ["/js/jquery-3.7.1.min.js"]);
+    assert_eq!(
+        result.meta_tags,
+        [
+            ("generator".to_string(), "WordPress 6.4.2".to_string()),
+            ("og:description".to_string(), "a > b".to_string()),
+        ]
+    );
+    assert!(result.inline_scripts.starts_with("gtag('config'"));
+    assert_eq!(result, HtmlExtractor::extract_with_limits(PAGE, &ExtractLimits::default()));
+}
+
+#[test]
+fn test_streaming_limits() {
+    let limits = ExtractLimits {
+        max_meta_tags: 1,
+        max_inline_script_bytes: 0,
+        max_comment_bytes: 8,
+        ..ExtractLimits::default()
+    };
+    let result = StreamingExtractor::extract_with_limits(PAGE, &limits);
+    assert_eq!(result.meta_tags.len(), 1);
+    assert!(result.inline_scripts.is_empty());
+    assert!(result.stats.meta_truncated && result.stats.comments_truncated);
+    assert_eq!(result, HtmlExtractor::extract_with_limits(PAGE, &limits));
+}
+
+#[test]
+fn test_streaming_detector() {
+    let expected = [
+        ("Foo CMS".to_string(), None),
+        ("Google Analytics".to_string(), None),
+        ("WordPress".to_string(), Some("6.4.2".to_string())),
+        ("jQuery".to_string(), Some("3.7.1".to_string())),
+    ];
+    assert_eq!(detect(HtmlParser::Streaming, PAGE), expected);
+    assert_eq!(detect(HtmlParser::Rewriter, PAGE), expected);
+}
@@ -0,0 +1,54 @@
+//! 阶段追踪埋点测试（tracing 特性）
+//! `cargo test -p rswappalyzer --features tracing --test tracing`
+#![cfg(feature = "tracing")]
+
+use std::sync::{Arc, Mutex};
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::{RuleConfig, TechDetector};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::RuleProcessor;
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::Layer;
+
+const RULES: &str = r#"{
+    "technologies": {
+        "PHP": { "cats": [27], "headers": { "X-Powered-By": "^php" } },
+        "WordPress": { "cats": [1], "implies": "PHP", "meta": { "generator": "^WordPress" } }
+    }
+}"#;
+
+/// 记录创建的 span 名称
+#[derive(Clone, Default)]
+struct SpanNames(Arc<Mutex<Vec<&'static str>>>);
+
+impl<S: Subscriber> Layer<S> for SpanNames {
+    fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        self.0.lock().unwrap().push(attrs.metadata().name());
+    }
+}
+
+#[test]
+fn test_stage_spans() {
+    let names = SpanNames::default();
+    let subscriber = tracing_subscriber::registry().with(names.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        let raw = WappalyzerParser.parse_to_rule_lib(RULES).unwrap();
+        let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+        let detector = TechDetector::with_rules(rules, RuleConfig::default()).unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", HeaderValue::from_static("text/html"));
+        headers.insert("x-powered-by", HeaderValue::from_static("PHP/8.2"));
+        let body = br#"<html><head><meta name="generator" content="WordPress 6.4"></head></html>"#;
+        let result = detector.detect(&headers, &["https://example.com/"], body).unwrap();
+        assert_eq!(result.technologies.len(), 2);
+    });
+
+    let names = names.0.lock().unwrap();
+    for stage in ["rule.compile", "detect", "detect.body", "detect.url", "detect.header", "detect.meta", "detect.post_process"] {
+        assert!(names.contains(&stage), "missing span {}: {:?}", stage, names);
+    }
+}
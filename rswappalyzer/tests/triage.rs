@@ -0,0 +1,53 @@
+//! 分诊模式测试（仅证据索引与准入门槛，结果为完整检测的超集）
+
+use http::header::{HeaderMap, HeaderValue};
+use rswappalyzer::{CustomConfigBuilder, DetectInput, PruneScope, TechDetector};
+use rswappalyzer_engine::source::WappalyzerParser;
+use rswappalyzer_engine::RuleProcessor;
+
+const RULES: &str = r#"{
+    "technologies": {
+        "PHP": { "cats": [27], "headers": { "X-Powered-By": "^php/?([\\d.]+)?\\;version:\\1" } },
+        "WordPress": { "cats": [1], "html": "<link[^>]+/wp-content/" },
+        "Acme Gateway": { "cats": [22], "headers": { "X-Acme-Gateway": "" } }
+    }
+}"#;
+
+fn detector(escalate: &[&str]) -> TechDetector {
+    let raw = WappalyzerParser.parse_to_rule_lib(RULES).unwrap();
+    let rules = RuleProcessor.clean_and_split_rules(&raw).unwrap();
+    let config = CustomConfigBuilder::new().triage_escalate(escalate.iter().copied()).build();
+    TechDetector::with_rules(rules, config).unwrap()
+}
+
+fn input() -> DetectInput {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-powered-by", HeaderValue::from_static("PHP/8.2.1"));
+    headers.insert("x-acme-gateway", HeaderValue::from_static("1"));
+    let body = r#"<html><head><link rel="stylesheet" href="/wp-content/themes/a.css"></head></html>"#;
+    DetectInput::new(headers, vec!["https://example.com/".to_string()], body)
+}
+
+#[test]
+fn test_triage_covers_full_detection() {
+    let detector = detector(&[]);
+    let input = input();
+    let triage = detector.detect_triage(&input);
+    assert!(triage.escalate);
+    assert_eq!(triage.possible["PHP"], [PruneScope::Header]);
+    assert_eq!(triage.possible["Acme Gateway"], [PruneScope::Header]);
+    assert_eq!(triage.possible["WordPress"], [PruneScope::Html]);
+
+    let urls: Vec<&str> = input.urls.iter().map(String::as_str).collect();
+    let full = detector.detect(&input.headers, &urls, &input.body).unwrap();
+    assert!(full.technologies.iter().all(|tech| triage.contains(&tech.name)));
+}
+
+#[test]
+fn test_triage_escalation_list() {
+    let mut input = input();
+    input.body.clear();
+    assert!(!detector(&["WordPress"]).detect_triage(&input).escalate);
+    assert!(detector(&["PHP"]).detect_triage(&input).escalate);
+    assert!(detector(&[]).detect_triage(&DetectInput::default()).is_empty());
+}
@@ -0,0 +1,68 @@
+//! 检测告警（软错误）测试
+#![cfg(feature = "embedded-rules")]
+
+use http::header::HeaderMap;
+use rswappalyzer::{CustomConfigBuilder, DetectWarning, ExtractLimits, HtmlRejectReason, TechDetector};
+use rustc_hash::FxHashMap;
+
+fn warnings(detector: &TechDetector, body: &[u8]) -> Vec<DetectWarning> {
+    detector.detect(&HeaderMap::new(), &[], body).unwrap().warnings
+}
+
+#[test]
+fn test_detect_warnings() {
+    let detector = TechDetector::with_embedded_rules(Default::default()).unwrap();
+    assert!(warnings(&detector, b"").is_empty());
+    assert!(warnings(&detector, b"<html><body>hello world</body></html>").is_empty());
+    assert_eq!(
+        warnings(&detector, b"  <p>  "),
+        [DetectWarning::HtmlRejected(HtmlRejectReason::TooShort)]
+    );
+    assert_eq!(
+        warnings(&detector, b"<html><body>caf\xe9 au lait</body></html>"),
+        [DetectWarning::BodyDecodeFallback("invalid utf-8".to_string())]
+    );
+
+    let limits = ExtractLimits {
+        max_attribute_len: 16,
+        ..ExtractLimits::default()
+    };
+    let limited = TechDetector::with_embedded_rules(CustomConfigBuilder::new().extract_limits(limits).build()).unwrap();
+    let body = format!("<html><body><div data-x=\"{}\"></div></body></html>", "x".repeat(64));
+    assert_eq!(
+        warnings(&limited, body.as_bytes()),
+        [DetectWarning::Truncated("attributes".to_string())]
+    );
+
+    let mut headers = FxHashMap::default();
+    headers.insert("bad header".to_string(), vec!["1".to_string()]);
+    let result = detector.detect_with_hashmap(&headers, &[], b"").unwrap();
+    assert_eq!(result.warnings, [DetectWarning::InvalidHeaderName("bad header".to_string())]);
+
+    // 序列化格式稳定
+    let json = serde_json::to_string(&DetectWarning::HtmlRejected(HtmlRejectReason::TooShort)).unwrap();
+    assert_eq!(json, r#"{"kind":"html_rejected","detail":"too_short"}"#);
+}
+
+#[test]
+fn test_body_checked_before_decoding() {
+    let detector = TechDetector::with_embedded_rules(Default::default()).unwrap();
+    // 过短的响应体在解码前被拒绝，不产生容错解码告警
+    assert_eq!(
+        warnings(&detector, b"  <p>\xe9  "),
+        [DetectWarning::HtmlRejected(HtmlRejectReason::TooShort)]
+    );
+
+    // 超长的非法 UTF-8 响应体先按字节截断再容错解码，头部内容照常检测
+    let mut body = br#"<html><head><meta name="generator" content="WordPress 6.4.2"></head><body>"#.to_vec();
+    body.resize(3 * 1024 * 1024, 0xe9);
+    let result = detector.detect(&HeaderMap::new(), &[], &body).unwrap();
+    assert!(result.technologies.iter().any(|tech| tech.name == "WordPress"));
+    assert_eq!(
+        result.warnings,
+        [
+            DetectWarning::Truncated("html_len".to_string()),
+            DetectWarning::BodyDecodeFallback("invalid utf-8".to_string()),
+        ]
+    );
+}